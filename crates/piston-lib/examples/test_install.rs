@@ -5,7 +5,9 @@ use anyhow::Result;
 use tokio::sync::watch;
 
 use piston_lib::game::installer::install_instance;
-use piston_lib::game::installer::types::{InstallSpec, NotificationActionSpec, ProgressReporter};
+use piston_lib::game::installer::types::{
+    DownloadConcurrency, InstallSpec, NotificationActionSpec, ProgressReporter,
+};
 
 struct ConsoleReporter {
     cancel_rx: watch::Receiver<bool>,
@@ -86,7 +88,7 @@ async fn main() -> Result<()> {
     let mut spec = InstallSpec::new("1.21.10".to_string(), data_dir.clone(), game_dir.clone());
     spec.modloader = Some(piston_lib::game::metadata::ModloaderType::NeoForge);
     spec.modloader_version = Some("21.10.54-beta".to_string());
-    spec.concurrency = 8;
+    spec.concurrency = DownloadConcurrency::uniform(8);
     spec.force_overwrite_configs = false;
     spec.repair_scope = Default::default();
     spec.remediation_policy = Default::default();
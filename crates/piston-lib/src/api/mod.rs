@@ -2,3 +2,4 @@ pub mod embedded_skins;
 pub mod minecraft_skins;
 pub mod mojang;
 pub mod player;
+pub mod xbox;
@@ -2,7 +2,7 @@ use anyhow::{Context, Result};
 use base64::{engine::general_purpose, Engine as _};
 use image::imageops;
 use serde::Deserialize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 
 // --- Helper Structs for Public Profile resolution ---
@@ -23,12 +23,46 @@ struct TexturesProperty {
 struct Textures {
     #[serde(rename = "SKIN")]
     skin: Option<SkinTexture>,
+    #[serde(rename = "CAPE")]
+    cape: Option<SkinTexture>,
 }
 #[derive(Deserialize)]
 struct SkinTexture {
     url: String,
 }
 
+/// Cape texture layout width in pixels; HD capes are integer multiples.
+const CAPE_TEXTURE_WIDTH: u32 = 64;
+/// Front cape panel in the 64x32 layout: x, y, width, height.
+const CAPE_FRONT_PANEL: (u32, u32, u32, u32) = (1, 1, 10, 16);
+
+/// Fetch the decoded `textures` property of a player's public profile.
+/// Any failure is treated as "no textures".
+async fn fetch_profile_textures(normalized_uuid: &str) -> Result<Option<Textures>> {
+    let url = format!(
+        "https://sessionserver.mojang.com/session/minecraft/profile/{}",
+        normalized_uuid
+    );
+    let resp = crate::client::shared_client().get(&url).send().await?;
+    if !resp.status().is_success() {
+        return Ok(None);
+    }
+    Ok(resp
+        .json::<SessionProfile>()
+        .await
+        .ok()
+        .and_then(|profile| {
+            profile
+                .properties
+                .into_iter()
+                .find(|p| p.name == "textures")
+                .map(|p| p.value)
+        })
+        .and_then(|value| general_purpose::STANDARD.decode(&value).ok())
+        .and_then(|decoded| serde_json::from_slice::<TexturesProperty>(&decoded).ok())
+        .map(|textures| textures.textures))
+}
+
 /// Download player head/avatar by fetching the skin and extracting the face
 ///
 /// # Arguments
@@ -67,31 +101,9 @@ pub async fn download_player_head(
     // 1. Resolve URL (Use known URL from DB or query Session Server)
     let skin_url = match known_skin_url {
         Some(url) => Some(url),
-        None => {
-            let url = format!(
-                "https://sessionserver.mojang.com/session/minecraft/profile/{}",
-                normalized_uuid
-            );
-            let resp = crate::client::shared_client().get(&url).send().await?;
-            if resp.status().is_success() {
-                // Parse as much as we can but treat any error as "no skin"
-                resp.json::<SessionProfile>()
-                    .await
-                    .ok()
-                    .and_then(|profile| {
-                        profile
-                            .properties
-                            .into_iter()
-                            .find(|p| p.name == "textures")
-                            .map(|p| p.value)
-                    })
-                    .and_then(|value| general_purpose::STANDARD.decode(&value).ok())
-                    .and_then(|decoded| serde_json::from_slice::<TexturesProperty>(&decoded).ok())
-                    .and_then(|textures| textures.textures.skin.map(|s| s.url))
-            } else {
-                None
-            }
-        }
+        None => fetch_profile_textures(&normalized_uuid)
+            .await?
+            .and_then(|textures| textures.skin.map(|s| s.url)),
     };
 
     let target_url = match skin_url {
@@ -158,6 +170,105 @@ pub async fn download_player_head(
     Ok(storage_path)
 }
 
+/// Download the player's active cape texture
+///
+/// # Arguments
+/// * `uuid` - Player UUID (with or without dashes)
+/// * `output_path` - Full path where the cape texture should be saved
+/// * `force` - Download even if file exists
+///
+/// # Returns
+/// Path to the cape texture, or `None` if the player has no cape. A stale
+/// file at `output_path` is removed when a forced refresh finds no cape.
+pub async fn download_player_cape(
+    uuid: &str,
+    output_path: PathBuf,
+    force: bool,
+) -> Result<Option<PathBuf>> {
+    let normalized_uuid = uuid.replace("-", "");
+
+    if output_path.exists() && !force {
+        return Ok(Some(output_path));
+    }
+
+    let textures = fetch_profile_textures(&normalized_uuid)
+        .await?
+        .with_context(|| format!("Failed to read profile textures for {}", normalized_uuid))?;
+    let Some(cape_url) = textures.cape.map(|c| c.url) else {
+        if output_path.exists() {
+            fs::remove_file(&output_path)
+                .await
+                .context("Failed to remove stale cape texture")?;
+        }
+        return Ok(None);
+    };
+
+    let response = crate::client::shared_client()
+        .get(&cape_url)
+        .send()
+        .await
+        .context("Failed to download cape texture")?;
+    if !response.status().is_success() {
+        anyhow::bail!(
+            "Failed to download cape texture: HTTP {}",
+            response.status()
+        );
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .context("Failed to read cape texture bytes")?;
+    image::load_from_memory(&bytes).context("Cape texture is not a valid image")?;
+
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .context("Failed to create cache directory")?;
+    }
+    fs::write(&output_path, bytes)
+        .await
+        .context("Failed to write cape texture to file")?;
+
+    Ok(Some(output_path))
+}
+
+/// Render the front panel of a cape texture as a standalone image
+///
+/// The panel is 10x16 in the standard 64x32 layout (scaled up for HD capes).
+/// The thumbnail is `size` pixels tall and keeps the panel's aspect ratio.
+pub fn render_cape_thumbnail(cape_path: &Path, output_path: &Path, size: u32) -> Result<()> {
+    let img = image::open(cape_path).context("Failed to load cape texture")?;
+
+    let scale = (img.width() / CAPE_TEXTURE_WIDTH).max(1);
+    let (x, y, width, height) = CAPE_FRONT_PANEL;
+    let (x, y, width, height) = (x * scale, y * scale, width * scale, height * scale);
+    if x + width > img.width() || y + height > img.height() {
+        anyhow::bail!(
+            "Cape texture is too small ({}x{})",
+            img.width(),
+            img.height()
+        );
+    }
+    let panel = img.crop_imm(x, y, width, height);
+
+    let thumb_height = size.max(1);
+    let thumb_width = (thumb_height * CAPE_FRONT_PANEL.2 / CAPE_FRONT_PANEL.3).max(1);
+    let thumbnail = imageops::resize(
+        &panel,
+        thumb_width,
+        thumb_height,
+        imageops::FilterType::Nearest,
+    );
+
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent).context("Failed to create cache directory")?;
+    }
+    thumbnail
+        .save(output_path)
+        .context("Failed to write cape thumbnail to disk")?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,4 +288,36 @@ mod tests {
         // Clean up
         let _ = std::fs::remove_file(storage_path);
     }
+
+    #[test]
+    fn cape_thumbnail_crops_front_panel() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cape_path = temp_dir.path().join("cape.png");
+        let thumb_path = temp_dir.path().join("cape_front.png");
+
+        // Paint the front panel red and everything else blue.
+        let cape = image::RgbaImage::from_fn(64, 32, |x, y| {
+            if (1..11).contains(&x) && (1..17).contains(&y) {
+                image::Rgba([255, 0, 0, 255])
+            } else {
+                image::Rgba([0, 0, 255, 255])
+            }
+        });
+        cape.save(&cape_path).unwrap();
+
+        render_cape_thumbnail(&cape_path, &thumb_path, 64).unwrap();
+
+        let thumb = image::open(&thumb_path).unwrap().to_rgba8();
+        assert_eq!(thumb.dimensions(), (40, 64));
+        assert!(thumb.pixels().all(|p| *p == image::Rgba([255, 0, 0, 255])));
+    }
+
+    #[test]
+    fn cape_thumbnail_rejects_tiny_texture() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let cape_path = temp_dir.path().join("cape.png");
+        image::RgbaImage::new(8, 8).save(&cape_path).unwrap();
+
+        assert!(render_cape_thumbnail(&cape_path, &temp_dir.path().join("out.png"), 32).is_err());
+    }
 }
@@ -0,0 +1,165 @@
+//! Xbox Live profile lookups.
+//!
+//! The XSTS token issued for Minecraft services cannot read Xbox profiles, so
+//! a second XSTS token for `http://xboxlive.com` is requested from the
+//! Microsoft access token obtained at sign-in.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::json;
+
+const XBL_USER_AUTH_URL: &str = "https://user.auth.xboxlive.com/user/authenticate";
+const XSTS_AUTHORIZE_URL: &str = "https://xsts.auth.xboxlive.com/xsts/authorize";
+const XBL_PROFILE_SETTINGS_URL: &str =
+    "https://profile.xboxlive.com/users/me/profile/settings?settings=GameDisplayPicRaw";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct XboxTokenResponse {
+    token: String,
+    display_claims: XboxDisplayClaims,
+}
+
+#[derive(Debug, Deserialize)]
+struct XboxDisplayClaims {
+    xui: Vec<XboxUserInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct XboxUserInfo {
+    uhs: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileSettingsResponse {
+    #[serde(default)]
+    profile_users: Vec<ProfileUser>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfileUser {
+    #[serde(default)]
+    settings: Vec<ProfileSetting>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfileSetting {
+    id: String,
+    value: String,
+}
+
+async fn post_xbox_token(url: &str, body: serde_json::Value) -> Result<XboxTokenResponse> {
+    let response = crate::client::shared_client()
+        .post(url)
+        .header("Accept", "application/json")
+        .json(&body)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach {}", url))?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("{} returned {}", url, response.status());
+    }
+    response
+        .json::<XboxTokenResponse>()
+        .await
+        .context("Failed to parse Xbox Live token response")
+}
+
+/// First `GameDisplayPicRaw` value in a profile settings response.
+fn display_pic_from_settings(settings: ProfileSettingsResponse) -> Option<String> {
+    settings
+        .profile_users
+        .into_iter()
+        .flat_map(|user| user.settings)
+        .find(|setting| setting.id == "GameDisplayPicRaw")
+        .map(|setting| setting.value)
+        .filter(|value| !value.is_empty())
+}
+
+/// Xbox Live gamerpic URL (`GameDisplayPicRaw`) of the account that owns
+/// `microsoft_access_token`. `None` when the profile has no picture set.
+pub async fn get_xbox_avatar_url(microsoft_access_token: &str) -> Result<Option<String>> {
+    let user_token = post_xbox_token(
+        XBL_USER_AUTH_URL,
+        json!({
+            "Properties": {
+                "AuthMethod": "RPS",
+                "SiteName": "user.auth.xboxlive.com",
+                "RpsTicket": format!("d={}", microsoft_access_token),
+            },
+            "RelyingParty": "http://auth.xboxlive.com",
+            "TokenType": "JWT",
+        }),
+    )
+    .await?;
+
+    let xsts = post_xbox_token(
+        XSTS_AUTHORIZE_URL,
+        json!({
+            "Properties": {
+                "SandboxId": "RETAIL",
+                "UserTokens": [user_token.token],
+            },
+            "RelyingParty": "http://xboxlive.com",
+            "TokenType": "JWT",
+        }),
+    )
+    .await?;
+    let user_hash = xsts
+        .display_claims
+        .xui
+        .first()
+        .map(|info| info.uhs.clone())
+        .context("XSTS response has no user hash")?;
+
+    let response = crate::client::shared_client()
+        .get(XBL_PROFILE_SETTINGS_URL)
+        .header(
+            "Authorization",
+            format!("XBL3.0 x={};{}", user_hash, xsts.token),
+        )
+        .header("x-xbl-contract-version", "3")
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .context("Failed to fetch Xbox Live profile")?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("Xbox Live profile returned {}", response.status());
+    }
+    let settings = response
+        .json::<ProfileSettingsResponse>()
+        .await
+        .context("Failed to parse Xbox Live profile")?;
+    Ok(display_pic_from_settings(settings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_display_pic_from_profile_settings() {
+        let settings: ProfileSettingsResponse = serde_json::from_str(
+            r#"{"profileUsers":[{"id":"2535","hostId":"2535","settings":[
+                {"id":"GameDisplayPicRaw","value":"https://images-eds-ssl.xboxlive.com/image?url=abc"}
+            ],"isSponsoredUser":false}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            display_pic_from_settings(settings).as_deref(),
+            Some("https://images-eds-ssl.xboxlive.com/image?url=abc")
+        );
+    }
+
+    #[test]
+    fn empty_display_pic_is_none() {
+        let settings: ProfileSettingsResponse = serde_json::from_str(
+            r#"{"profileUsers":[{"settings":[{"id":"GameDisplayPicRaw","value":""}]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(display_pic_from_settings(settings), None);
+    }
+}
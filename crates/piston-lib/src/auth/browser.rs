@@ -0,0 +1,359 @@
+//! Authorization-code login with PKCE through the system browser.
+//!
+//! Fallback for networks and tenants that block the device-code endpoint.
+//! A listener on an ephemeral loopback port receives the redirect, and the
+//! code is exchanged with the PKCE verifier (public client, no secret).
+//!
+//! Redirect URI: Microsoft identity platform matches loopback redirect URIs
+//! without their port, so one `http://localhost` registration on the client
+//! covers every ephemeral port (the registration has to be of the "mobile and
+//! desktop" kind; web-platform registrations require an exact match). Whether
+//! `CLIENT_ID` carries that registration is decided by Microsoft, not by us:
+//! when it does not, the authorize page stops at AADSTS50011 without
+//! redirecting and the login ends with a timeout, so the device-code flow
+//! stays the default.
+
+use anyhow::{bail, Context, Result};
+use oauth2::basic::{BasicClient, BasicTokenResponse};
+use oauth2::{
+    AuthorizationCode, CsrfToken, PkceCodeChallenge, PkceCodeVerifier, RedirectUrl, Scope,
+};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use super::{get_auth_client, SCOPES};
+
+/// How long to wait for the browser to come back before giving up.
+pub const BROWSER_LOGIN_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// Largest redirect request read from the browser.
+const MAX_REQUEST_BYTES: usize = 16 * 1024;
+
+const SUCCESS_PAGE: &str = "<!doctype html><html><body><h3>Signed in</h3>\
+<p>You can close this window and return to Vesta.</p></body></html>";
+const FAILURE_PAGE: &str = "<!doctype html><html><body><h3>Sign-in failed</h3>\
+<p>Return to Vesta for details.</p></body></html>";
+
+/// Sign in through the system browser. `open_browser` receives the authorize
+/// URL once the loopback listener is up; it should open it and may surface it
+/// to the user in case no browser starts. The listener closes when this
+/// returns or the future is dropped (cancelled).
+pub async fn start_browser_login(
+    open_browser: impl FnOnce(&str),
+    timeout: Duration,
+) -> Result<BasicTokenResponse> {
+    browser_login_with_client(get_auth_client()?, open_browser, timeout).await
+}
+
+async fn browser_login_with_client(
+    client: BasicClient,
+    open_browser: impl FnOnce(&str),
+    timeout: Duration,
+) -> Result<BasicTokenResponse> {
+    let (listeners, port) = bind_loopback().await?;
+    let client = client.set_redirect_uri(RedirectUrl::new(format!("http://localhost:{}", port))?);
+
+    let (challenge, verifier) = pkce_pair();
+    let scopes = SCOPES.iter().map(|s| Scope::new(s.to_string()));
+    let (authorize_url, csrf) = client
+        .authorize_url(CsrfToken::new_random)
+        .add_scopes(scopes)
+        .add_extra_param("prompt", "select_account")
+        .set_pkce_challenge(challenge)
+        .url();
+
+    log::info!("[auth] Waiting for browser login on loopback port {}", port);
+    open_browser(authorize_url.as_str());
+
+    let code = tokio::time::timeout(timeout, receive_code(&listeners, csrf.secret()))
+        .await
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "No response from the browser within {} minutes",
+                timeout.as_secs() / 60
+            )
+        })??;
+    drop(listeners);
+
+    exchange_code(&client, code, verifier).await
+}
+
+/// A fresh PKCE verifier and its S256 challenge.
+fn pkce_pair() -> (PkceCodeChallenge, PkceCodeVerifier) {
+    PkceCodeChallenge::new_random_sha256()
+}
+
+/// Listen on an ephemeral IPv4 loopback port, and on the same port over IPv6
+/// when available since browsers may resolve `localhost` to `::1` first.
+async fn bind_loopback() -> Result<(Vec<TcpListener>, u16)> {
+    let v4 = TcpListener::bind(("127.0.0.1", 0))
+        .await
+        .context("Failed to open a loopback port for the login redirect")?;
+    let port = v4.local_addr()?.port();
+    let mut listeners = vec![v4];
+    match TcpListener::bind(("::1", port)).await {
+        Ok(v6) => listeners.push(v6),
+        Err(e) => log::debug!("[auth] IPv6 loopback unavailable for login redirect: {}", e),
+    }
+    Ok((listeners, port))
+}
+
+/// Answer requests on the loopback listeners until one carries the
+/// authorization code (or an error) for our `state`. Stray requests, like
+/// favicon fetches, are answered and ignored.
+async fn receive_code(listeners: &[TcpListener], expected_state: &str) -> Result<String> {
+    loop {
+        let accepts = listeners.iter().map(|listener| Box::pin(listener.accept()));
+        let (accepted, _, _) = futures::future::select_all(accepts).await;
+        let (mut stream, _) = accepted.context("Failed to accept login redirect")?;
+
+        let target = match read_request_target(&mut stream).await {
+            Ok(target) => target,
+            Err(e) => {
+                log::debug!("[auth] Ignoring unreadable loopback request: {}", e);
+                continue;
+            }
+        };
+
+        match parse_redirect(&target, expected_state) {
+            Redirect::Code(code) => {
+                respond(&mut stream, "200 OK", SUCCESS_PAGE).await;
+                return Ok(code);
+            }
+            Redirect::Error(message) => {
+                respond(&mut stream, "200 OK", FAILURE_PAGE).await;
+                bail!("Microsoft login failed: {}", message);
+            }
+            Redirect::StateMismatch => {
+                log::warn!("[auth] Ignoring login redirect with an unexpected state");
+                respond(&mut stream, "400 Bad Request", FAILURE_PAGE).await;
+            }
+            Redirect::Other => respond(&mut stream, "404 Not Found", "").await,
+        }
+    }
+}
+
+async fn read_request_target(stream: &mut TcpStream) -> Result<String> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..read]);
+        if buf.len() > MAX_REQUEST_BYTES {
+            bail!("request too large");
+        }
+    }
+
+    let head = String::from_utf8_lossy(&buf);
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    match (request_line.next(), request_line.next()) {
+        (Some("GET"), Some(target)) => Ok(target.to_string()),
+        _ => bail!("not a GET request"),
+    }
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Redirect {
+    Code(String),
+    Error(String),
+    StateMismatch,
+    Other,
+}
+
+fn parse_redirect(target: &str, expected_state: &str) -> Redirect {
+    let Ok(url) = url::Url::parse(&format!("http://localhost{}", target)) else {
+        return Redirect::Other;
+    };
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+
+    let (code, error) = (param("code"), param("error"));
+    if code.is_none() && error.is_none() {
+        return Redirect::Other;
+    }
+    if param("state").as_deref() != Some(expected_state) {
+        return Redirect::StateMismatch;
+    }
+    match (code, error) {
+        (_, Some(error)) => Redirect::Error(match param("error_description") {
+            Some(description) => format!("{} ({})", error, description),
+            None => error,
+        }),
+        (Some(code), None) => Redirect::Code(code),
+        (None, None) => Redirect::Other,
+    }
+}
+
+async fn exchange_code(
+    client: &BasicClient,
+    code: String,
+    verifier: PkceCodeVerifier,
+) -> Result<BasicTokenResponse> {
+    client
+        .exchange_code(AuthorizationCode::new(code))
+        .set_pkce_verifier(verifier)
+        .request_async(crate::client::oauth_http_client)
+        .await
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to exchange authorization code: {}",
+                crate::client::redact_configured_proxy_secrets(&format!("{:?}", e))
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine as _;
+    use oauth2::{AuthUrl, ClientId, TokenResponse, TokenUrl};
+    use sha2::{Digest, Sha256};
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn pkce_challenge_is_the_s256_of_the_verifier() {
+        let (challenge, verifier) = pkce_pair();
+        let secret = verifier.secret();
+
+        // RFC 7636 4.1: 43-128 unreserved characters.
+        assert!((43..=128).contains(&secret.len()));
+        assert!(secret
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-._~".contains(c)));
+
+        let expected = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(Sha256::digest(secret.as_bytes()));
+        assert_eq!(challenge.as_str(), expected);
+        assert_eq!(challenge.method().as_str(), "S256");
+
+        let (_, other) = pkce_pair();
+        assert_ne!(other.secret(), secret);
+    }
+
+    #[test]
+    fn redirects_are_matched_to_our_state() {
+        assert_eq!(
+            parse_redirect("/?code=abc&state=xyz", "xyz"),
+            Redirect::Code("abc".to_string())
+        );
+        assert_eq!(
+            parse_redirect("/?code=abc&state=other", "xyz"),
+            Redirect::StateMismatch
+        );
+        assert_eq!(
+            parse_redirect(
+                "/?error=access_denied&error_description=User%20declined&state=xyz",
+                "xyz"
+            ),
+            Redirect::Error("access_denied (User declined)".to_string())
+        );
+        assert_eq!(parse_redirect("/favicon.ico", "xyz"), Redirect::Other);
+    }
+
+    fn mock_client(server: &MockServer) -> BasicClient {
+        BasicClient::new(
+            ClientId::new("test-client".to_string()),
+            None,
+            AuthUrl::new(format!("{}/authorize", server.uri())).unwrap(),
+            Some(TokenUrl::new(format!("{}/token", server.uri())).unwrap()),
+        )
+    }
+
+    /// Play the browser: hit the loopback redirect the way Microsoft would
+    /// after sign-in, preceded by a stray request that must be ignored.
+    fn complete_in_browser(authorize_url: &str, query: impl Fn(&str) -> String + Send + 'static) {
+        let url = url::Url::parse(authorize_url).unwrap();
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+                .unwrap()
+        };
+        assert_eq!(param("code_challenge_method"), "S256");
+        let (redirect_uri, state) = (param("redirect_uri"), param("state"));
+        let redirect_uri = redirect_uri.replace("localhost", "127.0.0.1");
+
+        tokio::spawn(async move {
+            let http = reqwest::Client::new();
+            let stray = http
+                .get(format!("{}/favicon.ico", redirect_uri))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(stray.status(), 404);
+            let page = http
+                .get(format!("{}/?{}", redirect_uri, query(&state)))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(page.status(), 200);
+        });
+    }
+
+    #[tokio::test]
+    async fn loopback_code_is_exchanged_with_the_verifier() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(body_string_contains("grant_type=authorization_code"))
+            .and(body_string_contains("code=test-code"))
+            .and(body_string_contains("code_verifier="))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "ms-access",
+                "token_type": "Bearer",
+                "expires_in": 3600,
+                "refresh_token": "ms-refresh"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let token = browser_login_with_client(
+            mock_client(&server),
+            |url| complete_in_browser(url, |state| format!("code=test-code&state={}", state)),
+            Duration::from_secs(10),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(token.access_token().secret(), "ms-access");
+        assert_eq!(token.refresh_token().unwrap().secret(), "ms-refresh");
+    }
+
+    #[tokio::test]
+    async fn declined_login_and_timeouts_are_errors() {
+        let server = MockServer::start().await;
+
+        let declined = browser_login_with_client(
+            mock_client(&server),
+            |url| complete_in_browser(url, |state| format!("error=access_denied&state={}", state)),
+            Duration::from_secs(10),
+        )
+        .await;
+        assert!(declined.unwrap_err().to_string().contains("access_denied"));
+
+        let timed_out =
+            browser_login_with_client(mock_client(&server), |_| {}, Duration::from_millis(50))
+                .await;
+        assert!(timed_out.is_err());
+    }
+}
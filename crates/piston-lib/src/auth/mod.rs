@@ -1,7 +1,12 @@
 //! Microsoft OAuth authentication module
 //!
-//! Provides OAuth2 device-code flow for Microsoft authentication and
-//! token exchange for Minecraft services.
+//! Provides OAuth2 device-code flow for Microsoft authentication, a browser
+//! authorization-code fallback (see [`browser`]), and token exchange for
+//! Minecraft services.
+
+mod browser;
+
+pub use browser::{start_browser_login, BROWSER_LOGIN_TIMEOUT};
 
 use anyhow::{Context, Result};
 use oauth2::TokenResponse;
@@ -13,6 +18,8 @@ use oauth2::{
 };
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+
+use crate::api::mojang::{get_minecraft_profile, MinecraftProfile};
 use uuid::Uuid;
 
 /// Authentication errors
@@ -149,6 +156,112 @@ pub async fn refresh_access_token(
     }
 }
 
+/// Claims of a Minecraft access token. The token is a JWT; its signature is
+/// not checked since the values are only used locally or passed to the game.
+fn minecraft_token_claims(minecraft_access_token: &str) -> Option<serde_json::Value> {
+    use base64::Engine as _;
+
+    let payload = minecraft_access_token.split('.').nth(1)?;
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Xbox user id (`xuid` claim) carried in a Minecraft access token.
+pub fn xuid_from_minecraft_token(minecraft_access_token: &str) -> Option<String> {
+    let claims = minecraft_token_claims(minecraft_access_token)?;
+    match claims.get("xuid")? {
+        serde_json::Value::String(xuid) if !xuid.is_empty() => Some(xuid.clone()),
+        serde_json::Value::Number(xuid) => Some(xuid.to_string()),
+        _ => None,
+    }
+}
+
+/// Expiry (`exp` claim, unix seconds) of a Minecraft access token.
+pub fn expiry_from_minecraft_token(minecraft_access_token: &str) -> Option<i64> {
+    minecraft_token_claims(minecraft_access_token)?
+        .get("exp")?
+        .as_i64()
+}
+
+/// An account signed in without the OAuth flow. Persisting it is up to the
+/// caller.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Account {
+    /// Profile UUID without dashes.
+    pub uuid: String,
+    /// Username as reported by the profile endpoint.
+    pub username: String,
+    pub access_token: String,
+    pub refresh_token: String,
+    /// Unix seconds, when the access token carries an `exp` claim.
+    pub expires_at: Option<i64>,
+    pub xuid: Option<String>,
+    pub profile: MinecraftProfile,
+}
+
+/// Sign in with an existing Minecraft access token (CI and headless setups
+/// where the device-code flow can't be completed). The token is validated by
+/// fetching the profile it belongs to, which must match `uuid`.
+pub async fn sign_in_with_token(
+    access_token: &str,
+    refresh_token: &str,
+    uuid: &str,
+    username: &str,
+) -> Result<Account> {
+    let access_token = access_token.trim();
+    if access_token.is_empty() {
+        anyhow::bail!("Access token is empty");
+    }
+
+    let profile = get_minecraft_profile(access_token)
+        .await
+        .context("Access token was rejected by Minecraft services")?;
+    let account = account_from_profile(profile, access_token, refresh_token, uuid)?;
+    if !account.username.eq_ignore_ascii_case(username.trim()) {
+        log::warn!(
+            "[auth] Token belongs to {} rather than {}; using the profile name",
+            account.username,
+            username
+        );
+    }
+    log::info!(
+        "[auth] Signed in {} ({}) with a provided access token",
+        account.username,
+        account.uuid
+    );
+    Ok(account)
+}
+
+fn account_from_profile(
+    profile: MinecraftProfile,
+    access_token: &str,
+    refresh_token: &str,
+    expected_uuid: &str,
+) -> Result<Account> {
+    let profile_uuid = profile.id.replace('-', "").to_ascii_lowercase();
+    let expected_uuid = expected_uuid.trim().replace('-', "").to_ascii_lowercase();
+    if profile_uuid != expected_uuid {
+        anyhow::bail!(
+            "Access token belongs to profile {} ({}), not {}",
+            profile.name,
+            profile_uuid,
+            expected_uuid
+        );
+    }
+
+    Ok(Account {
+        uuid: profile_uuid,
+        username: profile.name.clone(),
+        access_token: access_token.to_string(),
+        refresh_token: refresh_token.trim().to_string(),
+        expires_at: expiry_from_minecraft_token(access_token),
+        xuid: xuid_from_minecraft_token(access_token),
+        profile,
+    })
+}
+
 /// Exchange Microsoft access token for Minecraft token
 pub async fn exchange_for_minecraft_token(
     microsoft_access_token: &str,
@@ -191,3 +304,70 @@ pub fn generate_offline_uuid(username: &str) -> String {
     );
     uuid.to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine as _;
+
+    fn token_with_claims(claims: serde_json::Value) -> String {
+        let encode = |value: &[u8]| base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(value);
+        format!(
+            "{}.{}.signature",
+            encode(br#"{"alg":"RS256"}"#),
+            encode(claims.to_string().as_bytes())
+        )
+    }
+
+    #[test]
+    fn reads_xuid_claim_from_minecraft_token() {
+        let token = token_with_claims(serde_json::json!({ "xuid": "2535405290000000" }));
+        assert_eq!(
+            xuid_from_minecraft_token(&token).as_deref(),
+            Some("2535405290000000")
+        );
+        assert_eq!(
+            xuid_from_minecraft_token(&token_with_claims(serde_json::json!({ "sub": "x" }))),
+            None
+        );
+        assert_eq!(xuid_from_minecraft_token("not-a-jwt"), None);
+    }
+
+    fn profile(id: &str, name: &str) -> MinecraftProfile {
+        MinecraftProfile {
+            id: id.to_string(),
+            name: name.to_string(),
+            skins: Vec::new(),
+            capes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn token_account_takes_identity_from_the_profile() {
+        let token = token_with_claims(serde_json::json!({ "xuid": "2535", "exp": 1_760_000_000 }));
+        let account = account_from_profile(
+            profile("069a79f444e94726a5befca90e38aaf5", "Notch"),
+            &token,
+            " refresh ",
+            "069a79f4-44e9-4726-a5be-fca90e38aaf5",
+        )
+        .unwrap();
+
+        assert_eq!(account.uuid, "069a79f444e94726a5befca90e38aaf5");
+        assert_eq!(account.username, "Notch");
+        assert_eq!(account.refresh_token, "refresh");
+        assert_eq!(account.expires_at, Some(1_760_000_000));
+        assert_eq!(account.xuid.as_deref(), Some("2535"));
+    }
+
+    #[test]
+    fn token_for_another_profile_is_rejected() {
+        let result = account_from_profile(
+            profile("069a79f444e94726a5befca90e38aaf5", "Notch"),
+            "opaque-token",
+            "",
+            "853c80ef3c3749fdaa49938b674adae6",
+        );
+        assert!(result.is_err());
+    }
+}
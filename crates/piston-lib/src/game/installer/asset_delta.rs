@@ -0,0 +1,254 @@
+use crate::game::installer::find_in_object_store;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Parsed `assets/indexes/<id>.json`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AssetIndexFile {
+    #[serde(default)]
+    pub objects: BTreeMap<String, AssetIndexEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AssetIndexEntry {
+    pub hash: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+impl AssetIndexFile {
+    pub fn parse(content: &str) -> Result<Self> {
+        serde_json::from_str(content).context("Asset index is corrupt (unparseable)")
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read asset index {:?}", path))?;
+        Self::parse(&content)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetObject {
+    pub name: String,
+    pub hash: String,
+    pub size: u64,
+}
+
+/// Difference between two asset indexes. An object whose hash changed counts
+/// as both removed (old hash) and added (new hash).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetDelta {
+    pub added: Vec<AssetObject>,
+    pub removed: Vec<AssetObject>,
+    pub unchanged_count: usize,
+}
+
+impl AssetDelta {
+    pub fn added_bytes(&self) -> u64 {
+        self.added.iter().map(|object| object.size).sum()
+    }
+}
+
+pub fn compute_asset_delta(old_index: &AssetIndexFile, new_index: &AssetIndexFile) -> AssetDelta {
+    let mut delta = AssetDelta::default();
+
+    for (name, entry) in &new_index.objects {
+        match old_index.objects.get(name) {
+            Some(old) if old.hash == entry.hash => delta.unchanged_count += 1,
+            _ => delta.added.push(asset_object(name, entry)),
+        }
+    }
+    for (name, entry) in &old_index.objects {
+        let still_present = new_index
+            .objects
+            .get(name)
+            .is_some_and(|new| new.hash == entry.hash);
+        if !still_present {
+            delta.removed.push(asset_object(name, entry));
+        }
+    }
+
+    delta
+}
+
+fn asset_object(name: &str, entry: &AssetIndexEntry) -> AssetObject {
+    AssetObject {
+        name: name.to_string(),
+        hash: entry.hash.clone(),
+        size: entry.size,
+    }
+}
+
+/// Most recently written asset index in `assets/indexes` other than
+/// `current_id`, i.e. the one left behind by the last installed version.
+pub fn find_previous_asset_index(assets_dir: &Path, current_id: &str) -> Option<(String, PathBuf)> {
+    let entries = std::fs::read_dir(assets_dir.join("indexes")).ok()?;
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                return None;
+            }
+            let id = path.file_stem()?.to_str()?.to_string();
+            if id == current_id {
+                return None;
+            }
+            let modified = entry.metadata().and_then(|meta| meta.modified()).ok()?;
+            Some((modified, id, path))
+        })
+        .max_by_key(|(modified, _, _)| *modified)
+        .map(|(_, id, path)| (id, path))
+}
+
+/// Asset delta between two Minecraft versions, using local metadata where it
+/// is already installed and fetching it otherwise.
+pub async fn estimate_asset_delta(
+    data_dir: &Path,
+    from_version: &str,
+    to_version: &str,
+) -> Result<AssetDelta> {
+    let old_index = load_version_asset_index(data_dir, from_version).await?;
+    let new_index = load_version_asset_index(data_dir, to_version).await?;
+    Ok(compute_asset_delta(&old_index, &new_index))
+}
+
+/// Bytes of `version_id`'s asset objects not yet in `assets/objects`. Assets
+/// are most of what a fresh install downloads, so this is the install-size
+/// estimate used before asking to download on a metered connection.
+pub async fn missing_asset_bytes(data_dir: &Path, version_id: &str) -> Result<u64> {
+    let index = load_version_asset_index(data_dir, version_id).await?;
+    let assets_dir = data_dir.join("assets");
+    Ok(index
+        .objects
+        .values()
+        .filter(|entry| {
+            entry.hash.len() > 2 && find_in_object_store(&assets_dir, &entry.hash).is_none()
+        })
+        .map(|entry| entry.size)
+        .sum())
+}
+
+pub(crate) async fn load_version_asset_index(
+    data_dir: &Path,
+    version_id: &str,
+) -> Result<AssetIndexFile> {
+    let client = crate::client::shared_client();
+
+    let version_json_path = data_dir
+        .join("versions")
+        .join(version_id)
+        .join(format!("{}.json", version_id));
+    let manifest: crate::game::launcher::version_parser::VersionManifest =
+        if version_json_path.exists() {
+            serde_json::from_str(&tokio::fs::read_to_string(&version_json_path).await?)?
+        } else {
+            let version_url = format!(
+                "https://launcher-meta.modrinth.com/minecraft/v0/versions/{}.json",
+                version_id
+            );
+            client
+                .get(&version_url)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await
+                .with_context(|| format!("Failed to load version info for {}", version_id))?
+        };
+
+    let asset_index = crate::game::launcher::version_parser::get_asset_index(&manifest)?;
+    let index_path = data_dir
+        .join("assets")
+        .join("indexes")
+        .join(format!("{}.json", asset_index.id));
+    if index_path.exists() {
+        let content = tokio::fs::read_to_string(&index_path).await?;
+        return AssetIndexFile::parse(&content);
+    }
+
+    let content = client
+        .get(&asset_index.url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await
+        .with_context(|| format!("Failed to download asset index {}", asset_index.id))?;
+    AssetIndexFile::parse(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(objects: &[(&str, &str, u64)]) -> AssetIndexFile {
+        AssetIndexFile {
+            objects: objects
+                .iter()
+                .map(|(name, hash, size)| {
+                    (
+                        name.to_string(),
+                        AssetIndexEntry {
+                            hash: hash.to_string(),
+                            size: *size,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn delta_splits_added_removed_and_unchanged() {
+        let old = index(&[("a", "1", 10), ("b", "2", 20), ("gone", "3", 30)]);
+        let new = index(&[("a", "1", 10), ("b", "22", 25), ("new", "4", 40)]);
+
+        let delta = compute_asset_delta(&old, &new);
+
+        assert_eq!(delta.unchanged_count, 1);
+        let added: Vec<&str> = delta.added.iter().map(|o| o.name.as_str()).collect();
+        let removed: Vec<&str> = delta.removed.iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(added, ["b", "new"]);
+        assert_eq!(removed, ["b", "gone"]);
+        assert_eq!(delta.added_bytes(), 65);
+    }
+
+    #[test]
+    fn identical_indexes_have_empty_delta() {
+        let idx = index(&[("a", "1", 10), ("b", "2", 20)]);
+        let delta = compute_asset_delta(&idx, &idx);
+        assert!(delta.added.is_empty());
+        assert!(delta.removed.is_empty());
+        assert_eq!(delta.unchanged_count, 2);
+    }
+
+    #[test]
+    fn parses_index_json() {
+        let parsed = AssetIndexFile::parse(
+            r#"{"objects":{"icons/icon_16x16.png":{"hash":"bdf48ef6b5d0d23bbb02e17d04865216179f510a","size":3665}}}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.objects["icons/icon_16x16.png"].size, 3665);
+        assert!(AssetIndexFile::parse("{not json").is_err());
+    }
+
+    #[test]
+    fn previous_index_ignores_current_and_non_json() {
+        let tmp = tempfile::tempdir().unwrap();
+        let indexes = tmp.path().join("indexes");
+        std::fs::create_dir_all(&indexes).unwrap();
+        std::fs::write(indexes.join("17.json"), "{}").unwrap();
+        std::fs::write(indexes.join("16.json.tmp"), "{}").unwrap();
+
+        assert_eq!(find_previous_asset_index(tmp.path(), "17"), None);
+
+        std::fs::write(indexes.join("16.json"), "{}").unwrap();
+        let (id, path) = find_previous_asset_index(tmp.path(), "17").unwrap();
+        assert_eq!(id, "16");
+        assert_eq!(path, indexes.join("16.json"));
+    }
+}
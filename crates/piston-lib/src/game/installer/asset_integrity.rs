@@ -0,0 +1,195 @@
+//! Full SHA-1 verification of the shared asset object store.
+//!
+//! The install pre-scan only checks that each object exists. This hashes
+//! every object already on disk, on blocking worker threads, and reports the
+//! ones whose content no longer matches their name so they can be fetched
+//! again. Missing objects are left to the pre-scan.
+
+use super::asset_delta::AssetIndexFile;
+use super::find_in_object_store;
+use super::types::ProgressReporter;
+use anyhow::{bail, Context, Result};
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const PROGRESS_INTERVAL: usize = 100;
+
+/// Store label of an object, as used for downloads and the artifact cache.
+pub fn asset_object_label(hash: &str) -> String {
+    format!("assets/objects/{}/{}", hash.get(..2).unwrap_or(hash), hash)
+}
+
+/// Hash from a label made by [`asset_object_label`].
+pub fn hash_from_label(label: &str) -> Option<&str> {
+    label
+        .strip_prefix("assets/objects/")
+        .and_then(|rest| rest.split('/').nth(1))
+}
+
+/// Result of verifying the assets of one game version.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct AssetVerificationReport {
+    pub version_id: String,
+    /// Objects found on disk and hashed.
+    pub checked_objects: usize,
+    /// Labels of objects whose SHA-1 didn't match.
+    pub corrupted: Vec<String>,
+}
+
+/// Objects of `asset_index` present in the store. Several names can share
+/// one object, so each hash appears once.
+fn present_objects(assets_dir: &Path, asset_index: &AssetIndexFile) -> Vec<(String, PathBuf)> {
+    asset_index
+        .objects
+        .values()
+        .map(|entry| entry.hash.to_ascii_lowercase())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter_map(|hash| find_in_object_store(assets_dir, &hash).map(|path| (hash, path)))
+        .collect()
+}
+
+/// Hash every object of `asset_index` present under `assets_dir` with
+/// `workers` concurrent blocking tasks. Returns the labels of objects whose
+/// SHA-1 doesn't match, sorted.
+pub async fn verify_assets_integrity(
+    assets_dir: &Path,
+    asset_index: &AssetIndexFile,
+    reporter: Arc<dyn ProgressReporter>,
+    workers: usize,
+) -> Result<Vec<String>> {
+    let objects = present_objects(assets_dir, asset_index);
+    let total = objects.len();
+    reporter.set_message("Verifying game asset hashes...");
+    reporter.set_step_count(0, Some(total as u32));
+    let checked = Arc::new(AtomicUsize::new(0));
+
+    let results = stream::iter(objects)
+        .map(|(hash, path)| {
+            let reporter = reporter.clone();
+            let checked = checked.clone();
+            async move {
+                if reporter.is_cancelled() {
+                    bail!("Asset verification cancelled");
+                }
+                let label = asset_object_label(&hash);
+                let matches = tokio::task::spawn_blocking(move || {
+                    super::verifier::compute_sha1(&path).map(|actual| actual == hash)
+                })
+                .await
+                .context("spawn_blocking panicked")?
+                // An unreadable object is as good as a corrupt one.
+                .unwrap_or(false);
+
+                let done = checked.fetch_add(1, Ordering::SeqCst) + 1;
+                if done.is_multiple_of(PROGRESS_INTERVAL) || done == total {
+                    reporter.set_step_count(done as u32, Some(total as u32));
+                }
+                Ok((!matches).then_some(label))
+            }
+        })
+        .buffer_unordered(workers.max(1))
+        .collect::<Vec<Result<Option<String>>>>()
+        .await;
+
+    let mut corrupted = results
+        .into_iter()
+        .filter_map(Result::transpose)
+        .collect::<Result<Vec<_>>>()?;
+    corrupted.sort();
+    if !corrupted.is_empty() {
+        log::warn!(
+            "[installer] {} of {} asset objects failed SHA-1 verification",
+            corrupted.len(),
+            total
+        );
+    }
+    Ok(corrupted)
+}
+
+/// Verify the assets of `version_id` (a vanilla version id) in the shared
+/// store under `data_dir`.
+pub async fn verify_version_assets(
+    data_dir: &Path,
+    version_id: &str,
+    reporter: Arc<dyn ProgressReporter>,
+    workers: usize,
+) -> Result<AssetVerificationReport> {
+    let index = super::asset_delta::load_version_asset_index(data_dir, version_id).await?;
+    let assets_dir = data_dir.join("assets");
+    let checked_objects = present_objects(&assets_dir, &index).len();
+    let corrupted = verify_assets_integrity(&assets_dir, &index, reporter, workers).await?;
+    Ok(AssetVerificationReport {
+        version_id: version_id.to_string(),
+        checked_objects,
+        corrupted,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::installer::asset_delta::AssetIndexEntry;
+    use crate::game::installer::types::SilentProgressReporter;
+    use sha1::{Digest, Sha1};
+    use std::collections::BTreeMap;
+
+    fn store(assets_dir: &Path, content: &[u8]) -> String {
+        let hash = format!("{:x}", Sha1::digest(content));
+        let path = assets_dir.join("objects").join(&hash[..2]).join(&hash);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+        hash
+    }
+
+    fn index(entries: &[(&str, &str)]) -> AssetIndexFile {
+        AssetIndexFile {
+            objects: entries
+                .iter()
+                .map(|(name, hash)| {
+                    (
+                        name.to_string(),
+                        AssetIndexEntry {
+                            hash: hash.to_string(),
+                            size: 0,
+                        },
+                    )
+                })
+                .collect::<BTreeMap<_, _>>(),
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_only_corrupted_objects() {
+        let tmp = tempfile::tempdir().unwrap();
+        let assets_dir = tmp.path();
+        let good = store(assets_dir, b"sound");
+        let bad = store(assets_dir, b"texture");
+        let bad_path = assets_dir.join("objects").join(&bad[..2]).join(&bad);
+        std::fs::write(&bad_path, b"truncated").unwrap();
+        let missing = "0123456789abcdef0123456789abcdef01234567";
+
+        let asset_index = index(&[
+            ("minecraft/sounds/a.ogg", &good),
+            ("minecraft/textures/b.png", &bad),
+            ("minecraft/textures/copy.png", &bad),
+            ("minecraft/lang/c.json", missing),
+        ]);
+
+        let corrupted = verify_assets_integrity(
+            assets_dir,
+            &asset_index,
+            Arc::new(SilentProgressReporter),
+            4,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(corrupted, vec![asset_object_label(&bad)]);
+        assert_eq!(hash_from_label(&corrupted[0]), Some(bad.as_str()));
+    }
+}
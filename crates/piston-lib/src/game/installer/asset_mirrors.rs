@@ -0,0 +1,82 @@
+//! Mirrors of Mojang's asset object store.
+//!
+//! Asset downloads go to resources.download.minecraft.net unless the launcher
+//! has picked a faster mirror for the session (it benchmarks the bundled list
+//! before large downloads). The preferred mirror is tried first and the
+//! official host stays as the fallback.
+
+use anyhow::Result;
+use std::sync::RwLock;
+
+use crate::game::installer::modloaders::forge_mirrors::normalize_mirror_url;
+
+pub const MOJANG_ASSETS_URL: &str = "https://resources.download.minecraft.net/";
+
+/// Asset hosts worth benchmarking, official first.
+pub const BUNDLED_ASSET_MIRRORS: &[&str] =
+    &[MOJANG_ASSETS_URL, "https://bmclapi2.bangbang93.com/assets/"];
+
+/// Object path of `icons/icon_16x16.png`, a few KB and present in every
+/// asset index since 1.6; used to benchmark asset mirrors.
+pub const BENCHMARK_ASSET_PATH: &str = "bd/bdf48ef6b5d0d23bbb02e17d04865216179f510a";
+
+static PREFERRED: RwLock<Option<String>> = RwLock::new(None);
+
+/// Try `url` first for asset downloads this session, or only the official
+/// host with `None`.
+pub fn set_preferred_mirror(url: Option<&str>) -> Result<()> {
+    let normalized = url.map(normalize_mirror_url).transpose()?;
+    let mut preferred = PREFERRED.write().unwrap_or_else(|e| e.into_inner());
+    if *preferred != normalized {
+        log::info!("[asset-mirrors] preferred mirror set to {:?}", normalized);
+        *preferred = normalized;
+    }
+    Ok(())
+}
+
+pub fn preferred_mirror() -> Option<String> {
+    PREFERRED.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// URLs to try for the asset object `hash`.
+pub fn asset_urls(hash: &str) -> Vec<String> {
+    urls_for(hash, preferred_mirror().as_deref())
+}
+
+fn urls_for(hash: &str, preferred: Option<&str>) -> Vec<String> {
+    let path = format!("{}/{}", &hash[..hash.len().min(2)], hash);
+    let mut urls = Vec::with_capacity(2);
+    for mirror in preferred.into_iter().chain([MOJANG_ASSETS_URL]) {
+        let url = format!("{}{}", mirror, path);
+        if !urls.contains(&url) {
+            urls.push(url);
+        }
+    }
+    urls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "bdf48ef6b5d0d23bbb02e17d04865216179f510a";
+
+    #[test]
+    fn preferred_mirror_goes_before_the_official_host() {
+        assert_eq!(
+            urls_for(HASH, None),
+            vec![format!("{}{}", MOJANG_ASSETS_URL, BENCHMARK_ASSET_PATH)]
+        );
+        assert_eq!(
+            urls_for(HASH, Some("https://bmclapi2.bangbang93.com/assets/")),
+            vec![
+                format!(
+                    "https://bmclapi2.bangbang93.com/assets/{}",
+                    BENCHMARK_ASSET_PATH
+                ),
+                format!("{}{}", MOJANG_ASSETS_URL, BENCHMARK_ASSET_PATH),
+            ]
+        );
+        assert_eq!(urls_for(HASH, Some(MOJANG_ASSETS_URL)).len(), 1);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::utils::paths::long_path;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
@@ -11,9 +12,28 @@ const ARTIFACTS_FILE: &str = "cache/artifacts.json";
 const INDEX_FILE: &str = "cache/install_index.json";
 const LABEL_INDEX_FILE: &str = "cache/label_index.json";
 
+/// Suffix of zstd-compressed blobs in the object store.
+const COMPRESSED_SUFFIX: &str = ".zst";
+
+/// zstd level used for blobs; zstd's default, which keeps ingest fast.
+///
+/// Benchmark (`zstd -b3`, zstd 1.5.7) on a representative mix of cached
+/// artifact types:
+///
+/// ```text
+/// jars (library-like)   179902352 -> 165708117 (1.086)  197 MB/s comp, 2622 MB/s decomp  40 files
+/// JSON (version/index)   13049716 ->    452124 (28.86)  803 MB/s comp, 3497 MB/s decomp  18 files
+/// PNG (assets)           24789387 ->  23518213 (1.054)  289 MB/s comp, 4561 MB/s decomp 300 files
+/// ```
+///
+/// Jars and images are already deflated, so the savings come mostly from
+/// version manifests and asset indexes; decompression is cheap either way.
+const ZSTD_LEVEL: i32 = 3;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArtifactRecord {
     pub sha256: String,
+    /// Bytes the blob occupies in the store (compressed size when `compressed`).
     pub size: u64,
     #[serde(default)]
     pub signature: Option<String>,
@@ -23,6 +43,9 @@ pub struct ArtifactRecord {
     pub refs: u32,
     #[serde(default)]
     pub last_used: u64,
+    /// Whether the blob is stored zstd-compressed with a `.zst` suffix.
+    #[serde(default)]
+    pub compressed: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -66,6 +89,13 @@ pub struct ArtifactPruneSummary {
     pub pinned_bytes: u64,
 }
 
+/// Result of [`ArtifactCache::collect_garbage`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct GCStats {
+    pub files_deleted: usize,
+    pub bytes_reclaimed: u64,
+}
+
 #[derive(Debug, Clone)]
 struct UntrackedBlob {
     sha256: String,
@@ -86,6 +116,7 @@ pub struct ArtifactCache {
     artifacts: HashMap<String, ArtifactRecord>,
     install_index: HashMap<String, InstallIndexRecord>,
     label_index: HashMap<String, String>,
+    compression_enabled: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -122,6 +153,7 @@ impl ArtifactCache {
             artifacts,
             install_index,
             label_index: HashMap::new(),
+            compression_enabled: false,
         };
         cache.reconcile_with_disk();
         Ok(cache)
@@ -170,8 +202,20 @@ impl ArtifactCache {
         self.artifacts.contains_key(sha256)
     }
 
+    /// Compress newly ingested blobs with zstd. Blobs already in the store
+    /// keep their current form and stay restorable either way.
+    pub fn set_compression_enabled(&mut self, enabled: bool) {
+        self.compression_enabled = enabled;
+    }
+
     pub fn get_artifact_path(&self, sha256: &str) -> PathBuf {
-        artifact_path(&self.root, sha256)
+        match self.artifacts.get(sha256) {
+            Some(record) if record.compressed => compressed_artifact_path(&self.root, sha256),
+            Some(_) => artifact_path(&self.root, sha256),
+            None => stored_blob(&self.root, sha256)
+                .map(|(path, _)| path)
+                .unwrap_or_else(|| artifact_path(&self.root, sha256)),
+        }
     }
 
     pub fn add_artifact(
@@ -191,6 +235,7 @@ impl ArtifactCache {
                 source_url: source_url.clone(),
                 refs: 0,
                 last_used: timestamp(),
+                compressed: false,
             });
         record.size = size;
         if signature.is_some() {
@@ -221,19 +266,38 @@ impl ArtifactCache {
         signature: Option<String>,
         source_url: Option<String>,
     ) -> Result<String> {
-        let (sha256, size) = hash_file(path)
+        let path = &long_path(path);
+        let (sha256, _) = hash_file(path)
             .with_context(|| format!("Hash artifact for cache ingestion: {:?}", path))?;
-        let blob_path = self.get_artifact_path(&sha256);
-        if !blob_path.exists() {
-            if let Some(parent) = blob_path.parent() {
-                fs::create_dir_all(parent)
-                    .with_context(|| format!("Create cache blob dir {:?}", parent))?;
+        let (blob_path, compressed) = match stored_blob(&self.root, &sha256) {
+            Some(stored) => stored,
+            None => {
+                let compressed = self.compression_enabled;
+                let blob_path = if compressed {
+                    compressed_artifact_path(&self.root, &sha256)
+                } else {
+                    artifact_path(&self.root, &sha256)
+                };
+                if let Some(parent) = blob_path.parent() {
+                    fs::create_dir_all(parent)
+                        .with_context(|| format!("Create cache blob dir {:?}", parent))?;
+                }
+                if compressed {
+                    compress_to_path(path, &blob_path).with_context(|| {
+                        format!("Compress artifact into cache {:?} -> {:?}", path, blob_path)
+                    })?;
+                } else {
+                    fs::copy(path, &blob_path).with_context(|| {
+                        format!("Copy artifact into cache {:?} -> {:?}", path, blob_path)
+                    })?;
+                }
+                (blob_path, compressed)
             }
-            fs::copy(path, &blob_path).with_context(|| {
-                format!("Copy artifact into cache {:?} -> {:?}", path, blob_path)
-            })?;
+        };
+        self.add_artifact(sha256.clone(), file_size(&blob_path), signature, source_url);
+        if let Some(record) = self.artifacts.get_mut(&sha256) {
+            record.compressed = compressed;
         }
-        self.add_artifact(sha256.clone(), size, signature, source_url);
         Ok(sha256)
     }
 
@@ -267,6 +331,10 @@ impl ArtifactCache {
         self.rebuild_refs_from_install_index();
     }
 
+    pub fn has_install(&self, version_id: &str) -> bool {
+        self.install_index.contains_key(version_id)
+    }
+
     pub fn remove_install(&mut self, version_id: &str) {
         if self.install_index.remove(version_id).is_some() {
             self.rebuild_refs_from_install_index();
@@ -284,6 +352,64 @@ impl ArtifactCache {
         self.remove_artifacts(&removable);
     }
 
+    /// Delete every file in the blob store whose name is not a hash referenced
+    /// by an install record, then drop the matching index entries.
+    ///
+    /// Unlike [`prune_unused`](Self::prune_unused), this scans the disk, so it
+    /// also reclaims blobs the index never knew about (interrupted ingests,
+    /// stray files). Must not run while an install is ingesting artifacts.
+    pub fn collect_garbage(&mut self) -> Result<GCStats> {
+        let referenced = self
+            .install_index
+            .values()
+            .flat_map(|idx| idx.libraries.iter().chain(idx.components.values()))
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        let blobs_dir = self.root.join("cache").join("blobs");
+        let mut files = Vec::new();
+        collect_blob_files(&blobs_dir, &mut files)
+            .with_context(|| format!("Scan artifact blob store {:?}", blobs_dir))?;
+
+        let mut stats = GCStats::default();
+        let mut deleted = HashSet::new();
+        for (path, size) in files {
+            let name = blob_sha(&path).unwrap_or_default().to_string();
+            if referenced.contains(&name) {
+                continue;
+            }
+            match fs::remove_file(&path) {
+                Ok(()) => {
+                    stats.files_deleted += 1;
+                    stats.bytes_reclaimed = stats.bytes_reclaimed.saturating_add(size);
+                    deleted.insert(name);
+                }
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                    deleted.insert(name);
+                }
+                Err(error) => {
+                    log::warn!(
+                        "[artifact-cache] GC failed to remove blob {:?}: {}",
+                        path,
+                        error
+                    );
+                }
+            }
+        }
+
+        self.artifacts.retain(|sha, _| !deleted.contains(sha));
+        self.label_index.retain(|_, sha| !deleted.contains(sha));
+        self.rebuild_refs_from_install_index();
+        remove_empty_dirs(&blobs_dir);
+
+        log::info!(
+            "[artifact-cache] GC deleted {} file(s), reclaimed {} bytes",
+            stats.files_deleted,
+            stats.bytes_reclaimed
+        );
+        Ok(stats)
+    }
+
     pub fn usage_summary(&self) -> ArtifactUsageSummary {
         let reachable = self.reachable_artifacts();
         let mut summary = ArtifactUsageSummary {
@@ -420,7 +546,9 @@ impl ArtifactCache {
         Self::restore_blob_to_path(&blob_path, destination)
     }
 
+    /// Copy a blob to `destination`, decompressing `.zst` blobs on the way.
     pub fn restore_blob_to_path(blob_path: &Path, destination: &Path) -> Result<bool> {
+        let destination = &long_path(destination);
         if !blob_path.exists() {
             return Ok(false);
         }
@@ -432,11 +560,29 @@ impl ArtifactCache {
             fs::remove_file(destination)
                 .with_context(|| format!("Remove existing destination {:?}", destination))?;
         }
-        fs::copy(&blob_path, destination)
-            .with_context(|| format!("Restore artifact {:?} -> {:?}", blob_path, destination))?;
+        if is_compressed_blob(blob_path) {
+            decompress_to_path(blob_path, destination).with_context(|| {
+                format!("Decompress artifact {:?} -> {:?}", blob_path, destination)
+            })?;
+        } else {
+            fs::copy(blob_path, destination).with_context(|| {
+                format!("Restore artifact {:?} -> {:?}", blob_path, destination)
+            })?;
+        }
         Ok(true)
     }
 
+    /// [`restore_blob_to_path`](Self::restore_blob_to_path) on the blocking
+    /// pool, since decompressing large blobs is CPU-bound.
+    pub async fn restore_blob_to_path_async(
+        blob_path: PathBuf,
+        destination: PathBuf,
+    ) -> Result<bool> {
+        tokio::task::spawn_blocking(move || Self::restore_blob_to_path(&blob_path, &destination))
+            .await
+            .context("Artifact restore task panicked")?
+    }
+
     fn reachable_artifacts(&self) -> HashSet<String> {
         self.install_index
             .values()
@@ -544,6 +690,63 @@ fn artifact_path(root: &Path, sha256: &str) -> PathBuf {
     root.join("cache").join("blobs").join(prefix).join(sha256)
 }
 
+fn compressed_artifact_path(root: &Path, sha256: &str) -> PathBuf {
+    let prefix = sha256.get(..2).unwrap_or(sha256);
+    root.join("cache")
+        .join("blobs")
+        .join(prefix)
+        .join(format!("{}{}", sha256, COMPRESSED_SUFFIX))
+}
+
+/// Existing blob for `sha256` and whether it is compressed.
+fn stored_blob(root: &Path, sha256: &str) -> Option<(PathBuf, bool)> {
+    let raw = artifact_path(root, sha256);
+    if raw.is_file() {
+        return Some((raw, false));
+    }
+    let compressed = compressed_artifact_path(root, sha256);
+    compressed.is_file().then_some((compressed, true))
+}
+
+fn is_compressed_blob(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(COMPRESSED_SUFFIX))
+}
+
+/// Hash a blob file is stored under, with any `.zst` suffix removed.
+fn blob_sha(path: &Path) -> Option<&str> {
+    let name = path.file_name()?.to_str()?;
+    Some(name.strip_suffix(COMPRESSED_SUFFIX).unwrap_or(name))
+}
+
+fn compress_to_path(source: &Path, destination: &Path) -> Result<()> {
+    // Write next to the blob and rename so a crash never leaves a truncated
+    // `.zst` that looks valid; stray partials are removed by GC.
+    let partial = destination.with_extension("partial");
+    let mut reader = BufReader::new(fs::File::open(source)?);
+    let mut encoder = zstd::Encoder::new(fs::File::create(&partial)?, ZSTD_LEVEL)?;
+    std::io::copy(&mut reader, &mut encoder)?;
+    encoder.finish()?.sync_all()?;
+    fs::rename(&partial, destination)?;
+    Ok(())
+}
+
+fn decompress_to_path(source: &Path, destination: &Path) -> Result<()> {
+    let mut partial = destination.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+    let mut decoder = zstd::Decoder::new(fs::File::open(source)?)?;
+    let mut output = fs::File::create(&partial)?;
+    if let Err(error) = std::io::copy(&mut decoder, &mut output) {
+        drop(output);
+        let _ = fs::remove_file(&partial);
+        return Err(error.into());
+    }
+    fs::rename(&partial, destination)?;
+    Ok(())
+}
+
 fn file_size(path: &Path) -> u64 {
     fs::metadata(path)
         .map(|metadata| metadata.len())
@@ -587,7 +790,7 @@ fn collect_untracked_blobs(
             continue;
         }
 
-        let Some(file_name) = entry_path.file_name().and_then(|name| name.to_str()) else {
+        let Some(file_name) = blob_sha(&entry_path) else {
             continue;
         };
 
@@ -604,6 +807,39 @@ fn collect_untracked_blobs(
     }
 }
 
+fn collect_blob_files(path: &Path, files: &mut Vec<(PathBuf, u64)>) -> std::io::Result<()> {
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(error) => return Err(error),
+    };
+
+    for entry in entries {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            collect_blob_files(&entry.path(), files)?;
+        } else if metadata.is_file() {
+            files.push((entry.path(), metadata.len()));
+        }
+    }
+    Ok(())
+}
+
+/// Remove now-empty prefix directories below `root` (but not `root` itself).
+fn remove_empty_dirs(root: &Path) {
+    let Ok(entries) = fs::read_dir(root) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            remove_empty_dirs(&path);
+            let _ = fs::remove_dir(&path);
+        }
+    }
+}
+
 fn timestamp() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -880,4 +1116,147 @@ mod tests {
         assert_eq!(summary.total_bytes, size);
         assert!(cache.has_artifact(&sha));
     }
+
+    #[test]
+    fn test_collect_garbage_deletes_unreferenced_blobs_only() {
+        let tmp = tempdir().unwrap();
+        let mut cache = ArtifactCache::load_with_labels(tmp.path()).unwrap();
+
+        let (kept, _) = ingest_test_artifact(&mut cache, tmp.path(), "kept.bin", b"kept");
+        let (dropped, dropped_size) =
+            ingest_test_artifact(&mut cache, tmp.path(), "dropped.bin", b"dropped");
+        cache.record_install("1.20.1", None, &[InstallArtifactRef::new("kept", &kept)]);
+        cache.set_label("dropped", dropped.clone());
+
+        let stray_sha = "a".repeat(64);
+        let stray = write_untracked_blob(tmp.path(), &stray_sha, b"stray!");
+        let junk = tmp
+            .path()
+            .join("cache")
+            .join("blobs")
+            .join("ab")
+            .join("partial.tmp");
+        fs::create_dir_all(junk.parent().unwrap()).unwrap();
+        fs::write(&junk, b"xx").unwrap();
+
+        let stats = cache.collect_garbage().unwrap();
+
+        assert_eq!(stats.files_deleted, 3);
+        assert_eq!(stats.bytes_reclaimed, dropped_size + 6 + 2);
+        assert!(cache.get_artifact_path(&kept).exists());
+        assert!(!cache.get_artifact_path(&dropped).exists());
+        assert!(!stray.exists());
+        assert!(!junk.exists());
+        assert!(cache.has_artifact(&kept));
+        assert!(!cache.has_artifact(&dropped));
+        assert!(cache.find_component("dropped").is_none());
+    }
+
+    #[test]
+    fn test_compressed_blobs_round_trip_with_zst_suffix() {
+        let tmp = tempdir().unwrap();
+        let mut cache = ArtifactCache::load_with_labels(tmp.path()).unwrap();
+        cache.set_compression_enabled(true);
+
+        let contents = b"{\"id\":\"1.20.1\"}".repeat(512);
+        let (sha, original_size) =
+            ingest_test_artifact(&mut cache, tmp.path(), "1.20.1.json", &contents);
+        cache.set_label("version-json", sha.clone());
+        cache.save().unwrap();
+
+        let blob_path = cache.get_artifact_path(&sha);
+        assert_eq!(blob_path, compressed_artifact_path(tmp.path(), &sha));
+        assert!(blob_path.exists());
+        assert!(!artifact_path(tmp.path(), &sha).exists());
+        let record = cache.artifacts.get(&sha).unwrap();
+        assert!(record.compressed);
+        assert!(record.size < original_size);
+        assert_eq!(record.size, file_size(&blob_path));
+
+        let cache = ArtifactCache::load_with_labels(tmp.path()).unwrap();
+        assert!(cache.has_artifact(&sha));
+        let restored = tmp.path().join("restored.json");
+        assert!(cache.restore_artifact(&sha, &restored).unwrap());
+        assert_eq!(fs::read(&restored).unwrap(), contents);
+    }
+
+    #[tokio::test]
+    async fn test_async_restore_decompresses_blob() {
+        let tmp = tempdir().unwrap();
+        let mut cache = ArtifactCache::load_with_labels(tmp.path()).unwrap();
+        cache.set_compression_enabled(true);
+        let (sha, _) = ingest_test_artifact(&mut cache, tmp.path(), "a.bin", b"compressed");
+        cache.set_label("a", sha);
+
+        let candidate = cache.restore_candidate("a").unwrap();
+        let restored = tmp.path().join("out").join("a.bin");
+        assert!(
+            ArtifactCache::restore_blob_to_path_async(candidate.blob_path, restored.clone())
+                .await
+                .unwrap()
+        );
+        assert_eq!(fs::read(&restored).unwrap(), b"compressed");
+    }
+
+    #[test]
+    fn test_existing_blobs_keep_their_form_when_toggling_compression() {
+        let tmp = tempdir().unwrap();
+        let mut cache = ArtifactCache::load_with_labels(tmp.path()).unwrap();
+
+        let (raw, _) = ingest_test_artifact(&mut cache, tmp.path(), "raw.bin", b"raw");
+        cache.set_compression_enabled(true);
+        let (raw_again, _) = ingest_test_artifact(&mut cache, tmp.path(), "raw2.bin", b"raw");
+        let (packed, _) = ingest_test_artifact(&mut cache, tmp.path(), "zst.bin", b"packed");
+        cache.set_compression_enabled(false);
+        let (packed_again, _) = ingest_test_artifact(&mut cache, tmp.path(), "zst2.bin", b"packed");
+
+        assert_eq!(raw, raw_again);
+        assert_eq!(packed, packed_again);
+        assert!(!cache.artifacts[&raw].compressed);
+        assert!(cache.artifacts[&packed].compressed);
+        assert!(artifact_path(tmp.path(), &raw).exists());
+        assert!(compressed_artifact_path(tmp.path(), &packed).exists());
+        assert!(!artifact_path(tmp.path(), &packed).exists());
+    }
+
+    #[test]
+    fn test_collect_garbage_matches_compressed_blobs_by_hash() {
+        let tmp = tempdir().unwrap();
+        let mut cache = ArtifactCache::load_with_labels(tmp.path()).unwrap();
+        cache.set_compression_enabled(true);
+
+        let (kept, _) = ingest_test_artifact(&mut cache, tmp.path(), "kept.bin", b"kept");
+        let (dropped, _) = ingest_test_artifact(&mut cache, tmp.path(), "gone.bin", b"gone");
+        cache.record_install("1.20.1", None, &[InstallArtifactRef::new("kept", &kept)]);
+
+        let stats = cache.collect_garbage().unwrap();
+
+        assert_eq!(stats.files_deleted, 1);
+        assert!(compressed_artifact_path(tmp.path(), &kept).exists());
+        assert!(!compressed_artifact_path(tmp.path(), &dropped).exists());
+        assert!(cache.has_artifact(&kept));
+        assert!(!cache.has_artifact(&dropped));
+    }
+
+    #[test]
+    fn test_untracked_compressed_blobs_are_prunable() {
+        let tmp = tempdir().unwrap();
+        let cache = ArtifactCache::load_with_labels(tmp.path()).unwrap();
+        let sha = "b".repeat(64);
+        let path = compressed_artifact_path(tmp.path(), &sha);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, b"12345").unwrap();
+
+        let usage = cache.usage_summary();
+
+        assert_eq!(usage.total_bytes, 5);
+        assert_eq!(usage.prunable_bytes, 5);
+    }
+
+    #[test]
+    fn test_index_without_compressed_flag_loads_as_raw() {
+        let record: ArtifactRecord =
+            serde_json::from_str(r#"{"sha256":"abc","size":3,"refs":0}"#).unwrap();
+        assert!(!record.compressed);
+    }
 }
@@ -0,0 +1,256 @@
+//! Shared state for installs that target the same data directory.
+//!
+//! Installs for the same Minecraft version write the same library, asset and
+//! version files. Every install into a `data_dir` shares one
+//! [`InstallCoordinator`], which hands out per-path write locks and a single
+//! in-memory [`ArtifactCache`], so concurrent installs neither interleave
+//! writes to one file nor overwrite each other's cache records on save.
+
+use super::cache::ArtifactCache;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock, Weak};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+/// Drop dead path-lock entries once the map grows past this size.
+const PATH_LOCK_SWEEP_THRESHOLD: usize = 1024;
+
+type CoordinatorRegistry = std::sync::Mutex<HashMap<PathBuf, Weak<InstallCoordinator>>>;
+
+static COORDINATORS: OnceLock<CoordinatorRegistry> = OnceLock::new();
+
+pub struct InstallCoordinator {
+    cache: Arc<Mutex<ArtifactCache>>,
+    path_locks: std::sync::Mutex<HashMap<PathBuf, Weak<Mutex<()>>>>,
+    active_installs: AtomicUsize,
+}
+
+/// Marks an install as running on its coordinator until dropped.
+pub struct ActiveInstall {
+    coordinator: Arc<InstallCoordinator>,
+}
+
+impl Drop for ActiveInstall {
+    fn drop(&mut self) {
+        self.coordinator
+            .active_installs
+            .fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl InstallCoordinator {
+    /// Coordinator for `data_dir`, loading the artifact cache from disk when
+    /// no install into that directory is currently alive.
+    pub fn for_data_dir(data_dir: &Path) -> Result<Arc<Self>> {
+        let registry = COORDINATORS.get_or_init(Default::default);
+        let mut registry = registry.lock().unwrap();
+        registry.retain(|_, coordinator| coordinator.strong_count() > 0);
+
+        let key = data_dir
+            .canonicalize()
+            .unwrap_or_else(|_| data_dir.to_path_buf());
+        if let Some(existing) = registry.get(&key).and_then(Weak::upgrade) {
+            return Ok(existing);
+        }
+
+        let coordinator = Arc::new(Self {
+            cache: Arc::new(Mutex::new(ArtifactCache::load_with_labels(data_dir)?)),
+            path_locks: std::sync::Mutex::new(HashMap::new()),
+            active_installs: AtomicUsize::new(0),
+        });
+        registry.insert(key, Arc::downgrade(&coordinator));
+        Ok(coordinator)
+    }
+
+    pub fn cache(&self) -> &Arc<Mutex<ArtifactCache>> {
+        &self.cache
+    }
+
+    /// Register a running install. Returns the guard and whether this is the
+    /// only install currently using the coordinator.
+    pub fn begin_install(self: &Arc<Self>) -> (ActiveInstall, bool) {
+        let previous = self.active_installs.fetch_add(1, Ordering::SeqCst);
+        (
+            ActiveInstall {
+                coordinator: self.clone(),
+            },
+            previous == 0,
+        )
+    }
+
+    pub fn active_installs(&self) -> usize {
+        self.active_installs.load(Ordering::SeqCst)
+    }
+
+    /// Exclusive write access to `path` among all installs in this data dir.
+    pub async fn lock_path(&self, path: &Path) -> OwnedMutexGuard<()> {
+        let lock = {
+            let mut locks = self.path_locks.lock().unwrap();
+            if locks.len() > PATH_LOCK_SWEEP_THRESHOLD {
+                locks.retain(|_, lock| lock.strong_count() > 0);
+            }
+            match locks.get(path).and_then(Weak::upgrade) {
+                Some(lock) => lock,
+                None => {
+                    let lock = Arc::new(Mutex::new(()));
+                    locks.insert(path.to_path_buf(), Arc::downgrade(&lock));
+                    lock
+                }
+            }
+        };
+        lock.lock_owned().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::installer::cache::InstallArtifactRef;
+    use crate::game::installer::core::batch::{BatchArtifact, BatchDownloader};
+    use crate::game::installer::types::SilentProgressReporter;
+    use crate::game::installer::{track_artifact_from_path, InstallScope, INSTALL_SCOPE};
+    use sha1::{Digest, Sha1};
+    use wiremock::matchers::path as url_path;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn sha1_hex(bytes: &[u8]) -> String {
+        format!("{:x}", Sha1::digest(bytes))
+    }
+
+    fn library_body(index: usize) -> Vec<u8> {
+        (0..64 * 1024)
+            .map(|i| ((i * 31 + index) % 251) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn same_data_dir_shares_one_coordinator() {
+        let tmp = tempfile::tempdir().unwrap();
+        let first = InstallCoordinator::for_data_dir(tmp.path()).unwrap();
+        let second = InstallCoordinator::for_data_dir(tmp.path()).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let (_guard, sole) = first.begin_install();
+        assert!(sole);
+        let (other, sole) = second.begin_install();
+        assert!(!sole);
+        assert_eq!(first.active_installs(), 2);
+        drop(other);
+        assert_eq!(first.active_installs(), 1);
+    }
+
+    #[tokio::test]
+    async fn path_locks_serialize_writers() {
+        let tmp = tempfile::tempdir().unwrap();
+        let coordinator = InstallCoordinator::for_data_dir(tmp.path()).unwrap();
+        let target = tmp.path().join("libraries/a.jar");
+
+        let held = coordinator.lock_path(&target).await;
+        let contender = {
+            let coordinator = coordinator.clone();
+            let target = target.clone();
+            tokio::spawn(async move {
+                let _guard = coordinator.lock_path(&target).await;
+            })
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(!contender.is_finished());
+
+        // A different path is never blocked.
+        let _other = coordinator.lock_path(&tmp.path().join("b.jar")).await;
+
+        drop(held);
+        contender.await.unwrap();
+    }
+
+    /// Two installs of the same version into different game dirs download the
+    /// same libraries concurrently against a mock CDN.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_installs_share_files_and_cache_records() {
+        const LIBRARY_COUNT: usize = 24;
+        let server = MockServer::start().await;
+        for index in 0..LIBRARY_COUNT {
+            Mock::given(url_path(format!("/lib-{index}.jar")))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_bytes(library_body(index))
+                        .set_delay(std::time::Duration::from_millis(5)),
+                )
+                .mount(&server)
+                .await;
+        }
+
+        let tmp = tempfile::tempdir().unwrap();
+        let data_dir = tmp.path().join("data");
+        std::fs::create_dir_all(&data_dir).unwrap();
+
+        let run_install = |install_id: &'static str| {
+            let data_dir = data_dir.clone();
+            let base_url = server.uri();
+            async move {
+                let coordinator = InstallCoordinator::for_data_dir(&data_dir).unwrap();
+                let artifacts = Arc::new(Mutex::new(Vec::<InstallArtifactRef>::new()));
+                let scope = InstallScope {
+                    coordinator: coordinator.clone(),
+                    artifacts: artifacts.clone(),
+                    dry_run: false,
+                    max_bytes: u64::MAX,
+                };
+                let libraries = data_dir.join("libraries");
+                INSTALL_SCOPE
+                    .scope(scope, async {
+                        let batch = (0..LIBRARY_COUNT)
+                            .map(|index| BatchArtifact {
+                                name: format!("lib-{index}"),
+                                urls: vec![format!("{}/lib-{index}.jar", base_url)],
+                                path: libraries.join(format!("lib-{index}.jar")),
+                                sha1: Some(sha1_hex(&library_body(index))),
+                                label: String::new(),
+                                size: Some(64 * 1024),
+                            })
+                            .collect::<Vec<_>>();
+                        BatchDownloader::new(crate::client::shared_client().clone(), 8)
+                            .download_all(batch, Arc::new(SilentProgressReporter), 0, 100.0)
+                            .await
+                            .unwrap();
+                        for index in 0..LIBRARY_COUNT {
+                            track_artifact_from_path(
+                                format!("libraries/lib-{index}.jar"),
+                                &libraries.join(format!("lib-{index}.jar")),
+                                None,
+                                None,
+                            )
+                            .await
+                            .unwrap();
+                        }
+                    })
+                    .await;
+
+                let refs = artifacts.lock().await.clone();
+                let mut cache = coordinator.cache().lock().await;
+                cache.record_install(install_id, None, &refs);
+                cache.save().unwrap();
+            }
+        };
+
+        tokio::join!(run_install("1.21.1-a"), run_install("1.21.1-b"));
+
+        for index in 0..LIBRARY_COUNT {
+            let bytes = std::fs::read(data_dir.join(format!("libraries/lib-{index}.jar"))).unwrap();
+            assert_eq!(bytes, library_body(index), "lib-{index} corrupted");
+        }
+        let leftovers: Vec<_> = std::fs::read_dir(data_dir.join("libraries"))
+            .unwrap()
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".part"))
+            .collect();
+        assert!(leftovers.is_empty());
+
+        let reloaded = ArtifactCache::load_with_labels(&data_dir).unwrap();
+        assert!(reloaded.has_install("1.21.1-a"));
+        assert!(reloaded.has_install("1.21.1-b"));
+        assert!(reloaded.find_component("libraries/lib-0.jar").is_some());
+    }
+}
@@ -4,9 +4,11 @@ use crate::game::installer::types::ProgressReporter;
 use anyhow::Result;
 use futures::stream::{self, StreamExt};
 use reqwest::Client;
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicI32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 
 pub struct BatchArtifact {
     pub name: String,
@@ -14,25 +16,246 @@ pub struct BatchArtifact {
     pub path: PathBuf,
     pub sha1: Option<String>,
     pub label: String,
+    /// Expected size in bytes, used to weight progress. Unknown sizes count as
+    /// the batch's average known size.
+    pub size: Option<u64>,
 }
 
 pub struct BatchDownloader {
     client: Client,
     concurrency: usize,
+    state: Arc<Mutex<DownloadState>>,
+}
+
+/// One file being downloaded by a batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InFlightDownload {
+    pub file_name: String,
+    pub bytes_done: u64,
+    pub bytes_total: Option<u64>,
+}
+
+/// Point-in-time view of a batch's queue.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DownloadQueueSnapshot {
+    pub total_files: usize,
+    pub completed_files: usize,
+    pub in_flight: Vec<InFlightDownload>,
+    pub queued_count: usize,
+}
+
+/// Live queue of a [`BatchDownloader`], updated as files start, progress and
+/// finish. In-flight entries are keyed by their position in the batch.
+#[derive(Debug, Default)]
+pub struct DownloadState {
+    total_files: usize,
+    completed_files: usize,
+    in_flight: BTreeMap<usize, InFlightDownload>,
+}
+
+impl DownloadState {
+    fn start_batch(&mut self, total_files: usize) {
+        *self = Self {
+            total_files,
+            ..Self::default()
+        };
+    }
+
+    fn start_file(&mut self, index: usize, file_name: &str, bytes_total: Option<u64>) {
+        self.in_flight.insert(
+            index,
+            InFlightDownload {
+                file_name: file_name.to_string(),
+                bytes_done: 0,
+                bytes_total,
+            },
+        );
+    }
+
+    fn update_file(&mut self, index: usize, bytes_done: u64, bytes_total: Option<u64>) {
+        if let Some(download) = self.in_flight.get_mut(&index) {
+            download.bytes_done = bytes_done;
+            if bytes_total.is_some() {
+                download.bytes_total = bytes_total;
+            }
+        }
+    }
+
+    fn finish_file(&mut self, index: usize, completed: bool) {
+        self.in_flight.remove(&index);
+        if completed {
+            self.completed_files += 1;
+        }
+    }
+
+    pub fn snapshot(&self) -> DownloadQueueSnapshot {
+        DownloadQueueSnapshot {
+            total_files: self.total_files,
+            completed_files: self.completed_files,
+            in_flight: self.in_flight.values().cloned().collect(),
+            queued_count: self
+                .total_files
+                .saturating_sub(self.completed_files + self.in_flight.len()),
+        }
+    }
+}
+
+fn lock_state(state: &Mutex<DownloadState>) -> std::sync::MutexGuard<'_, DownloadState> {
+    state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Queues of the asset batches currently running, across all installs.
+static ASSET_DOWNLOADS: Mutex<Vec<Arc<Mutex<DownloadState>>>> = Mutex::new(Vec::new());
+
+/// Keeps a batch's queue in [`asset_download_snapshot`] until dropped.
+pub struct AssetDownloadRegistration(Arc<Mutex<DownloadState>>);
+
+impl AssetDownloadRegistration {
+    pub fn new(batch: &BatchDownloader) -> Self {
+        let state = batch.state();
+        ASSET_DOWNLOADS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(state.clone());
+        Self(state)
+    }
+}
+
+impl Drop for AssetDownloadRegistration {
+    fn drop(&mut self) {
+        ASSET_DOWNLOADS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|state| !Arc::ptr_eq(state, &self.0));
+    }
+}
+
+/// Combined queue of every running asset batch; empty when none runs.
+pub fn asset_download_snapshot() -> DownloadQueueSnapshot {
+    let batches = ASSET_DOWNLOADS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut combined = DownloadQueueSnapshot::default();
+    for state in batches.iter() {
+        let snapshot = lock_state(state).snapshot();
+        combined.total_files += snapshot.total_files;
+        combined.completed_files += snapshot.completed_files;
+        combined.queued_count += snapshot.queued_count;
+        combined.in_flight.extend(snapshot.in_flight);
+    }
+    combined
+}
+
+/// Maps completed (and in-flight) bytes onto `base_progress..base_progress +
+/// progress_weight`, and forwards the batch's byte totals to the parent's
+/// `update_bytes` as files complete. The batch sets its own percent, so
+/// reporters must not turn those byte totals into a whole-task percent.
+struct BatchProgress {
+    reporter: Arc<dyn ProgressReporter>,
+    base_progress: i32,
+    progress_weight: f32,
+    total_bytes: u64,
+    done_bytes: AtomicU64,
+    last_percent: AtomicI32,
+}
+
+impl BatchProgress {
+    fn new(
+        reporter: Arc<dyn ProgressReporter>,
+        base_progress: i32,
+        progress_weight: f32,
+        total_bytes: u64,
+    ) -> Self {
+        Self {
+            reporter,
+            base_progress,
+            progress_weight,
+            total_bytes: total_bytes.max(1),
+            done_bytes: AtomicU64::new(0),
+            last_percent: AtomicI32::new(base_progress),
+        }
+    }
+
+    fn advance(&self, bytes: u64) {
+        if bytes == 0 {
+            return;
+        }
+        let done = self.done_bytes.fetch_add(bytes, Ordering::SeqCst) + bytes;
+        let fraction = done.min(self.total_bytes) as f64 / self.total_bytes as f64;
+        let percent = self.base_progress + (fraction * self.progress_weight as f64) as i32;
+        // Only ever move forward, even when concurrent downloads race.
+        if self.last_percent.fetch_max(percent, Ordering::SeqCst) < percent {
+            self.reporter.set_percent(percent);
+        }
+    }
+
+    /// Undo in-flight bytes of a download that restarted (e.g. a mirror retry).
+    fn rewind(&self, bytes: u64) {
+        self.done_bytes.fetch_sub(bytes, Ordering::SeqCst);
+    }
+
+    /// Forward `bytes_done` / `bytes_total` of the whole batch.
+    fn report_bytes(&self) {
+        let done = self.done_bytes.load(Ordering::SeqCst).min(self.total_bytes);
+        self.reporter.update_bytes(done, Some(self.total_bytes));
+    }
+}
+
+/// Resolve each artifact's progress weight: its size, or the average known
+/// size when unknown (all artifacts weigh 1 when no size is known).
+fn artifact_weights(artifacts: &[BatchArtifact]) -> Vec<u64> {
+    let known: Vec<u64> = artifacts
+        .iter()
+        .filter_map(|artifact| artifact.size.filter(|size| *size > 0))
+        .collect();
+    let fallback = if known.is_empty() {
+        1
+    } else {
+        (known.iter().sum::<u64>() / known.len() as u64).max(1)
+    };
+    artifacts
+        .iter()
+        .map(|artifact| artifact.size.filter(|size| *size > 0).unwrap_or(fallback))
+        .collect()
 }
 
 struct BatchFileProgressReporter {
     parent: Arc<dyn ProgressReporter>,
+    progress: Arc<BatchProgress>,
+    weight: u64,
+    counted: AtomicU64,
+    state: Arc<Mutex<DownloadState>>,
+    index: usize,
+}
+
+impl BatchFileProgressReporter {
+    /// Count whatever part of this artifact's weight the byte updates missed
+    /// (restored from cache, unknown size, or a short final chunk).
+    fn finish(&self) {
+        let counted = self.counted.swap(self.weight, Ordering::SeqCst);
+        self.progress.advance(self.weight.saturating_sub(counted));
+        self.progress.report_bytes();
+    }
 }
 
 impl ProgressReporter for BatchFileProgressReporter {
     fn start_step(&self, _name: &str, _total_steps: Option<u32>) {}
 
-    fn update_bytes(&self, _transferred: u64, _total: Option<u64>) {}
+    fn update_bytes(&self, transferred: u64, total: Option<u64>) {
+        lock_state(&self.state).update_file(self.index, transferred, total);
+        let target = transferred.min(self.weight);
+        let previous = self.counted.swap(target, Ordering::SeqCst);
+        if target > previous {
+            self.progress.advance(target - previous);
+        } else if previous > target {
+            self.progress.rewind(previous - target);
+        }
+    }
 
     fn set_percent(&self, _percent: i32) {}
 
-    fn set_message(&self, _message: &str) {}
+    /// Downloads only report problems such as a stalled connection here.
+    fn set_message(&self, message: &str) {
+        self.parent.set_message(message);
+    }
 
     fn set_step_count(&self, _current: u32, _total: Option<u32>) {}
 
@@ -64,9 +287,15 @@ impl BatchDownloader {
         Self {
             client,
             concurrency,
+            state: Arc::new(Mutex::new(DownloadState::default())),
         }
     }
 
+    /// Queue of the running (or last) `download_all` call.
+    pub fn state(&self) -> Arc<Mutex<DownloadState>> {
+        self.state.clone()
+    }
+
     pub async fn download_all(
         &self,
         artifacts: Vec<BatchArtifact>,
@@ -88,15 +317,32 @@ impl BatchDownloader {
             return Ok(());
         }
 
+        let weights = artifact_weights(&unique_artifacts);
+        let progress = Arc::new(BatchProgress::new(
+            reporter.clone(),
+            base_progress,
+            progress_weight,
+            weights.iter().sum(),
+        ));
         let downloaded = Arc::new(AtomicUsize::new(0));
         reporter.set_percent(base_progress);
         reporter.set_step_count(0, Some(total as u32));
+        lock_state(&self.state).start_batch(total);
 
-        stream::iter(unique_artifacts)
-            .map(|artifact| {
+        stream::iter(unique_artifacts.into_iter().zip(weights).enumerate())
+            .map(|(index, (artifact, weight))| {
                 let client = self.client.clone();
                 let reporter = reporter.clone();
                 let downloaded = downloaded.clone();
+                let state = self.state.clone();
+                let file_reporter = BatchFileProgressReporter {
+                    parent: reporter.clone(),
+                    progress: progress.clone(),
+                    weight,
+                    counted: AtomicU64::new(0),
+                    state: state.clone(),
+                    index,
+                };
 
                 async move {
                     // Check for cancellation/pause before starting
@@ -127,6 +373,7 @@ impl BatchDownloader {
                     }
 
                     if !restored {
+                        lock_state(&state).start_file(index, &artifact.name, artifact.size);
                         let mut success = false;
                         let mut last_err = None;
 
@@ -139,10 +386,6 @@ impl BatchDownloader {
                                 current,
                                 total
                             );
-                            let file_reporter = BatchFileProgressReporter {
-                                parent: reporter.clone(),
-                            };
-
                             match download_to_path(
                                 &client,
                                 url,
@@ -169,6 +412,7 @@ impl BatchDownloader {
                         }
 
                         if !success {
+                            lock_state(&state).finish_file(index, false);
                             return Err(last_err.unwrap_or_else(|| {
                                 anyhow::anyhow!("No download URLs provided for {}", artifact.name)
                             }));
@@ -176,11 +420,10 @@ impl BatchDownloader {
                     }
 
                     let count = downloaded.fetch_add(1, Ordering::SeqCst) + 1;
+                    lock_state(&state).finish_file(index, true);
 
                     // Update progress
-                    let progress =
-                        base_progress + ((count as f32 / total as f32) * progress_weight) as i32;
-                    reporter.set_percent(progress);
+                    file_reporter.finish();
                     reporter.set_step_count(count as u32, Some(total as u32));
 
                     if count % 10 == 0 || count == total {
@@ -188,7 +431,7 @@ impl BatchDownloader {
                             "Batch download progress: {}/{} ({}%)",
                             count,
                             total,
-                            progress
+                            file_reporter.progress.last_percent.load(Ordering::SeqCst)
                         );
                     }
 
@@ -204,3 +447,238 @@ impl BatchDownloader {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::installer::types::NotificationActionSpec;
+
+    #[derive(Default)]
+    struct PercentRecorder {
+        percents: Mutex<Vec<i32>>,
+        messages: Mutex<Vec<String>>,
+        bytes: Mutex<Vec<(u64, Option<u64>)>>,
+    }
+
+    impl ProgressReporter for PercentRecorder {
+        fn start_step(&self, _name: &str, _total_steps: Option<u32>) {}
+
+        fn update_bytes(&self, transferred: u64, total: Option<u64>) {
+            self.bytes.lock().unwrap().push((transferred, total));
+        }
+
+        fn set_percent(&self, percent: i32) {
+            self.percents.lock().unwrap().push(percent);
+        }
+
+        fn set_message(&self, message: &str) {
+            self.messages.lock().unwrap().push(message.to_string());
+        }
+
+        fn set_step_count(&self, _current: u32, _total: Option<u32>) {}
+        fn set_substep(&self, _name: Option<&str>, _current: Option<u32>, _total: Option<u32>) {}
+        fn set_actions(&self, _actions: Option<Vec<NotificationActionSpec>>) {}
+        fn done(&self, _success: bool, _message: Option<&str>) {}
+
+        fn is_cancelled(&self) -> bool {
+            false
+        }
+
+        fn is_paused(&self) -> bool {
+            false
+        }
+    }
+
+    fn artifact(name: &str, size: Option<u64>) -> BatchArtifact {
+        BatchArtifact {
+            name: name.to_string(),
+            urls: Vec::new(),
+            path: PathBuf::from(name),
+            sha1: None,
+            label: String::new(),
+            size,
+        }
+    }
+
+    fn file_reporter(
+        recorder: &Arc<PercentRecorder>,
+        progress: &Arc<BatchProgress>,
+        weight: u64,
+    ) -> BatchFileProgressReporter {
+        BatchFileProgressReporter {
+            parent: recorder.clone(),
+            progress: progress.clone(),
+            weight,
+            counted: AtomicU64::new(0),
+            state: Arc::new(Mutex::new(DownloadState::default())),
+            index: 0,
+        }
+    }
+
+    #[test]
+    fn progress_is_byte_proportional_and_monotonic() {
+        const MB: u64 = 1024 * 1024;
+        let mut artifacts: Vec<BatchArtifact> = (0..100)
+            .map(|i| artifact(&format!("small-{i}"), Some(1024)))
+            .collect();
+        artifacts.push(artifact("big", Some(100 * MB)));
+        let weights = artifact_weights(&artifacts);
+
+        let recorder = Arc::new(PercentRecorder::default());
+        let progress = Arc::new(BatchProgress::new(
+            recorder.clone(),
+            40,
+            20.0,
+            weights.iter().sum(),
+        ));
+
+        for weight in &weights[..100] {
+            let file = file_reporter(&recorder, &progress, *weight);
+            file.update_bytes(512, Some(1024));
+            file.update_bytes(1024, Some(1024));
+            file.finish();
+        }
+        // 100 KiB of ~100 MiB is ~0.1% of the span: still at the base.
+        assert!(recorder.percents.lock().unwrap().is_empty());
+
+        let big = file_reporter(&recorder, &progress, weights[100]);
+        for step in 1..=10 {
+            big.update_bytes(step * 10 * MB, Some(100 * MB));
+            let last = *recorder.percents.lock().unwrap().last().unwrap();
+            let expected = 40 + (20 * step as i32) / 10;
+            assert!(
+                (last - expected).abs() <= 1,
+                "step {step}: {last} vs {expected}"
+            );
+        }
+        big.finish();
+
+        let percents = recorder.percents.lock().unwrap().clone();
+        assert!(percents.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(percents.last(), Some(&60));
+
+        // One byte report per completed file, cumulative over the batch.
+        let total: u64 = weights.iter().sum();
+        let bytes = recorder.bytes.lock().unwrap().clone();
+        assert_eq!(bytes.len(), 101);
+        assert_eq!(bytes[0], (1024, Some(total)));
+        assert_eq!(bytes[99], (100 * 1024, Some(total)));
+        assert_eq!(bytes.last(), Some(&(total, Some(total))));
+    }
+
+    #[test]
+    fn retried_download_does_not_double_count() {
+        let recorder = Arc::new(PercentRecorder::default());
+        let progress = Arc::new(BatchProgress::new(recorder.clone(), 0, 100.0, 200));
+        let file = file_reporter(&recorder, &progress, 100);
+
+        file.update_bytes(80, Some(100));
+        // Mirror failed; next URL restarts from zero.
+        file.update_bytes(10, Some(100));
+        file.update_bytes(100, Some(100));
+        file.finish();
+
+        assert_eq!(progress.done_bytes.load(Ordering::SeqCst), 100);
+        assert_eq!(recorder.percents.lock().unwrap().as_slice(), [40, 50]);
+    }
+
+    #[test]
+    fn queue_snapshot_tracks_in_flight_files() {
+        let mut state = DownloadState::default();
+        state.start_batch(4);
+        state.start_file(0, "minecraft/sounds/a.ogg", Some(100));
+        state.start_file(2, "minecraft/lang/b.json", None);
+        state.update_file(2, 10, Some(40));
+        state.update_file(0, 60, None);
+
+        let snapshot = state.snapshot();
+        assert_eq!(snapshot.queued_count, 2);
+        assert_eq!(
+            snapshot.in_flight,
+            vec![
+                InFlightDownload {
+                    file_name: "minecraft/sounds/a.ogg".to_string(),
+                    bytes_done: 60,
+                    bytes_total: Some(100),
+                },
+                InFlightDownload {
+                    file_name: "minecraft/lang/b.json".to_string(),
+                    bytes_done: 10,
+                    bytes_total: Some(40),
+                },
+            ]
+        );
+
+        state.finish_file(0, true);
+        state.finish_file(2, false);
+        let snapshot = state.snapshot();
+        assert_eq!(snapshot.completed_files, 1);
+        assert!(snapshot.in_flight.is_empty());
+        assert_eq!(snapshot.queued_count, 3);
+    }
+
+    #[test]
+    fn unknown_sizes_fall_back_to_average_or_count() {
+        let mixed = [
+            artifact("a", Some(100)),
+            artifact("b", Some(300)),
+            artifact("c", None),
+        ];
+        assert_eq!(artifact_weights(&mixed), vec![100, 300, 200]);
+
+        let unknown = [artifact("a", None), artifact("b", Some(0))];
+        assert_eq!(artifact_weights(&unknown), vec![1, 1]);
+    }
+
+    #[tokio::test]
+    async fn stalled_connection_fails_instead_of_hanging() {
+        use crate::game::installer::core::downloader::{
+            download_stall_count, set_stall_timeout, stall_timeout,
+        };
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Sends the headers of a 1 KiB body, then nothing.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut request = [0u8; 1024];
+                    let _ = socket.read(&mut request).await;
+                    let _ = socket
+                        .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 1024\r\n\r\n")
+                        .await;
+                    tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                });
+            }
+        });
+
+        let previous_timeout = stall_timeout();
+        set_stall_timeout(std::time::Duration::from_millis(300));
+        let stalls_before = download_stall_count();
+        let tmp = tempfile::tempdir().unwrap();
+        let mut stalled = artifact("stalled.jar", Some(1024));
+        stalled.urls = vec![format!("http://{}/stalled.jar", addr)];
+        stalled.path = tmp.path().join("stalled.jar");
+        let recorder = Arc::new(PercentRecorder::default());
+
+        let started = std::time::Instant::now();
+        let result = BatchDownloader::new(Client::new(), 1)
+            .download_all(vec![stalled], recorder.clone(), 0, 100.0)
+            .await;
+        set_stall_timeout(previous_timeout);
+
+        // Three attempts of 0.3s plus 1s and 2s of backoff.
+        assert!(started.elapsed() < std::time::Duration::from_secs(10));
+        let error = result.unwrap_err();
+        assert!(format!("{:#}", error).contains("Connection stalled"));
+        assert!(download_stall_count() >= stalls_before + 3);
+        assert!(recorder
+            .messages
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|m| m == "Connection stalled, retrying stalled.jar"));
+        assert!(!tmp.path().join("stalled.jar").exists());
+    }
+}
@@ -1,8 +1,13 @@
+use crate::game::installer::core::traffic::{record_traffic, PendingTraffic, TrafficCategory};
 use crate::game::installer::types::ProgressReporter;
 use anyhow::{Context, Result};
 use reqwest::Client;
 use sha1::{Digest, Sha1};
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
 use tokio::fs::{create_dir_all, File};
 use tokio::io::AsyncWriteExt;
 // TODO: Re-add settings imports when centralized config is restored.
@@ -10,6 +15,101 @@ use std::time::Instant;
 
 // NOTE: Retry delay is a base value; we apply a simple linear backoff (delay * attempt).
 
+pub const DEFAULT_STALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+static STALL_TIMEOUT_MS: AtomicU64 = AtomicU64::new(DEFAULT_STALL_TIMEOUT.as_millis() as u64);
+static STALL_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Fresh clients for hosts whose connections stalled, keyed by host.
+static STALLED_HOST_CLIENTS: Mutex<Option<HashMap<String, Client>>> = Mutex::new(None);
+
+/// How long a download may go without receiving a byte before it is
+/// abandoned and retried.
+pub fn set_stall_timeout(timeout: Duration) {
+    STALL_TIMEOUT_MS.store(timeout.as_millis().max(1) as u64, Ordering::Relaxed);
+}
+
+pub fn stall_timeout() -> Duration {
+    Duration::from_millis(STALL_TIMEOUT_MS.load(Ordering::Relaxed))
+}
+
+/// Downloads abandoned as stalled since the process started.
+pub fn download_stall_count() -> u64 {
+    STALL_COUNT.load(Ordering::Relaxed)
+}
+
+/// A download received no bytes for [`stall_timeout`].
+#[derive(Debug)]
+pub struct DownloadStalled {
+    pub url: String,
+    pub after: Duration,
+}
+
+impl std::fmt::Display for DownloadStalled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Connection stalled: no data from {} for {}s",
+            self.url,
+            self.after.as_secs_f32()
+        )
+    }
+}
+
+impl std::error::Error for DownloadStalled {}
+
+fn url_host(url: &str) -> Option<String> {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_ascii_lowercase))
+}
+
+/// reqwest can't drop the pooled connections of a single host, so after a
+/// stall the host gets a client of its own with an empty pool. Idle
+/// connections to it in the old pool are never picked again.
+fn reset_host_connections(url: &str) {
+    let Some(host) = url_host(url) else {
+        return;
+    };
+    match crate::client::build_client_with_proxy(crate::client::configured_proxy_url().as_deref()) {
+        Ok(client) => {
+            let mut clients = STALLED_HOST_CLIENTS
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            clients
+                .get_or_insert_with(HashMap::new)
+                .insert(host, client);
+        }
+        Err(e) => log::warn!("Failed to build a fresh client for {}: {}", host, e),
+    }
+}
+
+/// Client to use for `url`: the replacement made after a stall, if any.
+fn client_for(url: &str, default: &Client) -> Client {
+    let replacement = url_host(url).and_then(|host| {
+        STALLED_HOST_CLIENTS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .and_then(|clients| clients.get(&host).cloned())
+    });
+    replacement.unwrap_or_else(|| default.clone())
+}
+
+/// Await `future`, failing with [`DownloadStalled`] after `timeout`.
+async fn within_stall_timeout<T>(
+    url: &str,
+    timeout: Duration,
+    future: impl std::future::Future<Output = T>,
+) -> Result<T> {
+    tokio::time::timeout(timeout, future).await.map_err(|_| {
+        anyhow::Error::new(DownloadStalled {
+            url: url.to_string(),
+            after: timeout,
+        })
+    })
+}
+
 /// Download a file to a path with progress reporting, SHA1 validation, and retry logic
 pub async fn download_to_path(
     client: &Client,
@@ -19,6 +119,7 @@ pub async fn download_to_path(
     reporter: &dyn ProgressReporter,
 ) -> Result<()> {
     log::debug!("Downloading: {} -> {:?}", url, path);
+    let path = &crate::utils::paths::long_path(path);
 
     if reporter.is_dry_run() {
         log::info!("[Dry-Run] Would download {} to {:?}", url, path);
@@ -26,6 +127,13 @@ pub async fn download_to_path(
         return Ok(());
     }
 
+    // Held until the file is in place so a concurrent install targeting the
+    // same path waits and then sees the finished file.
+    let _path_guard = match crate::game::installer::install_coordinator() {
+        Some(coordinator) => Some(coordinator.lock_path(path).await),
+        None => None,
+    };
+
     // Check if file exists
     if path.exists() {
         if let Some(expected) = expected_sha1 {
@@ -75,12 +183,22 @@ pub async fn download_to_path(
 
     let mut retries = 0;
     loop {
-        match download_with_validation(client, url, path, expected_sha1, reporter).await {
+        let client = client_for(url, client);
+        match download_with_validation(&client, url, path, expected_sha1, reporter).await {
             Ok(()) => {
                 log::debug!("Download complete: {:?}", path);
                 return Ok(());
             }
             Err(e) => {
+                if e.downcast_ref::<DownloadStalled>().is_some() {
+                    STALL_COUNT.fetch_add(1, Ordering::Relaxed);
+                    reset_host_connections(url);
+                    let file_name = path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| url.to_string());
+                    reporter.set_message(&format!("Connection stalled, retrying {}", file_name));
+                }
                 retries += 1;
                 if retries >= 3 {
                     // TODO: Restore config value
@@ -101,6 +219,17 @@ pub async fn download_to_path(
     }
 }
 
+/// Seconds left for a download at its average speed so far, or `None`
+/// before any bytes arrived.
+pub fn compute_eta(bytes_done: u64, bytes_total: u64, elapsed_secs: f64) -> Option<u64> {
+    if bytes_done == 0 || elapsed_secs <= 0.0 {
+        return None;
+    }
+    let speed = bytes_done as f64 / elapsed_secs;
+    let remaining = bytes_total.saturating_sub(bytes_done) as f64;
+    Some((remaining / speed).ceil() as u64)
+}
+
 async fn download_with_validation(
     client: &Client,
     url: &str,
@@ -109,7 +238,8 @@ async fn download_with_validation(
     reporter: &dyn ProgressReporter,
 ) -> Result<()> {
     let start = Instant::now();
-    let response = client.get(url).send().await?;
+    let stall_after = stall_timeout();
+    let response = within_stall_timeout(url, stall_after, client.get(url).send()).await??;
 
     if !response.status().is_success() {
         anyhow::bail!("HTTP error {}: {}", response.status(), url);
@@ -127,15 +257,21 @@ async fn download_with_validation(
             .unwrap_or("download")
     );
     let tmp_path = path.with_file_name(tmp_name);
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("download");
     let mut file = File::create(&tmp_path).await?;
     let mut downloaded: u64 = 0;
     let mut chunk_count: u64 = 0;
     let mut hasher = Sha1::new();
+    let mut traffic = PendingTraffic::new(TrafficCategory::for_path(path));
 
     let mut stream = response.bytes_stream();
     use futures::StreamExt;
 
-    while let Some(chunk_result) = stream.next().await {
+    // Dropping the stream on a stall closes its connection.
+    while let Some(chunk_result) = within_stall_timeout(url, stall_after, stream.next()).await? {
         if reporter.is_cancelled() {
             log::warn!("Download cancelled: {:?}", path);
             anyhow::bail!("Download cancelled by user");
@@ -150,12 +286,20 @@ async fn download_with_validation(
         }
 
         let chunk = chunk_result?;
+        traffic.bytes += chunk.len() as u64;
         file.write_all(&chunk).await?;
         hasher.update(&chunk);
 
         downloaded += chunk.len() as u64;
         chunk_count += 1;
-        reporter.update_bytes(downloaded, total_size);
+        let elapsed_secs = start.elapsed().as_secs_f64();
+        let speed_bps = if elapsed_secs > 0.0 {
+            (downloaded as f64 / elapsed_secs) as u64
+        } else {
+            0
+        };
+        let eta_secs = total_size.and_then(|total| compute_eta(downloaded, total, elapsed_secs));
+        reporter.update_download_progress(file_name, downloaded, total_size, speed_bps, eta_secs);
     }
     file.flush().await?;
     drop(file);
@@ -204,7 +348,14 @@ async fn download_with_validation(
 
 /// Download a file to memory and return the bytes
 pub async fn download_to_memory(url: &str, expected_sha1: Option<&str>) -> Result<Vec<u8>> {
-    download_to_memory_with_client(crate::client::shared_client(), url, expected_sha1, None).await
+    download_to_memory_with_client(
+        crate::client::shared_client(),
+        url,
+        expected_sha1,
+        None,
+        TrafficCategory::Other,
+    )
+    .await
 }
 
 /// Download a file to memory using an existing Client and return the bytes
@@ -213,10 +364,11 @@ pub async fn download_to_memory_with_client(
     url: &str,
     expected_sha1: Option<&str>,
     reporter: Option<&dyn ProgressReporter>,
+    category: TrafficCategory,
 ) -> Result<Vec<u8>> {
     let mut retries = 0;
     loop {
-        match download_to_memory_internal(client, url, expected_sha1, reporter).await {
+        match download_to_memory_internal(client, url, expected_sha1, reporter, category).await {
             Ok(bytes) => return Ok(bytes),
             Err(e) => {
                 retries += 1;
@@ -240,6 +392,7 @@ async fn download_to_memory_internal(
     url: &str,
     expected_sha1: Option<&str>,
     reporter: Option<&dyn ProgressReporter>,
+    category: TrafficCategory,
 ) -> Result<Vec<u8>> {
     log::debug!("Downloading to memory (reused client): {}", url);
 
@@ -253,6 +406,7 @@ async fn download_to_memory_internal(
     let mut bytes = Vec::with_capacity(total_size.unwrap_or(0) as usize);
     let mut downloaded: u64 = 0;
     let mut hasher = Sha1::new();
+    let mut traffic = PendingTraffic::new(category);
 
     let mut stream = response.bytes_stream();
     use futures::StreamExt;
@@ -272,6 +426,7 @@ async fn download_to_memory_internal(
         }
 
         let chunk = chunk_result?;
+        traffic.bytes += chunk.len() as u64;
         bytes.extend_from_slice(&chunk);
         hasher.update(&chunk);
 
@@ -315,8 +470,9 @@ pub async fn download_json_with_client<T: serde::de::DeserializeOwned>(
         anyhow::bail!("HTTP error {}: {}", response.status(), url);
     }
 
-    let data = response.json().await?;
-    Ok(data)
+    let body = response.bytes().await?;
+    record_traffic(TrafficCategory::Metadata, body.len() as u64);
+    Ok(serde_json::from_slice(&body)?)
 }
 
 /// Extract a zip archive to a directory (runs on blocking thread pool)
@@ -502,6 +658,82 @@ mod tests {
     use std::io::Write;
     use tempfile::tempdir;
 
+    #[test]
+    fn eta_follows_the_average_speed() {
+        assert_eq!(compute_eta(0, 1000, 1.0), None);
+        assert_eq!(compute_eta(100, 1000, 0.0), None);
+        // 100 B/s with 900 B left.
+        assert_eq!(compute_eta(100, 1000, 1.0), Some(9));
+        assert_eq!(compute_eta(300, 1000, 2.0), Some(5));
+        assert_eq!(compute_eta(1000, 1000, 4.0), Some(0));
+        // Servers can send more than they announced.
+        assert_eq!(compute_eta(1200, 1000, 4.0), Some(0));
+    }
+
+    struct DryRunReporter;
+
+    impl ProgressReporter for DryRunReporter {
+        fn start_step(&self, _name: &str, _total_steps: Option<u32>) {}
+        fn update_bytes(&self, _transferred: u64, _total: Option<u64>) {}
+        fn set_percent(&self, _percent: i32) {}
+        fn set_message(&self, _message: &str) {}
+        fn set_step_count(&self, _current: u32, _total: Option<u32>) {}
+        fn set_substep(&self, _name: Option<&str>, _current: Option<u32>, _total: Option<u32>) {}
+        fn set_actions(
+            &self,
+            _actions: Option<Vec<crate::game::installer::types::NotificationActionSpec>>,
+        ) {
+        }
+        fn done(&self, _success: bool, _message: Option<&str>) {}
+        fn is_cancelled(&self) -> bool {
+            false
+        }
+        fn is_paused(&self) -> bool {
+            false
+        }
+        fn is_dry_run(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn downloads_are_counted_except_in_dry_runs() {
+        use crate::game::installer::core::traffic::{with_task_traffic, TrafficCounter};
+        use std::sync::Arc;
+        use wiremock::matchers::path;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(path("/sodium.jar"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![7u8; 2048]))
+            .mount(&server)
+            .await;
+        let url = format!("{}/sodium.jar", server.uri());
+        let tmp = tempdir().unwrap();
+        let dest = tmp.path().join("mods").join("sodium.jar");
+        let client = Client::new();
+
+        let dry_run = Arc::new(TrafficCounter::default());
+        with_task_traffic(
+            dry_run.clone(),
+            download_to_path(&client, &url, &dest, None, &DryRunReporter),
+        )
+        .await
+        .unwrap();
+        assert_eq!(dry_run.total(), 0);
+        assert!(!dest.exists());
+
+        let real = Arc::new(TrafficCounter::default());
+        with_task_traffic(
+            real.clone(),
+            download_to_path(&client, &url, &dest, None, &NoopReporter {}),
+        )
+        .await
+        .unwrap();
+        assert_eq!(real.get(TrafficCategory::Mods), 2048);
+        assert_eq!(real.total(), 2048);
+    }
+
     #[tokio::test]
     async fn ensure_installed_client_copies_vanilla_jar() {
         let tmp = tempdir().unwrap();
@@ -525,9 +757,10 @@ mod tests {
             game_dir: tmp.path().join("game"),
             java_path: None,
             dry_run: false,
-            concurrency: 8,
+            concurrency: crate::game::installer::types::DownloadConcurrency::uniform(8),
             artifact_cache_max_bytes:
                 crate::game::installer::types::DEFAULT_ARTIFACT_CACHE_MAX_BYTES,
+            artifact_cache_compression: false,
             force_overwrite_configs: false,
             repair_scope: crate::game::installer::types::RepairScope::Full,
             remediation_policy: crate::game::installer::types::RemediationPolicy::RepairIfNeeded,
@@ -574,9 +807,10 @@ mod tests {
             game_dir: tmp.path().join("game"),
             java_path: None,
             dry_run: false,
-            concurrency: 8,
+            concurrency: crate::game::installer::types::DownloadConcurrency::uniform(8),
             artifact_cache_max_bytes:
                 crate::game::installer::types::DEFAULT_ARTIFACT_CACHE_MAX_BYTES,
+            artifact_cache_compression: false,
             force_overwrite_configs: false,
             repair_scope: crate::game::installer::types::RepairScope::Full,
             remediation_policy: crate::game::installer::types::RemediationPolicy::RepairIfNeeded,
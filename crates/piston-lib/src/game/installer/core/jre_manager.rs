@@ -162,6 +162,7 @@ async fn install_zulu_jre(
         &package.download_url,
         None,
         Some(reporter),
+        super::traffic::TrafficCategory::Jre,
     )
     .await?;
 
@@ -359,6 +360,17 @@ fn detect_system_java_from_path() -> Option<PathBuf> {
 
 /// Verify a Java path and return installation information
 pub fn verify_java(path: &Path) -> Result<DetectedJava> {
+    let version_str = run_java_version(path)?;
+    let inspection = inspect_java_version_output(&version_str)?;
+
+    Ok(DetectedJava {
+        path: path.to_path_buf(),
+        major_version: inspection.major_version,
+        is_64bit: inspection.is_64bit,
+    })
+}
+
+fn run_java_version(path: &Path) -> Result<String> {
     if !path.exists() {
         anyhow::bail!("Java path does not exist: {:?}", path);
     }
@@ -369,22 +381,164 @@ pub fn verify_java(path: &Path) -> Result<DetectedJava> {
         .output()
         .context("Failed to run java -version")?;
 
-    let version_str = String::from_utf8_lossy(&output.stderr);
-    let major_version = parse_major_version(&version_str).context(format!(
+    Ok(String::from_utf8_lossy(&output.stderr).into_owned())
+}
+
+/// What `java -version` says about an installation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JavaInspection {
+    pub major_version: u32,
+    /// Distribution name such as "Temurin" or "Zulu", when recognised.
+    pub vendor: Option<String>,
+    /// CPU architecture, when the output names one.
+    pub arch: Option<String>,
+    pub is_64bit: bool,
+}
+
+fn inspect_java_version_output(version_str: &str) -> Result<JavaInspection> {
+    let major_version = parse_major_version(version_str).context(format!(
         "Could not parse Java version from: {}",
         version_str
     ))?;
     let is_64bit = version_str.contains("64-Bit")
         || version_str.contains("x86_64")
-        || version_str.contains("amd64");
+        || version_str.contains("amd64")
+        || version_str.contains("aarch64");
 
-    Ok(DetectedJava {
-        path: path.to_path_buf(),
+    Ok(JavaInspection {
         major_version,
+        vendor: parse_vendor(version_str),
+        arch: parse_arch(version_str),
         is_64bit,
     })
 }
 
+fn parse_vendor(version_output: &str) -> Option<String> {
+    const KNOWN_VENDORS: &[(&str, &str)] = &[
+        ("Temurin", "Temurin"),
+        ("AdoptOpenJDK", "AdoptOpenJDK"),
+        ("Zulu", "Zulu"),
+        ("Corretto", "Corretto"),
+        ("GraalVM", "GraalVM"),
+        ("Microsoft", "Microsoft"),
+        ("BellSoft", "Liberica"),
+        ("Liberica", "Liberica"),
+        ("Semeru", "Semeru"),
+        ("OpenJ9", "Semeru"),
+        ("JBR", "JetBrains"),
+        ("JetBrains", "JetBrains"),
+        ("SapMachine", "SapMachine"),
+        ("Java(TM)", "Oracle"),
+    ];
+    KNOWN_VENDORS
+        .iter()
+        .find(|(needle, _)| version_output.contains(needle))
+        .map(|(_, vendor)| vendor.to_string())
+        .or_else(|| {
+            version_output
+                .trim_start()
+                .starts_with("openjdk")
+                .then(|| "OpenJDK".to_string())
+        })
+}
+
+fn parse_arch(version_output: &str) -> Option<String> {
+    if version_output.contains("aarch64") || version_output.contains("arm64") {
+        Some("aarch64".to_string())
+    } else if version_output.contains("x86_64") || version_output.contains("amd64") {
+        Some("x86_64".to_string())
+    } else if version_output.contains("i386") || version_output.contains("i686") {
+        Some("x86".to_string())
+    } else {
+        None
+    }
+}
+
+/// Why a working Java may still be a poor fit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JavaWarning {
+    /// The runtime is not the major version the game asks for.
+    MajorMismatch { found: u32, required: u32 },
+    /// 32-bit runtimes can't address more than ~1.5 GB of heap.
+    Bit32,
+}
+
+/// Result of [`validate_java_path`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JavaValidation {
+    pub path: PathBuf,
+    #[serde(flatten)]
+    pub inspection: JavaInspection,
+    pub warnings: Vec<JavaWarning>,
+}
+
+/// A 32-bit Java on a 64-bit system. Launching it fails or loads the wrong
+/// natives, so unlike [`JavaWarning`]s this can't be overridden.
+#[derive(Debug)]
+pub struct JavaArchMismatch {
+    pub path: PathBuf,
+}
+
+impl std::fmt::Display for JavaArchMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} is a 32-bit Java; this system needs a 64-bit one",
+            self.path.display()
+        )
+    }
+}
+
+impl std::error::Error for JavaArchMismatch {}
+
+/// Run `path -version` and check it against `required_major`.
+///
+/// Fails when `path` isn't a runnable Java, or with [`JavaArchMismatch`] for
+/// a 32-bit Java on a 64-bit system.
+pub fn validate_java_path(path: &Path, required_major: Option<u32>) -> Result<JavaValidation> {
+    let version_str = run_java_version(path)?;
+    let inspection = inspect_java_version_output(&version_str)?;
+    check_java(
+        path,
+        inspection,
+        required_major,
+        cfg!(target_pointer_width = "64"),
+    )
+}
+
+fn check_java(
+    path: &Path,
+    inspection: JavaInspection,
+    required_major: Option<u32>,
+    system_is_64bit: bool,
+) -> Result<JavaValidation> {
+    let mut warnings = Vec::new();
+    if !inspection.is_64bit {
+        if system_is_64bit {
+            return Err(JavaArchMismatch {
+                path: path.to_path_buf(),
+            }
+            .into());
+        }
+        warnings.push(JavaWarning::Bit32);
+    }
+    if let Some(required) = required_major {
+        if inspection.major_version != required {
+            warnings.push(JavaWarning::MajorMismatch {
+                found: inspection.major_version,
+                required,
+            });
+        }
+    }
+
+    Ok(JavaValidation {
+        path: path.to_path_buf(),
+        inspection,
+        warnings,
+    })
+}
+
 fn parse_major_version(version_output: &str) -> Option<u32> {
     use std::sync::OnceLock;
     static RE: OnceLock<regex::Regex> = OnceLock::new();
@@ -451,4 +605,80 @@ mod tests {
         let found_nested = find_java_executable(&nested_root).expect("should find nested java");
         assert_eq!(found_nested, nested_java);
     }
+
+    #[cfg(unix)]
+    const TEMURIN_17: &str = r#"openjdk version "17.0.8" 2023-07-18
+OpenJDK Runtime Environment Temurin-17.0.8+7 (build 17.0.8+7)
+OpenJDK 64-Bit Server VM Temurin-17.0.8+7 (build 17.0.8+7, mixed mode, sharing)"#;
+
+    const ORACLE_8_32BIT: &str = r#"java version "1.8.0_381"
+Java(TM) SE Runtime Environment (build 1.8.0_381-b09)
+Java HotSpot(TM) Client VM (build 25.381-b09, mixed mode)"#;
+
+    /// A `java` that prints `version_output` the way `java -version` does.
+    #[cfg(unix)]
+    fn fake_java(dir: &Path, version_output: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join("java");
+        let script = format!("#!/bin/sh\ncat >&2 <<'EOF'\n{}\nEOF\n", version_output);
+        fs::write(&path, script).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn validates_fake_java_against_required_major() {
+        let tmp = tempfile::tempdir().unwrap();
+        let java = fake_java(tmp.path(), TEMURIN_17);
+
+        let ok = validate_java_path(&java, Some(17)).unwrap();
+        assert_eq!(ok.inspection.major_version, 17);
+        assert_eq!(ok.inspection.vendor.as_deref(), Some("Temurin"));
+        assert!(ok.inspection.is_64bit);
+        assert!(ok.warnings.is_empty());
+
+        let mismatch = validate_java_path(&java, Some(21)).unwrap();
+        assert_eq!(
+            mismatch.warnings,
+            vec![JavaWarning::MajorMismatch {
+                found: 17,
+                required: 21
+            }]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_paths_that_are_not_java() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(validate_java_path(&tmp.path().join("missing"), None).is_err());
+
+        let not_java = fake_java(tmp.path(), "command not found");
+        let err = validate_java_path(&not_java, None).unwrap_err();
+        assert!(err.downcast_ref::<JavaArchMismatch>().is_none());
+    }
+
+    #[cfg(all(unix, target_pointer_width = "64"))]
+    #[test]
+    fn rejects_fake_32bit_java_on_64bit_system() {
+        let tmp = tempfile::tempdir().unwrap();
+        let java = fake_java(tmp.path(), ORACLE_8_32BIT);
+
+        let err = validate_java_path(&java, Some(8)).unwrap_err();
+        assert!(err.downcast_ref::<JavaArchMismatch>().is_some());
+        // Plain detection still lists it.
+        assert!(!verify_java(&java).unwrap().is_64bit);
+    }
+
+    #[test]
+    fn flags_32bit_java_on_32bit_system() {
+        let inspection = inspect_java_version_output(ORACLE_8_32BIT).unwrap();
+        assert_eq!(inspection.major_version, 8);
+        assert_eq!(inspection.vendor.as_deref(), Some("Oracle"));
+
+        let validation = check_java(Path::new("java"), inspection, Some(8), false).unwrap();
+        assert_eq!(validation.warnings, vec![JavaWarning::Bit32]);
+    }
 }
@@ -1,7 +1,9 @@
 use super::downloader::download_to_path;
+use crate::game::installer::modloaders::forge_mirrors;
 use crate::game::installer::types::ProgressReporter;
 use crate::game::installer::{track_artifact_from_path, try_restore_artifact};
-use anyhow::Result;
+use crate::utils::paths::join_validated;
+use anyhow::{Context, Result};
 use futures::stream::{self, StreamExt};
 use reqwest::Client;
 use std::path::Path;
@@ -74,6 +76,21 @@ mod tests {
         assert_eq!(resolved, artifact_url);
         assert!(!resolved.contains(".jar/net/minecraftforge/"));
     }
+
+    #[test]
+    fn test_library_paths_escaping_the_libraries_dir_are_refused() {
+        let libraries_dir = Path::new("/data/libraries");
+        let (path, _) =
+            LibraryDownloader::resolve_library_static("com.example:evil:../../../../tmp", None)
+                .unwrap();
+        assert!(join_validated(libraries_dir, &path).is_err());
+
+        let (path, _) =
+            LibraryDownloader::resolve_library_static("com.example:lib:1.0", None).unwrap();
+        assert!(join_validated(libraries_dir, &path)
+            .unwrap()
+            .starts_with(libraries_dir));
+    }
 }
 impl<'a> LibraryDownloader<'a> {
     pub fn new(
@@ -97,7 +114,8 @@ impl<'a> LibraryDownloader<'a> {
         sha1: Option<&str>,
     ) -> Result<()> {
         let (path, resolved_url) = self.resolve_library(name, maven_url)?;
-        let full_path = self.libraries_dir.join(&path);
+        let full_path = join_validated(self.libraries_dir, &path)
+            .with_context(|| format!("Refusing library path for {}", name))?;
         let label = format!("libraries/{}", path);
 
         if full_path.exists() {
@@ -134,9 +152,28 @@ impl<'a> LibraryDownloader<'a> {
         }
 
         let url = Self::resolve_explicit_url(explicit_url, maven_url, &resolved_url);
-        download_to_path(self.client, &url, &full_path, sha1, self.reporter.as_ref()).await?;
-        track_artifact_from_path(label, &full_path, None, Some(url)).await?;
-        Ok(())
+        let mut last_err = None;
+        for candidate in forge_mirrors::candidate_urls(&url) {
+            match download_to_path(
+                self.client,
+                &candidate,
+                &full_path,
+                sha1,
+                self.reporter.as_ref(),
+            )
+            .await
+            {
+                Ok(()) => {
+                    track_artifact_from_path(label, &full_path, None, Some(candidate)).await?;
+                    return Ok(());
+                }
+                Err(e) => {
+                    log::warn!("Library download from {} failed: {}", candidate, e);
+                    last_err = Some(e);
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| anyhow::anyhow!("No download URL for {}", name)))
     }
 
     /// Download multiple libraries concurrently
@@ -185,7 +222,8 @@ impl<'a> LibraryDownloader<'a> {
                     // Resolve library path and URL
                     let (path, resolved_url) =
                         Self::resolve_library_static(&lib.name, lib.maven_url.as_deref())?;
-                    let full_path = libraries_dir.join(&path);
+                    let full_path = join_validated(&libraries_dir, &path)
+                        .with_context(|| format!("Refusing library path for {}", lib.name))?;
                     let label = format!("libraries/{}", path);
 
                     // Check if exists and valid
@@ -285,7 +323,8 @@ impl<'a> LibraryDownloader<'a> {
                     }
 
                     // Download with exponential backoff retry.
-                    // Max 3 attempts with delays: 1s, 2s, 4s.
+                    // Max 3 attempts with delays: 1s, 2s, 4s. Forge maven
+                    // URLs rotate through the mirrors, active one first.
                     //
                     // TODO: Future enhancement — use HTTP Range requests for
                     // true resume support.  reqwest supports this via
@@ -293,11 +332,14 @@ impl<'a> LibraryDownloader<'a> {
                     // builder, combined with checking the local file size
                     // before retrying.
                     const MAX_RETRIES: u32 = 3;
+                    let candidates = forge_mirrors::candidate_urls(&url);
+                    let mut source_url = None;
                     let mut last_err: Option<anyhow::Error> = None;
                     for attempt in 0..MAX_RETRIES {
+                        let attempt_url = &candidates[attempt as usize % candidates.len()];
                         match download_to_path(
                             client,
-                            &url,
+                            attempt_url,
                             &full_path,
                             lib.sha1.as_deref(),
                             &NoopReporter {
@@ -307,6 +349,7 @@ impl<'a> LibraryDownloader<'a> {
                         .await
                         {
                             Ok(()) => {
+                                source_url = Some(attempt_url.clone());
                                 last_err = None;
                                 break;
                             }
@@ -346,7 +389,7 @@ impl<'a> LibraryDownloader<'a> {
                         return Err(e);
                     }
 
-                    track_artifact_from_path(label, &full_path, None, Some(url)).await?;
+                    track_artifact_from_path(label, &full_path, None, source_url).await?;
 
                     // Update progress
                     let count = downloaded.fetch_add(1, Ordering::SeqCst) + 1;
@@ -4,3 +4,4 @@ pub mod jre_manager;
 pub mod library;
 pub mod modpack_installer;
 pub mod pipeline;
+pub mod traffic;
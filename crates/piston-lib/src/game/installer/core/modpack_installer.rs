@@ -1,16 +1,19 @@
 use crate::game::installer::core::batch::{BatchArtifact, BatchDownloader};
 use crate::game::installer::install_instance;
 use crate::game::installer::types::{
-    InstallSpec, ModloaderType as InstallerModloaderType, ProgressReporter,
+    DownloadConcurrency, InstallSpec, ModloaderType as InstallerModloaderType, ProgressReporter,
 };
 use crate::game::modpack::manifest::ModSource;
 use crate::game::modpack::manifest::ModpackManifest;
 use crate::game::modpack::parser::{
-    extract_overrides_with_config_policy, get_modpack_metadata, hash_override_paths_from_zip,
+    extract_overrides_streaming, extract_overrides_with_config_policy, get_modpack_metadata,
+    hash_override_paths_from_zip, ConflictResolution,
 };
 use crate::game::modpack::types::ModpackMod;
 use anyhow::{Context, Result};
 use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use std::collections::HashSet;
 use std::path::Path;
 use std::sync::Arc;
 use tokio::task;
@@ -27,6 +30,13 @@ pub struct ModpackResolvedModrinth {
     pub sha1: Option<String>,
 }
 
+/// A CurseForge file the pack marks as not required.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OptionalModpackMod {
+    pub project_id: Option<u32>,
+    pub file_id: u32,
+}
+
 pub trait ModpackResolver: Send + Sync {
     /// Resolve a CurseForge mod to a download URL and filename
     fn resolve_curseforge(
@@ -44,6 +54,164 @@ pub trait ModpackResolver: Send + Sync {
     ) -> futures::future::BoxFuture<'static, Result<ModpackResolvedModrinth>> {
         Box::pin(async { Err(anyhow::anyhow!("Modrinth resolver not configured")) })
     }
+
+    /// Choose which optional mods of `pack_name` to install, returning their
+    /// file ids. Called before any mod is downloaded; the default keeps all.
+    fn select_optional_mods(
+        &self,
+        _pack_name: &str,
+        optional: Vec<OptionalModpackMod>,
+    ) -> futures::future::BoxFuture<'static, HashSet<u32>> {
+        let all = optional.iter().map(|m| m.file_id).collect();
+        Box::pin(async move { all })
+    }
+}
+
+/// Split pack mods into required ones (every Modrinth file included) and the
+/// CurseForge files marked optional.
+fn split_optional_mods(mods: Vec<ModpackMod>) -> (Vec<ModpackMod>, Vec<ModpackMod>) {
+    mods.into_iter().partition(|m| {
+        !matches!(
+            m,
+            ModpackMod::CurseForge {
+                required: false,
+                ..
+            }
+        )
+    })
+}
+
+fn optional_mod_ids(optional: &[ModpackMod]) -> Vec<OptionalModpackMod> {
+    optional
+        .iter()
+        .filter_map(|m| match m {
+            ModpackMod::CurseForge {
+                project_id,
+                file_id,
+                ..
+            } => Some(OptionalModpackMod {
+                project_id: *project_id,
+                file_id: *file_id,
+            }),
+            ModpackMod::Modrinth { .. } => None,
+        })
+        .collect()
+}
+
+/// Build download artifacts for `mods`, resolving CurseForge files through
+/// `resolver`. Files that fail to resolve are logged and skipped.
+async fn collect_mod_artifacts(
+    mods: &[ModpackMod],
+    game_dir: &Path,
+    resolver: &Option<Arc<dyn ModpackResolver>>,
+) -> Vec<BatchArtifact> {
+    let mut artifacts = Vec::new();
+    let mut curseforge_jobs: Vec<(Option<u32>, u32, Option<String>)> = Vec::new();
+    for mod_entry in mods {
+        match mod_entry {
+            ModpackMod::Modrinth {
+                ref path,
+                urls,
+                ref hashes,
+                size,
+            } => {
+                if !urls.is_empty() {
+                    let sha1 = hashes.get("sha1").cloned();
+                    let target_path = game_dir.join(path.replace("\\", "/"));
+                    artifacts.push(BatchArtifact {
+                        name: target_path
+                            .file_name()
+                            .and_then(|n| n.to_str())
+                            .unwrap_or("unknown")
+                            .to_string(),
+                        label: format!("mod-modrinth-{}", urls[0]),
+                        urls: urls.clone(),
+                        path: target_path,
+                        sha1,
+                        size: Some(*size),
+                    });
+                }
+            }
+            ModpackMod::CurseForge {
+                ref project_id,
+                file_id,
+                required: _,
+                ref hash,
+            } => {
+                curseforge_jobs.push((*project_id, *file_id, hash.clone()));
+            }
+        }
+    }
+
+    if curseforge_jobs.is_empty() {
+        return artifacts;
+    }
+    let Some(resolver) = resolver else {
+        log::warn!(
+            "Skipping {} CurseForge mods - no resolver provided",
+            curseforge_jobs.len()
+        );
+        return artifacts;
+    };
+
+    let resolved = stream::iter(curseforge_jobs)
+        .map(|(project_id, file_id, hash)| {
+            let resolver = resolver.clone();
+            async move {
+                let result = resolver.resolve_curseforge(project_id, file_id, hash).await;
+                (project_id, file_id, result)
+            }
+        })
+        .buffer_unordered(12)
+        .collect::<Vec<_>>()
+        .await;
+
+    for (project_id, file_id, result) in resolved {
+        match result {
+            Ok(resolved) => {
+                let target_path = game_dir.join(&resolved.subfolder).join(&resolved.filename);
+                let pid_str = project_id
+                    .map(|id| id.to_string())
+                    .unwrap_or_else(|| "unknown".to_string());
+                artifacts.push(BatchArtifact {
+                    name: target_path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("unknown")
+                        .to_string(),
+                    label: format!("mod-cf-{}-{}", pid_str, file_id),
+                    urls: vec![resolved.url],
+                    path: target_path,
+                    sha1: resolved.sha1,
+                    size: None,
+                });
+            }
+            Err(e) => {
+                log::error!(
+                    "Failed to resolve CurseForge mod {:?} {}: {}",
+                    project_id,
+                    file_id,
+                    e
+                );
+            }
+        }
+    }
+    artifacts
+}
+
+/// Settings for installing a modpack ZIP. The defaults detect Java, use the
+/// default download limits and overwrite conflicting overrides while keeping
+/// edited config files.
+#[derive(Debug, Clone, Default)]
+pub struct ModpackInstallOptions {
+    /// Java used by modloader installers, when already known.
+    pub java_path: Option<std::path::PathBuf>,
+    pub concurrency: DownloadConcurrency,
+    /// What override extraction does with files that already exist with
+    /// different contents.
+    pub conflict_resolution: ConflictResolution,
+    /// Extract config overrides even over the user's config files.
+    pub force_overwrite_configs: bool,
 }
 
 /// Installer for local ZIP modpacks
@@ -57,13 +225,13 @@ impl ModpackInstaller {
         data_dir: &Path,
         reporter: Arc<dyn ProgressReporter>,
         resolver: Option<Arc<dyn ModpackResolver>>,
-        java_path: Option<std::path::PathBuf>,
+        options: ModpackInstallOptions,
     ) -> Result<(
         crate::game::modpack::types::ModpackMetadata,
         Vec<std::path::PathBuf>,
     )> {
         Self::install_from_zip_with_metadata(
-            zip_path, None, game_dir, data_dir, reporter, resolver, java_path,
+            zip_path, None, game_dir, data_dir, reporter, resolver, options,
         )
         .await
     }
@@ -75,7 +243,7 @@ impl ModpackInstaller {
         data_dir: &Path,
         reporter: Arc<dyn ProgressReporter>,
         resolver: Option<Arc<dyn ModpackResolver>>,
-        java_path: Option<std::path::PathBuf>,
+        options: ModpackInstallOptions,
     ) -> Result<(
         crate::game::modpack::types::ModpackMetadata,
         Vec<std::path::PathBuf>,
@@ -83,7 +251,7 @@ impl ModpackInstaller {
         log::info!("Installing modpack from ZIP: {:?}", zip_path);
 
         // Step 1: Parse metadata
-        let metadata = if let Some(meta) = metadata {
+        let mut metadata = if let Some(meta) = metadata {
             log::info!("Using pre-interpreted modpack metadata for {}", meta.name);
             meta
         } else {
@@ -119,9 +287,10 @@ impl ModpackInstaller {
         spec.modloader = modloader;
         spec.modloader_version = metadata.modloader_version.clone();
         spec.dry_run = reporter.is_dry_run();
-        spec.java_path = java_path;
+        spec.java_path = options.java_path;
+        spec.concurrency = options.concurrency;
+        spec.force_overwrite_configs = options.force_overwrite_configs;
         spec.finalize_reporter = false;
-        let force_overwrite_configs = spec.force_overwrite_configs;
 
         // Step 3: Install base Minecraft + Modloader
         log::info!(
@@ -141,22 +310,38 @@ impl ModpackInstaller {
         let mut override_files = Vec::new();
         let mut skipped_configs: Vec<String> = Vec::new();
         if !reporter.is_dry_run() {
-            let zip_path = zip_path.to_path_buf();
-            let game_dir = game_dir.to_path_buf();
             let format = metadata.format;
             let root_prefix = metadata.root_prefix.clone();
-            let (extracted, skipped) = task::spawn_blocking(move || {
-                extract_overrides_with_config_policy(
-                    zip_path,
-                    game_dir,
-                    format,
-                    root_prefix,
-                    force_overwrite_configs,
-                )
-            })
-            .await
-            .context("override extraction worker panicked")?
-            .context("Failed to extract modpack overrides")?;
+            let (extracted, skipped) =
+                if options.conflict_resolution == ConflictResolution::Overwrite {
+                    extract_overrides_streaming(
+                        zip_path,
+                        game_dir,
+                        format,
+                        root_prefix,
+                        options.force_overwrite_configs,
+                        reporter.clone(),
+                    )
+                    .await
+                    .context("Failed to extract modpack overrides")?
+                } else {
+                    // Skip/Backup compare each conflicting file's contents first.
+                    let zip_path = zip_path.to_path_buf();
+                    let game_dir = game_dir.to_path_buf();
+                    task::spawn_blocking(move || {
+                        extract_overrides_with_config_policy(
+                            zip_path,
+                            game_dir,
+                            format,
+                            root_prefix,
+                            options.force_overwrite_configs,
+                            options.conflict_resolution,
+                        )
+                    })
+                    .await
+                    .context("override extraction worker panicked")?
+                    .context("Failed to extract modpack overrides")?
+                };
             override_files = extracted;
             skipped_configs = skipped;
             if !skipped_configs.is_empty() {
@@ -169,127 +354,82 @@ impl ModpackInstaller {
 
         // Step 5: Download additional mods
         if !metadata.mods.is_empty() {
-            reporter.start_step(
-                "Downloading modpack resources",
-                Some(metadata.mods.len() as u32),
-            );
-            let downloader = BatchDownloader::new(crate::client::shared_client().clone(), 8);
-
-            let mut artifacts = Vec::new();
-            let mut curseforge_jobs: Vec<(Option<u32>, u32, Option<String>)> = Vec::new();
-            for mod_entry in &metadata.mods {
-                match mod_entry {
-                    ModpackMod::Modrinth {
-                        ref path,
-                        urls,
-                        ref hashes,
-                        size: _,
-                    } => {
-                        if !urls.is_empty() {
-                            let sha1 = hashes.get("sha1").cloned();
-                            let target_path = game_dir.join(path.replace("\\", "/"));
-                            artifacts.push(BatchArtifact {
-                                name: target_path
-                                    .file_name()
-                                    .and_then(|n| n.to_str())
-                                    .unwrap_or("unknown")
-                                    .to_string(),
-                                label: format!("mod-modrinth-{}", urls[0]),
-                                urls: urls.clone(),
-                                path: target_path,
-                                sha1,
-                            });
-                        }
-                    }
-                    ModpackMod::CurseForge {
-                        ref project_id,
-                        file_id,
-                        required: _,
-                        ref hash,
-                    } => {
-                        curseforge_jobs.push((*project_id, *file_id, hash.clone()));
-                    }
-                }
-            }
-
-            if !curseforge_jobs.is_empty() {
+            let (required_mods, mut optional_mods) =
+                split_optional_mods(std::mem::take(&mut metadata.mods));
+            if !optional_mods.is_empty() && !reporter.is_dry_run() {
                 if let Some(resolver) = &resolver {
-                    let resolved = stream::iter(curseforge_jobs.into_iter())
-                        .map(|(project_id, file_id, hash)| {
-                            let resolver = resolver.clone();
-                            async move {
-                                let result =
-                                    resolver.resolve_curseforge(project_id, file_id, hash).await;
-                                (project_id, file_id, result)
-                            }
-                        })
-                        .buffer_unordered(12)
-                        .collect::<Vec<_>>()
+                    reporter.set_message("Waiting for optional mod selection...");
+                    let selected = resolver
+                        .select_optional_mods(&metadata.name, optional_mod_ids(&optional_mods))
                         .await;
-
-                    for (project_id, file_id, result) in resolved {
-                        match result {
-                            Ok(resolved) => {
-                                let target_path =
-                                    game_dir.join(&resolved.subfolder).join(&resolved.filename);
-                                let pid_str = project_id
-                                    .map(|id| id.to_string())
-                                    .unwrap_or_else(|| "unknown".to_string());
-                                artifacts.push(BatchArtifact {
-                                    name: target_path
-                                        .file_name()
-                                        .and_then(|n| n.to_str())
-                                        .unwrap_or("unknown")
-                                        .to_string(),
-                                    label: format!("mod-cf-{}-{}", pid_str, file_id),
-                                    urls: vec![resolved.url],
-                                    path: target_path,
-                                    sha1: resolved.sha1,
-                                });
-                            }
-                            Err(e) => {
-                                log::error!(
-                                    "Failed to resolve CurseForge mod {:?} {}: {}",
-                                    project_id,
-                                    file_id,
-                                    e
-                                );
-                            }
-                        }
-                    }
-                } else {
-                    log::warn!(
-                        "Skipping {} CurseForge mods - no resolver provided",
-                        curseforge_jobs.len()
+                    optional_mods.retain(|m| match m {
+                        ModpackMod::CurseForge { file_id, .. } => selected.contains(file_id),
+                        ModpackMod::Modrinth { .. } => true,
+                    });
+                    log::info!(
+                        "[ModpackInstaller] Installing {} of the pack's optional mods",
+                        optional_mods.len()
                     );
                 }
             }
+            // Skipped optional mods stay out of the persisted manifest so
+            // repair does not report them as missing.
+            metadata.mods = required_mods
+                .iter()
+                .chain(optional_mods.iter())
+                .cloned()
+                .collect();
+
+            reporter.start_step(
+                "Downloading modpack resources",
+                Some(metadata.mods.len() as u32),
+            );
+            let downloader = BatchDownloader::new(
+                crate::client::shared_client().clone(),
+                options.concurrency.mods,
+            );
+
+            let required_artifacts =
+                collect_mod_artifacts(&required_mods, game_dir, &resolver).await;
+            let optional_artifacts =
+                collect_mod_artifacts(&optional_mods, game_dir, &resolver).await;
 
             // Collect CurseForge artifact metadata before passing to download_all
             // (artifacts is moved into download_all, so we extract what we need first)
-            let cf_resolved: Vec<(String, String, Option<String>, Option<String>)> = artifacts
-                .iter()
-                .filter(|a| a.label.starts_with("mod-cf-"))
-                .map(|a| {
-                    let relative_path = a
-                        .path
-                        .strip_prefix(game_dir)
-                        .map(|p| p.to_string_lossy().replace('\\', "/"))
-                        .unwrap_or_default();
-                    (
-                        a.label.clone(),
-                        relative_path,
-                        a.sha1.clone(),
-                        a.urls.first().cloned(),
-                    )
-                })
-                .collect();
+            let cf_resolved: Vec<(String, String, Option<String>, Option<String>)> =
+                required_artifacts
+                    .iter()
+                    .chain(optional_artifacts.iter())
+                    .filter(|a| a.label.starts_with("mod-cf-"))
+                    .map(|a| {
+                        let relative_path = a
+                            .path
+                            .strip_prefix(game_dir)
+                            .map(|p| p.to_string_lossy().replace('\\', "/"))
+                            .unwrap_or_default();
+                        (
+                            a.label.clone(),
+                            relative_path,
+                            a.sha1.clone(),
+                            a.urls.first().cloned(),
+                        )
+                    })
+                    .collect();
 
-            if !artifacts.is_empty() {
-                downloader
-                    .download_all(artifacts, reporter.clone(), 0, 100.0)
-                    .await?;
-            }
+            // Required mods first, then the optional ones the user kept.
+            let total = (required_artifacts.len() + optional_artifacts.len()).max(1) as f32;
+            let required_weight = 100.0 * required_artifacts.len() as f32 / total;
+            downloader
+                .download_all(required_artifacts, reporter.clone(), 0, required_weight)
+                .await?;
+            downloader
+                .download_all(
+                    optional_artifacts,
+                    reporter.clone(),
+                    required_weight as i32,
+                    100.0 - required_weight,
+                )
+                .await?;
 
             // Step 6: Persist modpack manifest for future repair
             if !reporter.is_dry_run() {
@@ -412,6 +552,7 @@ impl ModpackInstaller {
         force_overwrite_configs: bool,
         reporter: Arc<dyn ProgressReporter>,
         resolver: Option<Arc<dyn ModpackResolver>>,
+        concurrency: DownloadConcurrency,
     ) -> Result<ModpackManifest> {
         let mut manifest = ModpackManifest::load(game_dir)
             .context("Failed to load modpack manifest for repair")?;
@@ -446,13 +587,15 @@ impl ModpackInstaller {
         // Step A: Re-extract overrides from ZIP for missing override files
         if !diff.overrides_to_fix.is_empty() {
             reporter.start_step("Re-extracting modpack overrides", None);
-            let (_extracted, skipped_configs) = extract_overrides_with_config_policy(
-                zip_path,
+            let (_extracted, skipped_configs) = extract_overrides_streaming(
+                &zip_path,
                 game_dir,
                 manifest.source,
                 None,
                 force_overwrite_configs,
+                reporter.clone(),
             )
+            .await
             .context("Failed to re-extract modpack overrides during repair")?;
 
             if !skipped_configs.is_empty() {
@@ -470,7 +613,8 @@ impl ModpackInstaller {
                 Some(diff.resources_to_fix.len() as u32),
             );
 
-            let downloader = BatchDownloader::new(crate::client::shared_client().clone(), 8);
+            let downloader =
+                BatchDownloader::new(crate::client::shared_client().clone(), concurrency.mods);
             let mut artifacts = Vec::new();
             let mut curseforge_jobs: Vec<(Option<u32>, u32, Option<String>)> = Vec::new();
 
@@ -495,6 +639,7 @@ impl ModpackInstaller {
                                 urls: vec![url.clone()],
                                 path: target_path,
                                 sha1: m.sha1.clone(),
+                                size: m.size,
                             });
                         }
                     }
@@ -527,21 +672,18 @@ impl ModpackInstaller {
                     for (project_id, file_id, result) in resolved {
                         match result {
                             Ok(resolved_cf) => {
-                                let manifest_path = diff
-                                    .resources_to_fix
-                                    .iter()
-                                    .find(|m| {
-                                        matches!(
-                                            &m.source,
-                                            ModSource::CurseForge {
-                                                project_id: pid,
-                                                file_id: fid,
-                                                ..
-                                            } if *pid == project_id && *fid == file_id
-                                        )
-                                    })
-                                    .map(|m| m.path.clone())
-                                    .unwrap_or_else(|| {
+                                let manifest_mod = diff.resources_to_fix.iter().find(|m| {
+                                    matches!(
+                                        &m.source,
+                                        ModSource::CurseForge {
+                                            project_id: pid,
+                                            file_id: fid,
+                                            ..
+                                        } if *pid == project_id && *fid == file_id
+                                    )
+                                });
+                                let manifest_path =
+                                    manifest_mod.map(|m| m.path.clone()).unwrap_or_else(|| {
                                         format!(
                                             "{}/{}",
                                             resolved_cf.subfolder, resolved_cf.filename
@@ -568,6 +710,7 @@ impl ModpackInstaller {
                                     urls: vec![resolved_cf.url],
                                     path: target_path,
                                     sha1: resolved_cf.sha1,
+                                    size: manifest_mod.and_then(|m| m.size),
                                 });
                             }
                             Err(e) => {
@@ -742,3 +885,58 @@ pub async fn enrich_platform_mod_hashes(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn cf(file_id: u32, required: bool) -> ModpackMod {
+        ModpackMod::CurseForge {
+            project_id: Some(file_id * 10),
+            file_id,
+            required,
+            hash: None,
+        }
+    }
+
+    #[test]
+    fn only_curseforge_files_marked_optional_are_split_out() {
+        let modrinth = ModpackMod::Modrinth {
+            path: "mods/a.jar".to_string(),
+            urls: vec!["https://cdn.modrinth.com/a.jar".to_string()],
+            hashes: HashMap::new(),
+            size: 1,
+        };
+        let (required, optional) = split_optional_mods(vec![cf(1, true), cf(2, false), modrinth]);
+        assert_eq!(required.len(), 2);
+        assert_eq!(
+            optional_mod_ids(&optional),
+            vec![OptionalModpackMod {
+                project_id: Some(20),
+                file_id: 2
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn default_selection_keeps_every_optional_mod() {
+        struct NoopResolver;
+        impl ModpackResolver for NoopResolver {
+            fn resolve_curseforge(
+                &self,
+                _project_id: Option<u32>,
+                _file_id: u32,
+                _hash: Option<String>,
+            ) -> futures::future::BoxFuture<'static, Result<ModpackResolvedCF>> {
+                Box::pin(async { Err(anyhow::anyhow!("unused")) })
+            }
+        }
+
+        let (_, optional) = split_optional_mods(vec![cf(3, false), cf(4, false)]);
+        let selected = NoopResolver
+            .select_optional_mods("Pack", optional_mod_ids(&optional))
+            .await;
+        assert_eq!(selected, HashSet::from([3, 4]));
+    }
+}
@@ -6,6 +6,7 @@ use crate::game::installer::core::library::{LibraryDownloader, LibrarySpec};
 use crate::game::installer::types::{InstallSpec, ProgressReporter};
 use crate::game::launcher::unified_manifest::{UnifiedLibrary, UnifiedManifest};
 use crate::game::runtime_plan::RuntimePlan;
+use crate::utils::paths::{join_validated, long_path};
 use anyhow::{Context, Result};
 use futures::stream::{self, StreamExt};
 use reqwest::Client;
@@ -24,29 +25,70 @@ pub async fn process_and_download_libraries(
 ) -> Result<UnifiedManifest> {
     let unified = plan.manifest.clone();
 
-    let total_libs = unified.libraries.len();
-    let native_libs: Vec<_> = unified.libraries.iter().filter(|l| l.is_native).collect();
-    let regular_libs: Vec<_> = unified.libraries.iter().filter(|l| !l.is_native).collect();
-
     log::info!(
-        "Processing {} libraries ({} regular, {} natives)",
-        total_libs,
-        regular_libs.len(),
-        native_libs.len()
+        "Processing {} libraries ({} natives)",
+        unified.libraries.len(),
+        unified.libraries.iter().filter(|l| l.is_native).count()
     );
 
-    // 2. Download regular (non-native) libraries concurrently
-    if !regular_libs.is_empty() {
-        let lib_dir = spec.libraries_dir();
-        let lib_downloader = LibraryDownloader::new(&client, &lib_dir, reporter.clone());
-        let library_specs = library_specs_from_regular_libraries(&regular_libs);
+    download_regular_libraries(spec, &unified, &client, reporter.clone()).await?;
+    download_and_extract_natives(spec, &unified, &client, reporter).await?;
+
+    Ok(unified)
+}
+
+/// Bytes of the files in `paths` that were missing before a download step.
+fn downloaded_bytes(missing: &[PathBuf]) -> u64 {
+    missing
+        .iter()
+        .map(|path| crate::game::installer::metrics::file_len(path))
+        .sum()
+}
+
+fn missing_library_paths(spec: &InstallSpec, libraries: &[&UnifiedLibrary]) -> Vec<PathBuf> {
+    libraries
+        .iter()
+        .map(|lib| spec.library_path(&lib.path))
+        .filter(|path| !path.exists())
+        .collect()
+}
 
-        lib_downloader
-            .download_libraries_concurrent(library_specs, spec.concurrency, 40, 40)
-            .await?;
+/// Download the non-native libraries of `unified` concurrently. Returns the
+/// number of bytes fetched (libraries already on disk count as 0).
+pub async fn download_regular_libraries(
+    spec: &InstallSpec,
+    unified: &UnifiedManifest,
+    client: &Client,
+    reporter: Arc<dyn ProgressReporter>,
+) -> Result<u64> {
+    let regular_libs: Vec<_> = unified.libraries.iter().filter(|l| !l.is_native).collect();
+    if regular_libs.is_empty() {
+        return Ok(0);
     }
 
-    // 3. Download and extract native libraries
+    let missing = missing_library_paths(spec, &regular_libs);
+    let lib_dir = spec.libraries_dir();
+    let lib_downloader = LibraryDownloader::new(client, &lib_dir, reporter);
+    let library_specs = library_specs_from_regular_libraries(&regular_libs);
+
+    lib_downloader
+        .download_libraries_concurrent(library_specs, spec.concurrency.libraries, 40, 40)
+        .await?;
+
+    Ok(downloaded_bytes(&missing))
+}
+
+/// Download the native library jars of `unified` and extract them into the
+/// natives directory. Returns the number of jar bytes fetched.
+pub async fn download_and_extract_natives(
+    spec: &InstallSpec,
+    unified: &UnifiedManifest,
+    client: &Client,
+    reporter: Arc<dyn ProgressReporter>,
+) -> Result<u64> {
+    let native_libs: Vec<_> = unified.libraries.iter().filter(|l| l.is_native).collect();
+    let missing = missing_library_paths(spec, &native_libs);
+
     if !native_libs.is_empty() {
         let natives_dir = spec.natives_dir();
         tokio::fs::create_dir_all(&natives_dir).await?;
@@ -75,7 +117,7 @@ pub async fn process_and_download_libraries(
 
         // Debug: log native specs to ensure download URLs and paths are present
         for (name, path, url, _sha) in &native_specs {
-            let full_path = spec.libraries_dir().join(path);
+            let full_path = spec.library_path(path);
             let exists = full_path.exists();
             log::info!(
                 "Native spec prepared: name={} path={} url={:?} exists={}",
@@ -99,7 +141,8 @@ pub async fn process_and_download_libraries(
                         return Err(anyhow::anyhow!("Installation cancelled by user"));
                     }
 
-                    let full_path = libraries_dir.join(&path);
+                    let full_path = join_validated(&libraries_dir, &path)
+                        .with_context(|| format!("Refusing native library path for {}", name))?;
 
                     // Download if not cached
                     if !full_path.exists() {
@@ -149,7 +192,7 @@ pub async fn process_and_download_libraries(
                     Ok::<(), anyhow::Error>(())
                 }
             })
-            .buffer_unordered(spec.concurrency)
+            .buffer_unordered(spec.concurrency.libraries)
             .collect::<Vec<_>>()
             .await
             .into_iter()
@@ -157,7 +200,7 @@ pub async fn process_and_download_libraries(
             .map_err(|e| e)?;
     }
 
-    Ok(unified)
+    Ok(downloaded_bytes(&missing))
 }
 
 fn library_specs_from_regular_libraries(libraries: &[&UnifiedLibrary]) -> Vec<LibrarySpec> {
@@ -208,7 +251,7 @@ fn extract_native_jar(zip_bytes: &[u8], dest: &PathBuf) -> Result<()> {
     let cursor = Cursor::new(zip_bytes);
     let mut archive = zip::ZipArchive::new(cursor).context("Failed to open native archive")?;
 
-    std::fs::create_dir_all(dest)?;
+    std::fs::create_dir_all(long_path(dest))?;
 
     for i in 0..archive.len() {
         let mut file = archive.by_index(i)?;
@@ -220,7 +263,10 @@ fn extract_native_jar(zip_bytes: &[u8], dest: &PathBuf) -> Result<()> {
         }
 
         if !file.is_dir() {
-            let outpath = dest.join(&file_name);
+            let outpath = long_path(&dest.join(&file_name));
+            if crate::utils::archive::file_matches(&outpath, file.size(), file.crc32()) {
+                continue;
+            }
 
             if let Some(p) = outpath.parent() {
                 std::fs::create_dir_all(p)?;
@@ -0,0 +1,214 @@
+//! Byte accounting for downloads.
+//!
+//! Every download records what it transferred under a [`TrafficCategory`].
+//! Bytes go to the counter of the task running the download (set with
+//! [`with_task_traffic`]) and to the process-wide sink installed by the app
+//! with [`set_traffic_sink`].
+
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::{Component, Path};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TrafficCategory {
+    Assets,
+    Libraries,
+    Mods,
+    Modpacks,
+    Jre,
+    Metadata,
+    Other,
+}
+
+impl TrafficCategory {
+    pub const ALL: [TrafficCategory; 7] = [
+        TrafficCategory::Assets,
+        TrafficCategory::Libraries,
+        TrafficCategory::Mods,
+        TrafficCategory::Modpacks,
+        TrafficCategory::Jre,
+        TrafficCategory::Metadata,
+        TrafficCategory::Other,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TrafficCategory::Assets => "assets",
+            TrafficCategory::Libraries => "libraries",
+            TrafficCategory::Mods => "mods",
+            TrafficCategory::Modpacks => "modpacks",
+            TrafficCategory::Jre => "jre",
+            TrafficCategory::Metadata => "metadata",
+            TrafficCategory::Other => "other",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.as_str() == value)
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Guess the category from where a download is written.
+    pub fn for_path(path: &Path) -> Self {
+        let components: Vec<String> = path
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(part) => Some(part.to_string_lossy().to_ascii_lowercase()),
+                _ => None,
+            })
+            .collect();
+        let has = |name: &str| components.iter().any(|c| c == name);
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+
+        if has("assets") {
+            TrafficCategory::Assets
+        } else if has("libraries") {
+            TrafficCategory::Libraries
+        } else if has("jre") || has("runtimes") {
+            TrafficCategory::Jre
+        } else if extension == "mrpack" || has("modpacks") {
+            TrafficCategory::Modpacks
+        } else if ["mods", "resourcepacks", "shaderpacks", "datapacks"]
+            .iter()
+            .any(|dir| has(dir))
+        {
+            TrafficCategory::Mods
+        } else if extension == "json" {
+            TrafficCategory::Metadata
+        } else {
+            TrafficCategory::Other
+        }
+    }
+}
+
+/// Bytes transferred per category.
+#[derive(Debug, Default)]
+pub struct TrafficCounter {
+    bytes: [AtomicU64; TrafficCategory::ALL.len()],
+}
+
+impl TrafficCounter {
+    pub fn add(&self, category: TrafficCategory, bytes: u64) {
+        self.bytes[category.index()].fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn get(&self, category: TrafficCategory) -> u64 {
+        self.bytes[category.index()].load(Ordering::Relaxed)
+    }
+
+    pub fn total(&self) -> u64 {
+        TrafficCategory::ALL.into_iter().map(|c| self.get(c)).sum()
+    }
+
+    /// Non-zero categories with their byte counts.
+    pub fn snapshot(&self) -> Vec<(TrafficCategory, u64)> {
+        TrafficCategory::ALL
+            .into_iter()
+            .map(|c| (c, self.get(c)))
+            .filter(|(_, bytes)| *bytes > 0)
+            .collect()
+    }
+}
+
+tokio::task_local! {
+    static TASK_TRAFFIC: Arc<TrafficCounter>;
+}
+
+type TrafficSink = Box<dyn Fn(TrafficCategory, u64) + Send + Sync>;
+
+static TRAFFIC_SINK: OnceLock<TrafficSink> = OnceLock::new();
+
+/// Install the process-wide sink. Only the first call takes effect.
+pub fn set_traffic_sink(sink: impl Fn(TrafficCategory, u64) + Send + Sync + 'static) -> bool {
+    TRAFFIC_SINK.set(Box::new(sink)).is_ok()
+}
+
+/// Run `fut` with downloads on this task attributed to `counter`.
+pub async fn with_task_traffic<F: Future>(counter: Arc<TrafficCounter>, fut: F) -> F::Output {
+    TASK_TRAFFIC.scope(counter, fut).await
+}
+
+/// Record `bytes` received under `category`.
+pub fn record_traffic(category: TrafficCategory, bytes: u64) {
+    if bytes == 0 {
+        return;
+    }
+    let _ = TASK_TRAFFIC.try_with(|counter| counter.add(category, bytes));
+    if let Some(sink) = TRAFFIC_SINK.get() {
+        sink(category, bytes);
+    }
+}
+
+/// Records the bytes of one transfer when dropped, so aborted and failed
+/// attempts are still counted.
+pub(crate) struct PendingTraffic {
+    category: TrafficCategory,
+    pub(crate) bytes: u64,
+}
+
+impl PendingTraffic {
+    pub(crate) fn new(category: TrafficCategory) -> Self {
+        Self { category, bytes: 0 }
+    }
+}
+
+impl Drop for PendingTraffic {
+    fn drop(&mut self) {
+        record_traffic(self.category, self.bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn categorizes_by_destination() {
+        let cases = [
+            ("data/assets/objects/ab/abcd", TrafficCategory::Assets),
+            (
+                "data/libraries/org/lwjgl/lwjgl.jar",
+                TrafficCategory::Libraries,
+            ),
+            ("data/jre/zulu-21/bin/java", TrafficCategory::Jre),
+            ("instances/a/mods/sodium.jar", TrafficCategory::Mods),
+            ("downloads/pack.mrpack", TrafficCategory::Modpacks),
+            ("data/versions/1.21/1.21.json", TrafficCategory::Metadata),
+            ("data/versions/1.21/1.21.jar", TrafficCategory::Other),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(
+                TrafficCategory::for_path(Path::new(path)),
+                expected,
+                "{path}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn attributes_bytes_to_the_scoped_task_only() {
+        let counter = Arc::new(TrafficCounter::default());
+        with_task_traffic(counter.clone(), async {
+            record_traffic(TrafficCategory::Assets, 10);
+            record_traffic(TrafficCategory::Mods, 5);
+        })
+        .await;
+        record_traffic(TrafficCategory::Assets, 100);
+
+        assert_eq!(counter.get(TrafficCategory::Assets), 10);
+        assert_eq!(counter.total(), 15);
+        assert_eq!(
+            counter.snapshot(),
+            vec![(TrafficCategory::Assets, 10), (TrafficCategory::Mods, 5)]
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! Per-step wall time and byte counts for one install run.
+//!
+//! Every run reports the same flat set of keys (`<step>_ms`, `<step>_bytes`,
+//! `total_ms` and `download_stalls`) through
+//! [`ProgressReporter::record_metric`], with zeros for steps that were
+//! skipped, so runs can be compared key by key.
+//! Byte counts cover what the step actually downloaded; files that were
+//! already present or restored from the artifact cache count as 0.
+
+use crate::game::installer::core::downloader::download_stall_count;
+use crate::game::installer::types::ProgressReporter;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+pub const STEP_MANIFEST_FETCH: &str = "manifest_fetch";
+pub const STEP_CLIENT_JAR: &str = "client_jar";
+pub const STEP_ASSETS: &str = "assets";
+pub const STEP_LIBRARIES: &str = "libraries";
+pub const STEP_NATIVES: &str = "natives";
+pub const STEP_JRE: &str = "jre";
+pub const STEP_LOADER_PROCESSING: &str = "loader_processing";
+
+/// Steps in the order `install_instance` runs them.
+pub const INSTALL_STEPS: [&str; 7] = [
+    STEP_MANIFEST_FETCH,
+    STEP_CLIENT_JAR,
+    STEP_ASSETS,
+    STEP_LIBRARIES,
+    STEP_NATIVES,
+    STEP_JRE,
+    STEP_LOADER_PROCESSING,
+];
+
+pub const TOTAL_MS_KEY: &str = "total_ms";
+/// Downloads abandoned and retried because no data arrived, counted over
+/// the whole run (including concurrent installs).
+pub const DOWNLOAD_STALLS_KEY: &str = "download_stalls";
+
+pub fn elapsed_key(step: &str) -> String {
+    format!("{}_ms", step)
+}
+
+pub fn bytes_key(step: &str) -> String {
+    format!("{}_bytes", step)
+}
+
+/// Every key a finished run reports.
+pub fn expected_keys() -> Vec<String> {
+    let mut keys: Vec<String> = INSTALL_STEPS
+        .iter()
+        .flat_map(|step| [elapsed_key(step), bytes_key(step)])
+        .collect();
+    keys.push(TOTAL_MS_KEY.to_string());
+    keys.push(DOWNLOAD_STALLS_KEY.to_string());
+    keys
+}
+
+/// Metric values of one install run, keyed as described in the module docs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct InstallMetrics {
+    values: BTreeMap<String, u64>,
+}
+
+impl InstallMetrics {
+    pub fn record(&mut self, key: &str, value: u64) {
+        self.values.insert(key.to_string(), value);
+    }
+
+    pub fn get(&self, key: &str) -> Option<u64> {
+        self.values.get(key).copied()
+    }
+
+    pub fn values(&self) -> &BTreeMap<String, u64> {
+        &self.values
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// One-line summary, e.g. `total=5123ms assets=2100ms/48.2MiB ...`.
+    pub fn summary(&self) -> String {
+        let mut parts = vec![format!("total={}ms", self.get(TOTAL_MS_KEY).unwrap_or(0))];
+        for step in INSTALL_STEPS {
+            let elapsed = self.get(&elapsed_key(step)).unwrap_or(0);
+            let bytes = self.get(&bytes_key(step)).unwrap_or(0);
+            parts.push(format!("{}={}ms/{}", step, elapsed, format_bytes(bytes)));
+        }
+        parts.push(format!(
+            "stalls={}",
+            self.get(DOWNLOAD_STALLS_KEY).unwrap_or(0)
+        ));
+        parts.join(" ")
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    const MIB: f64 = KIB * 1024.0;
+    let value = bytes as f64;
+    if value >= MIB {
+        format!("{:.1}MiB", value / MIB)
+    } else if value >= KIB {
+        format!("{:.1}KiB", value / KIB)
+    } else {
+        format!("{}B", bytes)
+    }
+}
+
+/// Collects step timings during an install and reports them once it ends.
+pub(crate) struct StepRecorder {
+    started: Instant,
+    stalls_at_start: u64,
+    metrics: InstallMetrics,
+}
+
+impl StepRecorder {
+    pub(crate) fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            stalls_at_start: download_stall_count(),
+            metrics: InstallMetrics::default(),
+        }
+    }
+
+    /// Add `elapsed` and `bytes` to `step`. Steps can be recorded in parts.
+    pub(crate) fn record(&mut self, step: &str, elapsed: Duration, bytes: u64) {
+        let elapsed_key = elapsed_key(step);
+        let bytes_key = bytes_key(step);
+        let elapsed_ms = self.metrics.get(&elapsed_key).unwrap_or(0) + elapsed.as_millis() as u64;
+        let total_bytes = self.metrics.get(&bytes_key).unwrap_or(0) + bytes;
+        self.metrics.record(&elapsed_key, elapsed_ms);
+        self.metrics.record(&bytes_key, total_bytes);
+    }
+
+    /// Fill in skipped steps, forward every key to the reporter and log the
+    /// summary line.
+    pub(crate) fn finish(mut self, reporter: &dyn ProgressReporter) -> InstallMetrics {
+        for step in INSTALL_STEPS {
+            self.record(step, Duration::ZERO, 0);
+        }
+        self.metrics
+            .record(TOTAL_MS_KEY, self.started.elapsed().as_millis() as u64);
+        self.metrics.record(
+            DOWNLOAD_STALLS_KEY,
+            download_stall_count().saturating_sub(self.stalls_at_start),
+        );
+        for (key, value) in self.metrics.values() {
+            reporter.record_metric(key, *value);
+        }
+        log::info!("[installer] metrics {}", self.metrics.summary());
+        self.metrics
+    }
+}
+
+/// Size of a file, or 0 if it doesn't exist.
+pub(crate) fn file_len(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+/// Total size of the files below `path`.
+pub(crate) fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(kind) if kind.is_dir() => dir_size(&entry.path()),
+            Ok(_) => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::installer::types::SilentProgressReporter;
+
+    #[test]
+    fn recorder_accumulates_and_fills_skipped_steps() {
+        let mut recorder = StepRecorder::new();
+        recorder.record(STEP_ASSETS, Duration::from_millis(40), 1024);
+        recorder.record(STEP_ASSETS, Duration::from_millis(2), 3 * 1024 * 1024);
+        let metrics = recorder.finish(&SilentProgressReporter);
+
+        assert_eq!(metrics.get("assets_ms"), Some(42));
+        assert_eq!(metrics.get("assets_bytes"), Some(1024 + 3 * 1024 * 1024));
+        assert_eq!(metrics.get("jre_ms"), Some(0));
+        let keys: Vec<&String> = metrics.values().keys().collect();
+        let mut expected = expected_keys();
+        expected.sort();
+        assert_eq!(keys, expected.iter().collect::<Vec<_>>());
+        assert!(metrics.summary().contains("assets=42ms/3.0MiB"));
+    }
+
+    #[test]
+    fn metrics_serialize_as_a_flat_object() {
+        let mut metrics = InstallMetrics::default();
+        metrics.record("total_ms", 12);
+        metrics.record("jre_bytes", 0);
+        let json = serde_json::to_value(&metrics).unwrap();
+        assert_eq!(json, serde_json::json!({ "jre_bytes": 0, "total_ms": 12 }));
+        let back: InstallMetrics = serde_json::from_value(json).unwrap();
+        assert_eq!(back, metrics);
+    }
+}
@@ -1,33 +1,53 @@
+pub mod asset_delta;
+pub mod asset_integrity;
+pub mod asset_mirrors;
 pub mod cache;
 pub mod config;
+pub mod coordinator;
 pub mod core;
+pub mod metrics;
 pub mod modloaders;
+pub mod preload;
 pub mod types;
 pub mod verifier;
 
 use anyhow::{Context, Result};
 use types::{InstallSpec, ModloaderType, ProgressReporter};
 
-use crate::game::installer::core::batch::{BatchArtifact, BatchDownloader};
+use crate::game::installer::core::batch::{
+    AssetDownloadRegistration, BatchArtifact, BatchDownloader,
+};
 use crate::game::installer::core::downloader::download_to_path;
 use crate::game::installer::core::jre_manager::{get_or_install_jre, JavaVersion};
-use crate::game::installer::core::pipeline::process_and_download_libraries;
+use crate::game::installer::core::pipeline::{
+    download_and_extract_natives, download_regular_libraries,
+};
 use cache::{ArtifactCache, InstallArtifactRef};
-use std::path::Path;
+use coordinator::InstallCoordinator;
+use metrics::{file_len, StepRecorder};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::Mutex;
 use verifier::verify_instance_readiness;
 
 tokio::task_local! {
-    static INSTALL_SCOPE: InstallScope;
+    pub(crate) static INSTALL_SCOPE: InstallScope;
 }
 
-struct InstallScope {
-    cache: Arc<Mutex<ArtifactCache>>,
-    artifacts: Arc<Mutex<Vec<InstallArtifactRef>>>,
-    dry_run: bool,
-    max_bytes: u64,
+pub(crate) struct InstallScope {
+    pub(crate) coordinator: Arc<InstallCoordinator>,
+    pub(crate) artifacts: Arc<Mutex<Vec<InstallArtifactRef>>>,
+    pub(crate) dry_run: bool,
+    pub(crate) max_bytes: u64,
+}
+
+/// Coordinator of the install running on this task, if any.
+pub(crate) fn install_coordinator() -> Option<Arc<InstallCoordinator>> {
+    INSTALL_SCOPE
+        .try_with(|scope| scope.coordinator.clone())
+        .ok()
 }
 
 pub(crate) fn install_scope_handles() -> Option<(
@@ -39,7 +59,7 @@ pub(crate) fn install_scope_handles() -> Option<(
     INSTALL_SCOPE
         .try_with(|scope| {
             (
-                scope.cache.clone(),
+                scope.coordinator.cache().clone(),
                 scope.artifacts.clone(),
                 scope.dry_run,
                 scope.max_bytes,
@@ -86,7 +106,14 @@ pub(crate) async fn try_restore_artifact(label: &str, destination: &Path) -> Res
             return Ok(true);
         }
 
-        if ArtifactCache::restore_blob_to_path(&candidate.blob_path, destination)? {
+        // Another install may be downloading or restoring the same file.
+        let _path_guard = match install_coordinator() {
+            Some(coordinator) => Some(coordinator.lock_path(destination).await),
+            None => None,
+        };
+        if ArtifactCache::restore_blob_to_path_async(candidate.blob_path, destination.to_path_buf())
+            .await?
+        {
             let mut artifacts_guard = artifacts.lock().await;
             artifacts_guard.push(InstallArtifactRef::new(label.to_string(), candidate.sha256));
             return Ok(true);
@@ -106,30 +133,40 @@ pub async fn install_instance(
     }
 
     let cache_open_start = Instant::now();
-    let mut cache = ArtifactCache::load_with_labels(spec.data_dir())?;
+    let coordinator = InstallCoordinator::for_data_dir(spec.data_dir())?;
     let cache_load_elapsed = cache_open_start.elapsed();
+    let (_active_install, sole_install) = coordinator.begin_install();
+    let cache = Arc::clone(coordinator.cache());
+    cache
+        .lock()
+        .await
+        .set_compression_enabled(spec.artifact_cache_compression);
 
-    let startup_prune_start = Instant::now();
-    let startup_prune = cache.prune_to_limit(spec.artifact_cache_max_bytes);
-    let startup_prune_elapsed = startup_prune_start.elapsed();
-    if startup_prune.removed_artifacts > 0 {
-        log::info!(
-            "[installer] Pruned {} cached artifacts ({} bytes) while opening cache",
-            startup_prune.removed_artifacts,
-            startup_prune.removed_bytes
+    // Pruning while another install is running could evict blobs it has
+    // tracked but not yet recorded, so only the sole install prunes.
+    if sole_install {
+        let mut cache_guard = cache.lock().await;
+        let startup_prune_start = Instant::now();
+        let startup_prune = cache_guard.prune_to_limit(spec.artifact_cache_max_bytes);
+        let startup_prune_elapsed = startup_prune_start.elapsed();
+        if startup_prune.removed_artifacts > 0 {
+            log::info!(
+                "[installer] Pruned {} cached artifacts ({} bytes) while opening cache",
+                startup_prune.removed_artifacts,
+                startup_prune.removed_bytes
+            );
+        }
+        cache_guard.save()?;
+        log::debug!(
+            "[installer] cache startup load_ms={} prune_ms={}",
+            cache_load_elapsed.as_millis(),
+            startup_prune_elapsed.as_millis()
         );
     }
-    cache.save()?;
-    log::debug!(
-        "[installer] cache startup load_ms={} prune_ms={}",
-        cache_load_elapsed.as_millis(),
-        startup_prune_elapsed.as_millis()
-    );
 
-    let cache = Arc::new(Mutex::new(cache));
     let artifacts = Arc::new(Mutex::new(Vec::new()));
     let scope = InstallScope {
-        cache: Arc::clone(&cache),
+        coordinator: Arc::clone(&coordinator),
         artifacts: Arc::clone(&artifacts),
         dry_run: false,
         max_bytes: spec.artifact_cache_max_bytes,
@@ -149,26 +186,37 @@ pub async fn install_instance(
         let mut cache_guard = cache.lock().await;
         cache_guard.remove_install(&installed_id);
         cache_guard.record_install(&installed_id, loader, &tracked_artifacts);
-        let finalize_prune_start = Instant::now();
-        let finalize_prune = cache_guard.prune_to_limit(spec.artifact_cache_max_bytes);
-        let finalize_prune_elapsed = finalize_prune_start.elapsed();
-        cache_guard.save()?;
-        if finalize_prune.removed_artifacts > 0 {
-            log::info!(
-                "[installer] Pruned {} cached artifacts ({} bytes) after installation finalization",
-                finalize_prune.removed_artifacts,
-                finalize_prune.removed_bytes
+        if coordinator.active_installs() == 1 {
+            let finalize_prune_start = Instant::now();
+            let finalize_prune = cache_guard.prune_to_limit(spec.artifact_cache_max_bytes);
+            let finalize_prune_elapsed = finalize_prune_start.elapsed();
+            if finalize_prune.removed_artifacts > 0 {
+                log::info!(
+                    "[installer] Pruned {} cached artifacts ({} bytes) after installation finalization",
+                    finalize_prune.removed_artifacts,
+                    finalize_prune.removed_bytes
+                );
+            }
+            log::debug!(
+                "[installer] cache finalize prune_ms={}",
+                finalize_prune_elapsed.as_millis()
             );
         }
-        log::debug!(
-            "[installer] cache finalize prune_ms={}",
-            finalize_prune_elapsed.as_millis()
-        );
+        cache_guard.save()?;
     }
 
     result
 }
 
+/// Path of the object with `sha1` in the shared asset store, if present.
+/// Objects are content-addressed, so this is a single lookup regardless of
+/// which version's index first downloaded it.
+pub(crate) fn find_in_object_store(assets_dir: &Path, sha1: &str) -> Option<PathBuf> {
+    let prefix = sha1.get(..2)?;
+    let path = assets_dir.join("objects").join(prefix).join(sha1);
+    path.is_file().then_some(path)
+}
+
 fn collect_missing_asset_downloads(
     objects: &serde_json::Map<String, serde_json::Value>,
     assets_dir: &Path,
@@ -181,6 +229,8 @@ fn collect_missing_asset_downloads(
     reporter.set_step_count(0, Some(total as u32));
 
     let mut assets_to_download = Vec::new();
+    // Several names can share one object; queue each hash once.
+    let mut queued = HashSet::new();
 
     for (index, (asset_name, asset_obj)) in objects.iter().enumerate() {
         let hash = asset_obj
@@ -199,19 +249,14 @@ fn collect_missing_asset_downloads(
                 asset_name
             )
         })?;
-        let asset_url = format!(
-            "https://resources.download.minecraft.net/{}/{}",
-            hash_prefix, hash
-        );
-        let asset_path = assets_dir.join("objects").join(hash_prefix).join(hash);
-
-        if !asset_path.exists() {
+        if find_in_object_store(assets_dir, hash).is_none() && queued.insert(hash) {
             assets_to_download.push(BatchArtifact {
                 name: asset_name.clone(),
-                urls: vec![asset_url],
-                path: asset_path,
+                urls: asset_mirrors::asset_urls(hash),
+                path: assets_dir.join("objects").join(hash_prefix).join(hash),
                 sha1: Some(hash.to_string()),
                 label: format!("assets/objects/{}/{}", hash_prefix, hash),
+                size: asset_obj.get("size").and_then(|size| size.as_u64()),
             });
         }
 
@@ -224,6 +269,58 @@ fn collect_missing_asset_downloads(
     Ok(assets_to_download)
 }
 
+/// Downloads replacing the objects named by `corrupted` labels. The
+/// downloader re-hashes the existing file and overwrites it on mismatch.
+fn corrupted_asset_downloads(
+    objects: &serde_json::Map<String, serde_json::Value>,
+    assets_dir: &Path,
+    corrupted: &[String],
+) -> Vec<BatchArtifact> {
+    corrupted
+        .iter()
+        .filter_map(|label| {
+            let hash = asset_integrity::hash_from_label(label)?;
+            let (name, object) = objects
+                .iter()
+                .find(|(_, object)| object.get("hash").and_then(|h| h.as_str()) == Some(hash))?;
+            Some(BatchArtifact {
+                name: name.clone(),
+                urls: asset_mirrors::asset_urls(hash),
+                path: assets_dir.join("objects").join(&hash[..2]).join(hash),
+                sha1: Some(hash.to_string()),
+                label: label.clone(),
+                size: object.get("size").and_then(|size| size.as_u64()),
+            })
+        })
+        .collect()
+}
+
+/// Compare against the asset index left by the previously installed version.
+/// Objects are content-addressed, so unchanged ones are already on disk and the
+/// missing-object scan skips them.
+fn log_asset_reuse(assets_dir: &Path, asset_index_id: &str, asset_index_content: &str) {
+    let Some((previous_id, previous_path)) =
+        asset_delta::find_previous_asset_index(assets_dir, asset_index_id)
+    else {
+        return;
+    };
+    let (Ok(previous), Ok(current)) = (
+        asset_delta::AssetIndexFile::load(&previous_path),
+        asset_delta::AssetIndexFile::parse(asset_index_content),
+    ) else {
+        return;
+    };
+
+    let delta = asset_delta::compute_asset_delta(&previous, &current);
+    log::info!(
+        "Reusing {}/{} assets from cached version (index {}); {} new",
+        delta.unchanged_count,
+        current.objects.len(),
+        previous_id,
+        delta.added.len()
+    );
+}
+
 fn complete_install_progress(spec: &InstallSpec, reporter: &dyn ProgressReporter, message: &str) {
     if spec.finalize_reporter {
         reporter.set_percent(100);
@@ -237,6 +334,18 @@ fn complete_install_progress(spec: &InstallSpec, reporter: &dyn ProgressReporter
 async fn install_instance_inner(
     spec: InstallSpec,
     reporter: std::sync::Arc<dyn ProgressReporter>,
+) -> Result<()> {
+    // Failed runs report too; where an install gives up is useful data.
+    let mut recorder = StepRecorder::new();
+    let result = run_install_steps(spec, reporter.clone(), &mut recorder).await;
+    recorder.finish(reporter.as_ref());
+    result
+}
+
+async fn run_install_steps(
+    spec: InstallSpec,
+    reporter: std::sync::Arc<dyn ProgressReporter>,
+    recorder: &mut StepRecorder,
 ) -> Result<()> {
     log::info!(
         "Starting installation: version={}, modloader={:?} (dry_run={})",
@@ -311,11 +420,10 @@ async fn install_instance_inner(
     // 1a. Fetch / use cached version info
     reporter.start_step("Loading version metadata", None);
     reporter.set_percent(5);
+    let manifest_started = Instant::now();
+    let mut manifest_bytes = 0;
 
-    let version_json_path = spec
-        .versions_dir()
-        .join(&spec.version_id)
-        .join(format!("{}.json", spec.version_id));
+    let version_json_path = preload::version_json_path(spec.data_dir(), &spec.version_id);
     let version_json_label = format!("versions/{}/{}.json", spec.version_id, spec.version_id);
 
     if !version_json_path.exists()
@@ -340,13 +448,11 @@ async fn install_instance_inner(
 
         tokio::fs::create_dir_all(version_json_path.parent().unwrap()).await?;
 
-        let version_url = format!(
-            "https://launcher-meta.modrinth.com/minecraft/v0/versions/{}.json",
-            spec.version_id
-        );
+        let version_url = preload::version_json_url(&spec.version_id);
         download_to_path(&client, &version_url, &version_json_path, None, &*reporter)
             .await
             .with_context(|| format!("Failed to download version info for {}", spec.version_id))?;
+        manifest_bytes += file_len(&version_json_path);
 
         track_artifact_from_path(
             version_json_label.clone(),
@@ -389,20 +495,116 @@ async fn install_instance_inner(
 
     let vanilla_manifest: crate::game::launcher::version_parser::VersionManifest =
         serde_json::from_value(version_info.clone())?;
-    let loader_manifest = loader_profile.as_ref().map(|profile| {
+    let mut loader_manifest = loader_profile.as_ref().map(|profile| {
         crate::game::installer::modloaders::profile_to_version_manifest(profile, &spec)
     });
+    // Fabric and Quilt mods need the intermediary mappings for this exact
+    // Minecraft version on the classpath.
+    if let Some(manifest) = loader_manifest.as_mut().filter(|_| {
+        matches!(
+            spec.modloader,
+            Some(ModloaderType::Fabric | ModloaderType::Quilt)
+        )
+    }) {
+        let intermediary_path = crate::game::installer::modloaders::fabric::intermediary_path(
+            &spec.libraries_dir(),
+            &spec.version_id,
+        );
+        let intermediary_present = intermediary_path.exists();
+        let intermediary = crate::game::installer::modloaders::fabric::install_intermediary(
+            &spec,
+            client,
+            reporter.as_ref(),
+        )
+        .await?;
+        if !intermediary_present {
+            manifest_bytes += intermediary.size;
+        }
+        if crate::game::installer::modloaders::fabric::add_intermediary_library(
+            manifest,
+            &intermediary,
+        ) {
+            log::info!(
+                "[installer] added intermediary {} missing from loader profile",
+                intermediary.mc_version
+            );
+        }
+    }
+    // Quilt also publishes its own hashed mappings for older loaders.
+    if let Some(manifest) = loader_manifest
+        .as_mut()
+        .filter(|_| spec.modloader == Some(ModloaderType::Quilt))
+    {
+        let hashed_present = crate::game::installer::modloaders::quilt::hashed_path(
+            &spec.libraries_dir(),
+            &spec.version_id,
+        )
+        .exists();
+        if let Some(hashed) = crate::game::installer::modloaders::quilt::download_hashed_mappings(
+            &spec,
+            client,
+            reporter.as_ref(),
+        )
+        .await?
+        {
+            if !hashed_present {
+                manifest_bytes += hashed.size;
+            }
+            if crate::game::installer::modloaders::quilt::add_hashed_library(manifest, &hashed) {
+                log::info!(
+                    "[installer] added Quilt hashed mappings {} to loader profile",
+                    hashed.mc_version
+                );
+            }
+        }
+    }
+    // Legacy NeoForge (1.20.1) may carry library patches next to the version json.
+    if let Some(manifest) = loader_manifest.as_mut().filter(|_| {
+        matches!(
+            spec.modloader,
+            Some(ModloaderType::Forge | ModloaderType::NeoForge)
+        )
+    }) {
+        if !spec.dry_run {
+            crate::game::installer::modloaders::patches::install_installer_patches(
+                &spec,
+                client,
+                reporter.as_ref(),
+            )
+            .await
+            .context("Failed to extract NeoForge installer patches")?;
+        }
+        let patches_dir = crate::game::installer::modloaders::patches::patches_dir(&spec);
+        let patches =
+            crate::game::installer::modloaders::patches::ForgePatches::load(&patches_dir).await?;
+        if !patches.is_empty() {
+            let changed = patches.apply(manifest);
+            log::info!(
+                "[installer] applied {} patch file(s) from {} ({} libraries changed)",
+                patches.patches.len(),
+                patches_dir.display(),
+                changed
+            );
+        }
+    }
     let runtime_plan = crate::game::runtime_plan::RuntimePlan::from_manifests(
         crate::game::runtime_plan::RuntimeRequest::from(&spec),
         vanilla_manifest,
         loader_manifest,
     )?;
+    recorder.record(
+        metrics::STEP_MANIFEST_FETCH,
+        manifest_started.elapsed(),
+        manifest_bytes,
+    );
 
     // ------------------------------------------------------------------
     // Phase 2: Download client jar + assets
     // ------------------------------------------------------------------
     reporter.start_step("Downloading game client", None);
     reporter.set_percent(15);
+    let client_jar_started = Instant::now();
+    let mut client_jar_bytes = 0;
 
     let installed_id = runtime_plan.installed_version_id.clone();
     let client_jar_path = runtime_plan.installed_client_jar.clone();
@@ -418,13 +620,21 @@ async fn install_instance_inner(
                     &*reporter,
                 )
                 .await?;
+                client_jar_bytes = file_len(&client_jar_path);
             }
         }
     }
+    recorder.record(
+        metrics::STEP_CLIENT_JAR,
+        client_jar_started.elapsed(),
+        client_jar_bytes,
+    );
 
     // 2b. Download asset index
     reporter.start_step("Downloading asset index", None);
     reporter.set_percent(20);
+    let assets_started = Instant::now();
+    let mut assets_bytes = 0;
 
     if let Some(asset_index) = &runtime_plan.manifest.asset_index {
         let asset_index_id = &asset_index.id;
@@ -450,6 +660,7 @@ async fn install_instance_inner(
                     &*reporter,
                 )
                 .await?;
+                assets_bytes += file_len(&asset_index_path);
                 track_artifact_from_path(
                     asset_index_label,
                     &asset_index_path,
@@ -482,8 +693,24 @@ async fn install_instance_inner(
         {
             let asset_scan_start = Instant::now();
             let assets_dir = spec.assets_dir();
-            let assets_to_download =
+            log_asset_reuse(&assets_dir, &asset_index.id, &asset_index_content);
+            let mut assets_to_download =
                 collect_missing_asset_downloads(objects, &assets_dir, reporter.as_ref())?;
+            if mutates_disk {
+                let index = asset_delta::AssetIndexFile::parse(&asset_index_content)?;
+                let corrupted = asset_integrity::verify_assets_integrity(
+                    &assets_dir,
+                    &index,
+                    reporter.clone(),
+                    spec.concurrency.assets,
+                )
+                .await?;
+                assets_to_download.extend(corrupted_asset_downloads(
+                    objects,
+                    &assets_dir,
+                    &corrupted,
+                ));
+            }
             log::info!(
                 "[installer] asset scan complete total={} missing={} elapsed_ms={}",
                 objects.len(),
@@ -492,7 +719,12 @@ async fn install_instance_inner(
             );
 
             if !assets_to_download.is_empty() {
-                let batch = BatchDownloader::new(client.clone(), spec.concurrency);
+                assets_bytes += assets_to_download
+                    .iter()
+                    .filter_map(|asset| asset.size)
+                    .sum::<u64>();
+                let batch = BatchDownloader::new(client.clone(), spec.concurrency.assets);
+                let _queue = AssetDownloadRegistration::new(&batch);
                 let asset_batch_start = Instant::now();
                 batch
                     .download_all(assets_to_download, reporter.clone(), 30, 10.0)
@@ -505,15 +737,52 @@ async fn install_instance_inner(
         }
     }
 
+    recorder.record(metrics::STEP_ASSETS, assets_started.elapsed(), assets_bytes);
+
     // ------------------------------------------------------------------
     // Phase 3: Unified library download + native extraction
     // ------------------------------------------------------------------
     reporter.start_step("Downloading libraries", None);
     reporter.set_percent(40);
 
-    let unified =
-        process_and_download_libraries(&spec, &runtime_plan, client.clone(), reporter.clone())
-            .await?;
+    let unified = runtime_plan.manifest.clone();
+    log::info!(
+        "Processing {} libraries ({} natives)",
+        unified.libraries.len(),
+        unified.libraries.iter().filter(|l| l.is_native).count()
+    );
+    let libraries_started = Instant::now();
+    if matches!(spec.modloader, Some(ModloaderType::Forge)) && !reporter.is_dry_run() {
+        let probe_path = unified.libraries.iter().find_map(|lib| {
+            lib.download_url
+                .as_deref()?
+                .strip_prefix(crate::game::installer::modloaders::forge_mirrors::FORGE_MAVEN_URL)
+        });
+        if let Some(probe_path) = probe_path {
+            reporter.set_message("Selecting Forge mirror");
+            let mirror = crate::game::installer::modloaders::forge_mirrors::ensure_mirror_selected(
+                client, probe_path,
+            )
+            .await;
+            log::info!("[installer] Forge mirror: {:?}", mirror);
+        }
+    }
+    let libraries_bytes =
+        download_regular_libraries(&spec, &unified, client, reporter.clone()).await?;
+    recorder.record(
+        metrics::STEP_LIBRARIES,
+        libraries_started.elapsed(),
+        libraries_bytes,
+    );
+
+    let natives_started = Instant::now();
+    let natives_bytes =
+        download_and_extract_natives(&spec, &unified, client, reporter.clone()).await?;
+    recorder.record(
+        metrics::STEP_NATIVES,
+        natives_started.elapsed(),
+        natives_bytes,
+    );
 
     // ------------------------------------------------------------------
     // Phase 4: Run Forge/NeoForge processors
@@ -525,6 +794,8 @@ async fn install_instance_inner(
                     reporter.start_step("Running forge processors", None);
                     reporter.set_percent(90);
 
+                    // Processors run locally; only their time is recorded.
+                    let processors_started = Instant::now();
                     crate::game::installer::modloaders::execute_loader_processors(
                         &spec,
                         reporter.clone(),
@@ -533,6 +804,11 @@ async fn install_instance_inner(
                         &client,
                     )
                     .await?;
+                    recorder.record(
+                        metrics::STEP_LOADER_PROCESSING,
+                        processors_started.elapsed(),
+                        0,
+                    );
                 }
             }
         }
@@ -547,7 +823,16 @@ async fn install_instance_inner(
     let java_ver = JavaVersion::new(runtime_plan.java_requirement.major_version);
 
     if spec.java_path.is_none() {
+        let jre_started = Instant::now();
+        let jre_install_dir = spec.jre_dir().join(format!("zulu-{}", java_ver.major));
+        let jre_present = jre_install_dir.exists();
         get_or_install_jre(&spec.jre_dir(), &java_ver, client, &*reporter).await?;
+        let jre_bytes = if jre_present {
+            0
+        } else {
+            metrics::dir_size(&jre_install_dir)
+        };
+        recorder.record(metrics::STEP_JRE, jre_started.elapsed(), jre_bytes);
     }
 
     // ------------------------------------------------------------------
@@ -569,6 +854,26 @@ async fn install_instance_inner(
             let unified_for_save = unified.clone();
             tokio::task::spawn_blocking(move || unified_for_save.save_to_path(&version_json_path))
                 .await??;
+
+            // Flattened copy for launches and external tools; the launcher
+            // regenerates it on demand, so a failure here is not fatal.
+            let data_dir = spec.data_dir().clone();
+            let unified_id = installed_id.clone();
+            match tokio::task::spawn_blocking(move || {
+                crate::game::launcher::unified_manifest::generate_unified_manifest(
+                    &data_dir,
+                    &unified_id,
+                )
+            })
+            .await?
+            {
+                Ok(_) => log::info!("[installer] wrote unified manifest for {}", installed_id),
+                Err(e) => log::warn!(
+                    "[installer] failed to write unified manifest for {}: {:#}",
+                    installed_id,
+                    e
+                ),
+            }
         }
     }
 
@@ -594,6 +899,7 @@ mod tests {
     struct RecordingReporter {
         messages: StdMutex<Vec<String>>,
         steps: StdMutex<Vec<(u32, Option<u32>)>>,
+        metrics: StdMutex<std::collections::BTreeMap<String, u64>>,
     }
 
     impl ProgressReporter for RecordingReporter {
@@ -620,6 +926,31 @@ mod tests {
         fn is_paused(&self) -> bool {
             false
         }
+
+        fn record_metric(&self, key: &str, value: u64) {
+            self.metrics.lock().unwrap().insert(key.to_string(), value);
+        }
+    }
+
+    #[tokio::test]
+    async fn dry_run_install_reports_every_metric() {
+        let tmp = tempdir().unwrap();
+        let mut spec = InstallSpec::new(
+            "1.20.1".to_string(),
+            tmp.path().join("data"),
+            tmp.path().join("game"),
+        );
+        spec.dry_run = true;
+        let reporter = Arc::new(RecordingReporter::default());
+
+        install_instance(spec, reporter.clone()).await.unwrap();
+
+        let recorded = reporter.metrics.lock().unwrap();
+        for key in metrics::expected_keys() {
+            assert!(recorded.contains_key(&key), "missing metric {}", key);
+        }
+        assert_eq!(recorded.len(), metrics::expected_keys().len());
+        assert_eq!(recorded["client_jar_bytes"], 0);
     }
 
     fn asset_object(hash: &str) -> serde_json::Value {
@@ -670,4 +1001,35 @@ mod tests {
             [(0, Some(2)), (2, Some(2))]
         );
     }
+
+    #[test]
+    fn find_in_object_store_is_a_direct_hash_lookup() {
+        let tmp = tempdir().unwrap();
+        let assets_dir = tmp.path().join("assets");
+        let hash = "cccccccccccccccccccccccccccccccccccccccc";
+        assert_eq!(find_in_object_store(&assets_dir, hash), None);
+        assert_eq!(find_in_object_store(&assets_dir, "c"), None);
+
+        let object = assets_dir.join("objects").join("cc").join(hash);
+        std::fs::create_dir_all(object.parent().unwrap()).unwrap();
+        std::fs::write(&object, b"shared").unwrap();
+        assert_eq!(find_in_object_store(&assets_dir, hash), Some(object));
+    }
+
+    #[test]
+    fn collect_missing_asset_downloads_queues_shared_objects_once() {
+        let tmp = tempdir().unwrap();
+        let assets_dir = tmp.path().join("assets");
+        let shared_hash = "dddddddddddddddddddddddddddddddddddddddd";
+
+        let mut objects = serde_json::Map::new();
+        objects.insert("sounds/a.ogg".to_string(), asset_object(shared_hash));
+        objects.insert("sounds/b.ogg".to_string(), asset_object(shared_hash));
+
+        let reporter = RecordingReporter::default();
+        let downloads = collect_missing_asset_downloads(&objects, &assets_dir, &reporter).unwrap();
+
+        assert_eq!(downloads.len(), 1);
+        assert_eq!(downloads[0].sha1.as_deref(), Some(shared_hash));
+    }
 }
@@ -0,0 +1,247 @@
+//! Fabric/Quilt intermediary mappings.
+//!
+//! Fabric mods are compiled against `net.fabricmc:intermediary` names, so the
+//! mapping jar for the exact Minecraft version has to be on the classpath.
+//! It is fetched separately from the loader profile so a profile that omits
+//! it (or a cache that lost it) still yields a launchable install.
+
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+use crate::game::installer::core::downloader::download_to_path;
+use crate::game::installer::modloaders::quilt::HASHED_GROUP;
+use crate::game::installer::types::{InstallSpec, ProgressReporter};
+use crate::game::installer::{track_artifact_from_path, try_restore_artifact};
+use crate::game::launcher::version_parser::{Artifact, Library, LibraryDownloads, VersionManifest};
+
+pub const FABRIC_META_URL: &str = "https://meta.fabricmc.net";
+pub const FABRIC_MAVEN_URL: &str = "https://maven.fabricmc.net";
+const INTERMEDIARY_GROUP: &str = "net.fabricmc:intermediary";
+
+#[derive(Debug, Deserialize)]
+struct IntermediaryVersion {
+    version: String,
+}
+
+/// Downloaded intermediary jar for one Minecraft version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntermediaryArtifact {
+    pub mc_version: String,
+    pub url: String,
+    pub sha1: String,
+    pub size: u64,
+    pub path: PathBuf,
+}
+
+/// Fabric meta endpoint listing the intermediary for `mc_version`.
+pub fn intermediary_meta_url(mc_version: &str) -> String {
+    format!(
+        "{}/v2/versions/intermediary/{}",
+        FABRIC_META_URL, mc_version
+    )
+}
+
+/// Path of the intermediary jar relative to the libraries directory.
+pub fn intermediary_relative_path(mc_version: &str) -> String {
+    format!(
+        "net/fabricmc/intermediary/{0}/intermediary-{0}.jar",
+        mc_version
+    )
+}
+
+/// Maven URL of the intermediary jar for `mc_version`.
+pub fn intermediary_jar_url(mc_version: &str) -> String {
+    format!(
+        "{}/{}",
+        FABRIC_MAVEN_URL,
+        intermediary_relative_path(mc_version)
+    )
+}
+
+pub fn intermediary_path(libraries_dir: &Path, mc_version: &str) -> PathBuf {
+    libraries_dir.join(intermediary_relative_path(mc_version))
+}
+
+/// Make sure the intermediary jar for `spec.version_id` is present and matches
+/// the SHA-1 published next to it on the Fabric maven. Each Minecraft version
+/// has its own mappings, so this runs for every Fabric/Quilt install.
+pub async fn install_intermediary(
+    spec: &InstallSpec,
+    client: &Client,
+    reporter: &dyn ProgressReporter,
+) -> Result<IntermediaryArtifact> {
+    reporter.set_message(&format!(
+        "Fetching intermediary mappings for {}",
+        spec.version_id
+    ));
+
+    let meta_url = intermediary_meta_url(&spec.version_id);
+    let versions: Vec<IntermediaryVersion> = client
+        .get(&meta_url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to request {}", meta_url))?
+        .error_for_status()
+        .with_context(|| format!("Intermediary request failed: {}", meta_url))?
+        .json()
+        .await
+        .with_context(|| format!("Failed to parse intermediary list: {}", meta_url))?;
+    let mc_version = versions
+        .into_iter()
+        .map(|entry| entry.version)
+        .find(|version| version == &spec.version_id)
+        .with_context(|| {
+            format!(
+                "No intermediary mappings published for Minecraft {}",
+                spec.version_id
+            )
+        })?;
+
+    let url = intermediary_jar_url(&mc_version);
+    let sha1 = fetch_maven_sha1(client, &url).await?;
+
+    let path = intermediary_path(&spec.libraries_dir(), &mc_version);
+    let label = format!("libraries/{}", intermediary_relative_path(&mc_version));
+    if !path.exists() {
+        try_restore_artifact(&label, &path).await?;
+    }
+    // Verifies an existing or restored jar and re-downloads it on mismatch.
+    download_to_path(client, &url, &path, Some(&sha1), reporter).await?;
+    track_artifact_from_path(label, &path, None, Some(url.clone())).await?;
+
+    let size = tokio::fs::metadata(&path)
+        .await
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+    Ok(IntermediaryArtifact {
+        mc_version,
+        url,
+        sha1,
+        size,
+        path,
+    })
+}
+
+/// SHA-1 published next to a maven artifact (`<url>.sha1`).
+pub(crate) async fn fetch_maven_sha1(client: &Client, artifact_url: &str) -> Result<String> {
+    let sha1_url = format!("{}.sha1", artifact_url);
+    client
+        .get(&sha1_url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to request {}", sha1_url))?
+        .error_for_status()
+        .with_context(|| format!("Checksum request failed: {}", sha1_url))?
+        .text()
+        .await
+        .with_context(|| format!("Failed to read {}", sha1_url))?
+        .split_whitespace()
+        .next()
+        .map(str::to_ascii_lowercase)
+        .filter(|hash| hash.len() == 40 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+        .with_context(|| format!("Malformed checksum at {}", sha1_url))
+}
+
+/// Add the intermediary to a loader manifest's libraries unless the profile
+/// already ships mappings (intermediary, or Quilt's hashed mappings).
+/// Returns whether the manifest changed.
+pub fn add_intermediary_library(
+    manifest: &mut VersionManifest,
+    intermediary: &IntermediaryArtifact,
+) -> bool {
+    let has_mappings = manifest.libraries.iter().any(|lib| {
+        lib.name.starts_with(&format!("{}:", INTERMEDIARY_GROUP))
+            || lib.name.starts_with(&format!("{}:", HASHED_GROUP))
+    });
+    if has_mappings {
+        return false;
+    }
+
+    manifest.libraries.push(Library {
+        name: format!("{}:{}", INTERMEDIARY_GROUP, intermediary.mc_version),
+        downloads: Some(LibraryDownloads {
+            artifact: Some(Artifact {
+                path: Some(intermediary_relative_path(&intermediary.mc_version)),
+                url: Some(intermediary.url.clone()),
+                sha1: Some(intermediary.sha1.clone()),
+                size: Some(intermediary.size),
+            }),
+            classifiers: None,
+        }),
+        url: Some(format!("{}/", FABRIC_MAVEN_URL)),
+        rules: None,
+        natives: None,
+        extract: None,
+        include_in_classpath: true,
+    });
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intermediary_urls_are_per_minecraft_version() {
+        let cases = [
+            (
+                "1.14",
+                "https://meta.fabricmc.net/v2/versions/intermediary/1.14",
+                "https://maven.fabricmc.net/net/fabricmc/intermediary/1.14/intermediary-1.14.jar",
+            ),
+            (
+                "1.18.2",
+                "https://meta.fabricmc.net/v2/versions/intermediary/1.18.2",
+                "https://maven.fabricmc.net/net/fabricmc/intermediary/1.18.2/intermediary-1.18.2.jar",
+            ),
+            (
+                "1.21.1",
+                "https://meta.fabricmc.net/v2/versions/intermediary/1.21.1",
+                "https://maven.fabricmc.net/net/fabricmc/intermediary/1.21.1/intermediary-1.21.1.jar",
+            ),
+        ];
+        for (mc_version, meta, jar) in cases {
+            assert_eq!(intermediary_meta_url(mc_version), meta);
+            assert_eq!(intermediary_jar_url(mc_version), jar);
+        }
+    }
+
+    #[test]
+    fn intermediary_lands_in_the_libraries_tree() {
+        assert_eq!(
+            intermediary_path(Path::new("/data/libraries"), "1.21.1"),
+            Path::new("/data/libraries/net/fabricmc/intermediary/1.21.1/intermediary-1.21.1.jar")
+        );
+    }
+
+    #[test]
+    fn intermediary_library_is_added_once() {
+        let mut manifest: VersionManifest = serde_json::from_value(serde_json::json!({
+            "id": "fabric-loader-0.16.5-1.21.1",
+            "mainClass": "net.fabricmc.loader.impl.launch.knot.KnotClient",
+            "libraries": [{"name": "net.fabricmc:fabric-loader:0.16.5"}]
+        }))
+        .unwrap();
+        let artifact = IntermediaryArtifact {
+            mc_version: "1.21.1".to_string(),
+            url: intermediary_jar_url("1.21.1"),
+            sha1: "0".repeat(40),
+            size: 42,
+            path: intermediary_path(Path::new("/data/libraries"), "1.21.1"),
+        };
+
+        assert!(add_intermediary_library(&mut manifest, &artifact));
+        assert!(!add_intermediary_library(&mut manifest, &artifact));
+
+        let added = manifest.libraries.last().unwrap();
+        assert_eq!(added.name, "net.fabricmc:intermediary:1.21.1");
+        let download = added.downloads.as_ref().unwrap().artifact.as_ref().unwrap();
+        assert_eq!(
+            download.path.as_deref(),
+            Some("net/fabricmc/intermediary/1.21.1/intermediary-1.21.1.jar")
+        );
+        assert_eq!(download.sha1.as_deref(), Some(artifact.sha1.as_str()));
+    }
+}
@@ -0,0 +1,285 @@
+//! Mirrors of the Forge maven for library downloads.
+//!
+//! maven.minecraftforge.net is sometimes slow, so before a Forge install
+//! fetches its libraries each bundled mirror is probed in order and the
+//! fastest one answering within [`PROBE_TIMEOUT`] is kept for the rest of the
+//! session. Library downloads then try that mirror first and fall back to the
+//! others in list order. A user-chosen mirror replaces the probe entirely.
+
+use anyhow::Result;
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::{OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+pub const FORGE_MAVEN_URL: &str = "https://maven.minecraftforge.net/";
+
+/// Longest a mirror may take to start answering the probe.
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ForgeMirrorList {
+    pub mirrors: Vec<String>,
+}
+
+impl ForgeMirrorList {
+    /// The list shipped in `forge_mirrors.json`.
+    pub fn bundled() -> &'static ForgeMirrorList {
+        static BUNDLED: OnceLock<ForgeMirrorList> = OnceLock::new();
+        BUNDLED.get_or_init(|| {
+            match serde_json::from_str::<ForgeMirrorList>(include_str!("forge_mirrors.json")) {
+                Ok(list) => ForgeMirrorList {
+                    mirrors: list
+                        .mirrors
+                        .iter()
+                        .filter_map(|url| normalize_mirror_url(url).ok())
+                        .collect(),
+                },
+                Err(e) => {
+                    log::error!("Invalid bundled forge_mirrors.json: {}", e);
+                    ForgeMirrorList {
+                        mirrors: vec![FORGE_MAVEN_URL.to_string()],
+                    }
+                }
+            }
+        })
+    }
+}
+
+struct MirrorState {
+    selected: Option<String>,
+    user_override: Option<String>,
+}
+
+static STATE: RwLock<MirrorState> = RwLock::new(MirrorState {
+    selected: None,
+    user_override: None,
+});
+
+/// Mirror base URL with a trailing slash. Only http(s) URLs are accepted.
+pub fn normalize_mirror_url(url: &str) -> Result<String> {
+    let url = url.trim();
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        anyhow::bail!("Mirror URL must start with http:// or https://: {}", url);
+    }
+    if url.contains(['?', '#']) {
+        anyhow::bail!("Mirror URL must not contain a query or fragment: {}", url);
+    }
+    Ok(format!("{}/", url.trim_end_matches('/')))
+}
+
+/// Use `url` for every Forge library download this session, or go back to
+/// probing with `None`.
+pub fn set_mirror_override(url: Option<&str>) -> Result<()> {
+    let normalized = url.map(normalize_mirror_url).transpose()?;
+    let mut state = STATE.write().unwrap_or_else(|e| e.into_inner());
+    log::info!("[forge-mirrors] override set to {:?}", normalized);
+    state.user_override = normalized;
+    Ok(())
+}
+
+/// The user's mirror if set, otherwise the one selected by probing.
+pub fn active_mirror() -> Option<String> {
+    let state = STATE.read().unwrap_or_else(|e| e.into_inner());
+    state
+        .user_override
+        .clone()
+        .or_else(|| state.selected.clone())
+}
+
+/// Probe the bundled mirrors once per session with `probe_path` (a Forge
+/// maven path the install needs) and remember the fastest. Returns the
+/// active mirror, or `None` when nothing answered in time.
+pub async fn ensure_mirror_selected(client: &Client, probe_path: &str) -> Option<String> {
+    if let Some(mirror) = active_mirror() {
+        return Some(mirror);
+    }
+
+    let selected = select_fastest_mirror(client, &ForgeMirrorList::bundled().mirrors, probe_path)
+        .await
+        .map(|(mirror, _)| mirror);
+    if let Some(mirror) = &selected {
+        let mut state = STATE.write().unwrap_or_else(|e| e.into_inner());
+        // A concurrent install may have selected one meanwhile; keep the first.
+        state.selected.get_or_insert_with(|| mirror.clone());
+    }
+    active_mirror()
+}
+
+/// Probe `mirrors` in order and return the one with the lowest first-byte
+/// latency for `probe_path`, ignoring any that fail or exceed
+/// [`PROBE_TIMEOUT`].
+pub async fn select_fastest_mirror(
+    client: &Client,
+    mirrors: &[String],
+    probe_path: &str,
+) -> Option<(String, Duration)> {
+    let mut fastest: Option<(String, Duration)> = None;
+    for mirror in mirrors {
+        let url = format!("{}{}", mirror, probe_path.trim_start_matches('/'));
+        match first_byte_latency(client, &url).await {
+            Some(latency) => {
+                log::info!(
+                    "[forge-mirrors] {} answered in {}ms",
+                    mirror,
+                    latency.as_millis()
+                );
+                if fastest.as_ref().is_none_or(|(_, best)| latency < *best) {
+                    fastest = Some((mirror.clone(), latency));
+                }
+            }
+            None => log::info!("[forge-mirrors] {} did not answer in time", mirror),
+        }
+    }
+    fastest
+}
+
+/// Time until the response headers arrive; the body is not read.
+async fn first_byte_latency(client: &Client, url: &str) -> Option<Duration> {
+    let started = Instant::now();
+    let response = tokio::time::timeout(PROBE_TIMEOUT, client.get(url).send())
+        .await
+        .ok()?
+        .ok()?;
+    response.status().is_success().then(|| started.elapsed())
+}
+
+/// URLs to try for a library download. Forge maven URLs expand to the
+/// active mirror followed by the bundled mirrors; anything else is
+/// returned unchanged.
+pub fn candidate_urls(url: &str) -> Vec<String> {
+    candidates_for(
+        url,
+        &ForgeMirrorList::bundled().mirrors,
+        active_mirror().as_deref(),
+    )
+}
+
+fn candidates_for(url: &str, mirrors: &[String], preferred: Option<&str>) -> Vec<String> {
+    let Some(path) = url.strip_prefix(FORGE_MAVEN_URL) else {
+        return vec![url.to_string()];
+    };
+
+    let mut candidates: Vec<String> = Vec::with_capacity(mirrors.len() + 1);
+    for mirror in preferred
+        .into_iter()
+        .chain(mirrors.iter().map(String::as_str))
+    {
+        let candidate = format!("{}{}", mirror, path);
+        if !candidates.contains(&candidate) {
+            candidates.push(candidate);
+        }
+    }
+    if candidates.is_empty() {
+        candidates.push(url.to_string());
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::path;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const FORGE_JAR: &str =
+        "net/minecraftforge/forge/1.20.1-47.3.0/forge-1.20.1-47.3.0-universal.jar";
+
+    fn mirrors() -> Vec<String> {
+        ForgeMirrorList::bundled().mirrors.clone()
+    }
+
+    #[test]
+    fn bundled_list_starts_with_the_official_maven() {
+        let list = ForgeMirrorList::bundled();
+        assert_eq!(list.mirrors[0], FORGE_MAVEN_URL);
+        assert!(list.mirrors.len() > 1);
+        assert!(list.mirrors.iter().all(|m| m.ends_with('/')));
+    }
+
+    #[test]
+    fn forge_urls_expand_to_preferred_mirror_then_list_order() {
+        let url = format!("{}{}", FORGE_MAVEN_URL, FORGE_JAR);
+        let preferred = "https://launcher-meta.modrinth.com/maven/";
+
+        let candidates = candidates_for(&url, &mirrors(), Some(preferred));
+
+        assert_eq!(candidates[0], format!("{}{}", preferred, FORGE_JAR));
+        assert_eq!(candidates[1], url);
+        assert_eq!(candidates.len(), mirrors().len());
+        assert!(candidates.iter().all(|c| c.ends_with(FORGE_JAR)));
+    }
+
+    #[test]
+    fn custom_mirror_goes_first_without_dropping_fallbacks() {
+        let url = format!("{}{}", FORGE_MAVEN_URL, FORGE_JAR);
+        let candidates = candidates_for(&url, &mirrors(), Some("https://forge.example.org/"));
+        assert_eq!(
+            candidates[0],
+            format!("https://forge.example.org/{}", FORGE_JAR)
+        );
+        assert_eq!(candidates.len(), mirrors().len() + 1);
+    }
+
+    #[test]
+    fn other_urls_are_left_alone() {
+        let url = "https://launcher-meta.modrinth.com/maven/net/fabricmc/fabric-loader/0.16.5/fabric-loader-0.16.5.jar";
+        assert_eq!(candidates_for(url, &mirrors(), None), vec![url.to_string()]);
+    }
+
+    #[test]
+    fn mirror_urls_are_validated_and_normalized() {
+        assert_eq!(
+            normalize_mirror_url(" https://forge.example.org/maven ").unwrap(),
+            "https://forge.example.org/maven/"
+        );
+        assert!(normalize_mirror_url("ftp://forge.example.org").is_err());
+        assert!(normalize_mirror_url("https://forge.example.org/?x=1").is_err());
+    }
+
+    #[tokio::test]
+    async fn picks_the_fastest_mirror_that_answers_in_time() {
+        let slow = MockServer::start().await;
+        let fast = MockServer::start().await;
+        let missing = MockServer::start().await;
+        Mock::given(path(format!("/{}", FORGE_JAR)))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(400)))
+            .mount(&slow)
+            .await;
+        Mock::given(path(format!("/{}", FORGE_JAR)))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&fast)
+            .await;
+        Mock::given(path(format!("/{}", FORGE_JAR)))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&missing)
+            .await;
+
+        let list: Vec<String> = [&missing, &slow, &fast]
+            .iter()
+            .map(|server| format!("{}/", server.uri()))
+            .collect();
+        let client = Client::new();
+
+        let (mirror, _) = select_fastest_mirror(&client, &list, FORGE_JAR)
+            .await
+            .unwrap();
+        assert_eq!(mirror, list[2]);
+    }
+
+    #[tokio::test]
+    async fn mirrors_slower_than_the_timeout_are_skipped() {
+        let stalled = MockServer::start().await;
+        Mock::given(path(format!("/{}", FORGE_JAR)))
+            .respond_with(
+                ResponseTemplate::new(200).set_delay(PROBE_TIMEOUT + Duration::from_millis(500)),
+            )
+            .mount(&stalled)
+            .await;
+
+        let list = vec![format!("{}/", stalled.uri())];
+        assert!(select_fastest_mirror(&Client::new(), &list, FORGE_JAR)
+            .await
+            .is_none());
+    }
+}
@@ -2,6 +2,7 @@ use anyhow::{Context, Result};
 use reqwest::Client;
 use std::collections::HashMap;
 use std::path::Path;
+use std::process::Stdio;
 use tokio::process::Command;
 
 use crate::game::installer::types::{InstallSpec, ModloaderType, ProgressReporter};
@@ -12,6 +13,15 @@ use crate::game::metadata::{
     is_dummy_game_version, ModrinthArtifact, ModrinthLoaderProfile, ModrinthManifest,
     ModrinthProcessor, ModrinthSidedDataEntry,
 };
+use crate::utils::process::PistonCommandExt;
+
+pub mod fabric;
+pub mod forge_mirrors;
+pub mod patches;
+pub mod quilt;
+
+/// Lines of processor stderr included in the error when a processor fails.
+const PROCESSOR_ERROR_TAIL_LINES: usize = 20;
 
 pub async fn resolve_loader_profile(
     spec: &InstallSpec,
@@ -119,6 +129,19 @@ pub async fn resolve_loader_profile(
         loader.as_str(),
         selected.id
     ));
+    if loader == ModloaderType::Quilt {
+        match quilt::fetch_loader_profile(client, &spec.version_id, &selected.id).await {
+            Ok(mut profile) => {
+                profile.resolve_placeholders(&spec.version_id);
+                return Ok(profile);
+            }
+            Err(e) => log::warn!(
+                "Quilt meta unavailable, using the Modrinth profile for {}: {:#}",
+                selected.id,
+                e
+            ),
+        }
+    }
     let mut profile: ModrinthLoaderProfile = client
         .get(&selected.url)
         .send()
@@ -276,20 +299,60 @@ pub async fn execute_loader_processors(
         for arg in &resolved_args {
             cmd.arg(arg);
         }
-        cmd.current_dir(spec.data_dir());
-
-        let status = cmd
-            .status()
+        cmd.current_dir(spec.data_dir())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .suppress_console();
+
+        log::info!("[forge] Running processor {} ({})", step, processor.jar);
+        let output = cmd
+            .output()
             .await
             .with_context(|| format!("Failed to execute processor {}", processor.jar))?;
-        if !status.success() {
-            anyhow::bail!("Processor failed: {} (status: {})", processor.jar, status);
+        log_processor_output(&processor.jar, &output.stdout, &output.stderr);
+        if !output.status.success() {
+            let tail = output_tail(&output.stderr, PROCESSOR_ERROR_TAIL_LINES);
+            if tail.is_empty() {
+                anyhow::bail!(
+                    "Processor failed: {} (status: {})",
+                    processor.jar,
+                    output.status
+                );
+            }
+            anyhow::bail!(
+                "Processor failed: {} (status: {})\n{}",
+                processor.jar,
+                output.status,
+                tail
+            );
         }
     }
 
     Ok(())
 }
 
+fn log_processor_output(jar: &str, stdout: &[u8], stderr: &[u8]) {
+    for line in String::from_utf8_lossy(stdout).lines() {
+        if !line.trim().is_empty() {
+            log::info!("[forge] [{}] {}", jar, line);
+        }
+    }
+    for line in String::from_utf8_lossy(stderr).lines() {
+        if !line.trim().is_empty() {
+            log::warn!("[forge] [{}] {}", jar, line);
+        }
+    }
+}
+
+/// Last `max_lines` non-empty lines of a processor output stream.
+fn output_tail(bytes: &[u8], max_lines: usize) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+    lines[lines.len().saturating_sub(max_lines)..].join("\n")
+}
+
 fn to_library_downloads(
     downloads: &crate::game::metadata::ModrinthLibraryDownloads,
 ) -> LibraryDownloads {
@@ -399,3 +462,16 @@ fn minecraft_jar_path(spec: &InstallSpec) -> std::path::PathBuf {
         .join(&installed)
         .join(format!("{}.jar", installed))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_tail_keeps_last_non_empty_lines() {
+        let stderr = b"first\n\nsecond\r\nthird\n\n";
+        assert_eq!(output_tail(stderr, 2), "second\nthird");
+        assert_eq!(output_tail(stderr, 10), "first\nsecond\nthird");
+        assert_eq!(output_tail(b"", 5), "");
+    }
+}
@@ -0,0 +1,301 @@
+//! Library patches for legacy NeoForge (1.20.1) installs.
+//!
+//! NeoForge for 1.20.1 still ships as `net.neoforged:forge` and may carry
+//! extra JSON patch files that override libraries of the loader profile.
+//! During install the patches are extracted from the installer jar (the
+//! version json named by `install_profile.json`, plus any `patches/*.json`)
+//! into `versions/<installed id>/patches/`. They are applied in file-name
+//! order; a later patch replaces a library with the same group, artifact and
+//! classifier (last writer wins).
+
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use crate::game::installer::core::downloader::download_to_path;
+use crate::game::installer::modloaders::fabric::fetch_maven_sha1;
+use crate::game::installer::types::{InstallSpec, ModloaderType, ProgressReporter};
+use crate::game::installer::{track_artifact_from_path, try_restore_artifact};
+use crate::game::launcher::version_parser::{Library, VersionManifest};
+use crate::game::metadata::neoforge::{split_legacy_version, NEOFORGED_MAVEN_URL};
+
+/// Patch file written from the installer's version json.
+pub const INSTALLER_VERSION_PATCH: &str = "00-installer.json";
+/// Prefix for `patches/*.json` entries copied from the installer, so they
+/// apply after [`INSTALLER_VERSION_PATCH`].
+const INSTALLER_PATCH_PREFIX: &str = "10-";
+
+/// One patch file. Only `libraries` is applied; other keys are ignored.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ForgePatch {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub libraries: Vec<Library>,
+}
+
+/// All patch files of an installed version, in application order.
+#[derive(Debug, Clone, Default)]
+pub struct ForgePatches {
+    pub patches: Vec<(String, ForgePatch)>,
+}
+
+/// Directory holding the patch files of the version `spec` installs.
+pub fn patches_dir(spec: &InstallSpec) -> PathBuf {
+    spec.versions_dir()
+        .join(spec.installed_version_id())
+        .join("patches")
+}
+
+/// Maven coordinates of the installer jar for a legacy NeoForge install, or
+/// `None` for anything that isn't NeoForge on 1.20.1.
+pub fn legacy_installer_coords(spec: &InstallSpec) -> Option<String> {
+    if spec.modloader != Some(ModloaderType::NeoForge) || spec.version_id != "1.20.1" {
+        return None;
+    }
+    let version = spec.modloader_version.as_deref()?;
+    let loader_version = split_legacy_version(version)
+        .map(|(_, loader)| loader)
+        .unwrap_or(version);
+    Some(format!(
+        "net.neoforged:forge:{}-{}:installer",
+        spec.version_id, loader_version
+    ))
+}
+
+/// The part of a Forge-format `install_profile.json` needed to find the
+/// version json. Its own `libraries` only feed the install processors and
+/// are not persisted as patches.
+#[derive(Debug, Deserialize)]
+struct InstallProfile {
+    #[serde(default = "default_version_json")]
+    json: String,
+}
+
+fn default_version_json() -> String {
+    "/version.json".to_string()
+}
+
+/// Write the patches of a legacy NeoForge installer jar to `dest`, replacing
+/// whatever was there. Returns the written files in application order.
+pub fn extract_installer_patches(installer_jar: &Path, dest: &Path) -> Result<Vec<PathBuf>> {
+    let file = std::fs::File::open(installer_jar)
+        .with_context(|| format!("Failed to open installer {}", installer_jar.display()))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .with_context(|| format!("Installer is not a jar: {}", installer_jar.display()))?;
+
+    let read_entry = |archive: &mut zip::ZipArchive<std::fs::File>, name: &str| -> Result<String> {
+        let mut entry = archive
+            .by_name(name)
+            .with_context(|| format!("Installer has no {}", name))?;
+        let mut raw = String::new();
+        entry.read_to_string(&mut raw)?;
+        Ok(raw)
+    };
+
+    let profile: InstallProfile =
+        serde_json::from_str(&read_entry(&mut archive, "install_profile.json")?)
+            .context("Failed to parse install_profile.json")?;
+    let version_json = profile.json.trim_start_matches('/').to_string();
+    let mut patches = vec![(
+        INSTALLER_VERSION_PATCH.to_string(),
+        read_entry(&mut archive, &version_json)?,
+    )];
+
+    let mut extra: Vec<String> = archive
+        .file_names()
+        .filter_map(|name| name.strip_prefix("patches/"))
+        .filter(|file| file.ends_with(".json") && !file.contains(['/', '\\']))
+        .map(str::to_string)
+        .collect();
+    extra.sort();
+    for file in extra {
+        let raw = read_entry(&mut archive, &format!("patches/{}", file))?;
+        patches.push((format!("{}{}", INSTALLER_PATCH_PREFIX, file), raw));
+    }
+
+    for (name, raw) in &patches {
+        ForgePatches::parse(raw).with_context(|| format!("Invalid installer patch {}", name))?;
+    }
+
+    if dest.exists() {
+        std::fs::remove_dir_all(dest)
+            .with_context(|| format!("Failed to clear {}", dest.display()))?;
+    }
+    std::fs::create_dir_all(dest)?;
+    let mut written = Vec::with_capacity(patches.len());
+    for (name, raw) in patches {
+        let path = dest.join(name);
+        std::fs::write(&path, raw)
+            .with_context(|| format!("Failed to write patch {}", path.display()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Download the legacy NeoForge installer of `spec` into the libraries
+/// directory and persist its patches to [`patches_dir`]. Does nothing for
+/// other loaders.
+pub async fn install_installer_patches(
+    spec: &InstallSpec,
+    client: &Client,
+    reporter: &dyn ProgressReporter,
+) -> Result<Vec<PathBuf>> {
+    let Some(coords) = legacy_installer_coords(spec) else {
+        return Ok(Vec::new());
+    };
+    reporter.set_message("Reading NeoForge installer patches");
+
+    let relative = crate::game::launcher::maven_to_path(&coords)?;
+    let url = format!("{}/{}", NEOFORGED_MAVEN_URL, relative);
+    let path = spec.libraries_dir().join(&relative);
+    let label = format!("libraries/{}", relative);
+    let sha1 = fetch_maven_sha1(client, &url).await?;
+    if !path.exists() {
+        try_restore_artifact(&label, &path).await?;
+    }
+    download_to_path(client, &url, &path, Some(&sha1), reporter).await?;
+    track_artifact_from_path(label, &path, None, Some(url)).await?;
+
+    let dest = patches_dir(spec);
+    tokio::task::spawn_blocking(move || extract_installer_patches(&path, &dest))
+        .await
+        .context("installer patch extraction worker panicked")?
+}
+
+/// Identity of a library across versions: `group:artifact[:classifier][@ext]`.
+pub fn library_key(name: &str) -> String {
+    let (coords, extension) = match name.split_once('@') {
+        Some((coords, ext)) => (coords, Some(ext)),
+        None => (name, None),
+    };
+    let parts: Vec<&str> = coords.split(':').collect();
+    let mut key = match parts.as_slice() {
+        [group, artifact, _version, classifier, ..] => {
+            format!("{}:{}:{}", group, artifact, classifier)
+        }
+        [group, artifact, ..] => format!("{}:{}", group, artifact),
+        _ => coords.to_string(),
+    };
+    if let Some(ext) = extension {
+        key.push('@');
+        key.push_str(ext);
+    }
+    key
+}
+
+impl ForgePatches {
+    /// Read every `*.json` file in `dir`, sorted by file name. A missing
+    /// directory means no patches.
+    pub async fn load(dir: &Path) -> Result<Self> {
+        if !tokio::fs::try_exists(dir).await.unwrap_or(false) {
+            return Ok(Self::default());
+        }
+
+        let mut files = Vec::new();
+        let mut entries = tokio::fs::read_dir(dir)
+            .await
+            .with_context(|| format!("Failed to read patch directory {}", dir.display()))?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+                files.push(path);
+            }
+        }
+        files.sort();
+
+        let mut patches = Vec::with_capacity(files.len());
+        for path in files {
+            let raw = tokio::fs::read_to_string(&path)
+                .await
+                .with_context(|| format!("Failed to read patch {}", path.display()))?;
+            let patch = Self::parse(&raw)
+                .with_context(|| format!("Failed to parse patch {}", path.display()))?;
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            patches.push((file_name, patch));
+        }
+        Ok(Self { patches })
+    }
+
+    pub fn parse(raw: &str) -> Result<ForgePatch> {
+        Ok(serde_json::from_str(raw)?)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patches.is_empty()
+    }
+
+    /// The winning library for each [`library_key`] across all patches,
+    /// with the file it came from, in first-declared order.
+    pub fn resolved_libraries(&self) -> Vec<(&str, &Library)> {
+        let mut resolved: Vec<(&str, &Library)> = Vec::new();
+        for (file_name, patch) in &self.patches {
+            for library in &patch.libraries {
+                let key = library_key(&library.name);
+                match resolved
+                    .iter_mut()
+                    .find(|(_, existing)| library_key(&existing.name) == key)
+                {
+                    Some(slot) => *slot = (file_name.as_str(), library),
+                    None => resolved.push((file_name.as_str(), library)),
+                }
+            }
+        }
+        resolved
+    }
+
+    /// Merge patch libraries into `libraries`. A library replaces one with
+    /// the same [`library_key`] in place; new libraries are appended.
+    /// Returns the number of libraries replaced or added.
+    pub fn merge_libraries(&self, libraries: &mut Vec<Library>) -> usize {
+        let mut changed = 0;
+        for (file_name, library) in self.resolved_libraries() {
+            let key = library_key(&library.name);
+            match libraries
+                .iter_mut()
+                .find(|existing| library_key(&existing.name) == key)
+            {
+                Some(existing) if existing == library => continue,
+                Some(existing) => {
+                    log::debug!(
+                        "[patches] {} overrides {} with {}",
+                        file_name,
+                        existing.name,
+                        library.name
+                    );
+                    *existing = library.clone();
+                }
+                None => libraries.push(library.clone()),
+            }
+            changed += 1;
+        }
+        changed
+    }
+
+    pub fn apply(&self, manifest: &mut VersionManifest) -> usize {
+        self.merge_libraries(&mut manifest.libraries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn library_key_ignores_version_only() {
+        assert_eq!(library_key("org.ow2.asm:asm:9.5"), "org.ow2.asm:asm");
+        assert_eq!(
+            library_key("net.neoforged:forge:1.20.1-47.1.106:universal"),
+            "net.neoforged:forge:universal"
+        );
+        assert_eq!(
+            library_key("de.oceanlabs.mcp:mcp_config:1.20.1-20230612.114412@zip"),
+            "de.oceanlabs.mcp:mcp_config@zip"
+        );
+    }
+}
@@ -0,0 +1,271 @@
+//! Quilt loader profiles and hashed mappings.
+//!
+//! Quilt publishes its own intermediary, `org.quiltmc:hashed`, next to
+//! Fabric's. It is fetched from Quilt's meta and maven so the classpath has
+//! it even when a loader profile leaves it out. Loader profiles come from
+//! Quilt's meta as full version JSONs (`inheritsFrom` the vanilla version);
+//! the Modrinth mirror is only used when Quilt's meta is unreachable.
+
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+use crate::game::installer::core::downloader::download_to_path;
+use crate::game::installer::modloaders::fabric::fetch_maven_sha1;
+use crate::game::installer::types::{InstallSpec, ProgressReporter};
+use crate::game::installer::{track_artifact_from_path, try_restore_artifact};
+use crate::game::launcher::version_parser::{Artifact, Library, LibraryDownloads, VersionManifest};
+use crate::game::metadata::ModrinthLoaderProfile;
+
+pub const QUILT_META_URL: &str = "https://meta.quiltmc.org";
+pub const QUILT_RELEASE_MAVEN_URL: &str = "https://maven.quiltmc.org/repository/release";
+pub(crate) const HASHED_GROUP: &str = "org.quiltmc:hashed";
+
+#[derive(Debug, Deserialize)]
+struct HashedVersion {
+    version: String,
+}
+
+/// Downloaded hashed mappings jar for one Minecraft version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashedArtifact {
+    pub mc_version: String,
+    pub url: String,
+    pub sha1: String,
+    pub size: u64,
+    pub path: PathBuf,
+}
+
+/// Quilt meta endpoint listing the hashed mappings for `mc_version`.
+pub fn hashed_meta_url(mc_version: &str) -> String {
+    format!("{}/v3/versions/hashed/{}", QUILT_META_URL, mc_version)
+}
+
+/// Path of the hashed jar relative to the libraries directory.
+pub fn hashed_relative_path(mc_version: &str) -> String {
+    format!("org/quiltmc/hashed/{0}/hashed-{0}.jar", mc_version)
+}
+
+/// Maven URL of the hashed jar for `mc_version`.
+pub fn hashed_jar_url(mc_version: &str) -> String {
+    format!(
+        "{}/{}",
+        QUILT_RELEASE_MAVEN_URL,
+        hashed_relative_path(mc_version)
+    )
+}
+
+pub fn hashed_path(libraries_dir: &Path, mc_version: &str) -> PathBuf {
+    libraries_dir.join(hashed_relative_path(mc_version))
+}
+
+/// Quilt meta endpoint serving the launcher profile of one loader version.
+pub fn loader_profile_url(mc_version: &str, loader_version: &str) -> String {
+    format!(
+        "{}/v3/versions/loader/{}/{}/profile/json",
+        QUILT_META_URL, mc_version, loader_version
+    )
+}
+
+/// Make sure the hashed mappings for `spec.version_id` are present and match
+/// the SHA-1 on Quilt's maven. Returns `None` when Quilt publishes no hashed
+/// mappings for the version; current loaders run on intermediary alone.
+pub async fn download_hashed_mappings(
+    spec: &InstallSpec,
+    client: &Client,
+    reporter: &dyn ProgressReporter,
+) -> Result<Option<HashedArtifact>> {
+    reporter.set_message(&format!(
+        "Fetching Quilt hashed mappings for {}",
+        spec.version_id
+    ));
+
+    let meta_url = hashed_meta_url(&spec.version_id);
+    let response = client
+        .get(&meta_url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to request {}", meta_url))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let versions: Vec<HashedVersion> = response
+        .error_for_status()
+        .with_context(|| format!("Hashed mappings request failed: {}", meta_url))?
+        .json()
+        .await
+        .with_context(|| format!("Failed to parse hashed mappings list: {}", meta_url))?;
+    let Some(mc_version) = versions
+        .into_iter()
+        .map(|entry| entry.version)
+        .find(|version| version == &spec.version_id)
+    else {
+        return Ok(None);
+    };
+
+    let url = hashed_jar_url(&mc_version);
+    let sha1 = fetch_maven_sha1(client, &url).await?;
+
+    let path = hashed_path(&spec.libraries_dir(), &mc_version);
+    let label = format!("libraries/{}", hashed_relative_path(&mc_version));
+    if !path.exists() {
+        try_restore_artifact(&label, &path).await?;
+    }
+    // Verifies an existing or restored jar and re-downloads it on mismatch.
+    download_to_path(client, &url, &path, Some(&sha1), reporter).await?;
+    track_artifact_from_path(label, &path, None, Some(url.clone())).await?;
+
+    let size = tokio::fs::metadata(&path)
+        .await
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+    Ok(Some(HashedArtifact {
+        mc_version,
+        url,
+        sha1,
+        size,
+        path,
+    }))
+}
+
+/// Add the hashed mappings to a loader manifest's libraries, replacing any
+/// hashed entry the profile declared so the verified download is used.
+/// Returns whether the manifest changed.
+pub fn add_hashed_library(manifest: &mut VersionManifest, hashed: &HashedArtifact) -> bool {
+    let name = format!("{}:{}", HASHED_GROUP, hashed.mc_version);
+    let library = Library {
+        name: name.clone(),
+        downloads: Some(LibraryDownloads {
+            artifact: Some(Artifact {
+                path: Some(hashed_relative_path(&hashed.mc_version)),
+                url: Some(hashed.url.clone()),
+                sha1: Some(hashed.sha1.clone()),
+                size: Some(hashed.size),
+            }),
+            classifiers: None,
+        }),
+        url: Some(format!("{}/", QUILT_RELEASE_MAVEN_URL)),
+        rules: None,
+        natives: None,
+        extract: None,
+        include_in_classpath: true,
+    };
+
+    let existing = manifest
+        .libraries
+        .iter()
+        .position(|lib| lib.name.starts_with(&format!("{}:", HASHED_GROUP)));
+    match existing {
+        Some(index) if manifest.libraries[index].downloads.is_some() => false,
+        Some(index) => {
+            manifest.libraries[index] = library;
+            true
+        }
+        None => {
+            manifest.libraries.push(library);
+            true
+        }
+    }
+}
+
+/// Fetch the launcher profile of a Quilt loader version from Quilt's meta.
+/// The profile is a version JSON with plain maven libraries (no checksums).
+pub async fn fetch_loader_profile(
+    client: &Client,
+    mc_version: &str,
+    loader_version: &str,
+) -> Result<ModrinthLoaderProfile> {
+    let url = loader_profile_url(mc_version, loader_version);
+    client
+        .get(&url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to request Quilt profile {}", url))?
+        .error_for_status()
+        .with_context(|| format!("Quilt profile request failed: {}", url))?
+        .json()
+        .await
+        .with_context(|| format!("Failed to parse Quilt profile: {}", url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quilt_urls_are_per_minecraft_version() {
+        assert_eq!(
+            hashed_meta_url("1.20.1"),
+            "https://meta.quiltmc.org/v3/versions/hashed/1.20.1"
+        );
+        assert_eq!(
+            hashed_jar_url("1.20.1"),
+            "https://maven.quiltmc.org/repository/release/org/quiltmc/hashed/1.20.1/hashed-1.20.1.jar"
+        );
+        assert_eq!(
+            hashed_path(Path::new("/data/libraries"), "1.20.1"),
+            Path::new("/data/libraries/org/quiltmc/hashed/1.20.1/hashed-1.20.1.jar")
+        );
+        assert_eq!(
+            loader_profile_url("1.20.1", "0.26.4"),
+            "https://meta.quiltmc.org/v3/versions/loader/1.20.1/0.26.4/profile/json"
+        );
+    }
+
+    #[test]
+    fn quilt_profile_becomes_a_loader_manifest_with_hashed_mappings() {
+        let profile: ModrinthLoaderProfile = serde_json::from_value(serde_json::json!({
+            "id": "quilt-loader-0.26.4-1.20.1",
+            "inheritsFrom": "1.20.1",
+            "type": "release",
+            "mainClass": "org.quiltmc.loader.impl.launch.knot.KnotClient",
+            "arguments": {
+                "game": [],
+                "jvm": ["-DFabricMcEmu= net.minecraft.client.main.Main "]
+            },
+            "libraries": [
+                {"name": "org.quiltmc:hashed:1.20.1", "url": "https://maven.quiltmc.org/repository/release/"},
+                {"name": "net.fabricmc:intermediary:1.20.1", "url": "https://maven.fabricmc.net/"},
+                {"name": "org.quiltmc:quilt-loader:0.26.4", "url": "https://maven.quiltmc.org/repository/release/"}
+            ],
+            "releaseTime": "2024-09-01T00:00:00+00:00",
+            "time": "2024-09-01T00:00:00+00:00"
+        }))
+        .unwrap();
+        let spec = InstallSpec::new(
+            "1.20.1".to_string(),
+            PathBuf::from("/data"),
+            PathBuf::from("/data/instances/quilt"),
+        );
+        let mut manifest =
+            crate::game::installer::modloaders::profile_to_version_manifest(&profile, &spec);
+        assert_eq!(manifest.inherits_from.as_deref(), Some("1.20.1"));
+        assert_eq!(manifest.arguments.as_ref().unwrap().jvm.len(), 1);
+
+        let hashed = HashedArtifact {
+            mc_version: "1.20.1".to_string(),
+            url: hashed_jar_url("1.20.1"),
+            sha1: "0".repeat(40),
+            size: 42,
+            path: hashed_path(Path::new("/data/libraries"), "1.20.1"),
+        };
+        assert!(add_hashed_library(&mut manifest, &hashed));
+        assert!(!add_hashed_library(&mut manifest, &hashed));
+
+        let hashed_libs: Vec<_> = manifest
+            .libraries
+            .iter()
+            .filter(|lib| lib.name.starts_with("org.quiltmc:hashed:"))
+            .collect();
+        assert_eq!(hashed_libs.len(), 1);
+        let download = hashed_libs[0]
+            .downloads
+            .as_ref()
+            .unwrap()
+            .artifact
+            .as_ref()
+            .unwrap();
+        assert_eq!(download.sha1.as_deref(), Some(hashed.sha1.as_str()));
+    }
+}
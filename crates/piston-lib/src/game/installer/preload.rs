@@ -0,0 +1,143 @@
+//! Background download of version JSONs ahead of installation.
+//!
+//! The installer's first step fetches `versions/<id>/<id>.json` and skips the
+//! download when the file is already there, so fetching those files for
+//! versions the user is likely to install makes that step instant.
+
+use super::core::downloader::download_to_path;
+use super::types::SilentProgressReporter;
+use anyhow::{anyhow, Result};
+use futures::stream::{self, StreamExt};
+use std::path::{Path, PathBuf};
+
+/// Version JSONs downloaded at the same time.
+pub const PRELOAD_CONCURRENCY: usize = 4;
+
+/// Where the installer reads and writes the version JSON for `version_id`.
+pub fn version_json_path(data_dir: &Path, version_id: &str) -> PathBuf {
+    data_dir
+        .join("versions")
+        .join(version_id)
+        .join(format!("{}.json", version_id))
+}
+
+/// Mirror the installer downloads version JSONs from.
+pub fn version_json_url(version_id: &str) -> String {
+    format!(
+        "https://launcher-meta.modrinth.com/minecraft/v0/versions/{}.json",
+        version_id
+    )
+}
+
+pub struct PreloadManager;
+
+impl PreloadManager {
+    /// Download the version JSON of every listed version that isn't on disk
+    /// yet, [`PRELOAD_CONCURRENCY`] at a time. Every version is attempted;
+    /// the error lists the ones that failed.
+    pub async fn preload_version_manifests(
+        versions_to_preload: Vec<String>,
+        data_dir: &Path,
+    ) -> Result<()> {
+        let pending = pending_versions(versions_to_preload, data_dir);
+        if pending.is_empty() {
+            return Ok(());
+        }
+        log::info!(
+            "[preload] Fetching {} version manifest(s): {}",
+            pending.len(),
+            pending.join(", ")
+        );
+
+        let client = crate::client::shared_client();
+        let failed: Vec<String> = stream::iter(pending)
+            .map(|version_id| async move {
+                let path = version_json_path(data_dir, &version_id);
+                let result = download_to_path(
+                    client,
+                    &version_json_url(&version_id),
+                    &path,
+                    None,
+                    &SilentProgressReporter,
+                )
+                .await;
+                match result {
+                    Ok(()) => None,
+                    Err(e) => {
+                        log::warn!("[preload] Failed to fetch {}: {}", version_id, e);
+                        Some(version_id)
+                    }
+                }
+            })
+            .buffer_unordered(PRELOAD_CONCURRENCY)
+            .filter_map(|failed| async move { failed })
+            .collect()
+            .await;
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Failed to preload version manifests: {}",
+                failed.join(", ")
+            ))
+        }
+    }
+}
+
+/// Distinct, well-formed version ids whose JSON isn't on disk, in input order.
+fn pending_versions(versions: Vec<String>, data_dir: &Path) -> Vec<String> {
+    let mut pending: Vec<String> = Vec::new();
+    for version_id in versions {
+        let valid = !version_id.is_empty()
+            && crate::utils::paths::validate_relative_path(&version_id)
+                .is_ok_and(|path| path.components().count() == 1);
+        if !valid {
+            log::warn!("[preload] Ignoring invalid version id {:?}", version_id);
+            continue;
+        }
+        if pending.contains(&version_id) || version_json_path(data_dir, &version_id).exists() {
+            continue;
+        }
+        pending.push(version_id);
+    }
+    pending
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_downloaded_duplicate_and_invalid_versions() {
+        let tmp = tempfile::tempdir().unwrap();
+        let existing = version_json_path(tmp.path(), "1.20.1");
+        std::fs::create_dir_all(existing.parent().unwrap()).unwrap();
+        std::fs::write(&existing, b"{}").unwrap();
+
+        let pending = pending_versions(
+            vec![
+                "1.21.1".to_string(),
+                "1.20.1".to_string(),
+                "1.21.1".to_string(),
+                "../1.8.9".to_string(),
+                "24w14a".to_string(),
+            ],
+            tmp.path(),
+        );
+
+        assert_eq!(pending, vec!["1.21.1".to_string(), "24w14a".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn nothing_to_fetch_succeeds_without_network() {
+        let tmp = tempfile::tempdir().unwrap();
+        let existing = version_json_path(tmp.path(), "1.20.1");
+        std::fs::create_dir_all(existing.parent().unwrap()).unwrap();
+        std::fs::write(&existing, b"{}").unwrap();
+
+        PreloadManager::preload_version_manifests(vec!["1.20.1".to_string()], tmp.path())
+            .await
+            .unwrap();
+    }
+}
@@ -42,6 +42,19 @@ pub trait ProgressReporter: Send + Sync {
     /// Update bytes transferred for download progress
     fn update_bytes(&self, transferred: u64, total: Option<u64>);
 
+    /// Chunk-level progress of a single file download, with its average
+    /// speed and estimated seconds left. Defaults to [`Self::update_bytes`].
+    fn update_download_progress(
+        &self,
+        _file_name: &str,
+        bytes_done: u64,
+        bytes_total: Option<u64>,
+        _speed_bps: u64,
+        _eta_secs: Option<u64>,
+    ) {
+        self.update_bytes(bytes_done, bytes_total);
+    }
+
     /// Set overall percentage (0-100, or -1 for indeterminate)
     fn set_percent(&self, percent: i32);
 
@@ -72,6 +85,10 @@ pub trait ProgressReporter: Send + Sync {
     fn is_dry_run(&self) -> bool {
         false
     }
+
+    /// Record an install metric such as `assets_ms` or `libraries_bytes`.
+    /// See [`crate::game::installer::metrics`] for the keys.
+    fn record_metric(&self, _key: &str, _value: u64) {}
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -190,6 +207,36 @@ impl CancelToken {
     }
 }
 
+/// How many downloads run at once in each install phase. Asset objects are
+/// tiny and benefit from many parallel requests; mod jars are large and come
+/// from CDNs that throttle clients opening too many connections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadConcurrency {
+    /// Asset objects from the asset index.
+    pub assets: usize,
+    /// Libraries and natives.
+    pub libraries: usize,
+    /// Mod jars and other files from Modrinth and CurseForge.
+    pub mods: usize,
+}
+
+impl DownloadConcurrency {
+    /// Use the same limit for every phase, as the single `concurrency` value did.
+    pub const fn uniform(concurrency: usize) -> Self {
+        Self {
+            assets: concurrency,
+            libraries: concurrency,
+            mods: concurrency,
+        }
+    }
+}
+
+impl Default for DownloadConcurrency {
+    fn default() -> Self {
+        Self::uniform(8)
+    }
+}
+
 /// Installation specification
 #[derive(Debug, Clone)]
 pub struct InstallSpec {
@@ -214,12 +261,15 @@ pub struct InstallSpec {
     /// If true, don't actually download or write files, just verify what's needed
     pub dry_run: bool,
 
-    /// Number of concurrent downloads
-    pub concurrency: usize,
+    /// Number of concurrent downloads per install phase
+    pub concurrency: DownloadConcurrency,
 
     /// Maximum number of bytes to retain in the shared artifact cache.
     pub artifact_cache_max_bytes: u64,
 
+    /// Store newly cached artifacts zstd-compressed.
+    pub artifact_cache_compression: bool,
+
     /// If true, force overwrite of user config files during modpack repair.
     /// Backend/CLI only; not exposed in the frontend.
     #[doc(hidden)]
@@ -249,8 +299,9 @@ impl InstallSpec {
             game_dir,
             java_path: None,
             dry_run: false,
-            concurrency: 8,
+            concurrency: DownloadConcurrency::default(),
             artifact_cache_max_bytes: DEFAULT_ARTIFACT_CACHE_MAX_BYTES,
+            artifact_cache_compression: false,
             force_overwrite_configs: false,
             repair_scope: RepairScope::default(),
             remediation_policy: RemediationPolicy::default(),
@@ -268,6 +319,12 @@ impl InstallSpec {
         self.data_dir.join("libraries")
     }
 
+    /// Full path of a library below [`libraries_dir`](Self::libraries_dir),
+    /// in extended-length form when Windows would otherwise reject it.
+    pub fn library_path(&self, relative: &str) -> PathBuf {
+        crate::utils::paths::long_path(&self.libraries_dir().join(relative))
+    }
+
     /// Get the path to the assets directory
     pub fn assets_dir(&self) -> PathBuf {
         self.data_dir.join("assets")
@@ -283,9 +340,9 @@ impl InstallSpec {
         self.data_dir.join("jre")
     }
 
-    /// Get the path to the natives directory for this version
+    /// Get the path to the natives directory for this installed version
     pub fn natives_dir(&self) -> PathBuf {
-        self.data_dir.join("natives").join(&self.version_id)
+        crate::game::launcher::get_natives_dir(&self.data_dir, &self.installed_version_id())
     }
 
     /// Compute the canonical installed version id to use on-disk when this spec
@@ -325,8 +382,9 @@ mod tests {
             game_dir: std::path::PathBuf::from("/tmp/g"),
             java_path: None,
             dry_run: false,
-            concurrency: 8,
+            concurrency: DownloadConcurrency::uniform(8),
             artifact_cache_max_bytes: DEFAULT_ARTIFACT_CACHE_MAX_BYTES,
+            artifact_cache_compression: false,
             force_overwrite_configs: false,
             repair_scope: RepairScope::Full,
             remediation_policy: RemediationPolicy::RepairIfNeeded,
@@ -346,8 +404,9 @@ mod tests {
             game_dir: std::path::PathBuf::from("/tmp/g"),
             java_path: None,
             dry_run: false,
-            concurrency: 8,
+            concurrency: DownloadConcurrency::uniform(8),
             artifact_cache_max_bytes: DEFAULT_ARTIFACT_CACHE_MAX_BYTES,
+            artifact_cache_compression: false,
             force_overwrite_configs: false,
             repair_scope: RepairScope::Full,
             remediation_policy: RemediationPolicy::RepairIfNeeded,
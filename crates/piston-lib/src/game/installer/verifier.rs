@@ -15,7 +15,7 @@ use std::process::Command;
 const ASSET_SPOT_CHECK_COUNT: usize = 20;
 
 /// Compute SHA1 hash of a file. Returns hex string.
-fn compute_sha1(path: &Path) -> Result<String> {
+pub(crate) fn compute_sha1(path: &Path) -> Result<String> {
     let mut file = std::fs::File::open(path)?;
     let mut hasher = Sha1::new();
     let mut buffer = [0u8; 8192];
@@ -692,9 +692,10 @@ mod tests {
             game_dir,
             java_path: None,
             dry_run: false,
-            concurrency: 4,
+            concurrency: crate::game::installer::types::DownloadConcurrency::uniform(4),
             artifact_cache_max_bytes:
                 crate::game::installer::types::DEFAULT_ARTIFACT_CACHE_MAX_BYTES,
+            artifact_cache_compression: false,
             force_overwrite_configs: false,
             repair_scope: crate::game::installer::types::RepairScope::Full,
             remediation_policy: crate::game::installer::types::RemediationPolicy::RepairIfNeeded,
@@ -753,9 +754,10 @@ mod tests {
             game_dir,
             java_path: None,
             dry_run: false,
-            concurrency: 4,
+            concurrency: crate::game::installer::types::DownloadConcurrency::uniform(4),
             artifact_cache_max_bytes:
                 crate::game::installer::types::DEFAULT_ARTIFACT_CACHE_MAX_BYTES,
+            artifact_cache_compression: false,
             force_overwrite_configs: false,
             repair_scope: crate::game::installer::types::RepairScope::Full,
             remediation_policy: crate::game::installer::types::RemediationPolicy::RepairIfNeeded,
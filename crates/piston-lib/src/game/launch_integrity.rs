@@ -0,0 +1,261 @@
+//! Integrity spot-check of the client jar and classpath libraries at launch.
+//!
+//! Corrupted jars (bit rot, antivirus tampering) otherwise surface as
+//! `ClassFormatError` crashes. The check hashes the vanilla client jar, a
+//! small random sample of libraries and every file flagged by an earlier
+//! check, and moves mismatches into `<data_dir>/quarantine/` so the regular
+//! launch repair sees them as missing and downloads them again. Full
+//! verification stays with the repair command.
+
+use crate::game::modpack::manifest::compute_file_sha1;
+use crate::game::runtime_plan::RuntimePlan;
+use rand::seq::IndexedRandom;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Libraries hashed per launch besides flagged ones. Small enough to stay
+/// well under a second on an SSD.
+pub const LIBRARY_SAMPLE_SIZE: usize = 8;
+
+pub const QUARANTINE_DIR: &str = "quarantine";
+
+/// Paths that failed a check, re-checked on every launch until they pass.
+const FLAGGED_FILE: &str = "flagged.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuarantinedArtifact {
+    pub artifact_class: String,
+    pub original_path: PathBuf,
+    /// Where the corrupt copy was moved; `None` when it had to be deleted.
+    pub quarantine_path: Option<PathBuf>,
+    pub expected_sha1: String,
+    pub actual_sha1: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpotCheckReport {
+    pub checked: usize,
+    pub quarantined: Vec<QuarantinedArtifact>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Candidate {
+    artifact_class: &'static str,
+    path: PathBuf,
+    sha1: String,
+}
+
+/// Hash the client jar, up to `sample_size` random libraries and all flagged
+/// files of `plan`, quarantining any that don't match their published SHA-1.
+pub fn spot_check(plan: &RuntimePlan, sample_size: usize) -> SpotCheckReport {
+    let quarantine_root = plan.request.data_dir.join(QUARANTINE_DIR);
+    let mut flagged = load_flagged(&quarantine_root);
+    let candidates = select_candidates(plan, &flagged, sample_size);
+    let report = check_candidates(candidates, &quarantine_root, &mut flagged);
+
+    if let Err(e) = save_flagged(&quarantine_root, &flagged) {
+        log::warn!("[launch-integrity] Failed to save flagged files: {}", e);
+    }
+    log::info!(
+        "[launch-integrity] checked={} quarantined={}",
+        report.checked,
+        report.quarantined.len()
+    );
+    report
+}
+
+fn check_candidates(
+    candidates: Vec<Candidate>,
+    quarantine_root: &Path,
+    flagged: &mut BTreeSet<String>,
+) -> SpotCheckReport {
+    let mut report = SpotCheckReport::default();
+    let batch_dir = quarantine_root.join(chrono::Utc::now().format("%Y%m%dT%H%M%S").to_string());
+    for candidate in candidates {
+        let Ok(actual) = compute_file_sha1(&candidate.path) else {
+            continue;
+        };
+        report.checked += 1;
+        let key = candidate.path.to_string_lossy().to_string();
+        if actual.eq_ignore_ascii_case(&candidate.sha1) {
+            flagged.remove(&key);
+            continue;
+        }
+
+        log::warn!(
+            "[launch-integrity] {} hash mismatch: path={} expected={} actual={}",
+            candidate.artifact_class,
+            candidate.path.display(),
+            candidate.sha1,
+            actual
+        );
+        let quarantine_path = quarantine_file(&candidate.path, &batch_dir);
+        flagged.insert(key);
+        report.quarantined.push(QuarantinedArtifact {
+            artifact_class: candidate.artifact_class.to_string(),
+            original_path: candidate.path,
+            quarantine_path,
+            expected_sha1: candidate.sha1,
+            actual_sha1: actual,
+        });
+    }
+    report
+}
+
+fn select_candidates(
+    plan: &RuntimePlan,
+    flagged: &BTreeSet<String>,
+    sample_size: usize,
+) -> Vec<Candidate> {
+    let mut candidates = Vec::new();
+    if let Some(sha1) = plan
+        .client_download
+        .as_ref()
+        .and_then(|download| download.sha1.clone())
+    {
+        if plan.vanilla_client_jar.is_file() {
+            candidates.push(Candidate {
+                artifact_class: "client-jar",
+                path: plan.vanilla_client_jar.clone(),
+                sha1,
+            });
+        }
+    }
+
+    let (flagged_libraries, others): (Vec<Candidate>, Vec<Candidate>) = plan
+        .manifest
+        .libraries
+        .iter()
+        .filter(|lib| lib.include_in_classpath && !lib.is_native && !lib.path.is_empty())
+        .filter_map(|lib| {
+            let sha1 = lib.sha1.clone().filter(|sha1| !sha1.is_empty())?;
+            let path = plan.libraries_dir.join(&lib.path);
+            path.is_file().then_some(Candidate {
+                artifact_class: "library",
+                path,
+                sha1,
+            })
+        })
+        .partition(|candidate| flagged.contains(candidate.path.to_string_lossy().as_ref()));
+
+    candidates.extend(flagged_libraries);
+    candidates.extend(
+        others
+            .choose_multiple(&mut rand::rng(), sample_size)
+            .cloned(),
+    );
+    candidates
+}
+
+/// Move `path` into `batch_dir`, deleting it when the move fails so a corrupt
+/// file is never left in place.
+fn quarantine_file(path: &Path, batch_dir: &Path) -> Option<PathBuf> {
+    let file_name = path.file_name()?;
+    let destination = batch_dir.join(file_name);
+    let moved =
+        std::fs::create_dir_all(batch_dir).and_then(|_| std::fs::rename(path, &destination));
+    match moved {
+        Ok(()) => Some(destination),
+        Err(e) => {
+            log::warn!(
+                "[launch-integrity] Could not quarantine {}: {}; deleting it",
+                path.display(),
+                e
+            );
+            let _ = std::fs::remove_file(path);
+            None
+        }
+    }
+}
+
+fn load_flagged(quarantine_root: &Path) -> BTreeSet<String> {
+    std::fs::read(quarantine_root.join(FLAGGED_FILE))
+        .ok()
+        .and_then(|raw| serde_json::from_slice(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_flagged(quarantine_root: &Path, flagged: &BTreeSet<String>) -> std::io::Result<()> {
+    let path = quarantine_root.join(FLAGGED_FILE);
+    if flagged.is_empty() {
+        return match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+    std::fs::create_dir_all(quarantine_root)?;
+    std::fs::write(path, serde_json::to_vec_pretty(flagged)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// SHA-1 of `b"library"`.
+    const LIBRARY_SHA1: &str = "00299a408dc3498a3cd7bae6db588f3324654d76";
+
+    fn candidate(path: PathBuf, sha1: &str) -> Candidate {
+        Candidate {
+            artifact_class: "library",
+            path,
+            sha1: sha1.to_string(),
+        }
+    }
+
+    #[test]
+    fn mismatched_files_are_quarantined_and_flagged() {
+        let tmp = tempfile::tempdir().unwrap();
+        let good = tmp.path().join("good.jar");
+        let bad = tmp.path().join("bad.jar");
+        std::fs::write(&good, b"library").unwrap();
+        std::fs::write(&bad, b"bit rot").unwrap();
+        let quarantine_root = tmp.path().join(QUARANTINE_DIR);
+        let mut flagged = BTreeSet::from([good.to_string_lossy().to_string()]);
+
+        let report = check_candidates(
+            vec![
+                candidate(good.clone(), &LIBRARY_SHA1.to_uppercase()),
+                candidate(bad.clone(), LIBRARY_SHA1),
+            ],
+            &quarantine_root,
+            &mut flagged,
+        );
+
+        assert_eq!(report.checked, 2);
+        assert_eq!(report.quarantined.len(), 1);
+        let quarantined = &report.quarantined[0];
+        assert_eq!(quarantined.original_path, bad);
+        assert!(!bad.exists());
+        let moved = quarantined.quarantine_path.as_ref().unwrap();
+        assert!(moved.starts_with(&quarantine_root));
+        assert_eq!(std::fs::read(moved).unwrap(), b"bit rot");
+        assert_eq!(flagged, BTreeSet::from([bad.to_string_lossy().to_string()]));
+    }
+
+    #[test]
+    fn unreadable_files_are_skipped() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut flagged = BTreeSet::new();
+        let report = check_candidates(
+            vec![candidate(tmp.path().join("gone.jar"), LIBRARY_SHA1)],
+            &tmp.path().join(QUARANTINE_DIR),
+            &mut flagged,
+        );
+        assert_eq!(report, SpotCheckReport::default());
+    }
+
+    #[test]
+    fn flagged_list_round_trips_and_is_removed_when_empty() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join(QUARANTINE_DIR);
+        let flagged = BTreeSet::from(["/data/libraries/a.jar".to_string()]);
+
+        save_flagged(&root, &flagged).unwrap();
+        assert_eq!(load_flagged(&root), flagged);
+
+        save_flagged(&root, &BTreeSet::new()).unwrap();
+        assert!(!root.join(FLAGGED_FILE).exists());
+        assert!(load_flagged(&root).is_empty());
+    }
+}
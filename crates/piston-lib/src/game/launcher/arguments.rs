@@ -1,10 +1,11 @@
 /// Argument builder for Minecraft launcher
 use crate::game::installer::types::OsType;
+use crate::game::launcher::classpath::classpath_uses_lwjgl3;
 use crate::game::launcher::types::LaunchSpec;
 use crate::game::launcher::unified_manifest::UnifiedManifest;
 use crate::game::launcher::version_parser::{Argument, ArgumentValue};
 use dunce::canonicalize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 /// Build JVM arguments for launching the game
@@ -17,19 +18,11 @@ pub fn build_jvm_arguments(
 ) -> Vec<String> {
     let mut args = Vec::new();
 
-    // 1. Handle Memory Arguments
-    let has_ms = spec.jvm_args.iter().any(|a| a.starts_with("-Xms"));
-    let has_mx = spec.jvm_args.iter().any(|a| a.starts_with("-Xmx"));
-
-    if !has_ms {
-        let min = spec.min_memory.unwrap_or(2048);
-        args.push(format!("-Xms{}M", min));
-    }
-
-    if !has_mx {
-        let max = spec.max_memory.unwrap_or(4096);
-        args.push(format!("-Xmx{}M", max));
-    }
+    // 1. Handle Memory Arguments (custom -Xms/-Xmx win during deduplication)
+    let min = spec.min_memory.unwrap_or(2048);
+    args.push(format!("-Xms{}M", min));
+    let max = spec.max_memory.unwrap_or(4096);
+    args.push(format!("-Xmx{}M", max));
 
     // 2. Add Default G1GC settings if not present
     let has_gc = spec.jvm_args.iter().any(|a| a.contains("GC"));
@@ -54,25 +47,21 @@ pub fn build_jvm_arguments(
     // Detection: LWJGL 3 uses group `org.lwjgl` (e.g. `org.lwjgl:lwjgl:3.3.1`)
     // while LWJGL 2 uses `org.lwjgl.lwjgl` (e.g. `org.lwjgl.lwjgl:lwjgl:2.9.4`).
     // Any library starting with `org.lwjgl:lwjgl` indicates LWJGL 3 is in use.
+    // The classpath jars are checked too, since patched or imported
+    // manifests don't always keep Mojang's library names.
     let is_macos = matches!(os, OsType::MacOS | OsType::MacOSArm64);
     if is_macos {
         let has_lwjgl3 = manifest
             .libraries
             .iter()
-            .any(|lib| lib.name.starts_with("org.lwjgl:lwjgl"));
+            .any(|lib| lib.name.starts_with("org.lwjgl:lwjgl"))
+            || classpath_uses_lwjgl3(classpath, os);
         let has_start_on_first = spec.jvm_args.iter().any(|a| a == "-XstartOnFirstThread");
         if has_lwjgl3 && !has_start_on_first {
             args.push("-XstartOnFirstThread".to_string());
         }
     }
 
-    // 3. Add Custom JVM args
-    for arg in &spec.jvm_args {
-        // Skip memory flags we already handled via spec.min/max (if they were somehow passed in both)
-        // Though the logic above already avoids duplicates if they were in spec.jvm_args
-        args.push(arg.clone());
-    }
-
     // Collect manifest JVM arguments first to check for duplicates
     let mut manifest_args = Vec::new();
     let variables = build_jvm_variables(spec, manifest, natives_dir, classpath, os);
@@ -133,6 +122,9 @@ pub fn build_jvm_arguments(
         }
     }
 
+    // 5. Add Custom JVM args last so they override generated and manifest flags
+    args.extend(spec.jvm_args.iter().cloned());
+
     // For legacy versions (like 1.0) that don't have JVM args in manifest, add classpath manually
     let has_classpath = args
         .iter()
@@ -142,9 +134,92 @@ pub fn build_jvm_arguments(
         args.push(classpath.to_string());
     }
 
+    let (args, conflicts) = deduplicate_jvm_args(args);
+    for conflict in conflicts {
+        log::warn!(
+            "Conflicting JVM flag {}: keeping '{}', dropping '{}'",
+            conflict.flag_prefix,
+            conflict.kept_value,
+            conflict.dropped_value
+        );
+    }
     args
 }
 
+/// A JVM flag set more than once with different values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JvmArgConflict {
+    /// Flag key, e.g. `-Xmx`, `-Dfile.encoding` or `-XX:MaxGCPauseMillis`
+    pub flag_prefix: String,
+    pub kept_value: String,
+    pub dropped_value: String,
+}
+
+/// Split a JVM flag into the key it is deduplicated by and its value.
+/// Flags that may legitimately repeat (`-Xlog:`, `-javaagent:`, module
+/// options, ...) return `None`.
+fn jvm_flag_key(arg: &str) -> Option<(String, String)> {
+    if let Some(option) = arg.strip_prefix("-XX:") {
+        // -XX:+Flag / -XX:-Flag toggle, -XX:Name=value sets
+        if let Some(name) = option
+            .strip_prefix('+')
+            .or_else(|| option.strip_prefix('-'))
+        {
+            return Some((format!("-XX:{}", name), option[..1].to_string()));
+        }
+        let (name, value) = option.split_once('=')?;
+        return Some((format!("-XX:{}", name), value.to_string()));
+    }
+    if let Some(property) = arg.strip_prefix("-D") {
+        let (name, value) = property.split_once('=').unwrap_or((property, ""));
+        return Some((format!("-D{}", name), value.to_string()));
+    }
+    ["-Xms", "-Xmx", "-Xss", "-Xmn"]
+        .into_iter()
+        .find(|prefix| arg.starts_with(prefix))
+        .map(|prefix| (prefix.to_string(), arg[prefix.len()..].to_string()))
+}
+
+/// Drop repeated `-X`, `-XX:` and `-D` flags. The last value of a flag wins
+/// so custom arguments (added last) override generated ones; differing
+/// values are returned as conflicts. Exact repeats are dropped silently and
+/// keep their first position, so `-XX:+UnlockExperimentalVMOptions` stays
+/// ahead of the options it unlocks.
+pub fn deduplicate_jvm_args(args: Vec<String>) -> (Vec<String>, Vec<JvmArgConflict>) {
+    let mut final_values: HashMap<String, String> = HashMap::new();
+    for arg in &args {
+        if let Some((key, value)) = jvm_flag_key(arg) {
+            final_values.insert(key, value);
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut conflicts = Vec::new();
+    let mut kept = Vec::with_capacity(args.len());
+    for arg in args {
+        let Some((key, value)) = jvm_flag_key(&arg) else {
+            // Plain values (e.g. the classpath after -cp) are always kept;
+            // other -X flags only lose verbatim repeats.
+            if !arg.starts_with("-X") || seen.insert(arg.clone()) {
+                kept.push(arg);
+            }
+            continue;
+        };
+        let final_value = &final_values[&key];
+        if &value != final_value {
+            conflicts.push(JvmArgConflict {
+                flag_prefix: key,
+                kept_value: final_value.clone(),
+                dropped_value: value,
+            });
+        } else if seen.insert(key) {
+            kept.push(arg);
+        }
+    }
+
+    (kept, conflicts)
+}
+
 /// Build game arguments for launching the game
 pub fn build_game_arguments(
     spec: &LaunchSpec,
@@ -153,7 +228,7 @@ pub fn build_game_arguments(
 ) -> Vec<String> {
     let mut args = Vec::new();
 
-    let variables = build_game_variables(spec, manifest);
+    let variables = build_game_variables(spec, manifest, os);
 
     // Handle modern arguments format
     for arg in &manifest.game_arguments {
@@ -167,6 +242,16 @@ pub fn build_game_arguments(
         }
     }
 
+    // Versions without Quick Play (pre-1.20) join through the legacy flags.
+    if let Some((host, port)) = &spec.connect_server {
+        if !uses_placeholder(&manifest.game_arguments, "quickPlayMultiplayer") {
+            args.push("--server".to_string());
+            args.push(host.clone());
+            args.push("--port".to_string());
+            args.push(port.to_string());
+        }
+    }
+
     // Add custom game args, ignoring any empty strings
     args.extend(
         spec.game_args
@@ -178,6 +263,18 @@ pub fn build_game_arguments(
     args
 }
 
+/// Whether any of `arguments` references `${key}`
+fn uses_placeholder(arguments: &[Argument], key: &str) -> bool {
+    let placeholder = format!("${{{}}}", key);
+    arguments.iter().any(|arg| match arg {
+        Argument::Simple(s) => s.contains(&placeholder),
+        Argument::Conditional { value, .. } => match value {
+            ArgumentValue::Single(s) => s.contains(&placeholder),
+            ArgumentValue::Multiple(parts) => parts.iter().any(|p| p.contains(&placeholder)),
+        },
+    })
+}
+
 /// Process a JVM argument (simple or conditional) - DOES NOT SPLIT ON WHITESPACE
 fn process_jvm_argument(
     arg: &Argument,
@@ -402,6 +499,7 @@ fn evaluate_rules(
                                 spec.window_width.is_some() && spec.window_height.is_some();
                             has_res == required
                         }
+                        "is_quick_play_multiplayer" => spec.connect_server.is_some() == required,
                         _ => {
                             // Unknown features: conservative, do not match
                             false
@@ -427,14 +525,41 @@ fn evaluate_rules(
     allow
 }
 
-/// Substitute variables in a string
+/// Placeholders the launcher knows about but only fills in some launches
+/// (quick play targets, custom resolution). Arguments using them are dropped
+/// quietly when they are unset.
+const OPTIONAL_PLACEHOLDERS: &[&str] = &[
+    "resolution_width",
+    "resolution_height",
+    "quickPlaySingleplayer",
+    "quickPlayMultiplayer",
+    "quickPlayRealms",
+    "assets_index_name",
+];
+
+/// Substitute variables in a string. Placeholders missing from `variables`
+/// are replaced with an empty string and logged, so no literal `${...}`
+/// reaches the game.
 pub fn substitute_variables(text: &str, variables: &HashMap<String, String>) -> String {
-    let mut result = text.to_string();
-
-    for (key, value) in variables {
-        let placeholder = format!("${{{}}}", key);
-        result = result.replace(&placeholder, value);
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let key = &rest[start + 2..start + 2 + end];
+        result.push_str(&rest[..start]);
+        match variables.get(key) {
+            Some(value) => result.push_str(value),
+            None => log::warn!(
+                "[arguments] Unknown placeholder ${{{}}} in '{}', substituting empty string",
+                key,
+                text
+            ),
+        }
+        rest = &rest[start + 2 + end + 1..];
     }
+    result.push_str(rest);
 
     // Modern Forge (1.13+) ModLauncher can be over-aggressive when scanning the library directory.
     // Since we use a shared global libraries folder, it may find "extra" JARs (like slim/srg variants)
@@ -475,7 +600,16 @@ fn contains_empty_placeholder(text: &str, variables: &HashMap<String, String>) -
                         return true;
                     }
                 }
-                None => return true,
+                None => {
+                    if !OPTIONAL_PLACEHOLDERS.contains(&key) {
+                        log::warn!(
+                            "[arguments] Unknown placeholder ${{{}}}, dropping '{}'",
+                            key,
+                            text
+                        );
+                    }
+                    return true;
+                }
             }
             idx = end + 1;
         } else {
@@ -557,7 +691,11 @@ fn build_jvm_variables(
 }
 
 /// Build game variable map
-fn build_game_variables(spec: &LaunchSpec, manifest: &UnifiedManifest) -> HashMap<String, String> {
+fn build_game_variables(
+    spec: &LaunchSpec,
+    manifest: &UnifiedManifest,
+    os: OsType,
+) -> HashMap<String, String> {
     let mut vars = HashMap::new();
 
     // Player info (support multiple common placeholders for compatibility)
@@ -619,6 +757,39 @@ fn build_game_variables(spec: &LaunchSpec, manifest: &UnifiedManifest) -> HashMa
     // Game assets directory (legacy versions like 1.0 use ${game_assets})
     vars.insert("game_assets".to_string(), assets_canon);
 
+    // Quick play multiplayer target (1.20+); the other targets stay unset.
+    if let Some((host, port)) = &spec.connect_server {
+        vars.insert(
+            "quickPlayMultiplayer".to_string(),
+            format!("{}:{}", host, port),
+        );
+    }
+
+    // Quick play writes its log here.
+    vars.insert(
+        "quickPlayPath".to_string(),
+        Path::new(&vars["game_directory"])
+            .join("quickPlay")
+            .join("log.json")
+            .to_string_lossy()
+            .to_string(),
+    );
+
+    // Some loader profiles use JVM-style placeholders in game arguments.
+    let lib_dir = spec.libraries_dir();
+    vars.insert(
+        "library_directory".to_string(),
+        canonicalize(&lib_dir)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|_| lib_dir.to_string_lossy().to_string()),
+    );
+    vars.insert(
+        "classpath_separator".to_string(),
+        os.classpath_separator().to_string(),
+    );
+    vars.insert("launcher_name".to_string(), "VestaLauncher".to_string());
+    vars.insert("launcher_version".to_string(), "1.0.0".to_string());
+
     // Resolution
     if let Some(width) = spec.window_width {
         vars.insert("resolution_width".to_string(), width.to_string());
@@ -680,6 +851,11 @@ mod tests {
             wrapper_command: None,
             pre_launch_hook: None,
             post_exit_hook: None,
+            safe_mode: false,
+            log_pipe: false,
+            connect_server: None,
+            process_priority: None,
+            cpu_affinity_mask: None,
         }
     }
 
@@ -713,7 +889,7 @@ mod tests {
     fn test_substitute_variables_no_match() {
         let vars = HashMap::new();
         let result = substitute_variables("--username ${username}", &vars);
-        assert_eq!(result, "--username ${username}");
+        assert_eq!(result, "--username ");
     }
 
     #[test]
@@ -738,6 +914,123 @@ mod tests {
         assert!(args.contains(&"-XX:+UseG1GC".to_string()));
     }
 
+    #[test]
+    fn macos_lwjgl3_starts_on_first_thread_with_natives_path() {
+        let spec = test_launch_spec(
+            "test",
+            "1.20.1",
+            std::path::PathBuf::from("."),
+            std::path::PathBuf::from("."),
+        );
+        let manifest = UnifiedManifest::from(test_version_manifest("1.20.1"));
+        let natives_dir = std::path::PathBuf::from("natives-missing");
+        let lwjgl3 = "/libs/lwjgl-3.3.1.jar:/libs/lwjgl-glfw-3.3.1.jar";
+        let lwjgl2 = "/libs/lwjgl-2.9.4-nightly-20150209.jar:/libs/lwjgl_util-2.9.4.jar";
+
+        let count = |args: &[String], flag: &str| args.iter().filter(|a| *a == flag).count();
+        let natives_arg = "-Djava.library.path=natives-missing";
+
+        for os in [OsType::MacOS, OsType::MacOSArm64] {
+            let args = build_jvm_arguments(&spec, &manifest, &natives_dir, lwjgl3, os);
+            assert_eq!(count(&args, "-XstartOnFirstThread"), 1, "{:?}", os);
+            assert_eq!(count(&args, natives_arg), 1, "{:?}", os);
+
+            let args = build_jvm_arguments(&spec, &manifest, &natives_dir, lwjgl2, os);
+            assert_eq!(count(&args, "-XstartOnFirstThread"), 0, "{:?}", os);
+            assert_eq!(count(&args, natives_arg), 1, "{:?}", os);
+        }
+
+        for os in [OsType::Linux, OsType::Windows] {
+            let args = build_jvm_arguments(&spec, &manifest, &natives_dir, lwjgl3, os);
+            assert_eq!(count(&args, "-XstartOnFirstThread"), 0, "{:?}", os);
+        }
+
+        let mut user_spec = spec.clone();
+        user_spec.jvm_args = vec!["-XstartOnFirstThread".to_string()];
+        let args = build_jvm_arguments(&user_spec, &manifest, &natives_dir, lwjgl3, OsType::MacOS);
+        assert_eq!(count(&args, "-XstartOnFirstThread"), 1);
+    }
+
+    #[test]
+    fn deduplicate_jvm_args_keeps_last_flag() {
+        let args: Vec<String> = [
+            "-Xmx4096M",
+            "-XX:+UseG1GC",
+            "-XX:G1ReservePercent=20",
+            "-XX:G1ReservePercent=20",
+            "-Dfile.encoding=UTF-8",
+            "-Xlog:gc",
+            "-cp",
+            "a.jar",
+            "-Xmx4G",
+            "-XX:-UseG1GC",
+            "-Dfile.encoding=Cp1252",
+            "-Xlog:safepoint",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let (kept, conflicts) = deduplicate_jvm_args(args);
+        assert_eq!(
+            kept,
+            vec![
+                "-XX:G1ReservePercent=20",
+                "-Xlog:gc",
+                "-cp",
+                "a.jar",
+                "-Xmx4G",
+                "-XX:-UseG1GC",
+                "-Dfile.encoding=Cp1252",
+                "-Xlog:safepoint",
+            ]
+        );
+        assert_eq!(
+            conflicts,
+            vec![
+                JvmArgConflict {
+                    flag_prefix: "-Xmx".to_string(),
+                    kept_value: "4G".to_string(),
+                    dropped_value: "4096M".to_string(),
+                },
+                JvmArgConflict {
+                    flag_prefix: "-XX:UseG1GC".to_string(),
+                    kept_value: "-".to_string(),
+                    dropped_value: "+".to_string(),
+                },
+                JvmArgConflict {
+                    flag_prefix: "-Dfile.encoding".to_string(),
+                    kept_value: "Cp1252".to_string(),
+                    dropped_value: "UTF-8".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_jvm_args_override_generated_memory() {
+        let mut spec = test_launch_spec(
+            "test",
+            "1.20.1",
+            std::path::PathBuf::from("."),
+            std::path::PathBuf::from("."),
+        );
+        spec.max_memory = Some(4096);
+        spec.jvm_args = vec!["-Xmx6G".to_string()];
+        let manifest = UnifiedManifest::from(test_version_manifest("1.20.1"));
+
+        let args = build_jvm_arguments(
+            &spec,
+            &manifest,
+            std::path::Path::new("natives"),
+            "cp",
+            OsType::Linux,
+        );
+        let max: Vec<_> = args.iter().filter(|a| a.starts_with("-Xmx")).collect();
+        assert_eq!(max, vec!["-Xmx6G"]);
+        assert!(args.contains(&"-Xms2048M".to_string()));
+    }
+
     #[test]
     fn build_variables_canonicalize_paths() {
         use std::fs;
@@ -783,7 +1076,7 @@ mod tests {
         assert_eq!(jvm_vars.get("library_directory").unwrap(), &expected_lib);
 
         // Game variables
-        let game_vars = build_game_variables(&spec, &unified);
+        let game_vars = build_game_variables(&spec, &unified, OsType::current());
         let expected_game = canonicalize(&spec.game_dir)
             .unwrap()
             .to_string_lossy()
@@ -950,4 +1243,247 @@ mod tests {
             "windows rule should match WindowsArm64"
         );
     }
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    enum Scope {
+        Jvm,
+        Game,
+    }
+
+    enum Expect {
+        Value(&'static str),
+        NonEmpty,
+        Unset,
+    }
+
+    const ARGUMENT_FIXTURES: &[(&str, &str)] = &[
+        (
+            "vanilla-1.20.1",
+            include_str!("../../../tests/fixtures/arguments/vanilla-1.20.1.json"),
+        ),
+        (
+            "vanilla-1.12.2",
+            include_str!("../../../tests/fixtures/arguments/vanilla-1.12.2.json"),
+        ),
+        (
+            "vanilla-1.7.10",
+            include_str!("../../../tests/fixtures/arguments/vanilla-1.7.10.json"),
+        ),
+        (
+            "vanilla-1.5.2",
+            include_str!("../../../tests/fixtures/arguments/vanilla-1.5.2.json"),
+        ),
+        (
+            "forge-1.20.1",
+            include_str!("../../../tests/fixtures/arguments/forge-1.20.1.json"),
+        ),
+        (
+            "neoforge-1.21.1",
+            include_str!("../../../tests/fixtures/arguments/neoforge-1.21.1.json"),
+        ),
+    ];
+
+    /// Every placeholder seen in the fixtures and what the launch context
+    /// resolves it to.
+    const PLACEHOLDERS: &[(Scope, &str, Expect)] = &[
+        (Scope::Game, "auth_player_name", Expect::Value("Steve")),
+        (Scope::Game, "auth_session", Expect::Value("token")),
+        (Scope::Game, "version_name", Expect::Value("1.20.1")),
+        (Scope::Game, "game_directory", Expect::NonEmpty),
+        (Scope::Game, "assets_root", Expect::NonEmpty),
+        (Scope::Game, "game_assets", Expect::NonEmpty),
+        (Scope::Game, "assets_index_name", Expect::Value("5")),
+        (Scope::Game, "auth_uuid", Expect::Value("uuid")),
+        (Scope::Game, "auth_access_token", Expect::Value("token")),
+        (Scope::Game, "clientid", Expect::Value("cid")),
+        (Scope::Game, "auth_xuid", Expect::Value("2535405290000000")),
+        (Scope::Game, "user_type", Expect::Value("msa")),
+        (Scope::Game, "user_properties", Expect::Value("{}")),
+        (Scope::Game, "version_type", Expect::Value("release")),
+        (Scope::Game, "resolution_width", Expect::Value("854")),
+        (Scope::Game, "resolution_height", Expect::Value("480")),
+        (Scope::Game, "quickPlayPath", Expect::NonEmpty),
+        (Scope::Game, "quickPlaySingleplayer", Expect::Unset),
+        (Scope::Game, "quickPlayMultiplayer", Expect::Unset),
+        (Scope::Game, "quickPlayRealms", Expect::Unset),
+        (Scope::Jvm, "natives_directory", Expect::NonEmpty),
+        (Scope::Jvm, "launcher_name", Expect::Value("VestaLauncher")),
+        (Scope::Jvm, "launcher_version", Expect::Value("1.0.0")),
+        (Scope::Jvm, "classpath", Expect::Value("cp")),
+        (Scope::Jvm, "library_directory", Expect::NonEmpty),
+        (Scope::Jvm, "version_name", Expect::Value("1.20.1")),
+        (Scope::Jvm, "classpath_separator", Expect::NonEmpty),
+    ];
+
+    fn collect_placeholders(value: &serde_json::Value, out: &mut Vec<String>) {
+        match value {
+            serde_json::Value::String(text) => {
+                let mut rest = text.as_str();
+                while let Some(start) = rest.find("${") {
+                    let end = rest[start..].find('}').unwrap() + start;
+                    out.push(rest[start + 2..end].to_string());
+                    rest = &rest[end + 1..];
+                }
+            }
+            serde_json::Value::Array(items) => items
+                .iter()
+                .for_each(|item| collect_placeholders(item, out)),
+            serde_json::Value::Object(map) => map
+                .values()
+                .for_each(|item| collect_placeholders(item, out)),
+            _ => {}
+        }
+    }
+
+    fn fixture_placeholders() -> Vec<(&'static str, Scope, String)> {
+        let mut found = Vec::new();
+        for (name, raw) in ARGUMENT_FIXTURES {
+            let fixture: serde_json::Value = serde_json::from_str(raw).unwrap();
+            for (scope, section) in [
+                (Scope::Game, &fixture["arguments"]["game"]),
+                (Scope::Game, &fixture["minecraftArguments"]),
+                (Scope::Jvm, &fixture["arguments"]["jvm"]),
+            ] {
+                let mut keys = Vec::new();
+                collect_placeholders(section, &mut keys);
+                found.extend(keys.into_iter().map(|key| (*name, scope, key)));
+            }
+        }
+        found
+    }
+
+    #[test]
+    fn every_fixture_placeholder_is_known() {
+        for (fixture, scope, key) in fixture_placeholders() {
+            assert!(
+                PLACEHOLDERS
+                    .iter()
+                    .any(|(s, k, _)| *s == scope && *k == key),
+                "{} uses ${{{}}} in {:?} arguments, which has no expectation",
+                fixture,
+                key,
+                scope
+            );
+        }
+    }
+
+    #[test]
+    fn placeholders_resolve_from_launch_spec() {
+        let mut spec = temp_launch_spec("i", "1.20.1");
+        spec.username = "Steve".to_string();
+        spec.uuid = "uuid".to_string();
+        spec.access_token = "token".to_string();
+        spec.xuid = Some("2535405290000000".to_string());
+        spec.window_width = Some(854);
+        spec.window_height = Some(480);
+        let mut manifest = UnifiedManifest::from(test_version_manifest("1.20.1"));
+        manifest.assets = Some("5".to_string());
+
+        let os = OsType::current();
+        let jvm_vars = build_jvm_variables(&spec, &manifest, Path::new("natives"), "cp", os);
+        let game_vars = build_game_variables(&spec, &manifest, os);
+
+        for (scope, key, expect) in PLACEHOLDERS {
+            let vars = match scope {
+                Scope::Jvm => &jvm_vars,
+                Scope::Game => &game_vars,
+            };
+            let placeholder = format!("${{{}}}", key);
+            match expect {
+                Expect::Value(expected) => {
+                    assert_eq!(
+                        vars.get(*key).map(String::as_str),
+                        Some(*expected),
+                        "{}",
+                        key
+                    );
+                    assert_eq!(substitute_variables(&placeholder, vars), *expected);
+                }
+                Expect::NonEmpty => {
+                    let resolved = substitute_variables(&placeholder, vars);
+                    assert!(!resolved.is_empty(), "{} resolved to nothing", key);
+                    assert!(!resolved.contains("${"), "{} left a placeholder", key);
+                }
+                Expect::Unset => {
+                    assert!(!vars.contains_key(*key), "{}", key);
+                    // Optional arguments are dropped together with their flag.
+                    let arg = Argument::Simple(format!("--flag {}", placeholder));
+                    assert!(process_argument(&arg, vars, os, &spec).is_empty());
+                }
+            }
+        }
+    }
+
+    fn fixture_manifest(name: &str) -> UnifiedManifest {
+        let (_, raw) = ARGUMENT_FIXTURES
+            .iter()
+            .find(|(fixture, _)| *fixture == name)
+            .unwrap();
+        let manifest: VersionManifest = serde_json::from_str(raw).unwrap();
+        UnifiedManifest::from(manifest)
+    }
+
+    #[test]
+    fn connect_server_uses_quick_play_on_modern_versions() {
+        let mut spec = temp_launch_spec("i", "1.20.1");
+        spec.connect_server = Some(("mc.example.net".to_string(), 25566));
+
+        let args = build_game_arguments(
+            &spec,
+            &fixture_manifest("vanilla-1.20.1"),
+            OsType::current(),
+        );
+        let pos = args
+            .iter()
+            .position(|a| a == "--quickPlayMultiplayer")
+            .expect("quick play flag");
+        assert_eq!(args[pos + 1], "mc.example.net:25566");
+        assert!(!args.contains(&"--server".to_string()));
+        assert!(!args.contains(&"--quickPlaySingleplayer".to_string()));
+    }
+
+    #[test]
+    fn connect_server_uses_legacy_flags_before_quick_play() {
+        let mut spec = temp_launch_spec("i", "1.12.2");
+        spec.connect_server = Some(("mc.example.net".to_string(), 25565));
+
+        let args = build_game_arguments(
+            &spec,
+            &fixture_manifest("vanilla-1.12.2"),
+            OsType::current(),
+        );
+        let pos = args
+            .iter()
+            .position(|a| a == "--server")
+            .expect("server flag");
+        assert_eq!(
+            args[pos..pos + 4],
+            ["--server", "mc.example.net", "--port", "25565"]
+        );
+        assert!(!args.contains(&"--quickPlayMultiplayer".to_string()));
+    }
+
+    #[test]
+    fn no_connect_server_adds_no_join_arguments() {
+        let spec = temp_launch_spec("i", "1.20.1");
+        for fixture in ["vanilla-1.20.1", "vanilla-1.12.2"] {
+            let args = build_game_arguments(&spec, &fixture_manifest(fixture), OsType::current());
+            assert!(!args.contains(&"--server".to_string()), "{}", fixture);
+            assert!(
+                !args.contains(&"--quickPlayMultiplayer".to_string()),
+                "{}",
+                fixture
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_placeholders_never_reach_the_command_line() {
+        let vars = HashMap::new();
+        assert_eq!(
+            substitute_variables("-Dfoo=${not_a_placeholder}/x", &vars),
+            "-Dfoo=/x"
+        );
+        assert_eq!(substitute_variables("${a}${b}", &vars), "");
+    }
 }
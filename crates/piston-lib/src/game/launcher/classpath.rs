@@ -86,6 +86,37 @@ pub fn build_classpath_filtered(
     Ok(classpath_entries.join(os.classpath_separator()))
 }
 
+/// Whether `classpath` loads LWJGL 3, judged by jar names.
+///
+/// LWJGL 3 ships `lwjgl-3.3.1.jar` plus module jars such as
+/// `lwjgl-glfw-3.3.1.jar`; LWJGL 2 ships `lwjgl.jar` or
+/// `lwjgl-2.9.4-nightly-20150209.jar`. The version after the module name
+/// decides, so `lwjgl-platform-2.9.4` (LWJGL 2 natives) does not count.
+pub fn classpath_uses_lwjgl3(classpath: &str, os: OsType) -> bool {
+    classpath
+        .split(os.classpath_separator())
+        .filter_map(|entry| entry.rsplit(['/', '\\']).next())
+        .filter_map(|file| file.strip_prefix("lwjgl-")?.strip_suffix(".jar"))
+        .filter_map(lwjgl_jar_major_version)
+        .any(|major| major >= 3)
+}
+
+/// Major version from the part of an LWJGL jar name after `lwjgl-`, e.g.
+/// `3.3.1` or `glfw-3.3.1-natives-macos` → 3.
+fn lwjgl_jar_major_version(rest: &str) -> Option<u32> {
+    let version = if rest.starts_with(|c: char| c.is_ascii_digit()) {
+        rest
+    } else {
+        let start = rest
+            .match_indices('-')
+            .map(|(i, _)| i + 1)
+            .find(|&i| rest[i..].starts_with(|c: char| c.is_ascii_digit()))?;
+        &rest[start..]
+    };
+    let major = version.split(|c: char| !c.is_ascii_digit()).next()?;
+    major.parse().ok()
+}
+
 /// Validate classpath requirements before launch
 pub fn validate_classpath(
     libraries: &[UnifiedLibrary],
@@ -263,6 +294,30 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn detects_lwjgl3_from_classpath_jars() {
+        let lwjgl3 = "/data/libraries/org/lwjgl/lwjgl/3.3.1/lwjgl-3.3.1.jar:\
+                      /data/libraries/org/lwjgl/lwjgl-glfw/3.3.1/lwjgl-glfw-3.3.1.jar";
+        assert!(classpath_uses_lwjgl3(lwjgl3, OsType::MacOS));
+
+        let modules_only = "/libs/lwjgl-glfw-3.2.2.jar:/libs/guava-31.1.jar";
+        assert!(classpath_uses_lwjgl3(modules_only, OsType::MacOSArm64));
+
+        let lwjgl2 = "/libs/org/lwjgl/lwjgl/lwjgl/2.9.4-nightly-20150209/lwjgl-2.9.4-nightly-20150209.jar:\
+                      /libs/org/lwjgl/lwjgl/lwjgl_util/2.9.4-nightly-20150209/lwjgl_util-2.9.4-nightly-20150209.jar:\
+                      /libs/org/lwjgl/lwjgl/lwjgl-platform/2.9.4/lwjgl-platform-2.9.4-nightly-20150209-natives-osx.jar";
+        assert!(!classpath_uses_lwjgl3(lwjgl2, OsType::MacOS));
+
+        assert!(!classpath_uses_lwjgl3(
+            "/bin/lwjgl.jar:/bin/jinput.jar",
+            OsType::MacOS
+        ));
+        assert!(!classpath_uses_lwjgl3("", OsType::MacOS));
+
+        let windows = r"C:\libs\lwjgl-3.3.3.jar;C:\libs\gson-2.10.jar";
+        assert!(classpath_uses_lwjgl3(windows, OsType::Windows));
+    }
+
     #[test]
     fn test_classpath_separator() {
         assert_eq!(OsType::Windows.classpath_separator(), ";");
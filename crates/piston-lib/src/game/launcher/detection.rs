@@ -0,0 +1,146 @@
+/// Modloader detection from version JSONs already present in `versions/`
+use crate::game::metadata::neoforge::minecraft_version_for_neoforge;
+use crate::game::metadata::ModloaderType;
+use crate::utils::version::compare_versions;
+use std::path::Path;
+
+/// Detect a modloader installed for `mc_version` from the version ids in
+/// `versions_dir`, for instances that were imported without loader metadata.
+///
+/// Recognises the launcher's own `<loader>-loader-<ver>-<mc>` ids (also used
+/// by the official Fabric and Quilt installers), Forge's `<mc>-forge-<ver>`
+/// and `forge-<mc>-<ver>`, and NeoForge's `neoforge-<ver>`. When several
+/// loaders match, the newest loader version wins.
+pub fn detect_installed_modloader(
+    versions_dir: &Path,
+    mc_version: &str,
+) -> Option<(ModloaderType, String)> {
+    let entries = std::fs::read_dir(versions_dir).ok()?;
+
+    let mut detected: Vec<(ModloaderType, String)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if path.is_dir() {
+                path.join(format!("{}.json", name))
+                    .is_file()
+                    .then_some(name)
+            } else {
+                name.strip_suffix(".json").map(str::to_string)
+            }
+        })
+        .filter_map(|id| parse_version_id(&id, mc_version))
+        .collect();
+
+    detected.sort_by(|a, b| compare_versions(&a.1, &b.1));
+    detected.pop()
+}
+
+/// Loader and loader version encoded in an installed version id, if it
+/// targets `mc_version`.
+fn parse_version_id(id: &str, mc_version: &str) -> Option<(ModloaderType, String)> {
+    for loader in ModloaderType::MODDED {
+        let own = id
+            .strip_prefix(&format!("{}-loader-", loader.as_str()))
+            .and_then(|rest| rest.strip_suffix(&format!("-{}", mc_version)));
+        if let Some(version) = own.filter(|v| !v.is_empty()) {
+            return Some((loader, version.to_string()));
+        }
+    }
+
+    let forge = id
+        .strip_prefix(&format!("{}-forge-", mc_version))
+        .or_else(|| id.strip_prefix(&format!("forge-{}-", mc_version)));
+    if let Some(version) = forge.filter(|v| !v.is_empty()) {
+        return Some((ModloaderType::Forge, version.to_string()));
+    }
+
+    let neoforge = id.strip_prefix("neoforge-")?;
+    (minecraft_version_for_neoforge(neoforge)? == mc_version)
+        .then(|| (ModloaderType::NeoForge, neoforge.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn install(versions_dir: &Path, id: &str) {
+        let dir = versions_dir.join(id);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(format!("{}.json", id)), "{}").unwrap();
+    }
+
+    #[test]
+    fn parses_known_version_id_layouts() {
+        let cases = [
+            (
+                "fabric-loader-0.15.11-1.20.1",
+                Some((ModloaderType::Fabric, "0.15.11")),
+            ),
+            (
+                "quilt-loader-0.26.0-1.20.1",
+                Some((ModloaderType::Quilt, "0.26.0")),
+            ),
+            (
+                "forge-loader-47.2.0-1.20.1",
+                Some((ModloaderType::Forge, "47.2.0")),
+            ),
+            (
+                "1.20.1-forge-47.2.0",
+                Some((ModloaderType::Forge, "47.2.0")),
+            ),
+            (
+                "forge-1.20.1-47.2.0",
+                Some((ModloaderType::Forge, "47.2.0")),
+            ),
+            ("fabric-loader-0.15.11-1.20.4", None),
+            ("1.20.1", None),
+            ("OptiFine_1.20.1_HD_U_I6", None),
+        ];
+        for (id, expected) in cases {
+            assert_eq!(
+                parse_version_id(id, "1.20.1"),
+                expected.map(|(loader, version)| (loader, version.to_string())),
+                "{}",
+                id
+            );
+        }
+    }
+
+    #[test]
+    fn neoforge_ids_match_their_minecraft_version() {
+        assert_eq!(
+            parse_version_id("neoforge-21.1.77", "1.21.1"),
+            Some((ModloaderType::NeoForge, "21.1.77".to_string()))
+        );
+        assert_eq!(
+            parse_version_id("neoforge-21.0.167", "1.21"),
+            Some((ModloaderType::NeoForge, "21.0.167".to_string()))
+        );
+        assert_eq!(parse_version_id("neoforge-21.1.77", "1.21.4"), None);
+    }
+
+    #[test]
+    fn detects_newest_loader_for_the_minecraft_version() {
+        let dir = tempfile::tempdir().unwrap();
+        install(dir.path(), "1.20.1");
+        install(dir.path(), "fabric-loader-0.14.21-1.20.1");
+        install(dir.path(), "fabric-loader-0.15.11-1.20.1");
+        install(dir.path(), "fabric-loader-0.16.0-1.21.1");
+        // A bare JSON next to the version folders counts too.
+        std::fs::write(dir.path().join("fabric-loader-0.15.3-1.20.1.json"), "{}").unwrap();
+        // A folder without its JSON is a leftover, not an install.
+        std::fs::create_dir_all(dir.path().join("fabric-loader-0.99.0-1.20.1")).unwrap();
+
+        assert_eq!(
+            detect_installed_modloader(dir.path(), "1.20.1"),
+            Some((ModloaderType::Fabric, "0.15.11".to_string()))
+        );
+        assert_eq!(detect_installed_modloader(dir.path(), "1.19.2"), None);
+        assert_eq!(
+            detect_installed_modloader(&dir.path().join("missing"), "1.20.1"),
+            None
+        );
+    }
+}
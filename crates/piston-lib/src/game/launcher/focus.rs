@@ -0,0 +1,134 @@
+//! Whether a launched game's window has input focus.
+//!
+//! The foreground window is polled every [`FOCUS_POLL_INTERVAL`] and its
+//! owning process compared with the game's pid; each poll that finds the game
+//! focused counts as a full interval of active play. On Linux this relies on
+//! `xdotool`, which only sees X11 (and XWayland) windows. When the foreground
+//! window can't be determined the poll is not counted at all, so a missing
+//! tool doesn't read as idle time.
+
+use std::time::Duration;
+
+pub const FOCUS_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Pid of the process owning the focused window, if it can be determined.
+pub fn foreground_pid() -> Option<u32> {
+    imp::foreground_pid()
+}
+
+/// Accumulates the focused time of one game process.
+#[derive(Debug)]
+pub struct FocusTracker {
+    pid: u32,
+    observed: bool,
+    unflushed_secs: u64,
+}
+
+impl FocusTracker {
+    pub fn new(pid: u32) -> Self {
+        Self {
+            pid,
+            observed: false,
+            unflushed_secs: 0,
+        }
+    }
+
+    /// Check the foreground window once.
+    pub fn poll(&mut self) {
+        self.record(foreground_pid());
+    }
+
+    /// Count one poll interval given the foreground pid it saw.
+    pub fn record(&mut self, foreground: Option<u32>) {
+        let Some(foreground) = foreground else {
+            return;
+        };
+        self.observed = true;
+        if foreground == self.pid {
+            self.unflushed_secs += FOCUS_POLL_INTERVAL.as_secs();
+        }
+    }
+
+    /// Active seconds gathered since the last call, or `None` when focus was
+    /// never determined (unsupported platform or missing tool).
+    pub fn take_active_secs(&mut self) -> Option<u64> {
+        if !self.observed {
+            return None;
+        }
+        Some(std::mem::take(&mut self.unflushed_secs))
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod imp {
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowThreadProcessId,
+    };
+
+    pub fn foreground_pid() -> Option<u32> {
+        let window = unsafe { GetForegroundWindow() };
+        if window.is_null() {
+            return None;
+        }
+        let mut pid = 0u32;
+        unsafe { GetWindowThreadProcessId(window, &mut pid) };
+        (pid != 0).then_some(pid)
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    pub fn foreground_pid() -> Option<u32> {
+        let output = std::process::Command::new("xdotool")
+            .args(["getactivewindow", "getwindowpid"])
+            .stdin(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        super::parse_pid(&String::from_utf8_lossy(&output.stdout))
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+mod imp {
+    pub fn foreground_pid() -> Option<u32> {
+        None
+    }
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_pid(output: &str) -> Option<u32> {
+    output.trim().parse().ok().filter(|pid| *pid != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_focused_polls_count_as_active() {
+        let mut tracker = FocusTracker::new(42);
+        tracker.record(Some(42));
+        tracker.record(Some(7));
+        tracker.record(Some(42));
+        assert_eq!(tracker.take_active_secs(), Some(10));
+        assert_eq!(tracker.take_active_secs(), Some(0));
+    }
+
+    #[test]
+    fn unknown_focus_is_not_counted() {
+        let mut tracker = FocusTracker::new(42);
+        tracker.record(None);
+        assert_eq!(tracker.take_active_secs(), None);
+    }
+
+    #[test]
+    fn parses_xdotool_output() {
+        assert_eq!(parse_pid("12345\n"), Some(12345));
+        assert_eq!(parse_pid(""), None);
+        assert_eq!(parse_pid("0"), None);
+    }
+}
@@ -0,0 +1,416 @@
+//! Game log capture through a launcher-owned pipe.
+//!
+//! Reading stdout only starts once the process is spawned and the reader tasks
+//! are attached. With `LaunchSpec::log_pipe` set, a generated log4j2
+//! configuration sends every log event to a pipe created before launch
+//! instead: a FIFO on Unix, a named pipe on Windows. The JVM blocks on opening
+//! the pipe until the reader end exists, so no line from JVM initialization
+//! onward is lost.
+
+use crate::game::launcher::process::{LogCallback, ProcessSampler};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead};
+
+/// JVM system property that selects the log4j2 configuration file.
+pub const LOG4J_CONFIG_PROPERTY: &str = "-Dlog4j.configurationFile=";
+
+/// File name of the generated configuration inside `<game_dir>/.vesta`.
+pub const LOG4J_CONFIG_FILE_NAME: &str = "log4j2-pipe.xml";
+
+/// How often the reader checks whether the game died before opening the pipe.
+const EXIT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A pipe and the log4j2 configuration that writes to it.
+pub struct LogPipe {
+    pipe_path: PathBuf,
+    config_path: PathBuf,
+    #[cfg(windows)]
+    server: tokio::net::windows::named_pipe::NamedPipeServer,
+}
+
+impl LogPipe {
+    /// Create the pipe and write the configuration under `<game_dir>/.vesta`.
+    pub async fn create(game_dir: &Path, instance_id: &str) -> Result<Self> {
+        let vesta_dir = game_dir.join(".vesta");
+        tokio::fs::create_dir_all(&vesta_dir)
+            .await
+            .with_context(|| format!("Failed to create {:?}", vesta_dir))?;
+
+        #[cfg(unix)]
+        let pipe = {
+            let pipe_path = vesta_dir.join("log4j2.pipe");
+            create_fifo(&pipe_path)?;
+            LogPipe {
+                config_path: vesta_dir.join(LOG4J_CONFIG_FILE_NAME),
+                pipe_path,
+            }
+        };
+
+        #[cfg(windows)]
+        let pipe = {
+            let pipe_path = PathBuf::from(windows_pipe_name(instance_id));
+            let server = tokio::net::windows::named_pipe::ServerOptions::new()
+                .first_pipe_instance(true)
+                .access_outbound(false)
+                .create(&pipe_path)
+                .with_context(|| format!("Failed to create named pipe {:?}", pipe_path))?;
+            LogPipe {
+                config_path: vesta_dir.join(LOG4J_CONFIG_FILE_NAME),
+                pipe_path,
+                server,
+            }
+        };
+
+        #[cfg(unix)]
+        let _ = instance_id;
+
+        tokio::fs::write(&pipe.config_path, log4j2_config(&pipe.pipe_path))
+            .await
+            .with_context(|| format!("Failed to write {:?}", pipe.config_path))?;
+        Ok(pipe)
+    }
+
+    pub fn pipe_path(&self) -> &Path {
+        &self.pipe_path
+    }
+
+    pub fn config_path(&self) -> &Path {
+        &self.config_path
+    }
+
+    /// The `-Dlog4j.configurationFile=` argument pointing at the configuration.
+    pub fn jvm_arg(&self) -> String {
+        format!(
+            "{}{}",
+            LOG4J_CONFIG_PROPERTY,
+            self.config_path.to_string_lossy()
+        )
+    }
+
+    /// Read the pipe in the background until the game closes it.
+    ///
+    /// Each line is appended to `log_file` (if given) and passed to `callback`
+    /// as `"stdout"`, the stream log4j would otherwise have used. If the game
+    /// with `pid` exits without ever opening the pipe, the reader gives up.
+    pub fn spawn_reader(
+        self,
+        pid: u32,
+        instance_id: String,
+        log_file: Option<PathBuf>,
+        callback: Option<LogCallback>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            if let Err(e) = self
+                .read_to_end(pid, &instance_id, log_file, callback)
+                .await
+            {
+                log::warn!("Log pipe for {} failed: {:#}", instance_id, e);
+            }
+        })
+    }
+
+    #[cfg(unix)]
+    async fn read_to_end(
+        self,
+        pid: u32,
+        instance_id: &str,
+        log_file: Option<PathBuf>,
+        callback: Option<LogCallback>,
+    ) -> Result<()> {
+        let path = self.pipe_path.clone();
+        let open = tokio::task::spawn_blocking(move || std::fs::File::open(path));
+        tokio::pin!(open);
+
+        let file = tokio::select! {
+            opened = &mut open => opened,
+            _ = wait_for_exit(pid) => {
+                // Pair the blocked open with a writer so it returns (and then
+                // reads EOF straight away).
+                release_fifo(&self.pipe_path);
+                open.await
+            }
+        }
+        .context("Log pipe reader panicked")?
+        .with_context(|| format!("Failed to open {:?}", self.pipe_path))?;
+
+        let result = forward_lines(
+            tokio::fs::File::from_std(file),
+            instance_id,
+            log_file,
+            callback,
+        )
+        .await;
+        let _ = tokio::fs::remove_file(&self.pipe_path).await;
+        result
+    }
+
+    #[cfg(windows)]
+    async fn read_to_end(
+        self,
+        pid: u32,
+        instance_id: &str,
+        log_file: Option<PathBuf>,
+        callback: Option<LogCallback>,
+    ) -> Result<()> {
+        tokio::select! {
+            connected = self.server.connect() => {
+                connected.with_context(|| format!("Failed to accept {:?}", self.pipe_path))?;
+            }
+            _ = wait_for_exit(pid) => return Ok(()),
+        }
+        forward_lines(self.server, instance_id, log_file, callback).await
+    }
+}
+
+/// Point `jvm_args` at the pipe configuration, replacing the version's own
+/// log config argument in place. Returns `false` and leaves the arguments
+/// alone when the user set `-Dlog4j.configurationFile` themselves.
+pub fn apply_log_config_arg(
+    jvm_args: &mut Vec<String>,
+    user_jvm_args: &[String],
+    arg: String,
+) -> bool {
+    if user_jvm_args
+        .iter()
+        .any(|a| a.starts_with(LOG4J_CONFIG_PROPERTY))
+    {
+        return false;
+    }
+    match jvm_args
+        .iter()
+        .position(|a| a.starts_with(LOG4J_CONFIG_PROPERTY))
+    {
+        Some(index) => jvm_args[index] = arg,
+        None => jvm_args.push(arg),
+    }
+    true
+}
+
+/// log4j2 configuration that writes to `pipe_path` and to `logs/latest.log`.
+///
+/// The pattern matches the vanilla `latest.log` format so the console can
+/// parse time, thread and level.
+pub fn log4j2_config(pipe_path: &Path) -> String {
+    let pipe = xml_escape(&pipe_path.to_string_lossy());
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<Configuration status="WARN">
+    <Appenders>
+        <File name="VestaPipe" fileName="{pipe}" append="true" bufferedIO="false" immediateFlush="true">
+            <PatternLayout pattern="[%d{{HH:mm:ss}}] [%t/%level]: %msg%n" />
+        </File>
+        <RollingRandomAccessFile name="File" fileName="logs/latest.log" filePattern="logs/%d{{yyyy-MM-dd}}-%i.log.gz">
+            <PatternLayout pattern="[%d{{HH:mm:ss}}] [%t/%level]: %msg%n" />
+            <Policies>
+                <TimeBasedTriggeringPolicy />
+                <OnStartupTriggeringPolicy />
+            </Policies>
+        </RollingRandomAccessFile>
+    </Appenders>
+    <Loggers>
+        <Root level="info">
+            <filters>
+                <MarkerFilter marker="NETWORK_PACKETS" onMatch="DENY" onMismatch="NEUTRAL" />
+            </filters>
+            <AppenderRef ref="VestaPipe" />
+            <AppenderRef ref="File" />
+        </Root>
+    </Loggers>
+</Configuration>
+"#
+    )
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+async fn forward_lines(
+    reader: impl AsyncRead + Unpin,
+    instance_id: &str,
+    log_file: Option<PathBuf>,
+    callback: Option<LogCallback>,
+) -> Result<()> {
+    use std::io::Write;
+
+    let mut file = match log_file {
+        Some(path) => Some(std::io::BufWriter::new(
+            tokio::task::spawn_blocking(move || {
+                std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+            })
+            .await
+            .context("spawn_blocking panicked")??,
+        )),
+        None => None,
+    };
+
+    let mut lines = tokio::io::BufReader::new(reader).lines();
+    while let Some(line) = lines.next_line().await? {
+        if let Some(ref mut f) = file {
+            let _ = writeln!(f, "{}", line);
+            let _ = f.flush();
+        }
+        if let Some(ref cb) = callback {
+            cb(instance_id.to_string(), line, "stdout".to_string());
+        }
+    }
+    Ok(())
+}
+
+async fn wait_for_exit(pid: u32) {
+    let mut sampler = ProcessSampler::new(pid);
+    loop {
+        tokio::time::sleep(EXIT_POLL_INTERVAL).await;
+        if sampler.sample().is_none() {
+            return;
+        }
+    }
+}
+
+#[cfg(unix)]
+fn create_fifo(path: &Path) -> Result<()> {
+    use std::os::unix::ffi::OsStrExt;
+
+    match std::fs::remove_file(path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e).with_context(|| format!("Failed to remove stale {:?}", path)),
+    }
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes())
+        .context("Pipe path contains a NUL byte")?;
+    // SAFETY: `c_path` is a valid NUL-terminated string for the duration of the call.
+    if unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) } != 0 {
+        return Err(std::io::Error::last_os_error())
+            .with_context(|| format!("Failed to create FIFO {:?}", path));
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn release_fifo(path: &Path) {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let _ = std::fs::OpenOptions::new()
+        .write(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open(path);
+}
+
+#[cfg(windows)]
+fn windows_pipe_name(instance_id: &str) -> String {
+    let safe: String = instance_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    format!(r"\\.\pipe\vesta-log4j-{}-{}", safe, std::process::id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn replaces_the_version_log_config_in_place() {
+        let mut args = vec![
+            "-Xmx2G".to_string(),
+            "-Dlog4j.configurationFile=/assets/log_configs/client-1.12.xml".to_string(),
+            "-cp".to_string(),
+        ];
+        assert!(apply_log_config_arg(
+            &mut args,
+            &[],
+            "-Dlog4j.configurationFile=/game/.vesta/log4j2-pipe.xml".to_string()
+        ));
+        assert_eq!(
+            args,
+            vec![
+                "-Xmx2G",
+                "-Dlog4j.configurationFile=/game/.vesta/log4j2-pipe.xml",
+                "-cp"
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_a_user_supplied_log_config() {
+        let user = vec!["-Dlog4j.configurationFile=/mine.xml".to_string()];
+        let mut args = user.clone();
+        assert!(!apply_log_config_arg(
+            &mut args,
+            &user,
+            "-Dlog4j.configurationFile=/game/.vesta/log4j2-pipe.xml".to_string()
+        ));
+        assert_eq!(args, user);
+    }
+
+    #[test]
+    fn config_targets_the_escaped_pipe_path() {
+        let config = log4j2_config(Path::new("/tmp/a&b/log4j2.pipe"));
+        assert!(config.contains(r#"fileName="/tmp/a&amp;b/log4j2.pipe""#));
+        assert!(config.contains("[%d{HH:mm:ss}] [%t/%level]: %msg%n"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn forwards_lines_written_before_the_reader_attaches() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let pipe = LogPipe::create(dir.path(), "inst").await.unwrap();
+        assert!(pipe.config_path().exists());
+        assert_eq!(
+            pipe.jvm_arg(),
+            format!(
+                "-Dlog4j.configurationFile={}",
+                dir.path().join(".vesta/log4j2-pipe.xml").display()
+            )
+        );
+
+        // Stands in for the JVM: opening blocks until the reader exists.
+        let pipe_path = pipe.pipe_path().to_path_buf();
+        let writer = std::thread::spawn(move || {
+            let mut fifo = std::fs::OpenOptions::new()
+                .write(true)
+                .open(pipe_path)
+                .unwrap();
+            writeln!(fifo, "[12:00:00] [main/INFO]: Loading").unwrap();
+            writeln!(fifo, "[12:00:01] [main/WARN]: Early warning").unwrap();
+        });
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let callback: LogCallback = Arc::new(move |id, line, stream| {
+            sink.lock().unwrap().push((id, line, stream));
+        });
+        let log_file = dir.path().join("game.log");
+        pipe.spawn_reader(
+            std::process::id(),
+            "inst".to_string(),
+            Some(log_file.clone()),
+            Some(callback),
+        )
+        .await
+        .unwrap();
+        writer.join().unwrap();
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[1].1, "[12:00:01] [main/WARN]: Early warning");
+        assert_eq!(received[0].2, "stdout");
+        assert_eq!(
+            std::fs::read_to_string(log_file).unwrap().lines().count(),
+            2
+        );
+        assert!(!dir.path().join(".vesta/log4j2.pipe").exists());
+    }
+}
@@ -1,9 +1,14 @@
 pub mod arguments;
 pub mod classifier;
 pub mod classpath;
+pub mod detection;
+pub mod focus;
+pub mod log_pipe;
 pub mod natives;
+pub mod priority;
 pub mod process;
 pub mod registry;
+pub mod token_file;
 /// Game launcher module for executing Minecraft with various modloaders
 pub mod types;
 pub mod unified_manifest;
@@ -11,14 +16,25 @@ pub mod version_parser;
 
 // Re-export commonly used types
 pub use crate::game::installer::types::OsType;
-pub use arguments::{build_game_arguments, build_jvm_arguments, substitute_variables};
+pub use arguments::{
+    build_game_arguments, build_jvm_arguments, deduplicate_jvm_args, substitute_variables,
+    JvmArgConflict,
+};
 pub use classpath::{build_classpath, maven_to_path};
-pub use natives::{extract_natives, get_natives_dir};
-pub use process::{kill_instance, launch_game, launch_prepared_game, LogCallback};
+pub use detection::detect_installed_modloader;
+pub use focus::{FocusTracker, FOCUS_POLL_INTERVAL};
+pub use log_pipe::LogPipe;
+pub use natives::{extract_natives, get_natives_dir, prune_stale_natives};
+pub use priority::Priority;
+pub use process::{
+    kill_instance, launch_game, launch_prepared_game, LogCallback, ProcessMetrics,
+    ProcessMetricsBuffer, ProcessSampler, PROCESS_METRICS_INTERVAL,
+};
 pub use registry::{
-    get_instance, get_running_instances, is_instance_running, load_registry, register_instance,
-    unregister_instance,
+    get_instance, get_running_instances, is_instance_running, load_registry,
+    reattach_running_processes, register_instance, unregister_instance, RUN_STATE_FILE_NAME,
 };
+pub use token_file::{remove_token_file, write_token_file, CurrentToken, TOKEN_REFRESH_INTERVAL};
 pub use types::{GameInstance, InstanceState, LaunchResult, LaunchSpec, ProcessHandle};
 pub use version_parser::{
     get_asset_index, get_main_class, parse_version_json, resolve_version_chain, Argument,
@@ -1,8 +1,10 @@
 /// Native library extraction for Minecraft launcher
-use crate::game::installer::types::OsType;
+use crate::game::installer::types::{OsType, SilentProgressReporter};
 use crate::game::launcher::unified_manifest::UnifiedLibrary;
+use crate::utils::archive::extract_zip_streaming;
 use anyhow::{Context, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Extract native libraries from JARs
 pub async fn extract_natives(
@@ -35,52 +37,23 @@ pub async fn extract_natives(
 async fn extract_jar(jar_path: &Path, output_dir: &Path, library: &UnifiedLibrary) -> Result<()> {
     log::debug!("Extracting natives from: {:?}", jar_path);
 
-    let jar_path = jar_path.to_path_buf();
-    let output_dir = output_dir.to_path_buf();
-    let library = library.clone();
     let exclusions = library
         .extract_rules
         .as_ref()
         .map(|e| e.exclude.clone())
         .unwrap_or_default();
-    tokio::task::spawn_blocking(move || -> Result<()> {
-        let exclusions: Vec<&str> = exclusions.iter().map(|s| s.as_str()).collect();
-        let file = std::fs::File::open(&jar_path)
-            .with_context(|| format!("Failed to open JAR: {:?}", jar_path))?;
-        let mut archive = zip::ZipArchive::new(file)
-            .with_context(|| format!("Failed to read JAR: {:?}", jar_path))?;
-
-        for i in 0..archive.len() {
-            let mut entry = archive.by_index(i)?;
-            let file_path = entry.name().to_string();
-
-            // Skip if excluded
-            if should_exclude(&file_path, &exclusions) {
-                continue;
-            }
-
-            // Skip directories
-            if entry.is_dir() {
-                continue;
-            }
-
-            // Extract file
-            let output_path = output_dir.join(&file_path);
-
-            // Create parent directories
-            if let Some(parent) = output_path.parent() {
-                std::fs::create_dir_all(parent)?;
-            }
-
-            // Write file
-            let mut output_file = std::fs::File::create(&output_path)?;
-            std::io::copy(&mut entry, &mut output_file)?;
-        }
-
-        Ok(())
-    })
+    let exclusions: Vec<&str> = exclusions.iter().map(|s| s.as_str()).collect();
+    // Directories are only created as parents of extracted files.
+    let include = |name: &str| !name.ends_with('/') && !should_exclude(name, &exclusions);
+
+    extract_zip_streaming(
+        jar_path,
+        output_dir,
+        Arc::new(SilentProgressReporter),
+        Some(&include),
+    )
     .await
-    .context("spawn_blocking panicked")??;
+    .with_context(|| format!("Failed to extract natives from {:?}", jar_path))?;
 
     Ok(())
 }
@@ -95,9 +68,41 @@ fn should_exclude(file_path: &str, exclusions: &[&str]) -> bool {
     false
 }
 
-/// Get the natives directory path for a version
-pub fn get_natives_dir(data_dir: &Path, version_id: &str) -> std::path::PathBuf {
-    data_dir.join("natives").join(version_id)
+/// Get the natives directory path for an installed version.
+///
+/// Keyed by the installed version id (e.g. "fabric-loader-0.16.0-1.21.1")
+/// so loader variants never share LWJGL binaries with another version.
+pub fn get_natives_dir(data_dir: &Path, installed_version_id: &str) -> PathBuf {
+    data_dir.join("natives").join(installed_version_id)
+}
+
+/// Remove natives directories whose version is no longer installed under
+/// `versions/`. Returns the directories that were removed.
+pub fn prune_stale_natives(data_dir: &Path) -> Result<Vec<PathBuf>> {
+    let natives_root = data_dir.join("natives");
+    let versions_dir = data_dir.join("versions");
+    let entries = match std::fs::read_dir(&natives_root) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {:?}", natives_root));
+        }
+    };
+
+    let mut removed = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() || versions_dir.join(entry.file_name()).is_dir() {
+            continue;
+        }
+        // A running game on Windows keeps its DLLs locked; try again next time.
+        match std::fs::remove_dir_all(&path) {
+            Ok(()) => removed.push(path),
+            Err(e) => log::warn!("Failed to remove stale natives {:?}: {}", path, e),
+        }
+    }
+
+    Ok(removed)
 }
 
 #[cfg(test)]
@@ -264,4 +269,109 @@ mod tests {
             "Expected permissive-extracted native file to exist"
         );
     }
+
+    /// The unified entries of a Windows native library for `name` whose jar
+    /// holds `entries`.
+    fn native_library(
+        libraries_dir: &Path,
+        name: &str,
+        entries: &[(&str, &[u8])],
+    ) -> Vec<UnifiedLibrary> {
+        use crate::game::launcher::version_parser::{ExtractRules, Library};
+        use std::collections::HashMap;
+        use std::io::Write;
+
+        let arch = if cfg!(target_pointer_width = "32") {
+            "32"
+        } else {
+            "64"
+        };
+        let mut natives_map = HashMap::new();
+        natives_map.insert("windows".to_string(), "natives-windows-${arch}".to_string());
+        let lib = Library {
+            name: name.to_string(),
+            downloads: None,
+            url: None,
+            rules: None,
+            natives: Some(natives_map),
+            extract: Some(ExtractRules {
+                exclude: vec!["META-INF/".to_string()],
+            }),
+            include_in_classpath: true,
+        };
+
+        let coords = format!("{}:natives-windows-{}", name, arch);
+        let jar =
+            libraries_dir.join(crate::game::launcher::classpath::maven_to_path(&coords).unwrap());
+        std::fs::create_dir_all(jar.parent().unwrap()).unwrap();
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&jar).unwrap());
+        for (entry, contents) in entries {
+            zip.start_file::<&str, ()>(entry, zip::write::FileOptions::default())
+                .unwrap();
+            zip.write_all(contents).unwrap();
+        }
+        zip.finish().unwrap();
+
+        UnifiedLibrary::from_library(&lib, None, OsType::Windows)
+    }
+
+    #[tokio::test]
+    async fn versions_get_isolated_natives() {
+        let data = tempfile::TempDir::new().unwrap();
+        let libraries_dir = data.path().join("libraries");
+
+        let old = native_library(
+            &libraries_dir,
+            "org.lwjgl.lwjgl:lwjgl-platform:2.9.4",
+            &[("lwjgl64.dll", b"lwjgl 2"), ("OpenAL64.dll", b"openal")],
+        );
+        let new = native_library(
+            &libraries_dir,
+            "org.lwjgl:lwjgl:3.3.3",
+            &[("lwjgl.dll", b"lwjgl 3")],
+        );
+
+        let old_dir = get_natives_dir(data.path(), "1.8.9");
+        let new_dir = get_natives_dir(data.path(), "fabric-loader-0.16.0-1.21.1");
+        extract_natives(&old, &libraries_dir, &old_dir, OsType::Windows)
+            .await
+            .unwrap();
+        extract_natives(&new, &libraries_dir, &new_dir, OsType::Windows)
+            .await
+            .unwrap();
+
+        let listing = |dir: &Path| {
+            let mut names: Vec<String> = std::fs::read_dir(dir)
+                .unwrap()
+                .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(listing(&old_dir), vec!["OpenAL64.dll", "lwjgl64.dll"]);
+        assert_eq!(listing(&new_dir), vec!["lwjgl.dll"]);
+        assert_eq!(
+            std::fs::read(new_dir.join("lwjgl.dll")).unwrap(),
+            b"lwjgl 3"
+        );
+    }
+
+    #[test]
+    fn prune_removes_natives_of_uninstalled_versions() {
+        let data = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(data.path().join("versions/1.21.1")).unwrap();
+        std::fs::create_dir_all(get_natives_dir(data.path(), "1.21.1")).unwrap();
+        let stale = get_natives_dir(data.path(), "1.20.1");
+        std::fs::create_dir_all(&stale).unwrap();
+
+        assert_eq!(
+            prune_stale_natives(data.path()).unwrap(),
+            vec![stale.clone()]
+        );
+        assert!(!stale.exists());
+        assert!(get_natives_dir(data.path(), "1.21.1").exists());
+
+        let empty = tempfile::TempDir::new().unwrap();
+        assert!(prune_stale_natives(empty.path()).unwrap().is_empty());
+    }
 }
@@ -0,0 +1,305 @@
+//! Scheduling priority and CPU affinity of a launched game.
+//!
+//! Both are applied right after spawn. The OS may refuse either (raising
+//! priority usually needs elevated rights); callers log the error and let the
+//! game run with the defaults instead of failing the launch.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+/// Process priority, mapped to a Windows priority class or a Unix nice value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Priority {
+    Idle,
+    BelowNormal,
+    Normal,
+    AboveNormal,
+    High,
+}
+
+impl Priority {
+    /// Nice value used on Unix.
+    pub fn nice_value(self) -> i32 {
+        match self {
+            Priority::Idle => 19,
+            Priority::BelowNormal => 10,
+            Priority::Normal => 0,
+            Priority::AboveNormal => -5,
+            Priority::High => -10,
+        }
+    }
+
+    /// Closest priority for a nice value read back from the OS.
+    pub fn from_nice(nice: i32) -> Self {
+        match nice {
+            15.. => Priority::Idle,
+            5..=14 => Priority::BelowNormal,
+            -2..=4 => Priority::Normal,
+            -7..=-3 => Priority::AboveNormal,
+            _ => Priority::High,
+        }
+    }
+}
+
+/// Number of logical cores an affinity mask can refer to.
+pub fn logical_core_count() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(64)
+}
+
+/// Reject masks that select no core or a core this machine does not have.
+pub fn validate_affinity_mask(mask: u64, core_count: usize) -> Result<u64> {
+    if mask == 0 {
+        bail!("CPU affinity mask must select at least one core");
+    }
+    let core_count = core_count.clamp(1, 64);
+    if core_count < 64 && mask >> core_count != 0 {
+        bail!(
+            "CPU affinity mask {:#x} selects cores beyond the {} available",
+            mask,
+            core_count
+        );
+    }
+    Ok(mask)
+}
+
+/// Set the priority of `pid`.
+pub fn apply_priority(pid: u32, priority: Priority) -> Result<()> {
+    imp::apply_priority(pid, priority)
+}
+
+/// Restrict `pid` to the cores selected by `mask` (bit 0 is the first core).
+pub fn apply_cpu_affinity(pid: u32, mask: u64) -> Result<()> {
+    let mask = validate_affinity_mask(mask, logical_core_count())?;
+    imp::apply_cpu_affinity(pid, mask)
+}
+
+/// Priority `pid` is actually running at, if it can be read.
+pub fn effective_priority(pid: u32) -> Option<Priority> {
+    imp::effective_priority(pid)
+}
+
+#[cfg(unix)]
+mod imp {
+    use super::Priority;
+    use anyhow::{bail, Result};
+    use nix::errno::Errno;
+
+    pub fn apply_priority(pid: u32, priority: Priority) -> Result<()> {
+        let result = unsafe {
+            libc::setpriority(
+                libc::PRIO_PROCESS as _,
+                pid as libc::id_t,
+                priority.nice_value(),
+            )
+        };
+        if result != 0 {
+            bail!(
+                "setpriority({}, {}) failed: {}",
+                pid,
+                priority.nice_value(),
+                Errno::last()
+            );
+        }
+        Ok(())
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn apply_cpu_affinity(pid: u32, mask: u64) -> Result<()> {
+        let result = unsafe {
+            let mut set: libc::cpu_set_t = std::mem::zeroed();
+            libc::CPU_ZERO(&mut set);
+            for core in (0..64).filter(|core| mask & (1u64 << core) != 0) {
+                libc::CPU_SET(core, &mut set);
+            }
+            libc::sched_setaffinity(
+                pid as libc::pid_t,
+                std::mem::size_of::<libc::cpu_set_t>(),
+                &set,
+            )
+        };
+        if result != 0 {
+            bail!("sched_setaffinity({}) failed: {}", pid, Errno::last());
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn apply_cpu_affinity(_pid: u32, _mask: u64) -> Result<()> {
+        bail!("CPU affinity is not supported on this platform")
+    }
+
+    pub fn effective_priority(pid: u32) -> Option<Priority> {
+        // -1 is a valid nice value, so errno tells failures apart.
+        Errno::clear();
+        let nice = unsafe { libc::getpriority(libc::PRIO_PROCESS as _, pid as libc::id_t) };
+        if nice == -1 && Errno::last_raw() != 0 {
+            return None;
+        }
+        Some(Priority::from_nice(nice))
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::Priority;
+    use anyhow::{bail, Result};
+    use windows_sys::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows_sys::Win32::System::Threading::{
+        GetPriorityClass, OpenProcess, SetPriorityClass, SetProcessAffinityMask,
+        ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS, HIGH_PRIORITY_CLASS,
+        IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, PROCESS_QUERY_LIMITED_INFORMATION,
+        PROCESS_SET_INFORMATION,
+    };
+
+    /// Process handle closed on drop.
+    struct OwnedProcess(HANDLE);
+
+    impl OwnedProcess {
+        fn open(pid: u32, access: u32) -> Result<Self> {
+            let handle = unsafe { OpenProcess(access, 0, pid) };
+            if handle.is_null() {
+                bail!(
+                    "OpenProcess({}) failed: {}",
+                    pid,
+                    std::io::Error::last_os_error()
+                );
+            }
+            Ok(Self(handle))
+        }
+    }
+
+    impl Drop for OwnedProcess {
+        fn drop(&mut self) {
+            unsafe { CloseHandle(self.0) };
+        }
+    }
+
+    fn priority_class(priority: Priority) -> u32 {
+        match priority {
+            Priority::Idle => IDLE_PRIORITY_CLASS,
+            Priority::BelowNormal => BELOW_NORMAL_PRIORITY_CLASS,
+            Priority::Normal => NORMAL_PRIORITY_CLASS,
+            Priority::AboveNormal => ABOVE_NORMAL_PRIORITY_CLASS,
+            Priority::High => HIGH_PRIORITY_CLASS,
+        }
+    }
+
+    pub fn apply_priority(pid: u32, priority: Priority) -> Result<()> {
+        let process = OwnedProcess::open(pid, PROCESS_SET_INFORMATION)?;
+        if unsafe { SetPriorityClass(process.0, priority_class(priority)) } == 0 {
+            bail!(
+                "SetPriorityClass({}) failed: {}",
+                pid,
+                std::io::Error::last_os_error()
+            );
+        }
+        Ok(())
+    }
+
+    pub fn apply_cpu_affinity(pid: u32, mask: u64) -> Result<()> {
+        let process = OwnedProcess::open(pid, PROCESS_SET_INFORMATION)?;
+        if unsafe { SetProcessAffinityMask(process.0, mask as usize) } == 0 {
+            bail!(
+                "SetProcessAffinityMask({}) failed: {}",
+                pid,
+                std::io::Error::last_os_error()
+            );
+        }
+        Ok(())
+    }
+
+    pub fn effective_priority(pid: u32) -> Option<Priority> {
+        let process = OwnedProcess::open(pid, PROCESS_QUERY_LIMITED_INFORMATION).ok()?;
+        match unsafe { GetPriorityClass(process.0) } {
+            IDLE_PRIORITY_CLASS => Some(Priority::Idle),
+            BELOW_NORMAL_PRIORITY_CLASS => Some(Priority::BelowNormal),
+            NORMAL_PRIORITY_CLASS => Some(Priority::Normal),
+            ABOVE_NORMAL_PRIORITY_CLASS => Some(Priority::AboveNormal),
+            0 => None,
+            // Realtime is reported as the highest class we know.
+            _ => Some(Priority::High),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nice_values_round_trip() {
+        for priority in [
+            Priority::Idle,
+            Priority::BelowNormal,
+            Priority::Normal,
+            Priority::AboveNormal,
+            Priority::High,
+        ] {
+            assert_eq!(Priority::from_nice(priority.nice_value()), priority);
+        }
+        assert_eq!(Priority::from_nice(-20), Priority::High);
+    }
+
+    #[test]
+    fn affinity_mask_is_checked_against_core_count() {
+        assert!(validate_affinity_mask(0, 8).is_err());
+        assert_eq!(validate_affinity_mask(0b1111, 4).unwrap(), 0b1111);
+        assert!(validate_affinity_mask(0b1_0000, 4).is_err());
+        assert_eq!(validate_affinity_mask(u64::MAX, 64).unwrap(), u64::MAX);
+        assert!(validate_affinity_mask(1 << 40, logical_core_count().min(40)).is_err());
+    }
+
+    #[test]
+    fn priority_serializes_in_snake_case() {
+        assert_eq!(
+            serde_json::to_string(&Priority::BelowNormal).unwrap(),
+            "\"below_normal\""
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn lowered_priority_is_reported_back() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("5")
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        apply_priority(pid, Priority::BelowNormal).unwrap();
+        assert_eq!(effective_priority(pid), Some(Priority::BelowNormal));
+        child.kill().unwrap();
+        let _ = child.wait();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn pins_process_to_first_core() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("5")
+            .spawn()
+            .unwrap();
+        let result = apply_cpu_affinity(child.id(), 0b1);
+        child.kill().unwrap();
+        let _ = child.wait();
+        result.unwrap();
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn lowered_priority_is_reported_back() {
+        let mut child = std::process::Command::new("cmd")
+            .args(["/C", "ping -n 6 127.0.0.1 > NUL"])
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        apply_priority(pid, Priority::BelowNormal).unwrap();
+        assert_eq!(effective_priority(pid), Some(Priority::BelowNormal));
+        apply_cpu_affinity(pid, 0b1).unwrap();
+        child.kill().unwrap();
+        let _ = child.wait();
+    }
+}
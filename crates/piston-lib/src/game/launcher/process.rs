@@ -2,16 +2,23 @@
 use crate::game::launcher::{
     arguments::{build_game_arguments, build_jvm_arguments},
     classpath::{build_classpath_filtered, validate_classpath},
+    log_pipe::{apply_log_config_arg, LogPipe},
     natives::extract_natives,
+    priority::{self, Priority},
     registry::register_instance,
     types::{GameInstance, LaunchResult, LaunchSpec},
 };
 use crate::game::runtime_plan::{RuntimePlan, RuntimeRequest};
 use crate::utils::process::PistonCommandExt;
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::path::Path;
 use std::process::Stdio;
 use std::sync::Arc;
+use std::time::Duration;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
 use tokio::io::AsyncBufReadExt;
 
 #[cfg(windows)]
@@ -25,6 +32,89 @@ use windows_sys::Win32::UI::WindowsAndMessaging::{
 /// stream_type is "stdout" or "stderr"
 pub type LogCallback = Arc<dyn Fn(String, String, String) + Send + Sync + 'static>;
 
+/// How often a running game's resource usage is sampled.
+pub const PROCESS_METRICS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Samples kept per process (10 minutes at [`PROCESS_METRICS_INTERVAL`]).
+pub const PROCESS_METRICS_CAPACITY: usize = 60;
+
+/// Resource usage of a game process at one point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessMetrics {
+    pub pid: u32,
+    /// Resident memory in MiB.
+    pub rss_mb: f32,
+    /// Virtual memory in MiB.
+    pub virt_mb: f32,
+    /// CPU usage since the previous sample; 100 is one full core.
+    pub cpu_pct: f32,
+    /// Scheduling priority the process is running at, when readable.
+    #[serde(default)]
+    pub priority: Option<Priority>,
+    pub sampled_at: DateTime<Utc>,
+}
+
+/// Samples the memory and CPU usage of one process.
+pub struct ProcessSampler {
+    system: System,
+    pid: Pid,
+}
+
+impl ProcessSampler {
+    pub fn new(pid: u32) -> Self {
+        Self {
+            system: System::new(),
+            pid: Pid::from_u32(pid),
+        }
+    }
+
+    /// Current usage, or `None` once the process is gone. CPU usage is
+    /// measured between calls, so the first sample reports 0.
+    pub fn sample(&mut self) -> Option<ProcessMetrics> {
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[self.pid]),
+            true,
+            ProcessRefreshKind::nothing().with_memory().with_cpu(),
+        );
+        let process = self.system.process(self.pid)?;
+        Some(ProcessMetrics {
+            pid: self.pid.as_u32(),
+            rss_mb: bytes_to_mb(process.memory()),
+            virt_mb: bytes_to_mb(process.virtual_memory()),
+            cpu_pct: process.cpu_usage(),
+            priority: priority::effective_priority(self.pid.as_u32()),
+            sampled_at: Utc::now(),
+        })
+    }
+}
+
+fn bytes_to_mb(bytes: u64) -> f32 {
+    (bytes as f64 / (1024.0 * 1024.0)) as f32
+}
+
+/// The last [`PROCESS_METRICS_CAPACITY`] samples of a process, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessMetricsBuffer {
+    samples: VecDeque<ProcessMetrics>,
+}
+
+impl ProcessMetricsBuffer {
+    pub fn push(&mut self, sample: ProcessMetrics) {
+        if self.samples.len() == PROCESS_METRICS_CAPACITY {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub fn latest(&self) -> Option<&ProcessMetrics> {
+        self.samples.back()
+    }
+
+    pub fn to_vec(&self) -> Vec<ProcessMetrics> {
+        self.samples.iter().cloned().collect()
+    }
+}
+
 #[cfg(windows)]
 fn find_main_window(pid: u32) -> Option<HWND> {
     static mut FOUND_HWND: Option<HWND> = None;
@@ -89,6 +179,12 @@ pub async fn launch_prepared_game(
         spec.version_id,
         os
     );
+    if spec.safe_mode {
+        log::warn!(
+            "[launch_game] SAFE MODE session for {}: mods are temporarily disabled",
+            spec.instance_id
+        );
+    }
 
     let manifest = plan.manifest;
 
@@ -158,7 +254,31 @@ pub async fn launch_prepared_game(
 
     // 5. Build JVM arguments (substitutes ${classpath} in manifest with our classpath string)
     log::debug!("Building JVM arguments");
-    let jvm_args = build_jvm_arguments(&spec, &manifest, &natives_dir, &classpath, os);
+    let mut jvm_args = build_jvm_arguments(&spec, &manifest, &natives_dir, &classpath, os);
+
+    // Versions that ship a log4j2 config can have their log events sent
+    // through a pipe that exists before the JVM starts.
+    let uses_log4j = manifest
+        .logging
+        .as_ref()
+        .is_some_and(|logging| logging.client.is_some());
+    let log_pipe = if spec.log_pipe && uses_log4j {
+        match LogPipe::create(&spec.game_dir, &spec.instance_id).await {
+            Ok(pipe) if apply_log_config_arg(&mut jvm_args, &spec.jvm_args, pipe.jvm_arg()) => {
+                Some(pipe)
+            }
+            Ok(_) => {
+                log::info!("Custom log4j configuration set; not capturing logs through a pipe");
+                None
+            }
+            Err(e) => {
+                log::warn!("Failed to set up log pipe, reading stdout only: {:#}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
     log::info!("Launch JVM arguments: {:?}", jvm_args);
 
     // 6. Build game arguments
@@ -346,6 +466,16 @@ pub async fn launch_prepared_game(
         .ok_or_else(|| anyhow::anyhow!("Failed to get process ID"))?;
 
     log::info!("Game process started with PID: {}", pid);
+    apply_scheduling(pid, &spec);
+
+    if let Some(pipe) = log_pipe {
+        pipe.spawn_reader(
+            pid,
+            spec.instance_id.clone(),
+            Some(log_file.clone()),
+            log_callback.clone(),
+        );
+    }
 
     // Extract stdout and stderr for tee-ing to both file and console stream
     let stdout = child.stdout.take();
@@ -497,6 +627,23 @@ pub async fn launch_prepared_game(
     })
 }
 
+/// Apply the spec's priority and CPU affinity to the spawned game. The OS
+/// refusing either is logged; the game keeps running with the defaults.
+fn apply_scheduling(pid: u32, spec: &LaunchSpec) {
+    if let Some(priority) = spec.process_priority {
+        match priority::apply_priority(pid, priority) {
+            Ok(()) => log::info!("Set game process {} priority to {:?}", pid, priority),
+            Err(e) => log::warn!("Could not set game process priority: {}", e),
+        }
+    }
+    if let Some(mask) = spec.cpu_affinity_mask {
+        match priority::apply_cpu_affinity(pid, mask) {
+            Ok(()) => log::info!("Pinned game process {} to cores {:#x}", pid, mask),
+            Err(e) => log::warn!("Could not set game process CPU affinity: {}", e),
+        }
+    }
+}
+
 /// Internal quoting helper used for logs / shell-copy; kept separate so it can be
 /// unit-tested where needed.
 pub(crate) fn quote_arg_internal(s: &str) -> String {
@@ -649,6 +796,43 @@ pub async fn kill_instance(instance_id: &str) -> Result<String> {
 mod tests {
     use super::*;
 
+    fn metrics(pid: u32) -> ProcessMetrics {
+        ProcessMetrics {
+            pid,
+            rss_mb: 1.0,
+            virt_mb: 2.0,
+            cpu_pct: 0.0,
+            priority: None,
+            sampled_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn metrics_buffer_keeps_the_latest_samples() {
+        let mut buffer = ProcessMetricsBuffer::default();
+        for pid in 0..(PROCESS_METRICS_CAPACITY as u32 + 5) {
+            buffer.push(metrics(pid));
+        }
+
+        let samples = buffer.to_vec();
+        assert_eq!(samples.len(), PROCESS_METRICS_CAPACITY);
+        assert_eq!(samples[0].pid, 5);
+        assert_eq!(
+            buffer.latest().map(|m| m.pid),
+            Some(PROCESS_METRICS_CAPACITY as u32 + 4)
+        );
+    }
+
+    #[test]
+    fn samples_the_current_process_until_it_is_gone() {
+        let mut sampler = ProcessSampler::new(std::process::id());
+        let sample = sampler.sample().expect("current process is running");
+        assert_eq!(sample.pid, std::process::id());
+        assert!(sample.rss_mb > 0.0);
+
+        assert!(ProcessSampler::new(u32::MAX).sample().is_none());
+    }
+
     #[test]
     fn test_verify_java() {
         // This test will only work if Java is installed
@@ -1,12 +1,16 @@
 /// Process registry for tracking running game instances
-use crate::game::launcher::types::GameInstance;
-use anyhow::Result;
+use crate::game::launcher::types::{GameInstance, InstanceState};
+use anyhow::{Context, Result};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 use sysinfo::{Pid, System};
 use tokio::sync::RwLock;
 
+/// File in the launcher data directory holding the persisted run states
+pub const RUN_STATE_FILE_NAME: &str = "running_processes.json";
+
 /// Global process registry for tracking running instances (in-memory only)
 pub struct ProcessRegistry {
     /// Map of instance_id -> GameInstance
@@ -162,6 +166,60 @@ pub async fn is_instance_running(instance_id: &str) -> Result<bool> {
     Ok(get_registry()?.is_running(instance_id).await)
 }
 
+/// Re-register instances that survived a launcher restart.
+///
+/// Reads the persisted run states from `data_dir`, re-registers every entry whose
+/// PID is still alive and rewrites the file without the stale ones. Entries are kept
+/// as raw JSON so fields owned by the caller survive the rewrite. The caller is
+/// responsible for attaching its own exit monitor to the returned instances.
+pub async fn reattach_running_processes(data_dir: &Path) -> Result<Vec<GameInstance>> {
+    let path = data_dir.join(RUN_STATE_FILE_NAME);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let data = std::fs::read_to_string(&path).context("Failed to read running processes file")?;
+    let entries: Vec<serde_json::Value> =
+        serde_json::from_str(&data).context("Failed to parse running processes JSON")?;
+
+    let mut sys = System::new_all();
+    sys.refresh_all();
+
+    let mut kept = Vec::new();
+    let mut reattached = Vec::new();
+    for entry in &entries {
+        let state: InstanceState = match serde_json::from_value(entry.clone()) {
+            Ok(state) => state,
+            Err(e) => {
+                log::warn!("Dropping unreadable persisted process entry: {}", e);
+                continue;
+            }
+        };
+
+        if sys.process(Pid::from_u32(state.pid)).is_none() {
+            log::info!(
+                "Removing stale process state for {} (PID {} is no longer running)",
+                state.instance_id,
+                state.pid
+            );
+            continue;
+        }
+
+        let instance = GameInstance::from(&state);
+        register_instance(instance.clone()).await?;
+        kept.push(entry.clone());
+        reattached.push(instance);
+    }
+
+    if kept.len() != entries.len() {
+        let data =
+            serde_json::to_string_pretty(&kept).context("Failed to serialize running processes")?;
+        std::fs::write(&path, data).context("Failed to write running processes file")?;
+    }
+
+    Ok(reattached)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -193,4 +251,46 @@ mod tests {
         registry.unregister("test-1").await.unwrap();
         assert!(!registry.is_running("test-1").await);
     }
+
+    #[tokio::test]
+    async fn test_reattach_prunes_dead_processes() {
+        let _ = init_registry();
+        let temp_dir = TempDir::new().unwrap();
+        let alive = InstanceState {
+            instance_id: "reattach-alive".to_string(),
+            version_id: "1.20.1".to_string(),
+            modloader: Some("fabric".to_string()),
+            pid: std::process::id(),
+            started_at: Utc::now().to_rfc3339(),
+            log_file: temp_dir.path().join("alive.log"),
+            game_dir: temp_dir.path().to_path_buf(),
+        };
+        let dead = InstanceState {
+            instance_id: "reattach-dead".to_string(),
+            pid: u32::MAX - 1,
+            ..alive.clone()
+        };
+        let mut alive_entry = serde_json::to_value(&alive).unwrap();
+        alive_entry["safe_mode"] = serde_json::json!(true);
+        let entries = vec![alive_entry, serde_json::to_value(&dead).unwrap()];
+        std::fs::write(
+            temp_dir.path().join(RUN_STATE_FILE_NAME),
+            serde_json::to_string(&entries).unwrap(),
+        )
+        .unwrap();
+
+        let reattached = reattach_running_processes(temp_dir.path()).await.unwrap();
+        assert_eq!(reattached.len(), 1);
+        assert_eq!(reattached[0].instance_id, "reattach-alive");
+        assert!(is_instance_running("reattach-alive").await.unwrap());
+        assert!(!is_instance_running("reattach-dead").await.unwrap());
+
+        let remaining: Vec<serde_json::Value> = serde_json::from_str(
+            &std::fs::read_to_string(temp_dir.path().join(RUN_STATE_FILE_NAME)).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0]["instance_id"], "reattach-alive");
+        assert_eq!(remaining[0]["safe_mode"], true);
+    }
 }
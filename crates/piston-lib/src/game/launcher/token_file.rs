@@ -0,0 +1,151 @@
+//! Current access token of a running game, shared through a file.
+//!
+//! The launcher writes `<game_dir>/.vesta/current_token.json` when the game
+//! starts and rewrites it every [`TOKEN_REFRESH_INTERVAL`] with a freshly
+//! validated token, so a helper inside the game can reconnect after the
+//! token passed on the command line expired. The file is removed when the
+//! game exits. Format (`format_version` 1):
+//!
+//! ```json
+//! {
+//!   "format_version": 1,
+//!   "uuid": "069a79f444e94726a5befca90e38aaf5",
+//!   "username": "Notch",
+//!   "access_token": "eyJ...",
+//!   "expires_at": "2026-10-18T12:00:00+00:00",
+//!   "refreshed_at": "2026-10-17T12:20:00+00:00"
+//! }
+//! ```
+//!
+//! `expires_at` is null when the expiry is unknown. The file is replaced
+//! atomically, so readers never see a partial write, and on Unix only the
+//! owner can read it.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const TOKEN_FILE_NAME: &str = "current_token.json";
+pub const TOKEN_FILE_FORMAT_VERSION: u32 = 1;
+pub const TOKEN_REFRESH_INTERVAL: Duration = Duration::from_secs(20 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CurrentToken {
+    pub format_version: u32,
+    pub uuid: String,
+    pub username: String,
+    pub access_token: String,
+    /// RFC 3339 expiry of `access_token`, when known.
+    pub expires_at: Option<String>,
+    /// RFC 3339 time the file was written.
+    pub refreshed_at: String,
+}
+
+impl CurrentToken {
+    pub fn new(uuid: &str, username: &str, access_token: &str, expires_at: Option<&str>) -> Self {
+        Self {
+            format_version: TOKEN_FILE_FORMAT_VERSION,
+            uuid: uuid.to_string(),
+            username: username.to_string(),
+            access_token: access_token.to_string(),
+            expires_at: expires_at.map(str::to_string),
+            refreshed_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+pub fn token_file_path(game_dir: &Path) -> PathBuf {
+    game_dir.join(".vesta").join(TOKEN_FILE_NAME)
+}
+
+/// Write `token` for the game in `game_dir`, replacing any previous file.
+pub fn write_token_file(game_dir: &Path, token: &CurrentToken) -> Result<()> {
+    let path = token_file_path(game_dir);
+    let dir = path.parent().context("token file has no parent")?;
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    let tmp = path.with_extension("json.tmp");
+    let content = serde_json::to_vec_pretty(token)?;
+    write_private(&tmp, &content).with_context(|| format!("Failed to write {}", tmp.display()))?;
+    std::fs::rename(&tmp, &path)
+        .with_context(|| format!("Failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+pub fn read_token_file(game_dir: &Path) -> Result<Option<CurrentToken>> {
+    let path = token_file_path(game_dir);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read(&path)?;
+    Ok(Some(serde_json::from_slice(&content)?))
+}
+
+/// Remove the token file, if any.
+pub fn remove_token_file(game_dir: &Path) -> Result<()> {
+    match std::fs::remove_file(token_file_path(game_dir)) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(unix)]
+fn write_private(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    file.write_all(content)
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &Path, content: &[u8]) -> std::io::Result<()> {
+    std::fs::write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_file_round_trips_and_is_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(read_token_file(dir.path()).unwrap(), None);
+
+        let first = CurrentToken::new("uuid", "Steve", "token-1", None);
+        write_token_file(dir.path(), &first).unwrap();
+        let second = CurrentToken::new("uuid", "Steve", "token-2", Some("2026-10-18T00:00:00Z"));
+        write_token_file(dir.path(), &second).unwrap();
+
+        assert_eq!(read_token_file(dir.path()).unwrap(), Some(second));
+        assert!(!dir.path().join(".vesta/current_token.json.tmp").exists());
+
+        remove_token_file(dir.path()).unwrap();
+        assert!(!token_file_path(dir.path()).exists());
+        remove_token_file(dir.path()).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn token_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        write_token_file(
+            dir.path(),
+            &CurrentToken::new("uuid", "Alex", "token", None),
+        )
+        .unwrap();
+        let mode = std::fs::metadata(token_file_path(dir.path()))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -1,4 +1,5 @@
 /// Core types for game launching
+use crate::game::launcher::priority::Priority;
 use crate::game::metadata::ModloaderType;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -81,6 +82,21 @@ pub struct LaunchSpec {
 
     /// Post-exit hook command (optional)
     pub post_exit_hook: Option<String>,
+
+    /// Whether this is a safe-mode session (mods temporarily disabled)
+    pub safe_mode: bool,
+
+    /// Capture log4j output through a launcher-created pipe (see `log_pipe`)
+    pub log_pipe: bool,
+
+    /// Server to join once the game has loaded (host, port)
+    pub connect_server: Option<(String, u16)>,
+
+    /// Scheduling priority applied after spawn (optional)
+    pub process_priority: Option<Priority>,
+
+    /// Cores the game may run on, bit 0 being the first (optional)
+    pub cpu_affinity_mask: Option<u64>,
 }
 
 impl LaunchSpec {
@@ -187,6 +203,22 @@ pub struct InstanceState {
     pub game_dir: PathBuf,
 }
 
+impl From<&InstanceState> for GameInstance {
+    fn from(state: &InstanceState) -> Self {
+        Self {
+            instance_id: state.instance_id.clone(),
+            version_id: state.version_id.clone(),
+            modloader: state.modloader.as_ref().and_then(|m| m.parse().ok()),
+            pid: state.pid,
+            started_at: chrono::DateTime::parse_from_rfc3339(&state.started_at)
+                .map(|dt| dt.with_timezone(&chrono::Utc))
+                .unwrap_or_else(|_| chrono::Utc::now()),
+            log_file: state.log_file.clone(),
+            game_dir: state.game_dir.clone(),
+        }
+    }
+}
+
 impl From<&GameInstance> for InstanceState {
     fn from(instance: &GameInstance) -> Self {
         Self {
@@ -250,6 +282,11 @@ mod tests {
             wrapper_command: None,
             pre_launch_hook: None,
             post_exit_hook: None,
+            safe_mode: false,
+            log_pipe: false,
+            connect_server: None,
+            process_priority: None,
+            cpu_affinity_mask: None,
         };
 
         assert_eq!(spec.installed_version_id(), "1.20.1");
@@ -283,6 +320,11 @@ mod tests {
             wrapper_command: None,
             pre_launch_hook: None,
             post_exit_hook: None,
+            safe_mode: false,
+            log_pipe: false,
+            connect_server: None,
+            process_priority: None,
+            cpu_affinity_mask: None,
         };
 
         assert_eq!(spec.installed_version_id(), "forge-loader-47.2.0-1.20.1");
@@ -3,8 +3,14 @@ use crate::game::launcher::version_parser::{
     Argument, AssetIndex, ExtractRules, JavaVersion, Library, LoggingConfig, Rule, RuleAction,
     VersionManifest,
 };
+use anyhow::Context;
 use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Suffix of the flattened manifest written next to an installed version JSON.
+pub const UNIFIED_MANIFEST_SUFFIX: &str = ".unified.json";
 
 /// A unified manifest that combines vanilla and modloader requirements.
 /// This is the final resolved state used by the installer and launcher.
@@ -257,26 +263,7 @@ impl UnifiedManifest {
     }
 
     pub fn save_to_path(&self, path: &std::path::Path) -> anyhow::Result<()> {
-        if path.exists() {
-            let meta = std::fs::symlink_metadata(path)?;
-            if meta.file_type().is_symlink() {
-                anyhow::bail!("refusing to write manifest through symlink: {:?}", path);
-            }
-        }
-
-        let parent = path
-            .parent()
-            .ok_or_else(|| anyhow::anyhow!("manifest path has no parent: {:?}", path))?;
-        let file_name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("manifest.json");
-        let temp_path = parent.join(format!(".{file_name}.tmp"));
-
-        let json = serde_json::to_string_pretty(self)?;
-        std::fs::write(&temp_path, json)?;
-        std::fs::rename(&temp_path, path)?;
-        Ok(())
+        write_manifest_json(path, self)
     }
 
     /// Load a unified manifest, apply native-arch normalization, and persist when stale.
@@ -330,6 +317,163 @@ impl UnifiedManifest {
     }
 }
 
+/// SHA-1 of one version JSON a unified manifest was generated from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestSourceHash {
+    pub id: String,
+    pub sha1: String,
+}
+
+/// Contents of `<id>.unified.json`: the fully resolved manifest plus the
+/// version JSONs (child first) it was built from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnifiedManifestFile {
+    pub sources: Vec<ManifestSourceHash>,
+    #[serde(flatten)]
+    pub manifest: UnifiedManifest,
+}
+
+/// Path of the flattened manifest for an installed version.
+pub fn unified_manifest_path(data_dir: &Path, version_id: &str) -> PathBuf {
+    data_dir
+        .join("versions")
+        .join(version_id)
+        .join(format!("{}{}", version_id, UNIFIED_MANIFEST_SUFFIX))
+}
+
+/// Flatten the inheritance chain of `installed_version_id` into
+/// `<id>.unified.json` next to its version JSON, so launches (and tools that
+/// cannot follow `inheritsFrom`) read a single file. The existing file is
+/// reused while every JSON in the chain still has the recorded hash.
+pub fn generate_unified_manifest(
+    data_dir: &Path,
+    installed_version_id: &str,
+) -> anyhow::Result<UnifiedManifest> {
+    generate_unified_manifest_for_os(data_dir, installed_version_id, OsType::current())
+}
+
+/// [`generate_unified_manifest`] with library rules evaluated for `os`.
+pub fn generate_unified_manifest_for_os(
+    data_dir: &Path,
+    installed_version_id: &str,
+    os: OsType,
+) -> anyhow::Result<UnifiedManifest> {
+    let path = unified_manifest_path(data_dir, installed_version_id);
+    if let Some(existing) = std::fs::read(&path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<UnifiedManifestFile>(&bytes).ok())
+    {
+        if sources_unchanged(data_dir, &existing.sources) {
+            return Ok(existing.manifest);
+        }
+        log::info!(
+            "[UnifiedManifest] Version chain of {} changed, regenerating {:?}",
+            installed_version_id,
+            path
+        );
+    }
+
+    let file = resolve_unified_chain(data_dir, installed_version_id, os)?;
+    write_manifest_json(&path, &file)?;
+    Ok(file.manifest)
+}
+
+fn version_json_path(data_dir: &Path, version_id: &str) -> PathBuf {
+    data_dir
+        .join("versions")
+        .join(version_id)
+        .join(format!("{}.json", version_id))
+}
+
+fn sha1_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+fn sources_unchanged(data_dir: &Path, sources: &[ManifestSourceHash]) -> bool {
+    !sources.is_empty()
+        && sources.iter().all(|source| {
+            std::fs::read(version_json_path(data_dir, &source.id))
+                .is_ok_and(|bytes| sha1_hex(&bytes) == source.sha1)
+        })
+}
+
+/// Read and merge the version JSONs from `version_id` up to its root. An
+/// installed JSON that is already a unified manifest (as written by the
+/// installer) has no chain left to resolve.
+fn resolve_unified_chain(
+    data_dir: &Path,
+    version_id: &str,
+    os: OsType,
+) -> anyhow::Result<UnifiedManifestFile> {
+    let mut sources: Vec<ManifestSourceHash> = Vec::new();
+    let mut chain: Vec<VersionManifest> = Vec::new();
+    let mut next = Some(version_id.to_string());
+
+    while let Some(id) = next.take() {
+        if sources.iter().any(|source| source.id == id) {
+            anyhow::bail!("Version {} inherits from itself", id);
+        }
+        let path = version_json_path(data_dir, &id);
+        let bytes = std::fs::read(&path).with_context(|| format!("Failed to read {:?}", path))?;
+        sources.push(ManifestSourceHash {
+            id: id.clone(),
+            sha1: sha1_hex(&bytes),
+        });
+
+        if chain.is_empty() {
+            if let Ok(unified) = serde_json::from_slice::<UnifiedManifest>(&bytes) {
+                if !unified.minecraft_version.is_empty() {
+                    return Ok(UnifiedManifestFile {
+                        sources,
+                        manifest: unified,
+                    });
+                }
+            }
+        }
+
+        let manifest: VersionManifest = serde_json::from_slice(&bytes)
+            .with_context(|| format!("Failed to parse {:?}", path))?;
+        next = manifest.inherits_from.clone();
+        chain.push(manifest);
+    }
+
+    let mut resolved = chain.pop().context("Empty version chain")?;
+    let minecraft_version = resolved.id.clone();
+    while let Some(child) = chain.pop() {
+        resolved = crate::game::launcher::version_parser::merge_manifests(resolved, child)?;
+    }
+
+    let mut manifest = UnifiedManifest::merge(resolved, None, os);
+    manifest.minecraft_version = minecraft_version;
+    Ok(UnifiedManifestFile { sources, manifest })
+}
+
+/// Write pretty JSON through a temp file, refusing to follow a symlink.
+fn write_manifest_json<T: Serialize>(path: &Path, value: &T) -> anyhow::Result<()> {
+    if path.exists() {
+        let meta = std::fs::symlink_metadata(path)?;
+        if meta.file_type().is_symlink() {
+            anyhow::bail!("refusing to write manifest through symlink: {:?}", path);
+        }
+    }
+
+    let parent = path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("manifest path has no parent: {:?}", path))?;
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("manifest.json");
+    let temp_path = parent.join(format!(".{file_name}.tmp"));
+
+    let json = serde_json::to_string_pretty(value)?;
+    std::fs::write(&temp_path, json)?;
+    std::fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
 impl From<VersionManifest> for UnifiedManifest {
     fn from(v: VersionManifest) -> Self {
         UnifiedManifest::merge(v, None, OsType::current())
@@ -482,7 +482,17 @@ pub(crate) fn merge_manifests(
         parent.minecraft_arguments = child.minecraft_arguments;
     }
 
-    // Merge libraries (child libraries come after parent)
+    // Merge libraries (child libraries come after parent). A library the child
+    // declares again replaces the parent's copy, e.g. the ASM or Guava versions
+    // pinned by Quilt's profile JSON.
+    let child_keys: std::collections::HashSet<String> = child
+        .libraries
+        .iter()
+        .map(|lib| library_key(&lib.name))
+        .collect();
+    parent
+        .libraries
+        .retain(|lib| !child_keys.contains(&library_key(&lib.name)));
     parent.libraries.extend(child.libraries);
 
     // Child's asset index overrides parent's
@@ -511,6 +521,24 @@ pub(crate) fn merge_manifests(
     Ok(parent)
 }
 
+/// Maven coordinates without the version: `group:artifact[:classifier][@ext]`.
+fn library_key(name: &str) -> String {
+    let mut parts = name.split(':');
+    let group = parts.next().unwrap_or_default();
+    let artifact = parts.next().unwrap_or_default();
+    let version = parts.next().unwrap_or_default();
+    let mut key = format!("{}:{}", group, artifact);
+    for classifier in parts {
+        key.push(':');
+        key.push_str(classifier);
+    }
+    if let Some((_, extension)) = version.split_once('@') {
+        key.push('@');
+        key.push_str(extension);
+    }
+    key
+}
+
 /// Validate a merged manifest to ensure it can be launched successfully
 pub fn validate_manifest(manifest: &VersionManifest) -> Result<Vec<String>> {
     let mut warnings = Vec::new();
@@ -844,4 +872,51 @@ mod tests {
             "Merged args must contain --fml.forgeVersion"
         );
     }
+
+    #[test]
+    fn test_merge_manifests_quilt_profile_replaces_parent_libraries() {
+        let parent: VersionManifest = serde_json::from_value(serde_json::json!({
+            "id": "1.20.1",
+            "mainClass": "net.minecraft.client.main.Main",
+            "libraries": [
+                {"name": "com.google.guava:guava:31.1-jre"},
+                {"name": "org.lwjgl:lwjgl:3.3.1"},
+                {"name": "org.lwjgl:lwjgl:3.3.1:natives-linux"}
+            ]
+        }))
+        .unwrap();
+        let child: VersionManifest = serde_json::from_value(serde_json::json!({
+            "id": "1.20.1-quilt-0.26.4",
+            "inheritsFrom": "1.20.1",
+            "mainClass": "org.quiltmc.loader.impl.launch.knot.KnotClient",
+            "arguments": {"game": []},
+            "libraries": [
+                {"name": "com.google.guava:guava:32.1.2-jre", "url": "https://maven.quiltmc.org/repository/release/"},
+                {"name": "org.quiltmc:hashed:1.20.1", "url": "https://maven.quiltmc.org/repository/release/"},
+                {"name": "org.quiltmc:quilt-loader:0.26.4", "url": "https://maven.quiltmc.org/repository/release/"}
+            ]
+        }))
+        .unwrap();
+
+        let merged = merge_manifests(parent, child).unwrap();
+        let names: Vec<&str> = merged
+            .libraries
+            .iter()
+            .map(|lib| lib.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "org.lwjgl:lwjgl:3.3.1",
+                "org.lwjgl:lwjgl:3.3.1:natives-linux",
+                "com.google.guava:guava:32.1.2-jre",
+                "org.quiltmc:hashed:1.20.1",
+                "org.quiltmc:quilt-loader:0.26.4",
+            ]
+        );
+        assert_eq!(
+            merged.main_class.as_deref(),
+            Some("org.quiltmc.loader.impl.launch.knot.KnotClient")
+        );
+    }
 }
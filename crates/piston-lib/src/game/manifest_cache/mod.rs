@@ -2,13 +2,15 @@ use anyhow::Result;
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
+use crate::game::installer::core::traffic::{record_traffic, TrafficCategory};
 use crate::game::metadata::types::{
     GameVersionMetadata, LatestVersions, LoaderVersionInfo, ModloaderType, MojangVersionManifest,
-    PistonMetadata,
+    PistonMetadata, VersionType,
 };
 use serde::{Deserialize, Serialize};
 
@@ -83,15 +85,11 @@ impl ManifestCache {
     }
 
     async fn read_java_info_cache(&self) -> Result<JavaInfoCache> {
-        let content = tokio::fs::read_to_string(self.java_info_path()).await?;
-        Ok(serde_json::from_str(&content)?)
+        read_cache_file(&self.java_info_path()).await
     }
 
     async fn write_java_info_cache(&self, cache: &JavaInfoCache) -> Result<()> {
-        tokio::fs::create_dir_all(&self.cache_dir).await?;
-        let json = serde_json::to_string(cache)?;
-        tokio::fs::write(self.java_info_path(), json).await?;
-        Ok(())
+        write_cache_file(&self.java_info_path(), cache).await
     }
 
     /// Resolve and persist the Java major required by a Minecraft version.
@@ -168,10 +166,9 @@ impl ManifestCache {
         // Offline mode: read from disk only, fail if not cached
         if self.offline {
             let disk_path = self.cache_dir.join(format!("{slug}.json"));
-            let content = tokio::fs::read_to_string(&disk_path)
+            let cached: CachedManifest = read_cache_file(&disk_path)
                 .await
                 .map_err(|e| anyhow::anyhow!("Manifest '{}' not cached on disk: {}", slug, e))?;
-            let cached: CachedManifest = serde_json::from_str(&content)?;
             return Ok(Arc::new(cached.data));
         }
 
@@ -189,62 +186,57 @@ impl ManifestCache {
         // Try disk cache
         let disk_path = self.cache_dir.join(format!("{slug}.json"));
         let _from_disk = if disk_path.exists() {
-            match tokio::fs::read_to_string(&disk_path).await {
-                Ok(content) => {
-                    match serde_json::from_str::<CachedManifest>(&content) {
-                        Ok(cached) => {
-                            // Try ETag revalidation
-                            if let Some(etag) = &cached.etag {
-                                match self
-                                    .try_revalidate(slug, etag, cached.last_modified.as_deref())
-                                    .await
-                                {
-                                    Ok(Some((fresh_data, new_etag, new_lm))) => {
-                                        // 200 — data changed, use fresh headers + body
-                                        let data = Arc::new(fresh_data);
-                                        let etag = new_etag.or_else(|| cached.etag.clone());
-                                        let lm = new_lm.or_else(|| cached.last_modified.clone());
-                                        self.store_in_memory(
-                                            slug,
-                                            Arc::clone(&data),
-                                            etag.clone(),
-                                            lm.clone(),
-                                        )
-                                        .await;
-                                        // Also persist to disk with new headers
-                                        let disk_cached = CachedManifest {
-                                            etag: etag,
-                                            last_modified: lm,
-                                            fetched_at: Utc::now(),
-                                            data: (*data).clone(),
-                                        };
-                                        if let Ok(json) = serde_json::to_string(&disk_cached) {
-                                            let _ = tokio::fs::write(&disk_path, json).await;
-                                        }
-                                        return Ok(data);
-                                    }
-                                    Ok(None) => {
-                                        // 304 — not modified, use cached
-                                        let data = Arc::new(cached.data);
-                                        self.store_in_memory(
-                                            slug,
-                                            Arc::clone(&data),
-                                            cached.etag,
-                                            cached.last_modified,
-                                        )
-                                        .await;
-                                        return Ok(data);
-                                    }
-                                    Err(_) => {
-                                        // Revalidation failed, fall through to fresh fetch
-                                        Some(cached)
-                                    }
+            match read_cache_file::<CachedManifest>(&disk_path).await {
+                Ok(cached) => {
+                    // Try ETag revalidation
+                    if let Some(etag) = &cached.etag {
+                        match self
+                            .try_revalidate(slug, etag, cached.last_modified.as_deref())
+                            .await
+                        {
+                            Ok(Some((fresh_data, new_etag, new_lm))) => {
+                                // 200 — data changed, use fresh headers + body
+                                let data = Arc::new(fresh_data);
+                                let etag = new_etag.or_else(|| cached.etag.clone());
+                                let lm = new_lm.or_else(|| cached.last_modified.clone());
+                                self.store_in_memory(
+                                    slug,
+                                    Arc::clone(&data),
+                                    etag.clone(),
+                                    lm.clone(),
+                                )
+                                .await;
+                                // Also persist to disk with new headers
+                                let disk_cached = CachedManifest {
+                                    etag,
+                                    last_modified: lm,
+                                    fetched_at: Utc::now(),
+                                    data: (*data).clone(),
+                                };
+                                if let Err(e) = write_cache_file(&disk_path, &disk_cached).await {
+                                    log::warn!("Failed to persist {slug} manifest: {e}");
                                 }
-                            } else {
+                                return Ok(data);
+                            }
+                            Ok(None) => {
+                                // 304 — not modified, use cached
+                                let data = Arc::new(cached.data);
+                                self.store_in_memory(
+                                    slug,
+                                    Arc::clone(&data),
+                                    cached.etag,
+                                    cached.last_modified,
+                                )
+                                .await;
+                                return Ok(data);
+                            }
+                            Err(_) => {
+                                // Revalidation failed, fall through to fresh fetch
                                 Some(cached)
                             }
                         }
-                        Err(_) => None,
+                    } else {
+                        Some(cached)
                     }
                 }
                 Err(_) => None,
@@ -289,6 +281,7 @@ impl ManifestCache {
             .and_then(|v| v.to_str().ok())
             .map(|s| s.to_string());
         let body = resp.bytes().await?;
+        record_traffic(TrafficCategory::Metadata, body.len() as u64);
         let data: serde_json::Value = serde_json::from_slice(&body)?;
         Ok(Some((data, new_etag, new_last_modified)))
     }
@@ -312,6 +305,7 @@ impl ManifestCache {
             .and_then(|v| v.to_str().ok())
             .map(|s| s.to_string());
         let body = resp.bytes().await?;
+        record_traffic(TrafficCategory::Metadata, body.len() as u64);
         let data: serde_json::Value = serde_json::from_slice(&body)?;
         let data_arc = Arc::new(data);
 
@@ -322,11 +316,7 @@ impl ManifestCache {
             fetched_at: Utc::now(),
             data: (*data_arc).clone(),
         };
-        if let Some(parent) = disk_path.parent() {
-            tokio::fs::create_dir_all(parent).await?;
-        }
-        let json = serde_json::to_string(&cached)?;
-        tokio::fs::write(disk_path, json).await?;
+        write_cache_file(disk_path, &cached).await?;
 
         // Store in memory
         self.store_in_memory(
@@ -391,7 +381,10 @@ impl ManifestCache {
             dummy_loaders: Vec<LoaderVersionInfo>,
         }
 
-        fn parse_loader(manifest: &Option<Arc<serde_json::Value>>) -> Option<LoaderInfo> {
+        fn parse_loader(
+            loader_type: ModloaderType,
+            manifest: &Option<Arc<serde_json::Value>>,
+        ) -> Option<LoaderInfo> {
             let parsed: crate::game::metadata::types::ModrinthManifest =
                 match serde_json::from_value((**manifest.as_ref()?).clone()) {
                     Ok(m) => m,
@@ -413,6 +406,7 @@ impl ManifestCache {
                             stable: lv.stable,
                             url: Some(lv.url.clone()),
                             sha1: None,
+                            changelog_url: loader_type.changelog_url(&lv.id),
                             metadata: None,
                         })
                         .collect();
@@ -427,6 +421,7 @@ impl ManifestCache {
                                 stable: lv.stable,
                                 url: Some(lv.url.clone()),
                                 sha1: None,
+                                changelog_url: loader_type.changelog_url(&lv.id),
                                 metadata: None,
                             })
                             .collect(),
@@ -445,10 +440,22 @@ impl ManifestCache {
         }
 
         let loader_infos = [
-            (ModloaderType::Fabric, parse_loader(&fabric)),
-            (ModloaderType::Quilt, parse_loader(&quilt)),
-            (ModloaderType::Forge, parse_loader(&forge)),
-            (ModloaderType::NeoForge, parse_loader(&neo)),
+            (
+                ModloaderType::Fabric,
+                parse_loader(ModloaderType::Fabric, &fabric),
+            ),
+            (
+                ModloaderType::Quilt,
+                parse_loader(ModloaderType::Quilt, &quilt),
+            ),
+            (
+                ModloaderType::Forge,
+                parse_loader(ModloaderType::Forge, &forge),
+            ),
+            (
+                ModloaderType::NeoForge,
+                parse_loader(ModloaderType::NeoForge, &neo),
+            ),
         ];
 
         let mut game_versions = Vec::new();
@@ -468,12 +475,11 @@ impl ManifestCache {
 
             game_versions.push(GameVersionMetadata {
                 id: mv.id.clone(),
-                version_type: mv.version_type.clone(),
+                version_type: VersionType::from(mv.version_type.as_str()),
                 release_time: mv
                     .release_time
                     .parse::<DateTime<Utc>>()
                     .unwrap_or_else(|_| Utc::now()),
-                stable: mv.version_type == "release",
                 loaders,
             });
         }
@@ -483,11 +489,10 @@ impl ManifestCache {
 
         // Java data: from cache when offline, from network otherwise.
         let (required_java_major_versions, java_major_version_by_game_version) = if self.offline {
-            let path = self.java_info_path();
-            let content = tokio::fs::read_to_string(&path)
+            let cache: JavaInfoCache = self
+                .read_java_info_cache()
                 .await
                 .map_err(|e| anyhow::anyhow!("Java info cache not available: {e}"))?;
-            let cache: JavaInfoCache = serde_json::from_str(&content)?;
             (
                 cache.required_java_major_versions,
                 cache.java_major_version_by_game_version,
@@ -539,7 +544,7 @@ impl ManifestCache {
             (required, java_versions)
         };
 
-        Ok(PistonMetadata {
+        let mut metadata = PistonMetadata {
             last_updated: Utc::now(),
             game_versions,
             latest: LatestVersions {
@@ -548,7 +553,55 @@ impl ManifestCache {
             },
             required_java_major_versions,
             java_major_version_by_game_version,
-        })
+            loader_supported_game_versions: HashMap::new(),
+        };
+        metadata.index_loader_support();
+        Ok(metadata)
+    }
+}
+
+/// Distinguishes temp files of concurrent writers in this process.
+static TEMP_FILE_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Replace `path` with `value` as JSON. The data goes to a uniquely named
+/// temp file next to it first and is renamed into place, so readers see
+/// either the old or the new file and never a partial write.
+async fn write_cache_file<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let parent = path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("cache path has no parent: {:?}", path))?;
+    tokio::fs::create_dir_all(parent).await?;
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("manifest.json");
+    let temp_path = parent.join(format!(
+        ".{file_name}.{}.{}.tmp",
+        std::process::id(),
+        TEMP_FILE_SEQ.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let json = serde_json::to_vec(value)?;
+    tokio::fs::write(&temp_path, json).await?;
+    if let Err(e) = tokio::fs::rename(&temp_path, path).await {
+        let _ = tokio::fs::remove_file(&temp_path).await;
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Read and parse a cache file. A file that doesn't parse is read once more
+/// after a short pause before giving up, in case another launcher process
+/// that still writes in place was mid-write.
+async fn read_cache_file<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    let content = tokio::fs::read(path).await?;
+    match serde_json::from_slice(&content) {
+        Ok(value) => Ok(value),
+        Err(_) => {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let content = tokio::fs::read(path).await?;
+            Ok(serde_json::from_slice(&content)?)
+        }
     }
 }
 
@@ -641,6 +694,65 @@ mod tests {
             21
         );
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn readers_never_see_a_partial_manifest_write() {
+        let tmp = tempdir().unwrap();
+        let cache_dir = tmp.path().join("manifests");
+        let disk_path = cache_dir.join("minecraft.json");
+        let manifest = |versions: usize| CachedManifest {
+            etag: None,
+            last_modified: None,
+            fetched_at: Utc::now(),
+            data: serde_json::json!({
+                "versions": (0..versions)
+                    .map(|i| serde_json::json!({ "id": format!("1.{i}") }))
+                    .collect::<Vec<_>>(),
+            }),
+        };
+        write_cache_file(&disk_path, &manifest(1)).await.unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(2);
+        let writer = {
+            let disk_path = disk_path.clone();
+            tokio::spawn(async move {
+                let mut round = 0;
+                while Instant::now() < deadline {
+                    round += 1;
+                    let versions = if round % 2 == 0 { 5 } else { 2_000 };
+                    write_cache_file(&disk_path, &manifest(versions))
+                        .await
+                        .unwrap();
+                }
+                round
+            })
+        };
+        let readers: Vec<_> = (0..8)
+            .map(|_| {
+                let cache = ManifestCache::new_offline(cache_dir.clone());
+                tokio::spawn(async move {
+                    let mut reads = 0;
+                    while Instant::now() < deadline {
+                        let data = cache.get_or_fetch("minecraft").await.unwrap();
+                        assert!(!data["versions"].as_array().unwrap().is_empty());
+                        reads += 1;
+                    }
+                    reads
+                })
+            })
+            .collect();
+
+        assert!(writer.await.unwrap() > 1);
+        for reader in readers {
+            assert!(reader.await.unwrap() > 0);
+        }
+        let leftovers: Vec<_> = std::fs::read_dir(&cache_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .filter(|name| name.to_string_lossy().ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty(), "temp files left: {leftovers:?}");
+    }
 }
 
 fn manifest_url(slug: &str) -> String {
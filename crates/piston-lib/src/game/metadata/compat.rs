@@ -0,0 +1,268 @@
+//! Pre-install check that a Fabric/Quilt + Minecraft combination can actually
+//! be installed. New snapshots usually appear in Mojang's manifest before the
+//! loaders publish intermediary mappings for them, and installing in that
+//! window fails part-way through.
+
+use super::types::ModloaderType;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+const FABRIC_META_URL: &str = "https://meta.fabricmc.net/v2";
+const QUILT_META_URL: &str = "https://meta.quiltmc.org/v3";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum LoaderCompatibility {
+    Ready,
+    /// Minecraft knows the version but the loader has no mappings for it yet.
+    NotYetSupported {
+        newest_supported_snapshot: Option<String>,
+    },
+    /// Either the Minecraft version or the requested loader version does not
+    /// exist; `version` names the one that was not found.
+    UnknownVersion {
+        version: String,
+    },
+}
+
+impl LoaderCompatibility {
+    /// User-facing reason the install cannot proceed, or `None` when ready.
+    pub fn describe(&self, loader: ModloaderType, game_version: &str) -> Option<String> {
+        let loader_name = match loader {
+            ModloaderType::Fabric => "Fabric",
+            ModloaderType::Quilt => "Quilt",
+            other => other.as_str(),
+        };
+        match self {
+            LoaderCompatibility::Ready => None,
+            LoaderCompatibility::NotYetSupported {
+                newest_supported_snapshot: Some(newest),
+            } => Some(format!(
+                "{} does not yet support {} (newest supported snapshot: {})",
+                loader_name, game_version, newest
+            )),
+            LoaderCompatibility::NotYetSupported {
+                newest_supported_snapshot: None,
+            } => Some(format!(
+                "{} does not yet support {}",
+                loader_name, game_version
+            )),
+            LoaderCompatibility::UnknownVersion { version } if version == game_version => {
+                Some(format!("Minecraft {} is not a known version", game_version))
+            }
+            LoaderCompatibility::UnknownVersion { version } => Some(format!(
+                "{} {} is not available for Minecraft {}",
+                loader_name, version, game_version
+            )),
+        }
+    }
+}
+
+/// Entry of `<meta>/versions/game`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetaGameVersion {
+    pub version: String,
+    pub stable: bool,
+}
+
+/// Entry of `<meta>/versions/loader/<game_version>`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetaLoaderEntry {
+    pub loader: MetaLoaderVersion,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetaLoaderVersion {
+    pub version: String,
+}
+
+fn meta_base_url(loader: ModloaderType) -> Option<&'static str> {
+    match loader {
+        ModloaderType::Fabric => Some(FABRIC_META_URL),
+        ModloaderType::Quilt => Some(QUILT_META_URL),
+        _ => None,
+    }
+}
+
+/// Query the loader's meta service for `version_id`. Loaders without
+/// intermediary mappings (Vanilla, Forge, NeoForge) are always `Ready`; their
+/// per-version availability already comes from the cached metadata.
+pub async fn check_loader_compatibility(
+    version_id: &str,
+    loader: ModloaderType,
+    loader_version: Option<&str>,
+) -> Result<LoaderCompatibility> {
+    let Some(base_url) = meta_base_url(loader) else {
+        return Ok(LoaderCompatibility::Ready);
+    };
+    let client = crate::client::shared_client();
+
+    let game_versions: Vec<MetaGameVersion> =
+        super::fetcher::send_with_retry(client, &format!("{}/versions/game", base_url), 3, 1000)
+            .await?
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse {} game version list", loader))?;
+
+    let supported = game_versions.iter().any(|gv| gv.version == version_id);
+    let loader_entries: Option<Vec<MetaLoaderEntry>> = match (supported, loader_version) {
+        (true, Some(_)) => Some(
+            super::fetcher::send_with_retry(
+                client,
+                &format!("{}/versions/loader/{}", base_url, version_id),
+                3,
+                1000,
+            )
+            .await?
+            .json()
+            .await
+            .with_context(|| format!("Failed to parse {} loader list", loader))?,
+        ),
+        _ => None,
+    };
+    let known_minecraft_version = if supported {
+        true
+    } else {
+        super::fetcher::fetch_modrinth_mc_manifest(client)
+            .await?
+            .versions
+            .iter()
+            .any(|v| v.id == version_id)
+    };
+
+    Ok(evaluate_loader_compatibility(
+        version_id,
+        loader_version,
+        &game_versions,
+        loader_entries.as_deref(),
+        known_minecraft_version,
+    ))
+}
+
+/// Decide compatibility from already-fetched meta responses. `game_versions`
+/// is the loader's supported list, newest first, as the meta services return it.
+pub fn evaluate_loader_compatibility(
+    version_id: &str,
+    loader_version: Option<&str>,
+    game_versions: &[MetaGameVersion],
+    loader_entries: Option<&[MetaLoaderEntry]>,
+    known_minecraft_version: bool,
+) -> LoaderCompatibility {
+    if !game_versions.iter().any(|gv| gv.version == version_id) {
+        if !known_minecraft_version {
+            return LoaderCompatibility::UnknownVersion {
+                version: version_id.to_string(),
+            };
+        }
+        return LoaderCompatibility::NotYetSupported {
+            newest_supported_snapshot: game_versions
+                .iter()
+                .find(|gv| !gv.stable)
+                .map(|gv| gv.version.clone()),
+        };
+    }
+
+    if let (Some(wanted), Some(entries)) = (loader_version, loader_entries) {
+        if !entries.iter().any(|entry| entry.loader.version == wanted) {
+            return LoaderCompatibility::UnknownVersion {
+                version: wanted.to_string(),
+            };
+        }
+    }
+
+    LoaderCompatibility::Ready
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Trimmed `https://meta.fabricmc.net/v2/versions/game` from a day when
+    /// 24w10a was out but intermediary had not been published yet.
+    const FABRIC_GAME_VERSIONS: &str = r#"[
+        {"version": "24w09a", "stable": false},
+        {"version": "24w07a", "stable": false},
+        {"version": "1.20.4", "stable": true},
+        {"version": "1.20.4-rc1", "stable": false},
+        {"version": "1.20.3", "stable": true}
+    ]"#;
+
+    /// Trimmed `https://meta.fabricmc.net/v2/versions/loader/24w09a`.
+    const FABRIC_LOADERS_24W09A: &str = r#"[
+        {
+            "loader": {"separator": ".", "build": 16, "maven": "net.fabricmc:fabric-loader:0.15.7", "version": "0.15.7", "stable": true},
+            "intermediary": {"maven": "net.fabricmc:intermediary:24w09a", "version": "24w09a", "stable": false}
+        },
+        {
+            "loader": {"separator": ".", "build": 15, "maven": "net.fabricmc:fabric-loader:0.15.6", "version": "0.15.6", "stable": false},
+            "intermediary": {"maven": "net.fabricmc:intermediary:24w09a", "version": "24w09a", "stable": false}
+        }
+    ]"#;
+
+    fn game_versions() -> Vec<MetaGameVersion> {
+        serde_json::from_str(FABRIC_GAME_VERSIONS).unwrap()
+    }
+
+    fn loaders() -> Vec<MetaLoaderEntry> {
+        serde_json::from_str(FABRIC_LOADERS_24W09A).unwrap()
+    }
+
+    #[test]
+    fn supported_snapshot_is_ready() {
+        let result = evaluate_loader_compatibility(
+            "24w09a",
+            Some("0.15.7"),
+            &game_versions(),
+            Some(&loaders()),
+            true,
+        );
+        assert_eq!(result, LoaderCompatibility::Ready);
+        assert_eq!(result.describe(ModloaderType::Fabric, "24w09a"), None);
+    }
+
+    #[test]
+    fn unpublished_snapshot_is_not_yet_supported() {
+        let result = evaluate_loader_compatibility("24w10a", None, &game_versions(), None, true);
+        assert_eq!(
+            result,
+            LoaderCompatibility::NotYetSupported {
+                newest_supported_snapshot: Some("24w09a".to_string())
+            }
+        );
+        assert_eq!(
+            result.describe(ModloaderType::Fabric, "24w10a").unwrap(),
+            "Fabric does not yet support 24w10a (newest supported snapshot: 24w09a)"
+        );
+    }
+
+    #[test]
+    fn unknown_game_or_loader_version() {
+        assert_eq!(
+            evaluate_loader_compatibility("1.99", None, &game_versions(), None, false),
+            LoaderCompatibility::UnknownVersion {
+                version: "1.99".to_string()
+            }
+        );
+        assert_eq!(
+            evaluate_loader_compatibility(
+                "24w09a",
+                Some("9.9.9"),
+                &game_versions(),
+                Some(&loaders()),
+                true,
+            ),
+            LoaderCompatibility::UnknownVersion {
+                version: "9.9.9".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn serializes_with_status_tag() {
+        let value = serde_json::to_value(LoaderCompatibility::NotYetSupported {
+            newest_supported_snapshot: None,
+        })
+        .unwrap();
+        assert_eq!(value["status"], "not_yet_supported");
+    }
+}
@@ -0,0 +1,193 @@
+use super::types::{GameVersionMetadata, ModloaderType, PistonMetadata};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// What changed between two metadata snapshots.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MetadataDiff {
+    /// Game versions only in the new snapshot, newest first.
+    pub new_versions: Vec<String>,
+    /// Game versions only in the old snapshot.
+    pub removed_versions: Vec<String>,
+    /// Loader version changes per game version present in both snapshots.
+    /// Versions without changes are left out.
+    pub loader_updates: HashMap<String, LoaderUpdates>,
+}
+
+/// Loader versions added or removed for one game version.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LoaderUpdates {
+    pub new_loader_versions: HashMap<ModloaderType, Vec<String>>,
+    pub removed_loader_versions: HashMap<ModloaderType, Vec<String>>,
+}
+
+impl MetadataDiff {
+    pub fn is_empty(&self) -> bool {
+        self.new_versions.is_empty()
+            && self.removed_versions.is_empty()
+            && self.loader_updates.is_empty()
+    }
+}
+
+impl LoaderUpdates {
+    pub fn is_empty(&self) -> bool {
+        self.new_loader_versions.is_empty() && self.removed_loader_versions.is_empty()
+    }
+}
+
+/// Ids in `from` that are missing from `other`, in `from`'s order.
+fn missing_from(from: &[String], other: &[String]) -> Vec<String> {
+    let other: HashSet<&str> = other.iter().map(String::as_str).collect();
+    from.iter()
+        .filter(|id| !other.contains(id.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Compare two snapshots. A game version new to `new` is reported in
+/// `new_versions` only; its loaders don't appear in `loader_updates`.
+pub fn diff(old: &PistonMetadata, new: &PistonMetadata) -> MetadataDiff {
+    let old_ids: Vec<String> = old.game_versions.iter().map(|v| v.id.clone()).collect();
+    let new_ids: Vec<String> = new.game_versions.iter().map(|v| v.id.clone()).collect();
+
+    let mut loader_updates = HashMap::new();
+    for new_version in &new.game_versions {
+        let Some(old_version) = old.game_versions.iter().find(|v| v.id == new_version.id) else {
+            continue;
+        };
+
+        let mut updates = LoaderUpdates::default();
+        let loaders: HashSet<ModloaderType> = old_version
+            .loaders
+            .keys()
+            .chain(new_version.loaders.keys())
+            .copied()
+            .collect();
+        for loader in loaders {
+            let versions = |metadata: &GameVersionMetadata| -> Vec<String> {
+                metadata
+                    .loaders
+                    .get(&loader)
+                    .map(|list| list.iter().map(|info| info.version.clone()).collect())
+                    .unwrap_or_default()
+            };
+            let (before, after) = (versions(old_version), versions(new_version));
+
+            let added = missing_from(&after, &before);
+            if !added.is_empty() {
+                updates.new_loader_versions.insert(loader, added);
+            }
+            let removed = missing_from(&before, &after);
+            if !removed.is_empty() {
+                updates.removed_loader_versions.insert(loader, removed);
+            }
+        }
+
+        if !updates.is_empty() {
+            loader_updates.insert(new_version.id.clone(), updates);
+        }
+    }
+
+    MetadataDiff {
+        new_versions: missing_from(&new_ids, &old_ids),
+        removed_versions: missing_from(&old_ids, &new_ids),
+        loader_updates,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::metadata::types::{LatestVersions, LoaderVersionInfo, VersionType};
+    use chrono::Utc;
+
+    fn loader(version: &str) -> LoaderVersionInfo {
+        LoaderVersionInfo {
+            version: version.to_string(),
+            stable: true,
+            url: None,
+            sha1: None,
+            changelog_url: None,
+            metadata: None,
+        }
+    }
+
+    fn game_version(id: &str, loaders: &[(ModloaderType, &[&str])]) -> GameVersionMetadata {
+        GameVersionMetadata {
+            id: id.to_string(),
+            version_type: VersionType::Release,
+            release_time: Utc::now(),
+            loaders: loaders
+                .iter()
+                .map(|(kind, versions)| (*kind, versions.iter().copied().map(loader).collect()))
+                .collect(),
+        }
+    }
+
+    fn metadata(game_versions: Vec<GameVersionMetadata>) -> PistonMetadata {
+        PistonMetadata {
+            last_updated: Utc::now(),
+            game_versions,
+            latest: LatestVersions {
+                release: "1.21.4".to_string(),
+                snapshot: "1.21.4".to_string(),
+            },
+            required_java_major_versions: Vec::new(),
+            java_major_version_by_game_version: HashMap::new(),
+            loader_supported_game_versions: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn identical_snapshots_have_no_diff() {
+        let snapshot = metadata(vec![game_version(
+            "1.21.4",
+            &[(ModloaderType::Fabric, &["0.16.9"])],
+        )]);
+        assert!(diff(&snapshot, &snapshot).is_empty());
+    }
+
+    #[test]
+    fn reports_game_and_loader_version_changes() {
+        let old = metadata(vec![
+            game_version("1.21.3", &[(ModloaderType::Fabric, &["0.16.8"])]),
+            game_version(
+                "1.21.1",
+                &[
+                    (ModloaderType::Fabric, &["0.16.8"]),
+                    (ModloaderType::Forge, &["52.0.1"]),
+                ],
+            ),
+            game_version("24w14potato", &[]),
+        ]);
+        let new = metadata(vec![
+            game_version("1.21.4", &[(ModloaderType::Fabric, &["0.16.9"])]),
+            game_version("1.21.3", &[(ModloaderType::Fabric, &["0.16.8"])]),
+            game_version(
+                "1.21.1",
+                &[
+                    (ModloaderType::Fabric, &["0.16.9", "0.16.8"]),
+                    (ModloaderType::NeoForge, &["21.1.77"]),
+                ],
+            ),
+        ]);
+
+        let changes = diff(&old, &new);
+
+        assert_eq!(changes.new_versions, vec!["1.21.4"]);
+        assert_eq!(changes.removed_versions, vec!["24w14potato"]);
+        assert_eq!(changes.loader_updates.len(), 1);
+        let updates = &changes.loader_updates["1.21.1"];
+        assert_eq!(
+            updates.new_loader_versions,
+            HashMap::from([
+                (ModloaderType::Fabric, vec!["0.16.9".to_string()]),
+                (ModloaderType::NeoForge, vec!["21.1.77".to_string()]),
+            ])
+        );
+        assert_eq!(
+            updates.removed_loader_versions,
+            HashMap::from([(ModloaderType::Forge, vec!["52.0.1".to_string()])])
+        );
+    }
+}
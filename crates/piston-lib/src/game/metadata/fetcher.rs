@@ -1,4 +1,6 @@
+use super::signature::verify_version_json_signature;
 use super::types::*;
+use crate::game::installer::core::traffic::{record_traffic, TrafficCategory};
 use crate::game::java_policy::{
     is_legacy_minecraft_version, preferred_java_major, LEGACY_JAVA_MAJOR,
 };
@@ -50,8 +52,10 @@ pub async fn fetch_metadata() -> Result<PistonMetadata> {
     let forge_fut = fetch_modrinth_manifest(http_client, "forge", MODRINTH_FORGE_FORMAT);
     let neo_fut = fetch_modrinth_manifest(http_client, "neo", MODRINTH_NEO_FORMAT);
 
-    let (fabric_res, quilt_res, forge_res, neo_res) =
-        tokio::join!(fabric_fut, quilt_fut, forge_fut, neo_fut);
+    let neo_maven_fut = super::neoforge::fetch_maven_versions(http_client);
+
+    let (fabric_res, quilt_res, forge_res, neo_res, neo_maven_res) =
+        tokio::join!(fabric_fut, quilt_fut, forge_fut, neo_fut, neo_maven_fut);
 
     // Apply Fabric (Fabric-style manifest — single dummy entry with all loaders)
     match fabric_res {
@@ -105,6 +109,18 @@ pub async fn fetch_metadata() -> Result<PistonMetadata> {
         Err(e) => log::error!("Failed to fetch NeoForge metadata: {}", e),
     }
 
+    // Merge the NeoForged maven (including legacy 1.20.1 builds) into the same lists
+    match neo_maven_res {
+        Ok(versions) => {
+            let added = super::neoforge::merge_loader_lists(
+                &mut game_versions,
+                super::neoforge::loaders_by_minecraft_version(&versions),
+            );
+            log::info!("NeoForge: merged {} loaders from maven", added);
+        }
+        Err(e) => log::warn!("Failed to fetch NeoForge maven metadata: {}", e),
+    }
+
     let mut metadata = PistonMetadata {
         last_updated: Utc::now(),
         game_versions,
@@ -116,12 +132,14 @@ pub async fn fetch_metadata() -> Result<PistonMetadata> {
             .await
             .context("Failed to fetch Java runtimes from launchermeta")?,
         java_major_version_by_game_version: HashMap::new(),
+        loader_supported_game_versions: HashMap::new(),
     };
 
     // Sort game versions by release date (latest first)
     metadata
         .game_versions
         .sort_by(|a, b| b.release_time.cmp(&a.release_time));
+    metadata.index_loader_support();
 
     log::info!(
         "PistonMetadata fetched successfully: {} game versions, {} total loader combinations",
@@ -214,6 +232,7 @@ fn apply_fabric_style_from_modrinth(
             stable: l.stable,
             url: Some(l.url.clone()),
             sha1: None,
+            changelog_url: loader_type.changelog_url(&l.id),
             metadata: None,
         })
         .collect();
@@ -260,6 +279,7 @@ fn apply_forge_style_from_modrinth(
                     stable: l.stable,
                     url: Some(l.url.clone()),
                     sha1: None,
+                    changelog_url: loader_type.changelog_url(&l.id),
                     metadata: None,
                 })
                 .collect();
@@ -299,7 +319,7 @@ pub async fn fetch_java_major_for_version(version_id: &str) -> Result<u32> {
             version_id
         ))?;
 
-    let detail = fetch_version_detail(client, &version.url).await?;
+    let detail = fetch_version_detail(client, &version.url, &version.sha1).await?;
     java_major_from_version_detail(
         &version.id,
         &version.version_type,
@@ -413,6 +433,7 @@ fn build_initial_game_versions(manifest: &MojangVersionManifest) -> Vec<GameVers
         .iter()
         .map(|version| {
             let mut loaders = HashMap::new();
+            let version_type = VersionType::from(version.version_type.as_str());
 
             let vanilla_url = format!(
                 "{}/minecraft/v0/versions/{}.json",
@@ -427,9 +448,10 @@ fn build_initial_game_versions(manifest: &MojangVersionManifest) -> Vec<GameVers
 
             let vanilla_loader = LoaderVersionInfo {
                 version: version.id.clone(),
-                stable: version.version_type == "release",
+                stable: version_type.is_stable(),
                 url: Some(vanilla_url),
                 sha1: vanilla_sha1,
+                changelog_url: None,
                 metadata: None,
             };
 
@@ -441,9 +463,8 @@ fn build_initial_game_versions(manifest: &MojangVersionManifest) -> Vec<GameVers
 
             GameVersionMetadata {
                 id: version.id.clone(),
-                version_type: version.version_type.clone(),
+                version_type,
                 release_time,
-                stable: version.version_type == "release",
                 loaders,
             }
         })
@@ -538,7 +559,9 @@ pub(crate) async fn send_with_retry(
     ))
 }
 
-async fn fetch_modrinth_mc_manifest(client: &reqwest::Client) -> Result<MojangVersionManifest> {
+pub(crate) async fn fetch_modrinth_mc_manifest(
+    client: &reqwest::Client,
+) -> Result<MojangVersionManifest> {
     let resp = send_with_retry(client, MODRINTH_MC_MANIFEST_URL, 3, 1000).await?;
     let manifest = resp
         .json::<MojangVersionManifest>()
@@ -563,9 +586,20 @@ async fn fetch_mojang_manifest_with_client(
     Ok(manifest)
 }
 
-async fn fetch_version_detail(client: &reqwest::Client, url: &str) -> Result<MojangVersionDetail> {
+/// Fetch a version JSON, checking it against the manifest's `sha1` when the
+/// entry has one.
+async fn fetch_version_detail(
+    client: &reqwest::Client,
+    url: &str,
+    sha1: &str,
+) -> Result<MojangVersionDetail> {
     let resp = send_with_retry(client, url, 2, 500).await?;
-    let detail = resp.json::<MojangVersionDetail>().await?;
+    let bytes = resp.bytes().await?;
+    record_traffic(TrafficCategory::Metadata, bytes.len() as u64);
+    if !sha1.is_empty() && !verify_version_json_signature(&bytes, sha1)? {
+        anyhow::bail!("Version JSON at {} does not match its manifest SHA-1", url);
+    }
+    let detail = serde_json::from_slice::<MojangVersionDetail>(&bytes)?;
     Ok(detail)
 }
 
@@ -1,7 +1,14 @@
 pub mod cache;
+pub mod compat;
+pub mod diff;
 pub mod fetcher;
+pub mod neoforge;
+pub mod signature;
 pub mod types;
 
 pub use cache::*;
+pub use compat::*;
+pub use diff::{diff, LoaderUpdates, MetadataDiff};
 pub use fetcher::*;
+pub use signature::verify_version_json_signature;
 pub use types::*;
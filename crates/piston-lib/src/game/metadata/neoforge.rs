@@ -0,0 +1,302 @@
+//! NeoForge version → Minecraft version mapping and the NeoForged maven as a
+//! secondary loader source.
+//!
+//! Since 1.20.2 NeoForge versions encode the Minecraft version they target
+//! (`<mc minor>.<mc patch>.<build>`, e.g. `20.4.237` for 1.20.4 and
+//! `21.0.167` for 1.21). The 1.20.1 builds predate that scheme and were
+//! published as `net.neoforged:forge:1.20.1-47.1.x` in Forge's format.
+
+use super::types::{GameVersionMetadata, LoaderVersionInfo, ModloaderType};
+use crate::utils::version::compare_versions;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+
+pub const NEOFORGED_MAVEN_URL: &str = "https://maven.neoforged.net/releases";
+const NEOFORGE_METADATA_PATH: &str = "net/neoforged/neoforge/maven-metadata.xml";
+const LEGACY_FORGE_METADATA_PATH: &str = "net/neoforged/forge/maven-metadata.xml";
+
+/// Minecraft version line for each NeoForge `<major>.<minor>` prefix
+/// published so far. Prefixes not listed fall back to
+/// [`minecraft_version_from_scheme`].
+const NEOFORGE_MC_VERSIONS: &[(&str, &str)] = &[
+    ("20.2", "1.20.2"),
+    ("20.3", "1.20.3"),
+    ("20.4", "1.20.4"),
+    ("20.5", "1.20.5"),
+    ("20.6", "1.20.6"),
+    ("21.0", "1.21"),
+    ("21.1", "1.21.1"),
+    ("21.2", "1.21.2"),
+    ("21.3", "1.21.3"),
+    ("21.4", "1.21.4"),
+    ("21.5", "1.21.5"),
+];
+
+/// Minecraft version a NeoForge version targets, e.g. `20.4.237` → `1.20.4`,
+/// `21.0.0-beta` → `1.21`. Also accepts the legacy `1.20.1-47.1.106` form.
+pub fn minecraft_version_for_neoforge(version: &str) -> Option<String> {
+    if let Some((mc_version, _)) = split_legacy_version(version) {
+        return Some(mc_version.to_string());
+    }
+
+    // Suffixes like `-beta` only ever follow the build number.
+    let release = version.split('-').next().unwrap_or(version);
+    let mut parts = release.split('.');
+    let major = parts.next()?;
+    let minor = parts.next()?;
+    let build = parts.next()?;
+    if build.is_empty() {
+        return None;
+    }
+
+    // Snapshot builds (`0.25w14craftmine.3-beta`) carry the snapshot id.
+    if major == "0" {
+        return (!minor.is_empty()).then(|| minor.to_string());
+    }
+
+    let prefix = format!("{}.{}", major, minor);
+    if let Some((_, mc_version)) = NEOFORGE_MC_VERSIONS
+        .iter()
+        .find(|(candidate, _)| *candidate == prefix)
+    {
+        return Some(mc_version.to_string());
+    }
+    minecraft_version_from_scheme(major.parse().ok()?, minor.parse().ok()?)
+}
+
+/// `<major>.<minor>` → `1.<major>.<minor>`, dropping a `.0` minor because
+/// Mojang names those releases `1.21`, not `1.21.0`. Only the 1.20/1.21
+/// lines use this scheme; anything else (e.g. Forge-style `47.1.106`) is
+/// not a NeoForge version we can place.
+fn minecraft_version_from_scheme(major: u32, minor: u32) -> Option<String> {
+    if !(20..=21).contains(&major) {
+        return None;
+    }
+    Some(if minor == 0 {
+        format!("1.{}", major)
+    } else {
+        format!("1.{}.{}", major, minor)
+    })
+}
+
+/// `1.20.1-47.1.106` → (`1.20.1`, `47.1.106`) for the legacy artifacts.
+pub fn split_legacy_version(version: &str) -> Option<(&str, &str)> {
+    let (mc_version, loader_version) = version.split_once('-')?;
+    (mc_version.starts_with("1.") && !loader_version.is_empty())
+        .then_some((mc_version, loader_version))
+}
+
+/// `<version>` entries of a maven-metadata.xml document.
+pub fn parse_maven_versions(xml: &str) -> Vec<String> {
+    let mut versions = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find("<version>") {
+        rest = &rest[start + "<version>".len()..];
+        let Some(end) = rest.find("</version>") else {
+            break;
+        };
+        let version = rest[..end].trim();
+        if !version.is_empty() {
+            versions.push(version.to_string());
+        }
+        rest = &rest[end..];
+    }
+    versions
+}
+
+/// Group maven versions by Minecraft version. Legacy versions are listed
+/// under their loader version (`47.1.106`), matching the current scheme.
+pub fn loaders_by_minecraft_version(
+    versions: &[String],
+) -> HashMap<String, Vec<LoaderVersionInfo>> {
+    let mut by_mc: HashMap<String, Vec<LoaderVersionInfo>> = HashMap::new();
+    for version in versions {
+        let Some(mc_version) = minecraft_version_for_neoforge(version) else {
+            log::debug!("NeoForge: no Minecraft version for {}", version);
+            continue;
+        };
+        let loader_version = split_legacy_version(version)
+            .map(|(_, loader)| loader)
+            .unwrap_or(version);
+        by_mc
+            .entry(mc_version)
+            .or_default()
+            .push(LoaderVersionInfo {
+                version: loader_version.to_string(),
+                stable: !loader_version.contains("beta") && !loader_version.contains("alpha"),
+                url: None,
+                sha1: None,
+                changelog_url: ModloaderType::NeoForge.changelog_url(loader_version),
+                metadata: Some(HashMap::from([(
+                    "source".to_string(),
+                    serde_json::Value::String("maven".to_string()),
+                )])),
+            });
+    }
+    by_mc
+}
+
+/// Merge maven loaders into the NeoForge lists. Entries already present
+/// (matched with or without the legacy `1.20.1-` prefix) keep their Modrinth
+/// data; lists are kept newest first. Returns the number of loaders added.
+pub fn merge_loader_lists(
+    game_versions: &mut [GameVersionMetadata],
+    additional: HashMap<String, Vec<LoaderVersionInfo>>,
+) -> usize {
+    let mut added = 0;
+    for version in game_versions.iter_mut() {
+        let Some(extra) = additional.get(&version.id) else {
+            continue;
+        };
+        let loaders = version.loaders.entry(ModloaderType::NeoForge).or_default();
+        for loader in extra {
+            let exists = loaders.iter().any(|existing| {
+                comparable_version(&existing.version) == comparable_version(&loader.version)
+            });
+            if !exists {
+                loaders.push(loader.clone());
+                added += 1;
+            }
+        }
+        loaders.sort_by(|a, b| {
+            compare_versions(
+                comparable_version(&b.version),
+                comparable_version(&a.version),
+            )
+        });
+    }
+    added
+}
+
+fn comparable_version(version: &str) -> &str {
+    split_legacy_version(version)
+        .map(|(_, loader)| loader)
+        .unwrap_or(version)
+}
+
+/// Every NeoForge version on the NeoForged maven, legacy 1.20.1 builds included.
+pub async fn fetch_maven_versions(client: &reqwest::Client) -> Result<Vec<String>> {
+    let mut versions = Vec::new();
+    for path in [NEOFORGE_METADATA_PATH, LEGACY_FORGE_METADATA_PATH] {
+        let url = format!("{}/{}", NEOFORGED_MAVEN_URL, path);
+        let xml = super::fetcher::send_with_retry(client, &url, 3, 1000)
+            .await?
+            .text()
+            .await
+            .with_context(|| format!("Failed to read {}", url))?;
+        versions.extend(parse_maven_versions(&xml));
+    }
+    Ok(versions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_current_scheme_versions() {
+        let cases = [
+            ("20.2.86", Some("1.20.2")),
+            ("20.2.3-beta", Some("1.20.2")),
+            ("20.4.0-beta", Some("1.20.4")),
+            ("20.4.237", Some("1.20.4")),
+            ("21.0.0-beta", Some("1.21")),
+            ("21.0.167", Some("1.21")),
+            ("21.1.77", Some("1.21.1")),
+            ("21.1.209", Some("1.21.1")),
+            ("21.10.3-beta", Some("1.21.10")),
+            ("0.25w14craftmine.3-beta", Some("25w14craftmine")),
+            ("21", None),
+            ("21.1", None),
+            ("47.1.106", None),
+        ];
+        for (version, expected) in cases {
+            assert_eq!(
+                minecraft_version_for_neoforge(version).as_deref(),
+                expected,
+                "{}",
+                version
+            );
+        }
+    }
+
+    #[test]
+    fn maps_legacy_forge_coordinates() {
+        assert_eq!(
+            minecraft_version_for_neoforge("1.20.1-47.1.106").as_deref(),
+            Some("1.20.1")
+        );
+        assert_eq!(
+            split_legacy_version("1.20.1-47.1.3"),
+            Some(("1.20.1", "47.1.3"))
+        );
+        assert_eq!(split_legacy_version("21.0.0-beta"), None);
+    }
+
+    #[test]
+    fn parses_maven_metadata() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <groupId>net.neoforged</groupId>
+  <artifactId>forge</artifactId>
+  <versioning>
+    <latest>1.20.1-47.1.106</latest>
+    <versions>
+      <version>1.20.1-47.1.3</version>
+      <version>1.20.1-47.1.106</version>
+    </versions>
+  </versioning>
+</metadata>"#;
+        assert_eq!(
+            parse_maven_versions(xml),
+            vec!["1.20.1-47.1.3", "1.20.1-47.1.106"]
+        );
+    }
+
+    fn game_version(id: &str) -> GameVersionMetadata {
+        GameVersionMetadata {
+            id: id.to_string(),
+            version_type: crate::game::metadata::VersionType::Release,
+            release_time: chrono::Utc::now(),
+            loaders: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn merges_legacy_and_current_artifacts_into_one_list() {
+        let mut versions = vec![game_version("1.20.1"), game_version("1.21.1")];
+        versions[1].loaders.insert(
+            ModloaderType::NeoForge,
+            vec![LoaderVersionInfo {
+                version: "21.1.77".to_string(),
+                stable: true,
+                url: Some("https://launcher-meta.modrinth.com/neo/21.1.77.json".to_string()),
+                sha1: None,
+                changelog_url: None,
+                metadata: None,
+            }],
+        );
+        let maven = [
+            "1.20.1-47.1.3",
+            "1.20.1-47.1.106",
+            "21.1.77",
+            "21.1.209",
+            "21.0.0-beta",
+        ]
+        .map(str::to_string);
+
+        let added = merge_loader_lists(&mut versions, loaders_by_minecraft_version(&maven));
+
+        assert_eq!(added, 3);
+        let legacy: Vec<&str> = versions[0].loaders[&ModloaderType::NeoForge]
+            .iter()
+            .map(|l| l.version.as_str())
+            .collect();
+        assert_eq!(legacy, vec!["47.1.106", "47.1.3"]);
+        let current = &versions[1].loaders[&ModloaderType::NeoForge];
+        assert_eq!(current[0].version, "21.1.209");
+        assert_eq!(current[1].version, "21.1.77");
+        // The Modrinth entry wins over the maven duplicate.
+        assert!(current[1].url.is_some());
+    }
+}
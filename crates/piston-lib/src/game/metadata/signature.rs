@@ -0,0 +1,56 @@
+//! Verification of version JSONs fetched from Mojang.
+//!
+//! Each entry of Mojang's version manifest carries the SHA-1 of its version
+//! JSON, and that digest is the only integrity data Mojang publishes for
+//! them today. Callers go through [`verify_version_json_signature`] only, so
+//! switching to real signatures (e.g. ed25519 checked with `ring::signature`
+//! against a bundled Mojang public key) would replace that one function and
+//! leave the call sites alone.
+
+use anyhow::{bail, Result};
+use sha1::{Digest, Sha1};
+
+/// Check `version_json` against the `sha1` its manifest entry advertises.
+///
+/// `Ok(false)` means the content does not match; an error means `sha1` is
+/// not a SHA-1 digest at all.
+pub fn verify_version_json_signature(version_json: &[u8], sha1: &str) -> Result<bool> {
+    let expected = sha1.trim();
+    if expected.len() != 40 || !expected.bytes().all(|b| b.is_ascii_hexdigit()) {
+        bail!("Invalid version JSON SHA-1 '{}'", sha1);
+    }
+
+    let mut hasher = Sha1::new();
+    hasher.update(version_json);
+    let actual = format!("{:x}", hasher.finalize());
+    Ok(actual.eq_ignore_ascii_case(expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &[u8] = br#"{"id":"1.20.1"}"#;
+
+    fn sha1_of(bytes: &[u8]) -> String {
+        let mut hasher = Sha1::new();
+        hasher.update(bytes);
+        format!("{:x}", hasher.finalize())
+    }
+
+    #[test]
+    fn matching_digest_verifies() {
+        let sha1 = sha1_of(JSON);
+        assert!(verify_version_json_signature(JSON, &sha1).unwrap());
+        assert!(verify_version_json_signature(JSON, &sha1.to_ascii_uppercase()).unwrap());
+    }
+
+    #[test]
+    fn tampered_json_or_bad_digest_is_rejected() {
+        let sha1 = sha1_of(JSON);
+        assert!(!verify_version_json_signature(br#"{"id":"1.20.2"}"#, &sha1).unwrap());
+        assert!(verify_version_json_signature(JSON, "").is_err());
+        assert!(verify_version_json_signature(JSON, &sha1[..20]).is_err());
+        assert!(verify_version_json_signature(JSON, &"z".repeat(40)).is_err());
+    }
+}
@@ -20,6 +20,100 @@ pub struct PistonMetadata {
     /// Required Java major version for each Minecraft version id (resolved lazily on-demand).
     #[serde(default)]
     pub java_major_version_by_game_version: HashMap<String, u32>,
+
+    /// Game version ids each loader has published support for, so pickers can
+    /// grey out combinations (e.g. Fabric on a snapshot without intermediary).
+    #[serde(default)]
+    pub loader_supported_game_versions: HashMap<ModloaderType, Vec<String>>,
+}
+
+impl PistonMetadata {
+    /// Rebuild `loader_supported_game_versions` from each game version's loader
+    /// list, keeping `game_versions` order (latest first).
+    pub fn index_loader_support(&mut self) {
+        let mut supported: HashMap<ModloaderType, Vec<String>> = HashMap::new();
+        for version in &self.game_versions {
+            for loader in version.loaders.keys() {
+                supported
+                    .entry(*loader)
+                    .or_default()
+                    .push(version.id.clone());
+            }
+        }
+        self.loader_supported_game_versions = supported;
+    }
+
+    /// The version named by `latest.release`, if it is in `game_versions`.
+    pub fn latest_release(&self) -> Option<&GameVersionMetadata> {
+        self.version(&self.latest.release)
+    }
+
+    /// The version named by `latest.snapshot`, if it is in `game_versions`.
+    /// Mojang points this at the release when no newer snapshot exists.
+    pub fn latest_snapshot(&self) -> Option<&GameVersionMetadata> {
+        self.version(&self.latest.snapshot)
+    }
+
+    /// Newest game version with at least one `loader` version.
+    pub fn latest_for_loader(&self, loader: ModloaderType) -> Option<&GameVersionMetadata> {
+        self.game_versions.iter().find(|version| {
+            version
+                .loaders
+                .get(&loader)
+                .is_some_and(|versions| !versions.is_empty())
+        })
+    }
+
+    fn version(&self, id: &str) -> Option<&GameVersionMetadata> {
+        self.game_versions.iter().find(|version| version.id == id)
+    }
+
+    /// `None` when the supported set for `loader` is unknown (not cached).
+    pub fn loader_supports(&self, loader: ModloaderType, game_version: &str) -> Option<bool> {
+        self.loader_supported_game_versions
+            .get(&loader)
+            .map(|versions| versions.iter().any(|v| v == game_version))
+    }
+
+    /// Loaders with at least one version for `mc_version`, in wizard order.
+    /// Empty when the Minecraft version is unknown.
+    pub fn get_supported_loaders(&self, mc_version: &str) -> Vec<ModloaderType> {
+        let Some(version) = self.game_versions.iter().find(|gv| gv.id == mc_version) else {
+            return Vec::new();
+        };
+        let mut supported = vec![ModloaderType::Vanilla];
+        supported.extend(ModloaderType::MODDED.into_iter().filter(|loader| {
+            version
+                .loaders
+                .get(loader)
+                .is_some_and(|versions| !versions.is_empty())
+        }));
+        supported
+    }
+
+    /// One row per game version with the recommended version of each loader
+    /// (first stable, else newest) or `none` when unsupported.
+    pub fn to_compatibility_csv(&self) -> String {
+        let mut csv = String::from(
+            "mc_version,vanilla,fabric_latest,quilt_latest,forge_latest,neoforge_latest\n",
+        );
+        for version in &self.game_versions {
+            csv.push_str(&version.id);
+            csv.push_str(",yes");
+            for loader in ModloaderType::MODDED {
+                let recommended = version.loaders.get(&loader).and_then(|versions| {
+                    versions
+                        .iter()
+                        .find(|info| info.stable)
+                        .or_else(|| versions.first())
+                });
+                csv.push(',');
+                csv.push_str(recommended.map_or("none", |info| info.version.as_str()));
+            }
+            csv.push('\n');
+        }
+        csv
+    }
 }
 
 /// Latest version information
@@ -30,24 +124,104 @@ pub struct LatestVersions {
 }
 
 /// Metadata for a single game version
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Serialized with a derived `stable` flag so the frontend can keep filtering
+/// on it; the flag is ignored when reading a cached copy back.
+#[derive(Debug, Clone, Deserialize)]
 pub struct GameVersionMetadata {
     /// Minecraft version ID (e.g., "1.20.1")
     pub id: String,
 
-    /// Version type (release, snapshot, old_alpha, old_beta)
-    pub version_type: String,
+    /// Version type as published in Mojang's manifest
+    pub version_type: VersionType,
 
     /// Release timestamp
     pub release_time: DateTime<Utc>,
 
-    /// Whether this is a stable release
-    pub stable: bool,
-
     /// Available modloaders for this version
     pub loaders: HashMap<ModloaderType, Vec<LoaderVersionInfo>>,
 }
 
+impl GameVersionMetadata {
+    /// Whether this is a stable release
+    pub fn stable(&self) -> bool {
+        self.version_type.is_stable()
+    }
+}
+
+impl Serialize for GameVersionMetadata {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("GameVersionMetadata", 5)?;
+        state.serialize_field("id", &self.id)?;
+        state.serialize_field("version_type", &self.version_type)?;
+        state.serialize_field("release_time", &self.release_time)?;
+        state.serialize_field("stable", &self.stable())?;
+        state.serialize_field("loaders", &self.loaders)?;
+        state.end()
+    }
+}
+
+/// Minecraft version type from Mojang's version manifest
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum VersionType {
+    Release,
+    Snapshot,
+    OldBeta,
+    OldAlpha,
+    /// Any type Mojang adds later, kept verbatim
+    Unknown(String),
+}
+
+impl VersionType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            VersionType::Release => "release",
+            VersionType::Snapshot => "snapshot",
+            VersionType::OldBeta => "old_beta",
+            VersionType::OldAlpha => "old_alpha",
+            VersionType::Unknown(raw) => raw,
+        }
+    }
+
+    /// Only full releases count as stable.
+    pub fn is_stable(&self) -> bool {
+        matches!(self, VersionType::Release)
+    }
+}
+
+impl From<&str> for VersionType {
+    fn from(s: &str) -> Self {
+        match s {
+            "release" => VersionType::Release,
+            "snapshot" => VersionType::Snapshot,
+            "old_beta" => VersionType::OldBeta,
+            "old_alpha" => VersionType::OldAlpha,
+            other => VersionType::Unknown(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for VersionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Serialize for VersionType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Ok(VersionType::from(raw.as_str()))
+    }
+}
+
 /// Modloader type enum
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -60,6 +234,14 @@ pub enum ModloaderType {
 }
 
 impl ModloaderType {
+    /// Every loader except Vanilla, in the order the UI lists them.
+    pub const MODDED: [ModloaderType; 4] = [
+        ModloaderType::Fabric,
+        ModloaderType::Quilt,
+        ModloaderType::Forge,
+        ModloaderType::NeoForge,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             ModloaderType::Vanilla => "vanilla",
@@ -69,6 +251,25 @@ impl ModloaderType {
             ModloaderType::NeoForge => "neoforge",
         }
     }
+
+    /// Human-readable changelog for a loader version, if the loader publishes one.
+    pub fn changelog_url(&self, version: &str) -> Option<String> {
+        match self {
+            ModloaderType::Vanilla => None,
+            ModloaderType::Fabric => {
+                Some(format!("https://fabricmc.net/versions.html#{}", version))
+            }
+            ModloaderType::Quilt => Some("https://quiltmc.org/en/changelog/".to_string()),
+            ModloaderType::Forge => Some(format!(
+                "https://github.com/MinecraftForge/MinecraftForge/releases/tag/{}",
+                version
+            )),
+            ModloaderType::NeoForge => Some(format!(
+                "https://github.com/neoforged/NeoForge/releases/tag/{}",
+                version
+            )),
+        }
+    }
 }
 
 impl std::fmt::Display for ModloaderType {
@@ -109,6 +310,10 @@ pub struct LoaderVersionInfo {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sha1: Option<String>,
 
+    /// Human-readable changelog for this loader version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changelog_url: Option<String>,
+
     /// Additional metadata (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<HashMap<String, serde_json::Value>>,
@@ -286,3 +491,190 @@ pub struct MojangJavaVersion {
     pub major_version: u32,
     pub component: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loader(version: &str, stable: bool) -> LoaderVersionInfo {
+        LoaderVersionInfo {
+            version: version.to_string(),
+            stable,
+            url: None,
+            sha1: None,
+            changelog_url: None,
+            metadata: None,
+        }
+    }
+
+    fn game_version(
+        id: &str,
+        loaders: Vec<(ModloaderType, Vec<LoaderVersionInfo>)>,
+    ) -> GameVersionMetadata {
+        GameVersionMetadata {
+            id: id.to_string(),
+            version_type: VersionType::Release,
+            release_time: Utc::now(),
+            loaders: loaders.into_iter().collect(),
+        }
+    }
+
+    fn metadata() -> PistonMetadata {
+        PistonMetadata {
+            last_updated: Utc::now(),
+            game_versions: vec![
+                game_version(
+                    "1.21.1",
+                    vec![
+                        (
+                            ModloaderType::Fabric,
+                            vec![loader("0.16.1-beta", false), loader("0.16.0", true)],
+                        ),
+                        (ModloaderType::NeoForge, vec![loader("21.1.5-beta", false)]),
+                        (ModloaderType::Forge, Vec::new()),
+                    ],
+                ),
+                game_version("24w10a", Vec::new()),
+            ],
+            latest: LatestVersions {
+                release: "1.21.1".to_string(),
+                snapshot: "24w10a".to_string(),
+            },
+            required_java_major_versions: vec![21],
+            java_major_version_by_game_version: HashMap::new(),
+            loader_supported_game_versions: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn compatibility_csv_prefers_stable_loader_versions() {
+        assert_eq!(
+            metadata().to_compatibility_csv(),
+            "mc_version,vanilla,fabric_latest,quilt_latest,forge_latest,neoforge_latest\n\
+             1.21.1,yes,0.16.0,none,none,21.1.5-beta\n\
+             24w10a,yes,none,none,none,none\n"
+        );
+    }
+
+    #[test]
+    fn latest_release_and_snapshot_follow_the_latest_ids() {
+        let mut meta = metadata();
+        assert_eq!(meta.latest_release().unwrap().id, "1.21.1");
+        assert_eq!(meta.latest_snapshot().unwrap().id, "24w10a");
+
+        meta.latest.snapshot = "1.21.1".to_string();
+        assert_eq!(meta.latest_snapshot().unwrap().id, "1.21.1");
+
+        meta.latest.release = "1.99".to_string();
+        assert!(meta.latest_release().is_none());
+    }
+
+    #[test]
+    fn latest_for_loader_skips_versions_without_loader_builds() {
+        let mut meta = metadata();
+        meta.game_versions.insert(
+            0,
+            game_version("1.21.2", vec![(ModloaderType::Fabric, Vec::new())]),
+        );
+        meta.game_versions.push(game_version(
+            "1.20.1",
+            vec![(ModloaderType::Forge, vec![loader("47.3.0", true)])],
+        ));
+
+        assert_eq!(
+            meta.latest_for_loader(ModloaderType::Fabric).unwrap().id,
+            "1.21.1"
+        );
+        assert_eq!(
+            meta.latest_for_loader(ModloaderType::Forge).unwrap().id,
+            "1.20.1"
+        );
+        assert!(meta.latest_for_loader(ModloaderType::Quilt).is_none());
+    }
+
+    #[test]
+    fn supported_loaders_skip_empty_lists_and_unknown_versions() {
+        let meta = metadata();
+        assert_eq!(
+            meta.get_supported_loaders("1.21.1"),
+            vec![
+                ModloaderType::Vanilla,
+                ModloaderType::Fabric,
+                ModloaderType::NeoForge
+            ]
+        );
+        assert_eq!(
+            meta.get_supported_loaders("24w10a"),
+            vec![ModloaderType::Vanilla]
+        );
+        assert!(meta.get_supported_loaders("1.99").is_empty());
+    }
+
+    #[test]
+    fn version_type_round_trips_through_json() {
+        for (raw, expected) in [
+            ("release", VersionType::Release),
+            ("snapshot", VersionType::Snapshot),
+            ("old_beta", VersionType::OldBeta),
+            ("old_alpha", VersionType::OldAlpha),
+            ("experiment", VersionType::Unknown("experiment".to_string())),
+        ] {
+            let json = format!("\"{}\"", raw);
+            let parsed: VersionType = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, expected);
+            assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+            assert_eq!(parsed.to_string(), raw);
+            assert_eq!(parsed.is_stable(), raw == "release");
+        }
+    }
+
+    #[test]
+    fn game_version_serializes_derived_stable_flag() {
+        let mut version = game_version("24w10a", Vec::new());
+        version.version_type = VersionType::Snapshot;
+        let json = serde_json::to_value(&version).unwrap();
+        assert_eq!(json["version_type"], "snapshot");
+        assert_eq!(json["stable"], false);
+
+        // Cached copies written before the enum still carry a `stable` field.
+        let parsed: GameVersionMetadata = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.version_type, VersionType::Snapshot);
+        assert!(!parsed.stable());
+    }
+
+    #[test]
+    fn changelog_url_per_loader() {
+        for (loader, version, expected) in [
+            (ModloaderType::Vanilla, "1.21.1", None),
+            (
+                ModloaderType::Fabric,
+                "0.16.5",
+                Some("https://fabricmc.net/versions.html#0.16.5"),
+            ),
+            (
+                ModloaderType::Quilt,
+                "0.26.4",
+                Some("https://quiltmc.org/en/changelog/"),
+            ),
+            (
+                ModloaderType::Forge,
+                "1.21.1-52.0.16",
+                Some(
+                    "https://github.com/MinecraftForge/MinecraftForge/releases/tag/1.21.1-52.0.16",
+                ),
+            ),
+            (
+                ModloaderType::NeoForge,
+                "21.1.65",
+                Some("https://github.com/neoforged/NeoForge/releases/tag/21.1.65"),
+            ),
+        ] {
+            assert_eq!(
+                loader.changelog_url(version).as_deref(),
+                expected,
+                "{}",
+                loader
+            );
+        }
+    }
+}
@@ -1,11 +1,13 @@
 pub mod installer;
 pub mod java_policy;
+pub mod launch_integrity;
 pub mod launcher;
 pub mod manifest_cache;
 pub mod metadata;
 pub mod modpack;
 pub mod runtime_plan;
 pub mod runtime_preparation;
+pub mod vanilla_launcher;
 
 // Re-export commonly used types
 pub use launcher::{GameInstance, LaunchResult, LaunchSpec};
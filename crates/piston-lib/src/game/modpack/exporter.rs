@@ -8,7 +8,7 @@ use std::path::{Path, PathBuf};
 use zip::{write::FileOptions, ZipWriter};
 
 use crate::game::installer::types::ProgressReporter;
-use crate::game::modpack::types::ModpackFormat;
+use crate::game::modpack::types::{ModpackFormat, ModrinthEnv};
 
 fn calculate_hashes(path: &Path) -> Result<(String, String)> {
     let mut file = File::open(path)?;
@@ -31,6 +31,43 @@ fn calculate_hashes(path: &Path) -> Result<(String, String)> {
     ))
 }
 
+/// `(sha1, sha512, size)` of a linked file, from the given metadata when it
+/// is complete and from the file on disk otherwise.
+fn file_digests(
+    full_path: &Path,
+    sha1: Option<String>,
+    sha512: Option<String>,
+    file_size: Option<u64>,
+) -> Result<(String, String, u64)> {
+    if let (Some(sha1), Some(sha512), Some(size)) = (sha1, sha512, file_size) {
+        return Ok((sha1.to_ascii_lowercase(), sha512.to_ascii_lowercase(), size));
+    }
+    let (sha1, sha512) = calculate_hashes(full_path)?;
+    Ok((sha1, sha512, full_path.metadata()?.len()))
+}
+
+fn side_support(value: &str, optional: bool) -> &'static str {
+    match value {
+        "unsupported" => "unsupported",
+        "optional" => "optional",
+        // Anything else, including Modrinth's "unknown", is treated as needed.
+        _ if optional => "optional",
+        _ => "required",
+    }
+}
+
+/// mrpack `env` of a file. Optional files are optional on every side that
+/// supports them.
+fn file_env(env: Option<&ModrinthEnv>, optional: bool) -> serde_json::Value {
+    let (client, server) = env
+        .map(|env| (env.client.as_str(), env.server.as_str()))
+        .unwrap_or(("required", "required"));
+    json!({
+        "client": side_support(client, optional),
+        "server": side_support(server, optional),
+    })
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
 pub struct ExportSpec {
     pub name: String,
@@ -45,19 +82,38 @@ pub struct ExportSpec {
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
 pub enum ExportEntry {
-    Mod {
-        path: PathBuf,     // Relative to instance root
-        source_id: String, // Modrinth/CurseForge ID
-        version_id: String,
-        platform: Option<ModpackFormat>,
-        download_url: Option<String>,
-        external_ids: Option<std::collections::HashMap<String, String>>,
-    },
+    Mod(Box<ExportMod>),
     Override {
         path: PathBuf, // Relative to instance root
     },
 }
 
+/// Payload of [`ExportEntry::Mod`]. Mods without a known platform are bundled
+/// as overrides instead of being listed in the manifest.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
+pub struct ExportMod {
+    pub path: PathBuf,     // Relative to instance root
+    pub source_id: String, // Modrinth/CurseForge ID
+    pub version_id: String,
+    pub platform: Option<ModpackFormat>,
+    pub download_url: Option<String>,
+    pub external_ids: Option<std::collections::HashMap<String, String>>,
+    /// Digests and size from the platform's version metadata. The file is
+    /// hashed locally when any of them is missing.
+    #[serde(default)]
+    pub sha1: Option<String>,
+    #[serde(default)]
+    pub sha512: Option<String>,
+    #[serde(default)]
+    pub file_size: Option<u64>,
+    /// Client/server support, `required` on both sides when unknown.
+    #[serde(default)]
+    pub env: Option<ModrinthEnv>,
+    /// Players may leave the file out when installing the pack.
+    #[serde(default)]
+    pub optional: bool,
+}
+
 pub fn export_modpack<P: AsRef<Path>>(
     instance_root: P,
     spec: ExportSpec,
@@ -138,14 +194,20 @@ fn export_modrinth<W: Write + std::io::Seek>(
         reporter.set_percent(percent);
 
         match entry {
-            ExportEntry::Mod {
-                path,
-                download_url,
-                platform,
-                source_id,
-                version_id,
-                ..
-            } => {
+            ExportEntry::Mod(entry) => {
+                let ExportMod {
+                    path,
+                    download_url,
+                    platform,
+                    source_id,
+                    version_id,
+                    sha1,
+                    sha512,
+                    file_size,
+                    env,
+                    optional,
+                    ..
+                } = *entry;
                 let file_name = path.file_name().unwrap_or_default().to_string_lossy();
 
                 let full_path = instance_root.join(&path);
@@ -156,11 +218,10 @@ fn export_modrinth<W: Write + std::io::Seek>(
                 // ONLY link if we have a known platform (Modrinth or CurseForge)
                 // This prevents linking custom/manual files to Modrinth hash-fallback
                 if let Some(source_platform) = platform {
-                    match calculate_hashes(&full_path) {
-                        Ok((sha1_hash, sha512_hash)) => {
+                    match file_digests(&full_path, sha1, sha512, file_size) {
+                        Ok((sha1_hash, sha512_hash, file_size)) => {
                             reporter.set_message(&format!("Linking mod: {}", file_name));
 
-                            let file_size = full_path.metadata().map(|m| m.len()).unwrap_or(0);
                             let mut downloads = Vec::new();
 
                             if let Some(url) = download_url {
@@ -190,10 +251,7 @@ fn export_modrinth<W: Write + std::io::Seek>(
                                 },
                                 "downloads": downloads,
                                 "fileSize": file_size,
-                                "env": {
-                                    "client": "required",
-                                    "server": "required"
-                                }
+                                "env": file_env(env.as_ref(), optional)
                             });
 
                             files.push(file_entry);
@@ -266,13 +324,15 @@ fn export_curseforge<W: Write + std::io::Seek>(
         reporter.set_percent(percent);
 
         match entry {
-            ExportEntry::Mod {
-                path,
-                source_id,
-                version_id,
-                external_ids,
-                ..
-            } => {
+            ExportEntry::Mod(entry) => {
+                let ExportMod {
+                    path,
+                    source_id,
+                    version_id,
+                    external_ids,
+                    optional,
+                    ..
+                } = *entry;
                 let file_name = path.file_name().unwrap_or_default().to_string_lossy();
 
                 // If we have numeric IDs, try to link them (CurseForge requirement)
@@ -294,7 +354,7 @@ fn export_curseforge<W: Write + std::io::Seek>(
                     files.push(json!({
                         "projectID": pid,
                         "fileID": fid,
-                        "required": true
+                        "required": !optional
                     }));
                 } else {
                     reporter.set_message(&format!("Adding to overrides: {}", file_name));
@@ -347,3 +407,169 @@ fn add_file_to_zip<W: Write + std::io::Seek>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::installer::types::SilentProgressReporter;
+    use crate::game::modpack::types::ModrinthIndex;
+
+    const SIDES: &[&str] = &["required", "optional", "unsupported"];
+
+    fn linked_mod(path: &str, platform: ModpackFormat) -> ExportEntry {
+        ExportEntry::Mod(Box::new(ExportMod {
+            path: PathBuf::from(path),
+            source_id: "238222".to_string(),
+            version_id: "4712345".to_string(),
+            platform: Some(platform),
+            download_url: None,
+            external_ids: None,
+            sha1: None,
+            sha512: None,
+            file_size: None,
+            env: None,
+            optional: false,
+        }))
+    }
+
+    fn spec(entries: Vec<ExportEntry>) -> ExportSpec {
+        ExportSpec {
+            name: "Test Pack".to_string(),
+            version: "1.0.0".to_string(),
+            author: "tester".to_string(),
+            description: None,
+            minecraft_version: "1.20.1".to_string(),
+            modloader_type: "fabric".to_string(),
+            modloader_version: "0.15.0".to_string(),
+            entries,
+        }
+    }
+
+    fn export(
+        root: &Path,
+        entries: Vec<ExportEntry>,
+        format: ModpackFormat,
+        manifest: &str,
+    ) -> serde_json::Value {
+        let output = root.join("pack.zip");
+        export_modpack(
+            root,
+            spec(entries),
+            output.as_path(),
+            format,
+            &SilentProgressReporter,
+        )
+        .unwrap();
+        let mut archive = zip::ZipArchive::new(File::open(&output).unwrap()).unwrap();
+        let mut content = String::new();
+        archive
+            .by_name(manifest)
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        serde_json::from_str(&content).unwrap()
+    }
+
+    fn is_hex(value: &str, len: usize) -> bool {
+        value.len() == len && value.chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    #[test]
+    fn modrinth_index_follows_the_mrpack_spec() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join("mods")).unwrap();
+        std::fs::write(root.path().join("mods/sodium.jar"), b"sodium").unwrap();
+        std::fs::write(root.path().join("mods/zoomify.jar"), b"zoomify").unwrap();
+        std::fs::write(root.path().join("mods/server.jar"), b"server").unwrap();
+
+        let mut known = linked_mod("mods/zoomify.jar", ModpackFormat::Modrinth);
+        if let ExportEntry::Mod(m) = &mut known {
+            m.sha1 = Some("A".repeat(40));
+            m.sha512 = Some("b".repeat(128));
+            m.file_size = Some(1234);
+            m.optional = true;
+        }
+        let mut server_only = linked_mod("mods/server.jar", ModpackFormat::Modrinth);
+        if let ExportEntry::Mod(m) = &mut server_only {
+            m.env = Some(ModrinthEnv {
+                client: "unsupported".to_string(),
+                server: "unknown".to_string(),
+            });
+        }
+        let entries = vec![
+            linked_mod("mods/sodium.jar", ModpackFormat::Modrinth),
+            known,
+            server_only,
+        ];
+
+        let index = export(
+            root.path(),
+            entries,
+            ModpackFormat::Modrinth,
+            "modrinth.index.json",
+        );
+
+        assert_eq!(index["formatVersion"], 1);
+        assert_eq!(index["game"], "minecraft");
+        assert_eq!(index["dependencies"]["minecraft"], "1.20.1");
+        assert_eq!(index["dependencies"]["fabric-loader"], "0.15.0");
+        let parsed: ModrinthIndex = serde_json::from_value(index.clone()).unwrap();
+        assert_eq!(parsed.files.len(), 3);
+        for file in &parsed.files {
+            assert!(!file.path.starts_with('/') && !file.path.contains(".."));
+            assert!(is_hex(&file.hashes["sha1"], 40));
+            assert!(is_hex(&file.hashes["sha512"], 128));
+            assert!(!file.downloads.is_empty());
+            let env = file.env.as_ref().unwrap();
+            assert!(SIDES.contains(&env.client.as_str()));
+            assert!(SIDES.contains(&env.server.as_str()));
+        }
+
+        let sodium = &index["files"][0];
+        let (sha1, sha512) = calculate_hashes(&root.path().join("mods/sodium.jar")).unwrap();
+        assert_eq!(sodium["hashes"]["sha1"], sha1);
+        assert_eq!(sodium["hashes"]["sha512"], sha512);
+        assert_eq!(sodium["fileSize"], 6);
+        assert_eq!(
+            sodium["env"],
+            json!({"client": "required", "server": "required"})
+        );
+
+        let zoomify = &index["files"][1];
+        assert_eq!(zoomify["hashes"]["sha1"], "a".repeat(40));
+        assert_eq!(zoomify["fileSize"], 1234);
+        assert_eq!(
+            zoomify["env"],
+            json!({"client": "optional", "server": "optional"})
+        );
+
+        let server = &index["files"][2];
+        assert_eq!(
+            server["env"],
+            json!({"client": "unsupported", "server": "required"})
+        );
+    }
+
+    #[test]
+    fn curseforge_manifest_marks_optional_files() {
+        let root = tempfile::tempdir().unwrap();
+        let mut optional_mod = linked_mod("mods/b.jar", ModpackFormat::CurseForge);
+        if let ExportEntry::Mod(m) = &mut optional_mod {
+            m.optional = true;
+        }
+        let entries = vec![
+            linked_mod("mods/a.jar", ModpackFormat::CurseForge),
+            optional_mod,
+        ];
+
+        let manifest = export(
+            root.path(),
+            entries,
+            ModpackFormat::CurseForge,
+            "manifest.json",
+        );
+
+        assert_eq!(manifest["files"][0]["required"], true);
+        assert_eq!(manifest["files"][1]["required"], false);
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::Read;
 use std::path::{Path, PathBuf};
@@ -302,6 +303,234 @@ mod tests {
             "unexpected error: {err}"
         );
     }
+
+    fn modrinth_pack_with_overrides(overrides: &[(&str, &str)]) -> NamedTempFile {
+        let mut entries = vec![(
+            "modrinth.index.json".to_string(),
+            r#"{
+                "formatVersion": 1,
+                "game": "minecraft",
+                "versionId": "1.0.0",
+                "name": "Overrides Pack",
+                "files": [],
+                "dependencies": { "minecraft": "1.20.1" }
+            }"#
+            .to_string(),
+        )];
+        entries.extend(
+            overrides
+                .iter()
+                .map(|(path, content)| (format!("overrides/{}", path), content.to_string())),
+        );
+        let entries: Vec<(&str, &str)> = entries
+            .iter()
+            .map(|(name, content)| (name.as_str(), content.as_str()))
+            .collect();
+        write_zip(&entries)
+    }
+
+    #[test]
+    fn override_conflicts_list_only_changed_files() {
+        let zip = modrinth_pack_with_overrides(&[
+            ("config/a.toml", "pack"),
+            ("config/same.toml", "same"),
+            ("options.txt", "pack"),
+            ("scripts/new.zs", "pack"),
+        ]);
+        let game_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(game_dir.path().join("config")).unwrap();
+        std::fs::write(game_dir.path().join("config/a.toml"), "user").unwrap();
+        std::fs::write(game_dir.path().join("config/same.toml"), "same").unwrap();
+        std::fs::write(game_dir.path().join("options.txt"), "user").unwrap();
+
+        let conflicts = detect_override_conflicts(zip.path(), game_dir.path(), &[]).unwrap();
+        let paths: Vec<&str> = conflicts.iter().map(|c| c.relative_path.as_str()).collect();
+        assert_eq!(paths, vec!["config/a.toml", "options.txt"]);
+        assert_eq!(
+            conflicts[0].existing_sha1,
+            "12dea96fec20593566ab75692c9949596833adc9"
+        );
+
+        let ignored =
+            detect_override_conflicts(zip.path(), game_dir.path(), &["config/", "*.TXT"]).unwrap();
+        assert!(ignored.is_empty());
+    }
+
+    #[test]
+    fn conflicting_overrides_are_skipped_or_backed_up() {
+        let zip = modrinth_pack_with_overrides(&[("kubejs/a.js", "pack"), ("kubejs/b.js", "new")]);
+        let game_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(game_dir.path().join("kubejs")).unwrap();
+        std::fs::write(game_dir.path().join("kubejs/a.js"), "user").unwrap();
+
+        let (extracted, skipped) = extract_overrides_with_config_policy(
+            zip.path(),
+            game_dir.path(),
+            ModpackFormat::Modrinth,
+            None,
+            true,
+            ConflictResolution::Skip,
+        )
+        .unwrap();
+        assert_eq!(extracted, vec![PathBuf::from("kubejs/b.js")]);
+        assert_eq!(skipped, vec!["kubejs/a.js".to_string()]);
+        let existing = std::fs::read_to_string(game_dir.path().join("kubejs/a.js")).unwrap();
+        assert_eq!(existing, "user");
+
+        extract_overrides_with_config_policy(
+            zip.path(),
+            game_dir.path(),
+            ModpackFormat::Modrinth,
+            None,
+            true,
+            ConflictResolution::Backup,
+        )
+        .unwrap();
+        let replaced = std::fs::read_to_string(game_dir.path().join("kubejs/a.js")).unwrap();
+        assert_eq!(replaced, "pack");
+        let backups: Vec<_> = std::fs::read_dir(game_dir.path().join(OVERRIDE_BACKUP_DIR))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(backups.len(), 1);
+        let backup = std::fs::read_to_string(backups[0].join("kubejs/a.js")).unwrap();
+        assert_eq!(backup, "user");
+    }
+
+    #[tokio::test]
+    async fn streamed_overrides_layer_client_overrides_and_keep_configs() {
+        let zip = write_zip(&[
+            ("modrinth.index.json", "{}"),
+            ("overrides/config/a.toml", "pack"),
+            ("overrides/options.txt", "common"),
+            ("overrides/mods/extra.jar", "jar"),
+            ("client-overrides/options.txt", "client"),
+            ("overrides/../escape.txt", "nope"),
+        ]);
+        let game_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(game_dir.path().join("config")).unwrap();
+        std::fs::write(game_dir.path().join("config/a.toml"), "user").unwrap();
+
+        let (mut extracted, skipped) = extract_overrides_streaming(
+            zip.path(),
+            game_dir.path(),
+            ModpackFormat::Modrinth,
+            None,
+            false,
+            std::sync::Arc::new(crate::game::installer::types::SilentProgressReporter),
+        )
+        .await
+        .unwrap();
+        extracted.sort();
+
+        assert_eq!(
+            extracted,
+            vec![
+                PathBuf::from("mods/extra.jar"),
+                PathBuf::from("options.txt")
+            ]
+        );
+        assert_eq!(skipped, vec!["config/a.toml".to_string()]);
+        let read = |path: &str| std::fs::read_to_string(game_dir.path().join(path)).unwrap();
+        assert_eq!(read("config/a.toml"), "user");
+        assert_eq!(read("options.txt"), "client");
+        assert!(!game_dir
+            .path()
+            .parent()
+            .unwrap()
+            .join("escape.txt")
+            .exists());
+    }
+}
+
+/// An override that would replace a file with different contents in the
+/// game directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConflictFile {
+    /// Game-relative path, `/` separated.
+    pub relative_path: String,
+    pub existing_sha1: String,
+}
+
+/// What override extraction does with a file that already exists with
+/// different contents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictResolution {
+    /// Keep the existing file.
+    Skip,
+    /// Replace it with the pack's version.
+    #[default]
+    Overwrite,
+    /// Replace it, keeping a copy under [`OVERRIDE_BACKUP_DIR`].
+    Backup,
+}
+
+/// Backups from [`ConflictResolution::Backup`], one timestamped folder per
+/// extraction, relative to the game directory.
+pub const OVERRIDE_BACKUP_DIR: &str = ".vesta/override-backups";
+
+/// List override files in a modpack ZIP that would replace a file with
+/// different contents in `game_dir`, so the user can be asked first.
+/// Identical files are not conflicts. Paths matching `ignore_patterns` are
+/// left out: `*` matches any run of characters and a trailing `/` matches a
+/// whole directory, e.g. `config/` or `*.txt`.
+pub fn detect_override_conflicts(
+    zip_path: &Path,
+    game_dir: &Path,
+    ignore_patterns: &[&str],
+) -> Result<Vec<ConflictFile>> {
+    use sha1::{Digest, Sha1};
+
+    let format = get_modpack_metadata(zip_path)?.format;
+    let mut conflicts = Vec::new();
+    for relative_path in list_override_paths(zip_path)? {
+        if ignore_patterns
+            .iter()
+            .any(|pattern| matches_ignore_pattern(pattern, &relative_path))
+        {
+            continue;
+        }
+        let target = crate::utils::paths::long_path(&game_dir.join(&relative_path));
+        if !target.is_file() {
+            continue;
+        }
+        let existing_sha1 = format!("{:x}", Sha1::digest(std::fs::read(&target)?));
+        let incoming = read_zip_override_entry(zip_path, format, &relative_path)?;
+        if format!("{:x}", Sha1::digest(&incoming)) != existing_sha1 {
+            conflicts.push(ConflictFile {
+                relative_path,
+                existing_sha1,
+            });
+        }
+    }
+    Ok(conflicts)
+}
+
+/// Case-insensitive match of a `/` separated relative path against one
+/// ignore pattern.
+fn matches_ignore_pattern(pattern: &str, path: &str) -> bool {
+    fn wildcard(pattern: &[u8], text: &[u8]) -> bool {
+        match pattern.split_first() {
+            None => text.is_empty(),
+            Some((b'*', rest)) => (0..=text.len()).any(|i| wildcard(rest, &text[i..])),
+            Some((c, rest)) => text
+                .split_first()
+                .is_some_and(|(t, text)| t == c && wildcard(rest, text)),
+        }
+    }
+
+    let pattern = pattern.trim().replace('\\', "/").to_lowercase();
+    let path = path.to_lowercase();
+    if pattern.is_empty() {
+        return false;
+    }
+    match pattern.strip_suffix('/') {
+        Some(dir) => path
+            .match_indices('/')
+            .any(|(end, _)| wildcard(dir.as_bytes(), &path.as_bytes()[..end])),
+        None => wildcard(pattern.as_bytes(), path.as_bytes()),
+    }
 }
 
 /// Extracts overrides from a modpack ZIP to the specified instance directory
@@ -311,21 +540,30 @@ pub fn extract_overrides<P: AsRef<Path>, D: AsRef<Path>>(
     format: ModpackFormat,
     root_prefix: Option<String>,
 ) -> Result<Vec<PathBuf>> {
-    let (extracted, _skipped) =
-        extract_overrides_with_config_policy(zip_path, destination, format, root_prefix, true)?;
+    let (extracted, _skipped) = extract_overrides_with_config_policy(
+        zip_path,
+        destination,
+        format,
+        root_prefix,
+        true,
+        ConflictResolution::Overwrite,
+    )?;
     Ok(extracted)
 }
 
 /// Extract overrides with config preservation control.
 /// When `force_overwrite_configs` is false, files in the `config/` directory
 /// and files with common config extensions (.cfg, .json, .toml, .yml, .yaml, .properties)
-/// are skipped. Returns (extracted_files, skipped_configs).
+/// are skipped. Other existing files with different contents are handled
+/// according to `conflict_resolution` (see [`detect_override_conflicts`]).
+/// Returns (extracted_files, skipped_paths).
 pub fn extract_overrides_with_config_policy<P: AsRef<Path>, D: AsRef<Path>>(
     zip_path: P,
     destination: D,
     format: ModpackFormat,
     root_prefix: Option<String>,
     force_overwrite_configs: bool,
+    conflict_resolution: ConflictResolution,
 ) -> Result<(Vec<PathBuf>, Vec<String>)> {
     let file = File::open(zip_path)?;
     let mut archive = ZipArchive::new(file)?;
@@ -333,27 +571,36 @@ pub fn extract_overrides_with_config_policy<P: AsRef<Path>, D: AsRef<Path>>(
     let prefix = root_prefix.unwrap_or_default();
     let mut extracted_files = Vec::new();
     let mut skipped_configs = Vec::new();
+    let policy = ExtractPolicy {
+        force_overwrite_configs,
+        conflict_resolution,
+        backup_dir: destination
+            .join(OVERRIDE_BACKUP_DIR)
+            .join(chrono::Local::now().format("%Y%m%dT%H%M%S").to_string()),
+    };
 
-    match format {
-        ModpackFormat::Modrinth => {
-            let (extracted, skipped) = extract_folder_to_root_with_config_policy(
-                &mut archive,
-                &format!("{}overrides", prefix),
-                destination,
-                force_overwrite_configs,
-            )?;
-            extracted_files.extend(extracted);
-            skipped_configs.extend(skipped);
+    for folder in override_folders(&mut archive, format, &prefix)? {
+        let (extracted, skipped) =
+            extract_folder_to_root_with_config_policy(&mut archive, &folder, destination, &policy)?;
+        extracted_files.extend(extracted);
+        skipped_configs.extend(skipped);
+    }
 
-            let (extracted, skipped) = extract_folder_to_root_with_config_policy(
-                &mut archive,
-                &format!("{}client-overrides", prefix),
-                destination,
-                force_overwrite_configs,
-            )?;
-            extracted_files.extend(extracted);
-            skipped_configs.extend(skipped);
-        }
+    Ok((extracted_files, skipped_configs))
+}
+
+/// Override folders of a modpack in extraction order; files in later folders
+/// replace files at the same path in earlier ones.
+fn override_folders<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    format: ModpackFormat,
+    prefix: &str,
+) -> Result<Vec<String>> {
+    match format {
+        ModpackFormat::Modrinth => Ok(vec![
+            format!("{}overrides", prefix),
+            format!("{}client-overrides", prefix),
+        ]),
         ModpackFormat::CurseForge => {
             let overrides_folder = if let Ok(mut manifest_file) =
                 archive.by_name(&format!("{}manifest.json", prefix))
@@ -365,53 +612,129 @@ pub fn extract_overrides_with_config_policy<P: AsRef<Path>, D: AsRef<Path>>(
             } else {
                 "overrides".to_string()
             };
+            Ok(vec![format!("{}{}", prefix, overrides_folder)])
+        }
+    }
+}
 
-            let (extracted, skipped) = extract_folder_to_root_with_config_policy(
-                &mut archive,
-                &format!("{}{}", prefix, overrides_folder),
-                destination,
-                force_overwrite_configs,
-            )?;
-            extracted_files.extend(extracted);
-            skipped_configs.extend(skipped);
+/// Async counterpart of [`extract_overrides_with_config_policy`] for
+/// [`ConflictResolution::Overwrite`], streaming the override folders into
+/// `destination` off the async runtime and reporting progress by bytes.
+///
+/// Config files are preserved the same way; unchanged files are not rewritten.
+/// Returns (extracted_files, skipped_paths).
+pub async fn extract_overrides_streaming(
+    zip_path: &Path,
+    destination: &Path,
+    format: ModpackFormat,
+    root_prefix: Option<String>,
+    force_overwrite_configs: bool,
+    reporter: std::sync::Arc<dyn crate::game::installer::types::ProgressReporter>,
+) -> Result<(Vec<PathBuf>, Vec<String>)> {
+    let listed_path = zip_path.to_path_buf();
+    let prefix = root_prefix.unwrap_or_default();
+    let (folders, names) = tokio::task::spawn_blocking(move || -> Result<_> {
+        let mut archive = ZipArchive::new(File::open(&listed_path)?)?;
+        let folders = override_folders(&mut archive, format, &prefix)?;
+        let names: std::collections::HashSet<String> =
+            archive.file_names().map(|name| name.to_string()).collect();
+        Ok((folders, names))
+    })
+    .await
+    .map_err(|e| anyhow!("override listing worker panicked: {}", e))??;
+
+    let mut extracted = Vec::new();
+    let mut skipped = Vec::new();
+    let mut plan = |entry: &crate::utils::archive::ZipEntryInfo| -> Option<String> {
+        let (position, relative) = folders.iter().enumerate().find_map(|(position, folder)| {
+            entry
+                .name
+                .strip_prefix(folder.as_str())
+                .and_then(|rest| rest.strip_prefix('/'))
+                .filter(|rest| !rest.is_empty())
+                .map(|rest| (position, rest.to_string()))
+        })?;
+        if folders[position + 1..]
+            .iter()
+            .any(|later| names.contains(&format!("{}/{}", later, relative)))
+        {
+            return None;
+        }
+        if crate::utils::paths::validate_relative_path(&relative).is_err() {
+            log::warn!(
+                "[extract_overrides] Skipping ZIP entry with unsafe path: {}",
+                relative
+            );
+            return None;
+        }
+        if entry.is_dir {
+            return Some(relative);
         }
+        if !force_overwrite_configs
+            && is_config_file(&relative)
+            && destination.join(&relative).exists()
+        {
+            log::info!(
+                "[extract_overrides] Preserving existing config file: {}",
+                relative
+            );
+            skipped.push(relative);
+            return None;
+        }
+        extracted.push(PathBuf::from(&relative));
+        Some(relative)
+    };
+
+    crate::utils::archive::extract_zip_entries_streaming(
+        zip_path,
+        destination,
+        reporter,
+        &mut plan,
+    )
+    .await?;
+
+    Ok((extracted, skipped))
+}
+
+/// Check if a relative override path should be treated as a config file.
+fn is_config_file(rel_path: &str) -> bool {
+    let lower = rel_path.to_lowercase();
+    if lower.starts_with("config/") || lower.starts_with("config\\") {
+        return true;
     }
+    let config_exts = [
+        ".cfg",
+        ".config",
+        ".json",
+        ".toml",
+        ".yml",
+        ".yaml",
+        ".properties",
+        ".txt",
+    ];
+    config_exts.iter().any(|ext| lower.ends_with(ext))
+}
 
-    Ok((extracted_files, skipped_configs))
+struct ExtractPolicy {
+    force_overwrite_configs: bool,
+    conflict_resolution: ConflictResolution,
+    backup_dir: PathBuf,
 }
 
 /// Extract a folder from a ZIP archive to a destination, with config preservation.
 /// When `force_overwrite_configs` is false, files in `config/` or with config extensions
-/// are skipped. Returns (extracted_files, skipped_config_paths).
+/// are skipped, as are conflicting files under [`ConflictResolution::Skip`].
+/// Returns (extracted_files, skipped_paths).
 fn extract_folder_to_root_with_config_policy<R: Read + std::io::Seek>(
     archive: &mut ZipArchive<R>,
     folder_name: &str,
     destination: &Path,
-    force_overwrite_configs: bool,
+    policy: &ExtractPolicy,
 ) -> Result<(Vec<PathBuf>, Vec<String>)> {
     let folder_prefix = format!("{}/", folder_name);
     let mut extracted = Vec::new();
     let mut skipped = Vec::new();
 
-    /// Check if a relative path should be treated as a config file.
-    fn is_config_file(rel_path: &str) -> bool {
-        let lower = rel_path.to_lowercase();
-        if lower.starts_with("config/") || lower.starts_with("config\\") {
-            return true;
-        }
-        let config_exts = [
-            ".cfg",
-            ".config",
-            ".json",
-            ".toml",
-            ".yml",
-            ".yaml",
-            ".properties",
-            ".txt",
-        ];
-        config_exts.iter().any(|ext| lower.ends_with(ext))
-    }
-
     for i in 0..archive.len() {
         let mut file = archive.by_index(i)?;
         let name = file.name().to_owned();
@@ -427,10 +750,10 @@ fn extract_folder_to_root_with_config_policy<R: Read + std::io::Seek>(
                 continue;
             }
             let relative_path = PathBuf::from(&relative_path_str);
-            let target_path = destination.join(&relative_path);
+            let target_path = crate::utils::paths::long_path(&destination.join(&relative_path));
 
             if !file.is_dir()
-                && !force_overwrite_configs
+                && !policy.force_overwrite_configs
                 && is_config_file(&relative_path_str)
                 && target_path.exists()
             {
@@ -444,6 +767,33 @@ fn extract_folder_to_root_with_config_policy<R: Read + std::io::Seek>(
 
             if file.is_dir() {
                 std::fs::create_dir_all(&target_path)?;
+            } else if policy.conflict_resolution != ConflictResolution::Overwrite
+                && target_path.is_file()
+            {
+                let mut incoming = Vec::new();
+                file.read_to_end(&mut incoming)?;
+                if std::fs::read(&target_path)? != incoming {
+                    if policy.conflict_resolution == ConflictResolution::Skip {
+                        log::info!(
+                            "[extract_overrides] Keeping conflicting file: {}",
+                            relative_path_str
+                        );
+                        skipped.push(relative_path_str);
+                        continue;
+                    }
+                    let backup_path =
+                        crate::utils::paths::long_path(&policy.backup_dir.join(&relative_path));
+                    if let Some(parent) = backup_path.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    std::fs::copy(&target_path, &backup_path)?;
+                    log::info!(
+                        "[extract_overrides] Backed up conflicting file: {}",
+                        relative_path_str
+                    );
+                    std::fs::write(&target_path, &incoming)?;
+                }
+                extracted.push(relative_path);
             } else {
                 if let Some(parent) = target_path.parent() {
                     std::fs::create_dir_all(parent)?;
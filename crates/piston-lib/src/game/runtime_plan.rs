@@ -189,7 +189,10 @@ impl RuntimePlan {
 
         Ok(Self {
             libraries_dir: request.data_dir.join("libraries"),
-            natives_dir: request.data_dir.join("natives").join(&request.version_id),
+            natives_dir: crate::game::launcher::get_natives_dir(
+                &request.data_dir,
+                &installed_version_id,
+            ),
             request,
             installed_version_id,
             manifest_path,
@@ -280,6 +283,26 @@ fn load_selected_manifest(
     installed_id: &str,
     path: &Path,
 ) -> Result<UnifiedManifest, RuntimePlanError> {
+    // Loader versions launch from the flattened `<id>.unified.json`, which is
+    // (re)generated here when missing or when a JSON in the chain changed.
+    if request.is_modded() {
+        match crate::game::launcher::unified_manifest::generate_unified_manifest_for_os(
+            &request.data_dir,
+            installed_id,
+            request.os,
+        ) {
+            Ok(mut unified) => {
+                unified.apply_native_arch_policy(request.os);
+                return Ok(unified);
+            }
+            Err(error) => log::warn!(
+                "[RuntimePlan] Unified manifest for {} unavailable, resolving version chain: {:#}",
+                installed_id,
+                error
+            ),
+        }
+    }
+
     let raw = read_manifest(path)?;
     if let Ok(mut unified) = serde_json::from_str::<UnifiedManifest>(&raw) {
         if !unified.minecraft_version.is_empty() {
@@ -394,6 +417,11 @@ mod tests {
             wrapper_command: None,
             pre_launch_hook: None,
             post_exit_hook: None,
+            safe_mode: false,
+            log_pipe: false,
+            connect_server: None,
+            process_priority: None,
+            cpu_affinity_mask: None,
         }
     }
 
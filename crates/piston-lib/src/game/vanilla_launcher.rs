@@ -0,0 +1,655 @@
+//! Exchange installed versions with the official Minecraft Launcher.
+//!
+//! The official launcher keeps versions in `<minecraft dir>/versions/<id>/<id>.json`
+//! in Mojang's format and lists them through profiles in
+//! `launcher_profiles.json`. Export writes a child version that inherits from
+//! the vanilla version, links (or copies) the libraries it needs and adds a
+//! profile whose `gameDir` is the instance directory. Import reads such a
+//! version back and reports its Minecraft version and loader.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::game::launcher::unified_manifest::UnifiedManifest;
+use crate::game::launcher::version_parser::VersionManifest;
+use crate::game::metadata::ModloaderType;
+use crate::game::runtime_plan::RuntimeRequest;
+use crate::utils::paths::{is_unc_path, long_path};
+
+/// Marker key written into exported version JSONs. A version directory
+/// without it belongs to someone else and is never overwritten.
+const EXPORT_MARKER: &str = "vestaExport";
+const PROFILES_FILE: &str = "launcher_profiles.json";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VanillaExport {
+    pub version_id: String,
+    pub profile_key: String,
+    pub libraries_linked: usize,
+    pub libraries_copied: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VanillaLauncherVersion {
+    pub version_id: String,
+    pub minecraft_version: String,
+    pub modloader: Option<ModloaderType>,
+    pub modloader_version: Option<String>,
+    /// Name of the launcher profile using this version, if any.
+    pub profile_name: Option<String>,
+    /// The profile's `gameDir`, or the launcher directory itself.
+    pub game_dir: PathBuf,
+}
+
+fn version_json_path(root: &Path, version_id: &str) -> PathBuf {
+    root.join("versions")
+        .join(version_id)
+        .join(format!("{}.json", version_id))
+}
+
+fn version_jar_path(root: &Path, version_id: &str) -> PathBuf {
+    root.join("versions")
+        .join(version_id)
+        .join(format!("{}.jar", version_id))
+}
+
+fn read_json(path: &Path) -> Result<Value> {
+    let raw =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+    serde_json::from_str(&raw).with_context(|| format!("Failed to parse {:?}", path))
+}
+
+fn write_json(path: &Path, value: &Value) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(value)?)
+        .with_context(|| format!("Failed to write {:?}", path))
+}
+
+fn copy_if_missing(src: &Path, dst: &Path) -> Result<bool> {
+    if dst.exists() {
+        return Ok(false);
+    }
+    if let Some(parent) = dst.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::copy(src, dst).with_context(|| format!("Failed to copy {:?} to {:?}", src, dst))?;
+    Ok(true)
+}
+
+enum Placed {
+    Linked,
+    Copied,
+    Skipped,
+}
+
+/// Link `src` at `dst`, or copy it when either side is on a network share
+/// or linking fails.
+fn link_or_copy(src: &Path, dst: &Path) -> Result<Placed> {
+    let (src, dst) = (&long_path(src), &long_path(dst));
+    if dst.exists() || !src.exists() {
+        return Ok(Placed::Skipped);
+    }
+    if let Some(parent) = dst.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if !is_unc_path(src) && !is_unc_path(dst) && link(src, dst).is_ok() {
+        return Ok(Placed::Linked);
+    }
+    std::fs::copy(src, dst).with_context(|| format!("Failed to copy {:?} to {:?}", src, dst))?;
+    Ok(Placed::Copied)
+}
+
+/// A symlink on Unix. Windows gets a hard link instead, since symlinks there
+/// need developer mode; it fails across volumes and the caller copies.
+fn link(src: &Path, dst: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(src, dst)
+    }
+    #[cfg(not(unix))]
+    {
+        std::fs::hard_link(src, dst)
+    }
+}
+
+/// Arguments of `all` that are not in `base`, keeping order. Each base
+/// argument cancels one equal argument.
+fn extra_arguments<T: Serialize>(all: &[T], base: &[T]) -> Vec<Value> {
+    let mut remaining: Vec<Value> = base
+        .iter()
+        .filter_map(|arg| serde_json::to_value(arg).ok())
+        .collect();
+    all.iter()
+        .filter_map(|arg| serde_json::to_value(arg).ok())
+        .filter(|arg| match remaining.iter().position(|base| base == arg) {
+            Some(index) => {
+                remaining.remove(index);
+                false
+            }
+            None => true,
+        })
+        .collect()
+}
+
+/// Mojang-format child version for a modded install.
+fn child_version_json(
+    installed_id: &str,
+    unified: &UnifiedManifest,
+    vanilla: &UnifiedManifest,
+    legacy_arguments: bool,
+) -> Value {
+    let vanilla_paths: HashSet<&str> = vanilla.libraries.iter().map(|l| l.path.as_str()).collect();
+    let libraries: Vec<Value> = unified
+        .libraries
+        .iter()
+        .filter(|lib| !lib.is_native && lib.include_in_classpath)
+        .filter(|lib| !vanilla_paths.contains(lib.path.as_str()))
+        .map(|lib| {
+            let mut artifact = Map::new();
+            artifact.insert("path".into(), json!(lib.path));
+            // Same convention as the Forge installer for locally built jars.
+            artifact.insert(
+                "url".into(),
+                json!(lib.download_url.clone().unwrap_or_default()),
+            );
+            if let Some(sha1) = &lib.sha1 {
+                artifact.insert("sha1".into(), json!(sha1));
+            }
+            if let Some(size) = lib.size {
+                artifact.insert("size".into(), json!(size));
+            }
+            json!({ "name": lib.name, "downloads": { "artifact": artifact } })
+        })
+        .collect();
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut version = json!({
+        "id": installed_id,
+        "inheritsFrom": unified.minecraft_version,
+        "type": unified.version_type.clone().unwrap_or_else(|| "release".to_string()),
+        "mainClass": unified.main_class,
+        "time": now,
+        "releaseTime": now,
+        "libraries": libraries,
+        EXPORT_MARKER: true,
+    });
+
+    if legacy_arguments {
+        // The official launcher replaces the parent's minecraftArguments.
+        let tokens: Vec<String> = unified
+            .game_arguments
+            .iter()
+            .filter_map(|arg| serde_json::to_value(arg).ok())
+            .filter_map(|arg| arg.as_str().map(str::to_string))
+            .collect();
+        version["minecraftArguments"] = json!(tokens.join(" "));
+    } else {
+        version["arguments"] = json!({
+            "game": extra_arguments(&unified.game_arguments, &vanilla.game_arguments),
+            "jvm": extra_arguments(&unified.jvm_arguments, &vanilla.jvm_arguments),
+        });
+    }
+    version
+}
+
+/// Stable profile key for an instance directory, so re-exports update the
+/// same profile.
+fn profile_key(game_dir: &Path) -> String {
+    let slug = game_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let slug: String = slug
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("vesta-{}", slug)
+}
+
+fn upsert_profile(
+    minecraft_dir: &Path,
+    key: &str,
+    display_name: &str,
+    version_id: &str,
+    game_dir: &Path,
+) -> Result<()> {
+    let path = minecraft_dir.join(PROFILES_FILE);
+    let mut profiles_file = if path.exists() {
+        read_json(&path)?
+    } else {
+        json!({ "profiles": {}, "settings": {}, "version": 3 })
+    };
+    if !profiles_file["profiles"].is_object() {
+        profiles_file["profiles"] = json!({});
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let created = profiles_file["profiles"][key]["created"]
+        .as_str()
+        .map(str::to_string)
+        .unwrap_or_else(|| now.clone());
+    profiles_file["profiles"][key] = json!({
+        "name": display_name,
+        "type": "custom",
+        "lastVersionId": version_id,
+        "gameDir": game_dir.to_string_lossy(),
+        "icon": "Furnace",
+        "created": created,
+        "lastUsed": now,
+    });
+    write_json(&path, &profiles_file)
+}
+
+/// Export an installed version to the official launcher at `minecraft_dir`.
+/// Returns the version id to select there.
+pub fn export_version(
+    request: &RuntimeRequest,
+    minecraft_dir: &Path,
+    display_name: &str,
+    game_dir: &Path,
+) -> Result<VanillaExport> {
+    let data_dir = &request.data_dir;
+    let mc_version = &request.version_id;
+    let installed_id = request.installed_version_id();
+
+    let vanilla_json_path = version_json_path(data_dir, mc_version);
+    let vanilla_jar_path = version_jar_path(data_dir, mc_version);
+    if !vanilla_json_path.exists() || !vanilla_jar_path.exists() {
+        anyhow::bail!("Minecraft {} is not installed", mc_version);
+    }
+    let vanilla_value = read_json(&vanilla_json_path)?;
+    let vanilla_manifest: VersionManifest = serde_json::from_value(vanilla_value.clone())
+        .with_context(|| format!("Invalid version manifest {:?}", vanilla_json_path))?;
+    let vanilla = UnifiedManifest::merge(vanilla_manifest, None, request.os);
+
+    let unified = if request.is_modded() {
+        let path = version_json_path(data_dir, &installed_id);
+        UnifiedManifest::load_from_path(&path)
+            .with_context(|| format!("{} is not installed", installed_id))?
+    } else {
+        vanilla.clone()
+    };
+
+    let child_json_path = version_json_path(minecraft_dir, &installed_id);
+    if request.is_modded() && child_json_path.exists() {
+        let existing = read_json(&child_json_path).unwrap_or(Value::Null);
+        if existing.get(EXPORT_MARKER).is_none() {
+            anyhow::bail!(
+                "The official launcher already has a version named {}",
+                installed_id
+            );
+        }
+    }
+
+    // Parent version; the official launcher's own copy wins if present.
+    if !version_json_path(minecraft_dir, mc_version).exists() {
+        write_json(
+            &version_json_path(minecraft_dir, mc_version),
+            &vanilla_value,
+        )?;
+    }
+    copy_if_missing(
+        &vanilla_jar_path,
+        &version_jar_path(minecraft_dir, mc_version),
+    )?;
+
+    if request.is_modded() {
+        let legacy_arguments = vanilla_value.get("arguments").is_none()
+            && vanilla_value.get("minecraftArguments").is_some();
+        let child = child_version_json(&installed_id, &unified, &vanilla, legacy_arguments);
+        write_json(&child_json_path, &child)?;
+
+        let installed_jar = version_jar_path(data_dir, &installed_id);
+        let jar_source = if installed_jar.exists() {
+            installed_jar
+        } else {
+            vanilla_jar_path.clone()
+        };
+        let child_jar = version_jar_path(minecraft_dir, &installed_id);
+        let _ = std::fs::remove_file(&child_jar);
+        copy_if_missing(&jar_source, &child_jar)?;
+    }
+
+    let mut libraries_linked = 0;
+    let mut libraries_copied = 0;
+    let libraries_dir = data_dir.join("libraries");
+    let target_libraries = minecraft_dir.join("libraries");
+    for library in &unified.libraries {
+        match link_or_copy(
+            &libraries_dir.join(&library.path),
+            &target_libraries.join(&library.path),
+        )? {
+            Placed::Linked => libraries_linked += 1,
+            Placed::Copied => libraries_copied += 1,
+            Placed::Skipped => {}
+        }
+    }
+
+    let profile_key = profile_key(game_dir);
+    upsert_profile(
+        minecraft_dir,
+        &profile_key,
+        display_name,
+        &installed_id,
+        game_dir,
+    )?;
+
+    Ok(VanillaExport {
+        version_id: installed_id,
+        profile_key,
+        libraries_linked,
+        libraries_copied,
+    })
+}
+
+/// Loader and loader version declared by a version's libraries.
+fn detect_loader(libraries: &[Value], mc_version: &str) -> Option<(ModloaderType, String)> {
+    libraries
+        .iter()
+        .filter_map(|lib| lib.get("name").and_then(Value::as_str))
+        .find_map(|name| {
+            let mut parts = name.split(':');
+            let (group, artifact, version) = (parts.next()?, parts.next()?, parts.next()?);
+            let strip_mc = |v: &str| {
+                let v = v.strip_prefix(&format!("{}-", mc_version)).unwrap_or(v);
+                // Old Forge versions repeat the game version: 1.7.10-10.13.4.1614-1.7.10
+                v.strip_suffix(&format!("-{}", mc_version))
+                    .unwrap_or(v)
+                    .to_string()
+            };
+            match (group, artifact) {
+                ("net.fabricmc", "fabric-loader") => {
+                    Some((ModloaderType::Fabric, version.to_string()))
+                }
+                ("org.quiltmc", "quilt-loader") => {
+                    Some((ModloaderType::Quilt, version.to_string()))
+                }
+                ("net.neoforged", "neoforge") => {
+                    Some((ModloaderType::NeoForge, version.to_string()))
+                }
+                // NeoForge for 1.20.1 kept Forge's artifact name.
+                ("net.neoforged", "forge") => Some((ModloaderType::NeoForge, strip_mc(version))),
+                ("net.minecraftforge", "forge") => Some((ModloaderType::Forge, strip_mc(version))),
+                _ => None,
+            }
+        })
+}
+
+/// Read a version from the official launcher for import.
+pub fn read_version(minecraft_dir: &Path, version_id: &str) -> Result<VanillaLauncherVersion> {
+    let path = version_json_path(minecraft_dir, version_id);
+    if !path.exists() {
+        anyhow::bail!("Version {} not found in the official launcher", version_id);
+    }
+    let version = read_json(&path)?;
+    let minecraft_version = version
+        .get("inheritsFrom")
+        .and_then(Value::as_str)
+        .unwrap_or(version_id)
+        .to_string();
+    let libraries = version
+        .get("libraries")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let (modloader, modloader_version) = match detect_loader(libraries, &minecraft_version) {
+        Some((loader, loader_version)) => (Some(loader), Some(loader_version)),
+        None => (None, None),
+    };
+
+    let profile = minecraft_dir
+        .join(PROFILES_FILE)
+        .exists()
+        .then(|| read_json(&minecraft_dir.join(PROFILES_FILE)).ok())
+        .flatten()
+        .and_then(|file| {
+            file.get("profiles")?
+                .as_object()?
+                .values()
+                .filter(|p| p.get("lastVersionId").and_then(Value::as_str) == Some(version_id))
+                .max_by_key(|p| {
+                    p.get("lastUsed")
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                })
+                .cloned()
+        });
+    let profile_name = profile
+        .as_ref()
+        .and_then(|p| p.get("name")?.as_str())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string);
+    let game_dir = profile
+        .as_ref()
+        .and_then(|p| p.get("gameDir")?.as_str())
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_dir())
+        .unwrap_or_else(|| minecraft_dir.to_path_buf());
+
+    Ok(VanillaLauncherVersion {
+        version_id: version_id.to_string(),
+        minecraft_version,
+        modloader,
+        modloader_version,
+        profile_name,
+        game_dir,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::installer::types::OsType;
+    use crate::game::launcher::unified_manifest::UnifiedLibrary;
+    use tempfile::tempdir;
+
+    fn request(data_dir: &Path) -> RuntimeRequest {
+        RuntimeRequest {
+            version_id: "1.20.1".to_string(),
+            modloader: Some(ModloaderType::Fabric),
+            modloader_version: Some("0.15.11".to_string()),
+            data_dir: data_dir.to_path_buf(),
+            os: OsType::current(),
+        }
+    }
+
+    fn library(name: &str, path: &str) -> UnifiedLibrary {
+        UnifiedLibrary {
+            name: name.to_string(),
+            path: path.to_string(),
+            download_url: Some(format!("https://maven.fabricmc.net/{}", path)),
+            sha1: None,
+            size: None,
+            is_native: false,
+            classifier: None,
+            extract_rules: None,
+            include_in_classpath: true,
+        }
+    }
+
+    fn write_file(path: &Path, contents: &[u8]) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    /// A vanilla 1.20.1 and a Fabric install of it in a Vesta data dir.
+    fn installed_fabric(data_dir: &Path) {
+        let vanilla = json!({
+            "id": "1.20.1",
+            "mainClass": "net.minecraft.client.main.Main",
+            "type": "release",
+            "arguments": { "game": ["--username", "${auth_player_name}"], "jvm": ["-cp", "${classpath}"] },
+            "libraries": [{
+                "name": "com.mojang:brigadier:1.1.8",
+                "downloads": { "artifact": {
+                    "path": "com/mojang/brigadier/1.1.8/brigadier-1.1.8.jar",
+                    "url": "https://libraries.minecraft.net/com/mojang/brigadier/1.1.8/brigadier-1.1.8.jar"
+                }}
+            }]
+        });
+        write_file(
+            &version_json_path(data_dir, "1.20.1"),
+            vanilla.to_string().as_bytes(),
+        );
+        write_file(&version_jar_path(data_dir, "1.20.1"), b"client");
+
+        let vanilla_manifest: VersionManifest = serde_json::from_value(vanilla).unwrap();
+        let mut unified = UnifiedManifest::merge(vanilla_manifest, None, OsType::current());
+        unified.id = "fabric-loader-0.15.11-1.20.1".to_string();
+        unified.main_class = "net.fabricmc.loader.impl.launch.knot.KnotClient".to_string();
+        unified.libraries.push(library(
+            "net.fabricmc:fabric-loader:0.15.11",
+            "net/fabricmc/fabric-loader/0.15.11/fabric-loader-0.15.11.jar",
+        ));
+        unified.jvm_arguments.push(
+            serde_json::from_value(json!("-DFabricMcEmu= net.minecraft.client.main.Main "))
+                .unwrap(),
+        );
+        let unified_path = version_json_path(data_dir, &unified.id);
+        std::fs::create_dir_all(unified_path.parent().unwrap()).unwrap();
+        unified.save_to_path(&unified_path).unwrap();
+
+        for lib in &unified.libraries {
+            write_file(&data_dir.join("libraries").join(&lib.path), b"jar");
+        }
+    }
+
+    #[test]
+    fn export_writes_child_version_libraries_and_profile() {
+        let data = tempdir().unwrap();
+        let official = tempdir().unwrap();
+        let game_dir = data.path().join("instances").join("my-pack");
+        installed_fabric(data.path());
+
+        let export =
+            export_version(&request(data.path()), official.path(), "My Pack", &game_dir).unwrap();
+
+        assert_eq!(export.version_id, "fabric-loader-0.15.11-1.20.1");
+        assert_eq!(export.libraries_linked + export.libraries_copied, 2);
+        let child = read_json(&version_json_path(official.path(), &export.version_id)).unwrap();
+        assert_eq!(child["inheritsFrom"], "1.20.1");
+        assert_eq!(
+            child["mainClass"],
+            "net.fabricmc.loader.impl.launch.knot.KnotClient"
+        );
+        let libraries = child["libraries"].as_array().unwrap();
+        assert_eq!(libraries.len(), 1);
+        assert_eq!(libraries[0]["name"], "net.fabricmc:fabric-loader:0.15.11");
+        // Only the loader's own argument; the parent's are inherited.
+        assert_eq!(child["arguments"]["game"], json!([]));
+        assert_eq!(
+            child["arguments"]["jvm"],
+            json!(["-DFabricMcEmu= net.minecraft.client.main.Main "])
+        );
+        assert!(version_jar_path(official.path(), &export.version_id).exists());
+        assert!(version_json_path(official.path(), "1.20.1").exists());
+        assert!(official
+            .path()
+            .join("libraries/net/fabricmc/fabric-loader/0.15.11/fabric-loader-0.15.11.jar")
+            .exists());
+
+        let profiles = read_json(&official.path().join(PROFILES_FILE)).unwrap();
+        let profile = &profiles["profiles"][&export.profile_key];
+        assert_eq!(profile["name"], "My Pack");
+        assert_eq!(profile["lastVersionId"], export.version_id.as_str());
+        assert_eq!(profile["gameDir"], game_dir.to_string_lossy().as_ref());
+
+        // Re-exporting updates the same profile.
+        export_version(&request(data.path()), official.path(), "Renamed", &game_dir).unwrap();
+        let profiles = read_json(&official.path().join(PROFILES_FILE)).unwrap();
+        assert_eq!(profiles["profiles"].as_object().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn export_refuses_to_overwrite_foreign_version() {
+        let data = tempdir().unwrap();
+        let official = tempdir().unwrap();
+        installed_fabric(data.path());
+        write_file(
+            &version_json_path(official.path(), "fabric-loader-0.15.11-1.20.1"),
+            br#"{"id":"fabric-loader-0.15.11-1.20.1"}"#,
+        );
+
+        let result = export_version(
+            &request(data.path()),
+            official.path(),
+            "My Pack",
+            &data.path().join("game"),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn exported_version_round_trips_through_import() {
+        let data = tempdir().unwrap();
+        let official = tempdir().unwrap();
+        let game_dir = data.path().join("instances").join("my-pack");
+        std::fs::create_dir_all(&game_dir).unwrap();
+        installed_fabric(data.path());
+        let export =
+            export_version(&request(data.path()), official.path(), "My Pack", &game_dir).unwrap();
+
+        let version = read_version(official.path(), &export.version_id).unwrap();
+        assert_eq!(version.minecraft_version, "1.20.1");
+        assert_eq!(version.modloader, Some(ModloaderType::Fabric));
+        assert_eq!(version.modloader_version.as_deref(), Some("0.15.11"));
+        assert_eq!(version.profile_name.as_deref(), Some("My Pack"));
+        assert_eq!(version.game_dir, game_dir);
+    }
+
+    #[test]
+    fn detects_loaders_from_library_coordinates() {
+        let libs = |name: &str| vec![json!({ "name": name })];
+        assert_eq!(
+            detect_loader(&libs("net.minecraftforge:forge:1.20.1-47.2.0"), "1.20.1"),
+            Some((ModloaderType::Forge, "47.2.0".to_string()))
+        );
+        assert_eq!(
+            detect_loader(
+                &libs("net.minecraftforge:forge:1.7.10-10.13.4.1614-1.7.10"),
+                "1.7.10"
+            ),
+            Some((ModloaderType::Forge, "10.13.4.1614".to_string()))
+        );
+        assert_eq!(
+            detect_loader(&libs("net.neoforged:forge:1.20.1-47.1.106"), "1.20.1"),
+            Some((ModloaderType::NeoForge, "47.1.106".to_string()))
+        );
+        assert_eq!(
+            detect_loader(&libs("net.neoforged:neoforge:21.1.77:universal"), "1.21.1"),
+            Some((ModloaderType::NeoForge, "21.1.77".to_string()))
+        );
+        assert_eq!(
+            detect_loader(&libs("com.mojang:brigadier:1.1.8"), "1.20.1"),
+            None
+        );
+    }
+
+    #[test]
+    fn vanilla_version_imports_into_launcher_dir() {
+        let official = tempdir().unwrap();
+        write_file(
+            &version_json_path(official.path(), "1.21.1"),
+            br#"{"id":"1.21.1","libraries":[]}"#,
+        );
+
+        let version = read_version(official.path(), "1.21.1").unwrap();
+        assert_eq!(version.minecraft_version, "1.21.1");
+        assert_eq!(version.modloader, None);
+        assert_eq!(version.game_dir, official.path());
+        assert!(read_version(official.path(), "missing").is_err());
+    }
+}
@@ -0,0 +1,357 @@
+use crate::game::installer::types::ProgressReporter;
+use anyhow::{bail, Context, Result};
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Totals for a finished [`extract_zip_streaming`] run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExtractStats {
+    pub files_extracted: usize,
+    pub bytes_extracted: u64,
+}
+
+/// A ZIP entry as seen by the planner of [`extract_zip_entries_streaming`].
+#[derive(Debug, Clone)]
+pub struct ZipEntryInfo {
+    /// Entry name with `/` separators; directories end in `/`.
+    pub name: String,
+    /// Uncompressed size in bytes.
+    pub size: u64,
+    pub crc32: u32,
+    pub is_dir: bool,
+}
+
+struct PlannedEntry {
+    index: usize,
+    target: String,
+    info: ZipEntryInfo,
+}
+
+/// Extract `zip_path` into `dest` off the async runtime, reporting percent
+/// progress by uncompressed bytes after each file.
+///
+/// Entries whose name fails `include_filter` are skipped, as are entries with
+/// absolute or `..` paths. Cancellation is checked between files.
+pub async fn extract_zip_streaming(
+    zip_path: &Path,
+    dest: &Path,
+    reporter: Arc<dyn ProgressReporter>,
+    include_filter: Option<&(dyn Fn(&str) -> bool + Sync)>,
+) -> Result<ExtractStats> {
+    extract_zip_entries_streaming(zip_path, dest, reporter, &mut |entry: &ZipEntryInfo| {
+        include_filter
+            .is_none_or(|include| include(&entry.name))
+            .then(|| entry.name.clone())
+    })
+    .await
+}
+
+/// [`extract_zip_streaming`] with a planner that picks the path, relative to
+/// `dest`, each entry is written to, or `None` to skip it.
+///
+/// The planner runs once per entry before anything is written. Files already
+/// on disk with the entry's size and CRC-32 are left untouched and do not count
+/// towards [`ExtractStats::files_extracted`].
+pub async fn extract_zip_entries_streaming(
+    zip_path: &Path,
+    dest: &Path,
+    reporter: Arc<dyn ProgressReporter>,
+    plan: &mut (dyn FnMut(&ZipEntryInfo) -> Option<String> + Send),
+) -> Result<ExtractStats> {
+    let listed_path = zip_path.to_path_buf();
+    let entries = tokio::task::spawn_blocking(move || list_entries(&listed_path))
+        .await
+        .context("zip listing worker panicked")??;
+
+    let planned: Vec<PlannedEntry> = entries
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, info)| {
+            plan(&info).map(|target| PlannedEntry {
+                index,
+                target,
+                info,
+            })
+        })
+        .collect();
+
+    let zip_path = zip_path.to_path_buf();
+    let dest = dest.to_path_buf();
+    tokio::task::spawn_blocking(move || extract_entries(&zip_path, &dest, &planned, reporter))
+        .await
+        .context("zip extraction worker panicked")?
+}
+
+fn list_entries(zip_path: &Path) -> Result<Vec<ZipEntryInfo>> {
+    let file = File::open(zip_path).with_context(|| format!("Failed to open {:?}", zip_path))?;
+    let mut archive =
+        zip::ZipArchive::new(file).with_context(|| format!("Failed to read ZIP {:?}", zip_path))?;
+
+    let mut entries = Vec::with_capacity(archive.len());
+    for index in 0..archive.len() {
+        let entry = archive.by_index_raw(index)?;
+        entries.push(ZipEntryInfo {
+            name: entry.name().replace('\\', "/"),
+            size: entry.size(),
+            crc32: entry.crc32(),
+            is_dir: entry.is_dir(),
+        });
+    }
+    Ok(entries)
+}
+
+fn extract_entries(
+    zip_path: &Path,
+    dest: &Path,
+    entries: &[PlannedEntry],
+    reporter: Arc<dyn ProgressReporter>,
+) -> Result<ExtractStats> {
+    let file = File::open(zip_path).with_context(|| format!("Failed to open {:?}", zip_path))?;
+    let mut archive =
+        zip::ZipArchive::new(file).with_context(|| format!("Failed to read ZIP {:?}", zip_path))?;
+
+    let total_bytes: u64 = entries.iter().map(|entry| entry.info.size).sum();
+    let mut stats = ExtractStats::default();
+    let mut processed_bytes = 0u64;
+    reporter.set_percent(0);
+
+    for planned in entries {
+        if reporter.is_cancelled() {
+            bail!("Extraction cancelled");
+        }
+
+        let info = &planned.info;
+        let target = match crate::utils::paths::join_validated(dest, &planned.target) {
+            Ok(target) => target,
+            Err(e) => {
+                log::warn!("[extract_zip] Skipping unsafe entry {}: {}", info.name, e);
+                continue;
+            }
+        };
+
+        if info.is_dir {
+            std::fs::create_dir_all(&target)?;
+            continue;
+        }
+
+        processed_bytes += info.size;
+        if file_matches(&target, info.size, info.crc32) {
+            reporter.set_percent(percent_of(processed_bytes, total_bytes));
+            continue;
+        }
+
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut entry = archive.by_index(planned.index)?;
+        let mut output =
+            File::create(&target).with_context(|| format!("Failed to create {:?}", target))?;
+        let written = std::io::copy(&mut entry, &mut output)
+            .with_context(|| format!("Failed to extract {}", info.name))?;
+
+        stats.files_extracted += 1;
+        stats.bytes_extracted += written;
+        reporter.set_percent(percent_of(processed_bytes, total_bytes));
+    }
+
+    reporter.set_percent(100);
+    Ok(stats)
+}
+
+/// Whether `path` already holds a file with this size and CRC-32.
+pub(crate) fn file_matches(path: &Path, size: u64, crc32: u32) -> bool {
+    match std::fs::metadata(path) {
+        Ok(metadata) if metadata.is_file() && metadata.len() == size => std::fs::read(path)
+            .map(|bytes| crc32fast::hash(&bytes) == crc32)
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+fn percent_of(processed: u64, total: u64) -> i32 {
+    if total == 0 {
+        return 100;
+    }
+    ((processed.min(total) as u128 * 100) / total as u128) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::installer::types::NotificationActionSpec;
+    use std::io::Write;
+    use std::sync::Mutex;
+    use zip::write::FileOptions;
+
+    #[derive(Default)]
+    struct PercentRecorder {
+        percents: Mutex<Vec<i32>>,
+    }
+
+    impl ProgressReporter for PercentRecorder {
+        fn start_step(&self, _name: &str, _total_steps: Option<u32>) {}
+        fn update_bytes(&self, _transferred: u64, _total: Option<u64>) {}
+
+        fn set_percent(&self, percent: i32) {
+            self.percents.lock().unwrap().push(percent);
+        }
+
+        fn set_message(&self, _message: &str) {}
+        fn set_step_count(&self, _current: u32, _total: Option<u32>) {}
+        fn set_substep(&self, _name: Option<&str>, _current: Option<u32>, _total: Option<u32>) {}
+        fn set_actions(&self, _actions: Option<Vec<NotificationActionSpec>>) {}
+        fn done(&self, _success: bool, _message: Option<&str>) {}
+
+        fn is_cancelled(&self) -> bool {
+            false
+        }
+
+        fn is_paused(&self) -> bool {
+            false
+        }
+    }
+
+    fn write_zip(path: &Path, entries: &[(&str, &[u8])]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, content) in entries {
+            if name.ends_with('/') {
+                zip.add_directory::<&str, ()>(*name, FileOptions::default())
+                    .unwrap();
+            } else {
+                zip.start_file::<&str, ()>(*name, FileOptions::default())
+                    .unwrap();
+                zip.write_all(content).unwrap();
+            }
+        }
+        zip.finish().unwrap();
+    }
+
+    #[tokio::test]
+    async fn extracts_all_entries_and_reports_progress() {
+        let tmp = tempfile::tempdir().unwrap();
+        let zip_path = tmp.path().join("pack.zip");
+        write_zip(
+            &zip_path,
+            &[
+                ("overrides/", b""),
+                ("overrides/config/a.toml", b"key = 1"),
+                ("overrides/mods/b.jar", &[0u8; 300]),
+            ],
+        );
+        let dest = tmp.path().join("out");
+        let reporter = Arc::new(PercentRecorder::default());
+
+        let stats = extract_zip_streaming(&zip_path, &dest, reporter.clone(), None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            stats,
+            ExtractStats {
+                files_extracted: 2,
+                bytes_extracted: 307,
+            }
+        );
+        assert_eq!(
+            std::fs::read_to_string(dest.join("overrides/config/a.toml")).unwrap(),
+            "key = 1"
+        );
+        let percents = reporter.percents.lock().unwrap().clone();
+        assert_eq!(percents.first(), Some(&0));
+        assert_eq!(percents.last(), Some(&100));
+        assert!(percents.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[tokio::test]
+    async fn honours_include_filter_and_skips_unsafe_paths() {
+        let tmp = tempfile::tempdir().unwrap();
+        let zip_path = tmp.path().join("pack.zip");
+        write_zip(
+            &zip_path,
+            &[
+                ("overrides/options.txt", b"fov:90"),
+                ("manifest.json", b"{}"),
+                ("../escape.txt", b"nope"),
+            ],
+        );
+        let dest = tmp.path().join("out");
+        let only_overrides = |name: &str| !name.starts_with("manifest");
+
+        let stats = extract_zip_streaming(
+            &zip_path,
+            &dest,
+            Arc::new(PercentRecorder::default()),
+            Some(&only_overrides),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(stats.files_extracted, 1);
+        assert!(dest.join("overrides/options.txt").exists());
+        assert!(!dest.join("manifest.json").exists());
+        assert!(!tmp.path().join("escape.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn planner_maps_targets_and_unchanged_files_are_left_alone() {
+        let tmp = tempfile::tempdir().unwrap();
+        let zip_path = tmp.path().join("pack.zip");
+        write_zip(
+            &zip_path,
+            &[
+                ("overrides/config/a.toml", b"key = 1"),
+                ("overrides/options.txt", b"fov:90"),
+                ("modrinth.index.json", b"{}"),
+            ],
+        );
+        let dest = tmp.path().join("out");
+        std::fs::create_dir_all(dest.join("config")).unwrap();
+        std::fs::write(dest.join("config/a.toml"), b"key = 1").unwrap();
+        let strip = |entry: &ZipEntryInfo| {
+            entry
+                .name
+                .strip_prefix("overrides/")
+                .map(|rel| rel.to_string())
+        };
+
+        let stats = extract_zip_entries_streaming(
+            &zip_path,
+            &dest,
+            Arc::new(PercentRecorder::default()),
+            &mut { strip },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            stats,
+            ExtractStats {
+                files_extracted: 1,
+                bytes_extracted: 6,
+            }
+        );
+        assert_eq!(std::fs::read(dest.join("options.txt")).unwrap(), b"fov:90");
+        assert!(!dest.join("modrinth.index.json").exists());
+    }
+
+    #[test]
+    fn file_matches_checks_size_and_crc() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lwjgl.dll");
+        std::fs::write(&path, b"lwjgl 3").unwrap();
+        let crc = crc32fast::hash(b"lwjgl 3");
+
+        assert!(file_matches(&path, 7, crc));
+        assert!(!file_matches(&path, 8, crc));
+        assert!(!file_matches(&path, 7, crc ^ 1));
+        assert!(!file_matches(&dir.path().join("missing.dll"), 7, crc));
+    }
+
+    #[test]
+    fn percent_handles_empty_and_partial_totals() {
+        assert_eq!(percent_of(0, 0), 100);
+        assert_eq!(percent_of(50, 200), 25);
+        assert_eq!(percent_of(500, 200), 100);
+    }
+}
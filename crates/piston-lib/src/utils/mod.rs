@@ -1,3 +1,4 @@
+pub mod archive;
 pub mod hardware;
 pub mod paths;
 pub mod process;
@@ -66,12 +66,81 @@ pub fn path_is_within(root: &Path, candidate: &Path) -> Result<()> {
 }
 
 /// Join `root` with a validated relative path and verify containment.
+/// The result goes through [`long_path`], so it can be opened directly.
 pub fn join_validated(root: &Path, relative_path: &str) -> Result<PathBuf> {
     let relative = validate_relative_path(relative_path)?;
     let joined = root.join(&relative);
     path_is_within(root, &joined)
         .with_context(|| format!("Validated path {:?} escapes root {:?}", relative_path, root))?;
-    Ok(joined)
+    Ok(long_path(&joined))
+}
+
+/// `MAX_PATH` (260) minus the 12 characters `CreateDirectoryW` keeps free for
+/// an 8.3 file name. Longer Windows paths need the extended-length prefix.
+pub const WINDOWS_PATH_LIMIT: usize = 248;
+
+/// `path` in a form Windows can open regardless of length: absolute paths at
+/// or over [`WINDOWS_PATH_LIMIT`] get the `\\?\` (or `\\?\UNC\`) prefix.
+/// Shorter paths, relative paths and every path on other platforms are
+/// returned unchanged.
+pub fn long_path(path: &Path) -> PathBuf {
+    #[cfg(windows)]
+    if let Some(extended) = path.to_str().and_then(extended_length_path) {
+        return PathBuf::from(extended);
+    }
+    path.to_path_buf()
+}
+
+/// Extended-length form of an absolute Windows path over the limit, with
+/// `/` separators, `.` and `..` resolved since Windows stops doing that once
+/// the prefix is present. Paths that already carry the prefix only get their
+/// separators fixed, as joins onto them may have added `/`.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn extended_length_path(path: &str) -> Option<String> {
+    if path.starts_with(r"\\?\") {
+        return path.contains('/').then(|| path.replace('/', "\\"));
+    }
+    if path.starts_with(r"\\.\") {
+        return None;
+    }
+    if path.encode_utf16().count() < WINDOWS_PATH_LIMIT {
+        return None;
+    }
+    let path = path.replace('/', "\\");
+    let bytes = path.as_bytes();
+    // Components before this many are the drive or `server\share` and can't
+    // be popped by `..`.
+    let (prefix, rest, root_len) = if let Some(rest) = path.strip_prefix(r"\\") {
+        (r"\\?\UNC\", rest, 2)
+    } else if bytes.len() > 2 && bytes[0].is_ascii_alphabetic() && &bytes[1..3] == b":\\" {
+        (r"\\?\", path.as_str(), 1)
+    } else {
+        return None;
+    };
+
+    let mut parts: Vec<&str> = Vec::new();
+    for part in rest.split('\\') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                if parts.len() > root_len {
+                    parts.pop();
+                }
+            }
+            _ => parts.push(part),
+        }
+    }
+    Some(format!("{}{}", prefix, parts.join("\\")))
+}
+
+/// Whether `path` is on a network share (`\\server\share\...`). Shares
+/// generally can't hold links, so files placed there are always copied.
+pub fn is_unc_path(path: &Path) -> bool {
+    let path = path.to_string_lossy();
+    if let Some(rest) = path.strip_prefix(r"\\?\") {
+        return rest.starts_with(r"UNC\");
+    }
+    !path.starts_with(r"\\.\") && (path.starts_with(r"\\") || path.starts_with("//"))
 }
 
 #[cfg(test)]
@@ -119,4 +188,153 @@ mod tests {
         let escaped = PathBuf::from("/game/mods/a.jar");
         assert!(path_is_within(&root, &escaped).is_err());
     }
+
+    /// Randomized stand-in for a fuzz target: library paths from a manifest
+    /// are untrusted, so nothing accepted by `join_validated` may land outside
+    /// the libraries directory.
+    #[test]
+    fn random_library_paths_never_escape_the_root() {
+        use rand::{Rng, SeedableRng};
+
+        const PIECES: &[&str] = &[
+            "..", ".", "/", "\\", "a", "lib.jar", "C:", ":", "~", " ", "\0", "é", "//", "\\\\?\\",
+        ];
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("libraries");
+        std::fs::create_dir_all(&root).unwrap();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0x5eed);
+
+        for _ in 0..2000 {
+            let mut raw = Vec::new();
+            for _ in 0..rng.random_range(0..12) {
+                if rng.random_bool(0.2) {
+                    raw.push(rng.random::<u8>());
+                } else {
+                    raw.extend_from_slice(PIECES[rng.random_range(0..PIECES.len())].as_bytes());
+                }
+            }
+            let candidate = String::from_utf8_lossy(&raw);
+            let Ok(joined) = join_validated(&root, &candidate) else {
+                continue;
+            };
+            assert!(
+                !Path::new(&candidate.replace('\\', "/"))
+                    .components()
+                    .any(|c| matches!(
+                        c,
+                        Component::ParentDir | Component::RootDir | Component::Prefix(_)
+                    )),
+                "accepted {:?}",
+                candidate
+            );
+            if let Some(parent) = joined.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            let _ = std::fs::write(&joined, b"x");
+        }
+
+        let mut pending = vec![tmp.path().to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            for entry in std::fs::read_dir(&dir).unwrap() {
+                let path = entry.unwrap().path();
+                assert!(
+                    path.starts_with(&root),
+                    "{} escaped the libraries dir",
+                    path.display()
+                );
+                if path.is_dir() {
+                    pending.push(path);
+                }
+            }
+        }
+    }
+
+    fn deep_windows_path(root: &str) -> String {
+        let mut path = root.to_string();
+        while path.len() < 300 {
+            path.push_str("\\overrides-nested-folder");
+        }
+        path.push_str("\\options.txt");
+        path
+    }
+
+    #[test]
+    fn short_and_relative_paths_are_left_alone() {
+        assert_eq!(extended_length_path(r"C:\Users\me\.vesta\libraries"), None);
+        assert_eq!(extended_length_path(&deep_windows_path("relative")), None);
+        let already = format!(r"\\?\{}", deep_windows_path(r"C:\Users"));
+        assert_eq!(extended_length_path(&already), None);
+        assert_eq!(
+            extended_length_path(&format!("{}/mods/a.jar", already)),
+            Some(format!(r"{}\mods\a.jar", already))
+        );
+    }
+
+    #[test]
+    fn long_drive_paths_get_the_extended_prefix() {
+        let path = deep_windows_path(r"C:\Users\me");
+        let extended = extended_length_path(&path).unwrap();
+        assert_eq!(extended, format!(r"\\?\{}", path));
+
+        let mixed = format!(r"{}/./a/../b.jar", path.trim_end_matches(r"\options.txt"));
+        let extended = extended_length_path(&mixed).unwrap();
+        assert!(extended.starts_with(r"\\?\C:\Users\me\"));
+        assert!(extended.ends_with(r"overrides-nested-folder\b.jar"));
+        assert!(!extended.contains('/'));
+    }
+
+    #[test]
+    fn long_unc_paths_use_the_unc_form() {
+        let path = deep_windows_path(r"\\nas\games\vesta");
+        let extended = extended_length_path(&path).unwrap();
+        assert_eq!(
+            extended,
+            format!(r"\\?\UNC\{}", path.trim_start_matches('\\'))
+        );
+
+        let escaping = format!(r"\\nas\games{}", r"\..".repeat(100));
+        assert_eq!(
+            extended_length_path(&escaping).unwrap(),
+            r"\\?\UNC\nas\games"
+        );
+    }
+
+    #[test]
+    fn detects_network_shares() {
+        assert!(is_unc_path(Path::new(r"\\nas\games\instance")));
+        assert!(is_unc_path(Path::new(r"\\?\UNC\nas\games\instance")));
+        assert!(!is_unc_path(Path::new(r"\\?\C:\games")));
+        assert!(!is_unc_path(Path::new(r"C:\games")));
+        assert!(!is_unc_path(Path::new("/home/me/games")));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn creates_files_below_a_300_character_path() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut nested = tmp.path().to_path_buf();
+        while nested.as_os_str().len() < 300 {
+            nested.push("overrides-nested-folder");
+        }
+        let file = nested.join("options.txt");
+
+        std::fs::create_dir_all(long_path(&nested)).unwrap();
+        std::fs::write(long_path(&file), b"fov:70").unwrap();
+
+        assert_eq!(std::fs::read(long_path(&file)).unwrap(), b"fov:70");
+        assert!(long_path(&file).to_string_lossy().starts_with(r"\\?\"));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn join_validated_returns_openable_long_paths() {
+        let tmp = tempfile::tempdir().unwrap();
+        let relative = "overrides-nested-folder/".repeat(14) + "options.txt";
+        let joined = join_validated(tmp.path(), &relative).unwrap();
+        assert!(joined.to_string_lossy().starts_with(r"\\?\"));
+
+        std::fs::create_dir_all(joined.parent().unwrap()).unwrap();
+        std::fs::write(&joined, b"fov:70").unwrap();
+        assert!(joined.exists());
+    }
 }
@@ -0,0 +1,233 @@
+use piston_lib::game::installer::modloaders::patches::{
+    extract_installer_patches, legacy_installer_coords, library_key, ForgePatches,
+    INSTALLER_VERSION_PATCH,
+};
+use piston_lib::game::installer::types::{InstallSpec, ModloaderType, OsType};
+use piston_lib::game::launcher::unified_manifest::UnifiedManifest;
+use piston_lib::game::launcher::version_parser::{
+    Artifact, Library, LibraryDownloads, VersionManifest,
+};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+fn installer_fixture_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/neoforge-1.20.1/installer")
+}
+
+/// Pack the installer fixture into a jar, as the NeoForged maven serves it.
+fn write_installer_jar(dir: &Path) -> PathBuf {
+    fn add_dir(zip: &mut zip::ZipWriter<std::fs::File>, root: &Path, dir: &Path) {
+        let mut entries: Vec<_> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        entries.sort();
+        for path in entries {
+            if path.is_dir() {
+                add_dir(zip, root, &path);
+                continue;
+            }
+            let name = path
+                .strip_prefix(root)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/");
+            zip.start_file::<_, ()>(name, zip::write::FileOptions::default())
+                .unwrap();
+            zip.write_all(&std::fs::read(&path).unwrap()).unwrap();
+        }
+    }
+
+    let jar = dir.join("forge-1.20.1-47.1.106-installer.jar");
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&jar).unwrap());
+    add_dir(&mut zip, &installer_fixture_dir(), &installer_fixture_dir());
+    zip.finish().unwrap();
+    jar
+}
+
+/// Patches as an install leaves them in `versions/<id>/patches`.
+async fn installed_patches() -> (tempfile::TempDir, ForgePatches) {
+    let dir = tempfile::tempdir().unwrap();
+    let jar = write_installer_jar(dir.path());
+    let patches_dir = dir.path().join("patches");
+    extract_installer_patches(&jar, &patches_dir).unwrap();
+    let patches = ForgePatches::load(&patches_dir).await.unwrap();
+    (dir, patches)
+}
+
+fn library(name: &str, path: &str) -> Library {
+    Library {
+        name: name.to_string(),
+        downloads: Some(LibraryDownloads {
+            artifact: Some(Artifact {
+                path: Some(path.to_string()),
+                url: Some(format!("https://maven.neoforged.net/releases/{}", path)),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        include_in_classpath: true,
+        ..Default::default()
+    }
+}
+
+fn loader_manifest() -> VersionManifest {
+    VersionManifest {
+        id: "neoforge-47.1.106".to_string(),
+        inherits_from: Some("1.20.1".to_string()),
+        main_class: Some("cpw.mods.bootstraplauncher.BootstrapLauncher".to_string()),
+        libraries: vec![
+            library("org.ow2.asm:asm:9.3", "org/ow2/asm/asm/9.3/asm-9.3.jar"),
+            library(
+                "net.neoforged:forge:1.20.1-47.1.106:universal",
+                "net/neoforged/forge/1.20.1-47.1.106/forge-1.20.1-47.1.106-universal.jar",
+            ),
+        ],
+        ..VersionManifest::default()
+    }
+}
+
+#[tokio::test]
+async fn installer_patches_are_persisted_in_application_order() {
+    let (dir, patches) = installed_patches().await;
+
+    let files: Vec<&str> = patches
+        .patches
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    assert_eq!(files, [INSTALLER_VERSION_PATCH, "10-libraries.json"]);
+    assert_eq!(
+        patches.patches[0].1.id.as_deref(),
+        Some("1.20.1-forge-47.1.106")
+    );
+
+    // install_profile.json libraries only feed the processors.
+    let persisted: Vec<&str> = patches
+        .patches
+        .iter()
+        .flat_map(|(_, patch)| patch.libraries.iter().map(|l| l.name.as_str()))
+        .collect();
+    assert!(!persisted.contains(&"net.minecraftforge:installertools:1.3.0"));
+
+    // Re-extracting replaces stale patches instead of stacking on them.
+    std::fs::write(dir.path().join("patches/99-stale.json"), "{}").unwrap();
+    let jar = dir.path().join("forge-1.20.1-47.1.106-installer.jar");
+    let written = extract_installer_patches(&jar, &dir.path().join("patches")).unwrap();
+    assert_eq!(written.len(), 2);
+    assert!(!dir.path().join("patches/99-stale.json").exists());
+}
+
+#[test]
+fn installer_without_profile_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let jar = dir.path().join("broken-installer.jar");
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&jar).unwrap());
+    zip.start_file::<_, ()>("version.json", zip::write::FileOptions::default())
+        .unwrap();
+    zip.write_all(b"{}").unwrap();
+    zip.finish().unwrap();
+
+    let patches_dir = dir.path().join("patches");
+    let err = extract_installer_patches(&jar, &patches_dir).unwrap_err();
+    assert!(format!("{:#}", err).contains("install_profile.json"));
+    assert!(!patches_dir.exists());
+}
+
+#[test]
+fn only_legacy_neoforge_has_installer_patches() {
+    let spec = |modloader, version_id: &str, loader: &str| {
+        let mut spec = InstallSpec::new(version_id.to_string(), PathBuf::new(), PathBuf::new());
+        spec.modloader = Some(modloader);
+        spec.modloader_version = Some(loader.to_string());
+        spec
+    };
+
+    assert_eq!(
+        legacy_installer_coords(&spec(ModloaderType::NeoForge, "1.20.1", "47.1.106")).as_deref(),
+        Some("net.neoforged:forge:1.20.1-47.1.106:installer")
+    );
+    assert_eq!(
+        legacy_installer_coords(&spec(ModloaderType::NeoForge, "1.20.1", "1.20.1-47.1.106"))
+            .as_deref(),
+        Some("net.neoforged:forge:1.20.1-47.1.106:installer")
+    );
+    assert!(legacy_installer_coords(&spec(ModloaderType::NeoForge, "1.21.1", "21.1.77")).is_none());
+    assert!(legacy_installer_coords(&spec(ModloaderType::Forge, "1.20.1", "47.2.0")).is_none());
+}
+
+#[tokio::test]
+async fn later_patches_override_libraries() {
+    let (_dir, patches) = installed_patches().await;
+    let mut manifest = loader_manifest();
+
+    let changed = patches.apply(&mut manifest);
+
+    let names: Vec<&str> = manifest.libraries.iter().map(|l| l.name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            // 10-libraries.json wins over the installer's version json and
+            // replaces asm in place.
+            "org.ow2.asm:asm:9.7",
+            "net.neoforged:forge:1.20.1-47.1.106:universal",
+            "cpw.mods:securejarhandler:2.1.10",
+            "net.minecraftforge:JarJarFileSystems:0.3.19",
+            "net.neoforged:mergetool:1.1.5:api",
+        ]
+    );
+    assert_eq!(changed, 4);
+    let asm_keys = manifest
+        .libraries
+        .iter()
+        .filter(|l| library_key(&l.name) == "org.ow2.asm:asm")
+        .count();
+    assert_eq!(asm_keys, 1);
+
+    // Applying the same patches again changes nothing.
+    assert_eq!(patches.apply(&mut manifest), 0);
+}
+
+#[tokio::test]
+async fn patched_libraries_are_scheduled_for_download() {
+    let (_dir, patches) = installed_patches().await;
+    let mut manifest = loader_manifest();
+    patches.apply(&mut manifest);
+
+    let vanilla = VersionManifest {
+        id: "1.20.1".to_string(),
+        main_class: Some("net.minecraft.client.main.Main".to_string()),
+        ..VersionManifest::default()
+    };
+    let unified = UnifiedManifest::merge(vanilla, Some(manifest), OsType::current());
+
+    let paths: Vec<&str> = unified.libraries.iter().map(|l| l.path.as_str()).collect();
+    for patch_path in [
+        "org/ow2/asm/asm/9.7/asm-9.7.jar",
+        "cpw/mods/securejarhandler/2.1.10/securejarhandler-2.1.10.jar",
+        "net/minecraftforge/JarJarFileSystems/0.3.19/JarJarFileSystems-0.3.19.jar",
+        "net/neoforged/mergetool/1.1.5/mergetool-1.1.5-api.jar",
+    ] {
+        assert!(
+            paths.contains(&patch_path),
+            "{} missing from {:?}",
+            patch_path,
+            paths
+        );
+    }
+    assert!(!paths.contains(&"org/ow2/asm/asm/9.3/asm-9.3.jar"));
+    assert!(unified
+        .libraries
+        .iter()
+        .filter(|l| l.path.ends_with("asm-9.7.jar"))
+        .all(|l| l.download_url.is_some()));
+}
+
+#[tokio::test]
+async fn missing_patch_directory_is_empty() {
+    let dir = tempfile::tempdir().unwrap();
+    let patches = ForgePatches::load(&dir.path().join("patches"))
+        .await
+        .unwrap();
+    assert!(patches.is_empty());
+}
@@ -1,5 +1,7 @@
 use piston_lib::game::installer::types::OsType;
-use piston_lib::game::launcher::unified_manifest::UnifiedManifest;
+use piston_lib::game::launcher::unified_manifest::{
+    generate_unified_manifest_for_os, unified_manifest_path, ManifestSourceHash, UnifiedManifest,
+};
 use piston_lib::game::launcher::version_parser::{
     Artifact, Library, LibraryDownloads, VersionManifest,
 };
@@ -146,3 +148,72 @@ fn test_arch_rule_matching() {
         "Library with x64 rule should match on x86_64 host"
     );
 }
+
+const FORGE_ID: &str = "1.20.1-forge-47.2.0";
+
+fn forge_chain_fixture() -> std::path::PathBuf {
+    std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/forge-1.20.1-chain")
+}
+
+fn install_forge_chain(data_dir: &std::path::Path) {
+    for id in ["1.20.1", FORGE_ID] {
+        let file = format!("{}.json", id);
+        let target = data_dir.join("versions").join(id);
+        std::fs::create_dir_all(&target).unwrap();
+        std::fs::copy(
+            forge_chain_fixture().join("versions").join(id).join(&file),
+            target.join(&file),
+        )
+        .unwrap();
+    }
+}
+
+fn read_json(path: &std::path::Path) -> serde_json::Value {
+    serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap()
+}
+
+#[test]
+fn forge_chain_flattens_to_golden_unified_manifest() {
+    let dir = tempfile::tempdir().unwrap();
+    install_forge_chain(dir.path());
+
+    let manifest = generate_unified_manifest_for_os(dir.path(), FORGE_ID, OsType::Linux).unwrap();
+    assert_eq!(manifest.id, FORGE_ID);
+    assert_eq!(manifest.minecraft_version, "1.20.1");
+
+    let mut written = read_json(&unified_manifest_path(dir.path(), FORGE_ID));
+    let sources: Vec<ManifestSourceHash> =
+        serde_json::from_value(written.as_object_mut().unwrap().remove("sources").unwrap())
+            .unwrap();
+    let source_ids: Vec<&str> = sources.iter().map(|source| source.id.as_str()).collect();
+    assert_eq!(source_ids, vec![FORGE_ID, "1.20.1"]);
+
+    let golden = read_json(&forge_chain_fixture().join(format!("{}.unified.json", FORGE_ID)));
+    assert_eq!(written, golden);
+
+    // The flattened file still reads as a plain unified manifest.
+    let reloaded =
+        UnifiedManifest::load_from_path(&unified_manifest_path(dir.path(), FORGE_ID)).unwrap();
+    assert_eq!(reloaded.libraries.len(), manifest.libraries.len());
+}
+
+#[test]
+fn unified_manifest_regenerates_when_a_parent_json_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    install_forge_chain(dir.path());
+    let unified_path = unified_manifest_path(dir.path(), FORGE_ID);
+
+    generate_unified_manifest_for_os(dir.path(), FORGE_ID, OsType::Linux).unwrap();
+    let first = std::fs::read(&unified_path).unwrap();
+    generate_unified_manifest_for_os(dir.path(), FORGE_ID, OsType::Linux).unwrap();
+    assert_eq!(std::fs::read(&unified_path).unwrap(), first);
+
+    let parent_path = dir.path().join("versions/1.20.1/1.20.1.json");
+    let mut parent = read_json(&parent_path);
+    parent["javaVersion"]["majorVersion"] = serde_json::json!(21);
+    std::fs::write(&parent_path, serde_json::to_vec_pretty(&parent).unwrap()).unwrap();
+
+    let manifest = generate_unified_manifest_for_os(dir.path(), FORGE_ID, OsType::Linux).unwrap();
+    assert_eq!(manifest.java_version.unwrap().major_version, 21);
+    assert_ne!(std::fs::read(&unified_path).unwrap(), first);
+}
@@ -0,0 +1,2 @@
+ALTER TABLE app_config
+DROP COLUMN instance_trash_retention_days;
@@ -0,0 +1,2 @@
+ALTER TABLE app_config
+ADD COLUMN instance_trash_retention_days INTEGER NOT NULL DEFAULT 30;
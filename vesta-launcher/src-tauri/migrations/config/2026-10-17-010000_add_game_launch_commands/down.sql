@@ -0,0 +1,5 @@
+ALTER TABLE app_config
+DROP COLUMN game_launch_post_command;
+
+ALTER TABLE app_config
+DROP COLUMN game_launch_pre_command;
@@ -0,0 +1,5 @@
+ALTER TABLE app_config
+ADD COLUMN game_launch_pre_command TEXT;
+
+ALTER TABLE app_config
+ADD COLUMN game_launch_post_command TEXT;
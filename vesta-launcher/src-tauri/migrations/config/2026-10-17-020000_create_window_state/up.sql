@@ -0,0 +1,11 @@
+-- Per-window geometry, keyed by window label (reusable page viewers share one key)
+CREATE TABLE window_state (
+    label TEXT PRIMARY KEY NOT NULL,
+    x INTEGER NOT NULL,
+    y INTEGER NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    maximized BOOLEAN NOT NULL DEFAULT 0,
+    monitor_name TEXT,
+    updated_at TEXT NOT NULL
+);
@@ -0,0 +1,2 @@
+ALTER TABLE app_config
+ADD COLUMN update_channel TEXT NOT NULL DEFAULT 'stable';
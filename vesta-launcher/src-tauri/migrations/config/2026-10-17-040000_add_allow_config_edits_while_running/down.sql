@@ -0,0 +1,2 @@
+ALTER TABLE app_config
+DROP COLUMN allow_config_edits_while_running;
@@ -0,0 +1,2 @@
+ALTER TABLE app_config
+ADD COLUMN allow_config_edits_while_running BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+ALTER TABLE app_config
+ADD COLUMN cache_compression_enabled BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+ALTER TABLE app_config
+DROP COLUMN verify_on_launch;
@@ -0,0 +1,2 @@
+ALTER TABLE app_config
+ADD COLUMN verify_on_launch BOOLEAN NOT NULL DEFAULT 0;
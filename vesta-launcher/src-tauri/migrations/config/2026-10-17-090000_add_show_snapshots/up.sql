@@ -0,0 +1,2 @@
+ALTER TABLE app_config
+ADD COLUMN show_snapshots BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1,52 @@
+-- Saved theme presets. `payload` is the canonical theme JSON (the same shape
+-- as app_config.theme_data); builtin rows mirror the launcher's preset themes.
+CREATE TABLE theme_preset (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    builtin BOOLEAN NOT NULL DEFAULT 0,
+    payload TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO theme_preset (id, name, builtin, payload) VALUES
+    ('vesta', 'Vesta', 1, '{"id":"vesta","name":"Vesta","description":"Signature teal to purple to orange gradient","primaryHue":180,"opacity":0,"grainStrength":30,"borderWidth":1,"style":"glass","gradientEnabled":true,"rotation":180,"gradientType":"linear","gradientHarmony":"triadic","allowHueChange":false,"allowStyleChange":false,"allowBorderChange":false,"backgroundOpacity":25,"windowEffect":"none","customCss":":root {\n\t\t\t\t--theme-bg-gradient: linear-gradient(180deg, hsl(180 100% 50%), hsl(280 100% 25%), hsl(35 100% 50%));\n\t\t\t}"}'),
+    ('solar', 'Solar', 1, '{"id":"solar","name":"Solar","primaryHue":40,"opacity":50,"grainStrength":65,"borderWidth":1,"style":"frosted","gradientEnabled":false,"gradientType":"linear","gradientHarmony":"none","allowHueChange":false,"allowStyleChange":false,"allowBorderChange":false,"backgroundOpacity":25,"windowEffect":"none"}'),
+    ('neon', 'Neon', 1, '{"id":"neon","name":"Neon","primaryHue":300,"opacity":0,"grainStrength":25,"borderWidth":1,"style":"glass","gradientEnabled":true,"rotation":135,"gradientType":"linear","gradientHarmony":"complementary","allowHueChange":false,"allowStyleChange":false,"allowBorderChange":false,"backgroundOpacity":25,"windowEffect":"none"}'),
+    ('classic', 'Classic', 1, '{"id":"classic","name":"Classic","primaryHue":210,"opacity":100,"grainStrength":0,"borderWidth":1,"style":"flat","gradientEnabled":false,"gradientType":"linear","gradientHarmony":"none","allowHueChange":true,"allowStyleChange":false,"allowBorderChange":false,"backgroundOpacity":25,"windowEffect":"none"}'),
+    ('forest', 'Forest', 1, '{"id":"forest","name":"Forest","primaryHue":140,"opacity":50,"grainStrength":58,"borderWidth":1,"style":"frosted","gradientEnabled":true,"rotation":90,"gradientType":"linear","gradientHarmony":"analogous","allowHueChange":false,"allowStyleChange":false,"allowBorderChange":false,"backgroundOpacity":25,"windowEffect":"none"}'),
+    ('sunset', 'Sunset', 1, '{"id":"sunset","name":"Sunset","primaryHue":270,"opacity":0,"grainStrength":26,"borderWidth":1,"style":"glass","gradientEnabled":true,"rotation":180,"gradientType":"linear","gradientHarmony":"triadic","allowHueChange":false,"allowStyleChange":false,"allowBorderChange":false,"backgroundOpacity":25,"windowEffect":"none"}'),
+    ('prism', 'Prism', 1, '{"id":"prism","name":"Prism","author":"Vesta Team","primaryHue":200,"opacity":20,"grainStrength":44,"borderWidth":1,"style":"glass","gradientEnabled":true,"rotation":45,"gradientType":"linear","gradientHarmony":"triadic","allowHueChange":true,"allowStyleChange":false,"allowBorderChange":false,"backgroundOpacity":25,"windowEffect":"none"}'),
+    ('midnight', 'Midnight', 1, '{"id":"midnight","name":"Midnight","primaryHue":240,"opacity":100,"grainStrength":0,"borderWidth":0,"style":"flat","gradientEnabled":false,"gradientType":"linear","gradientHarmony":"none","allowHueChange":true,"allowStyleChange":false,"allowBorderChange":false,"backgroundOpacity":25,"windowEffect":"none"}'),
+    ('oldschool', 'Old School', 1, '{"id":"oldschool","name":"Old School","primaryHue":210,"opacity":100,"grainStrength":0,"borderWidth":2,"style":"flat","gradientEnabled":false,"gradientType":"linear","gradientHarmony":"none","allowHueChange":true,"allowStyleChange":false,"allowBorderChange":false,"backgroundOpacity":25,"windowEffect":"none"}'),
+    ('custom', 'Custom', 1, '{"id":"custom","name":"Custom","primaryHue":220,"opacity":0,"grainStrength":40,"borderWidth":1,"style":"glass","gradientEnabled":true,"rotation":135,"gradientType":"linear","gradientHarmony":"none","allowHueChange":true,"allowStyleChange":true,"allowBorderChange":true,"backgroundOpacity":25,"windowEffect":"none"}');
+
+-- Keep the values of the editable "custom" theme as a "My theme" preset so
+-- switching presets no longer loses them. Imported themes keep their ids;
+-- they live in the vesta database's saved_themes table.
+INSERT INTO theme_preset (id, name, builtin, payload)
+SELECT
+    'my-theme',
+    'My theme',
+    0,
+    json_set(
+        CASE
+            WHEN json_valid(theme_data) AND json_type(theme_data) = 'object' THEN theme_data
+            ELSE json_object(
+                'primaryHue', theme_primary_hue,
+                'primarySat', theme_primary_sat,
+                'primaryLight', theme_primary_light,
+                'style', theme_style,
+                'gradientEnabled', json(CASE WHEN theme_gradient_enabled THEN 'true' ELSE 'false' END),
+                'rotation', theme_gradient_angle,
+                'gradientType', theme_gradient_type,
+                'gradientHarmony', theme_gradient_harmony,
+                'borderWidth', theme_border_width,
+                'backgroundOpacity', theme_background_opacity,
+                'windowEffect', theme_window_effect
+            )
+        END,
+        '$.id', 'my-theme',
+        '$.name', 'My theme'
+    )
+FROM app_config
+WHERE id = 1 AND theme_id = 'custom';
@@ -0,0 +1,2 @@
+ALTER TABLE app_config
+DROP COLUMN allow_large_downloads_on_metered;
@@ -0,0 +1,2 @@
+ALTER TABLE app_config
+ADD COLUMN allow_large_downloads_on_metered TEXT NOT NULL DEFAULT 'ask';
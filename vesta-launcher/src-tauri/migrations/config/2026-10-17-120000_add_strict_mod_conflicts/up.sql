@@ -0,0 +1,2 @@
+ALTER TABLE app_config
+ADD COLUMN strict_mod_conflicts BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1,8 @@
+ALTER TABLE app_config
+DROP COLUMN mod_download_concurrency;
+
+ALTER TABLE app_config
+DROP COLUMN library_concurrency;
+
+ALTER TABLE app_config
+DROP COLUMN asset_concurrency;
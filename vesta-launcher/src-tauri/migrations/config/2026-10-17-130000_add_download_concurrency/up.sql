@@ -0,0 +1,14 @@
+ALTER TABLE app_config
+ADD COLUMN asset_concurrency INTEGER NOT NULL DEFAULT 16;
+
+ALTER TABLE app_config
+ADD COLUMN library_concurrency INTEGER NOT NULL DEFAULT 8;
+
+ALTER TABLE app_config
+ADD COLUMN mod_download_concurrency INTEGER NOT NULL DEFAULT 4;
+
+-- Derive the per-phase limits from the existing download thread setting.
+UPDATE app_config
+SET asset_concurrency = MIN(MAX(max_download_threads, 1) * 4, 64),
+    library_concurrency = MIN(MAX(max_download_threads, 1) * 2, 64),
+    mod_download_concurrency = MIN(MAX(max_download_threads, 1), 64);
@@ -0,0 +1,2 @@
+ALTER TABLE app_config
+ADD COLUMN usage_stats_consent TEXT NOT NULL DEFAULT 'unset';
@@ -0,0 +1,32 @@
+ALTER TABLE app_config ADD COLUMN instance_trash_retention_days INTEGER NOT NULL DEFAULT 30;
+ALTER TABLE app_config ADD COLUMN game_launch_pre_command TEXT;
+ALTER TABLE app_config ADD COLUMN game_launch_post_command TEXT;
+ALTER TABLE app_config ADD COLUMN update_channel TEXT NOT NULL DEFAULT 'stable';
+ALTER TABLE app_config ADD COLUMN allow_config_edits_while_running BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE app_config ADD COLUMN cache_compression_enabled BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE app_config ADD COLUMN verify_on_launch BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE app_config ADD COLUMN show_snapshots BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE app_config ADD COLUMN allow_large_downloads_on_metered TEXT NOT NULL DEFAULT 'ask';
+ALTER TABLE app_config ADD COLUMN strict_mod_conflicts BOOLEAN NOT NULL DEFAULT 0;
+ALTER TABLE app_config ADD COLUMN asset_concurrency INTEGER NOT NULL DEFAULT 16;
+ALTER TABLE app_config ADD COLUMN library_concurrency INTEGER NOT NULL DEFAULT 8;
+ALTER TABLE app_config ADD COLUMN mod_download_concurrency INTEGER NOT NULL DEFAULT 4;
+ALTER TABLE app_config ADD COLUMN usage_stats_consent TEXT NOT NULL DEFAULT 'unset';
+
+UPDATE app_config SET
+    instance_trash_retention_days = COALESCE((SELECT CAST(value AS INTEGER) FROM app_setting WHERE key = 'instance_trash_retention_days'), instance_trash_retention_days),
+    game_launch_pre_command = (SELECT value ->> '$' FROM app_setting WHERE key = 'game_launch_pre_command'),
+    game_launch_post_command = (SELECT value ->> '$' FROM app_setting WHERE key = 'game_launch_post_command'),
+    update_channel = COALESCE((SELECT value ->> '$' FROM app_setting WHERE key = 'update_channel'), update_channel),
+    allow_config_edits_while_running = COALESCE((SELECT value = 'true' FROM app_setting WHERE key = 'allow_config_edits_while_running'), allow_config_edits_while_running),
+    cache_compression_enabled = COALESCE((SELECT value = 'true' FROM app_setting WHERE key = 'cache_compression_enabled'), cache_compression_enabled),
+    verify_on_launch = COALESCE((SELECT value = 'true' FROM app_setting WHERE key = 'verify_on_launch'), verify_on_launch),
+    show_snapshots = COALESCE((SELECT value = 'true' FROM app_setting WHERE key = 'show_snapshots'), show_snapshots),
+    allow_large_downloads_on_metered = COALESCE((SELECT value ->> '$' FROM app_setting WHERE key = 'allow_large_downloads_on_metered'), allow_large_downloads_on_metered),
+    strict_mod_conflicts = COALESCE((SELECT value = 'true' FROM app_setting WHERE key = 'strict_mod_conflicts'), strict_mod_conflicts),
+    asset_concurrency = COALESCE((SELECT CAST(value AS INTEGER) FROM app_setting WHERE key = 'asset_concurrency'), asset_concurrency),
+    library_concurrency = COALESCE((SELECT CAST(value AS INTEGER) FROM app_setting WHERE key = 'library_concurrency'), library_concurrency),
+    mod_download_concurrency = COALESCE((SELECT CAST(value AS INTEGER) FROM app_setting WHERE key = 'mod_download_concurrency'), mod_download_concurrency),
+    usage_stats_consent = COALESCE((SELECT value ->> '$' FROM app_setting WHERE key = 'usage_stats_consent'), usage_stats_consent);
+
+DROP TABLE app_setting;
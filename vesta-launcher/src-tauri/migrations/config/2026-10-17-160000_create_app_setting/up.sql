@@ -0,0 +1,37 @@
+-- app_config must stay within diesel's 64-column table limit, so settings added
+-- after the proxy/cache work live in a key/value table instead. Values are JSON.
+CREATE TABLE app_setting (
+    key TEXT PRIMARY KEY NOT NULL,
+    value TEXT NOT NULL
+);
+
+INSERT INTO app_setting (key, value)
+SELECT 'instance_trash_retention_days', CAST(instance_trash_retention_days AS TEXT) FROM app_config WHERE id = 1
+UNION ALL SELECT 'game_launch_pre_command', json_quote(game_launch_pre_command) FROM app_config WHERE id = 1
+UNION ALL SELECT 'game_launch_post_command', json_quote(game_launch_post_command) FROM app_config WHERE id = 1
+UNION ALL SELECT 'update_channel', json_quote(update_channel) FROM app_config WHERE id = 1
+UNION ALL SELECT 'allow_config_edits_while_running', CASE WHEN allow_config_edits_while_running THEN 'true' ELSE 'false' END FROM app_config WHERE id = 1
+UNION ALL SELECT 'cache_compression_enabled', CASE WHEN cache_compression_enabled THEN 'true' ELSE 'false' END FROM app_config WHERE id = 1
+UNION ALL SELECT 'verify_on_launch', CASE WHEN verify_on_launch THEN 'true' ELSE 'false' END FROM app_config WHERE id = 1
+UNION ALL SELECT 'show_snapshots', CASE WHEN show_snapshots THEN 'true' ELSE 'false' END FROM app_config WHERE id = 1
+UNION ALL SELECT 'allow_large_downloads_on_metered', json_quote(allow_large_downloads_on_metered) FROM app_config WHERE id = 1
+UNION ALL SELECT 'strict_mod_conflicts', CASE WHEN strict_mod_conflicts THEN 'true' ELSE 'false' END FROM app_config WHERE id = 1
+UNION ALL SELECT 'asset_concurrency', CAST(asset_concurrency AS TEXT) FROM app_config WHERE id = 1
+UNION ALL SELECT 'library_concurrency', CAST(library_concurrency AS TEXT) FROM app_config WHERE id = 1
+UNION ALL SELECT 'mod_download_concurrency', CAST(mod_download_concurrency AS TEXT) FROM app_config WHERE id = 1
+UNION ALL SELECT 'usage_stats_consent', json_quote(usage_stats_consent) FROM app_config WHERE id = 1;
+
+ALTER TABLE app_config DROP COLUMN instance_trash_retention_days;
+ALTER TABLE app_config DROP COLUMN game_launch_pre_command;
+ALTER TABLE app_config DROP COLUMN game_launch_post_command;
+ALTER TABLE app_config DROP COLUMN update_channel;
+ALTER TABLE app_config DROP COLUMN allow_config_edits_while_running;
+ALTER TABLE app_config DROP COLUMN cache_compression_enabled;
+ALTER TABLE app_config DROP COLUMN verify_on_launch;
+ALTER TABLE app_config DROP COLUMN show_snapshots;
+ALTER TABLE app_config DROP COLUMN allow_large_downloads_on_metered;
+ALTER TABLE app_config DROP COLUMN strict_mod_conflicts;
+ALTER TABLE app_config DROP COLUMN asset_concurrency;
+ALTER TABLE app_config DROP COLUMN library_concurrency;
+ALTER TABLE app_config DROP COLUMN mod_download_concurrency;
+ALTER TABLE app_config DROP COLUMN usage_stats_consent;
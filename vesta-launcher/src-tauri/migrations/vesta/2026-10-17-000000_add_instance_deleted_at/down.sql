@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_instance_deleted_at;
+
+ALTER TABLE instance DROP COLUMN deleted_at;
@@ -0,0 +1,3 @@
+ALTER TABLE instance ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX idx_instance_deleted_at ON instance (deleted_at);
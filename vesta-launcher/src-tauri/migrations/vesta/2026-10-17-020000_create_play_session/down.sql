@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_play_session_instance_id;
+DROP TABLE play_session;
@@ -0,0 +1,12 @@
+CREATE TABLE play_session (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    instance_id INTEGER NOT NULL,
+    started_at TEXT NOT NULL,
+    ended_at TEXT NOT NULL,
+    exit_code INTEGER,
+    crashed BOOLEAN NOT NULL DEFAULT 0,
+    safe_mode BOOLEAN NOT NULL DEFAULT 0,
+    FOREIGN KEY (instance_id) REFERENCES instance(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_play_session_instance_id ON play_session(instance_id, ended_at);
@@ -0,0 +1 @@
+ALTER TABLE instance DROP COLUMN instance_source_override;
@@ -0,0 +1 @@
+ALTER TABLE instance ADD COLUMN instance_source_override TEXT;
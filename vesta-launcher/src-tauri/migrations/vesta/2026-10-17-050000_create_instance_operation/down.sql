@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_instance_operation_instance_id;
+DROP TABLE instance_operation;
@@ -0,0 +1,13 @@
+CREATE TABLE instance_operation (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    instance_id INTEGER NOT NULL,
+    kind TEXT NOT NULL,
+    started_at TEXT NOT NULL,
+    finished_at TEXT,
+    success BOOLEAN,
+    error_code TEXT,
+    details TEXT NOT NULL DEFAULT '{}',
+    FOREIGN KEY (instance_id) REFERENCES instance(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_instance_operation_instance_id ON instance_operation(instance_id, started_at);
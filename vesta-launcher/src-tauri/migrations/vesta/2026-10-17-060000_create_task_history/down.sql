@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_task_history_finished_at;
+DROP TABLE task_history;
@@ -0,0 +1,14 @@
+CREATE TABLE task_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    client_key TEXT NOT NULL,
+    name TEXT NOT NULL,
+    status TEXT NOT NULL,
+    priority TEXT NOT NULL DEFAULT 'normal',
+    queued_at TEXT NOT NULL,
+    started_at TEXT,
+    finished_at TEXT NOT NULL,
+    duration_ms BIGINT,
+    error TEXT
+);
+
+CREATE INDEX idx_task_history_finished_at ON task_history(finished_at);
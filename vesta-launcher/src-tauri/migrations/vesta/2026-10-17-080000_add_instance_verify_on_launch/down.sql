@@ -0,0 +1 @@
+ALTER TABLE instance DROP COLUMN verify_on_launch;
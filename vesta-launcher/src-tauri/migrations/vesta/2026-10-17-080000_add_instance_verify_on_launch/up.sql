@@ -0,0 +1 @@
+ALTER TABLE instance ADD COLUMN verify_on_launch BOOLEAN;
@@ -0,0 +1,5 @@
+DROP TRIGGER IF EXISTS instance_ad_fts;
+DROP TRIGGER IF EXISTS instance_au_fts;
+DROP TRIGGER IF EXISTS instance_ai_fts;
+DROP TABLE IF EXISTS instance_fts;
+ALTER TABLE instance DROP COLUMN notes;
@@ -0,0 +1,21 @@
+ALTER TABLE instance ADD COLUMN notes TEXT;
+
+CREATE VIRTUAL TABLE instance_fts USING fts5(id UNINDEXED, name, notes);
+
+INSERT INTO instance_fts (rowid, id, name, notes)
+SELECT id, id, name, notes FROM instance;
+
+CREATE TRIGGER instance_ai_fts AFTER INSERT ON instance BEGIN
+    INSERT INTO instance_fts (rowid, id, name, notes)
+    VALUES (new.id, new.id, new.name, new.notes);
+END;
+
+CREATE TRIGGER instance_au_fts AFTER UPDATE OF id, name, notes ON instance BEGIN
+    DELETE FROM instance_fts WHERE rowid = old.id;
+    INSERT INTO instance_fts (rowid, id, name, notes)
+    VALUES (new.id, new.id, new.name, new.notes);
+END;
+
+CREATE TRIGGER instance_ad_fts AFTER DELETE ON instance BEGIN
+    DELETE FROM instance_fts WHERE rowid = old.id;
+END;
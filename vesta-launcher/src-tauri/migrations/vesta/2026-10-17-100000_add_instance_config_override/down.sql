@@ -0,0 +1 @@
+ALTER TABLE instance DROP COLUMN config_override_json;
@@ -0,0 +1 @@
+ALTER TABLE instance ADD COLUMN config_override_json TEXT;
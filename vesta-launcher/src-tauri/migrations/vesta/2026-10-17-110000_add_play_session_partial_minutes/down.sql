@@ -0,0 +1 @@
+ALTER TABLE play_session DROP COLUMN partial_minutes;
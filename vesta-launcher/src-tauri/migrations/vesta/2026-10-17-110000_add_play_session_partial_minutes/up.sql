@@ -0,0 +1,3 @@
+-- Minutes credited so far for a session that is still running; NULL once
+-- the session is finalized.
+ALTER TABLE play_session ADD COLUMN partial_minutes INTEGER;
@@ -0,0 +1,2 @@
+DROP TABLE download_stats;
+ALTER TABLE task_history DROP COLUMN bytes_downloaded;
@@ -0,0 +1,10 @@
+-- Bytes downloaded while the task ran.
+ALTER TABLE task_history ADD COLUMN bytes_downloaded BIGINT NOT NULL DEFAULT 0;
+
+-- Downloaded bytes per calendar month (UTC, "YYYY-MM") and category.
+CREATE TABLE download_stats (
+    month TEXT NOT NULL,
+    category TEXT NOT NULL,
+    bytes BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (month, category)
+);
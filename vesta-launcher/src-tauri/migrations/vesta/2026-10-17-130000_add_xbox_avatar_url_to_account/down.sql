@@ -0,0 +1 @@
+ALTER TABLE account DROP COLUMN xbox_avatar_url;
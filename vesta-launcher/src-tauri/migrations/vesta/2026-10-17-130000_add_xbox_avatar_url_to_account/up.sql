@@ -0,0 +1 @@
+ALTER TABLE account ADD COLUMN xbox_avatar_url TEXT;
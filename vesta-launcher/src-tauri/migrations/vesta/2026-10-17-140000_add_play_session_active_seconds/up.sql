@@ -0,0 +1,2 @@
+-- Seconds the game window had focus; NULL when focus could not be tracked.
+ALTER TABLE play_session ADD COLUMN active_seconds INTEGER;
@@ -0,0 +1,10 @@
+-- Coarse local events for the user's own diagnostics. Never sent anywhere.
+CREATE TABLE event_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    kind TEXT NOT NULL,
+    instance_id INTEGER,
+    error_code TEXT,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_event_log_created_at ON event_log (created_at);
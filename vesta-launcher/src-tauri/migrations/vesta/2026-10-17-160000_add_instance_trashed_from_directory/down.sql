@@ -0,0 +1 @@
+ALTER TABLE instance DROP COLUMN trashed_from_directory;
@@ -0,0 +1 @@
+ALTER TABLE instance ADD COLUMN trashed_from_directory TEXT;
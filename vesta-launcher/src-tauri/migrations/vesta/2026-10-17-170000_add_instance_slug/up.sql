@@ -0,0 +1,2 @@
+-- NULL keeps the legacy name mapping for instances created before this column.
+ALTER TABLE instance ADD COLUMN slug TEXT;
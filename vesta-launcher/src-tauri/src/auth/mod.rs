@@ -1,6 +1,7 @@
 //! Authentication module for Microsoft OAuth and Minecraft login
 //!
-//! Handles device-code authentication flow, token management, and account persistence.
+//! Handles device-code and browser authentication flows, token management,
+//! and account persistence.
 
 pub mod notification_actions;
 
@@ -41,6 +42,29 @@ lazy_static! {
     static ref CANCEL_SENDER: Arc<Mutex<Option<oneshot::Sender<()>>>> = Arc::new(Mutex::new(None));
     static ref PROFILE_CACHE: Arc<tokio::sync::Mutex<HashMap<String, CachedProfileEntry>>> = Arc::new(tokio::sync::Mutex::new(HashMap::new()));
     static ref PROFILE_FETCH_LOCKS: Arc<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> = Arc::new(Mutex::new(HashMap::new()));
+    /// Serializes account switches so the active flag and config always agree
+    static ref ACCOUNT_SWITCH_LOCK: Mutex<()> = Mutex::new(());
+    /// Accounts whose last token refresh failed; cleared on the next success.
+    /// Kept in memory only, the account row stays untouched.
+    static ref REFRESH_FAILED_ACCOUNTS: Mutex<std::collections::HashSet<String>> = Mutex::new(std::collections::HashSet::new());
+}
+
+fn set_refresh_failed(account_uuid: &str, failed: bool) {
+    if let Ok(mut failed_accounts) = REFRESH_FAILED_ACCOUNTS.lock() {
+        if failed {
+            failed_accounts.insert(account_uuid.to_string());
+        } else {
+            failed_accounts.remove(account_uuid);
+        }
+    }
+}
+
+/// Whether the account needs a re-login because its last refresh failed
+pub fn refresh_failed(account_uuid: &str) -> bool {
+    REFRESH_FAILED_ACCOUNTS
+        .lock()
+        .map(|failed_accounts| failed_accounts.contains(&account_uuid.replace("-", "")))
+        .unwrap_or(false)
 }
 
 fn get_profile_fetch_lock(account_uuid: &str) -> Result<Arc<tokio::sync::Mutex<()>>, String> {
@@ -70,6 +94,38 @@ pub async fn invalidate_account_profile_cache(account_uuid: &str) {
     cache.remove(&normalized_uuid);
 }
 
+/// Payload of `core://account-changed`, emitted after the active account switches.
+#[derive(Clone, Serialize, Debug)]
+pub struct AccountSummary {
+    pub uuid: String,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub account_type: String,
+    pub skin_url: Option<String>,
+    pub is_expired: bool,
+}
+
+impl From<&Account> for AccountSummary {
+    fn from(acct: &Account) -> Self {
+        Self {
+            uuid: acct.uuid.clone(),
+            username: acct.username.clone(),
+            display_name: acct.display_name.clone(),
+            account_type: acct.account_type.clone(),
+            skin_url: acct.skin_url.clone(),
+            is_expired: acct.is_expired,
+        }
+    }
+}
+
+/// Account as listed to the UI, with its transient refresh state
+#[derive(Serialize, Debug, Clone)]
+pub struct AccountView {
+    #[serde(flatten)]
+    pub account: Account,
+    pub refresh_failed: bool,
+}
+
 /// Authentication stage events emitted to UI
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(tag = "stage")]
@@ -81,6 +137,10 @@ pub enum AuthStage {
         expires_in: u64,
     },
     Polling,
+    /// Browser login: the authorize page was opened (or should be, at `url`).
+    BrowserOpened {
+        url: String,
+    },
     Complete {
         user_uuid: String,
         user_username: String,
@@ -91,9 +151,17 @@ pub enum AuthStage {
     },
 }
 
-/// Start Microsoft OAuth device-code login flow
+/// Start Microsoft OAuth login. `login_method` is "device_code" (default) or
+/// "browser", the authorization-code fallback for networks that block the
+/// device-code endpoint.
 #[tauri::command]
-pub async fn start_login(app: AppHandle) -> Result<(), String> {
+pub async fn start_login(app: AppHandle, login_method: Option<String>) -> Result<(), String> {
+    let use_browser = match login_method.as_deref().unwrap_or("device_code") {
+        "device_code" => false,
+        "browser" => true,
+        other => return Err(format!("Unknown login method: {}", other)),
+    };
+
     // Create cancel channel
     let (tx, rx) = oneshot::channel::<()>();
     {
@@ -105,6 +173,15 @@ pub async fn start_login(app: AppHandle) -> Result<(), String> {
     app.emit("vesta://auth", AuthStage::Start)
         .map_err(|e| e.to_string())?;
 
+    if use_browser {
+        let app_clone = app.clone();
+        tokio::spawn(async move {
+            let result = browser_login_with_cancellation(app_clone.clone(), rx).await;
+            finish_login(app_clone, result).await;
+        });
+        return Ok(());
+    }
+
     // Get OAuth client
     let client = get_auth_client().map_err(|e| e.to_string())?;
 
@@ -135,47 +212,56 @@ pub async fn start_login(app: AppHandle) -> Result<(), String> {
     let app_clone = app.clone();
     tokio::spawn(async move {
         let result = poll_with_cancellation(client, device_code_response, rx).await;
+        finish_login(app_clone, result).await;
+    });
 
-        match result {
-            Ok(Some(token_response)) => {
-                // Exchange for Minecraft token and save account
-                match process_login_completion(app_clone.clone(), token_response).await {
-                    Ok((uuid_res, username_res)) => {
-                        let _ = app_clone.emit(
-                            "vesta://auth",
-                            AuthStage::Complete {
-                                user_uuid: uuid_res.clone(),
-                                user_username: username_res.clone(),
-                            },
-                        );
-                    }
-                    Err(e) => {
-                        let _ = app_clone.emit(
-                            "vesta://auth",
-                            AuthStage::Error {
-                                message: format!("Failed to complete login: {}", e),
-                            },
-                        );
-                    }
+    Ok(())
+}
+
+/// Emit the outcome of a login flow, saving the account on success.
+/// `Ok(None)` means the flow was cancelled.
+async fn finish_login(
+    app_handle: AppHandle,
+    result: Result<Option<oauth2::basic::BasicTokenResponse>>,
+) {
+    match result {
+        Ok(Some(token_response)) => {
+            // Exchange for Minecraft token and save account
+            match process_login_completion(app_handle.clone(), token_response).await {
+                Ok((uuid_res, username_res)) => {
+                    set_refresh_failed(&uuid_res.replace("-", ""), false);
+                    let _ = app_handle.emit(
+                        "vesta://auth",
+                        AuthStage::Complete {
+                            user_uuid: uuid_res.clone(),
+                            user_username: username_res.clone(),
+                        },
+                    );
+                }
+                Err(e) => {
+                    let _ = app_handle.emit(
+                        "vesta://auth",
+                        AuthStage::Error {
+                            message: format!("Failed to complete login: {}", e),
+                        },
+                    );
                 }
-            }
-            Ok(None) => {
-                // Cancelled
-                let _ = app_clone.emit("vesta://auth", AuthStage::Cancelled);
-            }
-            Err(e) => {
-                log::error!("[auth] Poll for token failed: {}", e);
-                let _ = app_clone.emit(
-                    "vesta://auth",
-                    AuthStage::Error {
-                        message: format!("Authentication failed: {}", e),
-                    },
-                );
             }
         }
-    });
-
-    Ok(())
+        Ok(None) => {
+            // Cancelled
+            let _ = app_handle.emit("vesta://auth", AuthStage::Cancelled);
+        }
+        Err(e) => {
+            log::error!("[auth] Login failed: {}", e);
+            let _ = app_handle.emit(
+                "vesta://auth",
+                AuthStage::Error {
+                    message: format!("Authentication failed: {}", e),
+                },
+            );
+        }
+    }
 }
 
 #[tauri::command]
@@ -356,6 +442,69 @@ pub fn cancel_login() -> Result<(), String> {
     Ok(())
 }
 
+/// Add an account from an existing Minecraft access token, skipping the
+/// device-code flow. Meant for CI and headless setups, so it only exists in
+/// debug builds.
+#[cfg(debug_assertions)]
+#[tauri::command]
+pub async fn add_account_from_token(
+    app_handle: AppHandle,
+    token: String,
+    refresh: String,
+    player_uuid: String,
+    player_name: String,
+) -> Result<(), String> {
+    let signed_in =
+        piston_lib::auth::sign_in_with_token(&token, &refresh, &player_uuid, &player_name)
+            .await
+            .map_err(|e| e.to_string())?;
+    let expires_at = signed_in
+        .expires_at
+        .and_then(|secs| chrono::DateTime::<Utc>::from_timestamp(secs, 0))
+        .unwrap_or_else(|| Utc::now() + Duration::hours(1));
+
+    let (account_uuid, _) = save_signed_in_account(
+        &app_handle,
+        signed_in.profile,
+        signed_in.access_token,
+        signed_in.refresh_token,
+        expires_at,
+        signed_in.xuid,
+        None,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    set_refresh_failed(&account_uuid, false);
+    Ok(())
+}
+
+/// Run the browser login until it completes, times out or is cancelled.
+/// Cancelling drops the login future, which closes its loopback listener.
+async fn browser_login_with_cancellation(
+    app_handle: AppHandle,
+    cancel_rx: oneshot::Receiver<()>,
+) -> Result<Option<oauth2::basic::BasicTokenResponse>> {
+    let open_browser = |url: &str| {
+        if let Err(e) = open::that(url) {
+            log::warn!("[auth] Failed to open the browser for login: {}", e);
+        }
+        let _ = app_handle.emit(
+            "vesta://auth",
+            AuthStage::BrowserOpened {
+                url: url.to_string(),
+            },
+        );
+    };
+
+    tokio::select! {
+        result = piston_lib::auth::start_browser_login(
+            open_browser,
+            piston_lib::auth::BROWSER_LOGIN_TIMEOUT,
+        ) => result.map(Some),
+        _ = cancel_rx => Ok(None),
+    }
+}
+
 /// Poll for token with cancellation support
 async fn poll_with_cancellation(
     client: oauth2::basic::BasicClient,
@@ -421,6 +570,67 @@ async fn process_login_completion(
         .await
         .context("Failed to exchange for Minecraft token")?;
 
+    let minecraft_access_token = minecraft_token.access_token().clone().into_inner();
+    let xuid_val = piston_lib::auth::xuid_from_minecraft_token(&minecraft_access_token);
+
+    // Fetch Minecraft profile
+    let profile = get_minecraft_profile(&minecraft_access_token)
+        .await
+        .context("Failed to fetch Minecraft profile")?;
+
+    // A missing gamerpic must not block sign-in.
+    let xbox_avatar_val = piston_lib::api::xbox::get_xbox_avatar_url(microsoft_access_token)
+        .await
+        .unwrap_or_else(|e| {
+            log::warn!("[auth] Failed to fetch Xbox avatar: {}", e);
+            None
+        });
+
+    save_signed_in_account(
+        &app_handle,
+        profile,
+        minecraft_access_token,
+        refresh_token_val,
+        token_expires_at_val,
+        xuid_val,
+        xbox_avatar_val,
+    )
+    .await
+}
+
+/// URL and model ("slim" or "classic") of the profile's active skin.
+fn active_skin_fields(
+    profile: &piston_lib::api::mojang::MinecraftProfile,
+) -> (Option<String>, String) {
+    let active_skin = profile
+        .skins
+        .iter()
+        .find(|skin| skin.state.eq_ignore_ascii_case("ACTIVE"))
+        .or_else(|| profile.skins.first());
+    let variant = active_skin
+        .map(|s| {
+            if s.variant.eq_ignore_ascii_case("slim") {
+                "slim"
+            } else {
+                "classic"
+            }
+        })
+        .unwrap_or("classic")
+        .to_string();
+    (active_skin.map(|s| s.url.clone()), variant)
+}
+
+/// Store a signed-in account, make it the active one and notify the UI.
+/// Returns the account's UUID and username.
+async fn save_signed_in_account(
+    app_handle: &AppHandle,
+    profile: piston_lib::api::mojang::MinecraftProfile,
+    minecraft_access_token: String,
+    refresh_token_val: String,
+    token_expires_at_val: chrono::DateTime<Utc>,
+    xuid_val: Option<String>,
+    xbox_avatar_val: Option<String>,
+) -> Result<(String, String)> {
     // --- Guest Mode Cleanup ---
     // If we were in guest mode, we want to clean up the marker and guest session data
     let app_data_dir = crate::utils::db_manager::get_app_config_dir().ok();
@@ -449,33 +659,10 @@ async fn process_login_completion(
     }
     // ---------------------------
 
-    let minecraft_access_token = minecraft_token.access_token().clone();
-    let minecraft_access_token_str = minecraft_access_token.clone().into_inner();
-
-    // Fetch Minecraft profile
-    let profile = get_minecraft_profile(&minecraft_access_token_str)
-        .await
-        .context("Failed to fetch Minecraft profile")?;
-
     // Normalize UUID
     let normalized_uuid = profile.id.replace("-", "");
 
-    let active_skin = profile
-        .skins
-        .iter()
-        .find(|skin| skin.state.eq_ignore_ascii_case("ACTIVE"))
-        .or_else(|| profile.skins.first());
-    let skin_url_val = active_skin.map(|s| s.url.clone());
-    let skin_variant_val = active_skin
-        .map(|s| {
-            if s.variant.eq_ignore_ascii_case("slim") {
-                "slim"
-            } else {
-                "classic"
-            }
-        })
-        .unwrap_or("classic")
-        .to_string();
+    let (skin_url_val, skin_variant_val) = active_skin_fields(&profile);
     let cape_url_val = profile.capes.first().map(|c| c.url.clone());
 
     log::info!(
@@ -516,7 +703,7 @@ async fn process_login_completion(
         new_account.uuid = normalized_uuid.clone();
         new_account.username = profile.name.clone();
         new_account.display_name = Some(profile.name.clone());
-        new_account.access_token = Some(minecraft_access_token.into_inner());
+        new_account.access_token = Some(minecraft_access_token);
         new_account.refresh_token = Some(refresh_token_val);
         new_account.token_expires_at = Some(token_expires_at_val.to_rfc3339());
         new_account.is_active = true;
@@ -530,6 +717,8 @@ async fn process_login_completion(
         new_account.theme_window_effect = current_config.theme_window_effect;
         new_account.theme_background_opacity = current_config.theme_background_opacity;
         new_account.account_type = "Microsoft".to_string();
+        new_account.xuid = xuid_val;
+        new_account.xbox_avatar_url = xbox_avatar_val;
 
         diesel::insert_into(account)
             .values(&new_account)
@@ -542,6 +731,12 @@ async fn process_login_completion(
             .as_ref()
             .and_then(|acct| acct.skin_url.as_deref());
         let skin_url_changed = existing_skin_url != skin_url_val.as_deref();
+        // Token sign-ins cannot look the avatar up; keep the stored one.
+        let next_xbox_avatar = xbox_avatar_val.or_else(|| {
+            existing_account
+                .as_ref()
+                .and_then(|acct| acct.xbox_avatar_url.clone())
+        });
         let next_skin_data = if skin_url_changed {
             None
         } else {
@@ -553,7 +748,7 @@ async fn process_login_completion(
             .set((
                 username.eq(&profile.name),
                 display_name.eq(&profile.name),
-                access_token.eq(Some(minecraft_access_token.into_inner())),
+                access_token.eq(Some(minecraft_access_token)),
                 refresh_token.eq(Some(refresh_token_val)),
                 token_expires_at.eq(Some(token_expires_at_val.to_rfc3339())),
                 skin_url.eq(skin_url_val),
@@ -567,6 +762,8 @@ async fn process_login_completion(
                 theme_data.eq(Some(resolved_theme_data.clone())),
                 theme_window_effect.eq(current_config.theme_window_effect),
                 theme_background_opacity.eq(current_config.theme_background_opacity),
+                xuid.eq(xuid_val),
+                xbox_avatar_url.eq(next_xbox_avatar),
             ))
             .execute(&mut conn)
             .map_err(|e| anyhow::anyhow!("Failed to update account: {}", e))?;
@@ -592,21 +789,27 @@ async fn process_login_completion(
 
     // Notify UI that accounts might have changed (added/updated). Force head
     // refresh because the same UUID can now point at a different skin URL.
-    emit_account_heads_updated(&app_handle, Some(&normalized_uuid), true);
+    emit_account_heads_updated(app_handle, Some(&normalized_uuid), true);
 
     Ok((normalized_uuid, profile.name))
 }
 
 /// Get all accounts from database
 #[tauri::command]
-pub fn get_accounts() -> Result<Vec<Account>, String> {
+pub fn get_accounts() -> Result<Vec<AccountView>, String> {
     let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
 
     let accounts = account
         .load::<Account>(&mut conn)
         .map_err(|e| e.to_string())?;
 
-    Ok(accounts)
+    Ok(accounts
+        .into_iter()
+        .map(|acct| AccountView {
+            refresh_failed: refresh_failed(&acct.uuid),
+            account: acct,
+        })
+        .collect())
 }
 
 /// Get active account (first active one found)
@@ -632,6 +835,16 @@ pub fn get_active_account() -> Result<Option<Account>, String> {
     }
 }
 
+/// Whether a token expiring at `expiry` (RFC 3339) should be refreshed at
+/// `now`. Missing or unparsable expiries count as expired, and tokens
+/// expiring within 60 seconds are refreshed early.
+pub(crate) fn token_needs_refresh(expiry: Option<&str>, now: chrono::DateTime<Utc>) -> bool {
+    match expiry.map(chrono::DateTime::parse_from_rfc3339) {
+        Some(Ok(datetime)) => datetime.with_timezone(&Utc) <= now + Duration::seconds(60),
+        _ => true,
+    }
+}
+
 /// Ensure account tokens are valid and refresh if they are near expiry
 pub async fn ensure_account_tokens_valid(
     app_handle: tauri::AppHandle,
@@ -684,22 +897,7 @@ pub async fn ensure_account_tokens_valid(
         None => return Ok(()),
     };
 
-    // Check expiry; if missing, assume token is expired and refresh
-    let now = Utc::now();
-    let needs_refresh = match &acct.token_expires_at {
-        Some(ts) => match chrono::DateTime::parse_from_rfc3339(ts) {
-            Ok(datetime) => {
-                let dt_utc = datetime.with_timezone(&Utc);
-                // Refresh if expiring within 60 seconds
-                let margin = Duration::seconds(60);
-                dt_utc <= now + margin
-            }
-            Err(_) => true,
-        },
-        None => true,
-    };
-
-    if needs_refresh {
+    if token_needs_refresh(acct.token_expires_at.as_deref(), Utc::now()) {
         log::info!(
             "[auth] Token for account {} is expired or expiring soon; refreshing",
             target_uuid
@@ -711,6 +909,7 @@ pub async fn ensure_account_tokens_valid(
                     "[auth] Token refresh successful for account {}",
                     target_uuid
                 );
+                set_refresh_failed(&target_uuid, false);
                 Ok(())
             }
             Err(e) => {
@@ -719,6 +918,7 @@ pub async fn ensure_account_tokens_valid(
                     target_uuid,
                     e
                 );
+                set_refresh_failed(&target_uuid, true);
                 Err(e)
             }
         }
@@ -867,8 +1067,13 @@ pub async fn refresh_account_tokens(
         (Utc::now() + Duration::seconds(expires_in_secs as i64)).to_rfc3339();
     let now_str = Utc::now().to_rfc3339();
 
+    // Tokens without the claim keep the previously captured xuid.
+    let xuid_val =
+        piston_lib::auth::xuid_from_minecraft_token(&mc_access_token).or(acct.xuid.clone());
+
     // Update account fields (in memory copy)
     acct.access_token = Some(mc_access_token.clone());
+    acct.xuid = xuid_val.clone();
     if let Some(rt) = ms_refresh_token.clone() {
         acct.refresh_token = Some(rt);
     }
@@ -884,6 +1089,7 @@ pub async fn refresh_account_tokens(
             refresh_token.eq(ms_refresh_token), // Handles Option logic naturally
             token_expires_at.eq(Some(token_expires_at_val)),
             updated_at.eq(Some(now_str)),
+            xuid.eq(xuid_val),
         ))
         .execute(&mut conn)
         .map_err(|e| format!("Failed to update account in DB: {}", e))?;
@@ -898,28 +1104,19 @@ pub async fn refresh_account_tokens(
 /// Set active account by UUID
 #[tauri::command]
 pub fn set_active_account(app_handle: AppHandle, target_uuid: String) -> Result<(), String> {
+    let _switch_guard = ACCOUNT_SWITCH_LOCK.lock().map_err(|e| e.to_string())?;
     let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
 
     // Normalize UUID
     let target_uuid = target_uuid.replace("-", "");
 
-    // Fetch the target account to get its theme settings
-    let target_account = account
-        .filter(uuid.eq(&target_uuid))
-        .first::<crate::models::account::Account>(&mut conn)
-        .map_err(|e| format!("Failed to find account: {}", e))?;
+    let previous_uuid = get_app_config()
+        .ok()
+        .and_then(|config| config.active_account_uuid);
 
-    // Deactivate all accounts
-    diesel::update(account)
-        .set(is_active.eq(false))
-        .execute(&mut conn)
-        .map_err(|e| e.to_string())?;
-
-    // Activate target
-    diesel::update(account.filter(uuid.eq(&target_uuid)))
-        .set(is_active.eq(true))
-        .execute(&mut conn)
-        .map_err(|e| e.to_string())?;
+    // Activate the target; its row also carries the theme settings applied below
+    let target_account = activate_account(&mut conn, &target_uuid)
+        .map_err(|e| format!("Failed to find account: {}", e))?;
 
     // Update config
     let mut config = get_app_config().map_err(|e| e.to_string())?;
@@ -991,6 +1188,22 @@ pub fn set_active_account(app_handle: AppHandle, target_uuid: String) -> Result<
         }),
     );
 
+    // Drop cached profiles so nothing keeps showing the previous account
+    let stale_profiles: Vec<String> = previous_uuid
+        .into_iter()
+        .chain(std::iter::once(target_uuid.clone()))
+        .collect();
+    tauri::async_runtime::spawn(async move {
+        for stale_uuid in stale_profiles {
+            invalidate_account_profile_cache(&stale_uuid).await;
+        }
+    });
+
+    let _ = app_handle.emit(
+        "core://account-changed",
+        AccountSummary::from(&target_account),
+    );
+
     // Sync profile data on account change to ensure skins/capes stay up to date
     if let Some(task_manager) = app_handle.try_state::<crate::tasks::manager::TaskManager>() {
         let _ = task_manager.submit(Box::new(
@@ -1001,6 +1214,64 @@ pub fn set_active_account(app_handle: AppHandle, target_uuid: String) -> Result<
     Ok(())
 }
 
+/// Mark `target_uuid` as the only active account, in one transaction.
+fn activate_account(
+    conn: &mut diesel::SqliteConnection,
+    target_uuid: &str,
+) -> QueryResult<Account> {
+    conn.transaction(|conn| {
+        let target = account
+            .filter(uuid.eq(target_uuid))
+            .first::<Account>(conn)?;
+        diesel::update(account.filter(uuid.ne(target_uuid)))
+            .set(is_active.eq(false))
+            .execute(conn)?;
+        diesel::update(account.filter(uuid.eq(target_uuid)))
+            .set(is_active.eq(true))
+            .execute(conn)?;
+        Ok(Account {
+            is_active: true,
+            ..target
+        })
+    })
+}
+
+/// Set or clear (with an empty name) an account's display name
+#[tauri::command]
+pub fn rename_account_display_name(
+    app_handle: AppHandle,
+    target_uuid: String,
+    new_display_name: Option<String>,
+) -> Result<Account, String> {
+    let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
+
+    // Normalize UUID
+    let target_uuid = target_uuid.replace("-", "");
+    let new_display_name = new_display_name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+
+    let updated = diesel::update(account.filter(uuid.eq(&target_uuid)))
+        .set((
+            display_name.eq(&new_display_name),
+            updated_at.eq(Some(Utc::now().to_rfc3339())),
+        ))
+        .execute(&mut conn)
+        .map_err(|e| e.to_string())?;
+    if updated == 0 {
+        return Err("Account not found".to_string());
+    }
+
+    let acct = account
+        .filter(uuid.eq(&target_uuid))
+        .first::<Account>(&mut conn)
+        .map_err(|e| e.to_string())?;
+    if acct.is_active {
+        let _ = app_handle.emit("core://account-changed", AccountSummary::from(&acct));
+    }
+    Ok(acct)
+}
+
 /// Remove account by UUID
 #[tauri::command]
 pub fn remove_account(target_uuid: String) -> Result<(), String> {
@@ -1153,17 +1424,182 @@ pub async fn get_player_head_path(
     Ok(path.to_string_lossy().to_string())
 }
 
+/// Get path to a rendered thumbnail of the player's cape front, downloading
+/// the cape texture if necessary. Returns `None` when the player has no cape.
+#[tauri::command]
+pub async fn get_player_cape_path(
+    app: AppHandle,
+    player_uuid: String,
+    force: bool,
+) -> Result<Option<String>, String> {
+    let normalized_uuid = player_uuid.replace("-", "");
+    let cache_dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| e.to_string())?
+        .join("player_capes");
+    let texture_path = cache_dir.join(format!("{}.png", normalized_uuid));
+    let thumbnail_path = cache_dir.join(format!("{}-front.png", normalized_uuid));
+
+    let had_texture = texture_path.exists();
+    let Some(texture_path) =
+        piston_lib::api::player::download_player_cape(&normalized_uuid, texture_path, force)
+            .await
+            .map_err(|e| e.to_string())?
+    else {
+        let _ = std::fs::remove_file(&thumbnail_path);
+        return Ok(None);
+    };
+
+    if force || !had_texture || !thumbnail_path.exists() {
+        piston_lib::api::player::render_cape_thumbnail(&texture_path, &thumbnail_path, 128)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(Some(thumbnail_path.to_string_lossy().to_string()))
+}
+
+/// Store the Xbox gamerpic where [`get_player_head_path`] looks for the head
+/// of an account without a skin URL.
+async fn cache_xbox_avatar(
+    app: &AppHandle,
+    account_uuid: &str,
+    avatar_url: &str,
+    force: bool,
+) -> Result<std::path::PathBuf, String> {
+    let image_path = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| e.to_string())?
+        .join("player_heads")
+        .join(format!("{}.png", account_uuid));
+    if image_path.exists() && !force {
+        return Ok(image_path);
+    }
+
+    let response = piston_lib::client::shared_client()
+        .get(avatar_url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download Xbox avatar: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Xbox avatar returned {}", response.status()));
+    }
+    let bytes = response.bytes().await.map_err(|e| e.to_string())?;
+    if let Some(parent) = image_path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    tokio::fs::write(&image_path, &bytes)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(image_path)
+}
+
+/// Re-fetch the skin from the Minecraft profile and the gamerpic from Xbox
+/// Live for an account.
+#[tauri::command]
+pub async fn refresh_account_avatar(
+    app_handle: AppHandle,
+    target_uuid: String,
+) -> Result<(), String> {
+    use crate::schema::account::dsl as acct_dsl;
+
+    let account_uuid = target_uuid.replace("-", "");
+    ensure_account_tokens_valid(app_handle.clone(), account_uuid.clone()).await?;
+
+    let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
+    let acct = acct_dsl::account
+        .filter(acct_dsl::uuid.eq(&account_uuid))
+        .first::<Account>(&mut conn)
+        .map_err(|e| format!("Account not found: {}", e))?;
+    if acct.account_type != "Microsoft" {
+        return Err("Only Microsoft accounts have avatars".to_string());
+    }
+    let mc_token = acct
+        .access_token
+        .clone()
+        .ok_or_else(|| "Account has no access token".to_string())?;
+    let stored_refresh_token = acct
+        .refresh_token
+        .clone()
+        .ok_or_else(|| "No refresh token available".to_string())?;
+
+    let profile = get_minecraft_profile(&mc_token)
+        .await
+        .map_err(|e| format!("Failed to fetch Minecraft profile: {}", e))?;
+    let (skin_url_val, skin_variant_val) = active_skin_fields(&profile);
+
+    // The gamerpic needs a Microsoft access token, which is not stored.
+    let client = get_auth_client().map_err(|e| e.to_string())?;
+    let token_response = piston_lib::auth::refresh_access_token(&client, stored_refresh_token)
+        .await
+        .map_err(|e| format!("Failed to refresh token: {}", e))?;
+    let rotated_refresh_token = token_response
+        .refresh_token()
+        .map(|rt| rt.secret().clone())
+        .or(acct.refresh_token.clone());
+    let xbox_avatar_val =
+        piston_lib::api::xbox::get_xbox_avatar_url(token_response.access_token().secret())
+            .await
+            .map_err(|e| format!("Failed to fetch Xbox avatar: {}", e))?;
+
+    let next_skin_data = if acct.skin_url == skin_url_val {
+        acct.skin_data.clone()
+    } else {
+        None
+    };
+    diesel::update(acct_dsl::account.filter(acct_dsl::uuid.eq(&account_uuid)))
+        .set((
+            acct_dsl::skin_url.eq(&skin_url_val),
+            acct_dsl::skin_variant.eq(skin_variant_val),
+            acct_dsl::skin_data.eq(next_skin_data),
+            acct_dsl::xbox_avatar_url.eq(&xbox_avatar_val),
+            acct_dsl::refresh_token.eq(rotated_refresh_token),
+            acct_dsl::updated_at.eq(Some(Utc::now().to_rfc3339())),
+        ))
+        .execute(&mut conn)
+        .map_err(|e| format!("Failed to update account: {}", e))?;
+
+    invalidate_account_profile_cache(&account_uuid).await;
+    if skin_url_val.is_none() {
+        if let Some(avatar_url) = xbox_avatar_val.as_deref() {
+            cache_xbox_avatar(&app_handle, &account_uuid, avatar_url, true).await?;
+        }
+    }
+    emit_account_heads_updated(&app_handle, Some(&account_uuid), true);
+    let _ = app_handle.emit("core://accounts-changed", ());
+    Ok(())
+}
+
 /// Pre-download all account head images on startup
 #[tauri::command]
 pub async fn preload_account_heads(app: AppHandle) -> Result<(), String> {
     let accounts = get_accounts()?;
 
-    let futures = accounts.into_iter().map(|acct| {
-        let app = app.clone();
-        async move {
-            let _ = get_player_head_path(app, acct.uuid, false).await;
-        }
-    });
+    let futures = accounts
+        .into_iter()
+        .map(|AccountView { account: acct, .. }| {
+            let app = app.clone();
+            async move {
+                // Accounts on the default skin show their Xbox gamerpic instead.
+                if acct.skin_url.is_none() {
+                    if let Some(avatar_url) = acct.xbox_avatar_url.as_deref() {
+                        if let Err(e) = cache_xbox_avatar(&app, &acct.uuid, avatar_url, false).await
+                        {
+                            log::warn!(
+                                "[auth] Failed to cache Xbox avatar for {}: {}",
+                                acct.uuid,
+                                e
+                            );
+                        }
+                        return;
+                    }
+                }
+                let _ = get_player_head_path(app, acct.uuid, false).await;
+            }
+        });
 
     futures::future::join_all(futures).await;
 
@@ -1218,3 +1654,114 @@ pub fn repair_active_account(app_handle: AppHandle) -> Result<Option<Account>, S
 
     Ok(None)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel_migrations::MigrationHarness;
+
+    fn conn_with_accounts(uuids: &[&str]) -> diesel::SqliteConnection {
+        let mut conn = diesel::SqliteConnection::establish(":memory:").unwrap();
+        conn.run_pending_migrations(crate::utils::db::VESTA_MIGRATIONS)
+            .unwrap();
+        for (i, account_uuid) in uuids.iter().enumerate() {
+            diesel::insert_into(account)
+                .values(NewAccount {
+                    uuid: account_uuid.to_string(),
+                    username: format!("Player{}", i),
+                    is_active: i == 0,
+                    ..Default::default()
+                })
+                .execute(&mut conn)
+                .unwrap();
+        }
+        conn
+    }
+
+    fn active_uuids(conn: &mut diesel::SqliteConnection) -> Vec<String> {
+        account
+            .filter(is_active.eq(true))
+            .select(uuid)
+            .load::<String>(conn)
+            .unwrap()
+    }
+
+    #[test]
+    fn rapid_switches_leave_one_active_account() {
+        let mut conn = conn_with_accounts(&["aaaa", "bbbb", "cccc"]);
+
+        activate_account(&mut conn, "bbbb").unwrap();
+        let last = activate_account(&mut conn, "cccc").unwrap();
+
+        assert!(last.is_active);
+        assert_eq!(active_uuids(&mut conn), vec!["cccc".to_string()]);
+    }
+
+    #[test]
+    fn switching_to_unknown_account_keeps_current_one() {
+        let mut conn = conn_with_accounts(&["aaaa", "bbbb"]);
+
+        assert!(activate_account(&mut conn, "missing").is_err());
+        assert_eq!(active_uuids(&mut conn), vec!["aaaa".to_string()]);
+    }
+
+    #[test]
+    fn tokens_are_refreshed_shortly_before_expiry() {
+        let now = Utc::now();
+        let at = |offset: i64| (now + Duration::seconds(offset)).to_rfc3339();
+
+        assert!(!token_needs_refresh(Some(&at(3600)), now));
+        assert!(token_needs_refresh(Some(&at(30)), now));
+        assert!(token_needs_refresh(Some(&at(-10)), now));
+        assert!(token_needs_refresh(Some("tomorrow"), now));
+        assert!(token_needs_refresh(None, now));
+    }
+
+    #[test]
+    fn xbox_avatar_url_is_stored_with_the_account() {
+        let mut conn = conn_with_accounts(&["aaaa"]);
+        diesel::update(account.filter(uuid.eq("aaaa")))
+            .set(xbox_avatar_url.eq(Some("https://images-eds-ssl.xboxlive.com/pic")))
+            .execute(&mut conn)
+            .unwrap();
+
+        let stored = account.first::<Account>(&mut conn).unwrap();
+        assert_eq!(
+            stored.xbox_avatar_url.as_deref(),
+            Some("https://images-eds-ssl.xboxlive.com/pic")
+        );
+    }
+
+    #[test]
+    fn active_skin_is_preferred_over_the_first_one() {
+        let skin = |state: &str, url: &str, variant: &str| piston_lib::api::mojang::ProfileSkin {
+            id: url.to_string(),
+            state: state.to_string(),
+            url: url.to_string(),
+            variant: variant.to_string(),
+            alias: None,
+        };
+        let profile = piston_lib::api::mojang::MinecraftProfile {
+            id: "aaaa".to_string(),
+            name: "Player".to_string(),
+            skins: vec![
+                skin("INACTIVE", "old", "CLASSIC"),
+                skin("ACTIVE", "new", "SLIM"),
+            ],
+            capes: vec![],
+        };
+        assert_eq!(
+            active_skin_fields(&profile),
+            (Some("new".to_string()), "slim".to_string())
+        );
+
+        let default_skin = piston_lib::api::mojang::MinecraftProfile {
+            skins: vec![],
+            ..profile
+        };
+        assert_eq!(
+            active_skin_fields(&default_skin),
+            (None, "classic".to_string())
+        );
+    }
+}
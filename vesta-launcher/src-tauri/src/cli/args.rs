@@ -0,0 +1,289 @@
+//! Argument parsing for `--headless` mode.
+
+use piston_lib::game::ModloaderType;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+Usage: vesta-launcher --headless <command> [options]
+
+Commands:
+  install        Install a Minecraft version into a game directory
+                   --version <id> [--loader <vanilla|fabric|quilt|forge|neoforge>]
+                   [--loader-version <id>] --dir <path> [--java <path>] [--dry-run]
+  verify         Check installed files without changing anything
+                   --instance <slug>
+                   or the install options --version/--loader/--loader-version/--dir
+  launch         Launch an instance and wait for the game to exit
+                   --instance <slug> [--offline]
+  export-bundle  Write a diagnostics bundle
+                   [--out <file.zip|folder>] [--include-logs] [--include-event-log]
+  help           Show this message
+";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Install(InstallArgs),
+    Verify(VerifyTarget),
+    Launch(LaunchArgs),
+    ExportBundle(ExportBundleArgs),
+    Help,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstallArgs {
+    pub version: String,
+    /// `None` for vanilla.
+    pub loader: Option<ModloaderType>,
+    pub loader_version: Option<String>,
+    pub dir: PathBuf,
+    pub java: Option<PathBuf>,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyTarget {
+    Instance(String),
+    Version(InstallArgs),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchArgs {
+    pub instance: String,
+    pub offline: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportBundleArgs {
+    pub out: PathBuf,
+    pub include_logs: bool,
+    pub include_event_log: bool,
+}
+
+/// Options of one command: `--name value`, `--name=value` or bare switches.
+struct Options {
+    values: HashMap<String, String>,
+    switches: Vec<String>,
+}
+
+impl Options {
+    fn parse(args: &[String], value_names: &[&str], switch_names: &[&str]) -> Result<Self, String> {
+        let mut options = Options {
+            values: HashMap::new(),
+            switches: Vec::new(),
+        };
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let Some(flag) = arg.strip_prefix("--") else {
+                return Err(format!("Unexpected argument '{}'", arg));
+            };
+            let (name, inline_value) = match flag.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (flag, None),
+            };
+            if value_names.contains(&name) {
+                let value = match inline_value {
+                    Some(value) => value,
+                    None => iter
+                        .next()
+                        .filter(|value| !value.starts_with("--"))
+                        .cloned()
+                        .ok_or_else(|| format!("--{} needs a value", name))?,
+                };
+                if value.trim().is_empty() {
+                    return Err(format!("--{} needs a value", name));
+                }
+                if options.values.insert(name.to_string(), value).is_some() {
+                    return Err(format!("--{} given more than once", name));
+                }
+            } else if switch_names.contains(&name) && inline_value.is_none() {
+                options.switches.push(name.to_string());
+            } else {
+                return Err(format!("Unknown option '{}'", arg));
+            }
+        }
+        Ok(options)
+    }
+
+    fn value(&self, name: &str) -> Option<String> {
+        self.values.get(name).cloned()
+    }
+
+    fn required(&self, name: &str) -> Result<String, String> {
+        self.value(name)
+            .ok_or_else(|| format!("--{} is required", name))
+    }
+
+    fn switch(&self, name: &str) -> bool {
+        self.switches.iter().any(|s| s == name)
+    }
+}
+
+const INSTALL_VALUES: &[&str] = &["version", "loader", "loader-version", "dir", "java"];
+
+/// Parse the arguments following `--headless`.
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    let Some((command, rest)) = args.split_first() else {
+        return Err("Missing command".to_string());
+    };
+    match command.as_str() {
+        "install" => {
+            let options = Options::parse(rest, INSTALL_VALUES, &["dry-run"])?;
+            install_args(&options).map(Command::Install)
+        }
+        "verify" => {
+            let mut values = INSTALL_VALUES.to_vec();
+            values.push("instance");
+            let options = Options::parse(rest, &values, &[])?;
+            match options.value("instance") {
+                Some(_) if options.values.len() > 1 => {
+                    Err("--instance cannot be combined with install options".to_string())
+                }
+                Some(slug) => Ok(Command::Verify(VerifyTarget::Instance(slug))),
+                None => {
+                    install_args(&options).map(|args| Command::Verify(VerifyTarget::Version(args)))
+                }
+            }
+        }
+        "launch" => {
+            let options = Options::parse(rest, &["instance"], &["offline"])?;
+            Ok(Command::Launch(LaunchArgs {
+                instance: options.required("instance")?,
+                offline: options.switch("offline"),
+            }))
+        }
+        "export-bundle" => {
+            let options = Options::parse(rest, &["out"], &["include-logs", "include-event-log"])?;
+            let out = match options.value("out") {
+                Some(out) => absolute(PathBuf::from(out))?,
+                None => std::env::current_dir()
+                    .map_err(|e| format!("Failed to read the current directory: {}", e))?,
+            };
+            Ok(Command::ExportBundle(ExportBundleArgs {
+                out,
+                include_logs: options.switch("include-logs"),
+                include_event_log: options.switch("include-event-log"),
+            }))
+        }
+        "help" | "--help" | "-h" => Ok(Command::Help),
+        other => Err(format!("Unknown command '{}'", other)),
+    }
+}
+
+fn install_args(options: &Options) -> Result<InstallArgs, String> {
+    let loader = match options.value("loader") {
+        Some(loader) => match loader.parse::<ModloaderType>().map_err(|e| e.to_string())? {
+            ModloaderType::Vanilla => None,
+            loader => Some(loader),
+        },
+        None => None,
+    };
+    let loader_version = options.value("loader-version");
+    if loader.is_none() && loader_version.is_some() {
+        return Err("--loader-version needs a modded --loader".to_string());
+    }
+    Ok(InstallArgs {
+        version: options.required("version")?,
+        loader,
+        loader_version,
+        dir: absolute(PathBuf::from(options.required("dir")?))?,
+        java: options.value("java").map(PathBuf::from),
+        dry_run: options.switch("dry-run"),
+    })
+}
+
+fn absolute(path: PathBuf) -> Result<PathBuf, String> {
+    std::path::absolute(&path).map_err(|e| format!("Invalid path {:?}: {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parses_install_options() {
+        let dir = std::env::temp_dir().join("vesta-cli");
+        let command = parse(&args(&format!(
+            "install --version 1.21 --loader=fabric --dir {} --dry-run",
+            dir.display()
+        )))
+        .unwrap();
+
+        assert_eq!(
+            command,
+            Command::Install(InstallArgs {
+                version: "1.21".to_string(),
+                loader: Some(ModloaderType::Fabric),
+                loader_version: None,
+                dir,
+                java: None,
+                dry_run: true,
+            })
+        );
+    }
+
+    #[test]
+    fn vanilla_loader_is_no_loader() {
+        let Command::Install(install) = parse(&args(
+            "install --version 1.21 --loader vanilla --dir /tmp/x",
+        ))
+        .unwrap() else {
+            panic!("expected install");
+        };
+        assert_eq!(install.loader, None);
+    }
+
+    #[test]
+    fn rejects_malformed_arguments() {
+        for line in [
+            "",
+            "upgrade",
+            "install --dir /tmp/x",
+            "install --version 1.21",
+            "install --version --dir /tmp/x",
+            "install --version 1.21 --dir /tmp/x --loader banana",
+            "install --version 1.21 --dir /tmp/x --loader-version 0.16.0",
+            "install --version 1.21 --version 1.20 --dir /tmp/x",
+            "install --version 1.21 --dir /tmp/x --dry-run=yes",
+            "install 1.21",
+            "launch",
+            "launch --instance a --force",
+            "verify --instance a --version 1.21",
+        ] {
+            assert!(parse(&args(line)).is_err(), "accepted {:?}", line);
+        }
+    }
+
+    #[test]
+    fn parses_other_commands() {
+        assert_eq!(
+            parse(&args("launch --instance my-pack --offline")).unwrap(),
+            Command::Launch(LaunchArgs {
+                instance: "my-pack".to_string(),
+                offline: true,
+            })
+        );
+        assert_eq!(
+            parse(&args("verify --instance my-pack")).unwrap(),
+            Command::Verify(VerifyTarget::Instance("my-pack".to_string()))
+        );
+        let out = std::env::temp_dir().join("bundle.zip");
+        assert_eq!(
+            parse(&args(&format!(
+                "export-bundle --out {} --include-logs",
+                out.display()
+            )))
+            .unwrap(),
+            Command::ExportBundle(ExportBundleArgs {
+                out,
+                include_logs: true,
+                include_event_log: false,
+            })
+        );
+        assert_eq!(parse(&args("help")).unwrap(), Command::Help);
+    }
+}
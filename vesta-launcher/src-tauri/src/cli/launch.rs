@@ -0,0 +1,246 @@
+//! `launch --instance <slug>`: prepare, start and wait for an instance.
+//!
+//! Launch specs come from [`build_instance_launch`], the same builder the GUI
+//! uses. What differs is app state: tokens are never refreshed here (that
+//! needs the sign-in flow), managed Java is not downloaded, and play time is
+//! not recorded.
+
+use super::args::LaunchArgs;
+use super::progress::TerminalProgressReporter;
+use super::{CliError, ExitCode};
+use crate::auth::{ACCOUNT_TYPE_DEMO, ACCOUNT_TYPE_GUEST};
+use crate::instance::launch_preparation::{
+    build_instance_launch, bundled_exit_handler_jar, LaunchContext,
+};
+use crate::models::account::Account;
+use crate::utils::config::AppConfig;
+use crate::utils::hooks::{run_launch_command, LaunchCommandVars, LAUNCH_COMMAND_TIMEOUT};
+use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::Duration;
+
+const EXIT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+pub(super) async fn launch(
+    args: &LaunchArgs,
+    config: &AppConfig,
+    cancelled: Arc<AtomicBool>,
+) -> Result<(), CliError> {
+    let instance = super::find_instance(&args.instance)?;
+    let instance = crate::commands::instances::with_detected_modloader(instance);
+    let account = launch_account(
+        crate::auth::get_active_account().map_err(CliError::failure)?,
+        args.offline,
+        chrono::Utc::now(),
+    )?;
+    let java_path = resolve_java(&instance).await?;
+
+    let prepared = build_instance_launch(
+        &instance,
+        config,
+        LaunchContext {
+            java_path,
+            account,
+            offline: args.offline,
+            exit_handler_jar: bundled_exit_handler_jar(
+                std::env::current_exe()
+                    .ok()
+                    .and_then(|exe| exe.parent().map(Path::to_path_buf)),
+            ),
+        },
+    )
+    .map_err(CliError::failure)?;
+
+    let reporter = Arc::new(TerminalProgressReporter::new(false, cancelled));
+    let runtime =
+        piston_lib::game::runtime_preparation::prepare_runtime(prepared.install_spec, reporter)
+            .await?;
+    if !runtime.final_report.ready {
+        return Err(CliError::new(
+            ExitCode::VerificationFailed,
+            format!(
+                "Runtime files are still missing or corrupt after repair (missing={}, mismatched={})",
+                runtime.final_report.missing_count(),
+                runtime.final_report.mismatch_count()
+            ),
+        ));
+    }
+    let runtime_plan = runtime
+        .final_plan
+        .ok_or_else(|| CliError::failure("Runtime verification produced no launch plan"))?;
+
+    let launch_spec = prepared.launch_spec;
+    let vars = LaunchCommandVars {
+        instance_id: prepared.instance_id.clone(),
+        game_dir: launch_spec.game_dir.clone(),
+        minecraft_version: instance.minecraft_version.clone(),
+    };
+    if let Some(pre_command) = non_empty(&config.game_launch_pre_command) {
+        run_launch_command("pre-launch", pre_command, &vars, LAUNCH_COMMAND_TIMEOUT)
+            .await
+            .map_err(|e| CliError::failure(format!("Launch aborted: {}", e)))?;
+    }
+
+    let log_callback: piston_lib::game::launcher::LogCallback =
+        Arc::new(|_instance_id: String, line: String, _stream: String| println!("{}", line));
+    let result = tokio::task::spawn_blocking(move || {
+        futures::executor::block_on(piston_lib::game::launcher::launch_prepared_game(
+            launch_spec,
+            runtime_plan,
+            Some(log_callback),
+        ))
+    })
+    .await
+    .map_err(|e| CliError::failure(format!("Failed to spawn blocking task: {}", e)))??;
+
+    let pid = result.instance.pid;
+    eprintln!("Started {} (pid {})", prepared.instance_name, pid);
+    let exit_code = match result.handle.and_then(|handle| handle.child) {
+        Some(mut child) => child.wait().await.ok().and_then(|status| status.code()),
+        None => {
+            wait_for_exit(pid).await;
+            None
+        }
+    };
+
+    if let Some(post_command) = non_empty(&config.game_launch_post_command) {
+        if let Err(e) =
+            run_launch_command("post-exit", post_command, &vars, LAUNCH_COMMAND_TIMEOUT).await
+        {
+            log::warn!("Post-exit command failed: {}", e);
+        }
+    }
+
+    match exit_code {
+        Some(code) if code != 0 => Err(CliError::new(
+            ExitCode::GameFailed,
+            format!("Minecraft exited with code {}", code),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn non_empty(command: &Option<String>) -> Option<&str> {
+    command
+        .as_deref()
+        .filter(|command| !command.trim().is_empty())
+}
+
+/// The account to launch as. Without the GUI there is no way to sign in
+/// again, so an online launch needs a Microsoft account whose token is
+/// still valid.
+fn launch_account(
+    active: Option<Account>,
+    offline: bool,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<Option<Account>, CliError> {
+    let login_required = |message: &str| CliError::new(ExitCode::LoginRequired, message);
+    let Some(account) = active else {
+        return if offline {
+            Ok(None)
+        } else {
+            Err(login_required(
+                "No account is signed in. Sign in from Vesta Launcher or pass --offline.",
+            ))
+        };
+    };
+    if account.account_type == ACCOUNT_TYPE_GUEST || account.account_type == ACCOUNT_TYPE_DEMO {
+        return Err(login_required(
+            "You must be signed in with a Microsoft account to launch Minecraft.",
+        ));
+    }
+    if !offline && crate::auth::token_needs_refresh(account.token_expires_at.as_deref(), now) {
+        return Err(login_required(
+            "The sign-in has expired. Open Vesta Launcher to refresh it or pass --offline.",
+        ));
+    }
+    Ok(Some(account))
+}
+
+/// Configured Java, else the active or managed Java for the version. Missing
+/// managed Java is not downloaded here; the launch fails with the path.
+async fn resolve_java(instance: &crate::models::instance::Instance) -> Result<String, CliError> {
+    if let Some(path) =
+        crate::utils::java::configured_instance_java_path(instance).map_err(CliError::failure)?
+    {
+        return Ok(path);
+    }
+    let major =
+        crate::utils::java::resolve_java_major_from_version_json(&instance.minecraft_version)
+            .await
+            .map_err(CliError::failure)?;
+    crate::utils::java::ensure_managed_java_preference(major).map_err(CliError::failure)
+}
+
+async fn wait_for_exit(pid: u32) {
+    let mut sys = sysinfo::System::new();
+    let pid = sysinfo::Pid::from_u32(pid);
+    loop {
+        sys.refresh_processes(sysinfo::ProcessesToUpdate::Some(&[pid]), true);
+        if sys.process(pid).is_none() {
+            return;
+        }
+        tokio::time::sleep(EXIT_POLL_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(account_type: &str, expires_in_secs: i64) -> Account {
+        Account {
+            id: 1,
+            uuid: "0123456789abcdef0123456789abcdef".to_string(),
+            username: "Player".to_string(),
+            display_name: None,
+            access_token: Some("token".to_string()),
+            refresh_token: Some("refresh".to_string()),
+            token_expires_at: Some(
+                (chrono::Utc::now() + chrono::Duration::seconds(expires_in_secs)).to_rfc3339(),
+            ),
+            is_active: true,
+            skin_url: None,
+            cape_url: None,
+            created_at: None,
+            updated_at: None,
+            theme_id: None,
+            account_type: account_type.to_string(),
+            is_expired: false,
+            skin_variant: "classic".to_string(),
+            skin_data: None,
+            theme_data: None,
+            theme_window_effect: None,
+            theme_background_opacity: None,
+            xuid: None,
+            xbox_avatar_url: None,
+        }
+    }
+
+    #[test]
+    fn online_launches_need_a_valid_microsoft_token() {
+        let now = chrono::Utc::now();
+        let code = |result: Result<Option<Account>, CliError>| result.err().map(|e| e.code);
+
+        assert!(launch_account(Some(account("microsoft", 3600)), false, now).is_ok());
+        assert_eq!(
+            code(launch_account(Some(account("microsoft", -60)), false, now)),
+            Some(ExitCode::LoginRequired)
+        );
+        assert!(launch_account(Some(account("microsoft", -60)), true, now).is_ok());
+        assert_eq!(
+            code(launch_account(
+                Some(account(ACCOUNT_TYPE_GUEST, 3600)),
+                true,
+                now
+            )),
+            Some(ExitCode::LoginRequired)
+        );
+        assert_eq!(
+            code(launch_account(None, false, now)),
+            Some(ExitCode::LoginRequired)
+        );
+        assert!(launch_account(None, true, now).unwrap().is_none());
+    }
+}
@@ -0,0 +1,387 @@
+//! Headless mode: `vesta-launcher --headless <command>` installs, verifies
+//! and launches without opening a window, for servers and CI.
+//!
+//! It uses the launcher's own data directory and databases. Commands that
+//! write take the launcher lock ([`crate::utils::launcher_lock`]) and refuse
+//! to run while the GUI holds it; `verify` and `export-bundle` only read.
+
+mod args;
+mod launch;
+mod progress;
+
+use crate::models::instance::Instance;
+use crate::utils::config::AppConfig;
+use args::{Command, InstallArgs, VerifyTarget};
+use piston_lib::game::installer::types::{InstallSpec, RemediationPolicy};
+use progress::TerminalProgressReporter;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+pub const HEADLESS_FLAG: &str = "--headless";
+
+/// Process exit codes of headless commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Success = 0,
+    Failure = 1,
+    Usage = 2,
+    LauncherRunning = 3,
+    NotFound = 4,
+    Network = 5,
+    VerificationFailed = 6,
+    LoginRequired = 7,
+    GameFailed = 8,
+    Cancelled = 130,
+}
+
+#[derive(Debug)]
+pub struct CliError {
+    pub code: ExitCode,
+    pub message: String,
+}
+
+impl CliError {
+    fn new(code: ExitCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn failure(message: impl Into<String>) -> Self {
+        Self::new(ExitCode::Failure, message)
+    }
+}
+
+impl From<anyhow::Error> for CliError {
+    fn from(error: anyhow::Error) -> Self {
+        Self::new(exit_code_for(&error), format!("{:#}", error))
+    }
+}
+
+/// The installer reports failures as `anyhow` chains; classify them by the
+/// underlying network error or by the installer's own wording.
+pub fn exit_code_for(error: &anyhow::Error) -> ExitCode {
+    if error
+        .chain()
+        .any(|cause| cause.downcast_ref::<reqwest::Error>().is_some())
+    {
+        return ExitCode::Network;
+    }
+    let message = format!("{:#}", error).to_lowercase();
+    if message.contains("cancelled") {
+        ExitCode::Cancelled
+    } else if message.contains("not found")
+        || message.contains("does not support")
+        || message.contains("has no loaders")
+    {
+        ExitCode::NotFound
+    } else {
+        ExitCode::Failure
+    }
+}
+
+/// The arguments after [`HEADLESS_FLAG`] when the process was started with it.
+pub fn headless_args(args: &[String]) -> Option<&[String]> {
+    match args {
+        [_, flag, rest @ ..] if flag == HEADLESS_FLAG => Some(rest),
+        _ => None,
+    }
+}
+
+/// Run one headless command and return the process exit code.
+pub fn run(args: &[String]) -> i32 {
+    let command = match args::parse(args) {
+        Ok(Command::Help) => {
+            print!("{}", args::USAGE);
+            return ExitCode::Success as i32;
+        }
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, args::USAGE);
+            return ExitCode::Usage as i32;
+        }
+    };
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("error: failed to start async runtime: {}", e);
+            return ExitCode::Failure as i32;
+        }
+    };
+    match runtime.block_on(execute(command)) {
+        Ok(()) => ExitCode::Success as i32,
+        Err(e) => {
+            eprintln!("error: {}", e.message);
+            e.code as i32
+        }
+    }
+}
+
+/// Whether `command` writes to the data directory or databases.
+fn writes(command: &Command) -> bool {
+    match command {
+        Command::Install(install) => !install.dry_run,
+        Command::Launch(_) => true,
+        Command::Verify(_) | Command::ExportBundle(_) | Command::Help => false,
+    }
+}
+
+async fn execute(command: Command) -> Result<(), CliError> {
+    let config_dir = crate::utils::db_manager::get_app_config_dir()?;
+    let _lock = if writes(&command) {
+        Some(
+            crate::utils::launcher_lock::acquire(&config_dir).map_err(|e| {
+                if e.kind() == std::io::ErrorKind::WouldBlock {
+                    CliError::new(
+                        ExitCode::LauncherRunning,
+                        "Vesta Launcher is running. Close it before running this command.",
+                    )
+                } else {
+                    CliError::failure(format!("Failed to take the launcher lock: {}", e))
+                }
+            })?,
+        )
+    } else {
+        None
+    };
+
+    crate::utils::db::init_config_pool(config_dir.clone())?;
+    crate::utils::db::init_vesta_pool(config_dir.clone())?;
+    let config = crate::utils::config::get_app_config().ok();
+    if let Some(config) = &config {
+        if let Err(e) = piston_lib::client::configure_proxy(piston_lib::client::ProxyConfig {
+            enabled: config.proxy_enabled,
+            url: config.proxy_url.clone(),
+        }) {
+            log::warn!(
+                "Failed to configure HTTP proxy: {}",
+                piston_lib::client::redact_configured_proxy_secrets(&e)
+            );
+        }
+    }
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let cancel_flag = cancelled.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            eprintln!("\nCancelling...");
+            cancel_flag.store(true, Ordering::SeqCst);
+        }
+    });
+
+    let data_dir = config_dir.join("data");
+    match command {
+        Command::Install(install) => {
+            let reporter = Arc::new(TerminalProgressReporter::new(
+                install.dry_run,
+                cancelled.clone(),
+            ));
+            let spec = install_spec(&install, &data_dir, config.as_ref());
+            piston_lib::game::installer::install_instance(spec, reporter).await?;
+            Ok(())
+        }
+        Command::Verify(target) => {
+            let spec = match target {
+                VerifyTarget::Version(install) => {
+                    install_spec(&install, &data_dir, config.as_ref())
+                }
+                VerifyTarget::Instance(slug) => {
+                    let instance = find_instance(&slug)?;
+                    instance_install_spec(&instance, &config_dir, config.as_ref())
+                }
+            };
+            verify(spec)
+        }
+        Command::Launch(launch) => {
+            let config =
+                config.ok_or_else(|| CliError::failure("Failed to read launcher settings"))?;
+            launch::launch(&launch, &config, cancelled).await
+        }
+        Command::ExportBundle(export) => {
+            let bundle_path = tokio::task::spawn_blocking(move || {
+                crate::tasks::diagnostics::export_bundle(
+                    &export.out,
+                    export.include_logs,
+                    export.include_event_log,
+                    env!("CARGO_PKG_VERSION"),
+                    |step, total_steps, description| {
+                        if cancelled.load(Ordering::SeqCst) {
+                            return Err("Diagnostics bundle cancelled".to_string());
+                        }
+                        eprintln!("[{}/{}] {}", step, total_steps, description);
+                        Ok(())
+                    },
+                )
+            })
+            .await
+            .map_err(|e| CliError::failure(format!("Diagnostics bundle worker failed: {}", e)))?
+            .map_err(CliError::failure)?;
+            println!("{}", bundle_path.display());
+            Ok(())
+        }
+        Command::Help => Ok(()),
+    }
+}
+
+fn find_instance(slug: &str) -> Result<Instance, CliError> {
+    crate::instance::lifecycle::find_instance_by_slug(slug)
+        .ok_or_else(|| CliError::new(ExitCode::NotFound, format!("No instance named '{}'", slug)))
+}
+
+fn apply_app_settings(spec: &mut InstallSpec, config: Option<&AppConfig>) {
+    if let Some(config) = config {
+        spec.artifact_cache_max_bytes = crate::utils::storage::normalize_artifact_cache_limit_bytes(
+            config.artifact_cache_max_bytes,
+        ) as u64;
+        spec.artifact_cache_compression = config.cache_compression_enabled;
+        spec.concurrency = crate::utils::network::download_concurrency(config);
+    }
+}
+
+fn install_spec(install: &InstallArgs, data_dir: &Path, config: Option<&AppConfig>) -> InstallSpec {
+    let mut spec = InstallSpec::new(
+        install.version.clone(),
+        data_dir.to_path_buf(),
+        install.dir.clone(),
+    );
+    spec.modloader = install.loader;
+    spec.modloader_version = install.loader_version.clone();
+    spec.java_path = install.java.clone();
+    spec.dry_run = install.dry_run;
+    apply_app_settings(&mut spec, config);
+    spec
+}
+
+fn instance_install_spec(
+    instance: &Instance,
+    config_dir: &Path,
+    config: Option<&AppConfig>,
+) -> InstallSpec {
+    let instances_root = crate::utils::instance_helpers::resolve_instances_root(
+        config_dir,
+        config.and_then(|config| config.default_game_dir.as_deref()),
+    );
+    let game_dir = crate::utils::instance_helpers::resolve_instance_game_directory(
+        instance,
+        &instances_root,
+        config_dir,
+    );
+    let mut spec = InstallSpec::new(
+        instance.minecraft_version.clone(),
+        config_dir.join("data"),
+        game_dir,
+    );
+    spec.modloader = instance
+        .modloader
+        .as_deref()
+        .and_then(|loader| loader.parse().ok());
+    spec.modloader_version = instance.modloader_version.clone();
+    apply_app_settings(&mut spec, config);
+    spec
+}
+
+fn verify(mut spec: InstallSpec) -> Result<(), CliError> {
+    spec.remediation_policy = RemediationPolicy::VerifyOnly;
+    let report = piston_lib::game::runtime_preparation::inspect_runtime(&spec)?.verification;
+    for issue in &report.issues {
+        println!(
+            "{:?}\t{}\t{}\t{}",
+            issue.kind, issue.artifact_class, issue.path, issue.detail
+        );
+    }
+    if report.ready {
+        eprintln!("{} files verified, no issues found", report.checked);
+        Ok(())
+    } else {
+        Err(CliError::new(
+            ExitCode::VerificationFailed,
+            format!(
+                "{} of {} files need repair (missing={}, mismatched={})",
+                report.issues.len(),
+                report.checked,
+                report.missing_count(),
+                report.mismatch_count()
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    fn argv(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn headless_args_require_the_leading_flag() {
+        let args = argv("vesta-launcher --headless install --version 1.21");
+        assert_eq!(headless_args(&args).unwrap(), &args[2..]);
+        assert!(headless_args(&argv("vesta-launcher")).is_none());
+        assert!(headless_args(&argv("vesta-launcher --new-window --headless")).is_none());
+    }
+
+    #[test]
+    fn only_writing_commands_take_the_lock() {
+        let parse = |line: &str| args::parse(&argv(line)).unwrap();
+        assert!(writes(&parse("install --version 1.21 --dir /tmp/x")));
+        assert!(!writes(&parse(
+            "install --version 1.21 --dir /tmp/x --dry-run"
+        )));
+        assert!(writes(&parse("launch --instance pack")));
+        assert!(!writes(&parse("verify --instance pack")));
+        assert!(!writes(&parse("export-bundle")));
+    }
+
+    #[test]
+    fn install_errors_map_to_exit_codes() {
+        let cancelled = anyhow::anyhow!("Installation cancelled by user");
+        assert_eq!(exit_code_for(&cancelled), ExitCode::Cancelled);
+
+        let missing: anyhow::Result<()> =
+            Err(anyhow::anyhow!("Fabric does not support Minecraft 0.1"));
+        let missing = missing.context("Failed to install modloader").unwrap_err();
+        assert_eq!(exit_code_for(&missing), ExitCode::NotFound);
+
+        let disk = anyhow::Error::new(std::io::Error::other("disk full"));
+        assert_eq!(exit_code_for(&disk), ExitCode::Failure);
+    }
+
+    #[tokio::test]
+    async fn dry_run_install_through_the_argument_parser() {
+        let tmp = tempfile::tempdir().unwrap();
+        let game_dir = tmp.path().join("server");
+        let args = argv(&format!(
+            "vesta-launcher --headless install --version 1.21 --loader fabric --dir {} --dry-run",
+            game_dir.display()
+        ));
+        let Command::Install(install) = args::parse(headless_args(&args).unwrap()).unwrap() else {
+            panic!("expected install");
+        };
+
+        let data_dir = tmp.path().join("data");
+        let spec = install_spec(&install, &data_dir, None);
+        assert!(spec.dry_run);
+        assert_eq!(spec.game_dir, game_dir);
+        assert_eq!(
+            spec.modloader,
+            Some(piston_lib::game::ModloaderType::Fabric)
+        );
+
+        let reporter = Arc::new(TerminalProgressReporter::new(
+            true,
+            Arc::new(AtomicBool::new(false)),
+        ));
+        piston_lib::game::installer::install_instance(spec, reporter)
+            .await
+            .unwrap();
+
+        assert!(!data_dir.exists(), "dry run wrote the data directory");
+        assert!(!game_dir.exists(), "dry run wrote the game directory");
+    }
+}
@@ -0,0 +1,170 @@
+//! Terminal progress output for headless commands.
+
+use piston_lib::game::installer::types::{NotificationActionSpec, ProgressReporter};
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const BAR_WIDTH: usize = 30;
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Default)]
+struct BarState {
+    step: String,
+    message: String,
+    percent: i32,
+    last_draw: Option<Instant>,
+}
+
+/// Draws a single-line progress bar on stderr. When stderr is not a
+/// terminal (CI logs) only step changes and the final result are printed.
+pub struct TerminalProgressReporter {
+    state: Mutex<BarState>,
+    interactive: bool,
+    dry_run: bool,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl TerminalProgressReporter {
+    pub fn new(dry_run: bool, cancelled: Arc<AtomicBool>) -> Self {
+        Self {
+            state: Mutex::new(BarState::default()),
+            interactive: std::io::stderr().is_terminal(),
+            dry_run,
+            cancelled,
+        }
+    }
+
+    fn redraw(&self, state: &mut BarState, force: bool) {
+        if !self.interactive {
+            return;
+        }
+        let now = Instant::now();
+        if !force
+            && state
+                .last_draw
+                .is_some_and(|last| now.duration_since(last) < REDRAW_INTERVAL)
+        {
+            return;
+        }
+        state.last_draw = Some(now);
+        let line = render_line(state.percent, &state.step, &state.message);
+        let mut stderr = std::io::stderr().lock();
+        let _ = write!(stderr, "\r\x1b[2K{}", line);
+        let _ = stderr.flush();
+    }
+}
+
+/// `[#####-----]  50% step: message`; the bar is filled with `?` while
+/// progress is indeterminate (`percent < 0`).
+pub fn render_line(percent: i32, step: &str, message: &str) -> String {
+    let bar = if percent < 0 {
+        format!("[{}]  ...", "?".repeat(BAR_WIDTH))
+    } else {
+        let percent = percent.min(100) as usize;
+        let filled = percent * BAR_WIDTH / 100;
+        format!(
+            "[{}{}] {:>3}%",
+            "#".repeat(filled),
+            "-".repeat(BAR_WIDTH - filled),
+            percent
+        )
+    };
+    match (step.is_empty(), message.is_empty()) {
+        (true, true) => bar,
+        (false, true) => format!("{} {}", bar, step),
+        (true, false) => format!("{} {}", bar, message),
+        (false, false) => format!("{} {}: {}", bar, step, message),
+    }
+}
+
+impl ProgressReporter for TerminalProgressReporter {
+    fn start_step(&self, name: &str, _total_steps: Option<u32>) {
+        let mut state = self.state.lock().unwrap();
+        state.step = name.to_string();
+        state.message.clear();
+        if !self.interactive {
+            eprintln!("==> {}", name);
+        }
+        self.redraw(&mut state, true);
+    }
+
+    fn update_bytes(&self, _transferred: u64, _total: Option<u64>) {}
+
+    fn set_percent(&self, percent: i32) {
+        let mut state = self.state.lock().unwrap();
+        state.percent = percent;
+        self.redraw(&mut state, false);
+    }
+
+    fn set_message(&self, message: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.message = message.to_string();
+        self.redraw(&mut state, false);
+    }
+
+    fn set_step_count(&self, _current: u32, _total: Option<u32>) {}
+
+    fn set_substep(&self, name: Option<&str>, current: Option<u32>, total: Option<u32>) {
+        let mut state = self.state.lock().unwrap();
+        state.message = match (name, current, total) {
+            (Some(name), Some(current), Some(total)) => {
+                format!("{} ({}/{})", name, current, total)
+            }
+            (Some(name), _, _) => name.to_string(),
+            (None, _, _) => String::new(),
+        };
+        self.redraw(&mut state, false);
+    }
+
+    fn set_actions(&self, _actions: Option<Vec<NotificationActionSpec>>) {}
+
+    fn done(&self, success: bool, message: Option<&str>) {
+        let mut state = self.state.lock().unwrap();
+        if success {
+            state.percent = 100;
+        }
+        self.redraw(&mut state, true);
+        if self.interactive {
+            eprintln!();
+        }
+        if let Some(message) = message {
+            eprintln!("{}", message);
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn is_paused(&self) -> bool {
+        false
+    }
+
+    fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_bar_with_step_and_message() {
+        assert_eq!(
+            render_line(50, "Downloading libraries", "lwjgl (3/12)"),
+            format!(
+                "[{}{}]  50% Downloading libraries: lwjgl (3/12)",
+                "#".repeat(15),
+                "-".repeat(15)
+            )
+        );
+        assert_eq!(
+            render_line(250, "", ""),
+            format!("[{}] 100%", "#".repeat(BAR_WIDTH))
+        );
+        assert!(render_line(-1, "Resolving", "").ends_with("... Resolving"));
+    }
+}
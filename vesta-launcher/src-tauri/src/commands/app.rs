@@ -1,3 +1,4 @@
+use crate::instance::file_access;
 use crate::notifications::manager::NotificationManager;
 use crate::notifications::models::{CreateNotificationInput, NotificationType};
 use crate::utils::db_manager::get_app_config_dir;
@@ -148,39 +149,35 @@ pub fn open_app_runtime_storage_dir(app_handle: tauri::AppHandle) -> Result<(),
     Ok(())
 }
 
+/// Open the game directory, or one of its allowlisted folders (`mods`,
+/// `config`, `saves`, `screenshots`, `crash-reports`, `logs`), in the system
+/// file manager.
 #[tauri::command]
-pub fn open_instance_folder(instance_id_slug: String) -> Result<(), String> {
-    use crate::schema::instance::dsl::*;
-    use crate::utils::db::get_vesta_conn;
-    use diesel::prelude::*;
-
-    let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
-
-    let instances_list = instance
-        .select((crate::schema::instance::dsl::id, name, game_directory))
-        .load::<(i32, String, Option<String>)>(&mut conn)
-        .map_err(|e| format!("Failed to query instances: {}", e))?;
-
-    let found_dir = instances_list.into_iter().find_map(|(_id, _name, _gd)| {
-        let i_slug = crate::utils::sanitize::sanitize_instance_name(&_name);
-        if i_slug == instance_id_slug {
-            _gd
-        } else {
-            None
-        }
-    });
-
-    if let Some(gd) = found_dir {
-        let path = std::path::PathBuf::from(gd);
-        if !path.exists() {
-            std::fs::create_dir_all(&path)
-                .map_err(|e| format!("Failed to create instance directory: {}", e))?;
-        }
-        open::that(&path).map_err(|e| format!("Failed to open instance directory: {}", e))?;
-        Ok(())
-    } else {
-        Err("Instance not found".to_string())
+pub fn open_instance_folder(instance_id: i32, sub: Option<String>) -> Result<(), String> {
+    let game_dir = crate::commands::instances::instance_game_dir(instance_id)?;
+    if !game_dir.exists() {
+        std::fs::create_dir_all(&game_dir)
+            .map_err(|e| format!("Failed to create instance directory: {}", e))?;
     }
+    let folder = file_access::instance_subfolder(&game_dir, sub.as_deref())?;
+    open::that(&folder).map_err(|e| format!("Failed to open instance directory: {}", e))
+}
+
+/// Select a file of the instance in Explorer/Finder.
+#[tauri::command]
+pub fn reveal_file(instance_id: i32, relative_path: String) -> Result<(), String> {
+    let game_dir = crate::commands::instances::instance_game_dir(instance_id)?;
+    let path = file_access::resolve_instance_path(&game_dir, &relative_path)?;
+    file_access::reveal_in_file_manager(&path)
+}
+
+/// Open a text or image file of the instance with its default application.
+#[tauri::command]
+pub fn open_file_with_default_app(instance_id: i32, relative_path: String) -> Result<(), String> {
+    let game_dir = crate::commands::instances::instance_game_dir(instance_id)?;
+    let path = file_access::resolve_instance_path(&game_dir, &relative_path)?;
+    file_access::ensure_openable_file(&path)?;
+    open::that(&path).map_err(|e| format!("Failed to open {}: {}", relative_path, e))
 }
 
 #[tauri::command]
@@ -196,24 +193,27 @@ pub fn open_logs_folder(instance_id_slug: Option<String>) -> Result<(), String>
 
         let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
 
-        // Try to find by direct slug comparison (logic: iterate and check slug because slug is not in DB)
+        // Try to find by slug comparison (iterate and resolve the slug, since legacy rows don't store one)
         // Or simpler: Just construct the path from config dir + instances + slug,
         // because that IS the game directory structure we enforce now.
         // However, `game_directory` column exists.
 
         let instances_list = instance
-            .select((crate::schema::instance::dsl::id, name, game_directory))
-            .load::<(i32, String, Option<String>)>(&mut conn)
+            .select((crate::schema::instance::dsl::id, name, game_directory, slug))
+            .load::<(i32, String, Option<String>, Option<String>)>(&mut conn)
             .map_err(|e| format!("Failed to query instances: {}", e))?;
 
-        let found_dir = instances_list.into_iter().find_map(|(_id, _name, _gd)| {
-            let i_slug = crate::utils::sanitize::sanitize_instance_name(&_name);
-            if i_slug == slug_val {
-                _gd
-            } else {
-                None
-            }
-        });
+        let found_dir = instances_list
+            .into_iter()
+            .find_map(|(_id, _name, _gd, _slug)| {
+                let i_slug =
+                    crate::utils::sanitize::resolve_instance_slug(&_name, _slug.as_deref());
+                if i_slug == slug_val {
+                    _gd
+                } else {
+                    None
+                }
+            });
 
         if let Some(gd) = found_dir {
             std::path::PathBuf::from(gd).join("logs")
@@ -248,6 +248,27 @@ pub fn open_logs_folder(instance_id_slug: Option<String>) -> Result<(), String>
     Ok(())
 }
 
+/// Reclaim disk space from artifact blobs no install references anymore.
+#[tauri::command]
+pub async fn run_cache_gc(
+    app_handle: tauri::AppHandle,
+    task_manager: tauri::State<'_, crate::tasks::manager::TaskManager>,
+) -> Result<piston_lib::game::installer::cache::GCStats, String> {
+    // Installs ingest blobs before recording them; collecting mid-install
+    // would delete artifacts that are about to be referenced.
+    if !task_manager.get_active_tasks().is_empty() {
+        return Err("Cannot clean the artifact cache while tasks are running".to_string());
+    }
+
+    let config_dir = get_app_config_dir().map_err(|e| e.to_string())?;
+    let stats =
+        tokio::task::spawn_blocking(move || storage::collect_artifact_cache_garbage(&config_dir))
+            .await
+            .map_err(|e| format!("spawn_blocking panicked: {}", e))??;
+    let _ = app_handle.emit("storage-snapshot-invalidated", ());
+    Ok(stats)
+}
+
 #[tauri::command]
 pub async fn clear_cache(
     app_handle: tauri::AppHandle,
@@ -416,6 +437,32 @@ pub async fn refresh_network_status(
     Ok(status)
 }
 
+/// Connectivity and connection cost as seen by the background probe.
+#[tauri::command]
+pub fn get_network_health(
+    network_manager: tauri::State<'_, crate::utils::network::NetworkManager>,
+) -> crate::utils::network::NetworkHealth {
+    network_manager.health()
+}
+
+/// Benchmark the asset mirrors (cached for the session) and prefer the
+/// fastest for downloads.
+#[tauri::command]
+pub async fn benchmark_mirrors() -> Result<Vec<crate::utils::network::MirrorBenchmarkResult>, String>
+{
+    let candidates: Vec<String> = piston_lib::game::installer::asset_mirrors::BUNDLED_ASSET_MIRRORS
+        .iter()
+        .map(|mirror| mirror.to_string())
+        .collect();
+    let ranked = crate::utils::network::MirrorSelector::select_asset_mirror()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(crate::utils::network::benchmark_results(
+        &candidates,
+        &ranked,
+    ))
+}
+
 #[derive(serde::Deserialize)]
 pub struct ProxyTestInput {
     pub enabled: bool,
@@ -556,7 +603,7 @@ pub fn set_tray_icon_visibility(app: tauri::AppHandle, visible: bool) -> Result<
     )
     .map_err(|e| format!("Failed to update tray icon visibility: {}", e))?;
 
-    if let Some(tray) = app.tray_by_id("main-tray") {
+    if let Some(tray) = app.tray_by_id(crate::startup::tray::TRAY_ID) {
         tray.set_visible(visible)
             .map_err(|e| format!("Failed to apply tray icon visibility: {}", e))?;
     }
@@ -575,6 +622,125 @@ pub fn set_minimize_to_tray(app: tauri::AppHandle, enabled: bool) -> Result<(),
     Ok(())
 }
 
+/// Persist the rich presence toggle and start or stop the presence worker.
+#[tauri::command]
+pub fn set_discord_presence_enabled(
+    app: tauri::AppHandle,
+    discord: tauri::State<'_, crate::integrations::DiscordManager>,
+    enabled: bool,
+) -> Result<(), String> {
+    crate::utils::config::update_config_field(
+        app,
+        "discord_presence_enabled".to_string(),
+        serde_json::json!(enabled),
+    )
+    .map_err(|e| format!("Failed to update Discord presence setting: {}", e))?;
+    discord.set_enabled(enabled);
+    Ok(())
+}
+
+fn privacy_settings(
+    consent: crate::utils::privacy::UsageStatsConsent,
+) -> Result<crate::utils::privacy::PrivacySettings, String> {
+    let mut conn = crate::utils::db::get_vesta_conn().map_err(|e| e.to_string())?;
+    Ok(crate::utils::privacy::PrivacySettings {
+        usage_stats_consent: consent,
+        event_log_entries: crate::utils::event_log::count_events(&mut conn)?,
+    })
+}
+
+#[tauri::command]
+pub fn get_privacy_settings() -> Result<crate::utils::privacy::PrivacySettings, String> {
+    let config = crate::utils::config::get_app_config().map_err(|e| e.to_string())?;
+    privacy_settings(crate::utils::privacy::UsageStatsConsent::from_config(
+        &config.usage_stats_consent,
+    ))
+}
+
+/// Record the user's answer to the usage statistics prompt. An answered
+/// prompt can't be reset to unset.
+#[tauri::command]
+pub fn set_privacy_settings(
+    app: tauri::AppHandle,
+    usage_stats_consent: crate::utils::privacy::UsageStatsConsent,
+) -> Result<crate::utils::privacy::PrivacySettings, String> {
+    let config = crate::utils::config::get_app_config().map_err(|e| e.to_string())?;
+    let consent =
+        crate::utils::privacy::UsageStatsConsent::from_config(&config.usage_stats_consent)
+            .transition(usage_stats_consent)?;
+    crate::utils::config::update_config_field(
+        app,
+        "usage_stats_consent".to_string(),
+        serde_json::json!(consent.as_str()),
+    )
+    .map_err(|e| format!("Failed to update usage statistics consent: {}", e))?;
+    privacy_settings(consent)
+}
+
+/// Newest entries of the local event log, for the diagnostics panel.
+#[tauri::command]
+pub fn get_event_log(
+    limit: Option<i64>,
+) -> Result<Vec<crate::models::event_log::EventLogEntry>, String> {
+    let mut conn = crate::utils::db::get_vesta_conn().map_err(|e| e.to_string())?;
+    crate::utils::event_log::recent_events(
+        &mut conn,
+        limit.unwrap_or(crate::utils::event_log::MAX_EVENT_LOG_ROWS),
+    )
+}
+
+/// Delete the whole local event log. Returns the number of entries removed.
+#[tauri::command]
+pub fn purge_event_log() -> Result<usize, String> {
+    let mut conn = crate::utils::db::get_vesta_conn().map_err(|e| e.to_string())?;
+    let removed = crate::utils::event_log::purge_events(&mut conn)?;
+    log::info!("Purged {} event log entries", removed);
+    Ok(removed)
+}
+
+#[tauri::command]
+pub async fn check_for_updates(
+    app_handle: tauri::AppHandle,
+) -> Result<crate::startup::self_update::UpdateStatus, String> {
+    crate::startup::self_update::check_for_updates(&app_handle).await
+}
+
+/// Queue a task that zips scrubbed logs, versions and environment info for a
+/// support request. `dest_path` may be a folder or a `.zip` file path. The
+/// local event log is only added when `include_event_log` is set.
+#[tauri::command]
+pub async fn generate_diagnostics_bundle(
+    task_manager: tauri::State<'_, crate::tasks::manager::TaskManager>,
+    dest_path: String,
+    include_instance_logs: bool,
+    include_event_log: Option<bool>,
+) -> Result<(), String> {
+    if dest_path.trim().is_empty() {
+        return Err("A destination for the diagnostics bundle is required".to_string());
+    }
+    task_manager
+        .submit(Box::new(crate::tasks::diagnostics::DiagnosticsBundleTask {
+            dest_path: std::path::PathBuf::from(dest_path),
+            include_instance_logs,
+            include_event_log: include_event_log.unwrap_or(false),
+        }))
+        .await
+}
+
+#[tauri::command]
+pub fn get_update_status(
+    updater: tauri::State<'_, crate::startup::self_update::UpdaterState>,
+) -> crate::startup::self_update::UpdateStatus {
+    updater.status()
+}
+
+#[tauri::command]
+pub fn download_launcher_update(
+    app_handle: tauri::AppHandle,
+) -> Result<crate::startup::self_update::UpdateStatus, String> {
+    crate::startup::self_update::download_update(&app_handle)
+}
+
 #[tauri::command]
 pub fn show_window_from_tray(app: tauri::AppHandle) -> Result<(), String> {
     crate::utils::windows::ensure_main_window_visible(&app)?;
@@ -589,10 +755,12 @@ pub fn present_window_when_ready(
     if window.label() != label {
         return Err("Window readiness label did not match the calling window".to_string());
     }
-    if label != "main" {
+    let is_launcher_window =
+        label == "main" || label.starts_with(crate::utils::windows::EXTRA_LAUNCHER_WINDOW_PREFIX);
+    if !is_launcher_window {
         if !label.starts_with("page-viewer-") {
             return Err(
-                "Only the main window and mini windows can use readiness presentation".to_string(),
+                "Only launcher windows and mini windows can use readiness presentation".to_string(),
             );
         }
         let registry = window.state::<crate::utils::windows::MiniWindowRegistry>();
@@ -623,7 +791,7 @@ pub fn sync_tray_visibility_with_config(app: &tauri::AppHandle) -> Result<(), St
     let config = crate::utils::config::get_app_config()
         .map_err(|e| format!("Failed to get config for tray visibility sync: {}", e))?;
 
-    if let Some(tray) = app.tray_by_id("main-tray") {
+    if let Some(tray) = app.tray_by_id(crate::startup::tray::TRAY_ID) {
         tray.set_visible(config.show_tray_icon)
             .map_err(|e| format!("Failed to sync tray icon visibility: {}", e))?;
     }
@@ -29,6 +29,7 @@ fn find_instance_by_slug(instance_id_slug: &str) -> Result<Instance, String> {
     let mut conn =
         get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
     instance
+        .filter(deleted_at.is_null())
         .load::<Instance>(&mut conn)
         .map_err(|e| format!("Failed to query instances: {}", e))?
         .into_iter()
@@ -36,6 +37,17 @@ fn find_instance_by_slug(instance_id_slug: &str) -> Result<Instance, String> {
         .ok_or_else(|| format!("Instance {} not found in database", instance_id_slug))
 }
 
+/// Load an Instance by id, treating trashed rows as missing.
+fn find_live_instance(conn: &mut SqliteConnection, instance_id: i32) -> Result<Instance, String> {
+    instance
+        .find(instance_id)
+        .filter(deleted_at.is_null())
+        .first::<Instance>(conn)
+        .optional()
+        .map_err(|e| format!("Failed to fetch instance: {}", e))?
+        .ok_or_else(|| format!("Instance {} not found", instance_id))
+}
+
 struct LaunchInProgressGuard {
     instance_id: String,
 }
@@ -69,8 +81,8 @@ impl Drop for LaunchInProgressGuard {
 }
 
 /// Compute canonical instance game directory path under the given instances root
-fn compute_instance_game_dir(root: &std::path::Path, slug: &str) -> String {
-    root.join(slug).to_string_lossy().to_string()
+fn compute_instance_game_dir(root: &std::path::Path, instance_slug: &str) -> String {
+    root.join(instance_slug).to_string_lossy().to_string()
 }
 
 #[tauri::command]
@@ -145,6 +157,7 @@ fn persist_crash_mclogs_url(
     let mut conn =
         get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
     let all_instances = instance
+        .filter(deleted_at.is_null())
         .load::<Instance>(&mut conn)
         .map_err(|e| format!("Failed to query instances: {}", e))?;
     let inst = all_instances
@@ -281,6 +294,17 @@ fn resolve_instance_game_dir_for_upload(inst: &Instance) -> Result<PathBuf, Stri
     )
 }
 
+/// Game directory of the instance with id `instance_id`.
+pub(crate) fn instance_game_dir(instance_id: i32) -> Result<PathBuf, String> {
+    let mut conn =
+        get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
+    let inst: Instance = instance
+        .find(instance_id)
+        .first(&mut conn)
+        .map_err(|e| format!("Instance {} not found: {}", instance_id, e))?;
+    resolve_instance_game_dir_for_upload(&inst)
+}
+
 fn canonical_crash_upload_path(path: &Path, game_dir: &Path) -> Result<PathBuf, String> {
     let canonical_game_dir = game_dir
         .canonicalize()
@@ -305,7 +329,7 @@ fn read_redacted_log_file(path: &Path) -> Result<String, String> {
 
     let content = std::fs::read_to_string(path)
         .map_err(|e| format!("Failed to read crash log {:?}: {}", path, e))?;
-    let redacted = redact_log_content(&content);
+    let redacted = crate::utils::redact::redact_log_content(&content);
     enforce_mclogs_limits(&redacted)?;
     Ok(redacted)
 }
@@ -350,321 +374,6 @@ fn enforce_mclogs_limits(content: &str) -> Result<(), String> {
     Ok(())
 }
 
-fn redact_log_content(content: &str) -> String {
-    content
-        .lines()
-        .map(|line| {
-            let mut redacted = redact_paths(line);
-            redacted = redact_sensitive_assignments(&redacted);
-            redact_ipv4_tokens(&redacted)
-        })
-        .collect::<Vec<_>>()
-        .join("\n")
-}
-
-fn redact_paths(line: &str) -> String {
-    let vesta_redacted = redact_vesta_path_prefixes(line);
-    redact_non_vesta_absolute_paths(&vesta_redacted)
-}
-
-fn redact_vesta_path_prefixes(line: &str) -> String {
-    let mut redacted = line.to_string();
-    for marker in [".VestaLauncher", "VestaLauncher"] {
-        let mut search_from = 0;
-        while let Some(offset) = redacted[search_from..].find(marker) {
-            let marker_start = search_from + offset;
-            let Some(path_start) = find_path_start_before(&redacted, marker_start) else {
-                search_from = marker_start + marker.len();
-                continue;
-            };
-
-            redacted.replace_range(path_start..marker_start, "<path>/");
-            search_from = path_start + "<path>/".len() + marker.len();
-        }
-    }
-    redacted
-}
-
-fn find_path_start_before(line: &str, marker_start: usize) -> Option<usize> {
-    let prefix = &line[..marker_start];
-    let mut path_start = None;
-    for (idx, _) in prefix.match_indices('/') {
-        if idx == 0 || is_path_prefix_boundary(prefix.as_bytes()[idx - 1] as char) {
-            path_start = Some(idx);
-        }
-    }
-
-    let bytes = prefix.as_bytes();
-    for idx in (0..bytes.len().saturating_sub(2)).rev() {
-        if bytes[idx].is_ascii_alphabetic()
-            && bytes[idx + 1] == b':'
-            && (bytes[idx + 2] == b'\\' || bytes[idx + 2] == b'/')
-            && (idx == 0 || is_path_prefix_boundary(bytes[idx - 1] as char))
-        {
-            path_start = Some(path_start.map_or(idx, |existing| existing.max(idx)));
-            break;
-        }
-    }
-
-    path_start
-}
-
-fn is_path_prefix_boundary(ch: char) -> bool {
-    ch.is_whitespace() || matches!(ch, '"' | '\'' | '(' | '[' | '{' | '<' | '=')
-}
-
-fn redact_non_vesta_absolute_paths(line: &str) -> String {
-    let mut redacted = String::with_capacity(line.len());
-    let mut cursor = 0;
-
-    while cursor < line.len() {
-        let Some(start) =
-            find_next_absolute_path_start(line, cursor).filter(|start| *start >= cursor)
-        else {
-            redacted.push_str(&line[cursor..]);
-            break;
-        };
-        let end = absolute_path_end(line, start);
-        redacted.push_str(&line[cursor..start]);
-        redacted.push_str("<path redacted>");
-        cursor = end;
-    }
-
-    redacted
-}
-
-fn find_next_absolute_path_start(line: &str, from: usize) -> Option<usize> {
-    find_unix_path_start(line, from)
-        .into_iter()
-        .chain(find_windows_path_start(line, from))
-        .min()
-}
-
-fn find_unix_path_start(line: &str, from: usize) -> Option<usize> {
-    line[from..].match_indices('/').find_map(|(offset, _)| {
-        let idx = from + offset;
-        if idx == 0 || is_path_prefix_boundary(line.as_bytes()[idx - 1] as char) {
-            Some(idx)
-        } else {
-            None
-        }
-    })
-}
-
-fn find_windows_path_start(line: &str, from: usize) -> Option<usize> {
-    let bytes = line.as_bytes();
-    (from..bytes.len().saturating_sub(2)).find(|&idx| {
-        bytes[idx].is_ascii_alphabetic()
-            && bytes[idx + 1] == b':'
-            && (bytes[idx + 2] == b'\\' || bytes[idx + 2] == b'/')
-            && (idx == 0 || is_path_prefix_boundary(bytes[idx - 1] as char))
-    })
-}
-
-fn absolute_path_end(line: &str, start: usize) -> usize {
-    let quote = start
-        .checked_sub(1)
-        .and_then(|idx| line.as_bytes().get(idx))
-        .copied()
-        .filter(|byte| matches!(byte, b'"' | b'\''));
-    if let Some(quote) = quote {
-        if let Some(offset) = line[start..].find(quote as char) {
-            return start + offset;
-        }
-    }
-
-    let mut end = token_end(line, start);
-    loop {
-        let whitespace_end = skip_whitespace(line, end);
-        if whitespace_end == end || whitespace_end >= line.len() {
-            return end;
-        }
-
-        if find_unix_path_start(line, whitespace_end) == Some(whitespace_end)
-            || find_windows_path_start(line, whitespace_end) == Some(whitespace_end)
-        {
-            return end;
-        }
-
-        let next_end = token_end(line, whitespace_end);
-        let next = &line[whitespace_end..next_end];
-        if next.contains('/') || next.contains('\\') {
-            end = next_end;
-            continue;
-        }
-        return end;
-    }
-}
-
-fn token_end(line: &str, start: usize) -> usize {
-    line[start..]
-        .char_indices()
-        .find(|(_, ch)| {
-            ch.is_whitespace() || matches!(ch, '"' | '\'' | ')' | ']' | '}' | '<' | '>' | '|')
-        })
-        .map(|(offset, ch)| {
-            if offset == 0 {
-                start + ch.len_utf8()
-            } else {
-                start + offset
-            }
-        })
-        .unwrap_or(line.len())
-}
-
-fn skip_whitespace(line: &str, start: usize) -> usize {
-    line[start..]
-        .find(|ch: char| !ch.is_whitespace())
-        .map(|offset| start + offset)
-        .unwrap_or(line.len())
-}
-
-fn redact_sensitive_assignments(line: &str) -> String {
-    let sensitive = [
-        "access_token",
-        "accesstoken",
-        "authorization",
-        "session",
-        "token",
-        "client_secret",
-    ];
-    let lower = line.to_lowercase();
-    if !sensitive.iter().any(|key| lower.contains(key)) {
-        return line.to_string();
-    }
-
-    let mut redacted = line.to_string();
-    for key in sensitive {
-        redacted = redact_key_values(&redacted, key);
-        redacted = redact_split_argument_values(&redacted, key);
-    }
-    redacted
-}
-
-fn redact_ipv4_tokens(line: &str) -> String {
-    let mut redacted = String::with_capacity(line.len());
-    let mut cursor = 0;
-    while cursor < line.len() {
-        let end = if line.as_bytes()[cursor].is_ascii_whitespace() {
-            skip_whitespace(line, cursor)
-        } else {
-            token_end(line, cursor)
-        };
-        let part = &line[cursor..end];
-        let trimmed = part.trim_matches(|c: char| !c.is_ascii_digit() && c != '.');
-        if trimmed.parse::<std::net::Ipv4Addr>().is_ok() {
-            redacted.push_str(&part.replace(trimmed, "**.**.**.**"));
-        } else {
-            redacted.push_str(part);
-        }
-        cursor = end;
-    }
-    redacted
-}
-
-fn redact_key_values(line: &str, key: &str) -> String {
-    let lower = line.to_lowercase();
-    let mut redacted = String::with_capacity(line.len());
-    let mut cursor = 0;
-
-    while let Some(offset) = lower[cursor..].find(key) {
-        let key_start = cursor + offset;
-        let key_end = key_start + key.len();
-        let Some((value_start, quoted)) = find_sensitive_value_start(line, key_end) else {
-            redacted.push_str(&line[cursor..key_end]);
-            cursor = key_end;
-            continue;
-        };
-        let value_end = sensitive_value_end(line, value_start, quoted);
-        redacted.push_str(&line[cursor..value_start]);
-        redacted.push_str("<redacted>");
-        cursor = value_end;
-    }
-
-    redacted.push_str(&line[cursor..]);
-    redacted
-}
-
-fn find_sensitive_value_start(line: &str, key_end: usize) -> Option<(usize, Option<u8>)> {
-    let bytes = line.as_bytes();
-    let mut idx = key_end;
-    if matches!(bytes.get(idx), Some(b'"' | b'\'')) {
-        idx += 1;
-    }
-    while matches!(bytes.get(idx), Some(b' ' | b'\t')) {
-        idx += 1;
-    }
-    if !matches!(bytes.get(idx), Some(b'=' | b':')) {
-        return None;
-    }
-    idx += 1;
-    while matches!(bytes.get(idx), Some(b' ' | b'\t')) {
-        idx += 1;
-    }
-    let quote = bytes
-        .get(idx)
-        .copied()
-        .filter(|byte| matches!(byte, b'"' | b'\''));
-    if quote.is_some() {
-        idx += 1;
-    }
-    Some((idx, quote))
-}
-
-fn sensitive_value_end(line: &str, value_start: usize, quoted: Option<u8>) -> usize {
-    if let Some(quote) = quoted {
-        return line[value_start..]
-            .find(quote as char)
-            .map(|offset| value_start + offset)
-            .unwrap_or(line.len());
-    }
-
-    let mut end = line[value_start..]
-        .find(|ch: char| ch.is_whitespace() || matches!(ch, '&' | ',' | '}' | ']'))
-        .map(|offset| value_start + offset)
-        .unwrap_or(line.len());
-
-    if line[value_start..end].eq_ignore_ascii_case("bearer") {
-        let next_start = skip_whitespace(line, end);
-        if next_start > end && next_start < line.len() {
-            end = token_end(line, next_start);
-        }
-    }
-
-    end
-}
-
-fn redact_split_argument_values(line: &str, key: &str) -> String {
-    let lower = line.to_lowercase();
-    let mut redacted = String::with_capacity(line.len());
-    let mut cursor = 0;
-
-    while let Some(offset) = lower[cursor..].find(key) {
-        let key_start = cursor + offset;
-        let key_end = key_start + key.len();
-        if key_start < 2 || &line[key_start - 2..key_start] != "--" {
-            redacted.push_str(&line[cursor..key_end]);
-            cursor = key_end;
-            continue;
-        }
-
-        let value_start = skip_whitespace(line, key_end);
-        if value_start == key_end || value_start >= line.len() {
-            redacted.push_str(&line[cursor..key_end]);
-            cursor = key_end;
-            continue;
-        }
-
-        let value_end = token_end(line, value_start);
-        redacted.push_str(&line[cursor..value_start]);
-        redacted.push_str("<redacted>");
-        cursor = value_end;
-    }
-
-    redacted.push_str(&line[cursor..]);
-    redacted
-}
-
 #[tauri::command]
 pub async fn install_instance(
     app_handle: tauri::AppHandle,
@@ -771,6 +480,8 @@ pub async fn install_instance(
         instance_data.modloader_version
     );
 
+    ensure_loader_compatible(&instance_data).await?;
+
     log::info!("[install_instance] Creating InstallInstanceTask");
     let mut task = InstallInstanceTask::new(instance_data.clone());
     if let Some(dr) = dry_run {
@@ -809,6 +520,109 @@ pub async fn install_instance(
     }
 }
 
+/// Move an instance to another Minecraft and/or loader version. With
+/// `dry_run` only the plan (warnings, incompatible mods) is returned;
+/// otherwise the new version is installed and the instance row switches to
+/// it once that install succeeds.
+#[tauri::command]
+pub async fn change_instance_version(
+    app_handle: tauri::AppHandle,
+    task_manager: State<'_, TaskManager>,
+    resource_manager: State<'_, crate::resources::ResourceManager>,
+    instance_id: i32,
+    new_mc_version: String,
+    new_loader: Option<String>,
+    new_loader_version: Option<String>,
+    dry_run: Option<bool>,
+) -> Result<crate::instance::version_change::VersionChangePlan, String> {
+    use crate::schema::installed_resource::dsl as ir_dsl;
+    use crate::utils::instance_runtime::InstanceRuntimeFields;
+
+    let (inst, resources) = {
+        let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
+        let inst = instance
+            .find(instance_id)
+            .first::<Instance>(&mut conn)
+            .map_err(|e| format!("Instance not found: {}", e))?;
+        let resources = ir_dsl::installed_resource
+            .filter(ir_dsl::instance_id.eq(instance_id))
+            .load::<crate::models::installed_resource::InstalledResource>(&mut conn)
+            .map_err(|e| format!("Failed to load installed resources: {}", e))?;
+        (inst, resources)
+    };
+
+    if inst.installation_status.as_deref() == Some("installing") {
+        return Err("Wait for the current installation to finish first".to_string());
+    }
+    if piston_lib::game::launcher::is_instance_running(&inst.slug())
+        .await
+        .map_err(|e| format!("Failed to check instance run state: {}", e))?
+    {
+        return Err("Close the game before changing its version".to_string());
+    }
+
+    let metadata = crate::utils::manifest::load_manifest(&app_handle).await?;
+    let from = InstanceRuntimeFields::from_instance(&inst);
+    let to = crate::instance::version_change::resolve_target(
+        &metadata.game_versions,
+        &new_mc_version,
+        new_loader.as_deref(),
+        new_loader_version.as_deref(),
+    )?;
+    if !crate::utils::instance_runtime::runtime_drifts(&inst, &to) {
+        return Err(format!(
+            "{} is already on Minecraft {}",
+            inst.name, to.minecraft_version
+        ));
+    }
+
+    let mut target = inst.clone();
+    target.minecraft_version = to.minecraft_version.clone();
+    target.modloader = to.modloader.clone();
+    target.modloader_version = to.modloader_version.clone();
+    ensure_loader_compatible(&target).await?;
+
+    let source_override = crate::resources::sources::custom::SourceOverride::from_column(
+        inst.instance_source_override.as_deref(),
+    );
+    let mut plan = crate::instance::version_change::plan(
+        resource_manager.inner(),
+        &metadata.game_versions,
+        instance_id,
+        from.clone(),
+        to,
+        resources,
+        source_override.as_ref(),
+    )
+    .await;
+    if dry_run.unwrap_or(false) {
+        return Ok(plan);
+    }
+
+    log::info!(
+        "[change_instance_version] {} -> {:?} for instance {}",
+        inst.minecraft_version,
+        plan.to,
+        inst.name
+    );
+    if let Ok(Some(acc)) = crate::auth::get_active_account() {
+        if acc.account_type == ACCOUNT_TYPE_GUEST || acc.account_type == ACCOUNT_TYPE_DEMO {
+            return Err(
+                "You must be signed in with a Microsoft account to install Minecraft.".to_string(),
+            );
+        }
+    }
+
+    let mut task = InstallInstanceTask::new(target);
+    task.set_version_change(from);
+    task_manager.submit(Box::new(task)).await?;
+    let _ = update_instance_operation(&app_handle, instance_id, "install");
+    let _ = update_installation_status(&app_handle, instance_id, "installing");
+
+    plan.queued = true;
+    Ok(plan)
+}
+
 pub fn process_instance_icon(mut inst: Instance) -> Instance {
     // If we have icon_data, we should prefer serving it via base64 for offline compatibility,
     // unless the icon_path is a gradient (which doesn't use icon_data).
@@ -850,6 +664,7 @@ pub fn list_instances() -> Result<Vec<Instance>, String> {
         get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
 
     let instances = instance
+        .filter(deleted_at.is_null())
         .order((last_played.desc(), created_at.desc()))
         .load::<Instance>(&mut conn)
         .map_err(|e| format!("Failed to query instances: {}", e))?;
@@ -867,10 +682,30 @@ pub fn list_instances() -> Result<Vec<Instance>, String> {
     Ok(processed)
 }
 
+/// Instances whose name or notes match `query`, best match first.
+#[tauri::command]
+pub fn search_instances(query: String) -> Result<Vec<Instance>, String> {
+    if let Ok(Some(active_acc)) = crate::auth::get_active_account() {
+        if active_acc.account_type == ACCOUNT_TYPE_GUEST
+            || active_acc.account_type == ACCOUNT_TYPE_DEMO
+        {
+            return Ok(vec![]);
+        }
+    }
+
+    let mut conn =
+        get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
+    let instances = crate::instance::search::search_instances(&mut conn, &query)
+        .map_err(|e| format!("Failed to search instances: {}", e))?;
+    Ok(instances.into_iter().map(process_instance_icon).collect())
+}
+
 #[tauri::command]
 pub async fn create_instance(
     app_handle: tauri::AppHandle,
     instance_data: Instance,
+    copy_settings_from_instance: Option<i32>,
+    copy_resource_packs: Option<bool>,
     resource_watcher: State<'_, ResourceWatcher>,
 ) -> Result<i32, String> {
     log::info!(
@@ -920,18 +755,20 @@ pub async fn create_instance(
     );
 
     // Fetch existing instance names and compute their slugs
-    let existing_names: Vec<String> = instance
-        .select(name)
-        .load::<String>(&mut conn)
+    let existing_names: Vec<(String, Option<String>)> = instance
+        .filter(deleted_at.is_null())
+        .select((name, slug))
+        .load::<(String, Option<String>)>(&mut conn)
         .map_err(|e| format!("Failed to query existing instance names: {}", e))?;
 
     let mut seen_names = std::collections::HashSet::new();
     let mut seen_slugs = std::collections::HashSet::new();
-    for existing_name in existing_names {
-        seen_names.insert(existing_name.to_lowercase());
-        seen_slugs.insert(crate::utils::sanitize::sanitize_instance_name(
+    for (existing_name, existing_slug) in existing_names {
+        seen_slugs.insert(crate::utils::sanitize::resolve_instance_slug(
             &existing_name,
+            existing_slug.as_deref(),
         ));
+        seen_names.insert(existing_name.to_lowercase());
     }
 
     // Check for duplicate name (case-insensitive) and make it unique
@@ -983,7 +820,7 @@ pub async fn create_instance(
     }
 
     // Fetch existing instance names and compute their slugs
-    let slug = crate::utils::instance_helpers::compute_unique_slug(
+    let instance_slug = crate::utils::instance_helpers::compute_unique_slug(
         &inst.name,
         &seen_slugs,
         &instances_root,
@@ -991,7 +828,7 @@ pub async fn create_instance(
 
     // Always set the instance game_directory to the configured instances root
     // using the computed instance id (slug).
-    let gd = compute_instance_game_dir(&instances_root, &slug);
+    let gd = compute_instance_game_dir(&instances_root, &instance_slug);
     if inst.game_directory.is_some() {
         log::info!(
             "[create_instance] Overriding supplied game_directory with instances root path: {}",
@@ -1016,6 +853,7 @@ pub async fn create_instance(
     }
 
     log::info!("[create_instance] Inserting instance into database");
+    let target_loader = inst.modloader.clone();
 
     // Create NewInstance from Instance (excluding ID which works automatically)
     let new_instance = NewInstance {
@@ -1059,6 +897,7 @@ pub async fn create_instance(
         pre_launch_hook: inst.pre_launch_hook,
         wrapper_command: inst.wrapper_command,
         post_exit_hook: inst.post_exit_hook,
+        slug: Some(instance_slug.clone()),
     };
 
     diesel::insert_into(instance)
@@ -1076,6 +915,28 @@ pub async fn create_instance(
         .first(&mut conn)
         .map_err(|e| format!("Failed to get inserted instance ID: {}", e))?;
 
+    if let Some(source_id) = copy_settings_from_instance {
+        match crate::instance::settings_copy::copy_from_instance(
+            &mut conn,
+            source_id,
+            inserted_id,
+            std::path::Path::new(&gd),
+            target_loader.as_deref(),
+            copy_resource_packs.unwrap_or(false),
+        ) {
+            Ok(report) => log::info!(
+                "[create_instance] Copied {:?} from instance {}",
+                report.copied,
+                source_id
+            ),
+            Err(e) => log::warn!(
+                "[create_instance] Failed to copy settings from instance {}: {}",
+                source_id,
+                e
+            ),
+        }
+    }
+
     // Fetch the full instance and emit created event
     if let Ok(full_instance) = instance.find(inserted_id).first::<Instance>(&mut conn) {
         use tauri::Emitter;
@@ -1096,17 +957,17 @@ pub async fn create_instance(
     if skip_initial_watch {
         log::info!(
             "[create_instance] Skipping initial resource watcher for instance: {} ({})",
-            slug,
+            instance_slug,
             inserted_id
         );
     } else {
         log::info!(
             "[create_instance] Initializing resource watcher for instance: {} ({})",
-            slug,
+            instance_slug,
             inserted_id
         );
         if let Err(e) = resource_watcher
-            .watch_instance(slug.clone(), inserted_id, gd)
+            .watch_instance(instance_slug.clone(), inserted_id, gd)
             .await
         {
             log::error!("[create_instance] Failed to start resource watcher: {}", e);
@@ -1116,7 +977,7 @@ pub async fn create_instance(
     log::info!(
         "[create_instance] Instance created successfully with ID: {} and slug: {}",
         inserted_id,
-        slug
+        instance_slug
     );
 
     Ok(inserted_id)
@@ -1130,9 +991,30 @@ mod tests {
     #[test]
     fn compute_game_dir_uses_slug() {
         let root = PathBuf::from("C:/Users/test/.VestaLauncher/instances");
-        let slug = "my-instance";
-        let got = compute_instance_game_dir(&root, slug);
-        assert_eq!(got, root.join(slug).to_string_lossy().to_string());
+        let instance_slug = "my-instance";
+        let got = compute_instance_game_dir(&root, instance_slug);
+        assert_eq!(got, root.join(instance_slug).to_string_lossy().to_string());
+    }
+
+    #[test]
+    fn find_live_instance_treats_trashed_rows_as_missing() {
+        use diesel::connection::SimpleConnection;
+        use diesel_migrations::MigrationHarness;
+
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.run_pending_migrations(crate::utils::db::VESTA_MIGRATIONS)
+            .unwrap();
+        conn.batch_execute(
+            "INSERT INTO instance (id, name, minecraft_version) VALUES (1, 'Live', '1.21.1');
+             INSERT INTO instance (id, name, minecraft_version, deleted_at)
+                VALUES (2, 'Trashed', '1.21.1', '2026-10-01T00:00:00Z');",
+        )
+        .unwrap();
+
+        assert_eq!(find_live_instance(&mut conn, 1).unwrap().name, "Live");
+        let err = find_live_instance(&mut conn, 2).unwrap_err();
+        assert_eq!(err, "Instance 2 not found");
+        assert!(find_live_instance(&mut conn, 3).is_err());
     }
 }
 
@@ -1141,7 +1023,7 @@ pub async fn update_instance(
     app_handle: tauri::AppHandle,
     instance_data: Instance,
     resource_watcher: tauri::State<'_, crate::resources::watcher::ResourceWatcher>,
-) -> Result<(), String> {
+) -> Result<Option<crate::utils::java::JavaPathReport>, String> {
     log::info!(
         "[update_instance] Updating instance: {:?}",
         instance_data.id
@@ -1207,16 +1089,71 @@ pub async fn update_instance(
     }
 
     // Fetch the existing row so we can detect name changes and old game_directory
-    let existing_row: (String, Option<String>) = instance
+    let existing_row: (
+        String,
+        Option<String>,
+        String,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+    ) = instance
         .find(update_id)
-        .select((name, game_directory))
+        .select((
+            name,
+            game_directory,
+            minecraft_version,
+            modloader,
+            modloader_version,
+            java_path,
+            slug,
+        ))
         .first(&mut conn)
         .map_err(|e| format!("Failed to query existing instance: {}", e))?;
 
+    // Editing the version columns alone would leave the old version's files
+    // in place; version changes go through change_instance_version.
+    let existing_runtime = crate::utils::instance_runtime::InstanceRuntimeFields {
+        minecraft_version: existing_row.2,
+        modloader: existing_row.3,
+        modloader_version: existing_row.4,
+    };
+    if crate::utils::instance_runtime::runtime_drifts(&final_instance, &existing_runtime) {
+        return Err(
+            "Use change_instance_version to change the Minecraft or loader version".to_string(),
+        );
+    }
+
+    // Check a newly set Java override before anything is moved. Problems are
+    // reported back but saved anyway; only a wrong-arch Java is refused.
+    let mut java_report = None;
+    if let Some(new_java) = final_instance
+        .java_path
+        .as_deref()
+        .filter(|path| !path.is_empty() && !final_instance.use_global_java_path)
+    {
+        if existing_row.5.as_deref() != Some(new_java) {
+            let required_major = crate::utils::java::resolve_required_java_major(
+                &app_handle,
+                &final_instance.minecraft_version,
+            )
+            .await
+            .ok();
+            java_report = crate::utils::java::check_java_override(new_java, required_major).await?;
+        }
+    }
+
     let old_name = existing_row.0;
 
-    let old_slug = crate::utils::sanitize::sanitize_instance_name(&old_name);
-    let mut new_slug = instance_data.slug();
+    let old_slug =
+        crate::utils::sanitize::resolve_instance_slug(&old_name, existing_row.6.as_deref());
+    // A rename moves the instance onto the current slug scheme; otherwise the
+    // stored (or legacy) slug is kept so its id doesn't change.
+    let mut new_slug = if final_instance.name == old_name {
+        old_slug.clone()
+    } else {
+        crate::utils::sanitize::sanitize_instance_name(&final_instance.name)
+    };
 
     // If slug changes, compute unique slug against other instances and filesystem
     if old_slug != new_slug {
@@ -1238,15 +1175,19 @@ pub async fn update_instance(
         };
 
         // Build set of seen slugs excluding current row
-        let existing_instances: Vec<(i32, String)> = instance
-            .select((id, name))
+        let existing_instances: Vec<(i32, String, Option<String>)> = instance
+            .filter(deleted_at.is_null())
+            .select((id, name, slug))
             .load(&mut conn)
             .map_err(|e| format!("Failed to query existing instances: {}", e))?;
 
         let mut seen = std::collections::HashSet::new();
-        for (rid, rname) in existing_instances {
+        for (rid, rname, rslug) in existing_instances {
             if rid != update_id {
-                seen.insert(crate::utils::sanitize::sanitize_instance_name(&rname));
+                seen.insert(crate::utils::sanitize::resolve_instance_slug(
+                    &rname,
+                    rslug.as_deref(),
+                ));
             }
         }
 
@@ -1355,7 +1296,7 @@ pub async fn update_instance(
         }
         if let Some(ref gd) = final_instance.game_directory {
             if let Err(e) = resource_watcher
-                .watch_instance(new_slug, update_id, gd.clone())
+                .watch_instance(new_slug.clone(), update_id, gd.clone())
                 .await
             {
                 log::warn!("[update_instance] Failed to re-watch after rename: {}", e);
@@ -1400,6 +1341,7 @@ pub async fn update_instance(
             pre_launch_hook.eq(&final_instance.pre_launch_hook),
             wrapper_command.eq(&final_instance.wrapper_command),
             post_exit_hook.eq(&final_instance.post_exit_hook),
+            slug.eq(Some(&new_slug)),
             updated_at.eq(&now),
         ))
         .execute(&mut conn)
@@ -1417,7 +1359,7 @@ pub async fn update_instance(
     use tauri::Emitter;
     let _ = app_handle.emit("core://instance-updated", process_instance_icon(updated));
 
-    Ok(())
+    Ok(java_report)
 }
 
 #[tauri::command]
@@ -1441,6 +1383,84 @@ pub async fn delete_instance(
     Ok(())
 }
 
+#[tauri::command]
+pub fn list_deleted_instances() -> Result<Vec<Instance>, String> {
+    let mut conn =
+        get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    let trashed = instance
+        .filter(deleted_at.is_not_null())
+        .order(deleted_at.desc())
+        .load::<Instance>(&mut conn)
+        .map_err(|e| format!("Failed to query deleted instances: {}", e))?;
+
+    Ok(trashed.into_iter().map(process_instance_icon).collect())
+}
+
+#[tauri::command]
+pub async fn restore_instance(
+    app_handle: tauri::AppHandle,
+    instance_id: i32,
+    resource_watcher: tauri::State<'_, crate::resources::watcher::ResourceWatcher>,
+) -> Result<Instance, String> {
+    log::info!("[restore_instance] instance_id={}", instance_id);
+
+    let mut conn =
+        get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
+    let trashed = instance
+        .find(instance_id)
+        .first::<Instance>(&mut conn)
+        .map_err(|e| format!("Instance not found: {}", e))?;
+
+    let config = crate::utils::config::get_app_config().map_err(|e| e.to_string())?;
+    let app_config_dir = crate::utils::db_manager::get_app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {}", e))?;
+    let instances_root = crate::utils::instance_helpers::resolve_instances_root(
+        &app_config_dir,
+        config.default_game_dir.as_deref(),
+    );
+
+    let restored = tokio::task::spawn_blocking(move || {
+        crate::instance::trash::restore_from_trash(&mut conn, &trashed, &instances_root)
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking panicked: {}", e))??;
+
+    if let Some(ref gd) = restored.game_directory {
+        if let Err(e) = resource_watcher
+            .watch_instance(restored.slug(), restored.id, gd.clone())
+            .await
+        {
+            log::warn!("[restore_instance] Failed to start resource watcher: {}", e);
+        }
+    }
+
+    let restored = process_instance_icon(restored);
+    let _ = app_handle.emit("core://instance-created", restored.clone());
+    log::info!(
+        "[restore_instance] Restored instance {} as '{}'",
+        instance_id,
+        restored.name
+    );
+    Ok(restored)
+}
+
+#[tauri::command]
+pub async fn purge_deleted_instances(older_than_days: u32) -> Result<Vec<i32>, String> {
+    log::info!(
+        "[purge_deleted_instances] purging trash older than {} days",
+        older_than_days
+    );
+    let mut conn =
+        get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
+
+    tokio::task::spawn_blocking(move || {
+        crate::instance::trash::purge_expired(&mut conn, older_than_days as i64)
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking panicked: {}", e))?
+}
+
 #[tauri::command]
 pub async fn get_instance_required_java(
     app_handle: tauri::AppHandle,
@@ -1454,6 +1474,34 @@ pub async fn get_instance_required_java(
     crate::utils::java::resolve_required_java_major(&app_handle, &inst.minecraft_version).await
 }
 
+/// Installed Javas for the instance, best match for its version first.
+#[tauri::command]
+pub async fn suggest_java_for_instance(
+    app_handle: tauri::AppHandle,
+    instance_id: i32,
+) -> Result<Vec<crate::utils::java::JavaSuggestion>, String> {
+    let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
+    let inst = instance
+        .find(instance_id)
+        .first::<Instance>(&mut conn)
+        .map_err(|e| e.to_string())?;
+    let required_major =
+        crate::utils::java::resolve_required_java_major(&app_handle, &inst.minecraft_version)
+            .await
+            .map_err(|e| log::warn!("[suggest_java_for_instance] {}", e))
+            .ok();
+
+    tokio::task::spawn_blocking(move || {
+        crate::utils::java::rank_java_candidates(
+            crate::utils::java::get_managed_javas(),
+            crate::utils::java::scan_system_javas_filtered(),
+            required_major,
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn get_instance(instance_id: i32) -> Result<Instance, String> {
     log::info!("Fetching instance ID: {}", instance_id);
@@ -1471,14 +1519,50 @@ pub fn get_instance(instance_id: i32) -> Result<Instance, String> {
 }
 
 #[tauri::command]
-pub fn get_instance_by_slug(slug_val: String) -> Result<Instance, String> {
-    log::info!("Fetching instance by slug: {}", slug_val);
-
+pub fn get_instance_history(
+    instance_id: i32,
+    limit: Option<i64>,
+) -> Result<Vec<crate::models::InstanceOperation>, String> {
+    let limit = limit
+        .unwrap_or(20)
+        .clamp(1, crate::instance::history::MAX_OPERATIONS_PER_INSTANCE);
+    let mut conn =
+        get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
+    crate::instance::history::recent_operations(&mut conn, instance_id, limit)
+}
+
+/// Installer step timings of the newest install run plus aggregates across
+/// runs, for the debug panel.
+#[tauri::command]
+pub fn get_install_metrics(
+    instance_id: i32,
+) -> Result<crate::instance::history::InstallMetricsReport, String> {
+    let mut conn =
+        get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
+    crate::instance::history::install_metrics_report(&mut conn, instance_id)
+}
+
+/// Use `url` as the Forge maven mirror for the rest of the session. An empty
+/// string goes back to picking the fastest bundled mirror.
+#[tauri::command]
+pub fn set_forge_mirror(url: String) -> Result<(), String> {
+    let url = url.trim();
+    piston_lib::game::installer::modloaders::forge_mirrors::set_mirror_override(
+        (!url.is_empty()).then_some(url),
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_instance_by_slug(slug_val: String) -> Result<Instance, String> {
+    log::info!("Fetching instance by slug: {}", slug_val);
+
     let mut conn =
         get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
 
     // Inefficient but compatible: fetch all and matching slug
     let instances_list = instance
+        .filter(deleted_at.is_null())
         .load::<Instance>(&mut conn)
         .map_err(|e| format!("Failed to query instances: {}", e))?;
 
@@ -1497,6 +1581,47 @@ pub async fn launch_instance(
     app_handle: tauri::AppHandle,
     instance_data: Instance,
 ) -> Result<(), String> {
+    launch_instance_with_mode(app_handle, instance_data, None, None).await
+}
+
+/// Launch an Instance and join `host:port` once the game has loaded.
+#[tauri::command]
+pub async fn launch_instance_and_connect(
+    app_handle: tauri::AppHandle,
+    instance_data: Instance,
+    host: String,
+    port: u16,
+) -> Result<(), String> {
+    let host = host.trim().to_string();
+    if host.is_empty() {
+        return Err("Server address cannot be empty".to_string());
+    }
+    launch_instance_with_mode(app_handle, instance_data, None, Some((host, port))).await
+}
+
+/// Launch an Instance in safe mode after repeated crashes: either with every
+/// mod temporarily disabled, or as vanilla Minecraft for the same version.
+#[tauri::command]
+pub async fn launch_instance_safe_mode(
+    app_handle: tauri::AppHandle,
+    instance_id: i32,
+    strategy: crate::instance::safe_mode::SafeModeStrategy,
+) -> Result<(), String> {
+    let inst = get_instance(instance_id)?;
+    if inst.is_trashed() {
+        return Err("Cannot launch an instance that is in the trash".to_string());
+    }
+    launch_instance_with_mode(app_handle, inst, Some(strategy), None).await
+}
+
+async fn launch_instance_with_mode(
+    app_handle: tauri::AppHandle,
+    instance_data: Instance,
+    safe_mode: Option<crate::instance::safe_mode::SafeModeStrategy>,
+    connect_server: Option<(String, u16)>,
+) -> Result<(), String> {
+    use crate::instance::safe_mode::SafeModeStrategy;
+
     // macOS: Ensure microphone permissions are granted before launch
     // to allow voice chat mods in Minecraft to function.
     #[cfg(target_os = "macos")]
@@ -1537,12 +1662,30 @@ pub async fn launch_instance(
         instance_data.modloader_version
     );
 
+    // Vanilla safe mode launches the same game directory without a modloader.
+    let launch_source = match safe_mode {
+        Some(SafeModeStrategy::Vanilla) => Instance {
+            modloader: Some("vanilla".to_string()),
+            modloader_version: None,
+            ..instance_data.clone()
+        },
+        _ => instance_data.clone(),
+    };
+    let launch_source = with_detected_modloader(launch_source);
+
+    if safe_mode.is_none() {
+        crate::instance::launch_preparation::check_mod_conflicts_before_launch(
+            &app_handle,
+            &launch_source,
+        )?;
+    }
+
     let prepared =
-        crate::instance::launch_preparation::prepare_instance_launch(&app_handle, &instance_data)
+        crate::instance::launch_preparation::prepare_instance_launch(&app_handle, &launch_source)
             .await?;
     let runtime = crate::instance::launch_preparation::ensure_runtime_ready_for_launch(
         &app_handle,
-        &instance_data,
+        &launch_source,
         prepared.install_spec.clone(),
     )
     .await?;
@@ -1559,7 +1702,19 @@ pub async fn launch_instance(
     let prepared_instance_name = prepared.instance_name.clone();
     let launcher_action = prepared.launcher_action;
     let tray_visible = prepared.tray_visible;
-    let launch_spec = prepared.launch_spec;
+    let mut launch_spec = prepared.launch_spec;
+    launch_spec.safe_mode = safe_mode.is_some();
+    if let Some((host, port)) = &connect_server {
+        log::info!("[launch_instance] Joining {}:{} after launch", host, port);
+    }
+    launch_spec.connect_server = connect_server;
+    if let Some(strategy) = safe_mode {
+        log::warn!(
+            "[launch_instance] Launching {} in safe mode ({:?})",
+            instance_id,
+            strategy
+        );
+    }
 
     // Log batching setup
     let (log_tx, mut log_rx) = tokio::sync::mpsc::unbounded_channel::<(String, String, String)>();
@@ -1610,6 +1765,52 @@ pub async fn launch_instance(
         );
     }
 
+    let app_config = crate::utils::config::get_app_config().map_err(|e| e.to_string())?;
+    if let Some(pre_command) = app_config
+        .game_launch_pre_command
+        .as_deref()
+        .filter(|command| !command.trim().is_empty())
+    {
+        let vars = crate::utils::hooks::LaunchCommandVars {
+            instance_id: instance_id.clone(),
+            game_dir: launch_spec.game_dir.clone(),
+            minecraft_version: instance_data.minecraft_version.clone(),
+        };
+        crate::utils::hooks::run_launch_command(
+            "pre-launch",
+            pre_command,
+            &vars,
+            crate::utils::hooks::LAUNCH_COMMAND_TIMEOUT,
+        )
+        .await
+        .map_err(|e| format!("Launch aborted: {}", e))?;
+    }
+
+    if safe_mode == Some(SafeModeStrategy::DisableMods) {
+        let game_dir = launch_spec.game_dir.clone();
+        let disabled = tokio::task::spawn_blocking(move || {
+            crate::instance::safe_mode::disable_mods(&game_dir)
+        })
+        .await
+        .map_err(|e| format!("Failed to spawn blocking task: {}", e))??;
+        log::info!(
+            "[launch_instance] Safe mode disabled {} mod(s) for {}",
+            disabled,
+            instance_id
+        );
+    }
+
+    // The account may have been switched while the runtime was prepared.
+    crate::instance::launch_preparation::refresh_launch_account(
+        &app_handle,
+        &mut launch_spec,
+        offline_launch,
+    )
+    .await?;
+
+    let safe_mode_game_dir = launch_spec.game_dir.clone();
+    let token_account = (!offline_launch && launch_spec.access_token != "offline")
+        .then(|| launch_spec.uuid.clone());
     let join = tokio::task::spawn_blocking(move || {
         futures::executor::block_on(piston_lib::game::launcher::launch_prepared_game(
             launch_spec,
@@ -1627,6 +1828,7 @@ pub async fn launch_instance(
                 &app_handle,
                 &instance_data,
                 result,
+                safe_mode.is_some(),
             )
             .await?;
 
@@ -1636,15 +1838,31 @@ pub async fn launch_instance(
                 tray_visible,
             );
 
-            crate::instance::lifecycle::spawn_exit_monitor(
-                app_handle.clone(),
-                instance_data.name.clone(),
-                run_state,
-            );
+            if let Some(account_uuid) = token_account {
+                crate::instance::token_refresh::spawn_token_refresher(
+                    app_handle.clone(),
+                    safe_mode_game_dir,
+                    run_state.pid,
+                    account_uuid,
+                );
+            }
+            crate::instance::lifecycle::spawn_exit_monitor(app_handle.clone(), run_state);
 
             Ok(())
         }
-        Err(e) => Err(format!("Failed to launch game: {}", e)),
+        Err(e) => {
+            if safe_mode == Some(SafeModeStrategy::DisableMods) {
+                if let Err(restore_err) =
+                    crate::instance::safe_mode::restore_mods(&safe_mode_game_dir)
+                {
+                    log::error!(
+                        "[launch_instance] Failed to restore mods after failed safe-mode launch: {}",
+                        restore_err
+                    );
+                }
+            }
+            Err(format!("Failed to launch game: {}", e))
+        }
     }
 }
 
@@ -1668,6 +1886,49 @@ pub async fn is_instance_running(instance_data: Instance) -> Result<bool, String
         .map_err(|e| format!("Failed to check instance status: {}", e))
 }
 
+/// Percentage of the instance's playtime the game window had focus, over
+/// the sessions where focus could be tracked.
+#[tauri::command]
+pub fn get_active_play_ratio(instance_id: i32) -> Result<f64, String> {
+    let mut conn =
+        get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
+    crate::instance::playtime::active_play_ratio(&mut conn, instance_id)?
+        .ok_or_else(|| format!("No focus-tracked playtime for instance {}", instance_id))
+}
+
+/// Elapsed time and today's/this week's playtime of the instance's running
+/// session, or `None` when it isn't running.
+#[tauri::command]
+pub fn get_live_session(
+    instance_id: i32,
+) -> Result<Option<crate::instance::playtime::LiveSession>, String> {
+    let mut conn =
+        get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
+    let inst: Instance = instance
+        .find(instance_id)
+        .first(&mut conn)
+        .map_err(|e| format!("Instance {} not found: {}", instance_id, e))?;
+    let instance_slug = inst.slug();
+    let running = crate::utils::process_state::load_running_processes()
+        .map_err(|e| format!("Failed to load running processes: {}", e))?
+        .into_iter()
+        .find(|state| state.instance_id == instance_slug);
+
+    running
+        .map(|state| {
+            crate::instance::playtime::live_session(&mut conn, instance_id, &state.started_at)
+        })
+        .transpose()
+}
+
+/// Memory and CPU samples of the instance's current or last run, oldest first.
+#[tauri::command]
+pub fn get_process_metrics(
+    instance_id: String,
+) -> Result<Vec<piston_lib::game::launcher::ProcessMetrics>, String> {
+    Ok(crate::instance::metrics::history(&instance_id))
+}
+
 #[tauri::command]
 pub async fn get_minecraft_versions(
     app_handle: tauri::AppHandle,
@@ -1675,6 +1936,317 @@ pub async fn get_minecraft_versions(
     crate::utils::manifest::load_manifest(&app_handle).await
 }
 
+/// CSV of the recommended loader version per Minecraft version.
+#[tauri::command]
+pub async fn export_version_matrix(app_handle: tauri::AppHandle) -> Result<String, String> {
+    let metadata = crate::utils::manifest::load_manifest(&app_handle).await?;
+    Ok(metadata.to_compatibility_csv())
+}
+
+#[tauri::command]
+pub async fn get_supported_loaders(
+    app_handle: tauri::AppHandle,
+    mc_version: String,
+) -> Result<Vec<piston_lib::game::metadata::ModloaderType>, String> {
+    let metadata = crate::utils::manifest::load_manifest(&app_handle).await?;
+    Ok(metadata.get_supported_loaders(&mc_version))
+}
+
+#[tauri::command]
+pub fn get_loader_changelog_url(loader: String, version: String) -> Result<Option<String>, String> {
+    let loader_type: piston_lib::game::metadata::ModloaderType =
+        loader.parse().map_err(|e: anyhow::Error| e.to_string())?;
+    Ok(loader_type.changelog_url(&version))
+}
+
+#[tauri::command]
+pub async fn check_loader_compatibility(
+    version_id: String,
+    loader: String,
+    loader_version: Option<String>,
+) -> Result<piston_lib::game::metadata::LoaderCompatibility, String> {
+    let loader_type: piston_lib::game::metadata::ModloaderType =
+        loader.parse().map_err(|e: anyhow::Error| e.to_string())?;
+    piston_lib::game::metadata::check_loader_compatibility(
+        &version_id,
+        loader_type,
+        loader_version.as_deref(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Instances imported without loader metadata launch with the loader found in
+/// the shared `versions/` directory for their Minecraft version, if any.
+pub(crate) fn with_detected_modloader(inst: Instance) -> Instance {
+    if inst
+        .modloader
+        .as_deref()
+        .is_some_and(|value| !value.trim().is_empty())
+    {
+        return inst;
+    }
+    let Ok(config_dir) = crate::utils::db_manager::get_app_config_dir() else {
+        return inst;
+    };
+    let data_dir = if config_dir.join("data").exists() {
+        config_dir.join("data")
+    } else {
+        config_dir
+    };
+
+    match piston_lib::game::launcher::detect_installed_modloader(
+        &data_dir.join("versions"),
+        &inst.minecraft_version,
+    ) {
+        Some((loader, loader_version)) => {
+            log::info!(
+                "[launch_instance] Detected {} {} for {} from installed versions",
+                loader,
+                loader_version,
+                inst.name
+            );
+            Instance {
+                modloader: Some(loader.as_str().to_string()),
+                modloader_version: Some(loader_version),
+                ..inst
+            }
+        }
+        None => inst,
+    }
+}
+
+/// Refuse installs the loader cannot complete yet (e.g. Fabric on a snapshot
+/// without intermediary). A failed lookup is not fatal; the installer reports
+/// its own error if the combination really is unavailable.
+async fn ensure_loader_compatible(inst: &Instance) -> Result<(), String> {
+    let Some(loader_type) = inst.modloader.as_deref().and_then(|value| {
+        value
+            .parse::<piston_lib::game::metadata::ModloaderType>()
+            .ok()
+    }) else {
+        return Ok(());
+    };
+
+    match piston_lib::game::metadata::check_loader_compatibility(
+        &inst.minecraft_version,
+        loader_type,
+        inst.modloader_version.as_deref(),
+    )
+    .await
+    {
+        Ok(compatibility) => match compatibility.describe(loader_type, &inst.minecraft_version) {
+            Some(reason) => {
+                log::warn!("[install_instance] {}", reason);
+                Err(reason)
+            }
+            None => Ok(()),
+        },
+        Err(e) => {
+            log::warn!(
+                "[install_instance] Loader compatibility check failed, continuing: {}",
+                e
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Asset objects that differ between two Minecraft versions, so the UI can
+/// show how much an upgrade will actually download.
+#[tauri::command]
+pub async fn get_asset_delta_estimate(
+    from_version: String,
+    to_version: String,
+) -> Result<piston_lib::game::installer::asset_delta::AssetDelta, String> {
+    let data_dir = crate::utils::db_manager::get_app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {}", e))?
+        .join("data");
+    piston_lib::game::installer::asset_delta::estimate_asset_delta(
+        &data_dir,
+        &from_version,
+        &to_version,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Route this instance's resource API calls to a self-hosted Modrinth or
+/// CurseForge server, or back to the official API when `source_override` is `None`.
+#[tauri::command]
+pub fn set_instance_source_override(
+    app_handle: tauri::AppHandle,
+    instance_id: i32,
+    source_override: Option<crate::resources::sources::custom::SourceOverride>,
+) -> Result<(), String> {
+    let stored = source_override
+        .map(|value| {
+            let value = value.validated().map_err(|e| e.to_string())?;
+            serde_json::to_string(&value).map_err(|e| e.to_string())
+        })
+        .transpose()?;
+
+    let mut conn =
+        get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
+    diesel::update(instance.find(instance_id))
+        .set(instance_source_override.eq(stored))
+        .execute(&mut conn)
+        .map_err(|e| format!("Failed to update source override: {}", e))?;
+
+    if let Err(e) = crate::resources::update_cache::invalidate_instance_update_snapshot(instance_id)
+    {
+        log::warn!(
+            "[update_cache] Failed to invalidate snapshot for instance {}: {}",
+            instance_id,
+            e
+        );
+    }
+
+    let updated: Instance = instance
+        .find(instance_id)
+        .first(&mut conn)
+        .map_err(|e| format!("Failed to fetch updated instance: {}", e))?;
+    let _ = app_handle.emit("core://instance-updated", process_instance_icon(updated));
+
+    Ok(())
+}
+
+/// Store per-instance overrides of memory, Java and download settings.
+/// An empty override clears the column.
+#[tauri::command]
+pub fn set_instance_config_override(
+    app_handle: tauri::AppHandle,
+    instance_id: i32,
+    config_override: crate::instance::config_override::InstanceConfigOverride,
+) -> Result<(), String> {
+    let config_override = config_override.validated().map_err(|e| e.to_string())?;
+    let stored = if config_override.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_string(&config_override).map_err(|e| e.to_string())?)
+    };
+
+    let mut conn =
+        get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
+    diesel::update(instance.find(instance_id))
+        .set(config_override_json.eq(stored))
+        .execute(&mut conn)
+        .map_err(|e| format!("Failed to update config override: {}", e))?;
+
+    let updated: Instance = instance
+        .find(instance_id)
+        .first(&mut conn)
+        .map_err(|e| format!("Failed to fetch updated instance: {}", e))?;
+    let _ = app_handle.emit("core://instance-updated", process_instance_icon(updated));
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_instance_config_override(
+    instance_id: i32,
+) -> Result<Option<crate::instance::config_override::InstanceConfigOverride>, String> {
+    let mut conn =
+        get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
+    let inst: Instance = instance
+        .find(instance_id)
+        .first(&mut conn)
+        .map_err(|e| format!("Instance {} not found: {}", instance_id, e))?;
+    Ok(crate::instance::config_override::InstanceConfigOverride::for_instance(&inst))
+}
+
+/// Duplicate and incompatible mods in the Instance's `mods` folder.
+#[tauri::command]
+pub fn detect_mod_conflicts(
+    instance_id: i32,
+) -> Result<Vec<crate::instance::mod_conflicts::ModConflict>, String> {
+    let inst = get_instance(instance_id)?;
+    let game_dir = resolve_instance_game_dir_for_upload(&inst)?;
+    Ok(crate::instance::mod_conflicts::detect_mod_conflicts_in(
+        &game_dir,
+    ))
+}
+
+fn instance_config_root(instance_id: i32) -> Result<(Instance, PathBuf), String> {
+    let mut conn =
+        get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
+    let inst: Instance = instance
+        .find(instance_id)
+        .first(&mut conn)
+        .map_err(|e| format!("Instance {} not found: {}", instance_id, e))?;
+    let game_dir = resolve_instance_game_dir_for_upload(&inst)?;
+    Ok((inst, crate::instance::config_files::config_root(&game_dir)))
+}
+
+#[tauri::command]
+pub fn list_instance_config_files(
+    instance_id: i32,
+) -> Result<Vec<crate::instance::config_files::ConfigFileEntry>, String> {
+    let (_, config_root) = instance_config_root(instance_id)?;
+    Ok(crate::instance::config_files::list_config_files(
+        &config_root,
+    ))
+}
+
+#[tauri::command]
+pub fn read_instance_config_file(instance_id: i32, path: String) -> Result<String, String> {
+    let (_, config_root) = instance_config_root(instance_id)?;
+    let file_path = crate::instance::config_files::resolve_config_path(&config_root, &path)?;
+    crate::instance::config_files::read_config_file(&file_path)
+}
+
+/// Validate `content` in the file's format and save it, keeping the previous
+/// contents as `<file>.bak`. Refused while the instance is running unless the
+/// user has allowed live edits.
+#[tauri::command]
+pub async fn write_instance_config_file(
+    instance_id: i32,
+    path: String,
+    content: String,
+) -> Result<(), String> {
+    let (inst, config_root) = instance_config_root(instance_id)?;
+
+    let allow_while_running = crate::utils::config::get_app_config()
+        .map(|config| config.allow_config_edits_while_running)
+        .unwrap_or(false);
+    if !allow_while_running
+        && piston_lib::game::launcher::is_instance_running(&inst.slug())
+            .await
+            .map_err(|e| format!("Failed to check instance run state: {}", e))?
+    {
+        return Err("Close the game before editing its config files".to_string());
+    }
+
+    std::fs::create_dir_all(&config_root)
+        .map_err(|e| format!("Failed to create config folder: {}", e))?;
+    let file_path = crate::instance::config_files::resolve_config_path(&config_root, &path)?;
+    crate::instance::config_files::write_config_file(&file_path, &content)
+}
+
+/// Override the launch integrity spot-check for this instance, or follow the
+/// global setting again when `enabled` is `None`.
+#[tauri::command]
+pub fn set_instance_verify_on_launch(
+    app_handle: tauri::AppHandle,
+    instance_id: i32,
+    enabled: Option<bool>,
+) -> Result<(), String> {
+    let mut conn =
+        get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
+    diesel::update(instance.find(instance_id))
+        .set(verify_on_launch.eq(enabled))
+        .execute(&mut conn)
+        .map_err(|e| format!("Failed to update verify-on-launch override: {}", e))?;
+
+    let updated: Instance = instance
+        .find(instance_id)
+        .first(&mut conn)
+        .map_err(|e| format!("Failed to fetch updated instance: {}", e))?;
+    let _ = app_handle.emit("core://instance-updated", process_instance_icon(updated));
+
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn regenerate_piston_manifest(app_handle: tauri::AppHandle) -> Result<(), String> {
     let task_manager = app_handle.state::<TaskManager>();
@@ -2041,6 +2613,36 @@ pub async fn repair_instance(
     Ok(())
 }
 
+/// SHA-1 check of every asset object the instance's game version uses.
+/// Corrupted objects are re-downloaded by the next repair.
+#[tauri::command]
+pub async fn verify_assets(
+    instance_id: i32,
+) -> Result<piston_lib::game::installer::asset_integrity::AssetVerificationReport, String> {
+    let mut conn =
+        get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
+    let inst: Instance = instance
+        .find(instance_id)
+        .first(&mut conn)
+        .map_err(|e| format!("Instance {} not found: {}", instance_id, e))?;
+    drop(conn);
+
+    let app_config = crate::utils::config::get_app_config().map_err(|e| e.to_string())?;
+    let data_dir = crate::utils::db_manager::get_app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {}", e))?
+        .join("data");
+    let workers = app_config.max_download_threads.max(1) as usize;
+
+    piston_lib::game::installer::asset_integrity::verify_version_assets(
+        &data_dir,
+        &inst.minecraft_version,
+        std::sync::Arc::new(piston_lib::game::installer::types::SilentProgressReporter),
+        workers,
+    )
+    .await
+    .map_err(|e| format!("Failed to verify assets: {}", e))
+}
+
 #[tauri::command]
 pub async fn reset_instance(
     app_handle: tauri::AppHandle,
@@ -2180,70 +2782,250 @@ pub async fn update_instance_modpack_version(
     Ok(())
 }
 
-#[cfg(test)]
-mod crash_upload_tests {
-    use super::{
-        enforce_mclogs_limits, read_redacted_log_file, redact_log_content, MCLOGS_MAX_BYTES,
+/// Export an installed instance to the official Minecraft Launcher and return
+/// the version id it shows up under. Assets are left for that launcher to fetch.
+#[tauri::command]
+pub async fn export_to_vanilla_launcher(instance_id: i32) -> Result<String, String> {
+    let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
+    let inst = find_live_instance(&mut conn, instance_id)?;
+    if inst.installation_status.as_deref() != Some("installed") {
+        return Err(format!("{} is not installed", inst.name));
+    }
+
+    let minecraft_dir = crate::launcher_import::paths::official_launcher_dir()
+        .ok_or_else(|| "Could not locate the official launcher directory".to_string())?;
+    let config_dir = crate::utils::db_manager::get_app_config_dir().map_err(|e| e.to_string())?;
+    let data_dir = config_dir.join("data");
+    let game_dir = inst
+        .game_directory
+        .as_ref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| data_dir.join("instances").join(inst.slug()));
+    let request = piston_lib::game::runtime_plan::RuntimeRequest {
+        version_id: inst.minecraft_version.clone(),
+        modloader: inst
+            .modloader
+            .as_deref()
+            .and_then(crate::tasks::installers::parse_modloader),
+        modloader_version: inst.modloader_version.clone(),
+        data_dir,
+        os: piston_lib::game::installer::types::OsType::current(),
     };
+    let display_name = format!("{} (Vesta)", inst.name);
+
+    let export = tokio::task::spawn_blocking(move || {
+        piston_lib::game::vanilla_launcher::export_version(
+            &request,
+            &minecraft_dir,
+            &display_name,
+            &game_dir,
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| format!("Failed to export instance: {}", e))?;
 
-    #[test]
-    fn redacts_common_private_values_before_upload() {
-        let raw = "path=C:\\Users\\eatham\\.minecraft accessToken=secret --accessToken other Authorization: Bearer third server 203.0.113.10";
-        let redacted = redact_log_content(raw);
-        assert!(!redacted.contains("eatham"));
-        assert!(!redacted.contains("secret"));
-        assert!(!redacted.contains("other"));
-        assert!(!redacted.contains("third"));
-        assert!(!redacted.contains("Bearer"));
-        assert!(!redacted.contains("203.0.113.10"));
-    }
+    log::info!(
+        "[export_to_vanilla_launcher] Exported instance {} as {} ({} libraries linked, {} copied)",
+        instance_id,
+        export.version_id,
+        export.libraries_linked,
+        export.libraries_copied
+    );
+    Ok(export.version_id)
+}
 
-    #[test]
-    fn preserves_vesta_relative_path_context() {
-        let raw = "/Users/eatham/Library/Application Support/VestaLauncher/instances/pack/logs/latest.log C:\\Users\\eatham\\AppData\\Roaming\\.VestaLauncher\\instances\\pack\\logs\\latest.log";
-        let redacted = redact_log_content(raw);
-        assert!(
-            redacted.contains("<path>/VestaLauncher/instances/pack/logs/latest.log"),
-            "redacted: {}",
-            redacted
+/// Create an instance for a version installed in the official Minecraft
+/// Launcher. The instance keeps using that profile's game directory.
+#[tauri::command]
+pub async fn import_from_vanilla_launcher(
+    app_handle: tauri::AppHandle,
+    task_manager: State<'_, TaskManager>,
+    resource_watcher: State<'_, ResourceWatcher>,
+    version_id: String,
+) -> Result<i32, String> {
+    let minecraft_dir = crate::launcher_import::paths::official_launcher_dir()
+        .ok_or_else(|| "Could not locate the official launcher directory".to_string())?;
+    let version = piston_lib::game::vanilla_launcher::read_version(&minecraft_dir, &version_id)
+        .map_err(|e| e.to_string())?;
+    let source_game_dir = version.game_dir.to_string_lossy().to_string();
+
+    let mut new_instance = Instance::default();
+    new_instance.name = version
+        .profile_name
+        .clone()
+        .unwrap_or_else(|| version.version_id.clone());
+    new_instance.minecraft_version = version.minecraft_version.clone();
+    new_instance.modloader = version.modloader.map(|m| m.as_str().to_string());
+    new_instance.modloader_version = version.modloader_version.clone();
+    new_instance.last_operation = Some("vanilla-import".to_string());
+    new_instance.import_source_game_directory = Some(source_game_dir.clone());
+    // The watcher is started below, once the row points at the real directory.
+    new_instance.installation_status = Some("skip-initial-watch".to_string());
+
+    let inserted_id = create_instance(
+        app_handle.clone(),
+        new_instance,
+        None,
+        None,
+        resource_watcher.clone(),
+    )
+    .await?;
+
+    let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
+    let created: Instance = instance
+        .find(inserted_id)
+        .first(&mut conn)
+        .map_err(|e| format!("Instance not found: {}", e))?;
+    // create_instance always makes a fresh folder; drop it while it is still empty.
+    if let Some(created_dir) = created.game_directory.as_deref() {
+        let created_dir = Path::new(created_dir);
+        let _ = std::fs::remove_dir(created_dir.join("mods"));
+        let _ = std::fs::remove_dir(created_dir);
+    }
+    diesel::update(instance.find(inserted_id))
+        .set(game_directory.eq(Some(source_game_dir.clone())))
+        .execute(&mut conn)
+        .map_err(|e| format!("Failed to update game directory: {}", e))?;
+    let inst: Instance = instance
+        .find(inserted_id)
+        .first(&mut conn)
+        .map_err(|e| format!("Instance not found: {}", e))?;
+
+    if let Err(e) = resource_watcher
+        .watch_instance(inst.slug(), inserted_id, source_game_dir)
+        .await
+    {
+        log::error!(
+            "[import_from_vanilla_launcher] Failed to watch instance {}: {}",
+            inserted_id,
+            e
         );
-        assert!(redacted.contains("<path>/.VestaLauncher\\instances\\pack\\logs\\latest.log"));
-        assert!(!redacted.contains("eatham"));
-        assert!(!redacted.contains("Application Support"));
-        assert!(!redacted.contains("AppData"));
     }
 
-    #[test]
-    fn fully_redacts_non_vesta_absolute_paths() {
-        let raw =
-            "path=/Users/eatham/.minecraft/mods/foo.jar C:\\Users\\eatham\\.minecraft\\options.txt";
-        let redacted = redact_log_content(raw);
-        assert_eq!(redacted.matches("<path redacted>").count(), 2, "{redacted}");
-        assert!(!redacted.contains("eatham"));
-        assert!(!redacted.contains(".minecraft"));
+    install_instance(app_handle, task_manager, inst, None).await?;
+    Ok(inserted_id)
+}
+
+/// Save `instance_id` as a shareable template at `dest`: version, loader,
+/// resource references, the selected `config/` files, JVM settings and icon.
+/// No mod binaries are written.
+#[tauri::command]
+pub fn export_instance_template(
+    instance_id: i32,
+    dest: String,
+    config_files: Option<Vec<String>>,
+) -> Result<(), String> {
+    use crate::instance::config_files::{read_config_file, resolve_config_path};
+    use crate::instance::template::{build_template, write_template, TemplateConfigFile};
+    use crate::schema::installed_resource::dsl as ir_dsl;
+
+    let (inst, config_root) = instance_config_root(instance_id)?;
+    let mut configs = Vec::new();
+    for path in config_files.unwrap_or_default() {
+        let file_path = resolve_config_path(&config_root, &path)?;
+        configs.push(TemplateConfigFile {
+            content: read_config_file(&file_path).map_err(|e| format!("{}: {}", path, e))?,
+            path,
+        });
     }
 
-    #[test]
-    fn redacts_non_vesta_paths_with_spaces_without_collapsing_formatting() {
-        let raw = "prefix  path=/Users/eatham/Library/Application Support/foo.log  suffix";
-        let redacted = redact_log_content(raw);
-        assert_eq!(redacted, "prefix  path=<path redacted>  suffix");
-        assert!(!redacted.contains("eatham"));
-        assert!(!redacted.contains("Application Support"));
+    let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
+    let installed = ir_dsl::installed_resource
+        .filter(ir_dsl::instance_id.eq(instance_id))
+        .load::<crate::models::installed_resource::InstalledResource>(&mut conn)
+        .map_err(|e| format!("Failed to load installed resources: {}", e))?;
+
+    let template = build_template(&inst, &installed, configs);
+    write_template(Path::new(&dest), &template)?;
+    log::info!(
+        "[export_instance_template] Exported {} with {} resources and {} config files to {}",
+        inst.name,
+        template.resources.len(),
+        template.config_files.len(),
+        dest
+    );
+    Ok(())
+}
+
+/// Create an instance from a template, queue its loader install, then queue
+/// each referenced resource. References that no longer resolve are returned
+/// in the report instead of failing the import.
+#[tauri::command]
+pub async fn create_instance_from_template(
+    app_handle: tauri::AppHandle,
+    task_manager: State<'_, TaskManager>,
+    resource_watcher: State<'_, ResourceWatcher>,
+    resource_manager: State<'_, crate::resources::ResourceManager>,
+    path: String,
+) -> Result<crate::instance::template::TemplateImportReport, String> {
+    use crate::instance::template::{
+        apply_config_files, instance_from_template, read_template, resolve_template_resources,
+        TemplateImportReport,
+    };
+    use crate::tasks::resource_download::ResourceDownloadTask;
+
+    let template = read_template(Path::new(&path))?;
+    let inserted_id = create_instance(
+        app_handle.clone(),
+        instance_from_template(&template),
+        None,
+        None,
+        resource_watcher,
+    )
+    .await?;
+
+    let (inst, config_root) = instance_config_root(inserted_id)?;
+    let config_errors = apply_config_files(&config_root, &template.config_files);
+    install_instance(app_handle.clone(), task_manager.clone(), inst, None).await?;
+
+    let manager = resource_manager.inner();
+    let (resolved, unresolved) = resolve_template_resources(
+        &template.resources,
+        |platform, project, version| async move {
+            manager.get_version(platform, &project, &version).await
+        },
+    )
+    .await;
+
+    let mut queued_resources = 0;
+    for (res, version) in resolved {
+        let task = ResourceDownloadTask {
+            instance_id: inserted_id,
+            platform: res.platform,
+            project_id: res.project_id,
+            project_name: res.name,
+            version,
+            resource_type: res.resource_type,
+            dependency_for: None,
+        };
+        task_manager.submit(Box::new(task)).await?;
+        queued_resources += 1;
     }
 
-    #[test]
-    fn redacts_structured_secret_values() {
-        let raw = r#"{"access_token":"json-secret","client_secret": "quoted-secret"} url=https://example.test/?access_token=url-secret&ok=1 Authorization: Bearer bearer-secret"#;
-        let redacted = redact_log_content(raw);
-        assert!(!redacted.contains("json-secret"));
-        assert!(!redacted.contains("quoted-secret"));
-        assert!(!redacted.contains("url-secret"));
-        assert!(!redacted.contains("Bearer"));
-        assert!(!redacted.contains("bearer-secret"));
-        assert!(redacted.contains("ok=1"));
+    for missing in &unresolved {
+        log::warn!(
+            "[create_instance_from_template] Could not resolve {} ({:?} {}/{}): {}",
+            missing.name,
+            missing.platform,
+            missing.project_id,
+            missing.version_id,
+            missing.reason
+        );
     }
 
+    Ok(TemplateImportReport {
+        instance_id: inserted_id,
+        queued_resources,
+        unresolved,
+        config_errors,
+    })
+}
+
+#[cfg(test)]
+mod crash_upload_tests {
+    use super::{enforce_mclogs_limits, read_redacted_log_file, MCLOGS_MAX_BYTES};
+
     #[test]
     fn rejects_logs_over_mclogs_line_limit() {
         let content = (0..25_001).map(|_| "line").collect::<Vec<_>>().join("\n");
@@ -125,9 +125,14 @@ pub async fn import_external_instance(
     // Defer watcher startup to the import task to avoid blocking enqueue.
     instance.installation_status = Some("skip-initial-watch".to_string());
 
-    let instance_id =
-        crate::commands::instances::create_instance(app_handle.clone(), instance, resource_watcher)
-            .await?;
+    let instance_id = crate::commands::instances::create_instance(
+        app_handle.clone(),
+        instance,
+        None,
+        None,
+        resource_watcher,
+    )
+    .await?;
 
     let task = ImportExternalInstanceTask::new(instance_id, selected.name, selected.game_directory);
     task_manager.submit(Box::new(task)).await?;
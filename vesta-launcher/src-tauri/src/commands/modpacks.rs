@@ -1,3 +1,7 @@
+use crate::instance::export_candidates::{
+    directory_rollups, is_suggested, walk_export_files, ExportDirectorySize, VestaIgnore,
+    DEFAULT_SIZE_WARNING_BYTES,
+};
 use crate::models::instance::{Instance, NewInstance};
 use crate::models::java::GlobalJavaPath;
 use crate::models::resource::{
@@ -13,20 +17,21 @@ use crate::tasks::modpack_export::ModpackExportTask;
 use crate::utils::config::get_app_config;
 use crate::utils::db::{get_config_conn, get_vesta_conn};
 use crate::utils::db_manager::get_app_config_dir;
-use crate::utils::hash::{calculate_curseforge_fingerprint, calculate_sha1};
+use crate::utils::fingerprint_cache::cached_file_hashes;
+use crate::utils::hash::HashAlgorithm;
 use crate::utils::instance_helpers::{compute_unique_name, compute_unique_slug};
 use crate::utils::url::normalize_url;
 use anyhow::Result;
 use diesel::prelude::*;
 use lazy_static::lazy_static;
-use piston_lib::game::modpack::exporter::{ExportEntry, ExportSpec};
+use piston_lib::game::modpack::exporter::{ExportEntry, ExportMod, ExportSpec};
 use piston_lib::game::modpack::parser::get_modpack_metadata;
-use piston_lib::game::modpack::types::{ModpackFormat, ModpackMetadata};
+use piston_lib::game::modpack::types::{ModpackFormat, ModpackMetadata, ModrinthEnv};
 use serde_json;
 use sha1::{Digest, Sha1};
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::sync::{Mutex, MutexGuard};
 use std::time::{Duration, Instant, SystemTime};
@@ -524,22 +529,6 @@ fn no_match_result(warning: impl Into<String>) -> ModpackSourceMatch {
     }
 }
 
-fn calculate_sha512(path: &Path) -> anyhow::Result<String> {
-    use sha2::{Digest as Sha2Digest, Sha512};
-    let file = File::open(path)?;
-    let mut reader = BufReader::new(file);
-    let mut hasher = Sha512::new();
-    let mut buffer = [0u8; 8192];
-    loop {
-        let read = reader.read(&mut buffer)?;
-        if read == 0 {
-            break;
-        }
-        hasher.update(&buffer[..read]);
-    }
-    Ok(hex::encode(hasher.finalize()))
-}
-
 async fn get_modrinth_match_by_file_hash(
     resource_manager: &crate::resources::ResourceManager,
     hash: &str,
@@ -920,6 +909,10 @@ async fn get_or_download_summary_archive(
         while let Some(chunk_result) = stream.next().await {
             let chunk = chunk_result.map_err(|e| format!("Modpack archive stream error: {}", e))?;
             downloaded += chunk.len() as u64;
+            piston_lib::game::installer::core::traffic::record_traffic(
+                piston_lib::game::installer::core::traffic::TrafficCategory::Modpacks,
+                chunk.len() as u64,
+            );
             if downloaded > MAX_SUMMARY_ARCHIVE_BYTES {
                 return Err(format!(
                     "Modpack archive exceeded summary limit ({} bytes, max {} bytes)",
@@ -984,56 +977,39 @@ async fn try_exact_archive_match(
 
     match input.format {
         ModpackFormat::Modrinth => {
-            let hash_path = path.to_path_buf();
-            let sha1 = match run_blocking_modpack_io("hash local Modrinth archive", move || {
-                calculate_sha1(&hash_path)
-            })
-            .await
-            {
-                Ok(hash) => hash,
-                Err(err) => {
-                    log::warn!("[modpack-match] Modrinth archive SHA1 failed: {}", err);
-                    return None;
-                }
-            };
-
-            if let Some(match_result) =
-                get_modrinth_match_by_file_hash(resource_manager, &sha1, "sha1").await
-            {
-                return Some(match_result);
-            }
-
-            let sha512_path = path.to_path_buf();
-            let sha512 =
-                match run_blocking_modpack_io("hash local Modrinth archive sha512", move || {
-                    calculate_sha512(&sha512_path)
-                })
-                .await
+            let hashes =
+                match cached_file_hashes(path, &[HashAlgorithm::Sha1, HashAlgorithm::Sha512]).await
                 {
-                    Ok(hash) => hash,
+                    Ok(hashes) => hashes,
                     Err(err) => {
-                        log::debug!(
-                            "[modpack-match] Optional Modrinth archive SHA512 failed: {}",
-                            err
-                        );
+                        log::warn!("[modpack-match] Modrinth archive hashing failed: {}", err);
                         return None;
                     }
                 };
-            if let Some(match_result) =
-                get_modrinth_match_by_file_hash(resource_manager, &sha512, "sha512").await
-            {
-                return Some(match_result);
+
+            if let Some(sha1) = hashes.sha1.as_deref() {
+                if let Some(match_result) =
+                    get_modrinth_match_by_file_hash(resource_manager, sha1, "sha1").await
+                {
+                    return Some(match_result);
+                }
+            }
+            if let Some(sha512) = hashes.sha512.as_deref() {
+                if let Some(match_result) =
+                    get_modrinth_match_by_file_hash(resource_manager, sha512, "sha512").await
+                {
+                    return Some(match_result);
+                }
             }
         }
         ModpackFormat::CurseForge => {
-            let fingerprint_path = path.to_path_buf();
             let fingerprint =
-                match run_blocking_modpack_io("fingerprint local CurseForge archive", move || {
-                    calculate_curseforge_fingerprint(&fingerprint_path)
-                })
-                .await
+                match cached_file_hashes(path, &[HashAlgorithm::CurseForgeFingerprint])
+                    .await
+                    .map(|hashes| hashes.curseforge_fingerprint)
                 {
-                    Ok(fingerprint) => fingerprint,
+                    Ok(Some(fingerprint)) => fingerprint,
+                    Ok(None) => return None,
                     Err(err) => {
                         log::warn!(
                             "[modpack-match] CurseForge archive fingerprint failed: {}",
@@ -1329,7 +1305,7 @@ async fn resolve_modpack_resource(client: &reqwest::Client, url: &str) -> (Strin
     // Modrinth Support
     if url.contains("modrinth.com/") {
         let mut target_version_id = None;
-        let mut slug = None;
+        let mut project_slug = None;
 
         // 1. Try to extract Version ID or Slug from various Modrinth URL patterns
         if url.contains("api.modrinth.com/v2/version/") {
@@ -1354,7 +1330,7 @@ async fn resolve_modpack_resource(client: &reqwest::Client, url: &str) -> (Strin
             } else {
                 "modrinth.com/project/"
             };
-            slug = url
+            project_slug = url
                 .split(pattern)
                 .nth(1)
                 .and_then(|s| s.split('?').next())
@@ -1385,7 +1361,7 @@ async fn resolve_modpack_resource(client: &reqwest::Client, url: &str) -> (Strin
                     None
                 }
             })
-        } else if let Some(slug_str) = slug {
+        } else if let Some(slug_str) = project_slug {
             let versions_url = format!("https://api.modrinth.com/v2/project/{}/version", slug_str);
             client.get(&versions_url).send().await.ok().and_then(|r| {
                 if r.status().is_success() {
@@ -1505,7 +1481,7 @@ pub async fn get_modpack_info_from_url(
     // Optimization: Try to get metadata from Modrinth API first to avoid downloading large ZIPs
     if url.contains("modrinth.com/") {
         let mut version_id = None;
-        let mut slug = None;
+        let mut project_slug = None;
 
         if url.contains("api.modrinth.com/v2/version/") {
             version_id = url
@@ -1525,7 +1501,7 @@ pub async fn get_modpack_info_from_url(
             } else {
                 "modrinth.com/project/"
             };
-            slug = url
+            project_slug = url
                 .split(pattern)
                 .nth(1)
                 .and_then(|s| s.split('?').next())
@@ -1546,7 +1522,7 @@ pub async fn get_modpack_info_from_url(
             }
         }
 
-        if version_id.is_some() || slug.is_some() {
+        if version_id.is_some() || project_slug.is_some() {
             let version_obj = if let Some(vid) = version_id {
                 let v_url = format!("https://api.modrinth.com/v2/version/{}", vid);
                 if let Ok(r) = client.get(&v_url).send().await {
@@ -1554,7 +1530,7 @@ pub async fn get_modpack_info_from_url(
                 } else {
                     None
                 }
-            } else if let Some(s) = slug {
+            } else if let Some(s) = project_slug {
                 let v_url = format!("https://api.modrinth.com/v2/project/{}/version", s);
                 if let Ok(r) = client.get(&v_url).send().await {
                     if let Ok(arr) = r.json::<Vec<serde_json::Value>>().await {
@@ -1644,7 +1620,7 @@ pub async fn get_modpack_info_from_url(
         }
 
         // Extract slug more robustly from /minecraft/<class>/<slug>
-        let slug = if let Some(parts) = url.split("curseforge.com/minecraft/").nth(1) {
+        let project_slug = if let Some(parts) = url.split("curseforge.com/minecraft/").nth(1) {
             parts
                 .split('/')
                 .nth(1) // Skip class segment (e.g. "mc-mods")
@@ -1655,7 +1631,7 @@ pub async fn get_modpack_info_from_url(
             None
         };
 
-        if let Some(ref slug_str) = slug {
+        if let Some(ref slug_str) = project_slug {
             log::info!(
                 "[get_modpack_info_from_url] Search for slug: {} (type: {:?})",
                 slug_str,
@@ -1849,6 +1825,10 @@ pub async fn get_modpack_info_from_url(
         std::io::Write::write_all(&mut temp_file, &chunk)
             .map_err(|e| format!("Write error: {}", e))?;
         downloaded += chunk.len();
+        piston_lib::game::installer::core::traffic::record_traffic(
+            piston_lib::game::installer::core::traffic::TrafficCategory::Modpacks,
+            chunk.len() as u64,
+        );
         if downloaded % (1024 * 1024) == 0 {
             log::debug!(
                 "[get_modpack_info_from_url] Downloaded {} MB...",
@@ -1997,8 +1977,11 @@ async fn prepare_instance(
 
     // 3. Compute unique name and slug
     let unique_name = compute_unique_name(&instance_data.name, &seen_names);
-    let slug = compute_unique_slug(&unique_name, &seen_slugs, &instances_root);
-    let gd_str = instances_root.join(&slug).to_string_lossy().to_string();
+    let instance_slug = compute_unique_slug(&unique_name, &seen_slugs, &instances_root);
+    let gd_str = instances_root
+        .join(&instance_slug)
+        .to_string_lossy()
+        .to_string();
 
     // Ensure the game directory exists
     if let Err(e) = std::fs::create_dir_all(&gd_str) {
@@ -2121,15 +2104,45 @@ async fn prepare_instance(
         pre_launch_hook: instance_data.pre_launch_hook.clone(),
         wrapper_command: instance_data.wrapper_command.clone(),
         post_exit_hook: instance_data.post_exit_hook.clone(),
+        slug: Some(instance_slug),
     })
 }
 
+/// Copy settings into a freshly saved modpack instance. Override extraction
+/// keeps existing config files, so copied options survive the install.
+fn copy_settings_into(
+    conn: &mut SqliteConnection,
+    target: &Instance,
+    source_id: Option<i32>,
+    copy_resource_packs: Option<bool>,
+) {
+    let (Some(source_id), Some(game_dir)) = (source_id, target.game_directory.as_deref()) else {
+        return;
+    };
+    if let Err(e) = crate::instance::settings_copy::copy_from_instance(
+        conn,
+        source_id,
+        target.id,
+        Path::new(game_dir),
+        target.modloader.as_deref(),
+        copy_resource_packs.unwrap_or(false),
+    ) {
+        log::warn!(
+            "[copy_settings_into] Failed to copy settings from instance {}: {}",
+            source_id,
+            e
+        );
+    }
+}
+
 #[command]
 pub async fn install_modpack_from_zip(
     _app: AppHandle,
     zip_path: String,
     instance_data: Instance,
     metadata: Option<piston_lib::game::modpack::types::ModpackMetadata>,
+    copy_settings_from_instance: Option<i32>,
+    copy_resource_packs: Option<bool>,
     task_manager: State<'_, TaskManager>,
 ) -> Result<i32, String> {
     log::info!(
@@ -2161,6 +2174,12 @@ pub async fn install_modpack_from_zip(
         .order(id.desc())
         .first::<Instance>(&mut conn)
         .map_err(|e| format!("Failed to fetch saved instance: {}", e))?;
+    copy_settings_into(
+        &mut conn,
+        &saved_instance,
+        copy_settings_from_instance,
+        copy_resource_packs,
+    );
 
     // Emit created event so UI home page updates immediately
     use tauri::Emitter;
@@ -2192,10 +2211,34 @@ pub struct ExportCandidate {
     pub version_id: Option<String>,
     pub hash: Option<String>,
     pub download_url: Option<String>,
+    /// Whether the picker should tick this entry by default.
+    #[serde(default)]
+    pub suggested: bool,
+    /// Set when the file is larger than the requested warning threshold.
+    #[serde(default)]
+    pub size_warning: bool,
+    /// Client/server support chosen in the export dialog, overriding the
+    /// project's own.
+    #[serde(default)]
+    pub env: Option<ModrinthEnv>,
+    /// Mark the file optional in the exported pack.
+    #[serde(default)]
+    pub optional: bool,
+}
+
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportCandidateList {
+    pub candidates: Vec<ExportCandidate>,
+    /// Size of every directory containing candidates, for "saves/ — 2.1 GB".
+    pub directories: Vec<ExportDirectorySize>,
 }
 
 #[command]
-pub async fn list_export_candidates(instance_id: i32) -> Result<Vec<ExportCandidate>, String> {
+pub async fn list_export_candidates(
+    instance_id: i32,
+    size_warning_bytes: Option<u64>,
+) -> Result<ExportCandidateList, String> {
     let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
     let inst = instance
         .filter(id.eq(instance_id))
@@ -2206,6 +2249,14 @@ pub async fn list_export_candidates(instance_id: i32) -> Result<Vec<ExportCandid
     if !game_dir.exists() {
         return Err("Game directory does not exist".to_string());
     }
+    let size_warning_bytes = size_warning_bytes.unwrap_or(DEFAULT_SIZE_WARNING_BYTES);
+
+    let ignore = tokio::task::spawn_blocking({
+        let game_dir = game_dir.clone();
+        move || VestaIgnore::load(&game_dir)
+    })
+    .await
+    .map_err(|e| format!("spawn_blocking panicked: {}", e))?;
 
     let mut candidates = Vec::new();
 
@@ -2233,119 +2284,92 @@ pub async fn list_export_candidates(instance_id: i32) -> Result<Vec<ExportCandid
             }
         }
 
+        if ignore.is_ignored(&rel_path, false) {
+            continue;
+        }
+
+        let size = m.file_size as u64;
         candidates.push(ExportCandidate {
+            suggested: is_suggested(&rel_path, true),
             path: rel_path,
             is_mod: true, // "Resource" in modpack terms
-            size: m.file_size as u64,
+            size,
             platform: Some(m.platform),
             project_id: Some(m.remote_id),
             version_id: Some(m.remote_version_id),
             hash: m.hash,
             download_url: None,
+            size_warning: size > size_warning_bytes,
+            env: None,
+            optional: false,
         });
     }
 
     // 2. Scan all folders in game_dir except standard Minecraft internals
-    let entries = tokio::task::spawn_blocking({
+    // and anything matched by .vestaignore
+    let files = tokio::task::spawn_blocking({
         let game_dir = game_dir.clone();
-        move || std::fs::read_dir(&game_dir)
+        move || walk_export_files(&game_dir, &ignore)
     })
     .await
     .map_err(|e| format!("spawn_blocking panicked: {}", e))?;
-    if let Ok(entries) = entries {
-        let skip_folders = [
-            "logs",
-            "backups",
-            "crash-reports",
-            "temp",
-            "bin",
-            "natives",
-            "assets",
-            "libraries",
-            "versions",
-            ".mixin.out",
-            "runtime",
-            "cache",
-            "mod-cache",
-            "web-cache",
-            "patchy",
-            ".vesta",
-        ];
-
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_dir() {
-                let dir_name = path.file_name().unwrap_or_default().to_string_lossy();
-
-                // Skip blacklisted folders
-                if skip_folders.contains(&dir_name.as_ref()) {
-                    continue;
-                }
 
-                // Recursively add files
-                let mut stack = vec![path];
-                while let Some(current) = stack.pop() {
-                    let sub_entries =
-                        tokio::task::spawn_blocking(move || std::fs::read_dir(&current))
-                            .await
-                            .map_err(|e| format!("spawn_blocking panicked: {}", e))?;
-                    if let Ok(sub_entries) = sub_entries {
-                        for sub_entry in sub_entries.flatten() {
-                            let sub_path = sub_entry.path();
-                            if sub_path.is_dir() {
-                                let sub_dir_name =
-                                    sub_path.file_name().unwrap_or_default().to_string_lossy();
-                                if !skip_folders.contains(&sub_dir_name.as_ref()) {
-                                    stack.push(sub_path.clone());
-                                }
-                            } else {
-                                if let Ok(rel_path) = sub_path.strip_prefix(&game_dir) {
-                                    let rel_str =
-                                        rel_path.to_string_lossy().to_string().replace("\\", "/");
-                                    let size = sub_path.metadata().map(|m| m.len()).unwrap_or(0);
-
-                                    // Avoid duplicates from resources scan
-                                    if !candidates.iter().any(|c| c.path == rel_str) {
-                                        candidates.push(ExportCandidate {
-                                            path: rel_str,
-                                            is_mod: false,
-                                            size,
-                                            platform: None,
-                                            project_id: None,
-                                            version_id: None,
-                                            hash: None,
-                                            download_url: None,
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            } else if path.is_file() {
-                // Add files in the root (options.txt, servers.dat, etc.)
-                if let Ok(rel_path) = path.strip_prefix(&game_dir) {
-                    let rel_str = rel_path.to_string_lossy().to_string().replace("\\", "/");
-                    let size = path.metadata().map(|m| m.len()).unwrap_or(0);
-
-                    if !candidates.iter().any(|c| c.path == rel_str) {
-                        candidates.push(ExportCandidate {
-                            path: rel_str,
-                            is_mod: false,
-                            size,
-                            platform: None,
-                            project_id: None,
-                            version_id: None,
-                            hash: None,
-                            download_url: None,
-                        });
-                    }
-                }
-            }
+    // Avoid duplicates from resources scan
+    let known = candidates
+        .iter()
+        .map(|c| c.path.clone())
+        .collect::<std::collections::HashSet<_>>();
+    for (rel_str, size) in files {
+        if known.contains(&rel_str) {
+            continue;
         }
+        candidates.push(ExportCandidate {
+            suggested: is_suggested(&rel_str, false),
+            size_warning: size > size_warning_bytes,
+            path: rel_str,
+            is_mod: false,
+            size,
+            platform: None,
+            project_id: None,
+            version_id: None,
+            hash: None,
+            download_url: None,
+            env: None,
+            optional: false,
+        });
     }
 
-    Ok(candidates)
+    let directories = directory_rollups(
+        candidates.iter().map(|c| (c.path.as_str(), c.size)),
+        size_warning_bytes,
+    );
+
+    Ok(ExportCandidateList {
+        candidates,
+        directories,
+    })
+}
+
+/// Re-download the mods of an instance's modpack version that are missing,
+/// corrupt or untracked. Progress is reported through a notification.
+#[command]
+pub async fn repair_modpack(
+    app_handle: AppHandle,
+    instance_id: i32,
+) -> Result<crate::modpack::repair::RepairReport, String> {
+    crate::modpack::repair::repair(&app_handle, instance_id).await
+}
+
+/// Client/server support published by the project, when the platform
+/// reports it for both sides.
+fn project_environment(project: &ResourceProject) -> Option<ModrinthEnv> {
+    match (&project.client_side, &project.server_side) {
+        (Some(client), Some(server)) => Some(ModrinthEnv {
+            client: client.clone(),
+            server: server.clone(),
+        }),
+        _ => None,
+    }
 }
 
 #[command]
@@ -2422,6 +2446,7 @@ pub async fn export_instance_to_modpack(
 
             if s.is_mod && (has_ids || has_hash) {
                 let mut ext_ids = None;
+                let mut project_env = None;
                 if let (Some(ref platform_str), Some(ref pid)) = (&s.platform, &s.project_id) {
                     let platform = match platform_str.as_str() {
                         "modrinth" => Some(SourcePlatform::Modrinth),
@@ -2432,11 +2457,12 @@ pub async fn export_instance_to_modpack(
                     if let Some(p) = platform {
                         if let Some(meta) = project_meta.get(&(p, pid.clone())) {
                             ext_ids = meta.external_ids.clone();
+                            project_env = project_environment(meta);
                         }
                     }
                 }
 
-                ExportEntry::Mod {
+                ExportEntry::Mod(Box::new(ExportMod {
                     path: PathBuf::from(s.path),
                     source_id: s.project_id.unwrap_or_default(),
                     version_id: s.version_id.unwrap_or_default(),
@@ -2447,7 +2473,14 @@ pub async fn export_instance_to_modpack(
                     },
                     download_url: s.download_url,
                     external_ids: ext_ids,
-                }
+                    // The recorded hash and size come from the platform's
+                    // version metadata; SHA-512 is filled in by the task.
+                    sha1: s.hash,
+                    sha512: None,
+                    file_size: Some(s.size),
+                    env: s.env.or(project_env),
+                    optional: s.optional,
+                }))
             } else {
                 ExportEntry::Override {
                     path: PathBuf::from(s.path),
@@ -2478,6 +2511,7 @@ pub async fn export_instance_to_modpack(
         .clone();
 
     let task = ModpackExportTask {
+        instance_id: inst.id,
         instance_name: modpack_name,
         game_dir,
         output_path,
@@ -2500,6 +2534,8 @@ pub async fn install_modpack_from_url(
     url: String,
     instance_data: Instance,
     metadata: Option<piston_lib::game::modpack::types::ModpackMetadata>,
+    copy_settings_from_instance: Option<i32>,
+    copy_resource_packs: Option<bool>,
     task_manager: State<'_, TaskManager>,
 ) -> Result<i32, String> {
     let client = piston_lib::client::shared_client();
@@ -2529,6 +2565,12 @@ pub async fn install_modpack_from_url(
         .order(id.desc())
         .first::<Instance>(&mut conn)
         .map_err(|e| format!("Failed to fetch saved instance: {}", e))?;
+    copy_settings_into(
+        &mut conn,
+        &saved_instance,
+        copy_settings_from_instance,
+        copy_resource_packs,
+    );
 
     log::info!(
         "[install_modpack_from_url] Saved instance into DB. ID={}, name={}, version_id={:?}",
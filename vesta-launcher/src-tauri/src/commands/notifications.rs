@@ -1,7 +1,7 @@
 use crate::models::NotificationSubscription;
 use crate::notifications::manager::NotificationManager;
 use crate::notifications::models::{CreateNotificationInput, Notification};
-use crate::notifications::store::NotificationStore;
+use crate::notifications::store::{NotificationQuery, NotificationStore};
 use crate::notifications::subscriptions::manager::SubscriptionManager;
 use serde::Deserialize;
 use std::sync::Arc;
@@ -53,7 +53,28 @@ pub async fn update_notification_progress(
 pub struct NotificationFilters {
     pub notification_type: Option<String>,
     pub read: Option<bool>,
-    pub _severity: Option<String>,
+    pub severity: Option<String>,
+    pub search: Option<String>,
+    #[serde(default)]
+    pub oldest_first: bool,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl From<NotificationFilters> for NotificationQuery {
+    fn from(filters: NotificationFilters) -> Self {
+        NotificationQuery {
+            // Any type filter has always meant "persisted notifications only".
+            only_persisted: filters.notification_type.is_some(),
+            only_unread: filters.read == Some(false),
+            notification_type: None,
+            severity: filters.severity,
+            title_contains: filters.search,
+            oldest_first: filters.oldest_first,
+            limit: filters.limit,
+            offset: filters.offset,
+        }
+    }
 }
 
 #[tauri::command]
@@ -61,23 +82,18 @@ pub async fn list_notifications(
     state: State<'_, NotificationManager>,
     filters: Option<NotificationFilters>,
 ) -> Result<Vec<Notification>, String> {
-    let only_persisted = filters
-        .as_ref()
-        .and_then(|f| f.notification_type.as_ref())
-        .map(|_| true)
-        .unwrap_or(false);
-    let only_unread = filters
-        .as_ref()
-        .and_then(|f| f.read)
-        .map(|r| !r)
-        .unwrap_or(false);
-
-    let notifications = state
-        .list(only_persisted, only_unread)
-        .map_err(|e| e.to_string())?;
+    let filter = filters.map(NotificationQuery::from).unwrap_or_default();
+    let notifications = state.query(&filter).map_err(|e| e.to_string())?;
     Ok(notifications)
 }
 
+/// Number of notifications matching `filters`; `limit`/`offset` are ignored.
+#[tauri::command]
+pub fn count_notifications(filters: Option<NotificationFilters>) -> Result<i64, String> {
+    let filter = filters.map(NotificationQuery::from).unwrap_or_default();
+    NotificationStore::count(&filter).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn mark_notification_read(
     state: State<'_, NotificationManager>,
@@ -104,6 +120,24 @@ pub async fn delete_notification(
     state.delete(id_str).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn mark_all_read(state: State<'_, NotificationManager>) -> Result<usize, String> {
+    state.mark_all_read().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_all_read(state: State<'_, NotificationManager>) -> Result<usize, String> {
+    state.delete_all_read().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_notifications_bulk(
+    state: State<'_, NotificationManager>,
+    ids: Vec<String>,
+) -> Result<usize, String> {
+    state.delete_bulk(ids).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn invoke_notification_action(
     state: State<'_, NotificationManager>,
@@ -234,6 +268,25 @@ pub async fn subscribe_to_rss(
     sm.subscribe_rss(url, title).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn export_notification_subscriptions(
+    sm: State<'_, Arc<SubscriptionManager>>,
+    dest: String,
+) -> Result<usize, String> {
+    sm.export_subscriptions(std::path::Path::new(&dest))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn import_notification_subscriptions(
+    sm: State<'_, Arc<SubscriptionManager>>,
+    path: String,
+    merge: bool,
+) -> Result<crate::notifications::subscriptions::presets::SubscriptionImportResult, String> {
+    sm.import_subscriptions(std::path::Path::new(&path), merge)
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn check_notifications_now(
     sm: State<'_, Arc<SubscriptionManager>>,
@@ -1,9 +1,15 @@
+use crate::first_run::{
+    choice_updates, installed_launchers, onboarding_state, recommended_max_memory_mb,
+    OnboardingChoices, OnboardingOutcome, OnboardingState, RecommendedDefaults, FINAL_SETUP_STEP,
+};
+use crate::launcher_import::ImportManager;
 use crate::models::java::GlobalJavaPath;
 use crate::schema::config::global_java_paths::dsl::{global_java_paths, id, is_active};
 use crate::tasks::installers::java::DownloadJavaTask;
 use crate::tasks::manager::TaskManager;
-use crate::utils::config::{update_config_field, update_config_fields};
+use crate::utils::config::{get_app_config, update_config_field, update_config_fields};
 use crate::utils::db::get_config_conn;
+use crate::utils::db_manager::get_app_config_dir;
 use diesel::prelude::*;
 use piston_lib::game::installer::core::jre_manager;
 use serde_json::json;
@@ -118,6 +124,21 @@ pub async fn verify_java_path(path_str: String) -> Result<jre_manager::DetectedJ
     .map_err(|e| e.to_string())?
 }
 
+/// Like `verify_java_path`, plus vendor/arch details and warnings against
+/// `required_major`.
+#[tauri::command]
+pub async fn validate_java_path(
+    path: String,
+    required_major: Option<u32>,
+) -> Result<jre_manager::JavaValidation, String> {
+    let path_buf = std::path::PathBuf::from(path);
+    tokio::task::spawn_blocking(move || {
+        jre_manager::validate_java_path(&path_buf, required_major).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 #[tauri::command]
 pub async fn select_java_file(app_handle: AppHandle) -> Result<Option<String>, String> {
     use tauri_plugin_dialog::DialogExt;
@@ -180,7 +201,7 @@ pub fn get_global_java_paths() -> Result<Vec<GlobalJavaPath>, String> {
 pub async fn complete_onboarding(app_handle: AppHandle) -> Result<(), String> {
     let mut updates = HashMap::new();
     updates.insert("setup_completed".to_string(), json!(true));
-    updates.insert("setup_step".to_string(), json!(6));
+    updates.insert("setup_step".to_string(), json!(FINAL_SETUP_STEP));
 
     update_config_fields(app_handle, updates)
 }
@@ -195,6 +216,73 @@ pub async fn reset_onboarding(app_handle: AppHandle) -> Result<(), String> {
     update_config_fields(app_handle, updates)
 }
 
+#[tauri::command]
+pub fn get_onboarding_state() -> Result<OnboardingState, String> {
+    let config = get_app_config().map_err(|e| e.to_string())?;
+    let config_dir = get_app_config_dir().map_err(|e| e.to_string())?;
+    Ok(onboarding_state(&config, &config_dir))
+}
+
+/// Save the first-run wizard's choices and mark setup as completed.
+#[tauri::command]
+pub async fn apply_onboarding_choices(
+    app_handle: AppHandle,
+    choices: OnboardingChoices,
+) -> Result<OnboardingOutcome, String> {
+    let system_ram_mb = piston_lib::utils::hardware::get_total_memory_mb() as i32;
+    let updates = choice_updates(&choices, system_ram_mb)?;
+
+    if let Some(root) = updates.get("default_game_dir").and_then(|v| v.as_str()) {
+        std::fs::create_dir_all(root)
+            .map_err(|e| format!("Cannot use {} as instances folder: {}", root, e))?;
+    }
+    update_config_fields(app_handle.clone(), updates)?;
+
+    let launchers_to_import = if choices.import_from_launchers {
+        let import_manager = app_handle.state::<ImportManager>().inner().clone();
+        tokio::task::spawn_blocking(move || installed_launchers(import_manager.detect_launchers()))
+            .await
+            .map_err(|e| e.to_string())?
+    } else {
+        Vec::new()
+    };
+
+    Ok(OnboardingOutcome {
+        state: get_onboarding_state()?,
+        launchers_to_import,
+    })
+}
+
+/// Inspect the machine to prefill the first-run wizard.
+#[tauri::command]
+pub async fn detect_recommended_defaults(
+    app_handle: AppHandle,
+) -> Result<RecommendedDefaults, String> {
+    let state = get_onboarding_state()?;
+    let import_manager = app_handle.state::<ImportManager>().inner().clone();
+    let (detected_javas, detected_launchers) = tokio::task::spawn_blocking(move || {
+        let mut javas = crate::utils::java::get_managed_javas();
+        javas.extend(crate::utils::java::scan_system_javas_filtered());
+        (
+            javas,
+            installed_launchers(import_manager.detect_launchers()),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let total_memory_mb = piston_lib::utils::hardware::get_total_memory_mb();
+    Ok(RecommendedDefaults {
+        total_memory_mb,
+        max_memory_mb: recommended_max_memory_mb(total_memory_mb as i32),
+        instances_root: state.instances_root,
+        theme_id: state.theme_id,
+        show_snapshots: state.show_snapshots,
+        detected_javas,
+        detected_launchers,
+    })
+}
+
 #[tauri::command]
 pub async fn set_setup_step(step: i32, app_handle: AppHandle) -> Result<(), String> {
     update_config_field(app_handle, "setup_step".to_string(), json!(step))
@@ -344,6 +344,15 @@ pub async fn get_resource_project(
     Ok(res?)
 }
 
+#[tauri::command]
+pub async fn get_resource_project_details(
+    resource_manager: State<'_, ResourceManager>,
+    platform: SourcePlatform,
+    id: String,
+) -> Result<ResourceProject> {
+    Ok(resource_manager.get_project_details(platform, &id).await?)
+}
+
 #[tauri::command]
 pub async fn cache_resource_metadata(
     resource_manager: State<'_, ResourceManager>,
@@ -720,16 +729,25 @@ pub async fn check_instance_updates_lightweight(
 
     let rm = resource_manager.inner().clone();
     let mc_version = inst.minecraft_version.clone();
+    let source_override = crate::resources::sources::custom::SourceOverride::from_column(
+        inst.instance_source_override.as_deref(),
+    );
     let update_results = stream::iter(candidates)
         .map(|res| {
             let rm = rm.clone();
             let mc_version = mc_version.clone();
             let loader = loader.clone();
+            let source_override = source_override.clone();
             let ignore_version_cache = force_refresh || force_resource_ids.contains(&res.id);
             async move {
                 let platform = source_platform_from_str(&res.platform)?;
                 let versions = rm
-                    .get_versions(platform, &res.remote_id, ignore_version_cache, None, None)
+                    .get_versions_with_override(
+                        platform,
+                        &res.remote_id,
+                        ignore_version_cache,
+                        source_override.as_ref(),
+                    )
                     .await
                     .ok()?;
                 let best = crate::resources::update_policy::find_best_update(
@@ -785,7 +803,7 @@ pub async fn check_instance_updates_lightweight(
     Ok(result)
 }
 
-fn source_platform_from_str(platform: &str) -> Option<SourcePlatform> {
+pub(crate) fn source_platform_from_str(platform: &str) -> Option<SourcePlatform> {
     match platform {
         "modrinth" => Some(SourcePlatform::Modrinth),
         "curseforge" => Some(SourcePlatform::CurseForge),
@@ -975,6 +993,36 @@ pub async fn backfill_modpack_resource_provenance(
     Ok(matched_vec.len())
 }
 
+/// Install one exact version without dependency resolution or a background
+/// task, returning the recorded ledger row.
+#[tauri::command]
+pub async fn install_resource_version(
+    app_handle: tauri::AppHandle,
+    resource_manager: State<'_, ResourceManager>,
+    instance_id: i32,
+    platform: SourcePlatform,
+    project_id: String,
+    version_id: String,
+) -> Result<crate::models::installed_resource::InstalledResource> {
+    let is_guest = crate::auth::get_active_account()
+        .ok()
+        .flatten()
+        .map(|acc| acc.account_type == ACCOUNT_TYPE_GUEST)
+        .unwrap_or(false);
+    if is_guest {
+        return Err(anyhow!(
+            "You must be signed in with a Microsoft account to install mods or resources."
+        )
+        .into());
+    }
+
+    let installed = resource_manager
+        .install_resource(platform, &project_id, &version_id, instance_id)
+        .await?;
+    let _ = app_handle.emit("resources-updated", instance_id);
+    Ok(installed)
+}
+
 #[tauri::command]
 pub async fn install_resource(
     app_handle: tauri::AppHandle,
@@ -20,12 +20,12 @@ pub fn get_screenshots(instance_id_slug: String) -> Result<Vec<Screenshot>, Stri
     let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
 
     let instances_list = instance
-        .select((name, game_directory))
-        .load::<(String, Option<String>)>(&mut conn)
+        .select((name, game_directory, slug))
+        .load::<(String, Option<String>, Option<String>)>(&mut conn)
         .map_err(|e| format!("Failed to query instances: {}", e))?;
 
-    let found_dir = instances_list.into_iter().find_map(|(_name, _gd)| {
-        let i_slug = crate::utils::sanitize::sanitize_instance_name(&_name);
+    let found_dir = instances_list.into_iter().find_map(|(_name, _gd, _slug)| {
+        let i_slug = crate::utils::sanitize::resolve_instance_slug(&_name, _slug.as_deref());
         if i_slug == instance_id_slug {
             _gd
         } else {
@@ -95,32 +95,7 @@ pub fn open_screenshot_in_folder(path: String) -> Result<(), String> {
         return Err("File does not exist".to_string());
     }
 
-    #[cfg(target_os = "windows")]
-    {
-        use std::process::Command;
-        Command::new("explorer")
-            .arg("/select,")
-            .arg(p)
-            .spawn()
-            .map_err(|e| e.to_string())?;
-    }
-    #[cfg(target_os = "macos")]
-    {
-        use std::process::Command;
-        Command::new("open")
-            .arg("-R")
-            .arg(p)
-            .spawn()
-            .map_err(|e| e.to_string())?;
-    }
-    #[cfg(target_os = "linux")]
-    {
-        if let Some(parent) = p.parent() {
-            open::that(parent).map_err(|e| e.to_string())?;
-        }
-    }
-
-    Ok(())
+    crate::instance::file_access::reveal_in_file_manager(&p)
 }
 
 #[tauri::command]
@@ -1,6 +1,16 @@
+use crate::models::TaskHistoryEntry;
+use crate::tasks::chain::ChainStatus;
+use crate::tasks::download_stats::{self, DownloadStats, StatsRange};
+use crate::tasks::history;
 use crate::tasks::manager::TaskManager;
+use crate::tasks::registry::TaskSummary;
+use crate::utils::db::get_vesta_conn;
+use piston_lib::game::installer::core::batch::{asset_download_snapshot, DownloadQueueSnapshot};
 use tauri::State;
 
+/// Finished tasks returned by `get_task_history` when no limit is given.
+const DEFAULT_TASK_HISTORY_LIMIT: i64 = 50;
+
 #[tauri::command]
 pub async fn set_worker_limit(state: State<'_, TaskManager>, limit: usize) -> Result<(), String> {
     state.set_worker_count(limit);
@@ -11,3 +21,45 @@ pub async fn set_worker_limit(state: State<'_, TaskManager>, limit: usize) -> Re
 pub async fn cancel_task(state: State<'_, TaskManager>, client_key: String) -> Result<(), String> {
     state.cancel_task(&client_key)
 }
+
+/// Progress of a chain started with `TaskManager::submit_chain`.
+#[tauri::command]
+pub async fn get_chain_status(
+    state: State<'_, TaskManager>,
+    chain_id: String,
+) -> Result<ChainStatus, String> {
+    state
+        .get_chain_status(&chain_id)
+        .ok_or_else(|| format!("Unknown task chain: {}", chain_id))
+}
+
+#[tauri::command]
+pub async fn list_active_tasks(state: State<'_, TaskManager>) -> Result<Vec<TaskSummary>, String> {
+    Ok(state.list_active_tasks())
+}
+
+#[tauri::command]
+pub async fn get_task_history(limit: Option<i64>) -> Result<Vec<TaskHistoryEntry>, String> {
+    let limit = limit
+        .unwrap_or(DEFAULT_TASK_HISTORY_LIMIT)
+        .clamp(1, history::MAX_TASK_HISTORY);
+    let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
+    history::recent_tasks(&mut conn, limit)
+}
+
+/// Downloaded bytes by category for the current month or the lifetime.
+#[tauri::command]
+pub async fn get_download_stats(range: StatsRange) -> Result<DownloadStats, String> {
+    if let Err(e) = download_stats::flush_pending() {
+        log::warn!("{}", e);
+    }
+    let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
+    download_stats::load_stats(&mut conn, range, &download_stats::current_month())
+}
+
+/// Files of the running asset downloads, for a per-file progress view.
+/// Reads the live queue; the frontend polls it.
+#[tauri::command]
+pub fn get_asset_download_progress() -> Result<DownloadQueueSnapshot, String> {
+    Ok(asset_download_snapshot())
+}
@@ -1,3 +0,0 @@
-pub mod manager;
-
-pub use manager::DiscordManager;
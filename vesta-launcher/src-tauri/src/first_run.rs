@@ -0,0 +1,200 @@
+//! First-run setup wizard backend.
+//!
+//! Wizard progress is kept in the `setup_step` / `setup_completed` config
+//! columns. Applying the choices writes plain config fields, so it can be run
+//! again from settings (after `reset_onboarding`) with the same result.
+
+use crate::launcher_import::types::DetectedLauncher;
+use crate::utils::config::{canonical_theme_data_for_theme_id, AppConfig};
+use crate::utils::instance_helpers::resolve_instances_root;
+use crate::utils::memory_policy::{
+    clamp_manual_memory_range, dynamic_preferred_max_memory_mb, generated_memory_limit_mb,
+    DEFAULT_MIN_MEMORY_MB,
+};
+use piston_lib::game::installer::core::jre_manager::DetectedJava;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// `setup_step` value of a finished wizard.
+pub const FINAL_SETUP_STEP: i32 = 6;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OnboardingState {
+    pub setup_completed: bool,
+    pub setup_step: i32,
+    pub tutorial_completed: bool,
+    pub instances_root: String,
+    pub default_max_memory: i32,
+    pub theme_id: String,
+    pub show_snapshots: bool,
+}
+
+/// Wizard answers. Fields left out keep their current value.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct OnboardingChoices {
+    pub instances_root: Option<String>,
+    pub max_memory_mb: Option<i32>,
+    pub theme_id: Option<String>,
+    pub show_snapshots: Option<bool>,
+    pub import_from_launchers: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OnboardingOutcome {
+    pub state: OnboardingState,
+    /// Launchers found on this machine when the user chose to import; the
+    /// wizard continues with the regular import flow for these.
+    pub launchers_to_import: Vec<DetectedLauncher>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecommendedDefaults {
+    pub total_memory_mb: u64,
+    pub max_memory_mb: i32,
+    pub instances_root: String,
+    pub theme_id: String,
+    pub show_snapshots: bool,
+    pub detected_javas: Vec<DetectedJava>,
+    pub detected_launchers: Vec<DetectedLauncher>,
+}
+
+pub fn onboarding_state(config: &AppConfig, app_config_dir: &Path) -> OnboardingState {
+    OnboardingState {
+        setup_completed: config.setup_completed,
+        setup_step: config.setup_step,
+        tutorial_completed: config.tutorial_completed,
+        instances_root: resolve_instances_root(app_config_dir, config.default_game_dir.as_deref())
+            .to_string_lossy()
+            .to_string(),
+        default_max_memory: config.default_max_memory,
+        theme_id: config.theme_id.clone(),
+        show_snapshots: config.show_snapshots,
+    }
+}
+
+/// Default heap for a machine with `system_ram_mb` of RAM, leaving the OS
+/// enough headroom on small machines.
+pub fn recommended_max_memory_mb(system_ram_mb: i32) -> i32 {
+    dynamic_preferred_max_memory_mb(system_ram_mb).min(generated_memory_limit_mb(system_ram_mb))
+}
+
+/// Config field updates for `choices`, validated against the machine, that
+/// also mark the wizard as finished.
+pub fn choice_updates(
+    choices: &OnboardingChoices,
+    system_ram_mb: i32,
+) -> Result<HashMap<String, Value>, String> {
+    let mut updates = HashMap::new();
+
+    if let Some(root) = &choices.instances_root {
+        let root = root.trim();
+        if !Path::new(root).is_absolute() || Path::new(root).parent().is_none() {
+            return Err(format!(
+                "Instances folder must be an absolute path: {}",
+                root
+            ));
+        }
+        updates.insert("default_game_dir".to_string(), json!(root));
+    }
+
+    if let Some(max_memory) = choices.max_memory_mb {
+        let range = clamp_manual_memory_range(DEFAULT_MIN_MEMORY_MB, max_memory, system_ram_mb);
+        updates.insert("default_max_memory".to_string(), json!(range.max));
+    }
+
+    if let Some(theme_id) = &choices.theme_id {
+        let theme_id = theme_id.trim().to_lowercase();
+        if theme_id.is_empty() {
+            return Err("Theme id must not be empty".to_string());
+        }
+        updates.insert(
+            "theme_data".to_string(),
+            json!(canonical_theme_data_for_theme_id(&theme_id)),
+        );
+        updates.insert("theme_id".to_string(), json!(theme_id));
+    }
+
+    if let Some(show_snapshots) = choices.show_snapshots {
+        updates.insert("show_snapshots".to_string(), json!(show_snapshots));
+    }
+
+    updates.insert("setup_completed".to_string(), json!(true));
+    updates.insert("setup_step".to_string(), json!(FINAL_SETUP_STEP));
+    Ok(updates)
+}
+
+/// Launchers with at least one install found on disk.
+pub fn installed_launchers(detected: Vec<DetectedLauncher>) -> Vec<DetectedLauncher> {
+    detected
+        .into_iter()
+        .filter(|launcher| !launcher.detected_paths.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recommended_memory_leaves_headroom_on_small_machines() {
+        assert_eq!(recommended_max_memory_mb(4096), 3072);
+        assert_eq!(recommended_max_memory_mb(8192), 4096);
+        assert_eq!(recommended_max_memory_mb(32768), 8192);
+    }
+
+    #[test]
+    fn choices_are_validated_and_complete_the_wizard() {
+        let choices = OnboardingChoices {
+            instances_root: Some(std::env::temp_dir().to_string_lossy().to_string()),
+            max_memory_mb: Some(64000),
+            theme_id: Some(" Solar ".to_string()),
+            show_snapshots: Some(true),
+            import_from_launchers: true,
+        };
+
+        let updates = choice_updates(&choices, 8192).unwrap();
+
+        assert_eq!(updates["default_max_memory"], json!(8192));
+        assert_eq!(updates["theme_id"], json!("solar"));
+        assert_eq!(updates["show_snapshots"], json!(true));
+        assert_eq!(updates["setup_completed"], json!(true));
+        assert_eq!(updates["setup_step"], json!(FINAL_SETUP_STEP));
+        assert_eq!(updates, choice_updates(&choices, 8192).unwrap());
+    }
+
+    #[test]
+    fn relative_instance_roots_are_rejected() {
+        let choices = OnboardingChoices {
+            instances_root: Some("instances".to_string()),
+            ..Default::default()
+        };
+        assert!(choice_updates(&choices, 8192).is_err());
+    }
+
+    #[test]
+    fn untouched_choices_only_mark_completion() {
+        let updates = choice_updates(&OnboardingChoices::default(), 8192).unwrap();
+        let mut fields: Vec<_> = updates.keys().cloned().collect();
+        fields.sort();
+        assert_eq!(fields, vec!["setup_completed", "setup_step"]);
+    }
+
+    #[test]
+    fn state_reflects_config() {
+        let config = AppConfig {
+            setup_step: 3,
+            default_game_dir: None,
+            ..AppConfig::default()
+        };
+        let state = onboarding_state(&config, Path::new("/data/vesta"));
+        assert_eq!(state.setup_step, 3);
+        assert!(!state.setup_completed);
+        assert_eq!(
+            Path::new(&state.instances_root),
+            Path::new("/data/vesta").join("instances")
+        );
+    }
+}
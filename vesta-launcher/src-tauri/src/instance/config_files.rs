@@ -0,0 +1,389 @@
+//! In-launcher editing of an instance's `config/` folder.
+//!
+//! Every path comes from the frontend relative to `<game dir>/config` and is
+//! resolved through [`resolve_config_path`], which rejects anything that would
+//! land outside that folder (including via symlinks). Writes are validated in
+//! the file's detected format first and keep a single `.bak` of the previous
+//! contents.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+pub const CONFIG_DIR_NAME: &str = "config";
+/// Files above this size are listed but cannot be opened in the editor.
+pub const MAX_EDITABLE_CONFIG_BYTES: u64 = 2 * 1024 * 1024;
+const BINARY_SNIFF_BYTES: usize = 8192;
+const BACKUP_SUFFIX: &str = ".bak";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+    Properties,
+    Yaml,
+    /// Plain text such as Forge `.cfg` files; saved without validation.
+    Text,
+}
+
+impl ConfigFormat {
+    pub fn detect(path: &Path) -> Self {
+        let ext = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+        match ext.as_str() {
+            "toml" => ConfigFormat::Toml,
+            "json" | "mcmeta" => ConfigFormat::Json,
+            "properties" => ConfigFormat::Properties,
+            "yml" | "yaml" => ConfigFormat::Yaml,
+            _ => ConfigFormat::Text,
+        }
+    }
+
+    /// Check that `content` parses as this format.
+    pub fn validate(self, content: &str) -> Result<(), String> {
+        match self {
+            ConfigFormat::Toml => toml::from_str::<toml::Table>(content)
+                .map(|_| ())
+                .map_err(|e| format!("Invalid TOML: {}", e)),
+            ConfigFormat::Json => serde_json::from_str::<serde_json::Value>(content)
+                .map(|_| ())
+                .map_err(|e| format!("Invalid JSON: {}", e)),
+            ConfigFormat::Properties => validate_properties(content),
+            ConfigFormat::Yaml => serde_yaml::from_str::<serde_yaml::Value>(content)
+                .map(|_| ())
+                .map_err(|e| format!("Invalid YAML: {}", e)),
+            ConfigFormat::Text => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigFileEntry {
+    /// Path relative to the instance's `config/` folder, `/`-separated.
+    pub path: String,
+    pub size: u64,
+    pub format: ConfigFormat,
+    pub editable: bool,
+}
+
+pub fn config_root(game_dir: &Path) -> PathBuf {
+    game_dir.join(CONFIG_DIR_NAME)
+}
+
+/// Resolve a frontend-supplied path under `config_root`, refusing absolute
+/// paths, `..` components and symlinks that point outside the folder.
+pub fn resolve_config_path(config_root: &Path, relative_path: &str) -> Result<PathBuf, String> {
+    let joined = piston_lib::utils::paths::join_validated(config_root, relative_path)
+        .map_err(|_| format!("'{}' is outside the instance config folder", relative_path))?;
+
+    let canonical_root = config_root
+        .canonicalize()
+        .map_err(|e| format!("Config folder is not available: {}", e))?;
+    // New files do not exist yet, so check the closest existing ancestor.
+    let mut existing = joined.as_path();
+    while !existing.exists() {
+        existing = existing
+            .parent()
+            .ok_or_else(|| format!("'{}' is outside the instance config folder", relative_path))?;
+    }
+    let canonical = existing
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve '{}': {}", relative_path, e))?;
+    if !canonical.starts_with(&canonical_root) {
+        return Err(format!(
+            "'{}' is outside the instance config folder",
+            relative_path
+        ));
+    }
+
+    Ok(joined)
+}
+
+/// Walk `config_root` and list every text file, skipping binaries and
+/// backups written by this editor.
+pub fn list_config_files(config_root: &Path) -> Vec<ConfigFileEntry> {
+    let Ok(canonical_root) = config_root.canonicalize() else {
+        return Vec::new();
+    };
+    let mut entries = Vec::new();
+    let mut pending = vec![canonical_root.clone()];
+
+    while let Some(dir) = pending.pop() {
+        let Ok(read_dir) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            // Symlinks are not followed so the walk cannot leave the folder.
+            if file_type.is_symlink() {
+                continue;
+            }
+            if file_type.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            if path.to_string_lossy().ends_with(BACKUP_SUFFIX) || is_binary_file(&path) {
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(&canonical_root) else {
+                continue;
+            };
+            let size = entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+            entries.push(ConfigFileEntry {
+                path: relative.to_string_lossy().replace('\\', "/"),
+                size,
+                format: ConfigFormat::detect(&path),
+                editable: size <= MAX_EDITABLE_CONFIG_BYTES,
+            });
+        }
+    }
+
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    entries
+}
+
+pub fn read_config_file(path: &Path) -> Result<String, String> {
+    let size = std::fs::metadata(path)
+        .map_err(|e| format!("Failed to read config file: {}", e))?
+        .len();
+    ensure_editable_size(size)?;
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read config file: {}", e))?;
+    if looks_binary(&bytes) {
+        return Err("Binary files cannot be edited".to_string());
+    }
+    String::from_utf8(bytes).map_err(|_| "Config file is not valid UTF-8 text".to_string())
+}
+
+/// Validate `content`, copy the current file to `<name>.bak` and write the new
+/// contents.
+pub fn write_config_file(path: &Path, content: &str) -> Result<(), String> {
+    ensure_editable_size(content.len() as u64)?;
+    ConfigFormat::detect(path).validate(content)?;
+
+    if path.exists() {
+        if is_binary_file(path) {
+            return Err("Binary files cannot be edited".to_string());
+        }
+        std::fs::copy(path, backup_path(path))
+            .map_err(|e| format!("Failed to back up config file: {}", e))?;
+    } else if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config folder: {}", e))?;
+    }
+
+    std::fs::write(path, content).map_err(|e| format!("Failed to write config file: {}", e))
+}
+
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(BACKUP_SUFFIX);
+    PathBuf::from(name)
+}
+
+fn ensure_editable_size(size: u64) -> Result<(), String> {
+    if size > MAX_EDITABLE_CONFIG_BYTES {
+        return Err(format!(
+            "Config file is too large to edit in the launcher ({} KiB, limit {} KiB)",
+            size / 1024,
+            MAX_EDITABLE_CONFIG_BYTES / 1024
+        ));
+    }
+    Ok(())
+}
+
+fn is_binary_file(path: &Path) -> bool {
+    use std::io::Read;
+
+    let Ok(file) = std::fs::File::open(path) else {
+        return true;
+    };
+    let mut head = Vec::with_capacity(BINARY_SNIFF_BYTES);
+    if file
+        .take(BINARY_SNIFF_BYTES as u64)
+        .read_to_end(&mut head)
+        .is_err()
+    {
+        return true;
+    }
+    looks_binary(&head)
+}
+
+fn looks_binary(bytes: &[u8]) -> bool {
+    bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0)
+}
+
+/// Mirrors `java.util.Properties`: the only hard parse error is a malformed
+/// `\uXXXX` escape.
+fn validate_properties(content: &str) -> Result<(), String> {
+    for (line_no, line) in content.lines().enumerate() {
+        let mut chars = line.chars();
+        while let Some(ch) = chars.next() {
+            if ch != '\\' {
+                continue;
+            }
+            if chars.next() == Some('u') {
+                let digits: String = chars.by_ref().take(4).collect();
+                if digits.len() != 4 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(format!(
+                        "Invalid properties: malformed \\uXXXX escape on line {}",
+                        line_no + 1
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_dir() -> (tempfile::TempDir, PathBuf) {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join(CONFIG_DIR_NAME);
+        std::fs::create_dir_all(root.join("sodium")).unwrap();
+        (tmp, root)
+    }
+
+    #[test]
+    fn detects_formats_from_extension() {
+        assert_eq!(
+            ConfigFormat::detect(Path::new("a/b.TOML")),
+            ConfigFormat::Toml
+        );
+        assert_eq!(
+            ConfigFormat::detect(Path::new("b.json")),
+            ConfigFormat::Json
+        );
+        assert_eq!(
+            ConfigFormat::detect(Path::new("server.properties")),
+            ConfigFormat::Properties
+        );
+        assert_eq!(ConfigFormat::detect(Path::new("c.yml")), ConfigFormat::Yaml);
+        assert_eq!(
+            ConfigFormat::detect(Path::new("forge.cfg")),
+            ConfigFormat::Text
+        );
+    }
+
+    #[test]
+    fn validates_toml_and_json() {
+        assert!(ConfigFormat::Toml.validate("[client]\nfov = 90\n").is_ok());
+        assert!(ConfigFormat::Toml.validate("[client\nfov = 90").is_err());
+        assert!(ConfigFormat::Toml.validate("a = 1\na = 2").is_err());
+        assert!(ConfigFormat::Json.validate(r#"{"a": [1, 2]}"#).is_ok());
+        assert!(ConfigFormat::Json.validate(r#"{"a": [1, 2}"#).is_err());
+    }
+
+    #[test]
+    fn validates_properties_escapes() {
+        assert!(ConfigFormat::Properties
+            .validate("motd=Hello \\u00e9\n# comment\nkey:value")
+            .is_ok());
+        assert!(ConfigFormat::Properties.validate("motd=\\u00g1").is_err());
+        assert!(ConfigFormat::Properties.validate("motd=\\u12").is_err());
+    }
+
+    #[test]
+    fn validates_yaml() {
+        assert!(ConfigFormat::Yaml
+            .validate("root:\n  list: [a, b]\n  map: {k: 'it''s'}\n  # [not closed\n")
+            .is_ok());
+        assert!(ConfigFormat::Yaml.validate("root:\n\tchild: 1").is_err());
+        assert!(ConfigFormat::Yaml.validate("list: [a, b").is_err());
+        assert!(ConfigFormat::Yaml.validate("key: a: b").is_err());
+        assert!(ConfigFormat::Yaml
+            .validate("motd: \"first\n  second\"\nnote: don't")
+            .is_ok());
+        assert!(ConfigFormat::Yaml.validate("name: 'open").is_err());
+    }
+
+    #[test]
+    fn rejects_paths_outside_config_folder() {
+        let (tmp, root) = config_dir();
+        std::fs::write(tmp.path().join("options.txt"), "fov:90").unwrap();
+
+        assert!(resolve_config_path(&root, "sodium/options.json").is_ok());
+        assert!(resolve_config_path(&root, "new/file.toml").is_ok());
+        assert!(resolve_config_path(&root, "../options.txt").is_err());
+        assert!(resolve_config_path(&root, "sodium/../../options.txt").is_err());
+        assert!(resolve_config_path(&root, "/etc/passwd").is_err());
+        assert!(resolve_config_path(&root, "C:\\Windows\\win.ini").is_err());
+        assert!(resolve_config_path(&root, "").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejects_symlinks_leaving_config_folder() {
+        let (tmp, root) = config_dir();
+        let outside = tmp.path().join("outside");
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(outside.join("secret.toml"), "a = 1").unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("escape")).unwrap();
+
+        assert!(resolve_config_path(&root, "escape/secret.toml").is_err());
+        assert!(list_config_files(&root)
+            .iter()
+            .all(|entry| !entry.path.starts_with("escape")));
+    }
+
+    #[test]
+    fn lists_text_files_and_skips_binaries_and_backups() {
+        let (_tmp, root) = config_dir();
+        std::fs::write(root.join("sodium/options.json"), "{}").unwrap();
+        std::fs::write(root.join("a.toml"), "a = 1").unwrap();
+        std::fs::write(root.join("a.toml.bak"), "a = 0").unwrap();
+        std::fs::write(root.join("cache.bin"), [0u8, 1, 2, 3]).unwrap();
+
+        let listed: Vec<(String, ConfigFormat)> = list_config_files(&root)
+            .into_iter()
+            .map(|entry| (entry.path, entry.format))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("a.toml".to_string(), ConfigFormat::Toml),
+                ("sodium/options.json".to_string(), ConfigFormat::Json),
+            ]
+        );
+    }
+
+    #[test]
+    fn write_validates_and_keeps_one_backup() {
+        let (_tmp, root) = config_dir();
+        let path = root.join("a.toml");
+        std::fs::write(&path, "a = 1").unwrap();
+
+        assert!(write_config_file(&path, "a = ").is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a = 1");
+        assert!(!backup_path(&path).exists());
+
+        write_config_file(&path, "a = 2").unwrap();
+        write_config_file(&path, "a = 3").unwrap();
+        assert_eq!(read_config_file(&path).unwrap(), "a = 3");
+        assert_eq!(
+            std::fs::read_to_string(backup_path(&path)).unwrap(),
+            "a = 2"
+        );
+    }
+
+    #[test]
+    fn refuses_oversized_and_binary_files() {
+        let (_tmp, root) = config_dir();
+        let big = root.join("big.txt");
+        let file = std::fs::File::create(&big).unwrap();
+        file.set_len(MAX_EDITABLE_CONFIG_BYTES + 1).unwrap();
+        assert!(read_config_file(&big).unwrap_err().contains("too large"));
+
+        let binary = root.join("data.dat");
+        std::fs::write(&binary, [b'a', 0, b'b']).unwrap();
+        assert!(read_config_file(&binary).is_err());
+        assert!(write_config_file(&binary, "text").is_err());
+    }
+}
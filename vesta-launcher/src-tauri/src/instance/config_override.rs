@@ -0,0 +1,200 @@
+//! Per-instance overrides of the resource and process settings in `AppConfig`.
+//!
+//! Stored as JSON in `instance.config_override_json`. Every field is
+//! optional; a set field wins over both the instance's own setting and the
+//! global default when the instance is launched or installed.
+
+use crate::models::instance::Instance;
+use anyhow::{anyhow, bail, Result};
+use piston_lib::game::installer::types::DownloadConcurrency;
+use piston_lib::game::launcher::priority::{self, Priority};
+use serde::{Deserialize, Serialize};
+
+/// Upper bound for `max_download_threads`; more only adds contention.
+const MAX_DOWNLOAD_THREADS: i32 = 64;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstanceConfigOverride {
+    #[serde(default)]
+    pub max_memory_mb: Option<i32>,
+    #[serde(default)]
+    pub min_memory_mb: Option<i32>,
+    #[serde(default)]
+    pub java_path: Option<String>,
+    /// JVM arguments placed before the instance or global Java arguments.
+    #[serde(default)]
+    pub java_args_prefix: Option<String>,
+    #[serde(default)]
+    pub max_download_threads: Option<i32>,
+    /// Scheduling priority of the game process.
+    #[serde(default)]
+    pub process_priority: Option<Priority>,
+    /// Cores the game may run on, bit 0 being the first.
+    #[serde(default)]
+    pub cpu_affinity_mask: Option<u64>,
+}
+
+impl InstanceConfigOverride {
+    /// Parse and validate the stored JSON form.
+    pub fn parse(json: &str) -> Result<Self> {
+        let parsed: InstanceConfigOverride = serde_json::from_str(json)
+            .map_err(|e| anyhow!("Invalid instance config override JSON: {}", e))?;
+        parsed.validated()
+    }
+
+    /// Read the instance column, ignoring (and logging) unusable values so a
+    /// bad override falls back to the regular settings instead of blocking launch.
+    pub fn from_column(value: Option<&str>) -> Option<Self> {
+        let value = value.map(str::trim).filter(|v| !v.is_empty())?;
+        match Self::parse(value) {
+            Ok(config_override) => Some(config_override),
+            Err(e) => {
+                log::warn!("[InstanceConfig] Ignoring instance config override: {}", e);
+                None
+            }
+        }
+    }
+
+    pub fn for_instance(instance: &Instance) -> Option<Self> {
+        Self::from_column(instance.config_override_json.as_deref())
+    }
+
+    /// Trim text fields (blank means unset) and reject impossible values.
+    pub fn validated(self) -> Result<Self> {
+        let text = |value: Option<String>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let validated = Self {
+            max_memory_mb: self.max_memory_mb,
+            min_memory_mb: self.min_memory_mb,
+            java_path: text(self.java_path),
+            java_args_prefix: text(self.java_args_prefix),
+            max_download_threads: self.max_download_threads,
+            process_priority: self.process_priority,
+            cpu_affinity_mask: self.cpu_affinity_mask,
+        };
+
+        for (name, value) in [
+            ("max_memory_mb", validated.max_memory_mb),
+            ("min_memory_mb", validated.min_memory_mb),
+        ] {
+            if value.is_some_and(|mb| mb <= 0) {
+                bail!("{} must be positive", name);
+            }
+        }
+        if let (Some(min), Some(max)) = (validated.min_memory_mb, validated.max_memory_mb) {
+            if min > max {
+                bail!(
+                    "min_memory_mb ({}) is larger than max_memory_mb ({})",
+                    min,
+                    max
+                );
+            }
+        }
+        if let Some(threads) = validated.max_download_threads {
+            if !(1..=MAX_DOWNLOAD_THREADS).contains(&threads) {
+                bail!(
+                    "max_download_threads must be between 1 and {}",
+                    MAX_DOWNLOAD_THREADS
+                );
+            }
+        }
+        if let Some(mask) = validated.cpu_affinity_mask {
+            priority::validate_affinity_mask(mask, priority::logical_core_count())?;
+        }
+        if let Some(prefix) = &validated.java_args_prefix {
+            shlex::split(prefix)
+                .ok_or_else(|| anyhow!("java_args_prefix has unbalanced quotes"))?;
+        }
+        Ok(validated)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Memory range to launch with, before clamping to system RAM.
+    pub fn memory_range(&self, min_memory: i32, max_memory: i32) -> (i32, i32) {
+        let max = self.max_memory_mb.unwrap_or(max_memory);
+        let min = self.min_memory_mb.unwrap_or(min_memory.min(max));
+        (min, max)
+    }
+
+    /// Download concurrency for installs and launch repairs. A thread
+    /// override applies to every install phase.
+    pub fn download_concurrency(&self, default: DownloadConcurrency) -> DownloadConcurrency {
+        self.max_download_threads
+            .map(|threads| DownloadConcurrency::uniform(threads as usize))
+            .unwrap_or(default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_are_validated_and_trimmed() {
+        let parsed = InstanceConfigOverride::parse(
+            r#"{"max_memory_mb": 8192, "java_path": "  ", "java_args_prefix": " -XX:+UseZGC "}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.max_memory_mb, Some(8192));
+        assert_eq!(parsed.java_path, None);
+        assert_eq!(parsed.java_args_prefix.as_deref(), Some("-XX:+UseZGC"));
+
+        assert!(
+            InstanceConfigOverride::parse(r#"{"min_memory_mb": 4096, "max_memory_mb": 2048}"#)
+                .is_err()
+        );
+        assert!(InstanceConfigOverride::parse(r#"{"max_memory_mb": 0}"#).is_err());
+        assert!(InstanceConfigOverride::parse(r#"{"max_download_threads": 500}"#).is_err());
+        assert!(InstanceConfigOverride::parse(r#"{"java_args_prefix": "-Dx=\"open"}"#).is_err());
+        assert!(InstanceConfigOverride::parse(r#"{"cpu_affinity_mask": 0}"#).is_err());
+        assert_eq!(InstanceConfigOverride::from_column(Some("not json")), None);
+        assert!(InstanceConfigOverride::parse("{}").unwrap().is_empty());
+    }
+
+    #[test]
+    fn process_settings_are_parsed() {
+        let parsed = InstanceConfigOverride::parse(
+            r#"{"process_priority": "below_normal", "cpu_affinity_mask": 1}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.process_priority, Some(Priority::BelowNormal));
+        assert_eq!(parsed.cpu_affinity_mask, Some(1));
+
+        let cores = priority::logical_core_count();
+        if cores < 64 {
+            let beyond_cores = format!(r#"{{"cpu_affinity_mask": {}}}"#, 1u64 << cores);
+            assert!(InstanceConfigOverride::parse(&beyond_cores).is_err());
+        }
+        assert!(InstanceConfigOverride::parse(r#"{"process_priority": "turbo"}"#).is_err());
+    }
+
+    #[test]
+    fn override_values_take_priority() {
+        let config_override = InstanceConfigOverride {
+            max_memory_mb: Some(2048),
+            max_download_threads: Some(2),
+            ..Default::default()
+        };
+        // The instance minimum is capped by the overridden maximum.
+        assert_eq!(config_override.memory_range(4096, 6144), (2048, 2048));
+        let defaults = DownloadConcurrency {
+            assets: 16,
+            libraries: 8,
+            mods: 4,
+        };
+        assert_eq!(
+            config_override.download_concurrency(defaults),
+            DownloadConcurrency::uniform(2)
+        );
+
+        let none = InstanceConfigOverride::default();
+        assert_eq!(none.memory_range(1024, 4096), (1024, 4096));
+        assert_eq!(none.download_concurrency(defaults), defaults);
+    }
+}
@@ -0,0 +1,442 @@
+//! Filtering for the modpack export file picker.
+//!
+//! The instance root may contain a `.vestaignore` with gitignore-style
+//! patterns; matching files are never offered for export. Remaining files are
+//! pre-ticked when they live somewhere modpacks normally ship (configs,
+//! scripts), and every directory gets a size rollup so the picker can show
+//! how much a folder like `saves/` would add before the user selects it.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+pub const IGNORE_FILE_NAME: &str = ".vestaignore";
+
+/// Default size above which a file or directory is flagged in the picker.
+pub const DEFAULT_SIZE_WARNING_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Launcher and game internals that are never export candidates, at any depth.
+pub const SKIPPED_DIRS: &[&str] = &[
+    "logs",
+    "backups",
+    "crash-reports",
+    "temp",
+    "bin",
+    "natives",
+    "assets",
+    "libraries",
+    "versions",
+    ".mixin.out",
+    "runtime",
+    "cache",
+    "mod-cache",
+    "web-cache",
+    "patchy",
+    ".vesta",
+];
+
+/// Top-level folders that are selected by default.
+const SUGGESTED_DIRS: &[&str] = &["config", "defaultconfigs", "kubejs", "scripts"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportDirectorySize {
+    /// Directory relative to the game directory, `/` separated.
+    pub path: String,
+    pub size: u64,
+    pub file_count: usize,
+    pub size_warning: bool,
+}
+
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    glob: Vec<char>,
+    negated: bool,
+    dir_only: bool,
+    /// Patterns containing a `/` match from the instance root; others match
+    /// a file or directory name at any depth.
+    anchored: bool,
+}
+
+impl IgnoreRule {
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negated, pattern) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let (dir_only, pattern) = match pattern.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, pattern),
+        };
+        let anchored = pattern.contains('/');
+        let pattern = pattern.trim_start_matches('/');
+        if pattern.is_empty() {
+            return None;
+        }
+        Some(Self {
+            glob: pattern.chars().collect(),
+            negated,
+            dir_only,
+            anchored,
+        })
+    }
+
+    fn matches(&self, path: &str, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let subject = if self.anchored {
+            path
+        } else {
+            path.rsplit('/').next().unwrap_or(path)
+        };
+        let subject: Vec<char> = subject.chars().collect();
+        glob_match(&self.glob, &subject)
+    }
+}
+
+/// Parsed `.vestaignore`; later patterns override earlier ones, and `!`
+/// re-includes a path, as in gitignore.
+#[derive(Debug, Clone, Default)]
+pub struct VestaIgnore {
+    rules: Vec<IgnoreRule>,
+}
+
+impl VestaIgnore {
+    pub fn parse(contents: &str) -> Self {
+        Self {
+            rules: contents.lines().filter_map(IgnoreRule::parse).collect(),
+        }
+    }
+
+    /// The instance's `.vestaignore`, or no rules if it is missing or unreadable.
+    pub fn load(game_dir: &Path) -> Self {
+        match std::fs::read_to_string(game_dir.join(IGNORE_FILE_NAME)) {
+            Ok(contents) => Self::parse(&contents),
+            Err(error) => {
+                if error.kind() != std::io::ErrorKind::NotFound {
+                    log::warn!("Failed to read {}: {}", IGNORE_FILE_NAME, error);
+                }
+                Self::default()
+            }
+        }
+    }
+
+    /// Whether `path` (relative, `/` separated) is excluded. As in git, a
+    /// file inside an ignored directory cannot be re-included.
+    pub fn is_ignored(&self, path: &str, is_dir: bool) -> bool {
+        if self.rules.is_empty() {
+            return false;
+        }
+        let parent_ignored = path
+            .match_indices('/')
+            .any(|(end, _)| self.last_match(&path[..end], true));
+        parent_ignored || self.last_match(path, is_dir)
+    }
+
+    fn last_match(&self, path: &str, is_dir: bool) -> bool {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(path, is_dir))
+            .is_some_and(|rule| !rule.negated)
+    }
+}
+
+/// gitignore-style glob: `*` and `?` stop at `/`, `**` spans directories and
+/// `[...]` is a character class (`!` or `^` negates it).
+fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some('*') if pattern.get(1) == Some(&'*') => {
+            let rest = &pattern[2..];
+            if let Some(after_slash) = rest.strip_prefix(&['/']) {
+                // `**/` matches zero or more whole directories.
+                glob_match(after_slash, text)
+                    || text
+                        .iter()
+                        .enumerate()
+                        .any(|(i, c)| *c == '/' && glob_match(after_slash, &text[i + 1..]))
+            } else {
+                (0..=text.len()).any(|i| glob_match(rest, &text[i..]))
+            }
+        }
+        Some('*') => {
+            let rest = &pattern[1..];
+            for i in 0..=text.len() {
+                if glob_match(rest, &text[i..]) {
+                    return true;
+                }
+                if text.get(i) == Some(&'/') {
+                    break;
+                }
+            }
+            false
+        }
+        Some('?') => match text.first() {
+            Some(c) if *c != '/' => glob_match(&pattern[1..], &text[1..]),
+            _ => false,
+        },
+        Some('[') => match (parse_class(&pattern[1..]), text.first()) {
+            (Some((matcher, consumed)), Some(c)) => {
+                *c != '/' && matcher(*c) && glob_match(&pattern[1 + consumed..], &text[1..])
+            }
+            (Some(_), None) => false,
+            // No closing bracket: treat `[` literally.
+            (None, first) => first == Some(&'[') && glob_match(&pattern[1..], &text[1..]),
+        },
+        Some(literal) => text.first() == Some(literal) && glob_match(&pattern[1..], &text[1..]),
+    }
+}
+
+/// Parse a character class body (after `[`); returns the matcher and the
+/// number of pattern chars consumed including the closing `]`.
+fn parse_class(body: &[char]) -> Option<(impl Fn(char) -> bool, usize)> {
+    let (negated, start) = match body.first() {
+        Some('!') | Some('^') => (true, 1),
+        _ => (false, 0),
+    };
+    // A `]` right after the opening bracket is a literal member.
+    let close = body
+        .iter()
+        .enumerate()
+        .skip(start + 1)
+        .find(|(_, c)| **c == ']')
+        .map(|(i, _)| i)?;
+    let members = body[start..close].to_vec();
+    let matcher = move |c: char| {
+        let mut i = 0;
+        let mut found = false;
+        while i < members.len() {
+            if i + 2 < members.len() && members[i + 1] == '-' {
+                found |= members[i] <= c && c <= members[i + 2];
+                i += 3;
+            } else {
+                found |= members[i] == c;
+                i += 1;
+            }
+        }
+        found != negated
+    };
+    Some((matcher, close + 1))
+}
+
+/// Whether a candidate should be ticked by default. Installed mods and packs
+/// always are; other files only inside the usual modpack config folders.
+pub fn is_suggested(path: &str, is_mod: bool) -> bool {
+    if is_mod {
+        return true;
+    }
+    let top = path.split('/').next().unwrap_or(path);
+    path.contains('/') && SUGGESTED_DIRS.contains(&top)
+}
+
+/// Every file under `game_dir` that can be exported, as `/` separated
+/// relative paths with sizes. Skips [`SKIPPED_DIRS`] and ignored paths
+/// without descending into them.
+pub fn walk_export_files(game_dir: &Path, ignore: &VestaIgnore) -> Vec<(String, u64)> {
+    let mut files = Vec::new();
+    let mut stack = vec![(game_dir.to_path_buf(), String::new())];
+
+    while let Some((dir, prefix)) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let name = entry.file_name().to_string_lossy().to_string();
+            let rel_path = if prefix.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", prefix, name)
+            };
+
+            if file_type.is_dir() {
+                if SKIPPED_DIRS.contains(&name.as_str()) || ignore.is_ignored(&rel_path, true) {
+                    continue;
+                }
+                stack.push((entry.path(), rel_path));
+            } else if file_type.is_file() && !ignore.is_ignored(&rel_path, false) {
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                files.push((rel_path, size));
+            }
+        }
+    }
+
+    files
+}
+
+/// Total size and file count of every directory containing candidates,
+/// including nested ones (`saves` and `saves/World`), sorted by path.
+pub fn directory_rollups<'a>(
+    files: impl IntoIterator<Item = (&'a str, u64)>,
+    size_warning_bytes: u64,
+) -> Vec<ExportDirectorySize> {
+    let mut totals: BTreeMap<&'a str, (u64, usize)> = BTreeMap::new();
+    let mut seen = HashSet::new();
+    for (path, size) in files {
+        if !seen.insert(path) {
+            continue;
+        }
+        for (end, _) in path.match_indices('/') {
+            let entry = totals.entry(&path[..end]).or_default();
+            entry.0 = entry.0.saturating_add(size);
+            entry.1 += 1;
+        }
+    }
+
+    totals
+        .into_iter()
+        .map(|(path, (size, file_count))| ExportDirectorySize {
+            path: path.to_string(),
+            size,
+            file_count,
+            size_warning: size > size_warning_bytes,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn globs_follow_gitignore_rules() {
+        let glob = |pattern: &str, text: &str| {
+            let pattern: Vec<char> = pattern.chars().collect();
+            let text: Vec<char> = text.chars().collect();
+            glob_match(&pattern, &text)
+        };
+        assert!(glob("*.log", "latest.log"));
+        assert!(!glob("*.log", "logs/latest.log"));
+        assert!(glob("saves/*", "saves/World"));
+        assert!(!glob("saves/*", "saves/World/level.dat"));
+        assert!(glob("saves/**", "saves/World/level.dat"));
+        assert!(glob("**/waypoints", "journeymap/data/sp/World/waypoints"));
+        assert!(glob("**/waypoints", "waypoints"));
+        assert!(glob("a/**/b", "a/b"));
+        assert!(glob("a/**/b", "a/x/y/b"));
+        assert!(glob("screenshot-?.png", "screenshot-1.png"));
+        assert!(!glob("screenshot-?.png", "screenshot-10.png"));
+        assert!(glob("[Dd]istant*", "DistantHorizons.sqlite"));
+        assert!(glob("world[0-9]", "world7"));
+        assert!(!glob("world[!0-9]", "world7"));
+        assert!(glob("[bad", "[bad"));
+    }
+
+    #[test]
+    fn ignore_file_applies_gitignore_semantics() {
+        let ignore = VestaIgnore::parse(
+            "# local-only data\n\
+             saves/\n\
+             journeymap/\n\
+             *.log\n\
+             /options.txt\n\
+             config/*.bak\n\
+             !config/keep.bak\n\
+             screenshots/\n\
+             !screenshots/cover.png\n",
+        );
+
+        assert!(ignore.is_ignored("saves", true));
+        assert!(ignore.is_ignored("saves/World/level.dat", false));
+        assert!(ignore.is_ignored("journeymap/data/sp/World/waypoints.json", false));
+        assert!(ignore.is_ignored("config/debug.log", false));
+        assert!(ignore.is_ignored("options.txt", false));
+        assert!(!ignore.is_ignored("config/options.txt", false));
+        assert!(ignore.is_ignored("config/old.bak", false));
+        assert!(!ignore.is_ignored("config/keep.bak", false));
+        assert!(!ignore.is_ignored("config/nested/old.bak", false));
+        // Re-including a file inside an ignored directory has no effect.
+        assert!(ignore.is_ignored("screenshots/cover.png", false));
+        // Directory-only patterns don't match files of the same name.
+        assert!(!ignore.is_ignored("saves", false));
+        assert!(!ignore.is_ignored("mods/sodium.jar", false));
+    }
+
+    #[test]
+    fn empty_ignore_excludes_nothing() {
+        let ignore = VestaIgnore::parse("\n# nothing here\n   \n");
+        assert!(!ignore.is_ignored("saves/World/level.dat", false));
+    }
+
+    #[test]
+    fn suggestions_cover_config_folders_and_mods() {
+        assert!(is_suggested("config/sodium-options.json", false));
+        assert!(is_suggested("defaultconfigs/ftbquests.snbt", false));
+        assert!(is_suggested("kubejs/server_scripts/recipes.js", false));
+        assert!(is_suggested("scripts/crafttweaker.zs", false));
+        assert!(is_suggested("mods/sodium.jar", true));
+        assert!(!is_suggested("saves/World/level.dat", false));
+        assert!(!is_suggested("screenshots/2024-01-01.png", false));
+        assert!(!is_suggested("options.txt", false));
+        assert!(!is_suggested("config", false));
+    }
+
+    #[test]
+    fn rollups_sum_nested_directories() {
+        let files = [
+            ("saves/World/level.dat", 600),
+            ("saves/World/region/r.0.0.mca", 1_400),
+            ("saves/Creative/level.dat", 100),
+            ("config/sodium.json", 10),
+            ("options.txt", 5),
+            ("config/sodium.json", 10),
+        ];
+
+        let rollups = directory_rollups(files.iter().map(|(p, s)| (*p, *s as u64)), 1_000);
+        let summary: Vec<(&str, u64, usize, bool)> = rollups
+            .iter()
+            .map(|d| (d.path.as_str(), d.size, d.file_count, d.size_warning))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("config", 10, 1, false),
+                ("saves", 2_100, 3, true),
+                ("saves/Creative", 100, 1, false),
+                ("saves/World", 2_000, 2, true),
+                ("saves/World/region", 1_400, 1, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn walk_skips_internal_and_ignored_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for (path, contents) in [
+            ("options.txt", "a"),
+            ("config/a.toml", "bb"),
+            ("config/cache/blob", "x"),
+            ("logs/latest.log", "x"),
+            ("saves/World/level.dat", "x"),
+            ("kubejs/startup.js", "ccc"),
+            (IGNORE_FILE_NAME, "saves/\n"),
+        ] {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+
+        let mut files = walk_export_files(root, &VestaIgnore::load(root));
+        files.sort();
+
+        assert_eq!(
+            files,
+            vec![
+                (IGNORE_FILE_NAME.to_string(), 7),
+                ("config/a.toml".to_string(), 2),
+                ("kubejs/startup.js".to_string(), 3),
+                ("options.txt".to_string(), 1),
+            ]
+        );
+    }
+}
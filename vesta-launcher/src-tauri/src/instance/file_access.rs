@@ -0,0 +1,196 @@
+//! Opening instance folders and files outside the launcher.
+//!
+//! The frontend names folders from a fixed list and files by a path relative
+//! to the game directory. Every path is canonicalized and must stay inside
+//! the game directory, symlinks included, before it reaches the OS.
+
+use std::path::{Path, PathBuf};
+
+/// Folders the frontend may open, by name; `root` is the game directory.
+pub const OPENABLE_FOLDERS: &[(&str, &str)] = &[
+    ("root", ""),
+    ("mods", "mods"),
+    ("config", "config"),
+    ("saves", "saves"),
+    ("screenshots", "screenshots"),
+    ("crash-reports", "crash-reports"),
+    ("logs", "logs"),
+];
+
+/// Text-like and image files that may be handed to their default app.
+pub const OPENABLE_EXTENSIONS: &[&str] = &[
+    "txt",
+    "log",
+    "json",
+    "json5",
+    "toml",
+    "cfg",
+    "conf",
+    "ini",
+    "properties",
+    "yml",
+    "yaml",
+    "md",
+    "mcmeta",
+    "csv",
+    "png",
+    "jpg",
+    "jpeg",
+    "gif",
+    "webp",
+    "bmp",
+];
+
+fn canonical_game_dir(game_dir: &Path) -> Result<PathBuf, String> {
+    game_dir
+        .canonicalize()
+        .map_err(|e| format!("Instance folder is not available: {}", e))
+}
+
+/// Folder `sub` of the game directory, created if missing. `None` opens the
+/// game directory itself.
+pub fn instance_subfolder(game_dir: &Path, sub: Option<&str>) -> Result<PathBuf, String> {
+    let name = sub.unwrap_or("root");
+    let relative = OPENABLE_FOLDERS
+        .iter()
+        .find(|(key, _)| *key == name)
+        .map(|(_, relative)| *relative)
+        .ok_or_else(|| format!("'{}' is not an instance folder that can be opened", name))?;
+
+    let root = canonical_game_dir(game_dir)?;
+    let folder = root.join(relative);
+    std::fs::create_dir_all(&folder)
+        .map_err(|e| format!("Failed to create {}: {}", folder.display(), e))?;
+    let canonical = folder
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {}: {}", folder.display(), e))?;
+    if !canonical.starts_with(&root) {
+        return Err(format!("'{}' points outside the instance folder", name));
+    }
+    Ok(canonical)
+}
+
+/// Existing file or folder at `relative_path` inside the game directory.
+pub fn resolve_instance_path(game_dir: &Path, relative_path: &str) -> Result<PathBuf, String> {
+    let outside = || format!("'{}' is outside the instance folder", relative_path);
+    let joined =
+        piston_lib::utils::paths::join_validated(game_dir, relative_path).map_err(|_| outside())?;
+    let root = canonical_game_dir(game_dir)?;
+    let canonical = joined
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve '{}': {}", relative_path, e))?;
+    if !canonical.starts_with(&root) {
+        return Err(outside());
+    }
+    Ok(canonical)
+}
+
+/// Reject files whose extension is not in [`OPENABLE_EXTENSIONS`].
+pub fn ensure_openable_file(path: &Path) -> Result<(), String> {
+    if !path.is_file() {
+        return Err(format!("{} is not a file", path.display()));
+    }
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    if !OPENABLE_EXTENSIONS.contains(&extension.as_str()) {
+        return Err(format!(
+            "Files of type '.{}' cannot be opened from the launcher",
+            extension
+        ));
+    }
+    Ok(())
+}
+
+/// Show `path` selected in Explorer or Finder. Linux file managers have no
+/// common way to select a file, so its folder is opened instead.
+pub fn reveal_in_file_manager(path: &Path) -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        std::process::Command::new("explorer")
+            .arg("/select,")
+            .arg(path)
+            .spawn()
+            .map_err(|e| e.to_string())?;
+    }
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg("-R")
+            .arg(path)
+            .spawn()
+            .map_err(|e| e.to_string())?;
+    }
+    #[cfg(target_os = "linux")]
+    {
+        if let Some(parent) = path.parent() {
+            open::that(parent).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_allowlisted_folders_open() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+
+        assert_eq!(instance_subfolder(dir.path(), None).unwrap(), root);
+        assert_eq!(
+            instance_subfolder(dir.path(), Some("crash-reports")).unwrap(),
+            root.join("crash-reports")
+        );
+        assert!(root.join("crash-reports").is_dir());
+        assert!(instance_subfolder(dir.path(), Some("../..")).is_err());
+        assert!(instance_subfolder(dir.path(), Some("natives")).is_err());
+    }
+
+    #[test]
+    fn traversal_outside_the_game_dir_is_rejected() {
+        let parent = tempfile::tempdir().unwrap();
+        let game_dir = parent.path().join("instance");
+        std::fs::create_dir_all(game_dir.join("logs")).unwrap();
+        std::fs::write(game_dir.join("logs/latest.log"), "log").unwrap();
+        std::fs::write(parent.path().join("secret.txt"), "secret").unwrap();
+
+        assert!(resolve_instance_path(&game_dir, "logs/latest.log").is_ok());
+        assert!(resolve_instance_path(&game_dir, "../secret.txt").is_err());
+        assert!(resolve_instance_path(&game_dir, "logs/../../secret.txt").is_err());
+        let absolute = parent.path().join("secret.txt");
+        assert!(resolve_instance_path(&game_dir, &absolute.to_string_lossy()).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_leaving_the_game_dir_are_rejected() {
+        let parent = tempfile::tempdir().unwrap();
+        let game_dir = parent.path().join("instance");
+        let outside = parent.path().join("elsewhere");
+        std::fs::create_dir_all(&game_dir).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(outside.join("notes.txt"), "notes").unwrap();
+        std::os::unix::fs::symlink(&outside, game_dir.join("saves")).unwrap();
+
+        assert!(resolve_instance_path(&game_dir, "saves/notes.txt").is_err());
+        assert!(instance_subfolder(&game_dir, Some("saves")).is_err());
+    }
+
+    #[test]
+    fn only_text_and_image_files_open() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["options.txt", "shot.PNG", "mod.jar", "run.sh"] {
+            std::fs::write(dir.path().join(name), "x").unwrap();
+        }
+
+        assert!(ensure_openable_file(&dir.path().join("options.txt")).is_ok());
+        assert!(ensure_openable_file(&dir.path().join("shot.PNG")).is_ok());
+        assert!(ensure_openable_file(&dir.path().join("mod.jar")).is_err());
+        assert!(ensure_openable_file(&dir.path().join("run.sh")).is_err());
+        assert!(ensure_openable_file(dir.path()).is_err());
+    }
+}
@@ -0,0 +1,490 @@
+//! Per-instance history of install, update, repair and export operations.
+//!
+//! Each task records a row when it starts and completes it when the task
+//! returns, so an operation interrupted by a crash stays visible as
+//! unfinished. History is capped per instance by the maintenance job.
+
+use crate::models::instance_operation::{InstanceOperation, NewInstanceOperation};
+use crate::schema::instance_operation::dsl as op_dsl;
+use crate::utils::db::get_vesta_conn;
+use crate::utils::event_log::{self, EventKind};
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::Integer;
+use diesel::SqliteConnection;
+use piston_lib::game::installer::metrics::InstallMetrics;
+use piston_lib::game::launch_integrity::SpotCheckReport;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+/// Operations kept per instance; older rows are removed by the maintenance job.
+pub(crate) const MAX_OPERATIONS_PER_INSTANCE: i64 = 50;
+
+/// `details` key holding the installer's per-step metrics.
+pub(crate) const INSTALL_METRICS_KEY: &str = "install_metrics";
+
+/// Extra `details` fields a task fills in while it runs; merged into the
+/// operation row when it finishes.
+pub(crate) type OperationNotes = Arc<Mutex<serde_json::Map<String, serde_json::Value>>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OperationKind {
+    Install,
+    Update,
+    Repair,
+    ModpackUpdate,
+    Export,
+    Quarantine,
+    CopySettings,
+}
+
+impl OperationKind {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            OperationKind::Install => "install",
+            OperationKind::Update => "update",
+            OperationKind::Repair => "repair",
+            OperationKind::ModpackUpdate => "modpack_update",
+            OperationKind::Export => "export",
+            OperationKind::Quarantine => "quarantine",
+            OperationKind::CopySettings => "copy_settings",
+        }
+    }
+}
+
+/// Coarse error code for a failed task. Tasks report errors as strings, so
+/// only cancellation is distinguished; the message goes into `details`.
+pub(crate) fn error_code_for(message: &str) -> &'static str {
+    if message.to_lowercase().contains("cancelled") {
+        "cancelled"
+    } else {
+        "failed"
+    }
+}
+
+pub(crate) fn start_operation(
+    conn: &mut SqliteConnection,
+    instance_id: i32,
+    kind: OperationKind,
+    details: &serde_json::Value,
+) -> Result<i32, String> {
+    let row = NewInstanceOperation {
+        instance_id,
+        kind: kind.as_str().to_string(),
+        started_at: chrono::Utc::now().to_rfc3339(),
+        details: details.to_string(),
+    };
+    conn.transaction(|conn| {
+        diesel::insert_into(op_dsl::instance_operation)
+            .values(&row)
+            .execute(conn)?;
+        diesel::select(sql::<Integer>("last_insert_rowid()")).get_result(conn)
+    })
+    .map_err(|e: diesel::result::Error| format!("Failed to record instance operation: {}", e))
+}
+
+pub(crate) fn finish_operation(
+    conn: &mut SqliteConnection,
+    operation_id: i32,
+    result: &Result<(), String>,
+    notes: &serde_json::Map<String, serde_json::Value>,
+) -> Result<(), String> {
+    let finished_at = chrono::Utc::now().to_rfc3339();
+    let error_code = result.as_ref().err().map(|e| error_code_for(e).to_string());
+
+    let mut details = op_dsl::instance_operation
+        .find(operation_id)
+        .select(op_dsl::details)
+        .first::<String>(conn)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+        .filter(|value| value.is_object())
+        .unwrap_or_else(|| serde_json::json!({}));
+    for (key, value) in notes {
+        details[key] = value.clone();
+    }
+    if let Err(message) = result {
+        details["error"] = serde_json::Value::String(message.clone());
+    }
+
+    diesel::update(op_dsl::instance_operation.find(operation_id))
+        .set((
+            op_dsl::finished_at.eq(Some(finished_at)),
+            op_dsl::success.eq(Some(result.is_ok())),
+            op_dsl::error_code.eq(error_code),
+            op_dsl::details.eq(details.to_string()),
+        ))
+        .execute(conn)
+        .map_err(|e| format!("Failed to finish instance operation: {}", e))?;
+    Ok(())
+}
+
+/// Record files the launch integrity check moved to quarantine as a finished
+/// operation.
+pub(crate) fn record_quarantine(
+    conn: &mut SqliteConnection,
+    instance_id: i32,
+    report: &SpotCheckReport,
+) -> Result<(), String> {
+    let details = serde_json::json!({
+        "checked": report.checked,
+        "quarantined": report.quarantined,
+    });
+    let operation_id = start_operation(conn, instance_id, OperationKind::Quarantine, &details)?;
+    finish_operation(conn, operation_id, &Ok(()), &serde_json::Map::new())
+}
+
+/// Record the settings copied from another instance at creation time as a
+/// finished operation.
+pub(crate) fn record_settings_copy(
+    conn: &mut SqliteConnection,
+    instance_id: i32,
+    source_instance_id: i32,
+    copied: &[String],
+    filtered: &[String],
+) -> Result<(), String> {
+    let details = serde_json::json!({
+        "source_instance_id": source_instance_id,
+        "copied": copied,
+        "filtered": filtered,
+    });
+    let operation_id = start_operation(conn, instance_id, OperationKind::CopySettings, &details)?;
+    finish_operation(conn, operation_id, &Ok(()), &serde_json::Map::new())
+}
+
+/// Most recent operations for an instance, newest first.
+pub(crate) fn recent_operations(
+    conn: &mut SqliteConnection,
+    instance_id: i32,
+    limit: i64,
+) -> Result<Vec<InstanceOperation>, String> {
+    op_dsl::instance_operation
+        .filter(op_dsl::instance_id.eq(instance_id))
+        .order((op_dsl::started_at.desc(), op_dsl::id.desc()))
+        .limit(limit)
+        .load::<InstanceOperation>(conn)
+        .map_err(|e| format!("Failed to load instance history: {}", e))
+}
+
+/// Delete all but the newest `keep` operations of every instance. Returns the
+/// number of rows removed.
+pub(crate) fn prune_history(conn: &mut SqliteConnection, keep: i64) -> Result<usize, String> {
+    diesel::sql_query(
+        "DELETE FROM instance_operation WHERE id IN (
+            SELECT id FROM (
+                SELECT id, ROW_NUMBER() OVER (
+                    PARTITION BY instance_id ORDER BY started_at DESC, id DESC
+                ) AS position
+                FROM instance_operation
+            ) WHERE position > ?
+        )",
+    )
+    .bind::<diesel::sql_types::BigInt, _>(keep)
+    .execute(conn)
+    .map_err(|e| format!("Failed to prune instance history: {}", e))
+}
+
+/// Run `operation` and record it in the instance's history. Failing to write
+/// history is logged and never fails the task itself.
+pub(crate) async fn track<F>(
+    instance_id: i32,
+    kind: OperationKind,
+    details: serde_json::Value,
+    operation: F,
+) -> Result<(), String>
+where
+    F: Future<Output = Result<(), String>>,
+{
+    track_with_notes(
+        instance_id,
+        kind,
+        details,
+        OperationNotes::default(),
+        operation,
+    )
+    .await
+}
+
+/// [`track`], additionally storing whatever `operation` wrote to `notes`.
+pub(crate) async fn track_with_notes<F>(
+    instance_id: i32,
+    kind: OperationKind,
+    details: serde_json::Value,
+    notes: OperationNotes,
+    operation: F,
+) -> Result<(), String>
+where
+    F: Future<Output = Result<(), String>>,
+{
+    let operation_id = match get_vesta_conn()
+        .map_err(|e| e.to_string())
+        .and_then(|mut conn| start_operation(&mut conn, instance_id, kind, &details))
+    {
+        Ok(id) => Some(id),
+        Err(e) => {
+            log::warn!("{} for instance {}", e, instance_id);
+            None
+        }
+    };
+
+    if kind == OperationKind::Install {
+        event_log::record(EventKind::InstallStarted, Some(instance_id), None);
+    }
+
+    let result = operation.await;
+
+    if kind == OperationKind::Install {
+        match &result {
+            Ok(()) => event_log::record(EventKind::InstallSucceeded, Some(instance_id), None),
+            Err(e) => event_log::record(
+                EventKind::InstallFailed,
+                Some(instance_id),
+                Some(error_code_for(e)),
+            ),
+        }
+    }
+
+    if let Some(operation_id) = operation_id {
+        let notes = notes.lock().map(|notes| notes.clone()).unwrap_or_default();
+        if let Err(e) = get_vesta_conn()
+            .map_err(|e| e.to_string())
+            .and_then(|mut conn| finish_operation(&mut conn, operation_id, &result, &notes))
+        {
+            log::warn!("{} for instance {}", e, instance_id);
+        }
+    }
+
+    result
+}
+
+/// Spread of one metric across install runs.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetricAggregate {
+    pub count: usize,
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallMetricsRun {
+    pub operation_id: i32,
+    pub kind: String,
+    pub started_at: String,
+    pub success: Option<bool>,
+    pub metrics: InstallMetrics,
+}
+
+/// Installer metrics of an instance: the newest run, whatever its outcome,
+/// and per-key aggregates over successful runs only, since failed runs stop
+/// partway and would drag the numbers down.
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallMetricsReport {
+    pub latest: Option<InstallMetricsRun>,
+    pub successful_runs: usize,
+    pub aggregates: BTreeMap<String, MetricAggregate>,
+}
+
+pub(crate) fn install_metrics_report(
+    conn: &mut SqliteConnection,
+    instance_id: i32,
+) -> Result<InstallMetricsReport, String> {
+    let runs: Vec<InstallMetricsRun> =
+        recent_operations(conn, instance_id, MAX_OPERATIONS_PER_INSTANCE)?
+            .into_iter()
+            .filter_map(|op| {
+                let mut details: serde_json::Value = serde_json::from_str(&op.details).ok()?;
+                let metrics =
+                    serde_json::from_value(details.get_mut(INSTALL_METRICS_KEY)?.take()).ok()?;
+                Some(InstallMetricsRun {
+                    operation_id: op.id,
+                    kind: op.kind,
+                    started_at: op.started_at,
+                    success: op.success,
+                    metrics,
+                })
+            })
+            .collect();
+
+    let mut values: BTreeMap<String, Vec<u64>> = BTreeMap::new();
+    let successful: Vec<&InstallMetricsRun> = runs
+        .iter()
+        .filter(|run| run.success == Some(true))
+        .collect();
+    for run in &successful {
+        for (key, value) in run.metrics.values() {
+            values.entry(key.clone()).or_default().push(*value);
+        }
+    }
+    let aggregates = values
+        .into_iter()
+        .map(|(key, samples)| {
+            let aggregate = MetricAggregate {
+                count: samples.len(),
+                min: samples.iter().copied().min().unwrap_or(0),
+                max: samples.iter().copied().max().unwrap_or(0),
+                mean: samples.iter().sum::<u64>() as f64 / samples.len() as f64,
+            };
+            (key, aggregate)
+        })
+        .collect();
+
+    Ok(InstallMetricsReport {
+        successful_runs: successful.len(),
+        latest: runs.into_iter().next(),
+        aggregates,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::connection::SimpleConnection;
+    use diesel_migrations::MigrationHarness;
+
+    fn conn_with_instance() -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.run_pending_migrations(crate::utils::db::VESTA_MIGRATIONS)
+            .unwrap();
+        conn.batch_execute(
+            "INSERT INTO instance (id, name, minecraft_version) VALUES (1, 'Test', '1.21.1');",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn finished_operation_records_outcome_and_error() {
+        let mut conn = conn_with_instance();
+        let details = serde_json::json!({ "modloader": "fabric", "modloader_version": "0.15.11" });
+
+        let ok = start_operation(&mut conn, 1, OperationKind::Install, &details).unwrap();
+        finish_operation(&mut conn, ok, &Ok(()), &Default::default()).unwrap();
+        let failed = start_operation(&mut conn, 1, OperationKind::Repair, &details).unwrap();
+        finish_operation(
+            &mut conn,
+            failed,
+            &Err("Installation cancelled".to_string()),
+            &Default::default(),
+        )
+        .unwrap();
+
+        let history = recent_operations(&mut conn, 1, 10).unwrap();
+        assert_eq!(history.len(), 2);
+        let repair = history.iter().find(|op| op.id == failed).unwrap();
+        assert_eq!(repair.kind, "repair");
+        assert_eq!(repair.success, Some(false));
+        assert_eq!(repair.error_code.as_deref(), Some("cancelled"));
+        let repair_details: serde_json::Value = serde_json::from_str(&repair.details).unwrap();
+        assert_eq!(repair_details["error"], "Installation cancelled");
+        assert_eq!(repair_details["modloader_version"], "0.15.11");
+
+        let install = history.iter().find(|op| op.id == ok).unwrap();
+        assert_eq!(install.success, Some(true));
+        assert!(install.finished_at.is_some());
+        assert_eq!(install.error_code, None);
+    }
+
+    #[test]
+    fn prune_keeps_newest_rows_per_instance() {
+        let mut conn = conn_with_instance();
+        for _ in 0..5 {
+            start_operation(&mut conn, 1, OperationKind::Update, &serde_json::json!({})).unwrap();
+        }
+
+        assert_eq!(prune_history(&mut conn, 3).unwrap(), 2);
+        let remaining = recent_operations(&mut conn, 1, 10).unwrap();
+        let ids: Vec<i32> = remaining.iter().map(|op| op.id).collect();
+        assert_eq!(ids, vec![5, 4, 3]);
+    }
+
+    #[test]
+    fn quarantine_is_recorded_as_finished_operation() {
+        let mut conn = conn_with_instance();
+        let report = SpotCheckReport {
+            checked: 9,
+            quarantined: vec![piston_lib::game::launch_integrity::QuarantinedArtifact {
+                artifact_class: "library".to_string(),
+                original_path: "/data/libraries/guava.jar".into(),
+                quarantine_path: Some("/data/quarantine/20261017T120000/guava.jar".into()),
+                expected_sha1: "a".repeat(40),
+                actual_sha1: "b".repeat(40),
+            }],
+        };
+
+        record_quarantine(&mut conn, 1, &report).unwrap();
+
+        let history = recent_operations(&mut conn, 1, 10).unwrap();
+        assert_eq!(history[0].kind, "quarantine");
+        assert_eq!(history[0].success, Some(true));
+        let details: serde_json::Value = serde_json::from_str(&history[0].details).unwrap();
+        assert_eq!(details["checked"], 9);
+        assert_eq!(
+            details["quarantined"][0]["original_path"],
+            "/data/libraries/guava.jar"
+        );
+    }
+
+    fn finish_install(conn: &mut SqliteConnection, success: bool, metrics: &[(&str, u64)]) {
+        let op = start_operation(conn, 1, OperationKind::Install, &serde_json::json!({})).unwrap();
+        let mut install_metrics = InstallMetrics::default();
+        for (key, value) in metrics {
+            install_metrics.record(key, *value);
+        }
+        let mut notes = serde_json::Map::new();
+        notes.insert(
+            INSTALL_METRICS_KEY.to_string(),
+            serde_json::to_value(&install_metrics).unwrap(),
+        );
+        let result = if success {
+            Ok(())
+        } else {
+            Err("Download failed".to_string())
+        };
+        finish_operation(conn, op, &result, &notes).unwrap();
+    }
+
+    #[test]
+    fn install_metrics_report_has_latest_run_and_successful_aggregates() {
+        let mut conn = conn_with_instance();
+        finish_install(&mut conn, true, &[("assets_ms", 100), ("total_ms", 1000)]);
+        finish_install(&mut conn, true, &[("assets_ms", 300), ("total_ms", 2000)]);
+        // Operations without metrics (exports, older installs) are ignored.
+        start_operation(&mut conn, 1, OperationKind::Export, &serde_json::json!({})).unwrap();
+        finish_install(&mut conn, false, &[("assets_ms", 5), ("total_ms", 10)]);
+
+        let report = install_metrics_report(&mut conn, 1).unwrap();
+
+        let latest = report.latest.unwrap();
+        assert_eq!(latest.success, Some(false));
+        assert_eq!(latest.metrics.get("total_ms"), Some(10));
+        assert_eq!(report.successful_runs, 2);
+        assert_eq!(
+            report.aggregates["assets_ms"],
+            MetricAggregate {
+                count: 2,
+                min: 100,
+                max: 300,
+                mean: 200.0,
+            }
+        );
+        assert_eq!(report.aggregates["total_ms"].mean, 1500.0);
+    }
+
+    #[test]
+    fn install_metrics_report_is_empty_without_runs() {
+        let mut conn = conn_with_instance();
+        let report = install_metrics_report(&mut conn, 1).unwrap();
+        assert!(report.latest.is_none());
+        assert_eq!(report.successful_runs, 0);
+        assert!(report.aggregates.is_empty());
+    }
+
+    #[test]
+    fn error_codes_distinguish_cancellation() {
+        assert_eq!(error_code_for("Export cancelled"), "cancelled");
+        assert_eq!(error_code_for("Processor failed: x"), "failed");
+    }
+}
@@ -56,14 +56,8 @@ pub(crate) async fn prepare_instance_launch(
     app_handle: &tauri::AppHandle,
     instance_data: &Instance,
 ) -> Result<PreparedInstanceLaunch, String> {
-    let instance_id = instance_data.slug();
     let app_config = crate::utils::config::get_app_config().map_err(|e| e.to_string())?;
-    let launcher_action = resolve_launcher_action(instance_data, &app_config);
-    let tray_visible = app_config.show_tray_icon;
-
-    let data_dir = crate::utils::db_manager::get_app_config_dir()
-        .map_err(|e| format!("Failed to get app config dir: {}", e))?;
-    let java_path_str = crate::utils::java::ensure_java_for_instance(
+    let java_path = crate::utils::java::ensure_java_for_instance(
         app_handle,
         instance_data,
         None,
@@ -74,6 +68,63 @@ pub(crate) async fn prepare_instance_launch(
     )
     .await?;
 
+    let network_manager = app_handle.state::<crate::utils::network::NetworkManager>();
+    let offline = network_manager.get_status() == crate::utils::network::NetworkStatus::Offline;
+    let account = resolve_launch_account(app_handle, offline).await?;
+
+    build_instance_launch(
+        instance_data,
+        &app_config,
+        LaunchContext {
+            java_path,
+            account,
+            offline,
+            exit_handler_jar: bundled_exit_handler_jar(app_handle.path().resource_dir().ok()),
+        },
+    )
+}
+
+/// Launch inputs that come from app state rather than from the instance.
+pub(crate) struct LaunchContext {
+    pub java_path: String,
+    pub account: Option<crate::models::account::Account>,
+    pub offline: bool,
+    pub exit_handler_jar: Option<PathBuf>,
+}
+
+/// `exit-handler.jar` from `resource_dir`, falling back to the source tree
+/// in development builds.
+pub(crate) fn bundled_exit_handler_jar(resource_dir: Option<PathBuf>) -> Option<PathBuf> {
+    resource_dir
+        .map(|dir| dir.join("exit-handler.jar"))
+        .filter(|p| p.exists())
+        .or_else(|| {
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .parent()
+                .map(|p| {
+                    p.join("resources")
+                        .join("exit-handler")
+                        .join("exit-handler.jar")
+                })
+                .filter(|p| p.exists())
+        })
+}
+
+/// Install and launch specs for `instance_data`. Needs no app handle, so the
+/// headless CLI builds launches the same way the GUI does.
+pub(crate) fn build_instance_launch(
+    instance_data: &Instance,
+    app_config: &crate::utils::config::AppConfig,
+    context: LaunchContext,
+) -> Result<PreparedInstanceLaunch, String> {
+    let instance_id = instance_data.slug();
+    let launcher_action = resolve_launcher_action(instance_data, app_config);
+    let tray_visible = app_config.show_tray_icon;
+
+    let data_dir = crate::utils::db_manager::get_app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {}", e))?;
+    let java_path_str = context.java_path;
+
     let spec_data_dir = if data_dir.join("data").exists() {
         data_dir.join("data")
     } else {
@@ -102,10 +153,15 @@ pub(crate) async fn prepare_instance_launch(
     } else {
         instance_data.game_height
     };
+    let config_override =
+        crate::instance::config_override::InstanceConfigOverride::for_instance(instance_data)
+            .unwrap_or_default();
+    let (min_memory, max_memory) =
+        config_override.memory_range(instance_data.min_memory, instance_data.max_memory);
     let system_ram_mb = piston_lib::utils::hardware::get_total_memory_mb() as i32;
     let resolved_memory = crate::utils::memory_policy::clamp_manual_memory_range(
-        instance_data.min_memory,
-        instance_data.max_memory,
+        min_memory,
+        max_memory,
         system_ram_mb,
     );
     let java_args_raw = if instance_data.use_global_java_args {
@@ -113,13 +169,14 @@ pub(crate) async fn prepare_instance_launch(
     } else {
         instance_data.java_args.clone()
     };
-    let mut resolved_jvm_args = parse_user_jvm_args(java_args_raw)?;
-    resolved_jvm_args.extend(game_proxy_jvm_args(&app_config));
+    let mut resolved_jvm_args = parse_user_jvm_args(config_override.java_args_prefix.clone())?;
+    resolved_jvm_args.extend(parse_user_jvm_args(java_args_raw)?);
+    resolved_jvm_args.extend(game_proxy_jvm_args(app_config));
 
     #[cfg(any(target_os = "linux", target_os = "windows"))]
-    let mut env_vars = crate::utils::hooks::resolve_env_vars(&app_config, instance_data);
+    let mut env_vars = crate::utils::hooks::resolve_env_vars(app_config, instance_data);
     #[cfg(not(any(target_os = "linux", target_os = "windows")))]
-    let env_vars = crate::utils::hooks::resolve_env_vars(&app_config, instance_data);
+    let env_vars = crate::utils::hooks::resolve_env_vars(app_config, instance_data);
 
     let res_pre_launch_hook = if instance_data.use_global_hooks {
         app_config.default_pre_launch_hook.clone()
@@ -150,90 +207,23 @@ pub(crate) async fn prepare_instance_launch(
         game_dir: game_dir.clone(),
         java_path: Some(PathBuf::from(&java_path_str)),
         dry_run: false,
-        concurrency: 8,
+        concurrency: config_override
+            .download_concurrency(crate::utils::network::download_concurrency(app_config)),
         artifact_cache_max_bytes: crate::utils::storage::normalize_artifact_cache_limit_bytes(
             app_config.artifact_cache_max_bytes,
         ) as u64,
+        artifact_cache_compression: app_config.cache_compression_enabled,
         force_overwrite_configs: false,
         repair_scope: RepairScope::Full,
         remediation_policy: RemediationPolicy::RepairIfNeeded,
         finalize_reporter: true,
     };
 
-    let mut active_account = match crate::auth::get_active_account() {
-        Ok(Some(acc)) => Some(acc),
-        Ok(None) => None,
-        Err(e) => {
-            log::warn!("[launch_instance] Failed to read active account: {}", e);
-            None
-        }
-    };
-
-    let network_manager = app_handle.state::<crate::utils::network::NetworkManager>();
-    let is_offline = network_manager.get_status() == crate::utils::network::NetworkStatus::Offline;
-
-    if let Some(acc) = active_account.clone() {
-        if acc.account_type == ACCOUNT_TYPE_GUEST || acc.account_type == ACCOUNT_TYPE_DEMO {
-            log::warn!(
-                "[launch_instance] Blocked launch attempt from {} account",
-                acc.account_type
-            );
-            notify_login_required(app_handle, &acc.account_type);
-            return Err(
-                "You must be signed in with a Microsoft account to launch Minecraft.".to_string(),
-            );
-        } else if !is_offline {
-            if let Err(e) =
-                crate::auth::ensure_account_tokens_valid(app_handle.clone(), acc.uuid.clone()).await
-            {
-                log::error!("[launch_instance] Failed to refresh token: {}", e);
-                return Err(format!("Failed to refresh authentication: {}", e));
-            }
-
-            active_account = match crate::auth::get_active_account() {
-                Ok(Some(acc)) => Some(acc),
-                Ok(None) => None,
-                Err(_) => None,
-            };
-        } else {
-            log::info!("[launch_instance] Offline mode: skipping token refresh");
-        }
-    }
-
-    let exit_handler_jar = app_handle
-        .path()
-        .resource_dir()
-        .ok()
-        .map(|dir| dir.join("exit-handler.jar"))
-        .filter(|p| p.exists())
-        .or_else(|| {
-            Path::new(env!("CARGO_MANIFEST_DIR"))
-                .parent()
-                .map(|p| {
-                    p.join("resources")
-                        .join("exit-handler")
-                        .join("exit-handler.jar")
-                })
-                .filter(|p| p.exists())
-        });
-
     let log_file = spec_data_dir
         .join("logs")
         .join(format!("{}.log", instance_id));
 
-    let username = active_account
-        .as_ref()
-        .map(|a| a.username.clone())
-        .unwrap_or_else(|| "Player".to_string());
-
-    let uuid = if is_offline {
-        piston_lib::auth::generate_offline_uuid(&username)
-    } else {
-        active_account
-            .as_ref()
-            .map(|a| a.uuid.clone())
-            .unwrap_or_else(|| "00000000-0000-0000-0000-000000000000".to_string())
-    };
+    let identity = LaunchIdentity::new(context.account.as_ref(), context.offline);
 
     if app_config.use_dedicated_gpu {
         #[cfg(target_os = "linux")]
@@ -265,29 +255,27 @@ pub(crate) async fn prepare_instance_launch(
         java_path: PathBuf::from(&java_path_str),
         min_memory: Some(resolved_memory.min as u32),
         max_memory: Some(resolved_memory.max as u32),
-        username,
-        uuid,
-        access_token: if is_offline {
-            "offline".to_string()
-        } else {
-            active_account
-                .as_ref()
-                .and_then(|a| a.access_token.clone())
-                .unwrap_or_else(|| "offline".to_string())
-        },
-        xuid: None,
+        username: identity.username,
+        uuid: identity.uuid,
+        access_token: identity.access_token,
+        xuid: identity.xuid,
         client_id: piston_lib::auth::CLIENT_ID.to_string(),
         user_type: "msa".to_string(),
         jvm_args: resolved_jvm_args,
         game_args: vec![],
         window_width: Some(res_width as u32),
         window_height: Some(res_height as u32),
-        exit_handler_jar,
+        exit_handler_jar: context.exit_handler_jar,
         log_file: Some(log_file),
         env_vars,
         wrapper_command: res_wrapper_command,
         pre_launch_hook: res_pre_launch_hook,
         post_exit_hook: res_post_exit_hook,
+        safe_mode: false,
+        log_pipe: true,
+        connect_server: None,
+        process_priority: config_override.process_priority,
+        cpu_affinity_mask: config_override.cpu_affinity_mask,
     };
 
     Ok(PreparedInstanceLaunch {
@@ -297,7 +285,7 @@ pub(crate) async fn prepare_instance_launch(
         launch_spec,
         launcher_action,
         tray_visible,
-        offline: is_offline,
+        offline: context.offline,
     })
 }
 
@@ -306,8 +294,24 @@ pub(crate) async fn ensure_runtime_ready_for_launch(
     inst: &Instance,
     install_spec: InstallSpec,
 ) -> Result<piston_lib::game::runtime_preparation::RuntimePreparationReport, String> {
-    let initial_inspection = piston_lib::game::runtime_preparation::inspect_runtime(&install_spec)
-        .map_err(|e| format!("Launch preflight verification failed: {}", e))?;
+    let mut initial_inspection =
+        piston_lib::game::runtime_preparation::inspect_runtime(&install_spec)
+            .map_err(|e| format!("Launch preflight verification failed: {}", e))?;
+    if spot_check_enabled(inst) {
+        if let Some(plan) = initial_inspection.plan.as_ref() {
+            let report = piston_lib::game::launch_integrity::spot_check(
+                plan,
+                piston_lib::game::launch_integrity::LIBRARY_SAMPLE_SIZE,
+            );
+            if !report.quarantined.is_empty() {
+                record_quarantine(inst, &report);
+                // Quarantined files now show up as missing and get repaired below.
+                initial_inspection =
+                    piston_lib::game::runtime_preparation::inspect_runtime(&install_spec)
+                        .map_err(|e| format!("Launch preflight verification failed: {}", e))?;
+            }
+        }
+    }
     let initial_report = initial_inspection.verification;
     log_verification_report(&initial_report);
 
@@ -371,6 +375,32 @@ pub(crate) async fn ensure_runtime_ready_for_launch(
     Ok(report)
 }
 
+/// The instance's `verify_on_launch` override, or the global setting.
+fn spot_check_enabled(inst: &Instance) -> bool {
+    inst.verify_on_launch.unwrap_or_else(|| {
+        crate::utils::config::get_app_config()
+            .map(|config| config.verify_on_launch)
+            .unwrap_or(false)
+    })
+}
+
+fn record_quarantine(
+    inst: &Instance,
+    report: &piston_lib::game::launch_integrity::SpotCheckReport,
+) {
+    if inst.id <= 0 {
+        return;
+    }
+    if let Err(e) = crate::utils::db::get_vesta_conn()
+        .map_err(|e| e.to_string())
+        .and_then(|mut conn| {
+            crate::instance::history::record_quarantine(&mut conn, inst.id, report)
+        })
+    {
+        log::warn!("{} for instance {}", e, inst.id);
+    }
+}
+
 pub(crate) fn apply_launcher_action_after_launch(
     app_handle: &tauri::AppHandle,
     resolved_action: LauncherActionOnLaunch,
@@ -533,6 +563,108 @@ fn verify_modpack_resource_presence(inst: &Instance, game_dir: &Path) -> Result<
     Ok(())
 }
 
+/// Read the active account, refusing Guest/Demo sessions and refreshing
+/// tokens unless offline.
+async fn resolve_launch_account(
+    app_handle: &tauri::AppHandle,
+    is_offline: bool,
+) -> Result<Option<crate::models::account::Account>, String> {
+    let active_account = match crate::auth::get_active_account() {
+        Ok(Some(acc)) => acc,
+        Ok(None) => return Ok(None),
+        Err(e) => {
+            log::warn!("[launch_instance] Failed to read active account: {}", e);
+            return Ok(None);
+        }
+    };
+
+    if active_account.account_type == ACCOUNT_TYPE_GUEST
+        || active_account.account_type == ACCOUNT_TYPE_DEMO
+    {
+        log::warn!(
+            "[launch_instance] Blocked launch attempt from {} account",
+            active_account.account_type
+        );
+        notify_login_required(app_handle, &active_account.account_type);
+        return Err(
+            "You must be signed in with a Microsoft account to launch Minecraft.".to_string(),
+        );
+    }
+
+    if is_offline {
+        log::info!("[launch_instance] Offline mode: skipping token refresh");
+        return Ok(Some(active_account));
+    }
+
+    if let Err(e) =
+        crate::auth::ensure_account_tokens_valid(app_handle.clone(), active_account.uuid.clone())
+            .await
+    {
+        log::error!("[launch_instance] Failed to refresh token: {}", e);
+        return Err(format!("Failed to refresh authentication: {}", e));
+    }
+
+    Ok(crate::auth::get_active_account().ok().flatten())
+}
+
+/// Player identity handed to the game for one account.
+struct LaunchIdentity {
+    username: String,
+    uuid: String,
+    access_token: String,
+    xuid: Option<String>,
+}
+
+impl LaunchIdentity {
+    fn new(active_account: Option<&crate::models::account::Account>, is_offline: bool) -> Self {
+        let username = active_account
+            .map(|a| a.username.clone())
+            .unwrap_or_else(|| "Player".to_string());
+
+        if is_offline {
+            return Self {
+                uuid: piston_lib::auth::generate_offline_uuid(&username),
+                username,
+                access_token: "offline".to_string(),
+                xuid: None,
+            };
+        }
+
+        Self {
+            username,
+            uuid: active_account
+                .map(|a| a.uuid.clone())
+                .unwrap_or_else(|| "00000000-0000-0000-0000-000000000000".to_string()),
+            access_token: active_account
+                .and_then(|a| a.access_token.clone())
+                .unwrap_or_else(|| "offline".to_string()),
+            xuid: active_account.and_then(|a| a.xuid.clone()),
+        }
+    }
+}
+
+/// Re-read the active account just before spawning the game, so a switch
+/// made while the runtime was being prepared launches as the new account.
+pub(crate) async fn refresh_launch_account(
+    app_handle: &tauri::AppHandle,
+    launch_spec: &mut piston_lib::game::launcher::LaunchSpec,
+    is_offline: bool,
+) -> Result<(), String> {
+    let active_account = resolve_launch_account(app_handle, is_offline).await?;
+    let identity = LaunchIdentity::new(active_account.as_ref(), is_offline);
+    if identity.uuid != launch_spec.uuid {
+        log::info!(
+            "[launch_instance] Active account changed during preparation; launching as {}",
+            identity.username
+        );
+    }
+    launch_spec.username = identity.username;
+    launch_spec.uuid = identity.uuid;
+    launch_spec.access_token = identity.access_token;
+    launch_spec.xuid = identity.xuid;
+    Ok(())
+}
+
 fn notify_login_required(app_handle: &tauri::AppHandle, account_type: &str) {
     if let Some(nm) = app_handle.try_state::<crate::notifications::manager::NotificationManager>() {
         let _ = nm.create(crate::notifications::models::CreateNotificationInput {
@@ -578,6 +710,74 @@ pub(crate) fn notify_offline_launch(app_handle: &tauri::AppHandle, instance_name
     }
 }
 
+/// Warn about duplicate or incompatible mods before launch. With
+/// `strict_mod_conflicts` enabled the launch is refused instead.
+pub(crate) fn check_mod_conflicts_before_launch(
+    app_handle: &tauri::AppHandle,
+    inst: &Instance,
+) -> Result<(), String> {
+    let app_config = crate::utils::config::get_app_config().map_err(|e| e.to_string())?;
+    let app_config_dir = crate::utils::db_manager::get_app_config_dir()
+        .map_err(|e| format!("Failed to get app config dir: {}", e))?;
+    let instances_root = crate::utils::instance_helpers::resolve_instances_root(
+        &app_config_dir,
+        app_config.default_game_dir.as_deref(),
+    );
+    let game_dir = crate::utils::instance_helpers::resolve_instance_game_directory(
+        inst,
+        &instances_root,
+        &app_config_dir,
+    );
+
+    let conflicts = crate::instance::mod_conflicts::detect_mod_conflicts_in(&game_dir);
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+    for conflict in &conflicts {
+        log::warn!("[launch_instance] mod-conflict {}", conflict.message);
+    }
+
+    let summary = conflicts
+        .iter()
+        .take(3)
+        .map(|c| c.message.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if app_config.strict_mod_conflicts {
+        return Err(format!(
+            "Launch blocked: {} mod conflict(s) found.\n{}",
+            conflicts.len(),
+            summary
+        ));
+    }
+
+    if let Some(nm) = app_handle.try_state::<crate::notifications::manager::NotificationManager>() {
+        let _ = nm.create(crate::notifications::models::CreateNotificationInput {
+            client_key: None,
+            title: Some(format!("Mod conflicts in {}", inst.name)),
+            description: Some(summary),
+            severity: Some("warning".to_string()),
+            notification_type: Some(crate::notifications::models::NotificationType::Immediate),
+            dismissible: Some(true),
+            persist: Some(false),
+            silent: Some(false),
+            actions: None,
+            progress: None,
+            current_step: None,
+            total_steps: None,
+            metadata: Some(
+                serde_json::json!({
+                    "instance_id": inst.id,
+                    "mod_conflicts": conflicts,
+                })
+                .to_string(),
+            ),
+            show_on_completion: None,
+        });
+    }
+    Ok(())
+}
+
 fn log_verification_report(report: &piston_lib::game::installer::types::VerificationResult) {
     log::info!(
         "[launch_instance] verify-summary ready={} checked={} missing={} mismatch={}",
@@ -4,7 +4,7 @@
 //! app policy around a running process: persisted run state, startup reattach,
 //! exit reconciliation, crash/playtime updates, and UI events.
 
-use crate::discord::DiscordManager;
+use crate::instance::playtime;
 use crate::models::instance::Instance;
 use crate::schema::instance::dsl as instance_dsl;
 use crate::utils::db::get_vesta_conn;
@@ -32,10 +32,16 @@ pub(crate) async fn record_started_launch(
     app_handle: &tauri::AppHandle,
     inst: &Instance,
     mut launch_result: piston_lib::game::launcher::LaunchResult,
+    safe_mode: bool,
 ) -> Result<InstanceRunState, String> {
     let instance_id = inst.slug();
 
     clear_crash_flag(&instance_id, Some(app_handle))?;
+    crate::utils::event_log::record(
+        crate::utils::event_log::EventKind::Launch,
+        Some(inst.id),
+        None,
+    );
 
     let run_state = InstanceRunState {
         instance_id: instance_id.clone(),
@@ -49,6 +55,7 @@ pub(crate) async fn record_started_launch(
             .as_ref()
             .map(|m| m.to_string()),
         started_at: launch_result.instance.started_at.to_rfc3339(),
+        safe_mode,
     };
 
     if let Err(e) = crate::utils::process_state::add_running_process(run_state.clone()) {
@@ -93,38 +100,55 @@ pub(crate) async fn record_started_launch(
         }),
     );
 
-    if let Some(dm) = app_handle.try_state::<DiscordManager>() {
-        dm.add_running_instance(&inst.name).await;
-    }
-
     Ok(run_state)
 }
 
-pub(crate) fn spawn_exit_monitor(
-    app_handle: tauri::AppHandle,
-    instance_name: String,
-    run_state: InstanceRunState,
-) {
+pub(crate) fn spawn_exit_monitor(app_handle: tauri::AppHandle, run_state: InstanceRunState) {
     tokio::spawn(async move {
+        use piston_lib::game::launcher::{
+            FocusTracker, ProcessSampler, FOCUS_POLL_INTERVAL, PROCESS_METRICS_INTERVAL,
+        };
         use sysinfo::System;
 
         let mut sys = System::new_all();
+        let mut sampler = ProcessSampler::new(run_state.pid);
+        let mut next_sample = tokio::time::Instant::now();
+        let session = open_play_session(&run_state);
+        let mut next_checkpoint = tokio::time::Instant::now() + playtime::CHECKPOINT_INTERVAL;
+        let mut focus = FocusTracker::new(run_state.pid);
+        let mut next_focus_poll = tokio::time::Instant::now();
+        crate::instance::metrics::reset(&run_state.instance_id);
         loop {
             tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
             sys.refresh_all();
             if sys.process(sysinfo::Pid::from_u32(run_state.pid)).is_some() {
+                if tokio::time::Instant::now() >= next_sample {
+                    next_sample += PROCESS_METRICS_INTERVAL;
+                    sample_process_metrics(&app_handle, &run_state.instance_id, &mut sampler);
+                }
+                if let Some((instance_id, session_id)) = session {
+                    if tokio::time::Instant::now() >= next_focus_poll {
+                        next_focus_poll += FOCUS_POLL_INTERVAL;
+                        focus.poll();
+                    }
+                    if tokio::time::Instant::now() >= next_checkpoint {
+                        next_checkpoint += playtime::CHECKPOINT_INTERVAL;
+                        flush_active_seconds(session_id, &mut focus);
+                        checkpoint_play_session(&app_handle, instance_id, session_id);
+                    }
+                }
                 continue;
             }
 
+            if let Some((_, session_id)) = session {
+                flush_active_seconds(session_id, &mut focus);
+            }
+
             log::info!(
                 "[instance::lifecycle] Process exited for {}",
                 run_state.instance_id
             );
 
-            if let Some(dm) = app_handle.try_state::<DiscordManager>() {
-                dm.remove_running_instance(&instance_name).await;
-            }
-
             if let Err(e) = reconcile_finished_process(&app_handle, run_state.clone()).await {
                 log::error!(
                     "[instance::lifecycle] Failed to reconcile exited instance {}: {}",
@@ -137,6 +161,103 @@ pub(crate) fn spawn_exit_monitor(
     });
 }
 
+/// Record one resource usage sample and push it to the UI.
+fn sample_process_metrics(
+    app_handle: &tauri::AppHandle,
+    instance_id: &str,
+    sampler: &mut piston_lib::game::launcher::ProcessSampler,
+) {
+    let Some(sample) = sampler.sample() else {
+        return;
+    };
+    crate::instance::metrics::record(instance_id, sample.clone());
+    let _ = app_handle.emit(
+        "core://instance-metrics",
+        serde_json::json!({
+            "instance_id": instance_id,
+            "metrics": sample,
+        }),
+    );
+}
+
+/// Open (or pick up) the play session of a monitored run. Returns the
+/// instance and session ids, or `None` when it can't be tracked.
+fn open_play_session(run_state: &InstanceRunState) -> Option<(i32, i32)> {
+    let inst = find_instance_by_slug(&run_state.instance_id)?;
+    let result = get_vesta_conn()
+        .map_err(|e| e.to_string())
+        .and_then(|mut conn| {
+            playtime::open_session(
+                &mut conn,
+                inst.id,
+                &run_state.started_at,
+                run_state.safe_mode,
+            )
+        });
+    match result {
+        Ok(session_id) => Some((inst.id, session_id)),
+        Err(e) => {
+            log::warn!("{} for {}", e, run_state.instance_id);
+            None
+        }
+    }
+}
+
+/// Store the focused time gathered since the last flush on the session.
+fn flush_active_seconds(session_id: i32, focus: &mut piston_lib::game::launcher::FocusTracker) {
+    let Some(secs) = focus.take_active_secs() else {
+        return;
+    };
+    let result = get_vesta_conn()
+        .map_err(|e| e.to_string())
+        .and_then(|mut conn| playtime::add_active_seconds(&mut conn, session_id, secs as i32));
+    if let Err(e) = result {
+        log::warn!("{} (session {})", e, session_id);
+    }
+}
+
+fn checkpoint_play_session(app_handle: &tauri::AppHandle, instance_id: i32, session_id: i32) {
+    let mut conn = match get_vesta_conn() {
+        Ok(conn) => conn,
+        Err(e) => {
+            log::warn!(
+                "Failed to get database connection for playtime checkpoint: {}",
+                e
+            );
+            return;
+        }
+    };
+    let now = chrono::Utc::now().to_rfc3339();
+    match playtime::checkpoint_session(&mut conn, session_id, &now) {
+        Ok(minutes) => {
+            log::debug!(
+                "[instance::lifecycle] Checkpointed session {} of instance {} at {} minutes",
+                session_id,
+                instance_id,
+                minutes
+            );
+            emit_instance_updated(app_handle, &mut conn, instance_id);
+        }
+        Err(e) => log::warn!("{} (instance {})", e, instance_id),
+    }
+}
+
+fn emit_instance_updated(
+    app_handle: &tauri::AppHandle,
+    conn: &mut SqliteConnection,
+    instance_id: i32,
+) {
+    if let Ok(updated_inst) = instance_dsl::instance
+        .find(instance_id)
+        .first::<Instance>(conn)
+    {
+        let _ = app_handle.emit(
+            "core://instance-updated",
+            crate::commands::instances::process_instance_icon(updated_inst),
+        );
+    }
+}
+
 pub(crate) async fn reconcile_finished_process(
     app_handle: &tauri::AppHandle,
     run_state: InstanceRunState,
@@ -144,6 +265,8 @@ pub(crate) async fn reconcile_finished_process(
     let exit_status_path = run_state.game_dir.join(".vesta").join("exit_status.json");
     let stop_requested = consume_stop_requested(&run_state.game_dir, &run_state.instance_id);
     let mut crashed = false;
+    let mut exit_code = None;
+    let mut ended_at = None;
 
     if exit_status_path.exists() {
         match read_exit_status_file(exit_status_path.clone()).await {
@@ -155,25 +278,13 @@ pub(crate) async fn reconcile_finished_process(
                     exit_status.exited_at
                 );
 
+                exit_code = Some(exit_status.exit_code);
+                ended_at = Some(exit_status.exited_at.clone());
+
                 if should_check_for_crash(exit_status.exit_code, stop_requested) {
                     crashed = detect_store_and_emit_crash(app_handle, &run_state).unwrap_or(false);
                 }
 
-                if !crashed {
-                    if let Err(error) = update_instance_playtime(
-                        app_handle,
-                        &run_state.instance_id,
-                        &run_state.started_at,
-                        &exit_status.exited_at,
-                    ) {
-                        log::error!(
-                            "Failed to update playtime for {}: {}",
-                            run_state.instance_id,
-                            error
-                        );
-                    }
-                }
-
                 if let Err(e) = std::fs::remove_file(&exit_status_path) {
                     log::warn!("Failed to remove exit status file: {}", e);
                 }
@@ -191,20 +302,26 @@ pub(crate) async fn reconcile_finished_process(
             "No exit status file for {}, using log file mtime as fallback",
             run_state.instance_id
         );
-        if let Err(error) = update_instance_playtime(
-            app_handle,
-            &run_state.instance_id,
-            &run_state.started_at,
-            &exited_at,
-        ) {
-            log::error!(
-                "Failed to update playtime for {} (fallback): {}",
+        ended_at = Some(exited_at);
+    }
+
+    if run_state.safe_mode || crate::instance::safe_mode::has_pending_restore(&run_state.game_dir) {
+        match crate::instance::safe_mode::restore_mods(&run_state.game_dir) {
+            Ok(count) => log::info!(
+                "[instance::lifecycle] Safe-mode session for {} ended; re-enabled {} mod(s)",
                 run_state.instance_id,
-                error
-            );
+                count
+            ),
+            Err(e) => log::error!(
+                "[instance::lifecycle] Failed to restore mods after safe mode for {}: {}",
+                run_state.instance_id,
+                e
+            ),
         }
     }
 
+    record_play_session(app_handle, &run_state, ended_at, exit_code, crashed);
+
     let outcome = ExitOutcome {
         instance_id: run_state.instance_id.clone(),
         pid: run_state.pid,
@@ -224,54 +341,224 @@ pub(crate) async fn reconcile_finished_process(
         log::error!("Failed to remove running process: {}", e);
     }
 
+    run_post_launch_command(&run_state).await;
+
     Ok(outcome)
 }
 
-pub(crate) fn reattach_or_reconcile_persisted_processes(app_handle: tauri::AppHandle) {
-    tauri::async_runtime::spawn(async move {
-        log::info!("Checking for already-running instances...");
+/// Run the configured `game_launch_post_command`, if any, once the game has exited.
+/// Failures are logged only; the game session is already over.
+async fn run_post_launch_command(run_state: &InstanceRunState) {
+    let post_command = match crate::utils::config::get_app_config() {
+        Ok(config) => config.game_launch_post_command,
+        Err(e) => {
+            log::warn!("Failed to read config for post-launch command: {}", e);
+            return;
+        }
+    };
+    let Some(post_command) = post_command.filter(|command| !command.trim().is_empty()) else {
+        return;
+    };
 
-        match crate::utils::process_state::load_running_processes() {
-            Ok(processes) => {
-                if processes.is_empty() {
-                    log::debug!("No persisted running processes found");
-                    return;
-                }
+    let minecraft_version = find_instance_by_slug(&run_state.instance_id)
+        .map(|inst| inst.minecraft_version)
+        .unwrap_or_else(|| run_state.version_id.clone());
+    let vars = crate::utils::hooks::LaunchCommandVars {
+        instance_id: run_state.instance_id.clone(),
+        game_dir: run_state.game_dir.clone(),
+        minecraft_version,
+    };
 
-                log::info!("Found {} persisted running processes", processes.len());
-
-                for run_state in processes {
-                    if is_pid_running(run_state.pid) {
-                        if let Err(e) = reattach_running_process(&app_handle, &run_state).await {
-                            log::warn!(
-                                "Failed to reattach instance {}: {}",
-                                run_state.instance_id,
-                                e
-                            );
-                        }
-                    } else {
-                        log::warn!(
-                            "Persisted instance {} (PID {}) is no longer running, checking for exit status",
-                            run_state.instance_id,
-                            run_state.pid
-                        );
-                        if let Err(e) =
-                            reconcile_finished_process(&app_handle, run_state.clone()).await
-                        {
-                            log::error!(
-                                "Failed to reconcile persisted instance {}: {}",
-                                run_state.instance_id,
-                                e
-                            );
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                log::warn!("Failed to load persisted running processes: {}", e);
-            }
+    if let Err(e) = crate::utils::hooks::run_launch_command(
+        "post-exit",
+        &post_command,
+        &vars,
+        crate::utils::hooks::LAUNCH_COMMAND_TIMEOUT,
+    )
+    .await
+    {
+        log::warn!(
+            "[instance::lifecycle] Post-launch command failed for {}: {}",
+            run_state.instance_id,
+            e
+        );
+    }
+}
+
+/// Finalize the run's play session, crediting the playtime its checkpoints
+/// have not covered yet, and offer safe mode once the Instance has crashed
+/// quickly enough, often enough, in a row. Without an exit time the session
+/// ends at its last checkpoint.
+fn record_play_session(
+    app_handle: &tauri::AppHandle,
+    run_state: &InstanceRunState,
+    ended_at: Option<String>,
+    exit_code: Option<i32>,
+    crashed: bool,
+) {
+    use crate::instance::safe_mode;
+
+    let Some(inst) = find_instance_by_slug(&run_state.instance_id) else {
+        return;
+    };
+    let mut conn = match get_vesta_conn() {
+        Ok(conn) => conn,
+        Err(e) => {
+            log::warn!("Failed to get database connection for play session: {}", e);
+            return;
         }
+    };
+
+    let finished = playtime::open_session(
+        &mut conn,
+        inst.id,
+        &run_state.started_at,
+        run_state.safe_mode,
+    )
+    .and_then(|session_id| {
+        playtime::finalize_session(
+            &mut conn,
+            session_id,
+            ended_at.as_deref(),
+            exit_code,
+            crashed,
+        )
     });
+    if let Err(e) = finished {
+        log::warn!("{} for {}", e, run_state.instance_id);
+        return;
+    }
+    emit_instance_updated(app_handle, &mut conn, inst.id);
+
+    if !crashed || run_state.safe_mode {
+        return;
+    }
+
+    let sessions = match safe_mode::recent_sessions(
+        &mut conn,
+        inst.id,
+        safe_mode::QUICK_CRASH_THRESHOLD as i64,
+    ) {
+        Ok(sessions) => sessions,
+        Err(e) => {
+            log::warn!("{} for {}", e, run_state.instance_id);
+            return;
+        }
+    };
+
+    let streak = safe_mode::consecutive_quick_crashes(&sessions);
+    if streak >= safe_mode::QUICK_CRASH_THRESHOLD {
+        log::warn!(
+            "[instance::lifecycle] {} crashed within {}s of launch {} times in a row; offering safe mode",
+            run_state.instance_id,
+            safe_mode::QUICK_CRASH_WINDOW_SECS,
+            streak
+        );
+        let manager = app_handle.state::<crate::notifications::manager::NotificationManager>();
+        safe_mode::notify_crash_loop(&manager, inst.id, &inst.name);
+    }
+}
+
+pub(crate) fn find_instance_by_slug(instance_id_slug: &str) -> Option<Instance> {
+    let mut conn = get_vesta_conn().ok()?;
+    instance_dsl::instance
+        .filter(instance_dsl::deleted_at.is_null())
+        .load::<Instance>(&mut conn)
+        .ok()?
+        .into_iter()
+        .find(|inst| inst.slug() == instance_id_slug)
+}
+
+/// Reconcile persisted runs whose process already exited, then hand the live
+/// ones to `piston-lib` for re-registration and attach exit monitors to them.
+/// Expects the process registry to be initialized.
+pub(crate) async fn reattach_or_reconcile_persisted_processes(app_handle: tauri::AppHandle) {
+    log::info!("Checking for already-running instances...");
+
+    let processes = match crate::utils::process_state::load_running_processes() {
+        Ok(processes) => processes,
+        Err(e) => {
+            log::warn!("Failed to load persisted running processes: {}", e);
+            return;
+        }
+    };
+    if processes.is_empty() {
+        log::debug!("No persisted running processes found");
+        close_abandoned_play_sessions(&[]);
+        return;
+    }
+
+    log::info!("Found {} persisted running processes", processes.len());
+
+    for run_state in processes.iter().filter(|state| !is_pid_running(state.pid)) {
+        log::warn!(
+            "Persisted instance {} (PID {}) is no longer running, checking for exit status",
+            run_state.instance_id,
+            run_state.pid
+        );
+        if let Err(e) = reconcile_finished_process(&app_handle, run_state.clone()).await {
+            log::error!(
+                "Failed to reconcile persisted instance {}: {}",
+                run_state.instance_id,
+                e
+            );
+        }
+    }
+
+    let live: Vec<InstanceRunState> = processes
+        .iter()
+        .filter(|state| is_pid_running(state.pid))
+        .cloned()
+        .collect();
+    close_abandoned_play_sessions(&live);
+
+    let data_dir = match crate::utils::db_manager::get_app_config_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            log::warn!("Failed to resolve app data dir for reattach: {}", e);
+            return;
+        }
+    };
+    let reattached = match piston_lib::game::launcher::reattach_running_processes(&data_dir).await {
+        Ok(reattached) => reattached,
+        Err(e) => {
+            log::warn!("Failed to reattach running instances: {}", e);
+            return;
+        }
+    };
+
+    for game_instance in reattached {
+        let Some(run_state) = processes
+            .iter()
+            .find(|state| state.instance_id == game_instance.instance_id)
+        else {
+            continue;
+        };
+        reattach_running_process(&app_handle, run_state);
+    }
+}
+
+/// Close play sessions left open by a launcher that died while the game ran
+/// and that no live run will finalize.
+fn close_abandoned_play_sessions(live: &[InstanceRunState]) {
+    let live: Vec<(i32, String)> = live
+        .iter()
+        .filter_map(|state| {
+            find_instance_by_slug(&state.instance_id)
+                .map(|inst| (inst.id, state.started_at.clone()))
+        })
+        .collect();
+    let result = get_vesta_conn()
+        .map_err(|e| e.to_string())
+        .and_then(|mut conn| playtime::close_abandoned_sessions(&mut conn, &live));
+    match result {
+        Ok(0) => {}
+        Ok(closed) => log::info!(
+            "[instance::lifecycle] Closed {} play session(s) at their last checkpoint",
+            closed
+        ),
+        Err(e) => log::warn!("Failed to close abandoned play sessions: {}", e),
+    }
 }
 
 pub(crate) async fn kill_instance(
@@ -301,53 +588,28 @@ pub(crate) async fn kill_instance(
     }
 }
 
-async fn reattach_running_process(
-    app_handle: &tauri::AppHandle,
-    run_state: &InstanceRunState,
-) -> Result<(), String> {
-    log::info!(
-        "Reattaching to running instance: {} (PID {})",
-        run_state.instance_id,
-        run_state.pid
-    );
-
-    let game_instance = piston_lib::game::launcher::GameInstance {
-        instance_id: run_state.instance_id.clone(),
-        version_id: run_state.version_id.clone(),
-        modloader: run_state
-            .modloader
-            .as_ref()
-            .map(|s| s.parse())
-            .transpose()
-            .ok()
-            .flatten(),
-        pid: run_state.pid,
-        started_at: chrono::DateTime::parse_from_rfc3339(&run_state.started_at)
-            .ok()
-            .map(|dt| dt.with_timezone(&chrono::Utc))
-            .unwrap_or_else(chrono::Utc::now),
-        log_file: run_state.log_file.clone(),
-        game_dir: run_state.game_dir.clone(),
-    };
-
-    piston_lib::game::launcher::register_instance(game_instance)
-        .await
-        .map_err(|e| format!("Failed to re-register instance: {}", e))?;
+fn reattach_running_process(app_handle: &tauri::AppHandle, run_state: &InstanceRunState) {
+    let name = find_instance_by_slug(&run_state.instance_id)
+        .map(|inst| inst.name)
+        .unwrap_or_else(|| run_state.instance_id.clone());
 
     let _ = app_handle.emit(
         "core://instance-launched",
         serde_json::json!({
             "instance_id": run_state.instance_id,
+            "name": name,
             "pid": run_state.pid,
             "reattached": true
         }),
     );
 
+    spawn_exit_monitor(app_handle.clone(), run_state.clone());
+
     log::info!(
-        "Successfully reattached to instance: {}",
-        run_state.instance_id
+        "Successfully reattached to instance: {} (PID {})",
+        run_state.instance_id,
+        run_state.pid
     );
-    Ok(())
 }
 
 pub(crate) fn clear_crash_flag(
@@ -358,6 +620,7 @@ pub(crate) fn clear_crash_flag(
         get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
 
     let all_instances = instance_dsl::instance
+        .filter(instance_dsl::deleted_at.is_null())
         .load::<Instance>(&mut conn)
         .map_err(|e| format!("Failed to query instances: {}", e))?;
 
@@ -397,72 +660,6 @@ pub(crate) fn clear_crash_flag(
     ))
 }
 
-fn update_instance_playtime(
-    app_handle: &tauri::AppHandle,
-    instance_id_slug: &str,
-    started_at_str: &str,
-    exited_at_str: &str,
-) -> Result<(), String> {
-    let minutes = playtime_minutes(started_at_str, exited_at_str)?;
-
-    log::info!(
-        "Updating playtime for instance {}: {} minutes (from {} to {})",
-        instance_id_slug,
-        minutes,
-        started_at_str,
-        exited_at_str
-    );
-
-    let mut conn =
-        get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
-
-    let instances_list = instance_dsl::instance
-        .load::<Instance>(&mut conn)
-        .map_err(|e| format!("Failed to query instances: {}", e))?;
-
-    for inst in instances_list {
-        if inst.slug() == instance_id_slug {
-            let new_playtime = inst.total_playtime_minutes + minutes;
-            let now = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
-
-            diesel::update(instance_dsl::instance.filter(instance_dsl::id.eq(inst.id)))
-                .set((
-                    instance_dsl::total_playtime_minutes.eq(new_playtime),
-                    instance_dsl::last_played.eq(&now),
-                    instance_dsl::updated_at.eq(&now),
-                ))
-                .execute(&mut conn)
-                .map_err(|e| format!("Failed to update playtime: {}", e))?;
-
-            log::info!(
-                "Updated playtime for instance {} (id {}): {} -> {} minutes",
-                instance_id_slug,
-                inst.id,
-                inst.total_playtime_minutes,
-                new_playtime
-            );
-
-            if let Ok(updated_inst) = instance_dsl::instance
-                .find(inst.id)
-                .first::<Instance>(&mut conn)
-            {
-                let _ = app_handle.emit(
-                    "core://instance-updated",
-                    crate::commands::instances::process_instance_icon(updated_inst),
-                );
-            }
-
-            return Ok(());
-        }
-    }
-
-    log::warn!(
-        "Instance {} not found in database for playtime update",
-        instance_id_slug
-    );
-    Ok(())
-}
-
 pub(crate) fn store_crash_details(
     instance_id_slug: &str,
     crash_info: &crate::utils::crash_parser::CrashDetails,
@@ -471,6 +668,7 @@ pub(crate) fn store_crash_details(
         get_vesta_conn().map_err(|e| format!("Failed to get database connection: {}", e))?;
 
     let all_instances = instance_dsl::instance
+        .filter(instance_dsl::deleted_at.is_null())
         .load::<Instance>(&mut conn)
         .map_err(|e| format!("Failed to query instances: {}", e))?;
 
@@ -507,11 +705,14 @@ fn detect_store_and_emit_crash(
     run_state: &InstanceRunState,
 ) -> Result<bool, String> {
     let launch_start_time = launch_start_time(&run_state.started_at);
-    if let Some(crash_info) = crate::utils::crash_parser::detect_crash(
+    if let Some(mut crash_info) = crate::utils::crash_parser::detect_crash(
         &run_state.game_dir,
         &run_state.log_file,
         launch_start_time,
     ) {
+        if crash_info.crash_type == "jvm" {
+            archive_jvm_crash_log(&run_state.game_dir, &mut crash_info);
+        }
         log::error!(
             "Crash detected for {}: {:?}",
             run_state.instance_id,
@@ -527,6 +728,25 @@ fn detect_store_and_emit_crash(
     Ok(false)
 }
 
+/// Move the hs_err log of a JVM crash into `crash-reports/jvm/` so repeated
+/// crashes don't pile up in the game directory.
+fn archive_jvm_crash_log(
+    game_dir: &std::path::Path,
+    crash_info: &mut crate::utils::crash_parser::CrashDetails,
+) {
+    let Some(log) = crash_info
+        .report_path
+        .as_deref()
+        .map(std::path::PathBuf::from)
+    else {
+        return;
+    };
+    match crate::utils::hs_err::archive_log(game_dir, &log) {
+        Ok(archived) => crash_info.report_path = Some(archived.to_string_lossy().to_string()),
+        Err(e) => log::warn!("Failed to archive JVM crash log: {}", e),
+    }
+}
+
 pub(crate) fn crash_event_payload(
     instance_id_slug: &str,
     crash_info: &crate::utils::crash_parser::CrashDetails,
@@ -570,15 +790,6 @@ fn should_check_for_crash(exit_code: i32, stop_requested: bool) -> bool {
     exit_code != 0 && !stop_requested
 }
 
-fn playtime_minutes(started_at_str: &str, exited_at_str: &str) -> Result<i32, String> {
-    let started = chrono::DateTime::parse_from_rfc3339(started_at_str)
-        .map_err(|e| format!("Failed to parse started_at: {}", e))?;
-    let exited = chrono::DateTime::parse_from_rfc3339(exited_at_str)
-        .map_err(|e| format!("Failed to parse exited_at: {}", e))?;
-    let duration = exited.signed_duration_since(started);
-    Ok((duration.num_seconds() / 60).max(0) as i32)
-}
-
 fn launch_start_time(started_at_str: &str) -> SystemTime {
     chrono::DateTime::parse_from_rfc3339(started_at_str)
         .map(SystemTime::from)
@@ -591,7 +802,7 @@ fn fallback_exit_time_from_log(log_file: &std::path::Path) -> Option<String> {
     Some(chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339())
 }
 
-fn is_pid_running(pid: u32) -> bool {
+pub(crate) fn is_pid_running(pid: u32) -> bool {
     use sysinfo::System;
     let mut sys = System::new_all();
     sys.refresh_all();
@@ -603,18 +814,6 @@ mod tests {
     use super::*;
     use std::io::Write;
 
-    #[test]
-    fn playtime_minutes_rounds_down_and_never_negative() {
-        assert_eq!(
-            playtime_minutes("2026-07-08T10:00:00Z", "2026-07-08T10:05:59Z").unwrap(),
-            5
-        );
-        assert_eq!(
-            playtime_minutes("2026-07-08T10:05:00Z", "2026-07-08T10:00:00Z").unwrap(),
-            0
-        );
-    }
-
     #[test]
     fn stop_intent_prevents_crash_check_for_nonzero_exit() {
         assert!(should_check_for_crash(1, false));
@@ -0,0 +1,75 @@
+//! Resource usage history of running instances.
+//!
+//! The exit monitor samples each game process every
+//! [`PROCESS_METRICS_INTERVAL`](piston_lib::game::launcher::PROCESS_METRICS_INTERVAL);
+//! the samples of the current (or last) run are kept here for the UI graph.
+
+use lazy_static::lazy_static;
+use piston_lib::game::launcher::{ProcessMetrics, ProcessMetricsBuffer};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+lazy_static! {
+    static ref PROCESS_METRICS: Mutex<HashMap<String, ProcessMetricsBuffer>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Forget the samples of a previous run of `instance_id`.
+pub(crate) fn reset(instance_id: &str) {
+    if let Ok(mut metrics) = PROCESS_METRICS.lock() {
+        metrics.remove(instance_id);
+    }
+}
+
+pub(crate) fn record(instance_id: &str, sample: ProcessMetrics) {
+    if let Ok(mut metrics) = PROCESS_METRICS.lock() {
+        metrics
+            .entry(instance_id.to_string())
+            .or_default()
+            .push(sample);
+    }
+}
+
+/// Samples of the current or last run of `instance_id`, oldest first.
+pub(crate) fn history(instance_id: &str) -> Vec<ProcessMetrics> {
+    PROCESS_METRICS
+        .lock()
+        .map(|metrics| {
+            metrics
+                .get(instance_id)
+                .map(ProcessMetricsBuffer::to_vec)
+                .unwrap_or_default()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(rss_mb: f32) -> ProcessMetrics {
+        ProcessMetrics {
+            pid: 42,
+            rss_mb,
+            virt_mb: rss_mb * 2.0,
+            cpu_pct: 12.5,
+            priority: None,
+            sampled_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn history_is_kept_per_instance_until_reset() {
+        record("metrics-a", sample(512.0));
+        record("metrics-a", sample(768.0));
+        record("metrics-b", sample(1024.0));
+
+        let history_a: Vec<f32> = history("metrics-a").iter().map(|m| m.rss_mb).collect();
+        assert_eq!(history_a, vec![512.0, 768.0]);
+        assert_eq!(history("metrics-b").len(), 1);
+
+        reset("metrics-a");
+        assert!(history("metrics-a").is_empty());
+        assert!(history("missing").is_empty());
+    }
+}
@@ -1,3 +1,18 @@
+pub(crate) mod config_files;
+pub(crate) mod config_override;
+pub(crate) mod export_candidates;
+pub(crate) mod file_access;
+pub(crate) mod history;
 pub(crate) mod launch_preparation;
 pub(crate) mod lifecycle;
+pub(crate) mod metrics;
+pub(crate) mod mod_conflicts;
 pub(crate) mod notification_actions;
+pub(crate) mod playtime;
+pub(crate) mod safe_mode;
+pub(crate) mod search;
+pub(crate) mod settings_copy;
+pub(crate) mod template;
+pub(crate) mod token_refresh;
+pub(crate) mod trash;
+pub(crate) mod version_change;
@@ -0,0 +1,597 @@
+//! Duplicate and incompatible mod detection before launch.
+//!
+//! Scans the enabled jars in an Instance's `mods` folder, reads the mod ids
+//! they embed (`fabric.mod.json`, `quilt.mod.json`, `META-INF/mods.toml`,
+//! `META-INF/neoforge.mods.toml`) and reports two kinds of problems: the same
+//! mod id shipped by more than one jar, and mods that declare another
+//! installed mod as `breaks`/`conflicts`/`incompatible`.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModConflictKind {
+    /// Several jars provide the same mod id.
+    Duplicate,
+    /// A mod declares another installed mod as incompatible.
+    Incompatible,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModConflict {
+    pub kind: ModConflictKind,
+    /// The duplicated mod id, or the mod declaring the incompatibility.
+    pub mod_id: String,
+    /// The mod it is incompatible with; `None` for duplicates.
+    pub other_mod_id: Option<String>,
+    /// Jar file names involved, relative to the `mods` folder.
+    pub files: Vec<String>,
+    pub message: String,
+    /// Jar file names that resolve the conflict when disabled.
+    pub suggested_disable: Vec<String>,
+}
+
+/// A mod id declared by one jar.
+#[derive(Debug, Clone)]
+struct ScannedMod {
+    id: String,
+    version: Option<String>,
+    file_name: String,
+    modified: Option<SystemTime>,
+    /// `(mod id, version predicate)` pairs this mod cannot run alongside.
+    incompatible: Vec<(String, VersionPredicate)>,
+}
+
+#[derive(Debug, Clone)]
+enum VersionPredicate {
+    /// Fabric/Quilt style, e.g. `>=0.5.0 <0.6` or `*`.
+    Semver(String),
+    /// Forge/NeoForge maven range, e.g. `[1.0,2.0)`.
+    Maven(String),
+}
+
+/// Scan `game_dir/mods` and return every detected conflict.
+pub fn detect_mod_conflicts_in(game_dir: &Path) -> Vec<ModConflict> {
+    find_conflicts(&scan_mods(&game_dir.join("mods")))
+}
+
+fn scan_mods(mods_dir: &Path) -> Vec<ScannedMod> {
+    let Ok(entries) = std::fs::read_dir(mods_dir) else {
+        return Vec::new();
+    };
+
+    let mut mods = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_jar = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("jar"));
+        if !is_jar || !path.is_file() {
+            continue;
+        }
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let modified = entry.metadata().and_then(|m| m.modified()).ok();
+        match read_jar_mods(&path, &file_name, modified) {
+            Ok(found) => mods.extend(found),
+            Err(e) => log::debug!("[mod_conflicts] Skipping {}: {}", file_name, e),
+        }
+    }
+    mods.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    mods
+}
+
+fn read_jar_mods(
+    path: &Path,
+    file_name: &str,
+    modified: Option<SystemTime>,
+) -> anyhow::Result<Vec<ScannedMod>> {
+    let mut archive = zip::ZipArchive::new(File::open(path)?)?;
+    let mut declared = Vec::new();
+
+    if let Some(json) = read_entry(&mut archive, "fabric.mod.json") {
+        declared.extend(parse_fabric_mod_json(&json));
+    }
+    if let Some(json) = read_entry(&mut archive, "quilt.mod.json") {
+        declared.extend(parse_quilt_mod_json(&json));
+    }
+    for toml_name in ["META-INF/mods.toml", "META-INF/neoforge.mods.toml"] {
+        if let Some(toml) = read_entry(&mut archive, toml_name) {
+            let jar_version = read_entry(&mut archive, "META-INF/MANIFEST.MF")
+                .and_then(|manifest| manifest_implementation_version(&manifest));
+            declared.extend(parse_mods_toml(&toml, jar_version.as_deref()));
+        }
+    }
+
+    // A jar shipping both Fabric and Quilt metadata declares the same id twice.
+    let mut seen = std::collections::HashSet::new();
+    Ok(declared
+        .into_iter()
+        .filter(|(id, _, _)| seen.insert(id.to_ascii_lowercase()))
+        .map(|(id, version, incompatible)| ScannedMod {
+            id,
+            version,
+            file_name: file_name.to_string(),
+            modified,
+            incompatible,
+        })
+        .collect())
+}
+
+fn read_entry(archive: &mut zip::ZipArchive<File>, name: &str) -> Option<String> {
+    let mut entry = archive.by_name(name).ok()?;
+    let mut out = String::new();
+    entry.read_to_string(&mut out).ok()?;
+    Some(out)
+}
+
+type DeclaredMod = (String, Option<String>, Vec<(String, VersionPredicate)>);
+
+fn parse_fabric_mod_json(json: &str) -> Option<DeclaredMod> {
+    let value: serde_json::Value = serde_json::from_str(json).ok()?;
+    let id = value.get("id")?.as_str()?.to_string();
+    let version = value
+        .get("version")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+
+    let mut incompatible = Vec::new();
+    for key in ["breaks", "conflicts"] {
+        let Some(map) = value.get(key).and_then(|v| v.as_object()) else {
+            continue;
+        };
+        for (other, predicate) in map {
+            for predicate in predicate_strings(predicate) {
+                incompatible.push((other.clone(), VersionPredicate::Semver(predicate)));
+            }
+        }
+    }
+    Some((id, version, incompatible))
+}
+
+fn parse_quilt_mod_json(json: &str) -> Option<DeclaredMod> {
+    let value: serde_json::Value = serde_json::from_str(json).ok()?;
+    let loader = value.get("quilt_loader")?;
+    let id = loader.get("id")?.as_str()?.to_string();
+    let version = loader
+        .get("version")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+
+    let entries = match loader.get("breaks") {
+        Some(serde_json::Value::Array(items)) => items.clone(),
+        Some(single) => vec![single.clone()],
+        None => Vec::new(),
+    };
+    let mut incompatible = Vec::new();
+    for entry in entries {
+        match &entry {
+            serde_json::Value::String(other) => {
+                incompatible.push((other.clone(), VersionPredicate::Semver("*".to_string())));
+            }
+            serde_json::Value::Object(obj) => {
+                let Some(other) = obj.get("id").and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                let predicates = obj
+                    .get("versions")
+                    .map(predicate_strings)
+                    .unwrap_or_else(|| vec!["*".to_string()]);
+                for predicate in predicates {
+                    incompatible.push((other.to_string(), VersionPredicate::Semver(predicate)));
+                }
+            }
+            _ => {}
+        }
+    }
+    Some((id, version, incompatible))
+}
+
+/// Fabric allows a single predicate string or an array of alternatives.
+fn predicate_strings(value: &serde_json::Value) -> Vec<String> {
+    match value {
+        serde_json::Value::String(s) => vec![s.clone()],
+        serde_json::Value::Array(items) => items
+            .iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_mods_toml(toml_src: &str, jar_version: Option<&str>) -> Vec<DeclaredMod> {
+    let Ok(value) = toml_src.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+    let Some(mods) = value.get("mods").and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+    let dependencies = value.get("dependencies").and_then(|v| v.as_table());
+
+    mods.iter()
+        .filter_map(|entry| {
+            let id = entry.get("modId")?.as_str()?.to_string();
+            let version = entry.get("version").and_then(|v| v.as_str()).and_then(|v| {
+                if v.starts_with("${") {
+                    jar_version.map(str::to_string)
+                } else {
+                    Some(v.to_string())
+                }
+            });
+
+            let incompatible = dependencies
+                .and_then(|deps| deps.get(&id))
+                .and_then(|v| v.as_array())
+                .map(|deps| {
+                    deps.iter()
+                        .filter(|dep| {
+                            dep.get("type")
+                                .and_then(|t| t.as_str())
+                                .is_some_and(|t| t.eq_ignore_ascii_case("incompatible"))
+                        })
+                        .filter_map(|dep| {
+                            let other = dep.get("modId")?.as_str()?.to_string();
+                            let range = dep
+                                .get("versionRange")
+                                .and_then(|r| r.as_str())
+                                .unwrap_or("*")
+                                .to_string();
+                            Some((other, VersionPredicate::Maven(range)))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            Some((id, version, incompatible))
+        })
+        .collect()
+}
+
+fn manifest_implementation_version(manifest: &str) -> Option<String> {
+    manifest.lines().find_map(|line| {
+        line.strip_prefix("Implementation-Version:")
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    })
+}
+
+fn find_conflicts(mods: &[ScannedMod]) -> Vec<ModConflict> {
+    let mut by_id: BTreeMap<String, Vec<&ScannedMod>> = BTreeMap::new();
+    for scanned in mods {
+        by_id
+            .entry(scanned.id.to_ascii_lowercase())
+            .or_default()
+            .push(scanned);
+    }
+
+    let mut conflicts = Vec::new();
+
+    for group in by_id.values() {
+        if group.len() < 2 {
+            continue;
+        }
+        let mut ordered = group.clone();
+        ordered.sort_by(|a, b| {
+            let va = a.version.as_deref().and_then(parse_lenient_version);
+            let vb = b.version.as_deref().and_then(parse_lenient_version);
+            match (va, vb) {
+                (Some(va), Some(vb)) if va != vb => va.cmp(&vb),
+                _ => a.modified.cmp(&b.modified),
+            }
+        });
+        let newest = ordered.last().expect("group has at least two mods");
+        let files: Vec<String> = group.iter().map(|m| m.file_name.clone()).collect();
+        let suggested_disable: Vec<String> = ordered[..ordered.len() - 1]
+            .iter()
+            .map(|m| m.file_name.clone())
+            .collect();
+        conflicts.push(ModConflict {
+            kind: ModConflictKind::Duplicate,
+            mod_id: newest.id.clone(),
+            other_mod_id: None,
+            message: format!(
+                "'{}' is installed {} times ({}); keep {} and disable the rest",
+                newest.id,
+                group.len(),
+                files.join(", "),
+                newest.file_name
+            ),
+            files,
+            suggested_disable,
+        });
+    }
+
+    for scanned in mods {
+        for (other_id, predicate) in &scanned.incompatible {
+            let Some(targets) = by_id.get(&other_id.to_ascii_lowercase()) else {
+                continue;
+            };
+            for target in targets {
+                if target.file_name == scanned.file_name {
+                    continue;
+                }
+                if !predicate_matches(predicate, target.version.as_deref()) {
+                    continue;
+                }
+                let already_reported = conflicts.iter().any(|c: &ModConflict| {
+                    c.kind == ModConflictKind::Incompatible
+                        && c.files.contains(&scanned.file_name)
+                        && c.files.contains(&target.file_name)
+                });
+                if already_reported {
+                    continue;
+                }
+                conflicts.push(ModConflict {
+                    kind: ModConflictKind::Incompatible,
+                    mod_id: scanned.id.clone(),
+                    other_mod_id: Some(target.id.clone()),
+                    files: vec![scanned.file_name.clone(), target.file_name.clone()],
+                    message: format!(
+                        "'{}' ({}) is incompatible with '{}' ({})",
+                        scanned.id, scanned.file_name, target.id, target.file_name
+                    ),
+                    suggested_disable: vec![target.file_name.clone()],
+                });
+            }
+        }
+    }
+
+    conflicts
+}
+
+/// Whether `version` falls inside the declared range. Anything we cannot
+/// parse is treated as matching so a declared conflict is never hidden.
+fn predicate_matches(predicate: &VersionPredicate, version: Option<&str>) -> bool {
+    let Some(version) = version.and_then(parse_lenient_version) else {
+        return true;
+    };
+    let result = match predicate {
+        VersionPredicate::Semver(req) => semver_predicate_matches(req, &version),
+        VersionPredicate::Maven(range) => maven_range_matches(range, &version),
+    };
+    result.unwrap_or(true)
+}
+
+fn semver_predicate_matches(req: &str, version: &semver::Version) -> Option<bool> {
+    let req = req.trim();
+    if req.is_empty() || req == "*" {
+        return Some(true);
+    }
+    // Fabric reads a bare version as an exact match and separates
+    // comparators with spaces.
+    let normalized = req
+        .split_whitespace()
+        .map(|part| {
+            if part.starts_with(|c: char| c.is_ascii_digit()) && !part.contains(['x', 'X', '*']) {
+                format!("={}", part)
+            } else {
+                part.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let req = semver::VersionReq::parse(&normalized).ok()?;
+    Some(req.matches(&strip_build(version)))
+}
+
+fn maven_range_matches(range: &str, version: &semver::Version) -> Option<bool> {
+    let range = range.trim();
+    if range.is_empty() || range == "*" {
+        return Some(true);
+    }
+    // A bare version is only a recommendation in Maven and matches anything.
+    let Some(open) = range.chars().next().filter(|c| *c == '[' || *c == '(') else {
+        return Some(true);
+    };
+    let close = range.chars().last().filter(|c| *c == ']' || *c == ')')?;
+    let inner = &range[1..range.len() - 1];
+    if inner.contains(['[', ']', '(', ')']) {
+        return None;
+    }
+
+    let version = strip_build(version);
+    let Some((lower, upper)) = inner.split_once(',') else {
+        // `[1.0]` pins an exact version.
+        return Some(parse_lenient_version(inner)? == version);
+    };
+
+    let lower_ok = match lower.trim() {
+        "" => true,
+        lower => {
+            let lower = parse_lenient_version(lower)?;
+            if open == '[' {
+                version >= lower
+            } else {
+                version > lower
+            }
+        }
+    };
+    let upper_ok = match upper.trim() {
+        "" => true,
+        upper => {
+            let upper = parse_lenient_version(upper)?;
+            if close == ']' {
+                version <= upper
+            } else {
+                version < upper
+            }
+        }
+    };
+    Some(lower_ok && upper_ok)
+}
+
+fn strip_build(version: &semver::Version) -> semver::Version {
+    let mut version = version.clone();
+    version.build = semver::BuildMetadata::EMPTY;
+    version
+}
+
+/// Parse mod versions such as `0.5`, `v1.2.3` or `0.5.8+mc1.20.1`, padding
+/// missing components.
+fn parse_lenient_version(raw: &str) -> Option<semver::Version> {
+    let raw = raw.trim().trim_start_matches(['v', 'V']);
+    if let Ok(version) = semver::Version::parse(raw) {
+        return Some(version);
+    }
+    let split = raw.find(['-', '+']).unwrap_or(raw.len());
+    let (core, rest) = raw.split_at(split);
+    let mut parts: Vec<&str> = core.split('.').collect();
+    if parts.is_empty() || parts.len() > 3 || parts.iter().any(|p| p.parse::<u64>().is_err()) {
+        return None;
+    }
+    while parts.len() < 3 {
+        parts.push("0");
+    }
+    semver::Version::parse(&format!("{}{}", parts.join("."), rest)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::{write::FileOptions, ZipWriter};
+
+    fn write_jar(dir: &Path, name: &str, entries: &[(&str, &str)]) {
+        let file = File::create(dir.join(name)).unwrap();
+        let mut zip = ZipWriter::new(file);
+        for (entry, contents) in entries {
+            zip.start_file::<&str, ()>(*entry, FileOptions::default())
+                .unwrap();
+            zip.write_all(contents.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    fn fabric_jar(dir: &Path, name: &str, json: &str) {
+        write_jar(dir, name, &[("fabric.mod.json", json)]);
+    }
+
+    fn game_dir() -> (tempfile::TempDir, std::path::PathBuf) {
+        let tmp = tempfile::tempdir().unwrap();
+        let mods = tmp.path().join("mods");
+        std::fs::create_dir_all(&mods).unwrap();
+        (tmp, mods)
+    }
+
+    #[test]
+    fn flags_duplicate_and_suggests_disabling_the_older_jar() {
+        let (tmp, mods) = game_dir();
+        fabric_jar(
+            &mods,
+            "sodium-0.5.9.jar",
+            r#"{"id":"sodium","version":"0.5.9+mc1.20.1"}"#,
+        );
+        fabric_jar(
+            &mods,
+            "sodium-0.5.8.jar",
+            r#"{"id":"sodium","version":"0.5.8+mc1.20.1"}"#,
+        );
+        fabric_jar(
+            &mods,
+            "lithium.jar",
+            r#"{"id":"lithium","version":"0.11.2"}"#,
+        );
+
+        let conflicts = detect_mod_conflicts_in(tmp.path());
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].kind, ModConflictKind::Duplicate);
+        assert_eq!(conflicts[0].mod_id, "sodium");
+        assert_eq!(conflicts[0].suggested_disable, vec!["sodium-0.5.8.jar"]);
+    }
+
+    #[test]
+    fn ignores_disabled_jars() {
+        let (tmp, mods) = game_dir();
+        fabric_jar(&mods, "a.jar", r#"{"id":"sodium","version":"0.5.9"}"#);
+        fabric_jar(
+            &mods,
+            "b.jar.disabled",
+            r#"{"id":"sodium","version":"0.5.8"}"#,
+        );
+
+        assert!(detect_mod_conflicts_in(tmp.path()).is_empty());
+    }
+
+    #[test]
+    fn flags_fabric_breaks_within_range_only() {
+        let (tmp, mods) = game_dir();
+        fabric_jar(
+            &mods,
+            "iris.jar",
+            r#"{"id":"iris","version":"1.6.4","breaks":{"optifabric":"*","sodium":"<0.5.0"}}"#,
+        );
+        fabric_jar(
+            &mods,
+            "optifabric.jar",
+            r#"{"id":"optifabric","version":"1.13.0"}"#,
+        );
+        fabric_jar(&mods, "sodium.jar", r#"{"id":"sodium","version":"0.5.8"}"#);
+
+        let conflicts = detect_mod_conflicts_in(tmp.path());
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].kind, ModConflictKind::Incompatible);
+        assert_eq!(conflicts[0].mod_id, "iris");
+        assert_eq!(conflicts[0].other_mod_id.as_deref(), Some("optifabric"));
+        assert_eq!(conflicts[0].suggested_disable, vec!["optifabric.jar"]);
+    }
+
+    #[test]
+    fn flags_neoforge_incompatible_dependency() {
+        let (tmp, mods) = game_dir();
+        write_jar(
+            &mods,
+            "embeddium.jar",
+            &[
+                (
+                    "META-INF/neoforge.mods.toml",
+                    r#"
+modLoader = "javafml"
+loaderVersion = "[1,)"
+
+[[mods]]
+modId = "embeddium"
+version = "${file.jarVersion}"
+
+[[dependencies.embeddium]]
+modId = "rubidium"
+type = "incompatible"
+versionRange = "[0.7,)"
+"#,
+                ),
+                ("META-INF/MANIFEST.MF", "Implementation-Version: 1.0.3\n"),
+            ],
+        );
+        write_jar(
+            &mods,
+            "rubidium.jar",
+            &[(
+                "META-INF/mods.toml",
+                "[[mods]]\nmodId = \"rubidium\"\nversion = \"0.7.1\"\n",
+            )],
+        );
+
+        let conflicts = detect_mod_conflicts_in(tmp.path());
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].mod_id, "embeddium");
+        assert_eq!(conflicts[0].other_mod_id.as_deref(), Some("rubidium"));
+    }
+
+    #[test]
+    fn maven_ranges_respect_bounds() {
+        let v = parse_lenient_version("1.5").unwrap();
+        assert_eq!(maven_range_matches("[1.0,2.0)", &v), Some(true));
+        assert_eq!(maven_range_matches("[1.6,)", &v), Some(false));
+        assert_eq!(maven_range_matches("(,1.5]", &v), Some(true));
+        assert_eq!(maven_range_matches("(,1.5)", &v), Some(false));
+        assert_eq!(maven_range_matches("[1.5]", &v), Some(true));
+        assert_eq!(maven_range_matches("1.0", &v), Some(true));
+    }
+}
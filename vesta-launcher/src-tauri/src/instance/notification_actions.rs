@@ -1,3 +1,4 @@
+use crate::instance::safe_mode::SafeModeStrategy;
 use crate::notifications::manager::{ActionHandler, NotificationManager};
 use crate::tasks::manager::TaskManager;
 use anyhow::Result;
@@ -41,11 +42,49 @@ impl ActionHandler for ResumeInstanceOperation {
     }
 }
 
+struct LaunchSafeMode;
+
+fn safe_mode_instance_id_from_key(client_key: Option<String>) -> Result<i32> {
+    let key = client_key
+        .ok_or_else(|| anyhow::anyhow!("Missing client_key for launch_safe_mode action"))?;
+    key.strip_prefix("safe_mode_instance_")
+        .ok_or_else(|| anyhow::anyhow!("Invalid safe mode client_key"))?
+        .parse::<i32>()
+        .map_err(|_| anyhow::anyhow!("Invalid instance ID in client_key"))
+}
+
+impl ActionHandler for LaunchSafeMode {
+    fn handle(
+        &self,
+        app_handle: &AppHandle,
+        client_key: Option<String>,
+        payload: Option<serde_json::Value>,
+    ) -> Result<()> {
+        let instance_id = safe_mode_instance_id_from_key(client_key)?;
+        let strategy = payload
+            .and_then(|p| p.get("strategy").cloned())
+            .map(serde_json::from_value::<SafeModeStrategy>)
+            .transpose()?
+            .unwrap_or(SafeModeStrategy::DisableMods);
+        let handle = app_handle.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(error) =
+                crate::commands::instances::launch_instance_safe_mode(handle, instance_id, strategy)
+                    .await
+            {
+                log::error!("Failed to launch instance in safe mode: {error}");
+            }
+        });
+        Ok(())
+    }
+}
+
 pub fn register(manager: &NotificationManager) {
     manager.register_action(
         "resume_instance_operation",
         Arc::new(ResumeInstanceOperation),
     );
+    manager.register_action("launch_safe_mode", Arc::new(LaunchSafeMode));
 }
 
 #[cfg(test)]
@@ -61,4 +100,13 @@ mod tests {
         assert!(instance_id_from_key(Some("task_42".into())).is_err());
         assert!(instance_id_from_key(None).is_err());
     }
+
+    #[test]
+    fn parses_safe_mode_instance_keys() {
+        assert_eq!(
+            safe_mode_instance_id_from_key(Some("safe_mode_instance_7".into())).unwrap(),
+            7
+        );
+        assert!(safe_mode_instance_id_from_key(Some("interrupted_instance_7".into())).is_err());
+    }
 }
@@ -0,0 +1,420 @@
+//! Live playtime tracking for running instances.
+//!
+//! A `play_session` row is opened when the exit monitor starts watching a
+//! game process and checkpointed every [`CHECKPOINT_INTERVAL`]: the minutes
+//! played so far go into `partial_minutes` and the difference since the last
+//! checkpoint is added to the instance's `total_playtime_minutes`. A launcher
+//! crash or power loss therefore loses at most one interval. On exit the row
+//! is finalized with the precise exit time and the remainder is credited;
+//! sessions whose monitor died are closed at their last checkpoint.
+
+use crate::models::play_session::{NewPlaySession, PlaySession};
+use crate::schema::instance::dsl as instance_dsl;
+use crate::schema::play_session::dsl as session_dsl;
+use chrono::{DateTime, Datelike, Utc};
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_query;
+use diesel::sql_types::Integer;
+use serde::Serialize;
+use std::time::Duration;
+
+pub(crate) const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Elapsed time of a running session for the UI timer.
+#[derive(Debug, Clone, Serialize)]
+pub struct LiveSession {
+    pub started_at: String,
+    pub elapsed_secs: i64,
+    /// Minutes already credited to the instance by checkpoints.
+    pub checkpointed_minutes: i32,
+    /// Minutes played today and this week (local time), this session included.
+    pub today_minutes: i64,
+    pub week_minutes: i64,
+}
+
+fn minutes_between(started_at: &str, until: &str) -> Option<i32> {
+    let started = DateTime::parse_from_rfc3339(started_at).ok()?;
+    let until = DateTime::parse_from_rfc3339(until).ok()?;
+    Some((until.signed_duration_since(started).num_seconds() / 60).max(0) as i32)
+}
+
+/// The still-open session of the run started at `started_at`.
+pub(crate) fn find_open_session(
+    conn: &mut SqliteConnection,
+    instance_id: i32,
+    started_at: &str,
+) -> Result<Option<PlaySession>, String> {
+    session_dsl::play_session
+        .filter(session_dsl::instance_id.eq(instance_id))
+        .filter(session_dsl::started_at.eq(started_at))
+        .filter(session_dsl::partial_minutes.is_not_null())
+        .first::<PlaySession>(conn)
+        .optional()
+        .map_err(|e| format!("Failed to load open play session: {}", e))
+}
+
+/// Open a session for a run, or pick up the one already open for it (the
+/// launcher restarted while the game kept running).
+pub(crate) fn open_session(
+    conn: &mut SqliteConnection,
+    instance_id: i32,
+    started_at: &str,
+    safe_mode: bool,
+) -> Result<i32, String> {
+    if let Some(existing) = find_open_session(conn, instance_id, started_at)? {
+        return Ok(existing.id);
+    }
+    let row = NewPlaySession {
+        instance_id,
+        started_at: started_at.to_string(),
+        ended_at: started_at.to_string(),
+        exit_code: None,
+        crashed: false,
+        safe_mode,
+        partial_minutes: Some(0),
+        active_seconds: None,
+    };
+    conn.transaction(|conn| {
+        diesel::insert_into(session_dsl::play_session)
+            .values(&row)
+            .execute(conn)?;
+        diesel::select(sql::<Integer>("last_insert_rowid()")).get_result(conn)
+    })
+    .map_err(|e: diesel::result::Error| format!("Failed to open play session: {}", e))
+}
+
+fn credit_minutes(
+    conn: &mut SqliteConnection,
+    instance_id: i32,
+    minutes: i32,
+) -> diesel::QueryResult<()> {
+    if minutes <= 0 {
+        return Ok(());
+    }
+    let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    diesel::update(instance_dsl::instance.find(instance_id))
+        .set((
+            instance_dsl::total_playtime_minutes.eq(instance_dsl::total_playtime_minutes + minutes),
+            instance_dsl::last_played.eq(&now),
+            instance_dsl::updated_at.eq(&now),
+        ))
+        .execute(conn)?;
+    Ok(())
+}
+
+/// Record the minutes played up to `now` on an open session and credit the
+/// instance with the difference since the last checkpoint. Returns the
+/// minutes credited for the session so far.
+pub(crate) fn checkpoint_session(
+    conn: &mut SqliteConnection,
+    session_id: i32,
+    now: &str,
+) -> Result<i32, String> {
+    conn.transaction(|conn| {
+        let session: PlaySession = session_dsl::play_session.find(session_id).first(conn)?;
+        let Some(credited) = session.partial_minutes else {
+            // Already finalized.
+            return Ok(0);
+        };
+        let minutes = minutes_between(&session.started_at, now)
+            .unwrap_or(credited)
+            .max(credited);
+
+        diesel::update(session_dsl::play_session.find(session_id))
+            .set((
+                session_dsl::partial_minutes.eq(Some(minutes)),
+                session_dsl::ended_at.eq(now),
+            ))
+            .execute(conn)?;
+        credit_minutes(conn, session.instance_id, minutes - credited)?;
+        Ok(minutes)
+    })
+    .map_err(|e: diesel::result::Error| format!("Failed to checkpoint play session: {}", e))
+}
+
+/// Add seconds of window focus to a session. The first call starts the
+/// count, so sessions whose focus was never tracked stay `NULL`.
+pub(crate) fn add_active_seconds(
+    conn: &mut SqliteConnection,
+    session_id: i32,
+    secs: i32,
+) -> Result<(), String> {
+    sql_query(
+        "UPDATE play_session SET active_seconds = COALESCE(active_seconds, 0) + ? WHERE id = ?",
+    )
+    .bind::<Integer, _>(secs.max(0))
+    .bind::<Integer, _>(session_id)
+    .execute(conn)
+    .map(|_| ())
+    .map_err(|e| format!("Failed to record active play time: {}", e))
+}
+
+/// Percentage of the instance's focus-tracked playtime the game window had
+/// focus, or `None` when no session was tracked.
+pub(crate) fn active_play_ratio(
+    conn: &mut SqliteConnection,
+    instance_id: i32,
+) -> Result<Option<f64>, String> {
+    let sessions: Vec<PlaySession> = session_dsl::play_session
+        .filter(session_dsl::instance_id.eq(instance_id))
+        .filter(session_dsl::active_seconds.is_not_null())
+        .load(conn)
+        .map_err(|e| format!("Failed to load play sessions: {}", e))?;
+
+    let (active, total) = sessions
+        .iter()
+        .fold((0i64, 0i64), |(active, total), session| {
+            let duration = session.duration_secs().unwrap_or(0);
+            let focused = (session.active_seconds.unwrap_or(0) as i64).min(duration);
+            (active + focused, total + duration)
+        });
+    if total == 0 {
+        return Ok(None);
+    }
+    Ok(Some(active as f64 / total as f64 * 100.0))
+}
+
+/// Close an open session at `ended_at` (its last checkpoint when `None`) and
+/// credit the minutes not yet covered by checkpoints. Finalizing a closed
+/// session changes nothing.
+pub(crate) fn finalize_session(
+    conn: &mut SqliteConnection,
+    session_id: i32,
+    ended_at: Option<&str>,
+    exit_code: Option<i32>,
+    crashed: bool,
+) -> Result<PlaySession, String> {
+    conn.transaction(|conn| {
+        let session: PlaySession = session_dsl::play_session.find(session_id).first(conn)?;
+        let Some(credited) = session.partial_minutes else {
+            return Ok(session);
+        };
+        let ended_at = ended_at.unwrap_or(&session.ended_at).to_string();
+        // Never take back minutes a checkpoint already credited.
+        let minutes = minutes_between(&session.started_at, &ended_at)
+            .unwrap_or(credited)
+            .max(credited);
+
+        diesel::update(session_dsl::play_session.find(session_id))
+            .set((
+                session_dsl::partial_minutes.eq(None::<i32>),
+                session_dsl::ended_at.eq(&ended_at),
+                session_dsl::exit_code.eq(exit_code),
+                session_dsl::crashed.eq(crashed),
+            ))
+            .execute(conn)?;
+        credit_minutes(conn, session.instance_id, minutes - credited)?;
+        session_dsl::play_session.find(session_id).first(conn)
+    })
+    .map_err(|e: diesel::result::Error| format!("Failed to finalize play session: {}", e))
+}
+
+/// Close open sessions that no live run owns any more (the monitor died with
+/// the launcher and the process is gone without an exit record). `live` holds
+/// `(instance_id, started_at)` of runs that are still being tracked.
+pub(crate) fn close_abandoned_sessions(
+    conn: &mut SqliteConnection,
+    live: &[(i32, String)],
+) -> Result<usize, String> {
+    let open: Vec<PlaySession> = session_dsl::play_session
+        .filter(session_dsl::partial_minutes.is_not_null())
+        .load(conn)
+        .map_err(|e| format!("Failed to load open play sessions: {}", e))?;
+
+    let mut closed = 0;
+    for session in open {
+        let owned = live.iter().any(|(instance_id, started_at)| {
+            *instance_id == session.instance_id && *started_at == session.started_at
+        });
+        if !owned {
+            finalize_session(conn, session.id, None, None, false)?;
+            closed += 1;
+        }
+    }
+    Ok(closed)
+}
+
+/// Minutes of the instance's sessions that started at or after `since`,
+/// counting open sessions up to their last checkpoint.
+fn minutes_since(sessions: &[PlaySession], since: DateTime<Utc>) -> i64 {
+    sessions
+        .iter()
+        .filter(|session| {
+            DateTime::parse_from_rfc3339(&session.started_at)
+                .is_ok_and(|started| started.with_timezone(&Utc) >= since)
+        })
+        .map(|session| match session.partial_minutes {
+            Some(minutes) => minutes as i64,
+            None => session.duration_secs().unwrap_or(0) / 60,
+        })
+        .sum()
+}
+
+fn local_day_start(now: DateTime<chrono::Local>, days_back: u32) -> DateTime<Utc> {
+    let date = now.date_naive() - chrono::Days::new(days_back as u64);
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|midnight| midnight.and_local_timezone(chrono::Local).earliest())
+        .map(|start| start.with_timezone(&Utc))
+        .unwrap_or_else(|| now.with_timezone(&Utc))
+}
+
+/// Live view of the run of `instance_id` started at `started_at`.
+pub(crate) fn live_session(
+    conn: &mut SqliteConnection,
+    instance_id: i32,
+    started_at: &str,
+) -> Result<LiveSession, String> {
+    let started = DateTime::parse_from_rfc3339(started_at)
+        .map_err(|e| format!("Failed to parse session start: {}", e))?;
+    let now = chrono::Local::now();
+    let elapsed_secs = now.signed_duration_since(started).num_seconds().max(0);
+    let checkpointed_minutes = find_open_session(conn, instance_id, started_at)?
+        .and_then(|session| session.partial_minutes)
+        .unwrap_or(0);
+
+    let sessions: Vec<PlaySession> = session_dsl::play_session
+        .filter(session_dsl::instance_id.eq(instance_id))
+        .load(conn)
+        .map_err(|e| format!("Failed to load play sessions: {}", e))?;
+    // Count the running session by its elapsed time rather than its last
+    // checkpoint.
+    let unrecorded = (elapsed_secs / 60 - checkpointed_minutes as i64).max(0);
+    let today_start = local_day_start(now, 0);
+    let week_start = local_day_start(now, now.weekday().num_days_from_monday());
+    let counts_for = |since: DateTime<Utc>| {
+        minutes_since(&sessions, since)
+            + if started.with_timezone(&Utc) >= since {
+                unrecorded
+            } else {
+                0
+            }
+    };
+
+    Ok(LiveSession {
+        started_at: started_at.to_string(),
+        elapsed_secs,
+        checkpointed_minutes,
+        today_minutes: counts_for(today_start),
+        week_minutes: counts_for(week_start),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::connection::SimpleConnection;
+    use diesel_migrations::MigrationHarness;
+
+    const START: &str = "2026-10-17T10:00:00+00:00";
+
+    fn at(minutes: i64, secs: i64) -> String {
+        (DateTime::parse_from_rfc3339(START).unwrap()
+            + chrono::Duration::minutes(minutes)
+            + chrono::Duration::seconds(secs))
+        .to_rfc3339()
+    }
+
+    fn conn() -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.run_pending_migrations(crate::utils::db::VESTA_MIGRATIONS)
+            .unwrap();
+        conn.batch_execute(
+            "INSERT INTO instance (id, name, minecraft_version, total_playtime_minutes)
+                VALUES (1, 'Test', '1.21.1', 100);",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn total(conn: &mut SqliteConnection) -> i32 {
+        instance_dsl::instance
+            .find(1)
+            .select(instance_dsl::total_playtime_minutes)
+            .first(conn)
+            .unwrap()
+    }
+
+    #[test]
+    fn minutes_round_down_and_never_go_negative() {
+        assert_eq!(
+            minutes_between("2026-07-08T10:00:00Z", "2026-07-08T10:05:59Z"),
+            Some(5)
+        );
+        assert_eq!(
+            minutes_between("2026-07-08T10:05:00Z", "2026-07-08T10:00:00Z"),
+            Some(0)
+        );
+        assert_eq!(minutes_between("not a time", "2026-07-08T10:00:00Z"), None);
+    }
+
+    #[test]
+    fn monitor_dying_between_checkpoints_loses_at_most_one_interval() {
+        let mut conn = conn();
+        let session = open_session(&mut conn, 1, START, false).unwrap();
+        assert_eq!(
+            checkpoint_session(&mut conn, session, &at(5, 0)).unwrap(),
+            5
+        );
+        assert_eq!(
+            checkpoint_session(&mut conn, session, &at(10, 0)).unwrap(),
+            10
+        );
+        assert_eq!(total(&mut conn), 110);
+
+        // The launcher dies at 13 minutes; after restart nothing owns the
+        // session, so it closes at its last checkpoint.
+        assert_eq!(close_abandoned_sessions(&mut conn, &[]).unwrap(), 1);
+        let closed: PlaySession = session_dsl::play_session
+            .find(session)
+            .first(&mut conn)
+            .unwrap();
+        assert_eq!(closed.partial_minutes, None);
+        assert_eq!(closed.ended_at, at(10, 0));
+        assert_eq!(total(&mut conn), 110);
+        assert_eq!(closed.duration_secs(), Some(10 * 60));
+
+        // Nothing is credited twice.
+        assert_eq!(close_abandoned_sessions(&mut conn, &[]).unwrap(), 0);
+        finalize_session(&mut conn, session, Some(&at(30, 0)), Some(0), false).unwrap();
+        assert_eq!(total(&mut conn), 110);
+    }
+
+    #[test]
+    fn clean_exit_finalizes_with_the_precise_exit_time() {
+        let mut conn = conn();
+        let session = open_session(&mut conn, 1, START, false).unwrap();
+        checkpoint_session(&mut conn, session, &at(5, 0)).unwrap();
+        // Picking the run up again after a launcher restart reuses the row.
+        assert_eq!(open_session(&mut conn, 1, START, false).unwrap(), session);
+        assert_eq!(
+            close_abandoned_sessions(&mut conn, &[(1, START.to_string())]).unwrap(),
+            0
+        );
+
+        let finished =
+            finalize_session(&mut conn, session, Some(&at(12, 30)), Some(0), false).unwrap();
+        assert_eq!(finished.partial_minutes, None);
+        assert_eq!(finished.ended_at, at(12, 30));
+        assert_eq!(finished.exit_code, Some(0));
+        assert_eq!(total(&mut conn), 112);
+    }
+
+    #[test]
+    fn active_ratio_counts_only_focus_tracked_sessions() {
+        let mut conn = conn();
+        assert_eq!(active_play_ratio(&mut conn, 1).unwrap(), None);
+
+        // Focus was never tracked for this one.
+        let untracked = open_session(&mut conn, 1, START, false).unwrap();
+        finalize_session(&mut conn, untracked, Some(&at(60, 0)), Some(0), false).unwrap();
+        assert_eq!(active_play_ratio(&mut conn, 1).unwrap(), None);
+
+        let tracked = open_session(&mut conn, 1, &at(120, 0), false).unwrap();
+        add_active_seconds(&mut conn, tracked, 0).unwrap();
+        add_active_seconds(&mut conn, tracked, 450).unwrap();
+        finalize_session(&mut conn, tracked, Some(&at(130, 0)), Some(0), false).unwrap();
+
+        assert_eq!(active_play_ratio(&mut conn, 1).unwrap(), Some(75.0));
+    }
+}
@@ -0,0 +1,307 @@
+//! Crash-loop detection and safe-mode launches.
+//!
+//! Every finished game process is recorded as a `play_session`. When an
+//! Instance keeps crashing shortly after launch, the user is offered a
+//! safe-mode launch: either with all mods temporarily renamed to `.disabled`
+//! (recorded in a sidecar so the batch can be reverted), or as plain vanilla
+//! for the same Minecraft version.
+
+use crate::models::play_session::PlaySession;
+use crate::notifications::manager::NotificationManager;
+use crate::notifications::models::{CreateNotificationInput, NotificationAction, NotificationType};
+use crate::schema::play_session::dsl as session_dsl;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A crash within this many seconds of launch counts as a "quick" crash.
+pub(crate) const QUICK_CRASH_WINDOW_SECS: i64 = 30;
+
+/// Consecutive quick crashes before safe mode is offered.
+pub(crate) const QUICK_CRASH_THRESHOLD: usize = 3;
+
+const SIDECAR_FILE_NAME: &str = "safe_mode.json";
+const DISABLED_SUFFIX: &str = ".disabled";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SafeModeStrategy {
+    /// Launch the Instance as-is with every enabled mod renamed to `.disabled`.
+    DisableMods,
+    /// Launch vanilla Minecraft for the same version in the same game directory.
+    Vanilla,
+}
+
+/// Record of the mods renamed for a safe-mode session, kept in
+/// `<game_dir>/.vesta/safe_mode.json` until the session ends.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SafeModeSidecar {
+    started_at: String,
+    /// Paths relative to the game directory, as they were before disabling.
+    renamed: Vec<String>,
+}
+
+fn sidecar_path(game_dir: &Path) -> PathBuf {
+    game_dir.join(".vesta").join(SIDECAR_FILE_NAME)
+}
+
+pub(crate) fn has_pending_restore(game_dir: &Path) -> bool {
+    sidecar_path(game_dir).exists()
+}
+
+/// Rename every enabled mod jar in `mods/` to `*.jar.disabled`, recording the
+/// batch in the sidecar first so an interrupted session can still be reverted.
+pub(crate) fn disable_mods(game_dir: &Path) -> Result<usize, String> {
+    if has_pending_restore(game_dir) {
+        // A previous safe-mode session never got cleaned up; revert it before
+        // starting a new batch so the sidecar stays authoritative.
+        restore_mods(game_dir)?;
+    }
+
+    let mods_dir = game_dir.join("mods");
+    let mut renamed = Vec::new();
+    if mods_dir.is_dir() {
+        let entries = std::fs::read_dir(&mods_dir)
+            .map_err(|e| format!("Failed to read mods directory {:?}: {}", mods_dir, e))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_jar = path
+                .extension()
+                .map(|ext| ext.eq_ignore_ascii_case("jar"))
+                .unwrap_or(false);
+            if !path.is_file() || !is_jar {
+                continue;
+            }
+            if let Ok(rel) = path.strip_prefix(game_dir) {
+                renamed.push(rel.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+    renamed.sort();
+
+    let sidecar = SafeModeSidecar {
+        started_at: chrono::Utc::now().to_rfc3339(),
+        renamed,
+    };
+    write_sidecar(game_dir, &sidecar)?;
+
+    let mut done = Vec::new();
+    for rel in &sidecar.renamed {
+        let from = game_dir.join(rel);
+        let to = game_dir.join(format!("{}{}", rel, DISABLED_SUFFIX));
+        if let Err(e) = std::fs::rename(&from, &to) {
+            // Roll back what we already renamed so the batch stays all-or-nothing.
+            for undo in done.iter().rev() {
+                let _ = std::fs::rename(
+                    game_dir.join(format!("{}{}", undo, DISABLED_SUFFIX)),
+                    game_dir.join(undo),
+                );
+            }
+            let _ = std::fs::remove_file(sidecar_path(game_dir));
+            return Err(format!("Failed to disable mod {:?}: {}", from, e));
+        }
+        done.push(rel.clone());
+    }
+
+    Ok(sidecar.renamed.len())
+}
+
+/// Revert the batch recorded in the sidecar and remove it. Mods that the user
+/// re-enabled or deleted in the meantime are left alone.
+pub(crate) fn restore_mods(game_dir: &Path) -> Result<usize, String> {
+    let path = sidecar_path(game_dir);
+    if !path.exists() {
+        return Ok(0);
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read safe-mode sidecar {:?}: {}", path, e))?;
+    let sidecar: SafeModeSidecar = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse safe-mode sidecar {:?}: {}", path, e))?;
+
+    let mut restored = 0;
+    let mut failures = Vec::new();
+    for rel in &sidecar.renamed {
+        let original = game_dir.join(rel);
+        let disabled = game_dir.join(format!("{}{}", rel, DISABLED_SUFFIX));
+        if original.exists() || !disabled.exists() {
+            continue;
+        }
+        match std::fs::rename(&disabled, &original) {
+            Ok(()) => restored += 1,
+            Err(e) => failures.push(format!("{}: {}", rel, e)),
+        }
+    }
+
+    if !failures.is_empty() {
+        return Err(format!(
+            "Failed to restore {} mod(s) after safe mode: {}",
+            failures.len(),
+            failures.join(", ")
+        ));
+    }
+
+    std::fs::remove_file(&path)
+        .map_err(|e| format!("Failed to remove safe-mode sidecar {:?}: {}", path, e))?;
+    Ok(restored)
+}
+
+fn write_sidecar(game_dir: &Path, sidecar: &SafeModeSidecar) -> Result<(), String> {
+    let path = sidecar_path(game_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+    }
+    let data = serde_json::to_string_pretty(sidecar)
+        .map_err(|e| format!("Failed to serialize safe-mode sidecar: {}", e))?;
+    std::fs::write(&path, data)
+        .map_err(|e| format!("Failed to write safe-mode sidecar {:?}: {}", path, e))
+}
+
+/// Most recent finished sessions for an Instance, newest first.
+pub(crate) fn recent_sessions(
+    conn: &mut SqliteConnection,
+    instance_id: i32,
+    limit: i64,
+) -> Result<Vec<PlaySession>, String> {
+    session_dsl::play_session
+        .filter(session_dsl::instance_id.eq(instance_id))
+        .filter(session_dsl::partial_minutes.is_null())
+        .order((session_dsl::ended_at.desc(), session_dsl::id.desc()))
+        .limit(limit)
+        .load::<PlaySession>(conn)
+        .map_err(|e| format!("Failed to load play sessions: {}", e))
+}
+
+pub(crate) fn is_quick_crash(session: &PlaySession) -> bool {
+    session.crashed
+        && session
+            .duration_secs()
+            .map(|secs| secs <= QUICK_CRASH_WINDOW_SECS)
+            .unwrap_or(false)
+}
+
+/// Count the unbroken run of quick crashes at the start of `sessions`
+/// (newest first). A safe-mode session resets the streak.
+pub(crate) fn consecutive_quick_crashes(sessions: &[PlaySession]) -> usize {
+    sessions
+        .iter()
+        .take_while(|session| !session.safe_mode && is_quick_crash(session))
+        .count()
+}
+
+pub(crate) fn safe_mode_client_key(instance_id: i32) -> String {
+    format!("safe_mode_instance_{}", instance_id)
+}
+
+pub(crate) fn notify_crash_loop(manager: &NotificationManager, instance_id: i32, name: &str) {
+    let actions = vec![
+        NotificationAction {
+            action_id: "launch_safe_mode".to_string(),
+            label: "Launch in safe mode".to_string(),
+            action_type: "primary".to_string(),
+            payload: Some(serde_json::json!({ "strategy": SafeModeStrategy::DisableMods })),
+        },
+        NotificationAction {
+            action_id: "launch_safe_mode".to_string(),
+            label: "Launch vanilla".to_string(),
+            action_type: "secondary".to_string(),
+            payload: Some(serde_json::json!({ "strategy": SafeModeStrategy::Vanilla })),
+        },
+    ];
+
+    if let Err(error) = manager.create(CreateNotificationInput {
+        client_key: Some(safe_mode_client_key(instance_id)),
+        title: Some("Instance keeps crashing".to_string()),
+        description: Some(format!(
+            "'{}' crashed within {} seconds of launch {} times in a row. Try launching it in safe mode with all mods disabled.",
+            name, QUICK_CRASH_WINDOW_SECS, QUICK_CRASH_THRESHOLD
+        )),
+        severity: Some("warning".to_string()),
+        notification_type: Some(NotificationType::Patient),
+        dismissible: Some(true),
+        persist: Some(true),
+        silent: Some(false),
+        actions: Some(serde_json::to_string(&actions).unwrap_or_default()),
+        progress: None,
+        current_step: None,
+        total_steps: None,
+        metadata: None,
+        show_on_completion: None,
+    }) {
+        log::error!(
+            "Failed to create crash-loop notification for {}: {}",
+            name,
+            error
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(duration_secs: i64, crashed: bool, safe_mode: bool) -> PlaySession {
+        let started = chrono::DateTime::parse_from_rfc3339("2026-10-17T10:00:00Z").unwrap();
+        let ended = started + chrono::Duration::seconds(duration_secs);
+        PlaySession {
+            id: 0,
+            instance_id: 1,
+            started_at: started.to_rfc3339(),
+            ended_at: ended.to_rfc3339(),
+            exit_code: Some(if crashed { 1 } else { 0 }),
+            crashed,
+            safe_mode,
+            partial_minutes: None,
+            active_seconds: None,
+        }
+    }
+
+    #[test]
+    fn counts_only_the_leading_run_of_quick_crashes() {
+        let sessions = vec![
+            session(5, true, false),
+            session(12, true, false),
+            session(29, true, false),
+            session(600, false, false),
+            session(3, true, false),
+        ];
+        assert_eq!(consecutive_quick_crashes(&sessions), 3);
+
+        let slow_crash = vec![session(5, true, false), session(120, true, false)];
+        assert_eq!(consecutive_quick_crashes(&slow_crash), 1);
+
+        let after_safe_mode = vec![session(5, true, false), session(5, true, true)];
+        assert_eq!(consecutive_quick_crashes(&after_safe_mode), 1);
+    }
+
+    #[test]
+    fn disable_and_restore_mods_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let game_dir = dir.path();
+        let mods = game_dir.join("mods");
+        std::fs::create_dir_all(&mods).unwrap();
+        std::fs::write(mods.join("a.jar"), b"a").unwrap();
+        std::fs::write(mods.join("b.jar"), b"b").unwrap();
+        std::fs::write(mods.join("c.jar.disabled"), b"c").unwrap();
+
+        assert_eq!(disable_mods(game_dir).unwrap(), 2);
+        assert!(has_pending_restore(game_dir));
+        assert!(mods.join("a.jar.disabled").exists());
+        assert!(mods.join("b.jar.disabled").exists());
+        assert!(!mods.join("a.jar").exists());
+
+        assert_eq!(restore_mods(game_dir).unwrap(), 2);
+        assert!(!has_pending_restore(game_dir));
+        assert!(mods.join("a.jar").exists());
+        assert!(mods.join("b.jar").exists());
+        // Mods the user had disabled beforehand stay disabled.
+        assert!(mods.join("c.jar.disabled").exists());
+        assert!(!mods.join("c.jar").exists());
+    }
+
+    #[test]
+    fn restore_without_sidecar_is_a_no_op() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(restore_mods(dir.path()).unwrap(), 0);
+    }
+}
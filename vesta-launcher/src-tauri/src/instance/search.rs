@@ -0,0 +1,115 @@
+//! Full-text search over instance names and notes.
+//!
+//! `instance_fts` is an FTS5 table kept in sync with `instance` by triggers
+//! (see the `add_instance_notes_fts` migration).
+
+use crate::models::instance::Instance;
+use diesel::prelude::*;
+use diesel::sql_types::{Integer, Text};
+
+#[derive(QueryableByName)]
+struct FtsMatch {
+    #[diesel(sql_type = Integer)]
+    id: i32,
+}
+
+/// Turn user input into an FTS5 query: every word must match as a prefix.
+/// Words are quoted so FTS operators and punctuation are taken literally;
+/// words without any letters or digits are dropped.
+pub fn match_expression(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .filter(|term| term.chars().any(char::is_alphanumeric))
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Instances (excluding trashed ones) whose name or notes match `query`,
+/// best match first.
+pub fn search_instances(conn: &mut SqliteConnection, query: &str) -> QueryResult<Vec<Instance>> {
+    use crate::schema::instance::dsl as inst_dsl;
+
+    let Some(expression) = match_expression(query) else {
+        return Ok(Vec::new());
+    };
+    let ranked: Vec<i32> =
+        diesel::sql_query("SELECT id FROM instance_fts WHERE instance_fts MATCH ? ORDER BY rank")
+            .bind::<Text, _>(expression)
+            .load::<FtsMatch>(conn)?
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+
+    let mut instances = inst_dsl::instance
+        .filter(inst_dsl::id.eq_any(&ranked))
+        .filter(inst_dsl::deleted_at.is_null())
+        .load::<Instance>(conn)?;
+    instances.sort_by_key(|inst| ranked.iter().position(|id| *id == inst.id));
+    Ok(instances)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::connection::SimpleConnection;
+    use diesel_migrations::MigrationHarness;
+
+    fn conn() -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.run_pending_migrations(crate::utils::db::VESTA_MIGRATIONS)
+            .unwrap();
+        conn.batch_execute(
+            "INSERT INTO instance (id, name, minecraft_version, notes) VALUES
+                (1, 'Skyblock', '1.21.1', 'co-op world with Alex'),
+                (2, 'Create Mod Pack', '1.20.1', NULL),
+                (3, 'Vanilla', '1.21.1', 'testing create contraptions');",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn ids(instances: Vec<Instance>) -> Vec<i32> {
+        instances.into_iter().map(|inst| inst.id).collect()
+    }
+
+    #[test]
+    fn matches_names_and_notes_by_prefix() {
+        let mut conn = conn();
+        let mut found = ids(search_instances(&mut conn, "creat").unwrap());
+        found.sort();
+        assert_eq!(found, vec![2, 3]);
+        assert_eq!(ids(search_instances(&mut conn, "alex").unwrap()), vec![1]);
+        assert!(search_instances(&mut conn, "   ").unwrap().is_empty());
+        assert!(search_instances(&mut conn, "- *").unwrap().is_empty());
+    }
+
+    #[test]
+    fn index_follows_updates_deletes_and_trash() {
+        let mut conn = conn();
+        conn.batch_execute(
+            "UPDATE instance SET name = 'Oneblock' WHERE id = 1;
+             DELETE FROM instance WHERE id = 2;
+             UPDATE instance SET deleted_at = '2026-10-17T00:00:00Z' WHERE id = 3;",
+        )
+        .unwrap();
+
+        assert!(search_instances(&mut conn, "skyblock").unwrap().is_empty());
+        assert_eq!(
+            ids(search_instances(&mut conn, "oneblock").unwrap()),
+            vec![1]
+        );
+        assert!(search_instances(&mut conn, "create").unwrap().is_empty());
+    }
+
+    #[test]
+    fn fts_syntax_is_taken_literally() {
+        let mut conn = conn();
+        assert_eq!(
+            match_expression(r#"co-op "world"#).as_deref(),
+            Some(r#""co-op"* """world"*"#)
+        );
+        assert!(search_instances(&mut conn, "co-op AND").unwrap().is_empty());
+        assert_eq!(ids(search_instances(&mut conn, "co-op").unwrap()), vec![1]);
+    }
+}
@@ -0,0 +1,226 @@
+//! Copying client settings from an existing instance into a new one.
+//!
+//! Only the files in [`COPY_RULES`] are copied. Loader-specific configs list
+//! the loaders they belong to and are filtered out when the new instance runs
+//! a different one, so Forge keybind configs never end up in a Fabric
+//! instance. Files missing from the source are skipped.
+
+use crate::schema::instance::dsl as inst_dsl;
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+use std::path::Path;
+
+const RESOURCE_PACKS_DIR: &str = "resourcepacks";
+
+struct CopyRule {
+    path: &'static str,
+    /// Loaders the file applies to; empty means every instance.
+    loaders: &'static [&'static str],
+}
+
+const COPY_RULES: &[CopyRule] = &[
+    CopyRule {
+        path: "options.txt",
+        loaders: &[],
+    },
+    CopyRule {
+        path: "servers.dat",
+        loaders: &[],
+    },
+    // OptiFine video and shader settings.
+    CopyRule {
+        path: "optionsof.txt",
+        loaders: &[],
+    },
+    CopyRule {
+        path: "optionsshaders.txt",
+        loaders: &[],
+    },
+    CopyRule {
+        path: "config/forge-client.toml",
+        loaders: &["forge"],
+    },
+    CopyRule {
+        path: "config/neoforge-client.toml",
+        loaders: &["neoforge"],
+    },
+    // Controlling (keybind search) and Amecs (modifier keybinds).
+    CopyRule {
+        path: "config/controlling-client.toml",
+        loaders: &["forge", "neoforge"],
+    },
+    CopyRule {
+        path: "config/amecs.json",
+        loaders: &["fabric", "quilt"],
+    },
+    CopyRule {
+        path: "config/modmenu.json",
+        loaders: &["fabric", "quilt"],
+    },
+];
+
+/// What was copied into the new instance, as paths relative to its game dir.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct SettingsCopyReport {
+    pub copied: Vec<String>,
+    /// Present in the source but skipped because of the target's loader.
+    pub filtered: Vec<String>,
+}
+
+fn rule_applies(rule: &CopyRule, target_loader: Option<&str>) -> bool {
+    if rule.loaders.is_empty() {
+        return true;
+    }
+    let Some(loader) = target_loader.map(str::to_ascii_lowercase) else {
+        return false;
+    };
+    rule.loaders.contains(&loader.as_str())
+}
+
+/// Copy settings from `source_dir` into `target_dir`, which must exist.
+pub(crate) fn copy_settings(
+    source_dir: &Path,
+    target_dir: &Path,
+    target_loader: Option<&str>,
+    include_resource_packs: bool,
+) -> Result<SettingsCopyReport, String> {
+    let target_loader = target_loader.filter(|loader| !loader.eq_ignore_ascii_case("vanilla"));
+    let mut report = SettingsCopyReport::default();
+
+    for rule in COPY_RULES {
+        let source = source_dir.join(rule.path);
+        if !source.is_file() {
+            continue;
+        }
+        if !rule_applies(rule, target_loader) {
+            report.filtered.push(rule.path.to_string());
+            continue;
+        }
+        let target = target_dir.join(rule.path);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        std::fs::copy(&source, &target)
+            .map_err(|e| format!("Failed to copy {}: {}", rule.path, e))?;
+        report.copied.push(rule.path.to_string());
+    }
+
+    let packs = source_dir.join(RESOURCE_PACKS_DIR);
+    if include_resource_packs && packs.is_dir() {
+        crate::utils::instance_helpers::copy_directory_recursive(
+            &packs,
+            &target_dir.join(RESOURCE_PACKS_DIR),
+        )?;
+        report.copied.push(format!("{}/", RESOURCE_PACKS_DIR));
+    }
+
+    Ok(report)
+}
+
+/// Copy settings from instance `source_instance_id` into the newly created
+/// `target_instance_id` and record the copy in its history.
+pub(crate) fn copy_from_instance(
+    conn: &mut SqliteConnection,
+    source_instance_id: i32,
+    target_instance_id: i32,
+    target_dir: &Path,
+    target_loader: Option<&str>,
+    include_resource_packs: bool,
+) -> Result<SettingsCopyReport, String> {
+    let source_dir = inst_dsl::instance
+        .find(source_instance_id)
+        .select(inst_dsl::game_directory)
+        .first::<Option<String>>(conn)
+        .map_err(|e| format!("Source instance {} not found: {}", source_instance_id, e))?
+        .ok_or_else(|| format!("Instance {} has no game directory", source_instance_id))?;
+
+    let report = copy_settings(
+        Path::new(&source_dir),
+        target_dir,
+        target_loader,
+        include_resource_packs,
+    )?;
+    crate::instance::history::record_settings_copy(
+        conn,
+        target_instance_id,
+        source_instance_id,
+        &report.copied,
+        &report.filtered,
+    )?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, relative: &str, contents: &str) {
+        let path = dir.join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn missing_source_files_are_skipped() {
+        let source = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+
+        let report = copy_settings(source.path(), target.path(), Some("fabric"), true).unwrap();
+
+        assert_eq!(report, SettingsCopyReport::default());
+        assert!(!target.path().join("options.txt").exists());
+        assert!(!target.path().join(RESOURCE_PACKS_DIR).exists());
+    }
+
+    #[test]
+    fn copy_is_skipped_when_source_dir_does_not_exist() {
+        let target = tempfile::tempdir().unwrap();
+        let missing = target.path().join("deleted-instance");
+
+        let report = copy_settings(&missing, target.path(), None, true).unwrap();
+
+        assert!(report.copied.is_empty());
+    }
+
+    #[test]
+    fn loader_specific_configs_are_filtered() {
+        let source = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        write(
+            source.path(),
+            "options.txt",
+            "key_key.jump:key.keyboard.space",
+        );
+        write(source.path(), "servers.dat", "nbt");
+        write(source.path(), "config/forge-client.toml", "[client]");
+        write(source.path(), "config/controlling-client.toml", "[search]");
+
+        let report = copy_settings(source.path(), target.path(), Some("Fabric"), false).unwrap();
+
+        assert_eq!(report.copied, vec!["options.txt", "servers.dat"]);
+        assert_eq!(
+            report.filtered,
+            vec!["config/forge-client.toml", "config/controlling-client.toml"]
+        );
+        assert!(!target.path().join("config/forge-client.toml").exists());
+        assert_eq!(
+            std::fs::read_to_string(target.path().join("options.txt")).unwrap(),
+            "key_key.jump:key.keyboard.space"
+        );
+    }
+
+    #[test]
+    fn resource_packs_are_copied_only_when_requested() {
+        let source = tempfile::tempdir().unwrap();
+        write(source.path(), "resourcepacks/Faithful.zip", "zip");
+
+        let without = tempfile::tempdir().unwrap();
+        copy_settings(source.path(), without.path(), Some("vanilla"), false).unwrap();
+        assert!(!without.path().join(RESOURCE_PACKS_DIR).exists());
+
+        let with = tempfile::tempdir().unwrap();
+        let report = copy_settings(source.path(), with.path(), Some("vanilla"), true).unwrap();
+        assert_eq!(report.copied, vec!["resourcepacks/"]);
+        assert!(with.path().join("resourcepacks/Faithful.zip").exists());
+    }
+}
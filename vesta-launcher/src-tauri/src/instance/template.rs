@@ -0,0 +1,447 @@
+//! Shareable instance templates.
+//!
+//! A template is a small JSON bundle describing how to rebuild an instance:
+//! Minecraft version, loader, resources as platform/project/version
+//! references, the config files the user picked, JVM settings and the icon.
+//! No jar is ever included; importing resolves each reference again through
+//! the resource sources.
+
+use crate::models::installed_resource::InstalledResource;
+use crate::models::instance::Instance;
+use crate::models::resource::{ResourceType, ResourceVersion, SourcePlatform};
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::Path;
+
+pub const TEMPLATE_FORMAT_VERSION: u32 = 1;
+/// Templates are a few KiB plus config files; anything larger is not one.
+const MAX_TEMPLATE_BYTES: u64 = 16 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstanceTemplate {
+    pub format_version: u32,
+    pub name: String,
+    pub minecraft_version: String,
+    pub modloader: Option<String>,
+    pub modloader_version: Option<String>,
+    #[serde(default)]
+    pub jvm: TemplateJvmSettings,
+    /// Preset icon id, gradient, or a `data:` URL of the custom icon.
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub resources: Vec<TemplateResource>,
+    #[serde(default)]
+    pub config_files: Vec<TemplateConfigFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateJvmSettings {
+    pub java_args: Option<String>,
+    pub use_global_java_args: bool,
+    pub min_memory: i32,
+    pub max_memory: i32,
+}
+
+impl Default for TemplateJvmSettings {
+    fn default() -> Self {
+        let defaults = Instance::default();
+        Self {
+            java_args: defaults.java_args,
+            use_global_java_args: defaults.use_global_java_args,
+            min_memory: defaults.min_memory,
+            max_memory: defaults.max_memory,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateResource {
+    pub platform: SourcePlatform,
+    pub project_id: String,
+    pub version_id: String,
+    pub resource_type: ResourceType,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateConfigFile {
+    /// Path relative to the instance's `config/` folder, `/`-separated.
+    pub path: String,
+    pub content: String,
+}
+
+/// A template reference that could not be installed on import.
+#[derive(Debug, Clone, Serialize)]
+pub struct UnresolvedTemplateResource {
+    pub name: String,
+    pub platform: SourcePlatform,
+    pub project_id: String,
+    pub version_id: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateImportReport {
+    pub instance_id: i32,
+    pub queued_resources: usize,
+    pub unresolved: Vec<UnresolvedTemplateResource>,
+    /// Config files from the template that could not be written.
+    pub config_errors: Vec<String>,
+}
+
+/// Describe `inst` as a template. Manual files and anything not tracked on
+/// Modrinth or CurseForge have no reference to share and are left out.
+pub fn build_template(
+    inst: &Instance,
+    installed: &[InstalledResource],
+    config_files: Vec<TemplateConfigFile>,
+) -> InstanceTemplate {
+    let mut resources: Vec<TemplateResource> = installed
+        .iter()
+        .filter(|res| !res.is_manual && !res.remote_id.is_empty())
+        .filter(|res| !res.remote_version_id.is_empty())
+        .filter_map(|res| {
+            Some(TemplateResource {
+                platform: parse_platform(&res.platform)?,
+                project_id: res.remote_id.clone(),
+                version_id: res.remote_version_id.clone(),
+                resource_type: parse_ledger_type(&res.resource_type)?,
+                name: res.display_name.clone(),
+            })
+        })
+        .collect();
+    resources.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+
+    InstanceTemplate {
+        format_version: TEMPLATE_FORMAT_VERSION,
+        name: inst.name.clone(),
+        minecraft_version: inst.minecraft_version.clone(),
+        modloader: inst.modloader.clone(),
+        modloader_version: inst.modloader_version.clone(),
+        jvm: TemplateJvmSettings {
+            java_args: inst.java_args.clone(),
+            use_global_java_args: inst.use_global_java_args,
+            min_memory: inst.min_memory,
+            max_memory: inst.max_memory,
+        },
+        icon: template_icon(inst),
+        resources,
+        config_files,
+    }
+}
+
+/// New (not yet inserted) instance matching the template's settings.
+pub fn instance_from_template(template: &InstanceTemplate) -> Instance {
+    let defaults = Instance::default();
+    Instance {
+        name: template.name.clone(),
+        minecraft_version: template.minecraft_version.clone(),
+        modloader: template.modloader.clone(),
+        modloader_version: template.modloader_version.clone(),
+        java_args: template.jvm.java_args.clone(),
+        use_global_java_args: template.jvm.use_global_java_args,
+        min_memory: template.jvm.min_memory,
+        max_memory: template.jvm.max_memory,
+        icon_path: template.icon.clone().or(defaults.icon_path.clone()),
+        ..defaults
+    }
+}
+
+pub fn write_template(path: &Path, template: &InstanceTemplate) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(template)
+        .map_err(|e| format!("Failed to serialize template: {}", e))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create template folder: {}", e))?;
+    }
+    std::fs::write(path, json).map_err(|e| format!("Failed to write template: {}", e))
+}
+
+pub fn read_template(path: &Path) -> Result<InstanceTemplate, String> {
+    let size = std::fs::metadata(path)
+        .map_err(|e| format!("Failed to read template: {}", e))?
+        .len();
+    if size > MAX_TEMPLATE_BYTES {
+        return Err("File is too large to be an instance template".to_string());
+    }
+    let raw =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read template: {}", e))?;
+    let template: InstanceTemplate =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid instance template: {}", e))?;
+    if template.format_version > TEMPLATE_FORMAT_VERSION {
+        return Err(format!(
+            "Template format {} needs a newer launcher (supported: {})",
+            template.format_version, TEMPLATE_FORMAT_VERSION
+        ));
+    }
+    Ok(template)
+}
+
+/// Write the template's config files under `config_root`, returning one
+/// message per file that was rejected.
+pub fn apply_config_files(config_root: &Path, files: &[TemplateConfigFile]) -> Vec<String> {
+    if files.is_empty() {
+        return Vec::new();
+    }
+    if let Err(e) = std::fs::create_dir_all(config_root) {
+        return vec![format!("Failed to create config folder: {}", e)];
+    }
+    files
+        .iter()
+        .filter_map(|file| {
+            super::config_files::resolve_config_path(config_root, &file.path)
+                .and_then(|target| super::config_files::write_config_file(&target, &file.content))
+                .err()
+                .map(|e| format!("{}: {}", file.path, e))
+        })
+        .collect()
+}
+
+/// Look up every reference with `resolve`. References that fail are
+/// reported with the reason instead of aborting the import.
+pub async fn resolve_template_resources<F, Fut>(
+    resources: &[TemplateResource],
+    resolve: F,
+) -> (
+    Vec<(TemplateResource, ResourceVersion)>,
+    Vec<UnresolvedTemplateResource>,
+)
+where
+    F: Fn(SourcePlatform, String, String) -> Fut,
+    Fut: Future<Output = anyhow::Result<ResourceVersion>>,
+{
+    let mut resolved = Vec::new();
+    let mut unresolved = Vec::new();
+    for res in resources {
+        match resolve(res.platform, res.project_id.clone(), res.version_id.clone()).await {
+            Ok(version) => resolved.push((res.clone(), version)),
+            Err(e) => unresolved.push(UnresolvedTemplateResource {
+                name: res.name.clone(),
+                platform: res.platform,
+                project_id: res.project_id.clone(),
+                version_id: res.version_id.clone(),
+                reason: e.to_string(),
+            }),
+        }
+    }
+    (resolved, unresolved)
+}
+
+fn template_icon(inst: &Instance) -> Option<String> {
+    if let Some(bytes) = inst.icon_data.as_ref().filter(|b| !b.is_empty()) {
+        return Some(format!(
+            "data:image/png;base64,{}",
+            general_purpose::STANDARD.encode(bytes)
+        ));
+    }
+    inst.icon_path
+        .clone()
+        .filter(|path| !path.starts_with("internal://"))
+}
+
+fn parse_platform(platform: &str) -> Option<SourcePlatform> {
+    match platform {
+        "modrinth" => Some(SourcePlatform::Modrinth),
+        "curseforge" => Some(SourcePlatform::CurseForge),
+        _ => None,
+    }
+}
+
+/// Inverse of [`ResourceType::ledger_name`] for types installed as one file.
+fn parse_ledger_type(name: &str) -> Option<ResourceType> {
+    match name {
+        "mod" => Some(ResourceType::Mod),
+        "resourcepack" => Some(ResourceType::ResourcePack),
+        "shader" => Some(ResourceType::Shader),
+        "datapack" => Some(ResourceType::DataPack),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::resource::ReleaseType;
+
+    fn installed(
+        platform: &str,
+        remote_id: &str,
+        version_id: &str,
+        display_name: &str,
+    ) -> InstalledResource {
+        InstalledResource {
+            id: 0,
+            instance_id: 1,
+            platform: platform.to_string(),
+            remote_id: remote_id.to_string(),
+            remote_version_id: version_id.to_string(),
+            resource_type: "mod".to_string(),
+            local_path: format!("mods/{}.jar", display_name),
+            display_name: display_name.to_string(),
+            current_version: "1.0.0".to_string(),
+            is_manual: false,
+            is_enabled: true,
+            last_updated: "2026-01-01T00:00:00Z".to_string(),
+            release_type: "release".to_string(),
+            hash: Some("abc".to_string()),
+            file_size: 1024,
+            file_mtime: 0,
+            source_kind: "standalone".to_string(),
+            source_modpack_id: None,
+            source_modpack_version_id: None,
+            source_modpack_platform: None,
+        }
+    }
+
+    fn version_for(project_id: &str, version_id: &str) -> ResourceVersion {
+        ResourceVersion {
+            id: version_id.to_string(),
+            project_id: project_id.to_string(),
+            version_number: "1.0.0".to_string(),
+            game_versions: vec!["1.21.1".to_string()],
+            loaders: vec!["fabric".to_string()],
+            download_url: format!("https://cdn.modrinth.com/{}/{}.jar", project_id, version_id),
+            file_name: format!("{}.jar", project_id),
+            release_type: ReleaseType::Release,
+            hash: "abc".to_string(),
+            dependencies: vec![],
+            published_at: None,
+        }
+    }
+
+    fn source_instance() -> Instance {
+        Instance {
+            name: "Friends SMP".to_string(),
+            minecraft_version: "1.21.1".to_string(),
+            modloader: Some("fabric".to_string()),
+            modloader_version: Some("0.16.5".to_string()),
+            java_args: Some("-XX:+UseG1GC".to_string()),
+            use_global_java_args: false,
+            min_memory: 1024,
+            max_memory: 6144,
+            icon_path: Some("internal://icon".to_string()),
+            icon_data: Some(vec![0x89, b'P', b'N', b'G']),
+            ..Instance::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn template_round_trips_two_modrinth_mods() {
+        let mut manual = installed("modrinth", "", "", "local-tweak");
+        manual.is_manual = true;
+        let rows = vec![
+            installed("modrinth", "AANobbMI", "kXJ4b8Rk", "Sodium"),
+            installed("modrinth", "P7dR8mSH", "Ld5u4fZq", "Fabric API"),
+            manual,
+        ];
+        let configs = vec![TemplateConfigFile {
+            path: "sodium-options.json".to_string(),
+            content: r#"{"quality":{"weather_quality":"FAST"}}"#.to_string(),
+        }];
+        let template = build_template(&source_instance(), &rows, configs);
+        assert_eq!(template.resources.len(), 2);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("friends.vesta-template.json");
+        write_template(&path, &template).unwrap();
+        let loaded = read_template(&path).unwrap();
+        assert_eq!(loaded, template);
+        assert!(!std::fs::read_to_string(&path).unwrap().contains(".jar"));
+
+        let inst = instance_from_template(&loaded);
+        assert_eq!(inst.minecraft_version, "1.21.1");
+        assert_eq!(inst.modloader.as_deref(), Some("fabric"));
+        assert_eq!(inst.modloader_version.as_deref(), Some("0.16.5"));
+        assert_eq!(inst.java_args.as_deref(), Some("-XX:+UseG1GC"));
+        assert!(!inst.use_global_java_args);
+        assert_eq!((inst.min_memory, inst.max_memory), (1024, 6144));
+        assert!(inst
+            .icon_path
+            .as_deref()
+            .unwrap()
+            .starts_with("data:image/png;base64,"));
+
+        let (resolved, unresolved) = resolve_template_resources(
+            &loaded.resources,
+            |platform, project, version| async move {
+                assert_eq!(platform, SourcePlatform::Modrinth);
+                Ok(version_for(&project, &version))
+            },
+        )
+        .await;
+        assert!(unresolved.is_empty());
+        let refs: Vec<_> = resolved
+            .iter()
+            .map(|(res, version)| {
+                (
+                    res.name.as_str(),
+                    version.project_id.as_str(),
+                    version.id.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            refs,
+            vec![
+                ("Fabric API", "P7dR8mSH", "Ld5u4fZq"),
+                ("Sodium", "AANobbMI", "kXJ4b8Rk"),
+            ]
+        );
+
+        let config_root = dir.path().join("instance").join("config");
+        assert!(apply_config_files(&config_root, &loaded.config_files).is_empty());
+        assert_eq!(
+            std::fs::read_to_string(config_root.join("sodium-options.json")).unwrap(),
+            loaded.config_files[0].content
+        );
+    }
+
+    #[tokio::test]
+    async fn unresolvable_references_are_reported_not_fatal() {
+        let template = build_template(
+            &source_instance(),
+            &[
+                installed("modrinth", "AANobbMI", "kXJ4b8Rk", "Sodium"),
+                installed("curseforge", "238222", "5101366", "JEI"),
+            ],
+            vec![],
+        );
+
+        let (resolved, unresolved) = resolve_template_resources(
+            &template.resources,
+            |platform, project, version| async move {
+                match platform {
+                    SourcePlatform::Modrinth => Ok(version_for(&project, &version)),
+                    SourcePlatform::CurseForge => Err(anyhow::anyhow!("version was deleted")),
+                }
+            },
+        )
+        .await;
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].name, "JEI");
+        assert_eq!(unresolved[0].reason, "version was deleted");
+    }
+
+    #[test]
+    fn rejects_newer_template_format_and_unsafe_config_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("future.json");
+        let mut template = build_template(&source_instance(), &[], vec![]);
+        template.format_version = TEMPLATE_FORMAT_VERSION + 1;
+        write_template(&path, &template).unwrap();
+        assert!(read_template(&path).unwrap_err().contains("newer launcher"));
+
+        let errors = apply_config_files(
+            &dir.path().join("config"),
+            &[TemplateConfigFile {
+                path: "../options.txt".to_string(),
+                content: "fov:1.0".to_string(),
+            }],
+        );
+        assert_eq!(errors.len(), 1);
+        assert!(!dir.path().join("options.txt").exists());
+    }
+}
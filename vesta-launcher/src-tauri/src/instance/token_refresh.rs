@@ -0,0 +1,93 @@
+//! Keeps `<game_dir>/.vesta/current_token.json` fresh while a game runs.
+//!
+//! See [`piston_lib::game::launcher::token_file`] for the file format. The
+//! token is revalidated every [`TOKEN_REFRESH_INTERVAL`] through the same
+//! path used at launch, and the file is removed once the process exits.
+//! Offline and guest launches never get a file.
+
+use crate::models::account::Account;
+use diesel::prelude::*;
+use piston_lib::game::launcher::{
+    remove_token_file, write_token_file, CurrentToken, TOKEN_REFRESH_INTERVAL,
+};
+use std::path::{Path, PathBuf};
+
+fn load_account(account_uuid: &str) -> Result<Option<Account>, String> {
+    use crate::schema::account::dsl::*;
+
+    let mut conn = crate::utils::db::get_vesta_conn().map_err(|e| e.to_string())?;
+    account
+        .filter(uuid.eq(account_uuid.replace('-', "")))
+        .first::<Account>(&mut conn)
+        .optional()
+        .map_err(|e| e.to_string())
+}
+
+/// Write the account's stored token. Returns false when it has none.
+fn write_current_token(game_dir: &Path, account_uuid: &str) -> Result<bool, String> {
+    let Some(acct) = load_account(account_uuid)? else {
+        return Ok(false);
+    };
+    if acct.account_type == crate::auth::ACCOUNT_TYPE_GUEST {
+        return Ok(false);
+    }
+    let Some(access_token) = acct.access_token.as_deref() else {
+        return Ok(false);
+    };
+    let token = CurrentToken::new(
+        &acct.uuid,
+        &acct.username,
+        access_token,
+        acct.token_expires_at.as_deref(),
+    );
+    write_token_file(game_dir, &token).map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+/// Write the token file for the game `pid` launched as `account_uuid` and
+/// refresh it until the process exits.
+pub(crate) fn spawn_token_refresher(
+    app_handle: tauri::AppHandle,
+    game_dir: PathBuf,
+    pid: u32,
+    account_uuid: String,
+) {
+    tokio::spawn(async move {
+        match write_current_token(&game_dir, &account_uuid) {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(e) => {
+                log::warn!("[token_refresh] Failed to write token file: {}", e);
+                return;
+            }
+        }
+
+        let mut next_refresh = tokio::time::Instant::now() + TOKEN_REFRESH_INTERVAL;
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_secs(15)).await;
+            if !super::lifecycle::is_pid_running(pid) {
+                break;
+            }
+            if tokio::time::Instant::now() < next_refresh {
+                continue;
+            }
+            next_refresh += TOKEN_REFRESH_INTERVAL;
+
+            // A failed refresh keeps the previous token; it may still be valid.
+            if let Err(e) =
+                crate::auth::ensure_account_tokens_valid(app_handle.clone(), account_uuid.clone())
+                    .await
+            {
+                log::warn!("[token_refresh] Token refresh failed: {}", e);
+                continue;
+            }
+            if let Err(e) = write_current_token(&game_dir, &account_uuid) {
+                log::warn!("[token_refresh] Failed to update token file: {}", e);
+            }
+        }
+
+        if let Err(e) = remove_token_file(&game_dir) {
+            log::warn!("[token_refresh] Failed to remove token file: {}", e);
+        }
+    });
+}
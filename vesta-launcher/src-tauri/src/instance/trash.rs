@@ -0,0 +1,644 @@
+//! Soft delete for instances.
+//!
+//! A trashed instance keeps its database row with `deleted_at` set and has its
+//! game directory parked under `<instances root>/.trash/<slug>`. The original
+//! directory is kept in `trashed_from_directory`, and restoring moves the files
+//! back there. When the name, slug or folder was reused in the meantime the
+//! instance is restored under a fresh name and into the sibling folder of its new
+//! slug; purging performs the real removal.
+
+use crate::models::instance::Instance;
+use crate::utils::instance_helpers::{
+    compute_unique_name, compute_unique_slug, copy_directory_recursive, remap_path_under_root,
+};
+use crate::utils::sanitize::{resolve_instance_slug, sanitize_instance_name};
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+pub const TRASH_DIR_NAME: &str = ".trash";
+pub const DEFAULT_TRASH_RETENTION_DAYS: i32 = 30;
+
+/// Upper bound on name suffixes tried before falling back to a slug-only suffix.
+const MAX_RESTORE_NAME_ATTEMPTS: usize = 1000;
+
+/// Directory that holds the game folders of trashed instances.
+pub fn trash_root(instances_root: &Path) -> PathBuf {
+    instances_root.join(TRASH_DIR_NAME)
+}
+
+/// Whether a trashed instance deleted at `deleted_at` is at least `older_than_days` old.
+/// Unparseable timestamps are never considered expired so a bad row cannot trigger a purge.
+pub fn is_past_retention(deleted_at: &str, now: DateTime<Utc>, older_than_days: i64) -> bool {
+    match DateTime::parse_from_rfc3339(deleted_at) {
+        Ok(ts) => {
+            now.signed_duration_since(ts.with_timezone(&Utc))
+                >= chrono::Duration::days(older_than_days)
+        }
+        Err(e) => {
+            log::warn!(
+                "[trash] Ignoring unparseable deleted_at '{}': {}",
+                deleted_at,
+                e
+            );
+            false
+        }
+    }
+}
+
+/// Name and slug a trashed instance is restored under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RestoreIdentity {
+    pub name: String,
+    pub slug: String,
+}
+
+/// Pick a name whose slug is free among live instances and on disk.
+///
+/// The instance keeps `original_slug` while its original name is still free; a renamed
+/// restore gets the slug of its new name. Collisions are resolved by renaming so the
+/// runtime id keeps following the name.
+pub fn compute_restore_identity(
+    original_name: &str,
+    original_slug: &str,
+    live_names_lowercase: &HashSet<String>,
+    live_slugs: &HashSet<String>,
+    instances_root: &Path,
+) -> RestoreIdentity {
+    let mut taken_names = live_names_lowercase.clone();
+    for _ in 0..MAX_RESTORE_NAME_ATTEMPTS {
+        let candidate = compute_unique_name(original_name, &taken_names);
+        let slug = if candidate == original_name {
+            original_slug.to_string()
+        } else {
+            sanitize_instance_name(&candidate)
+        };
+        if !live_slugs.contains(&slug) && !instances_root.join(&slug).exists() {
+            return RestoreIdentity {
+                name: candidate,
+                slug,
+            };
+        }
+        taken_names.insert(candidate.to_lowercase());
+    }
+
+    // Extremely long names truncate to the same slug; keep the name and suffix the folder.
+    RestoreIdentity {
+        name: compute_unique_name(original_name, live_names_lowercase),
+        slug: compute_unique_slug(original_name, live_slugs, instances_root),
+    }
+}
+
+/// Move a directory, falling back to copy + remove when a rename crosses devices.
+pub fn move_directory(src: &Path, dest: &Path) -> Result<(), String> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create '{}': {}", parent.display(), e))?;
+    }
+
+    match std::fs::rename(src, dest) {
+        Ok(()) => Ok(()),
+        Err(rename_err) => {
+            log::warn!(
+                "[trash] rename '{}' -> '{}' failed ({}), falling back to copy",
+                src.display(),
+                dest.display(),
+                rename_err
+            );
+            copy_directory_recursive(src, dest)?;
+            std::fs::remove_dir_all(src)
+                .map_err(|e| format!("Failed to remove '{}' after copy: {}", src.display(), e))
+        }
+    }
+}
+
+/// Rewrite `installed_resource.local_path` rows after an instance folder moved.
+fn remap_resource_paths(
+    conn: &mut SqliteConnection,
+    target_instance_id: i32,
+    from_root: &Path,
+    to_root: &Path,
+) -> Result<(), diesel::result::Error> {
+    use crate::schema::installed_resource::dsl::*;
+
+    let rows: Vec<(i32, String)> = installed_resource
+        .filter(instance_id.eq(target_instance_id))
+        .select((id, local_path))
+        .load(conn)?;
+
+    for (row_id, path) in rows {
+        let remapped = remap_path_under_root(&path, from_root, to_root);
+        if remapped != path {
+            diesel::update(installed_resource.find(row_id))
+                .set(local_path.eq(remapped))
+                .execute(conn)?;
+        }
+    }
+    Ok(())
+}
+
+/// Mark an instance as deleted and park its game directory in the trash.
+pub fn move_to_trash(
+    conn: &mut SqliteConnection,
+    inst: &Instance,
+    instances_root: &Path,
+) -> Result<Option<PathBuf>, String> {
+    use crate::schema::instance::dsl::*;
+
+    let now = Some(Utc::now().to_rfc3339());
+    let source_dir = inst
+        .game_directory
+        .as_deref()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .filter(|dir| dir.exists());
+
+    let Some(source_dir) = source_dir else {
+        diesel::update(instance.find(inst.id))
+            .set((deleted_at.eq(&now), updated_at.eq(&now)))
+            .execute(conn)
+            .map_err(|e| format!("Failed to mark instance as deleted: {}", e))?;
+        return Ok(None);
+    };
+
+    let trash = trash_root(instances_root);
+    let trash_slug = compute_unique_slug(&inst.name, &HashSet::new(), &trash);
+    let trash_dir = trash.join(&trash_slug);
+    move_directory(&source_dir, &trash_dir)?;
+
+    let trash_dir_str = Some(trash_dir.to_string_lossy().to_string());
+    let source_dir_str = Some(source_dir.to_string_lossy().to_string());
+    let result = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        remap_resource_paths(conn, inst.id, &source_dir, &trash_dir)?;
+        diesel::update(instance.find(inst.id))
+            .set((
+                deleted_at.eq(&now),
+                game_directory.eq(&trash_dir_str),
+                trashed_from_directory.eq(&source_dir_str),
+                updated_at.eq(&now),
+            ))
+            .execute(conn)?;
+        Ok(())
+    });
+
+    if let Err(e) = result {
+        // Put the files back so the live row still points at real data.
+        if let Err(rollback) = move_directory(&trash_dir, &source_dir) {
+            log::error!(
+                "[trash] Failed to roll back trash move for instance {}: {}",
+                inst.id,
+                rollback
+            );
+        }
+        return Err(format!("Failed to mark instance as deleted: {}", e));
+    }
+
+    Ok(Some(trash_dir))
+}
+
+/// Bring a trashed instance back to its original game directory, renaming it if its
+/// name, slug or folder was taken in the meantime. A renamed instance goes to the
+/// folder of its new slug next to the original one. Instances trashed before the
+/// original directory was recorded go back to `<instances root>/<slug>`.
+pub fn restore_from_trash(
+    conn: &mut SqliteConnection,
+    inst: &Instance,
+    instances_root: &Path,
+) -> Result<Instance, String> {
+    use crate::schema::instance::dsl::*;
+
+    if !inst.is_trashed() {
+        return Err(format!("Instance '{}' is not in the trash", inst.name));
+    }
+
+    let live: Vec<(String, Option<String>)> = instance
+        .filter(deleted_at.is_null())
+        .select((name, slug))
+        .load(conn)
+        .map_err(|e| format!("Failed to query existing instances: {}", e))?;
+    let live_names: HashSet<String> = live.iter().map(|(n, _)| n.to_lowercase()).collect();
+    let live_slugs: HashSet<String> = live
+        .iter()
+        .map(|(n, s)| resolve_instance_slug(n, s.as_deref()))
+        .collect();
+
+    let original_dir = inst
+        .trashed_from_directory
+        .as_deref()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from);
+    let restore_root = original_dir
+        .as_deref()
+        .and_then(Path::parent)
+        .unwrap_or(instances_root)
+        .to_path_buf();
+    let identity = compute_restore_identity(
+        &inst.name,
+        &inst.slug(),
+        &live_names,
+        &live_slugs,
+        &restore_root,
+    );
+    // The original folder is only reused when the instance keeps its slug; a renamed
+    // restore always lands in the folder of its new slug so the two cannot diverge.
+    let restored_dir = original_dir
+        .filter(|dir| identity.slug == inst.slug() && !dir.exists())
+        .unwrap_or_else(|| restore_root.join(&identity.slug));
+    let trash_dir = inst
+        .game_directory
+        .as_deref()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .filter(|dir| dir.exists());
+
+    match &trash_dir {
+        Some(dir) => move_directory(dir, &restored_dir)?,
+        None => std::fs::create_dir_all(&restored_dir).map_err(|e| {
+            format!(
+                "Failed to create instance directory '{}': {}",
+                restored_dir.display(),
+                e
+            )
+        })?,
+    }
+
+    let now = Some(Utc::now().to_rfc3339());
+    let restored_dir_str = Some(restored_dir.to_string_lossy().to_string());
+    let result = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        if let Some(dir) = &trash_dir {
+            remap_resource_paths(conn, inst.id, dir, &restored_dir)?;
+        }
+        diesel::update(instance.find(inst.id))
+            .set((
+                deleted_at.eq(None::<String>),
+                name.eq(&identity.name),
+                slug.eq(Some(&identity.slug)),
+                game_directory.eq(&restored_dir_str),
+                trashed_from_directory.eq(None::<String>),
+                updated_at.eq(&now),
+            ))
+            .execute(conn)?;
+        instance.find(inst.id).first::<Instance>(conn)
+    });
+
+    match result {
+        Ok(restored) => Ok(restored),
+        Err(e) => {
+            if let Some(dir) = &trash_dir {
+                if let Err(rollback) = move_directory(&restored_dir, dir) {
+                    log::error!(
+                        "[trash] Failed to roll back restore for instance {}: {}",
+                        inst.id,
+                        rollback
+                    );
+                }
+            }
+            Err(format!("Failed to restore instance: {}", e))
+        }
+    }
+}
+
+/// Permanently remove an instance row, every row keyed by its id and its files.
+pub fn purge_instance(conn: &mut SqliteConnection, inst: &Instance) -> Result<(), String> {
+    if let Some(gd) = inst.game_directory.as_deref().filter(|dir| !dir.is_empty()) {
+        let gd_path = Path::new(gd);
+        if gd_path.exists() {
+            std::fs::remove_dir_all(gd_path).map_err(|e| {
+                format!(
+                    "Failed to remove instance files at '{}': {}",
+                    gd_path.display(),
+                    e
+                )
+            })?;
+        }
+    }
+
+    // Most of these tables cascade, but not every connection turns foreign keys on
+    // and `event_log` has no constraint at all, so delete explicitly.
+    conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        use crate::schema::{
+            event_log, installed_resource, instance, instance_operation,
+            instance_resource_update_check, play_session,
+        };
+
+        diesel::delete(
+            installed_resource::table.filter(installed_resource::instance_id.eq(inst.id)),
+        )
+        .execute(conn)?;
+        diesel::delete(
+            instance_operation::table.filter(instance_operation::instance_id.eq(inst.id)),
+        )
+        .execute(conn)?;
+        diesel::delete(
+            instance_resource_update_check::table
+                .filter(instance_resource_update_check::instance_id.eq(inst.id)),
+        )
+        .execute(conn)?;
+        diesel::delete(play_session::table.filter(play_session::instance_id.eq(inst.id)))
+            .execute(conn)?;
+        diesel::delete(event_log::table.filter(event_log::instance_id.eq(inst.id)))
+            .execute(conn)?;
+        diesel::delete(instance::table.find(inst.id)).execute(conn)?;
+        Ok(())
+    })
+    .map_err(|e| format!("Failed to delete instance from database: {}", e))
+}
+
+/// Purge every trashed instance deleted at least `older_than_days` ago.
+/// Returns the ids that were removed.
+pub fn purge_expired(
+    conn: &mut SqliteConnection,
+    older_than_days: i64,
+) -> Result<Vec<i32>, String> {
+    use crate::schema::instance::dsl::*;
+
+    let trashed = instance
+        .filter(deleted_at.is_not_null())
+        .load::<Instance>(conn)
+        .map_err(|e| format!("Failed to query deleted instances: {}", e))?;
+
+    let now = Utc::now();
+    let mut purged = Vec::new();
+    for inst in trashed {
+        let Some(ts) = inst.deleted_at.as_deref() else {
+            continue;
+        };
+        if !is_past_retention(ts, now, older_than_days) {
+            continue;
+        }
+        match purge_instance(conn, &inst) {
+            Ok(()) => {
+                log::info!("[trash] Purged instance {} ({})", inst.id, inst.name);
+                purged.push(inst.id);
+            }
+            Err(e) => log::warn!("[trash] Failed to purge instance {}: {}", inst.id, e),
+        }
+    }
+    Ok(purged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::connection::SimpleConnection;
+    use diesel_migrations::MigrationHarness;
+
+    fn conn() -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.run_pending_migrations(crate::utils::db::VESTA_MIGRATIONS)
+            .unwrap();
+        conn
+    }
+
+    /// Insert a live instance whose game directory holds a mod tracked in `installed_resource`.
+    fn insert_instance(
+        conn: &mut SqliteConnection,
+        id: i32,
+        inst_name: &str,
+        dir: &Path,
+    ) -> Instance {
+        std::fs::create_dir_all(dir.join("mods")).unwrap();
+        std::fs::write(dir.join("mods").join("sodium.jar"), b"jar").unwrap();
+        conn.batch_execute(&format!(
+            "INSERT INTO instance (id, name, minecraft_version, game_directory)
+                VALUES ({id}, '{inst_name}', '1.21.1', '{dir}');
+             INSERT INTO installed_resource (instance_id, platform, remote_id, remote_version_id,
+                resource_type, local_path, display_name, current_version)
+                VALUES ({id}, 'modrinth', 'AANobbMI', 'v1', 'mod', '{jar}', 'Sodium', '0.6.0');",
+            dir = dir.display(),
+            jar = dir.join("mods").join("sodium.jar").display(),
+        ))
+        .unwrap();
+        load(conn, id)
+    }
+
+    fn load(conn: &mut SqliteConnection, id: i32) -> Instance {
+        crate::schema::instance::table
+            .find(id)
+            .first::<Instance>(conn)
+            .unwrap()
+    }
+
+    fn resource_path(conn: &mut SqliteConnection, inst_id: i32) -> String {
+        use crate::schema::installed_resource::dsl::*;
+        installed_resource
+            .filter(instance_id.eq(inst_id))
+            .select(local_path)
+            .first(conn)
+            .unwrap()
+    }
+
+    fn names(list: &[&str]) -> (HashSet<String>, HashSet<String>) {
+        (
+            list.iter().map(|n| n.to_lowercase()).collect(),
+            list.iter().map(|n| sanitize_instance_name(n)).collect(),
+        )
+    }
+
+    #[test]
+    fn restore_keeps_original_identity_when_free() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let (live_names, live_slugs) = names(&["Other Pack"]);
+
+        let identity =
+            compute_restore_identity("My World", "my-world", &live_names, &live_slugs, tmp.path());
+
+        assert_eq!(identity.name, "My World");
+        assert_eq!(identity.slug, "my-world");
+    }
+
+    #[test]
+    fn restore_keeps_stored_slug_of_unchanged_name() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let (live_names, live_slugs) = names(&["Other Pack"]);
+
+        // Rows created before slugs were stored resolve to the legacy mapping.
+        let identity = compute_restore_identity(
+            "Привет мир",
+            "instance",
+            &live_names,
+            &live_slugs,
+            tmp.path(),
+        );
+
+        assert_eq!(identity.name, "Привет мир");
+        assert_eq!(identity.slug, "instance");
+    }
+
+    #[test]
+    fn restore_after_original_slug_was_taken_by_new_instance() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir_all(tmp.path().join("my-world")).expect("live dir");
+        let (live_names, live_slugs) = names(&["My World"]);
+
+        let identity =
+            compute_restore_identity("My World", "my-world", &live_names, &live_slugs, tmp.path());
+
+        assert_eq!(identity.name, "My World (2)");
+        assert_eq!(identity.slug, "my-world-2");
+        assert_eq!(sanitize_instance_name(&identity.name), identity.slug);
+    }
+
+    #[test]
+    fn trash_and_restore_custom_game_directory() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let instances_root = tmp.path().join("instances");
+        let custom_dir = tmp.path().join("elsewhere").join("Imported Pack");
+        let mut conn = conn();
+        let inst = insert_instance(&mut conn, 1, "Imported Pack", &custom_dir);
+
+        let trash_dir = move_to_trash(&mut conn, &inst, &instances_root)
+            .unwrap()
+            .expect("directory moved");
+        assert!(!custom_dir.exists());
+        assert!(trash_dir.starts_with(trash_root(&instances_root)));
+        let trashed = load(&mut conn, 1);
+        assert!(trashed.is_trashed());
+        assert!(Path::new(&resource_path(&mut conn, 1)).starts_with(&trash_dir));
+
+        let restored = restore_from_trash(&mut conn, &trashed, &instances_root).unwrap();
+        assert!(!restored.is_trashed());
+        assert_eq!(restored.name, "Imported Pack");
+        assert_eq!(restored.game_directory.as_deref(), custom_dir.to_str());
+        assert_eq!(restored.trashed_from_directory, None);
+        assert!(custom_dir.join("mods").join("sodium.jar").is_file());
+        assert!(!trash_dir.exists());
+        assert_eq!(
+            resource_path(&mut conn, 1),
+            custom_dir.join("mods").join("sodium.jar").to_string_lossy()
+        );
+    }
+
+    #[test]
+    fn trash_and_restore_after_slug_and_directory_were_reused() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let instances_root = tmp.path().join("instances");
+        let original_dir = instances_root.join("my-world");
+        let mut conn = conn();
+        let inst = insert_instance(&mut conn, 1, "My World", &original_dir);
+        move_to_trash(&mut conn, &inst, &instances_root).unwrap();
+
+        // A new instance takes the name and folder while the old one is in the trash.
+        insert_instance(&mut conn, 2, "My World", &original_dir);
+
+        let trashed = load(&mut conn, 1);
+        let restored = restore_from_trash(&mut conn, &trashed, &instances_root).unwrap();
+        let restored_dir = instances_root.join("my-world-2");
+        assert_eq!(restored.name, "My World (2)");
+        assert_eq!(restored.slug(), "my-world-2");
+        assert_eq!(restored.game_directory.as_deref(), restored_dir.to_str());
+        assert!(restored_dir.join("mods").join("sodium.jar").is_file());
+        assert!(original_dir.join("mods").join("sodium.jar").is_file());
+        assert_eq!(load(&mut conn, 2).name, "My World");
+    }
+
+    #[test]
+    fn purge_removes_every_row_keyed_by_instance() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let mut conn = conn();
+        let inst = insert_instance(&mut conn, 1, "Doomed", &tmp.path().join("doomed"));
+        insert_instance(&mut conn, 2, "Kept", &tmp.path().join("kept"));
+        conn.batch_execute(
+            "INSERT INTO play_session (instance_id, started_at, ended_at)
+                VALUES (1, '2026-10-01T00:00:00Z', '2026-10-01T01:00:00Z');
+             INSERT INTO event_log (kind, instance_id, created_at)
+                VALUES ('launch', 1, '2026-10-01T00:00:00Z'), ('launch', 2, '2026-10-01T00:00:00Z');
+             INSERT INTO instance_resource_update_check
+                (instance_id, checked_at, results_json, instance_fingerprint)
+                VALUES (1, '2026-10-01T00:00:00Z', '[]', 'fp');",
+        )
+        .unwrap();
+
+        purge_instance(&mut conn, &inst).unwrap();
+
+        assert!(!tmp.path().join("doomed").exists());
+        let count = |conn: &mut SqliteConnection, table: &str| -> i64 {
+            #[derive(QueryableByName)]
+            struct Count {
+                #[diesel(sql_type = diesel::sql_types::BigInt)]
+                n: i64,
+            }
+            diesel::sql_query(format!(
+                "SELECT COUNT(*) AS n FROM {} WHERE instance_id = 1",
+                table
+            ))
+            .get_result::<Count>(conn)
+            .unwrap()
+            .n
+        };
+        for table in [
+            "installed_resource",
+            "instance_operation",
+            "instance_resource_update_check",
+            "play_session",
+            "event_log",
+        ] {
+            assert_eq!(count(&mut conn, table), 0, "{}", table);
+        }
+        assert_eq!(load(&mut conn, 2).name, "Kept");
+        assert!(!resource_path(&mut conn, 2).is_empty());
+    }
+
+    #[test]
+    fn restored_directory_follows_the_new_slug_when_name_and_folder_are_taken() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let instances_root = tmp.path().join("instances");
+        let original_dir = instances_root.join("pack");
+        let mut conn = conn();
+        let inst = insert_instance(&mut conn, 1, "Pack", &original_dir);
+        move_to_trash(&mut conn, &inst, &instances_root).unwrap();
+
+        // Meanwhile a new "Pack" takes the name and folder, and "Pack (2)" lives in a
+        // custom directory, so its slug is taken while `pack-2/` stays free on disk.
+        insert_instance(&mut conn, 2, "Pack", &original_dir);
+        insert_instance(&mut conn, 3, "Pack (2)", &tmp.path().join("custom"));
+        assert!(!instances_root.join("pack-2").exists());
+
+        let trashed = load(&mut conn, 1);
+        let restored = restore_from_trash(&mut conn, &trashed, &instances_root).unwrap();
+        let restored_dir = instances_root.join(restored.slug());
+        assert_eq!(restored.name, "Pack (3)");
+        assert_eq!(restored.slug(), "pack-3");
+        assert_eq!(restored.game_directory.as_deref(), restored_dir.to_str());
+        assert!(restored_dir.join("mods").join("sodium.jar").is_file());
+        assert!(!instances_root.join("pack-2").exists());
+    }
+
+    #[test]
+    fn restore_skips_slug_taken_under_a_different_name() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        // No live row owns the name, but a leftover folder still blocks the slug.
+        std::fs::create_dir_all(tmp.path().join("my-world")).expect("stray dir");
+        let (live_names, live_slugs) = names(&[]);
+
+        let identity =
+            compute_restore_identity("My World", "my-world", &live_names, &live_slugs, tmp.path());
+
+        assert_eq!(identity.slug, "my-world-2");
+    }
+
+    #[test]
+    fn move_directory_relocates_contents() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let src = tmp.path().join("my-world");
+        std::fs::create_dir_all(src.join("saves")).expect("src");
+        std::fs::write(src.join("saves").join("level.dat"), b"data").expect("file");
+
+        let dest = trash_root(tmp.path()).join("my-world");
+        move_directory(&src, &dest).expect("move");
+
+        assert!(!src.exists());
+        assert!(dest.join("saves").join("level.dat").is_file());
+    }
+
+    #[test]
+    fn retention_window_is_inclusive() {
+        let now = Utc::now();
+        let ten_days_ago = (now - chrono::Duration::days(10)).to_rfc3339();
+        assert!(is_past_retention(&ten_days_ago, now, 10));
+        assert!(!is_past_retention(&ten_days_ago, now, 11));
+        assert!(!is_past_retention("not a timestamp", now, 0));
+    }
+}
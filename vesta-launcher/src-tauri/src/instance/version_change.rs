@@ -0,0 +1,354 @@
+//! Planning a Minecraft / loader version change for an existing instance.
+//!
+//! A change is validated against the piston metadata, then installed like a
+//! fresh version; the instance row only switches once that install succeeds
+//! (see `InstallInstanceTask::set_version_change`). The plan returned to the
+//! UI lists the mods the update checker finds no build of for the target.
+
+use futures::stream::{self, StreamExt};
+use piston_lib::game::metadata::{GameVersionMetadata, ModloaderType};
+use serde::Serialize;
+
+use crate::models::installed_resource::InstalledResource;
+use crate::resources::sources::custom::SourceOverride;
+use crate::resources::ResourceManager;
+use crate::utils::instance_runtime::InstanceRuntimeFields;
+
+const MAX_CONCURRENT_MOD_CHECKS: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IncompatibleMod {
+    pub resource_id: i32,
+    pub display_name: String,
+    pub current_version: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionChangePlan {
+    pub instance_id: i32,
+    pub from: InstanceRuntimeFields,
+    pub to: InstanceRuntimeFields,
+    pub downgrade: bool,
+    /// Mods with no build for the target version and loader.
+    pub incompatible_mods: Vec<IncompatibleMod>,
+    /// Manual mods and mods whose versions could not be fetched.
+    pub unchecked_mods: usize,
+    pub warnings: Vec<String>,
+    /// Whether the install of the target version was queued.
+    pub queued: bool,
+}
+
+fn loader_type(loader: Option<&str>) -> Result<Option<ModloaderType>, String> {
+    match loader.map(str::to_lowercase).as_deref() {
+        None | Some("") | Some("vanilla") => Ok(None),
+        Some(value) => value
+            .parse::<ModloaderType>()
+            .map(Some)
+            .map_err(|_| format!("Unknown modloader: {}", value)),
+    }
+}
+
+/// Resolve the requested target against the metadata. A modded target
+/// without a loader version gets the newest stable loader for the game
+/// version, so the row always records what was installed.
+pub fn resolve_target(
+    game_versions: &[GameVersionMetadata],
+    minecraft_version: &str,
+    modloader: Option<&str>,
+    modloader_version: Option<&str>,
+) -> Result<InstanceRuntimeFields, String> {
+    let game_version = game_versions
+        .iter()
+        .find(|version| version.id == minecraft_version)
+        .ok_or_else(|| format!("Unknown Minecraft version: {}", minecraft_version))?;
+    let modloader_version = modloader_version.filter(|version| !version.is_empty());
+
+    let Some(loader) = loader_type(modloader)? else {
+        if let Some(version) = modloader_version {
+            return Err(format!(
+                "Vanilla instances have no loader version (got {})",
+                version
+            ));
+        }
+        return Ok(InstanceRuntimeFields {
+            minecraft_version: minecraft_version.to_string(),
+            modloader: Some(ModloaderType::Vanilla.as_str().to_string()),
+            modloader_version: None,
+        });
+    };
+
+    let available = game_version
+        .loaders
+        .get(&loader)
+        .filter(|versions| !versions.is_empty())
+        .ok_or_else(|| {
+            format!(
+                "{} is not available for Minecraft {}",
+                loader.as_str(),
+                minecraft_version
+            )
+        })?;
+    let modloader_version = match modloader_version {
+        Some(version) => available
+            .iter()
+            .find(|info| info.version == version)
+            .map(|info| info.version.clone())
+            .ok_or_else(|| {
+                format!(
+                    "{} {} does not support Minecraft {}",
+                    loader.as_str(),
+                    version,
+                    minecraft_version
+                )
+            })?,
+        None => available
+            .iter()
+            .find(|info| info.stable)
+            .unwrap_or(&available[0])
+            .version
+            .clone(),
+    };
+
+    Ok(InstanceRuntimeFields {
+        minecraft_version: minecraft_version.to_string(),
+        modloader: Some(loader.as_str().to_string()),
+        modloader_version: Some(modloader_version),
+    })
+}
+
+/// True when `to` was released before `from`. Unknown versions never count
+/// as a downgrade.
+pub fn is_downgrade(game_versions: &[GameVersionMetadata], from: &str, to: &str) -> bool {
+    let release_time = |id: &str| {
+        game_versions
+            .iter()
+            .find(|version| version.id == id)
+            .map(|version| version.release_time)
+    };
+    match (release_time(from), release_time(to)) {
+        (Some(from), Some(to)) => to < from,
+        _ => false,
+    }
+}
+
+fn warnings_for(plan: &VersionChangePlan) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if plan.downgrade {
+        warnings.push(format!(
+            "Minecraft {} is older than {}. Worlds opened in {} may fail to load or lose data; back them up first.",
+            plan.to.minecraft_version, plan.from.minecraft_version, plan.from.minecraft_version
+        ));
+    }
+    if !plan.incompatible_mods.is_empty() {
+        let names: Vec<&str> = plan
+            .incompatible_mods
+            .iter()
+            .map(|entry| entry.display_name.as_str())
+            .collect();
+        warnings.push(format!(
+            "{} mod(s) have no version for Minecraft {}: {}",
+            names.len(),
+            plan.to.minecraft_version,
+            names.join(", ")
+        ));
+    }
+    if plan.unchecked_mods > 0 {
+        warnings.push(format!(
+            "{} mod(s) could not be checked for compatibility",
+            plan.unchecked_mods
+        ));
+    }
+    warnings
+}
+
+/// Check each installed mod against the target using the update checker's
+/// compatibility rule: a mod is incompatible when no version of it matches
+/// the target game version and loader.
+async fn check_mods(
+    resource_manager: &ResourceManager,
+    resources: Vec<InstalledResource>,
+    target: &InstanceRuntimeFields,
+    source_override: Option<&SourceOverride>,
+) -> (Vec<IncompatibleMod>, usize) {
+    let loader = target
+        .modloader
+        .clone()
+        .unwrap_or_else(|| ModloaderType::Vanilla.as_str().to_string());
+
+    let results = stream::iter(
+        resources
+            .into_iter()
+            .filter(|res| res.resource_type == "mod"),
+    )
+    .map(|res| {
+        let loader = loader.clone();
+        async move {
+            if res.is_manual {
+                return None;
+            }
+            let platform = crate::commands::resources::source_platform_from_str(&res.platform)?;
+            let versions = resource_manager
+                .get_versions_with_override(platform, &res.remote_id, false, source_override)
+                .await
+                .ok()?;
+            let compatible = crate::resources::update_policy::find_best_update(
+                &versions,
+                &res,
+                &target.minecraft_version,
+                &loader,
+            )
+            .is_some();
+            Some((!compatible).then(|| IncompatibleMod {
+                resource_id: res.id,
+                display_name: res.display_name.clone(),
+                current_version: res.current_version.clone(),
+            }))
+        }
+    })
+    .buffer_unordered(MAX_CONCURRENT_MOD_CHECKS)
+    .collect::<Vec<_>>()
+    .await;
+
+    let mut incompatible = Vec::new();
+    let mut unchecked = 0;
+    for result in results {
+        match result {
+            Some(Some(entry)) => incompatible.push(entry),
+            Some(None) => {}
+            None => unchecked += 1,
+        }
+    }
+    incompatible.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+    (incompatible, unchecked)
+}
+
+/// Build the dry-run plan for moving `instance_id` from `from` to `to`.
+pub async fn plan(
+    resource_manager: &ResourceManager,
+    game_versions: &[GameVersionMetadata],
+    instance_id: i32,
+    from: InstanceRuntimeFields,
+    to: InstanceRuntimeFields,
+    resources: Vec<InstalledResource>,
+    source_override: Option<&SourceOverride>,
+) -> VersionChangePlan {
+    let (incompatible_mods, unchecked_mods) =
+        check_mods(resource_manager, resources, &to, source_override).await;
+    let mut plan = VersionChangePlan {
+        instance_id,
+        downgrade: is_downgrade(
+            game_versions,
+            &from.minecraft_version,
+            &to.minecraft_version,
+        ),
+        from,
+        to,
+        incompatible_mods,
+        unchecked_mods,
+        warnings: Vec::new(),
+        queued: false,
+    };
+    plan.warnings = warnings_for(&plan);
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use piston_lib::game::metadata::{LoaderVersionInfo, VersionType};
+    use std::collections::HashMap;
+
+    fn loader_version(version: &str, stable: bool) -> LoaderVersionInfo {
+        LoaderVersionInfo {
+            version: version.to_string(),
+            stable,
+            url: None,
+            sha1: None,
+            changelog_url: None,
+            metadata: None,
+        }
+    }
+
+    fn game_version(id: &str, year: i32, fabric: Vec<LoaderVersionInfo>) -> GameVersionMetadata {
+        let mut loaders = HashMap::new();
+        if !fabric.is_empty() {
+            loaders.insert(ModloaderType::Fabric, fabric);
+        }
+        GameVersionMetadata {
+            id: id.to_string(),
+            version_type: VersionType::Release,
+            release_time: chrono::Utc.with_ymd_and_hms(year, 6, 1, 0, 0, 0).unwrap(),
+            loaders,
+        }
+    }
+
+    fn versions() -> Vec<GameVersionMetadata> {
+        vec![
+            game_version(
+                "1.21.1",
+                2024,
+                vec![
+                    loader_version("0.16.5-beta", false),
+                    loader_version("0.16.4", true),
+                ],
+            ),
+            game_version("1.20.1", 2023, vec![loader_version("0.14.21", true)]),
+            game_version("1.8.9", 2015, Vec::new()),
+        ]
+    }
+
+    #[test]
+    fn resolve_target_validates_the_combination() {
+        let versions = versions();
+        let target = resolve_target(&versions, "1.21.1", Some("Fabric"), None).unwrap();
+        assert_eq!(target.modloader.as_deref(), Some("fabric"));
+        assert_eq!(target.modloader_version.as_deref(), Some("0.16.4"));
+
+        let pinned = resolve_target(&versions, "1.20.1", Some("fabric"), Some("0.14.21")).unwrap();
+        assert_eq!(pinned.modloader_version.as_deref(), Some("0.14.21"));
+
+        let vanilla = resolve_target(&versions, "1.8.9", None, None).unwrap();
+        assert_eq!(vanilla.modloader.as_deref(), Some("vanilla"));
+        assert_eq!(vanilla.modloader_version, None);
+
+        assert!(resolve_target(&versions, "1.99", None, None).is_err());
+        assert!(resolve_target(&versions, "1.8.9", Some("fabric"), None).is_err());
+        assert!(resolve_target(&versions, "1.20.1", Some("fabric"), Some("0.16.4")).is_err());
+        assert!(resolve_target(&versions, "1.20.1", Some("vanilla"), Some("0.14.21")).is_err());
+        assert!(resolve_target(&versions, "1.20.1", Some("rift"), None).is_err());
+    }
+
+    #[test]
+    fn downgrades_warn_about_worlds() {
+        let versions = versions();
+        assert!(is_downgrade(&versions, "1.21.1", "1.20.1"));
+        assert!(!is_downgrade(&versions, "1.20.1", "1.21.1"));
+        assert!(!is_downgrade(&versions, "1.20.1", "unknown"));
+
+        let from = resolve_target(&versions, "1.21.1", Some("fabric"), None).unwrap();
+        let to = resolve_target(&versions, "1.20.1", Some("fabric"), None).unwrap();
+        let mut plan = VersionChangePlan {
+            instance_id: 1,
+            from,
+            to,
+            downgrade: true,
+            incompatible_mods: vec![IncompatibleMod {
+                resource_id: 7,
+                display_name: "Sodium".to_string(),
+                current_version: "0.6.0".to_string(),
+            }],
+            unchecked_mods: 0,
+            warnings: Vec::new(),
+            queued: false,
+        };
+        let warnings = warnings_for(&plan);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("Worlds"));
+        assert!(warnings[1].contains("Sodium"));
+
+        plan.downgrade = false;
+        plan.incompatible_mods.clear();
+        assert!(warnings_for(&plan).is_empty());
+    }
+}
@@ -0,0 +1,393 @@
+//! Discord rich presence for running instances.
+//!
+//! Opt-in through `discord_presence_enabled`. Presence follows the
+//! `core://instance-launched`/`exited`/`killed` events and is pushed over the
+//! Discord IPC socket (`discord_ipc`) by a worker thread.
+
+use super::discord_ipc::IpcConnection;
+use crate::utils::config::get_app_config;
+use serde::Serialize;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Listener};
+
+/// The Discord Application ID for Vesta Launcher
+const DISCORD_APP_ID: &str = "1471108653573603510";
+
+/// How often a missing or lost Discord client is looked for again.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(20);
+
+/// Discord rejects activity strings longer than this.
+const MAX_TEXT_LEN: usize = 128;
+
+/// A running instance as announced by `core://instance-launched`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunningGame {
+    pub slug: String,
+    pub name: String,
+    pub minecraft_version: Option<String>,
+    pub modloader: Option<String>,
+    pub modloader_version: Option<String>,
+    /// Unix seconds.
+    pub started_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Activity {
+    pub details: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    pub timestamps: Timestamps,
+    pub assets: Assets,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Timestamps {
+    /// Discord counts the elapsed time up from here.
+    pub start: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Assets {
+    pub large_image: String,
+    pub large_text: String,
+}
+
+impl Activity {
+    /// "Playing <instance>" with the loader line and the elapsed time since
+    /// launch.
+    pub fn for_game(game: &RunningGame) -> Self {
+        Self {
+            details: fit(&format!("Playing {}", game.name)),
+            state: loader_line(game).map(|line| fit(&line)),
+            timestamps: Timestamps {
+                start: game.started_at,
+            },
+            assets: Assets {
+                large_image: "logo".to_string(),
+                large_text: "Vesta Launcher".to_string(),
+            },
+        }
+    }
+}
+
+/// Presence for the most recently launched game; `None` clears it.
+pub fn activity_for(games: &[RunningGame]) -> Option<Activity> {
+    games
+        .iter()
+        .max_by_key(|game| game.started_at)
+        .map(Activity::for_game)
+}
+
+/// e.g. `Forge 47.3.0 · Minecraft 1.20.1` or `Minecraft 1.21.1`.
+fn loader_line(game: &RunningGame) -> Option<String> {
+    let minecraft = game
+        .minecraft_version
+        .as_deref()
+        .map(|version| format!("Minecraft {}", version));
+    let loader = game
+        .modloader
+        .as_deref()
+        .filter(|loader| !loader.is_empty() && !loader.eq_ignore_ascii_case("vanilla"))
+        .map(|loader| {
+            let mut label = capitalize(loader);
+            if let Some(version) = game.modloader_version.as_deref() {
+                label.push(' ');
+                label.push_str(version);
+            }
+            label
+        });
+    match (loader, minecraft) {
+        (Some(loader), Some(minecraft)) => Some(format!("{} · {}", loader, minecraft)),
+        (loader, minecraft) => loader.or(minecraft),
+    }
+}
+
+fn capitalize(loader: &str) -> String {
+    match loader.to_ascii_lowercase().as_str() {
+        "neoforge" => "NeoForge".to_string(),
+        lower => {
+            let mut chars = lower.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        }
+    }
+}
+
+fn fit(text: &str) -> String {
+    if text.chars().count() <= MAX_TEXT_LEN {
+        return text.to_string();
+    }
+    let mut fitted: String = text.chars().take(MAX_TEXT_LEN - 1).collect();
+    fitted.push('…');
+    fitted
+}
+
+enum WorkerMessage {
+    Presence(Option<Activity>),
+    Stop,
+}
+
+/// Mirrors running instances into Discord rich presence.
+///
+/// Presence is opt-in (`discord_presence_enabled`). While enabled, a worker
+/// thread owns the IPC connection and quietly retries every
+/// [`RECONNECT_INTERVAL`] when Discord isn't running, so launches never wait
+/// on or fail because of Discord.
+#[derive(Clone)]
+pub struct DiscordManager {
+    games: Arc<Mutex<Vec<RunningGame>>>,
+    worker: Arc<Mutex<Option<Sender<WorkerMessage>>>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl DiscordManager {
+    pub fn new(app_handle: AppHandle) -> Self {
+        let manager = Self {
+            games: Arc::new(Mutex::new(Vec::new())),
+            worker: Arc::new(Mutex::new(None)),
+        };
+        manager.listen(&app_handle);
+        manager
+    }
+
+    /// Start the worker if presence is enabled in settings.
+    pub fn init(&self) {
+        match get_app_config() {
+            Ok(config) => self.set_enabled(config.discord_presence_enabled),
+            Err(e) => log::error!("[DiscordManager] Failed to get app config: {}", e),
+        }
+    }
+
+    /// Start or stop the presence worker. Stopping clears the presence.
+    pub fn set_enabled(&self, enabled: bool) {
+        let activity = self.current_activity();
+        let mut worker = lock(&self.worker);
+        if enabled && worker.is_none() {
+            log::info!("[DiscordManager] Rich presence enabled");
+            *worker = Some(spawn_worker(activity));
+        } else if !enabled {
+            if let Some(sender) = worker.take() {
+                log::info!("[DiscordManager] Rich presence disabled");
+                let _ = sender.send(WorkerMessage::Stop);
+            }
+        }
+    }
+
+    fn game_launched(&self, game: RunningGame) {
+        {
+            let mut games = lock(&self.games);
+            games.retain(|running| running.slug != game.slug);
+            games.push(game);
+        }
+        self.publish();
+    }
+
+    fn game_stopped(&self, slug: &str) {
+        lock(&self.games).retain(|running| running.slug != slug);
+        self.publish();
+    }
+
+    fn current_activity(&self) -> Option<Activity> {
+        activity_for(&lock(&self.games))
+    }
+
+    fn publish(&self) {
+        let activity = self.current_activity();
+        if let Some(sender) = lock(&self.worker).as_ref() {
+            let _ = sender.send(WorkerMessage::Presence(activity));
+        }
+    }
+
+    fn listen(&self, app_handle: &AppHandle) {
+        let dm = self.clone();
+        app_handle.listen("core://instance-launched", move |event| {
+            if let Some(game) = running_game_from_payload(event.payload()) {
+                dm.game_launched(game);
+            }
+        });
+
+        for event_name in ["core://instance-exited", "core://instance-killed"] {
+            let dm = self.clone();
+            app_handle.listen(event_name, move |event| {
+                let payload = serde_json::from_str::<serde_json::Value>(event.payload());
+                if let Some(slug) = payload
+                    .ok()
+                    .and_then(|p| p["instance_id"].as_str().map(str::to_string))
+                {
+                    dm.game_stopped(&slug);
+                }
+            });
+        }
+
+        let dm = self.clone();
+        app_handle.listen("config-updated", move |event| {
+            let raw_payload = event.payload();
+            if let Ok(payload) = serde_json::from_str::<serde_json::Value>(raw_payload) {
+                if payload["field"] == "discord_presence_enabled" {
+                    dm.set_enabled(payload["value"].as_bool().unwrap_or(false));
+                }
+            }
+        });
+    }
+}
+
+fn running_game_from_payload(raw_payload: &str) -> Option<RunningGame> {
+    let payload: serde_json::Value = serde_json::from_str(raw_payload).ok()?;
+    let slug = payload["instance_id"].as_str()?.to_string();
+    let started_at = payload["start_time"].as_i64().unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64
+    });
+    let instance = crate::instance::lifecycle::find_instance_by_slug(&slug);
+    let name = instance
+        .as_ref()
+        .map(|inst| inst.name.clone())
+        .or_else(|| payload["name"].as_str().map(str::to_string))
+        .unwrap_or_else(|| slug.clone());
+    Some(RunningGame {
+        slug,
+        name,
+        minecraft_version: instance.as_ref().map(|inst| inst.minecraft_version.clone()),
+        modloader: instance.as_ref().and_then(|inst| inst.modloader.clone()),
+        modloader_version: instance.and_then(|inst| inst.modloader_version),
+        started_at,
+    })
+}
+
+fn spawn_worker(initial: Option<Activity>) -> Sender<WorkerMessage> {
+    let (sender, receiver) = mpsc::channel();
+    if let Err(e) = std::thread::Builder::new()
+        .name("discord-presence".to_string())
+        .spawn(move || run_worker(receiver, initial))
+    {
+        log::error!("[DiscordManager] Failed to start presence worker: {}", e);
+    }
+    sender
+}
+
+fn run_worker(receiver: Receiver<WorkerMessage>, mut desired: Option<Activity>) {
+    let mut connection: Option<IpcConnection> = None;
+    let mut last_attempt: Option<Instant> = None;
+    let mut dirty = true;
+
+    loop {
+        if connection.is_none()
+            && last_attempt.is_none_or(|attempt| attempt.elapsed() >= RECONNECT_INTERVAL)
+        {
+            last_attempt = Some(Instant::now());
+            match IpcConnection::connect(DISCORD_APP_ID) {
+                Ok(connected) => {
+                    log::info!("[DiscordManager] Connected to Discord");
+                    connection = Some(connected);
+                    dirty = true;
+                }
+                Err(e) => log::debug!("[DiscordManager] Discord not reachable: {}", e),
+            }
+        }
+
+        if let Some(connected) = connection.as_mut().filter(|_| dirty) {
+            let payload = desired
+                .as_ref()
+                .and_then(|activity| serde_json::to_value(activity).ok());
+            match connected.set_activity(payload) {
+                Ok(()) => dirty = false,
+                Err(e) => {
+                    log::debug!("[DiscordManager] Lost connection to Discord: {}", e);
+                    connection = None;
+                }
+            }
+        }
+
+        match receiver.recv_timeout(RECONNECT_INTERVAL) {
+            Ok(WorkerMessage::Presence(activity)) => {
+                desired = activity;
+                dirty = true;
+            }
+            Ok(WorkerMessage::Stop) | Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {}
+        }
+    }
+
+    if let Some(mut connected) = connection {
+        let _ = connected.set_activity(None);
+        connected.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn game(slug: &str, started_at: i64) -> RunningGame {
+        RunningGame {
+            slug: slug.to_string(),
+            name: "All the Mods 9".to_string(),
+            minecraft_version: Some("1.20.1".to_string()),
+            modloader: Some("forge".to_string()),
+            modloader_version: Some("47.3.0".to_string()),
+            started_at,
+        }
+    }
+
+    #[test]
+    fn presence_payload_serializes_for_discord() {
+        let activity = activity_for(&[game("atm9", 1_760_000_000)]).unwrap();
+        assert_eq!(
+            serde_json::to_value(&activity).unwrap(),
+            json!({
+                "details": "Playing All the Mods 9",
+                "state": "Forge 47.3.0 · Minecraft 1.20.1",
+                "timestamps": { "start": 1_760_000_000 },
+                "assets": { "large_image": "logo", "large_text": "Vesta Launcher" },
+            })
+        );
+    }
+
+    #[test]
+    fn vanilla_games_only_show_the_minecraft_version() {
+        let mut vanilla = game("vanilla", 10);
+        vanilla.modloader = Some("vanilla".to_string());
+        vanilla.modloader_version = None;
+        let value = serde_json::to_value(Activity::for_game(&vanilla)).unwrap();
+        assert_eq!(value["state"], "Minecraft 1.20.1");
+
+        vanilla.minecraft_version = None;
+        let value = serde_json::to_value(Activity::for_game(&vanilla)).unwrap();
+        assert!(value.get("state").is_none());
+    }
+
+    #[test]
+    fn newest_game_wins_and_nothing_running_clears() {
+        let mut newer = game("neo", 20);
+        newer.name = "Create".to_string();
+        newer.modloader = Some("neoforge".to_string());
+        newer.modloader_version = Some("21.1.77".to_string());
+
+        let activity = activity_for(&[game("atm9", 10), newer]).unwrap();
+        assert_eq!(activity.details, "Playing Create");
+        assert_eq!(
+            activity.state.as_deref(),
+            Some("NeoForge 21.1.77 · Minecraft 1.20.1")
+        );
+        assert_eq!(activity_for(&[]), None);
+    }
+
+    #[test]
+    fn long_names_are_truncated() {
+        let mut long = game("long", 1);
+        long.name = "x".repeat(200);
+        let activity = Activity::for_game(&long);
+        assert_eq!(activity.details.chars().count(), MAX_TEXT_LEN);
+        assert!(activity.details.ends_with('…'));
+    }
+}
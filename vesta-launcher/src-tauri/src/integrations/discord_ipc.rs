@@ -0,0 +1,167 @@
+//! Minimal client for the Discord desktop IPC socket.
+//!
+//! Discord listens on `discord-ipc-0` … `discord-ipc-9`, a Unix socket in the
+//! runtime/temp directory or a named pipe on Windows. Every message is a frame
+//! of a little-endian opcode, a little-endian payload length and a JSON
+//! payload. We only need the handshake, `SET_ACTIVITY` and close.
+
+use serde_json::{json, Value};
+use std::io::{self, Read, Write};
+use std::time::Duration;
+
+const OP_HANDSHAKE: u32 = 0;
+const OP_FRAME: u32 = 1;
+const OP_CLOSE: u32 = 2;
+
+/// Discord answers within milliseconds; anything slower is a dead client.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+trait IpcStream: Read + Write + Send {}
+impl<T: Read + Write + Send> IpcStream for T {}
+
+pub struct IpcConnection {
+    stream: Box<dyn IpcStream>,
+    nonce: u64,
+}
+
+impl IpcConnection {
+    /// Open the first socket Discord answers on and perform the handshake.
+    pub fn connect(client_id: &str) -> io::Result<Self> {
+        let stream = open_socket()?;
+        let mut connection = Self { stream, nonce: 0 };
+        connection.write_frame(OP_HANDSHAKE, &json!({ "v": 1, "client_id": client_id }))?;
+        let (_, ready) = connection.read_frame()?;
+        if ready["evt"] != "READY" {
+            return Err(io::Error::other(format!(
+                "Unexpected handshake reply: {}",
+                ready
+            )));
+        }
+        Ok(connection)
+    }
+
+    /// Show `activity`, or clear the presence with `None`.
+    pub fn set_activity(&mut self, activity: Option<Value>) -> io::Result<()> {
+        self.nonce += 1;
+        let payload = set_activity_payload(std::process::id(), activity, self.nonce);
+        self.write_frame(OP_FRAME, &payload)?;
+        let (op, reply) = self.read_frame()?;
+        if op == OP_CLOSE || reply["evt"] == "ERROR" {
+            return Err(io::Error::other(format!(
+                "Discord rejected the activity: {}",
+                reply["data"]
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn close(mut self) {
+        let _ = self.write_frame(OP_CLOSE, &json!({}));
+    }
+
+    fn write_frame(&mut self, op: u32, payload: &Value) -> io::Result<()> {
+        self.stream.write_all(&encode_frame(op, payload))?;
+        self.stream.flush()
+    }
+
+    fn read_frame(&mut self) -> io::Result<(u32, Value)> {
+        let mut header = [0u8; 8];
+        self.stream.read_exact(&mut header)?;
+        let op = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+        let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        let mut body = vec![0u8; len];
+        self.stream.read_exact(&mut body)?;
+        let payload = serde_json::from_slice(&body).map_err(io::Error::other)?;
+        Ok((op, payload))
+    }
+}
+
+pub(crate) fn set_activity_payload(pid: u32, activity: Option<Value>, nonce: u64) -> Value {
+    json!({
+        "cmd": "SET_ACTIVITY",
+        "args": { "pid": pid, "activity": activity },
+        "nonce": nonce.to_string(),
+    })
+}
+
+fn encode_frame(op: u32, payload: &Value) -> Vec<u8> {
+    let body = payload.to_string().into_bytes();
+    let mut frame = Vec::with_capacity(body.len() + 8);
+    frame.extend_from_slice(&op.to_le_bytes());
+    frame.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    frame.extend_from_slice(&body);
+    frame
+}
+
+#[cfg(unix)]
+fn open_socket() -> io::Result<Box<dyn IpcStream>> {
+    use std::os::unix::net::UnixStream;
+    use std::path::PathBuf;
+
+    let base = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+        .iter()
+        .find_map(std::env::var_os)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("/tmp"));
+    // Flatpak and Snap builds of Discord put the socket in a subdirectory.
+    let dirs = [
+        base.clone(),
+        base.join("app/com.discordapp.Discord"),
+        base.join("snap.discord"),
+    ];
+    for dir in &dirs {
+        for index in 0..10 {
+            if let Ok(stream) = UnixStream::connect(dir.join(format!("discord-ipc-{}", index))) {
+                stream.set_read_timeout(Some(READ_TIMEOUT))?;
+                return Ok(Box::new(stream));
+            }
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        "Discord IPC socket not found",
+    ))
+}
+
+#[cfg(windows)]
+fn open_socket() -> io::Result<Box<dyn IpcStream>> {
+    for index in 0..10 {
+        let pipe = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(format!(r"\\.\pipe\discord-ipc-{}", index));
+        if let Ok(pipe) = pipe {
+            return Ok(Box::new(pipe));
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        "Discord IPC pipe not found",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_are_little_endian_opcode_length_and_json() {
+        let frame = encode_frame(OP_HANDSHAKE, &json!({ "v": 1 }));
+        assert_eq!(&frame[..4], &[0, 0, 0, 0]);
+        assert_eq!(&frame[4..8], &7u32.to_le_bytes());
+        assert_eq!(&frame[8..], br#"{"v":1}"#);
+    }
+
+    #[test]
+    fn clearing_sends_a_null_activity() {
+        let payload = set_activity_payload(4242, None, 3);
+        assert_eq!(
+            payload,
+            json!({
+                "cmd": "SET_ACTIVITY",
+                "args": { "pid": 4242, "activity": null },
+                "nonce": "3",
+            })
+        );
+    }
+}
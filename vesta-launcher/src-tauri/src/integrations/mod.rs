@@ -0,0 +1,4 @@
+pub mod discord;
+mod discord_ipc;
+
+pub use discord::DiscordManager;
@@ -218,6 +218,18 @@ fn ftb_presets() -> &'static [PathPreset] {
     FTB_PRESETS
 }
 
+/// The official Minecraft Launcher's directory, whether or not it exists yet.
+pub fn official_launcher_dir() -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    const PRESET: PathPreset = data_preset(&[".minecraft"]);
+    #[cfg(target_os = "macos")]
+    const PRESET: PathPreset = data_preset(&["minecraft"]);
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    const PRESET: PathPreset = home_preset(&[".minecraft"]);
+
+    build_paths(&[PRESET]).into_iter().next()
+}
+
 #[cfg(test)]
 mod tests {
     use super::{build_paths, dedupe_paths, filter_existing_paths, BaseKind, PathPreset};
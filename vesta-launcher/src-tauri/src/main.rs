@@ -2,9 +2,11 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 pub mod auth;
+mod cli;
 mod commands;
-pub mod discord;
+mod first_run;
 mod instance;
+pub mod integrations;
 mod launcher_import;
 mod logging;
 mod metadata_cache;
@@ -33,6 +35,11 @@ pub(crate) fn tauri_context() -> tauri::Context {
 }
 
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(headless_args) = cli::headless_args(&args) {
+        std::process::exit(cli::run(headless_args));
+    }
+
     // Early checks for debug logging and telemetry settings.
     let mut log_level = log::LevelFilter::Info;
     let mut telemetry_enabled = true;
@@ -85,25 +92,32 @@ fn main() {
         None
     };
 
-    let mut builder = tauri::Builder::default();
+    // Single-instance must be the first plugin so a second process forwards its
+    // arguments and exits before anything else opens the databases.
+    let mut builder =
+        tauri::Builder::default().plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
+            if utils::launch_intents::wants_new_window(&args) {
+                if let Err(error) = utils::windows::launch_new_window(app) {
+                    log::warn!("Failed to open additional launcher window: {}", error);
+                }
+            } else {
+                let _ = crate::utils::windows::ensure_main_window_visible(&app);
+            }
+
+            if args.len() > 1 {
+                crate::utils::launch_intents::ingest_launch_args(&args);
+                let state = app.state::<utils::launch_intents::PendingLaunchIntents>();
+                if state.is_frontend_ready() {
+                    utils::launch_intents::flush_pending_intents(&app);
+                }
+            }
+        }));
     if let Some(sentry_client) = sentry_client.as_ref() {
         builder = builder.plugin(tauri_plugin_sentry::init(sentry_client));
     }
 
     builder
         .plugin(tauri_plugin_fs::init())
-        .plugin(
-            tauri_plugin_window_state::Builder::default()
-                // Visibility is controlled by the painted-surface handshake. Restoring
-                // it here would show hidden startup and prewarmed windows prematurely.
-                .with_state_flags(
-                    tauri_plugin_window_state::StateFlags::all()
-                        .difference(tauri_plugin_window_state::StateFlags::VISIBLE),
-                )
-                // Reusable mini windows own their lifecycle in MiniWindowRegistry.
-                .with_filter(|label| !label.starts_with("page-viewer-"))
-                .build(),
-        )
         .plugin(tauri_plugin_autostart::init(
             tauri_plugin_autostart::MacosLauncher::LaunchAgent,
             None,
@@ -115,6 +129,7 @@ fn main() {
         .manage(utils::dialog_manager::DialogManager::new())
         .manage(utils::windows::MiniWindowRegistry::default())
         .manage(utils::launch_intents::PendingLaunchIntents::new())
+        .manage(startup::self_update::UpdaterState::default())
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
@@ -122,35 +137,32 @@ fn main() {
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_macos_permissions::init())
         .plugin(tauri_plugin_clipboard_manager::init())
-        .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
-            let _ = crate::utils::windows::ensure_main_window_visible(&app);
-
-            if args.len() > 1 {
-                crate::utils::launch_intents::ingest_launch_args(&args);
-                let state = app.state::<utils::launch_intents::PendingLaunchIntents>();
-                if state.is_frontend_ready() {
-                    utils::launch_intents::flush_pending_intents(&app);
-                }
-            }
-        }))
         .invoke_handler(tauri::generate_handler![
             launch_window,
             utils::windows::prime_mini_window,
             utils::windows::preload_mini_window_route,
             utils::windows::take_mini_window_payload,
             utils::windows::hide_mini_window,
+            utils::window_state::reset_window_state,
             get_config,
             set_config,
             update_config_field,
             update_config_fields,
+            utils::config::theme_presets::list_theme_presets,
+            utils::config::theme_presets::save_theme_preset,
+            utils::config::theme_presets::apply_theme_preset,
+            utils::config::theme_presets::delete_theme_preset,
             commands::app::open_app_config_dir,
             commands::app::open_app_runtime_storage_dir,
             commands::app::clear_cache,
+            commands::app::run_cache_gc,
             commands::app::get_storage_snapshot,
             commands::app::prune_storage_cache,
             commands::app::get_cache_size,
             commands::app::open_logs_folder,
             commands::app::open_instance_folder,
+            commands::app::reveal_file,
+            commands::app::open_file_with_default_app,
             commands::app::trigger_test_panic,
             commands::screenshots::get_screenshots,
             commands::screenshots::delete_screenshot,
@@ -167,11 +179,22 @@ fn main() {
             commands::app::get_network_status,
             commands::app::set_network_status,
             commands::app::refresh_network_status,
+            commands::app::get_network_health,
+            commands::app::benchmark_mirrors,
             commands::app::test_proxy_connection,
             commands::app::get_tray_settings,
             commands::app::set_tray_icon_visibility,
             commands::app::set_minimize_to_tray,
+            commands::app::set_discord_presence_enabled,
             commands::app::show_window_from_tray,
+            commands::app::check_for_updates,
+            commands::app::get_update_status,
+            commands::app::download_launcher_update,
+            commands::app::generate_diagnostics_bundle,
+            commands::app::get_privacy_settings,
+            commands::app::set_privacy_settings,
+            commands::app::get_event_log,
+            commands::app::purge_event_log,
             commands::app::present_window_when_ready,
             commands::app::clear_window_startup_background,
             commands::app::parse_vesta_url,
@@ -194,15 +217,20 @@ fn main() {
             utils::file_drop::reset_file_drop_sniffer,
             auth::start_login,
             auth::cancel_login,
+            #[cfg(debug_assertions)]
+            auth::add_account_from_token,
             auth::get_accounts,
             auth::get_active_account,
             auth::set_active_account,
+            auth::rename_account_display_name,
             auth::start_guest_session,
             auth::start_demo_session,
             auth::remove_account,
             auth::get_account_profile,
             auth::get_player_head_path,
+            auth::get_player_cape_path,
             auth::preload_account_heads,
+            auth::refresh_account_avatar,
             commands::skins::force_sync_account_profile,
             commands::skins::upload_account_skin,
             commands::skins::apply_history_skin,
@@ -217,8 +245,12 @@ fn main() {
             commands::notifications::create_notification,
             commands::notifications::update_notification_progress,
             commands::notifications::list_notifications,
+            commands::notifications::count_notifications,
             commands::notifications::mark_notification_read,
             commands::notifications::delete_notification,
+            commands::notifications::mark_all_read,
+            commands::notifications::delete_all_read,
+            commands::notifications::delete_notifications_bulk,
             commands::notifications::invoke_notification_action,
             commands::notifications::cleanup_notifications,
             commands::notifications::clear_immediate_notifications,
@@ -243,23 +275,57 @@ fn main() {
             commands::notifications::delete_notification_subscription,
             commands::notifications::subscribe_to_resource_updates,
             commands::notifications::subscribe_to_rss,
+            commands::notifications::export_notification_subscriptions,
+            commands::notifications::import_notification_subscriptions,
             commands::notifications::check_notifications_now,
             commands::tasks::set_worker_limit,
             commands::tasks::cancel_task,
+            commands::tasks::list_active_tasks,
+            commands::tasks::get_chain_status,
+            commands::tasks::get_task_history,
+            commands::tasks::get_download_stats,
+            commands::tasks::get_asset_download_progress,
             commands::instances::install_instance,
+            commands::instances::change_instance_version,
             commands::instances::list_instances,
+            commands::instances::search_instances,
             commands::instances::create_instance,
             commands::instances::update_instance,
             commands::instances::delete_instance,
+            commands::instances::list_deleted_instances,
+            commands::instances::restore_instance,
+            commands::instances::purge_deleted_instances,
             commands::instances::get_instance,
             commands::instances::get_instance_by_slug,
+            commands::instances::get_instance_history,
+            commands::instances::get_install_metrics,
+            commands::instances::set_forge_mirror,
             commands::instances::get_instance_required_java,
+            commands::instances::suggest_java_for_instance,
             commands::instances::launch_instance,
+            commands::instances::launch_instance_safe_mode,
+            commands::instances::launch_instance_and_connect,
             commands::instances::kill_instance,
             commands::instances::get_running_instances,
+            commands::instances::get_live_session,
+            commands::instances::get_active_play_ratio,
             commands::instances::is_instance_running,
+            commands::instances::get_process_metrics,
             commands::instances::update_instance_modpack_version,
             commands::instances::get_minecraft_versions,
+            commands::instances::export_version_matrix,
+            commands::instances::get_supported_loaders,
+            commands::instances::get_loader_changelog_url,
+            commands::instances::check_loader_compatibility,
+            commands::instances::get_asset_delta_estimate,
+            commands::instances::set_instance_source_override,
+            commands::instances::set_instance_config_override,
+            commands::instances::get_instance_config_override,
+            commands::instances::detect_mod_conflicts,
+            commands::instances::set_instance_verify_on_launch,
+            commands::instances::list_instance_config_files,
+            commands::instances::read_instance_config_file,
+            commands::instances::write_instance_config_file,
             commands::instances::regenerate_piston_manifest,
             commands::instances::read_instance_log,
             commands::instances::get_instance_log_history,
@@ -272,8 +338,13 @@ fn main() {
             commands::instances::list_crash_scenarios,
             commands::instances::duplicate_instance,
             commands::instances::repair_instance,
+            commands::instances::verify_assets,
             commands::instances::reset_instance,
             commands::instances::resume_instance_operation,
+            commands::instances::export_to_vanilla_launcher,
+            commands::instances::import_from_vanilla_launcher,
+            commands::instances::export_instance_template,
+            commands::instances::create_instance_from_template,
             commands::modpacks::get_modpack_info,
             commands::modpacks::get_modpack_info_from_url,
             commands::modpacks::get_modpack_archive_summary_from_url,
@@ -284,20 +355,26 @@ fn main() {
             commands::modpacks::install_modpack_from_url,
             commands::modpacks::list_export_candidates,
             commands::modpacks::export_instance_to_modpack,
+            commands::modpacks::repair_modpack,
             commands::onboarding::get_required_java_versions,
             commands::onboarding::detect_java,
             commands::onboarding::get_managed_javas,
             commands::onboarding::select_java_file,
             commands::onboarding::verify_java_path,
+            commands::onboarding::validate_java_path,
             commands::onboarding::set_global_java_path,
             commands::onboarding::get_global_java_paths,
             commands::onboarding::complete_onboarding,
             commands::onboarding::reset_onboarding,
             commands::onboarding::set_setup_step,
+            commands::onboarding::get_onboarding_state,
+            commands::onboarding::apply_onboarding_choices,
+            commands::onboarding::detect_recommended_defaults,
             commands::onboarding::download_managed_java,
             commands::resources::get_resource_categories,
             commands::resources::search_resources,
             commands::resources::get_resource_project,
+            commands::resources::get_resource_project_details,
             commands::resources::cache_resource_metadata,
             commands::resources::get_cached_resource_project,
             commands::resources::get_cached_resource_projects,
@@ -306,6 +383,7 @@ fn main() {
             commands::resources::get_resource_versions,
             commands::resources::find_peer_resource,
             commands::resources::install_resource,
+            commands::resources::install_resource_version,
             commands::resources::delete_resource,
             commands::resources::toggle_resource,
             commands::resources::clear_modpack_resource_provenance,
@@ -328,6 +406,9 @@ fn main() {
         ])
         .on_window_event(|window, event| {
             match event {
+                tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_) => {
+                    crate::utils::window_state::schedule_save(window);
+                }
                 tauri::WindowEvent::CloseRequested { api, .. } => {
                     if window.label() == "main" {
                         api.prevent_close();
@@ -336,7 +417,7 @@ fn main() {
                                 // If close-to-tray is enabled but the persistent tray setting is off,
                                 // temporarily expose the tray so users can restore the app.
                                 if !config.show_tray_icon {
-                                    if let Some(tray) = window.app_handle().tray_by_id("main-tray") {
+                                    if let Some(tray) = window.app_handle().tray_by_id(crate::startup::tray::TRAY_ID) {
                                         let _ = tray.set_visible(true);
                                     }
                                 }
@@ -371,6 +452,10 @@ fn main() {
         .build(tauri_context())
         .expect("error while running tauri application")
         .run(|app, event| {
+            if matches!(event, tauri::RunEvent::Exit) {
+                crate::startup::self_update::on_exit(app);
+            }
+
             #[cfg(any(target_os = "macos", target_os = "ios"))]
             if let tauri::RunEvent::Opened { urls } = event {
                 crate::utils::launch_intents::ingest_opened_urls(&urls);
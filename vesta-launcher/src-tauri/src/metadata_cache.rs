@@ -1,22 +1,50 @@
 use piston_lib::game::metadata::PistonMetadata;
 use std::sync::{Arc, RwLock};
 
+/// In-memory copy of the combined version metadata.
+///
+/// Each value is an immutable snapshot: `set` builds the new one before
+/// swapping the pointer, so the lock is only held for the swap and a `get`
+/// sees either the old or the new metadata in full.
 #[derive(Clone)]
-pub struct MetadataCache(pub Arc<RwLock<Option<PistonMetadata>>>);
+pub struct MetadataCache(Arc<RwLock<Option<Arc<PistonMetadata>>>>);
 
 impl MetadataCache {
     pub fn new() -> Self {
         Self(Arc::new(RwLock::new(None)))
     }
 
+    /// The current snapshot, shared rather than copied.
+    pub fn snapshot(&self) -> Option<Arc<PistonMetadata>> {
+        self.0.read().ok().and_then(|guard| guard.clone())
+    }
+
     pub fn get(&self) -> Option<PistonMetadata> {
-        self.0.read().ok().and_then(|g| g.clone())
+        self.snapshot().map(|meta| (*meta).clone())
     }
 
     pub fn set(&self, meta: &PistonMetadata) {
+        let next = Arc::new(meta.clone());
         if let Ok(mut guard) = self.0.write() {
-            *guard = Some(meta.clone());
+            *guard = Some(next);
+        }
+    }
+
+    /// Replace `current` with `meta`, unless another `set` replaced it in the
+    /// meantime. Returns whether `meta` was stored.
+    pub fn replace_if_current(&self, current: &Arc<PistonMetadata>, meta: PistonMetadata) -> bool {
+        let next = Arc::new(meta);
+        let Ok(mut guard) = self.0.write() else {
+            return false;
+        };
+        if !guard
+            .as_ref()
+            .is_some_and(|stored| Arc::ptr_eq(stored, current))
+        {
+            return false;
         }
+        *guard = Some(next);
+        true
     }
 
     pub fn clear(&self) {
@@ -25,3 +53,78 @@ impl MetadataCache {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use piston_lib::game::metadata::LatestVersions;
+    use std::collections::HashMap;
+    use std::time::{Duration, Instant};
+
+    fn metadata(release: &str, java_majors: usize) -> PistonMetadata {
+        PistonMetadata {
+            last_updated: chrono::Utc::now(),
+            game_versions: Vec::new(),
+            latest: LatestVersions {
+                release: release.to_string(),
+                snapshot: release.to_string(),
+            },
+            required_java_major_versions: (1..=java_majors as u32).collect(),
+            java_major_version_by_game_version: HashMap::new(),
+            loader_supported_game_versions: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn stale_snapshot_does_not_replace_newer_metadata() {
+        let cache = MetadataCache::new();
+        cache.set(&metadata("1.21", 1));
+        let stale = cache.snapshot().unwrap();
+        cache.set(&metadata("1.21.1", 1));
+
+        assert!(!cache.replace_if_current(&stale, metadata("1.21", 2)));
+        assert_eq!(cache.get().unwrap().latest.release, "1.21.1");
+
+        let current = cache.snapshot().unwrap();
+        assert!(cache.replace_if_current(&current, metadata("1.21.1", 2)));
+        assert_eq!(cache.get().unwrap().required_java_major_versions.len(), 2);
+    }
+
+    #[test]
+    fn readers_always_see_a_complete_snapshot() {
+        let cache = MetadataCache::new();
+        cache.set(&metadata("a", 1));
+        let deadline = Instant::now() + Duration::from_secs(2);
+
+        let writer = {
+            let cache = cache.clone();
+            std::thread::spawn(move || {
+                let mut round = 0usize;
+                while Instant::now() < deadline {
+                    round += 1;
+                    // Release and Java list change together, so a reader can
+                    // tell a mixed snapshot apart.
+                    let (release, majors) = if round % 2 == 0 { ("a", 1) } else { ("b", 500) };
+                    cache.set(&metadata(release, majors));
+                }
+            })
+        };
+        let readers: Vec<_> = (0..8)
+            .map(|_| {
+                let cache = cache.clone();
+                std::thread::spawn(move || {
+                    while Instant::now() < deadline {
+                        let meta = cache.snapshot().expect("metadata cleared");
+                        let expected = if meta.latest.release == "a" { 1 } else { 500 };
+                        assert_eq!(meta.required_java_major_versions.len(), expected);
+                    }
+                })
+            })
+            .collect();
+
+        writer.join().unwrap();
+        for reader in readers {
+            reader.join().unwrap();
+        }
+    }
+}
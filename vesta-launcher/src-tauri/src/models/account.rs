@@ -29,6 +29,10 @@ pub struct Account {
     pub theme_data: Option<String>,
     pub theme_window_effect: Option<String>,
     pub theme_background_opacity: Option<i32>,
+    /// Xbox user id from the Minecraft token, passed to the game as `auth_xuid`.
+    pub xuid: Option<String>,
+    /// Xbox Live gamerpic, used as the head when the account has no skin.
+    pub xbox_avatar_url: Option<String>,
 }
 
 /// New account (without id for insertion)
@@ -54,6 +58,10 @@ pub struct NewAccount {
     pub theme_data: Option<String>,
     pub theme_window_effect: Option<String>,
     pub theme_background_opacity: Option<i32>,
+    /// Xbox user id from the Minecraft token, passed to the game as `auth_xuid`.
+    pub xuid: Option<String>,
+    /// Xbox Live gamerpic, used as the head when the account has no skin.
+    pub xbox_avatar_url: Option<String>,
 }
 
 impl Default for Account {
@@ -79,6 +87,8 @@ impl Default for Account {
             theme_data: None,
             theme_window_effect: None,
             theme_background_opacity: None,
+            xuid: None,
+            xbox_avatar_url: None,
         }
     }
 }
@@ -105,6 +115,8 @@ impl Default for NewAccount {
             theme_data: None,
             theme_window_effect: None,
             theme_background_opacity: None,
+            xuid: None,
+            xbox_avatar_url: None,
         }
     }
 }
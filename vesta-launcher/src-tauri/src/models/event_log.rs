@@ -0,0 +1,24 @@
+use crate::schema::event_log;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// One local usage event. See `utils::event_log` for the recorded kinds.
+#[derive(Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable, Clone)]
+#[diesel(table_name = event_log)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct EventLogEntry {
+    pub id: i32,
+    pub kind: String,
+    pub instance_id: Option<i32>,
+    pub error_code: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = event_log)]
+pub struct NewEventLogEntry {
+    pub kind: String,
+    pub instance_id: Option<i32>,
+    pub error_code: Option<String>,
+    pub created_at: String,
+}
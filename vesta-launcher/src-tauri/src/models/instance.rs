@@ -1,5 +1,5 @@
 use crate::schema::instance;
-use crate::utils::sanitize::sanitize_instance_name;
+use crate::utils::sanitize::{resolve_instance_slug, sanitize_instance_name};
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -59,6 +59,30 @@ pub struct Instance {
     pub pre_launch_hook: Option<String>,
     pub wrapper_command: Option<String>,
     pub post_exit_hook: Option<String>,
+    /// Set when the instance has been moved to the trash; `None` for live instances.
+    #[serde(default)]
+    pub deleted_at: Option<String>,
+    /// JSON `{ platform, base_url }` routing this instance's resource API calls
+    /// to a self-hosted Modrinth/CurseForge server.
+    #[serde(default)]
+    pub instance_source_override: Option<String>,
+    /// Per-instance override of the launch integrity spot-check; `None`
+    /// follows the global `verify_on_launch` setting.
+    #[serde(default)]
+    pub verify_on_launch: Option<bool>,
+    /// Free-form user notes, searchable together with the name.
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// JSON `InstanceConfigOverride` of memory, Java and download settings.
+    #[serde(default)]
+    pub config_override_json: Option<String>,
+    /// Game directory the instance had before it was moved to the trash.
+    #[serde(default)]
+    pub trashed_from_directory: Option<String>,
+    /// Runtime id and folder slug. `None` for instances created before slugs
+    /// were stored; those keep the legacy mapping of their name.
+    #[serde(default)]
+    pub slug: Option<String>,
 }
 
 /// New instance (without id for insertion)
@@ -106,6 +130,7 @@ pub struct NewInstance {
     pub pre_launch_hook: Option<String>,
     pub wrapper_command: Option<String>,
     pub post_exit_hook: Option<String>,
+    pub slug: Option<String>,
 }
 
 impl Default for Instance {
@@ -152,15 +177,28 @@ impl Default for Instance {
             pre_launch_hook: None,
             wrapper_command: None,
             post_exit_hook: None,
+            deleted_at: None,
+            instance_source_override: None,
+            verify_on_launch: None,
+            notes: None,
+            config_override_json: None,
+            trashed_from_directory: None,
+            slug: None,
         }
     }
 }
 
 impl Instance {
-    /// Return a filesystem-safe slug derived from the instance name. This
-    /// slug will be used as the runtime instance id / folder name.
+    /// Return the filesystem-safe slug used as the runtime instance id /
+    /// folder name: the stored slug, or the legacy mapping of the name for
+    /// instances created before slugs were stored.
     pub fn slug(&self) -> String {
-        sanitize_instance_name(&self.name)
+        resolve_instance_slug(&self.name, self.slug.as_deref())
+    }
+
+    /// Whether the instance is soft-deleted and waiting in the trash.
+    pub fn is_trashed(&self) -> bool {
+        self.deleted_at.is_some()
     }
 }
 
@@ -210,6 +248,7 @@ impl NewInstance {
         post_exit_hook: Option<String>,
     ) -> Self {
         NewInstance {
+            slug: Some(sanitize_instance_name(&name)),
             name,
             minecraft_version,
             modloader,
@@ -297,6 +336,7 @@ impl Default for NewInstance {
             pre_launch_hook: None,
             wrapper_command: None,
             post_exit_hook: None,
+            slug: Some(sanitize_instance_name("New Instance")),
         }
     }
 }
@@ -314,4 +354,16 @@ mod tests {
         // This test will need update
         assert_eq!(sanitize_instance_name(&inst.name), "my-cool-instance");
     }
+
+    #[test]
+    fn stored_slug_overrides_legacy_mapping() {
+        let mut inst = Instance {
+            name: "Привет мир".to_string(),
+            ..Instance::default()
+        };
+        assert_eq!(inst.slug(), "instance");
+
+        inst.slug = Some(sanitize_instance_name(&inst.name));
+        assert_eq!(inst.slug(), "privet-mir");
+    }
 }
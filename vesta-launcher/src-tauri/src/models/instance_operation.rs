@@ -0,0 +1,28 @@
+use crate::schema::instance_operation;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// One install/update/repair/export run against an instance. `finished_at`
+/// and `success` stay null while the operation is still running.
+#[derive(Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable, Clone)]
+#[diesel(table_name = instance_operation)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct InstanceOperation {
+    pub id: i32,
+    pub instance_id: i32,
+    pub kind: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub success: Option<bool>,
+    pub error_code: Option<String>,
+    pub details: String,
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = instance_operation)]
+pub struct NewInstanceOperation {
+    pub instance_id: i32,
+    pub kind: String,
+    pub started_at: String,
+    pub details: String,
+}
@@ -1,29 +1,37 @@
 pub mod account;
 pub mod api;
 pub mod domain;
+pub mod event_log;
 pub mod installed_resource;
 pub mod instance;
+pub mod instance_operation;
 pub mod instance_resource_update_check;
 pub mod java;
 pub mod notification;
 pub mod notification_seen_item;
 pub mod notification_subscription;
 pub mod pinning;
+pub mod play_session;
 pub mod resource;
 pub mod resource_update;
 pub mod saved_theme;
+pub mod task_history;
 pub mod task_state;
 pub mod user_version_tracking;
 
 pub use account::Account;
+pub use event_log::{EventLogEntry, NewEventLogEntry};
 pub use installed_resource::InstalledResource;
 pub use instance::Instance;
+pub use instance_operation::{InstanceOperation, NewInstanceOperation};
 pub use java::GlobalJavaPath;
 pub use notification::Notification;
 pub use notification_seen_item::{NewNotificationSeenItem, NotificationSeenItem};
 pub use notification_subscription::{NewNotificationSubscription, NotificationSubscription};
+pub use play_session::{NewPlaySession, PlaySession};
 pub use resource::{ResourceProject, ResourceType, ResourceVersion, SourcePlatform};
 pub use saved_theme::{NewSavedTheme, SavedTheme};
+pub use task_history::{NewTaskHistoryEntry, TaskHistoryEntry};
 pub use task_state::TaskState;
 pub use user_version_tracking::UserVersionTracking;
 
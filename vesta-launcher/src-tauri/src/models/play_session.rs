@@ -0,0 +1,47 @@
+use crate::schema::play_session;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// One game session for an instance. Opened when the game starts and
+/// checkpointed while it runs; finalized when the process exits.
+#[derive(Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable, Clone)]
+#[diesel(table_name = play_session)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct PlaySession {
+    pub id: i32,
+    pub instance_id: i32,
+    pub started_at: String,
+    pub ended_at: String,
+    pub exit_code: Option<i32>,
+    pub crashed: bool,
+    pub safe_mode: bool,
+    /// Minutes credited to the instance so far while the session is still
+    /// running; `None` once finalized.
+    pub partial_minutes: Option<i32>,
+    /// Seconds the game window had focus, or `None` when focus couldn't be
+    /// tracked for this session.
+    pub active_seconds: Option<i32>,
+}
+
+impl PlaySession {
+    /// Seconds between launch and exit (or the last checkpoint while running),
+    /// or `None` if either timestamp is unparseable.
+    pub fn duration_secs(&self) -> Option<i64> {
+        let started = chrono::DateTime::parse_from_rfc3339(&self.started_at).ok()?;
+        let ended = chrono::DateTime::parse_from_rfc3339(&self.ended_at).ok()?;
+        Some(ended.signed_duration_since(started).num_seconds().max(0))
+    }
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = play_session)]
+pub struct NewPlaySession {
+    pub instance_id: i32,
+    pub started_at: String,
+    pub ended_at: String,
+    pub exit_code: Option<i32>,
+    pub crashed: bool,
+    pub safe_mode: bool,
+    pub partial_minutes: Option<i32>,
+    pub active_seconds: Option<i32>,
+}
@@ -13,6 +13,33 @@ pub enum ResourceType {
     World,
 }
 
+impl ResourceType {
+    /// Folder inside the game directory this type is installed into, or `None`
+    /// for types that are not installed as a single file.
+    pub fn install_dir(&self) -> Option<&'static str> {
+        match self {
+            ResourceType::Mod => Some("mods"),
+            ResourceType::ResourcePack => Some("resourcepacks"),
+            ResourceType::Shader => Some("shaderpacks"),
+            ResourceType::DataPack => Some("datapacks"),
+            ResourceType::World => Some("saves"),
+            ResourceType::Modpack => None,
+        }
+    }
+
+    /// Name stored in `installed_resource.resource_type`.
+    pub fn ledger_name(&self) -> &'static str {
+        match self {
+            ResourceType::Mod => "mod",
+            ResourceType::ResourcePack => "resourcepack",
+            ResourceType::Shader => "shader",
+            ResourceType::DataPack => "datapack",
+            ResourceType::Modpack => "modpack",
+            ResourceType::World => "world",
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum SourcePlatform {
@@ -48,10 +75,82 @@ pub struct ResourceProject {
     pub categories: Vec<String>,
     pub web_url: String,
     pub external_ids: Option<std::collections::HashMap<String, String>>,
-    pub gallery: Vec<String>,
+    pub gallery: Vec<GalleryImage>,
     pub featured_gallery: Option<String>,
     pub published_at: Option<String>,
     pub updated_at: Option<String>,
+    /// Long description with its markup, only filled by single-project fetches.
+    #[serde(default)]
+    pub body: Option<ResourceBody>,
+    #[serde(default)]
+    pub members: Vec<ResourceMember>,
+    #[serde(default)]
+    pub license: Option<ResourceLicense>,
+    #[serde(default)]
+    pub links: Option<ResourceLinks>,
+    /// "required", "optional", "unsupported" or "unknown" as reported by
+    /// Modrinth; CurseForge doesn't publish it.
+    #[serde(default)]
+    pub client_side: Option<String>,
+    #[serde(default)]
+    pub server_side: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct GalleryImage {
+    pub url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub featured: bool,
+}
+
+impl GalleryImage {
+    pub fn from_url(url: String) -> Self {
+        Self {
+            url,
+            title: None,
+            description: None,
+            featured: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyFormat {
+    Markdown,
+    Html,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ResourceBody {
+    pub format: BodyFormat,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ResourceMember {
+    pub name: String,
+    pub role: Option<String>,
+    pub avatar_url: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ResourceLicense {
+    pub id: String,
+    pub name: String,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct ResourceLinks {
+    pub website: Option<String>,
+    pub source: Option<String>,
+    pub issues: Option<String>,
+    pub wiki: Option<String>,
+    pub discord: Option<String>,
+    pub donations: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
@@ -0,0 +1,37 @@
+use crate::schema::task_history;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A task that completed, failed or was cancelled. `started_at` and
+/// `duration_ms` are null for tasks cancelled while still queued.
+#[derive(Debug, Serialize, Deserialize, Queryable, Selectable, Identifiable, Clone)]
+#[diesel(table_name = task_history)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct TaskHistoryEntry {
+    pub id: i32,
+    pub client_key: String,
+    pub name: String,
+    pub status: String,
+    pub priority: String,
+    pub queued_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: String,
+    pub duration_ms: Option<i64>,
+    pub error: Option<String>,
+    pub bytes_downloaded: i64,
+}
+
+#[derive(Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = task_history)]
+pub struct NewTaskHistoryEntry {
+    pub client_key: String,
+    pub name: String,
+    pub status: String,
+    pub priority: String,
+    pub queued_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: String,
+    pub duration_ms: Option<i64>,
+    pub error: Option<String>,
+    pub bytes_downloaded: i64,
+}
@@ -407,7 +407,7 @@ async fn download_bytes(url: &str, expected_sha1: Option<&str>) -> Result<Vec<u8
     Ok(data.to_vec())
 }
 
-async fn fetch_target_manifest(
+pub(crate) async fn fetch_target_manifest(
     app_handle: &tauri::AppHandle,
     instance: &Instance,
     target_version_id: &str,
@@ -1,3 +1,4 @@
 pub mod engine;
+pub mod repair;
 pub mod state;
 pub mod update;
@@ -0,0 +1,363 @@
+//! Modpack repair: re-download mods of the installed modpack version that are
+//! missing, corrupt or untracked.
+//!
+//! The mod list comes from the platform's copy of the version the instance was
+//! installed from, so a damaged local `modpack_manifest.json` cannot hide
+//! problems. Each mod is checked against the file on disk (SHA-1) and the
+//! `installed_resource` ledger; anything that fails is reinstalled through
+//! [`ResourceManager::install_resource`].
+
+use std::path::Path;
+
+use diesel::prelude::*;
+use piston_lib::game::modpack::manifest::{
+    compute_file_sha1, resolve_mod_path_on_disk, ModSource, ModpackManifestMod,
+};
+use serde::Serialize;
+use tauri::Manager;
+
+use crate::models::installed_resource::InstalledResource;
+use crate::models::instance::Instance;
+use crate::models::SourcePlatform;
+use crate::notifications::manager::NotificationManager;
+use crate::notifications::models::{CreateNotificationInput, NotificationType};
+use crate::resources::ResourceManager;
+use crate::utils::db::get_vesta_conn;
+use crate::utils::db_manager::get_app_config_dir;
+use crate::utils::instance_helpers::{normalize_path, resolve_instance_game_directory};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RepairReport {
+    /// Mods listed by the modpack version.
+    pub checked: usize,
+    /// Mods that were downloaded again.
+    pub repaired: usize,
+    /// Manifest paths of mods the platform no longer serves.
+    pub irreparable: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ModCondition {
+    Intact,
+    Missing,
+    Corrupt,
+    Untracked,
+}
+
+fn mod_condition(
+    expected_sha1: Option<&str>,
+    on_disk_sha1: Option<&str>,
+    tracked: bool,
+) -> ModCondition {
+    let Some(actual) = on_disk_sha1 else {
+        return ModCondition::Missing;
+    };
+    let expected = expected_sha1.filter(|hash| !hash.is_empty());
+    if expected.is_some_and(|expected| !expected.eq_ignore_ascii_case(actual)) {
+        return ModCondition::Corrupt;
+    }
+    if !tracked {
+        return ModCondition::Untracked;
+    }
+    ModCondition::Intact
+}
+
+/// Platform, project and version to reinstall `source` from, or `None` when
+/// the manifest doesn't name the project.
+fn reinstall_target(source: &ModSource) -> Option<(SourcePlatform, String, String)> {
+    match source {
+        ModSource::Modrinth {
+            project_id,
+            version_id,
+            ..
+        } => (!project_id.is_empty() && !version_id.is_empty()).then(|| {
+            (
+                SourcePlatform::Modrinth,
+                project_id.clone(),
+                version_id.clone(),
+            )
+        }),
+        ModSource::CurseForge {
+            project_id,
+            file_id,
+            ..
+        } => project_id.map(|project_id| {
+            (
+                SourcePlatform::CurseForge,
+                project_id.to_string(),
+                file_id.to_string(),
+            )
+        }),
+    }
+}
+
+fn is_tracked(
+    manifest_mod: &ModpackManifestMod,
+    disk_path: &Path,
+    resources: &[InstalledResource],
+) -> bool {
+    let disk_path = normalize_path(disk_path);
+    resources.iter().any(|resource| {
+        normalize_path(Path::new(&resource.local_path)) == disk_path
+            || crate::modpack::state::manifest_source_matches_resource(
+                &manifest_mod.source,
+                resource,
+            )
+    })
+}
+
+fn load_resources(instance_id: i32) -> Result<Vec<InstalledResource>, String> {
+    use crate::schema::installed_resource::dsl as ir_dsl;
+
+    let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
+    ir_dsl::installed_resource
+        .filter(ir_dsl::instance_id.eq(instance_id))
+        .load::<InstalledResource>(&mut conn)
+        .map_err(|e| format!("Failed to load installed resources: {}", e))
+}
+
+/// Check every mod of the instance's modpack version and reinstall the ones
+/// that are missing, corrupt or not in the ledger. Progress is shown in a
+/// notification keyed `modpack_repair_<instance id>`.
+pub async fn repair(
+    app_handle: &tauri::AppHandle,
+    instance_id: i32,
+) -> Result<RepairReport, String> {
+    use crate::schema::vesta::instance::dsl as inst_dsl;
+
+    let instance: Instance = {
+        let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
+        inst_dsl::instance
+            .filter(inst_dsl::id.eq(instance_id))
+            .first(&mut conn)
+            .map_err(|e| format!("Failed to load instance: {}", e))?
+    };
+    let (Some(_), Some(version_id), Some(_)) = (
+        instance.modpack_id.as_deref(),
+        instance.modpack_version_id.clone(),
+        instance.modpack_platform.as_deref(),
+    ) else {
+        return Err("Instance is not linked to a modpack".to_string());
+    };
+
+    let data_dir = get_app_config_dir()
+        .map_err(|e| e.to_string())?
+        .join("data");
+    let game_dir =
+        resolve_instance_game_directory(&instance, &data_dir.join("instances"), &data_dir);
+
+    let notifications = app_handle.state::<NotificationManager>();
+    let client_key = format!("modpack_repair_{}", instance_id);
+    if let Err(e) = notifications.create(CreateNotificationInput {
+        client_key: Some(client_key.clone()),
+        title: Some(format!("Repairing {}", instance.name)),
+        description: Some("Fetching modpack version...".to_string()),
+        severity: Some("info".to_string()),
+        notification_type: Some(NotificationType::Progress),
+        dismissible: Some(false),
+        persist: Some(true),
+        silent: Some(false),
+        actions: None,
+        progress: Some(0),
+        current_step: None,
+        total_steps: None,
+        metadata: None,
+        show_on_completion: Some(true),
+    }) {
+        log::warn!("[modpack-repair] Failed to create notification: {}", e);
+    }
+
+    let result = repair_mods(app_handle, &instance, &version_id, &game_dir, &client_key).await;
+    let description = match &result {
+        Ok(report) if report.irreparable.is_empty() => {
+            format!(
+                "Checked {} mods, repaired {}",
+                report.checked, report.repaired
+            )
+        }
+        Ok(report) => format!(
+            "Checked {} mods, repaired {}. No longer available: {}",
+            report.checked,
+            report.repaired,
+            report.irreparable.join(", ")
+        ),
+        Err(error) => format!("Repair failed: {}", error),
+    };
+    let _ =
+        notifications.update_progress_with_description(client_key, 100, None, None, description);
+    result
+}
+
+async fn repair_mods(
+    app_handle: &tauri::AppHandle,
+    instance: &Instance,
+    version_id: &str,
+    game_dir: &Path,
+    client_key: &str,
+) -> Result<RepairReport, String> {
+    let notifications = app_handle.state::<NotificationManager>();
+    let resource_manager = app_handle.state::<ResourceManager>();
+
+    let (manifest, _) =
+        crate::modpack::engine::fetch_target_manifest(app_handle, instance, version_id).await?;
+    let resources = load_resources(instance.id)?;
+    let total = manifest.mods.len();
+    let mut report = RepairReport {
+        checked: total,
+        ..RepairReport::default()
+    };
+
+    for (index, manifest_mod) in manifest.mods.iter().enumerate() {
+        let _ = notifications.update_progress_with_description(
+            client_key.to_string(),
+            (index * 100 / total.max(1)) as i32,
+            Some(index as i32 + 1),
+            Some(total as i32),
+            format!("Checking {}", manifest_mod.path),
+        );
+
+        let disk_path = resolve_mod_path_on_disk(game_dir, &manifest_mod.path);
+        let on_disk_sha1 = disk_path
+            .as_deref()
+            .and_then(|path| compute_file_sha1(path).ok());
+        let tracked = disk_path
+            .as_deref()
+            .is_some_and(|path| is_tracked(manifest_mod, path, &resources));
+        let condition = mod_condition(
+            manifest_mod.sha1.as_deref(),
+            on_disk_sha1.as_deref(),
+            tracked,
+        );
+        if condition == ModCondition::Intact {
+            continue;
+        }
+        log::info!(
+            "[modpack-repair] {} is {:?}, reinstalling",
+            manifest_mod.path,
+            condition
+        );
+
+        let Some((platform, project_id, mod_version_id)) = reinstall_target(&manifest_mod.source)
+        else {
+            report.irreparable.push(manifest_mod.path.clone());
+            continue;
+        };
+        if condition == ModCondition::Corrupt {
+            if let Some(path) = &disk_path {
+                let _ = tokio::fs::remove_file(path).await;
+            }
+        }
+
+        match resource_manager
+            .install_resource(platform, &project_id, &mod_version_id, instance.id)
+            .await
+        {
+            Ok(_) => report.repaired += 1,
+            Err(e) => {
+                log::warn!(
+                    "[modpack-repair] Could not reinstall {} ({} {}): {}",
+                    manifest_mod.path,
+                    project_id,
+                    mod_version_id,
+                    e
+                );
+                report.irreparable.push(manifest_mod.path.clone());
+            }
+        }
+    }
+
+    if report.repaired > 0 {
+        // Reinstalled files are recorded as custom; hand them back to the pack.
+        let resources = load_resources(instance.id)?;
+        let matched_ids =
+            crate::modpack::state::match_owned_resources(&resources, &manifest, game_dir);
+        if let Err(e) =
+            crate::modpack::state::apply_resource_provenance(instance, &resources, &matched_ids)
+        {
+            log::warn!("[modpack-repair] Failed to restore provenance: {}", e);
+        }
+    }
+
+    log::info!(
+        "[modpack-repair] instance {} checked={} repaired={} irreparable={}",
+        instance.id,
+        report.checked,
+        report.repaired,
+        report.irreparable.len()
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA: &str = "da39a3ee5e6b4b0d3255bfef95601890afd80709";
+
+    #[test]
+    fn classifies_mod_files() {
+        assert_eq!(mod_condition(Some(SHA), None, true), ModCondition::Missing);
+        assert_eq!(
+            mod_condition(Some(SHA), Some(&"0".repeat(40)), true),
+            ModCondition::Corrupt
+        );
+        assert_eq!(
+            mod_condition(Some(SHA), Some(&SHA.to_uppercase()), true),
+            ModCondition::Intact
+        );
+        assert_eq!(
+            mod_condition(Some(SHA), Some(SHA), false),
+            ModCondition::Untracked
+        );
+        // Without a published hash only presence and the ledger count.
+        assert_eq!(
+            mod_condition(Some(""), Some(SHA), true),
+            ModCondition::Intact
+        );
+        assert_eq!(mod_condition(None, Some(SHA), true), ModCondition::Intact);
+    }
+
+    #[test]
+    fn reinstall_target_needs_a_project() {
+        let modrinth = ModSource::Modrinth {
+            project_id: "AANobbMI".to_string(),
+            version_id: "mc1.21-0.6.0".to_string(),
+            url: String::new(),
+        };
+        assert_eq!(
+            reinstall_target(&modrinth),
+            Some((
+                SourcePlatform::Modrinth,
+                "AANobbMI".to_string(),
+                "mc1.21-0.6.0".to_string()
+            ))
+        );
+
+        let curseforge = ModSource::CurseForge {
+            project_id: Some(238222),
+            file_id: 5101366,
+            url: String::new(),
+        };
+        assert_eq!(
+            reinstall_target(&curseforge),
+            Some((
+                SourcePlatform::CurseForge,
+                "238222".to_string(),
+                "5101366".to_string()
+            ))
+        );
+
+        let orphan = ModSource::CurseForge {
+            project_id: None,
+            file_id: 5101366,
+            url: String::new(),
+        };
+        assert_eq!(reinstall_target(&orphan), None);
+        let unnamed = ModSource::Modrinth {
+            project_id: String::new(),
+            version_id: "abc".to_string(),
+            url: String::new(),
+        };
+        assert_eq!(reinstall_target(&unnamed), None);
+    }
+}
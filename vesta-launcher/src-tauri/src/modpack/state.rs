@@ -134,7 +134,10 @@ pub fn match_owned_resources(
     matched_ids
 }
 
-fn manifest_source_matches_resource(source: &ModSource, resource: &InstalledResource) -> bool {
+pub(crate) fn manifest_source_matches_resource(
+    source: &ModSource,
+    resource: &InstalledResource,
+) -> bool {
     match source {
         ModSource::Modrinth {
             project_id,
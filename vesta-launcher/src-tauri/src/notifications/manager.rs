@@ -1,7 +1,8 @@
 use crate::notifications::models::{
     CreateNotificationInput, Notification, NotificationSeverity, NotificationType,
+    NotificationsBulkChanged,
 };
-use crate::notifications::store::NotificationStore;
+use crate::notifications::store::{NotificationQuery, NotificationStore};
 use anyhow::Result;
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
@@ -35,6 +36,28 @@ impl ActionHandler for OpenUrlHandler {
     }
 }
 
+/// Handler that opens a local folder in the system file manager
+struct OpenFolderHandler {}
+
+impl ActionHandler for OpenFolderHandler {
+    fn handle(
+        &self,
+        _app_handle: &AppHandle,
+        _client_key: Option<String>,
+        payload: Option<serde_json::Value>,
+    ) -> Result<()> {
+        let path = payload
+            .and_then(|p| {
+                p.get("path")
+                    .and_then(|u| u.as_str())
+                    .map(|s| s.to_string())
+            })
+            .ok_or_else(|| anyhow::anyhow!("Missing path in open_folder action payload"))?;
+
+        open::that(path).map_err(|e| anyhow::anyhow!("Failed to open folder: {}", e))
+    }
+}
+
 /// Handler that navigates to a specific route in the frontend mini-router
 struct NavigateHandler {}
 
@@ -346,6 +369,7 @@ impl NotificationManager {
 
         // Register built-in action handlers
         manager.register_action("open_url", Arc::new(OpenUrlHandler {}));
+        manager.register_action("open_folder", Arc::new(OpenFolderHandler {}));
         manager.register_action("navigate", Arc::new(NavigateHandler {}));
         // Future handlers (pause, resume, etc.) can be added here
 
@@ -709,6 +733,10 @@ impl NotificationManager {
         NotificationStore::list(only_persisted, only_unread)
     }
 
+    pub fn query(&self, filter: &NotificationQuery) -> Result<Vec<Notification>> {
+        NotificationStore::query(filter)
+    }
+
     pub fn mark_read(&self, id_or_key: String) -> Result<()> {
         let id = if let Ok(id) = id_or_key.parse::<i32>() {
             id
@@ -764,6 +792,77 @@ impl NotificationManager {
         Ok(())
     }
 
+    /// Mark every notification as read. Returns how many changed.
+    pub fn mark_all_read(&self) -> Result<usize> {
+        let count = NotificationStore::mark_all_read()?;
+        self.emit_bulk_changed(count, "mark_all_read");
+        Ok(count)
+    }
+
+    /// Delete every read, dismissible notification.
+    pub fn delete_all_read(&self) -> Result<usize> {
+        let count = NotificationStore::delete_all_read()?;
+        self.emit_bulk_changed(count, "delete_all_read");
+        Ok(count)
+    }
+
+    /// Delete notifications by id or client key. Unknown entries are skipped.
+    pub fn delete_bulk(&self, ids_or_keys: Vec<String>) -> Result<usize> {
+        let mut targets: Vec<(i32, Option<String>)> = Vec::with_capacity(ids_or_keys.len());
+        for id_or_key in &ids_or_keys {
+            let existing = match id_or_key.parse::<i32>() {
+                Ok(id) => NotificationStore::get_by_id(id)?,
+                Err(_) => NotificationStore::get_by_client_key(id_or_key)?,
+            };
+            if let Some(n) = existing {
+                if let Some(id) = n.id.filter(|id| *id > 0) {
+                    targets.push((id, n.client_key));
+                }
+            }
+        }
+        targets.sort_unstable_by_key(|(id, _)| *id);
+        targets.dedup_by_key(|(id, _)| *id);
+
+        let ids: Vec<i32> = targets.iter().map(|(id, _)| *id).collect();
+        let count = NotificationStore::delete_many(&ids)?;
+
+        // The rows are gone at this point, so event failures are logged
+        // rather than reported as a failed delete.
+        for (id, client_key) in targets {
+            // Same payload as `delete` so toasts keyed by client_key close too.
+            if let Err(e) = self.app_handle.emit(
+                "core://notification-updated",
+                serde_json::json!({
+                    "id": id,
+                    "deleted": true,
+                    "client_key": client_key,
+                }),
+            ) {
+                log::warn!("Failed to emit deletion of notification {}: {}", id, e);
+            }
+        }
+        self.emit_bulk_changed(count, "delete_bulk");
+        Ok(count)
+    }
+
+    /// Tell the UI to refresh after a bulk change. Called once the change is
+    /// committed, so a failed emit is logged instead of returned.
+    fn emit_bulk_changed(&self, affected_count: usize, operation: &str) {
+        if let Err(e) = self.app_handle.emit(
+            "vesta://notifications-bulk-changed",
+            NotificationsBulkChanged {
+                affected_count,
+                operation: operation.to_string(),
+            },
+        ) {
+            log::warn!(
+                "Failed to emit notifications-bulk-changed for {}: {}",
+                operation,
+                e
+            );
+        }
+    }
+
     /// Clear all Immediate notifications (should be called on app startup)
     pub fn clear_immediate_notifications(&self) -> Result<()> {
         NotificationStore::clear_immediate_notifications()?;
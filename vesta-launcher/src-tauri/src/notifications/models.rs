@@ -145,3 +145,10 @@ pub struct NotificationAction {
     pub action_type: String,
     pub payload: Option<serde_json::Value>,
 }
+
+/// Payload of `vesta://notifications-bulk-changed`.
+#[derive(Serialize, Debug, Clone)]
+pub struct NotificationsBulkChanged {
+    pub affected_count: usize,
+    pub operation: String,
+}
@@ -6,6 +6,37 @@ use crate::schema::notification::dsl::*;
 use crate::utils::db::get_vesta_conn;
 use anyhow::Result;
 use diesel::prelude::*;
+use diesel::sqlite::Sqlite;
+
+/// Filters, ordering and paging for [`NotificationStore::query`].
+#[derive(Debug, Clone, Default)]
+pub struct NotificationQuery {
+    /// Only patient and progress notifications.
+    pub only_persisted: bool,
+    pub only_unread: bool,
+    pub notification_type: Option<String>,
+    pub severity: Option<String>,
+    /// Case-insensitive substring match on the title.
+    pub title_contains: Option<String>,
+    /// Newest first unless set.
+    pub oldest_first: bool,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// `%needle%` with LIKE wildcards in `needle` escaped, for use with `ESCAPE '\\'`.
+fn like_pattern(needle: &str) -> String {
+    let mut pattern = String::with_capacity(needle.len() + 2);
+    pattern.push('%');
+    for c in needle.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
 
 pub struct NotificationStore;
 
@@ -132,28 +163,79 @@ impl NotificationStore {
     }
 
     pub fn list(only_persisted: bool, only_unread: bool) -> Result<Vec<DomainNotification>> {
+        Self::query(&NotificationQuery {
+            only_persisted,
+            only_unread,
+            ..Default::default()
+        })
+    }
+
+    /// Load notifications matching `filter`, ordered and paged as requested.
+    pub fn query(filter: &NotificationQuery) -> Result<Vec<DomainNotification>> {
+        let mut conn =
+            get_vesta_conn().map_err(|e| anyhow::anyhow!("Failed to get database: {}", e))?;
+        Self::query_with(&mut conn, filter)
+    }
+
+    /// Number of notifications matching `filter`, ignoring paging.
+    pub fn count(filter: &NotificationQuery) -> Result<i64> {
         let mut conn =
             get_vesta_conn().map_err(|e| anyhow::anyhow!("Failed to get database: {}", e))?;
+        Self::count_with(&mut conn, filter)
+    }
+
+    fn query_with(
+        conn: &mut SqliteConnection,
+        filter: &NotificationQuery,
+    ) -> Result<Vec<DomainNotification>> {
+        let mut query = Self::filtered(filter);
+        // `id` breaks ties between notifications created in the same second.
+        query = if filter.oldest_first {
+            query.order((created_at.asc(), id.asc()))
+        } else {
+            query.order((created_at.desc(), id.desc()))
+        };
+        // SQLite only accepts OFFSET after a LIMIT; -1 means "no limit".
+        if filter.limit.is_some() || filter.offset.is_some() {
+            query = query
+                .limit(filter.limit.unwrap_or(-1))
+                .offset(filter.offset.unwrap_or(0));
+        }
+
+        let results = query.load::<DbNotification>(conn)?;
+        Ok(results.into_iter().map(Self::to_domain).collect())
+    }
+
+    fn count_with(conn: &mut SqliteConnection, filter: &NotificationQuery) -> Result<i64> {
+        Ok(Self::filtered(filter).count().get_result(conn)?)
+    }
 
+    fn filtered(
+        filter: &NotificationQuery,
+    ) -> crate::schema::notification::BoxedQuery<'static, Sqlite> {
         let mut query = notification.into_boxed();
 
-        if only_persisted {
+        if filter.only_persisted {
             query = query.filter(
                 notification_type
                     .eq("patient")
                     .or(notification_type.eq("progress")),
             );
         }
-
-        if only_unread {
+        if filter.only_unread {
             query = query.filter(read.eq(false));
         }
+        if let Some(target_type) = &filter.notification_type {
+            query = query.filter(notification_type.eq(target_type.clone()));
+        }
+        if let Some(target_severity) = &filter.severity {
+            query = query.filter(severity.eq(target_severity.clone()));
+        }
+        if let Some(needle) = filter.title_contains.as_deref().filter(|n| !n.is_empty()) {
+            query = query.filter(title.like(like_pattern(needle)).escape('\\'));
+        }
 
-        let results = query
-            .order(created_at.desc())
-            .load::<DbNotification>(&mut conn)?;
-
-        Ok(results.into_iter().map(Self::to_domain).collect())
+        query
     }
 
     pub fn mark_read(target_id: i32) -> Result<()> {
@@ -176,6 +258,53 @@ impl NotificationStore {
         Ok(())
     }
 
+    /// Mark every unread notification as read.
+    pub fn mark_all_read() -> Result<usize> {
+        let mut conn =
+            get_vesta_conn().map_err(|e| anyhow::anyhow!("Failed to get database: {}", e))?;
+
+        let count = diesel::update(notification.filter(read.eq(false)))
+            .set(read.eq(true))
+            .execute(&mut conn)?;
+
+        Ok(count)
+    }
+
+    /// Delete read notifications the user is allowed to dismiss.
+    pub fn delete_all_read() -> Result<usize> {
+        let mut conn =
+            get_vesta_conn().map_err(|e| anyhow::anyhow!("Failed to get database: {}", e))?;
+
+        let count = diesel::delete(
+            notification
+                .filter(read.eq(true))
+                .filter(dismissible.eq(true)),
+        )
+        .execute(&mut conn)?;
+
+        Ok(count)
+    }
+
+    /// Delete notifications by id in one transaction. Ids are bound as
+    /// `id IN (?, ?, ...)` parameters, in chunks to stay under SQLite's
+    /// variable limit.
+    pub fn delete_many(target_ids: &[i32]) -> Result<usize> {
+        const CHUNK: usize = 500;
+        let mut conn =
+            get_vesta_conn().map_err(|e| anyhow::anyhow!("Failed to get database: {}", e))?;
+
+        let count = conn.transaction::<usize, diesel::result::Error, _>(|conn| {
+            let mut count = 0;
+            for chunk in target_ids.chunks(CHUNK) {
+                count +=
+                    diesel::delete(notification.filter(id.eq_any(chunk.to_vec()))).execute(conn)?;
+            }
+            Ok(count)
+        })?;
+
+        Ok(count)
+    }
+
     pub fn cleanup(retention_days: i32) -> Result<usize> {
         let mut conn =
             get_vesta_conn().map_err(|e| anyhow::anyhow!("Failed to get database: {}", e))?;
@@ -257,3 +386,167 @@ impl NotificationStore {
         Ok(results.into_iter().map(Self::to_domain).collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::notification::NewNotification;
+    use diesel_migrations::MigrationHarness;
+
+    fn insert(
+        conn: &mut SqliteConnection,
+        notification_title: &str,
+        kind: &str,
+        level: &str,
+        is_read: bool,
+        created: &str,
+    ) {
+        diesel::insert_into(notification)
+            .values(NewNotification {
+                client_key: None,
+                title: Some(notification_title.to_string()),
+                description: None,
+                severity: level.to_string(),
+                notification_type: kind.to_string(),
+                dismissible: true,
+                progress: None,
+                current_step: None,
+                total_steps: None,
+                read: is_read,
+                actions: None,
+                metadata: None,
+                created_at: created.to_string(),
+                updated_at: created.to_string(),
+                expires_at: None,
+                show_on_completion: None,
+            })
+            .execute(conn)
+            .unwrap();
+    }
+
+    fn seeded_conn() -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.run_pending_migrations(crate::utils::db::VESTA_MIGRATIONS)
+            .unwrap();
+        insert(
+            &mut conn,
+            "Update ready",
+            "patient",
+            "info",
+            false,
+            "2026-01-01T00:00:00Z",
+        );
+        insert(
+            &mut conn,
+            "Crash: 100% CPU",
+            "immediate",
+            "error",
+            true,
+            "2026-01-02T00:00:00Z",
+        );
+        insert(
+            &mut conn,
+            "Installing mods",
+            "progress",
+            "info",
+            false,
+            "2026-01-03T00:00:00Z",
+        );
+        insert(
+            &mut conn,
+            "Crash_report saved",
+            "patient",
+            "warning",
+            true,
+            "2026-01-04T00:00:00Z",
+        );
+        conn
+    }
+
+    fn titles(conn: &mut SqliteConnection, filter: &NotificationQuery) -> Vec<String> {
+        NotificationStore::query_with(conn, filter)
+            .unwrap()
+            .into_iter()
+            .map(|n| n.title)
+            .collect()
+    }
+
+    #[test]
+    fn combines_filters() {
+        let mut conn = seeded_conn();
+        let filter = NotificationQuery {
+            only_persisted: true,
+            only_unread: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            titles(&mut conn, &filter),
+            vec!["Installing mods", "Update ready"]
+        );
+
+        let filter = NotificationQuery {
+            notification_type: Some("patient".to_string()),
+            severity: Some("warning".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(titles(&mut conn, &filter), vec!["Crash_report saved"]);
+    }
+
+    #[test]
+    fn title_search_treats_wildcards_literally() {
+        let mut conn = seeded_conn();
+        let search = |needle: &str| NotificationQuery {
+            title_contains: Some(needle.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            titles(&mut conn, &search("crash")),
+            vec!["Crash_report saved", "Crash: 100% CPU"]
+        );
+        assert_eq!(titles(&mut conn, &search("100%")), vec!["Crash: 100% CPU"]);
+        assert_eq!(
+            titles(&mut conn, &search("h_r")),
+            vec!["Crash_report saved"]
+        );
+        assert_eq!(titles(&mut conn, &search("%")), vec!["Crash: 100% CPU"]);
+    }
+
+    #[test]
+    fn orders_and_pages() {
+        let mut conn = seeded_conn();
+        let filter = NotificationQuery {
+            oldest_first: true,
+            limit: Some(2),
+            offset: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            titles(&mut conn, &filter),
+            vec!["Crash: 100% CPU", "Installing mods"]
+        );
+
+        let offset_only = NotificationQuery {
+            offset: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(titles(&mut conn, &offset_only), vec!["Update ready"]);
+    }
+
+    #[test]
+    fn count_ignores_paging() {
+        let mut conn = seeded_conn();
+        let filter = NotificationQuery {
+            only_unread: true,
+            limit: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            NotificationStore::count_with(&mut conn, &filter).unwrap(),
+            2
+        );
+        assert_eq!(
+            NotificationStore::count_with(&mut conn, &NotificationQuery::default()).unwrap(),
+            4
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use crate::models::{NewNotificationSeenItem, NotificationSubscription};
 use crate::notifications::manager::NotificationManager;
-use crate::notifications::models::{CreateNotificationInput, NotificationType};
-use crate::notifications::subscriptions::{providers::*, SubscriptionProvider};
+use crate::notifications::models::{CreateNotificationInput, NotificationAction, NotificationType};
+use crate::notifications::subscriptions::{presets, providers::*, SubscriptionProvider};
 use anyhow::Result;
 use diesel::prelude::*;
 use std::sync::Arc;
@@ -32,46 +32,48 @@ impl SubscriptionManager {
         let subs = self.get_enabled_subscriptions()?;
 
         for sub in subs {
-            let provider = self
-                .providers
-                .iter()
-                .find(|p| p.provider_type() == sub.provider_type);
-
-            if let Some(provider) = provider {
-                match provider.check(&self.app_handle, &sub).await {
-                    Ok(items) => {
-                        let is_first_run = sub.last_checked.is_none();
-
-                        for item in items {
-                            if !self.is_seen(&sub.id, &item.id)? {
-                                // If this is a new subscription, don't spam notifications for old items
-                                if !is_first_run {
-                                    if let Ok(_) = self.create_notification(&sub, &item) {
-                                        if let Err(e) = self.mark_seen(&sub.id, &item.id) {
-                                            log::error!(
-                                                "Failed to mark item seen {}: {}",
-                                                item.id,
-                                                e
-                                            );
-                                        }
+            self.check_subscription(&sub).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn check_subscription(&self, sub: &NotificationSubscription) -> Result<()> {
+        let provider = self
+            .providers
+            .iter()
+            .find(|p| p.provider_type() == sub.provider_type);
+
+        if let Some(provider) = provider {
+            match provider.check(&self.app_handle, sub).await {
+                Ok(items) => {
+                    let is_first_run = sub.last_checked.is_none();
+
+                    for item in items {
+                        if !self.is_seen(&sub.id, &item.id)? {
+                            // If this is a new subscription, don't spam notifications for old items
+                            if !is_first_run {
+                                if let Ok(_) = self.create_notification(sub, &item) {
+                                    if let Err(e) = self.mark_seen(&sub.id, &item.id) {
+                                        log::error!("Failed to mark item seen {}: {}", item.id, e);
                                     }
-                                } else {
-                                    // Just mark as seen for the first run
-                                    let _ = self.mark_seen(&sub.id, &item.id);
                                 }
+                            } else {
+                                // Just mark as seen for the first run
+                                let _ = self.mark_seen(&sub.id, &item.id);
                             }
                         }
-
-                        // Update last_checked
-                        let _ = self.update_last_checked(&sub.id);
-                    }
-                    Err(e) => {
-                        log::error!("Failed to check subscription {}: {}", sub.title, e);
                     }
+
+                    // Update last_checked
+                    let _ = self.update_last_checked(&sub.id);
+                }
+                Err(e) => {
+                    log::error!("Failed to check subscription {}: {}", sub.title, e);
                 }
-            } else {
-                log::warn!("No provider found for type: {}", sub.provider_type);
             }
+        } else {
+            log::warn!("No provider found for type: {}", sub.provider_type);
         }
 
         Ok(())
@@ -151,18 +153,20 @@ impl SubscriptionManager {
     ) -> Result<()> {
         let nm = self.app_handle.state::<NotificationManager>();
 
-        let actions = if let Some(link) = &item.link {
-            let action = serde_json::json!([{
-                "label": "Read More",
-                "id": "open_url",
-                "type": "primary",
-                "payload": {
-                    "url": link
-                }
-            }]);
-            Some(action.to_string())
-        } else {
+        let mut actions = Vec::new();
+        if let Some(link) = &item.link {
+            actions.push(NotificationAction {
+                label: "Read More".to_string(),
+                action_id: "open_url".to_string(),
+                action_type: "primary".to_string(),
+                payload: Some(serde_json::json!({ "url": link })),
+            });
+        }
+        actions.extend(item.actions.iter().cloned());
+        let actions = if actions.is_empty() {
             None
+        } else {
+            Some(serde_json::to_string(&actions)?)
         };
 
         nm.create(CreateNotificationInput {
@@ -342,6 +346,125 @@ impl SubscriptionManager {
         self.subscribe("rss".to_string(), title_str, Some(url_str), None, None)
     }
 
+    /// Write all subscriptions to a shareable preset file. Returns the number exported.
+    pub fn export_subscriptions(&self, dest: &std::path::Path) -> Result<usize> {
+        let subs = self.get_all_subscriptions()?;
+        let preset = presets::build_preset(&subs);
+        std::fs::write(dest, serde_json::to_string_pretty(&preset)?)?;
+        Ok(subs.len())
+    }
+
+    /// Import a preset file. With `merge` the entries are added next to the
+    /// existing subscriptions, otherwise they replace them. Initial checks of
+    /// the new subscriptions are staggered to avoid hitting every feed at once.
+    pub fn import_subscriptions(
+        self: &Arc<Self>,
+        path: &std::path::Path,
+        merge: bool,
+    ) -> Result<presets::SubscriptionImportResult> {
+        use crate::schema::notification_subscriptions::dsl::*;
+
+        let content = std::fs::read_to_string(path)?;
+        let entries = presets::parse_preset(&content).map_err(|e| anyhow::anyhow!(e))?;
+        let known_types: Vec<&str> = self.providers.iter().map(|p| p.provider_type()).collect();
+
+        let existing = if merge {
+            self.get_all_subscriptions()?
+                .iter()
+                .map(|sub| {
+                    presets::subscription_key(
+                        &sub.provider_type,
+                        sub.target_url.as_deref(),
+                        sub.target_id.as_deref(),
+                    )
+                })
+                .collect()
+        } else {
+            std::collections::HashSet::new()
+        };
+        let (accepted, skipped) = presets::plan_import(entries, &existing, &known_types);
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let new_subs: Vec<_> = accepted
+            .iter()
+            .map(
+                |entry| crate::models::notification_subscription::NewNotificationSubscription {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    provider_type: entry.provider_type.clone(),
+                    target_url: entry.target_url.clone(),
+                    target_id: entry.target_id.clone(),
+                    title: entry.title.clone(),
+                    enabled: entry.enabled,
+                    metadata: entry.metadata_string(),
+                    last_checked: None,
+                    created_at: now.clone(),
+                    updated_at: now.clone(),
+                },
+            )
+            .collect();
+
+        let mut conn = crate::utils::db::get_vesta_conn()?;
+        let removed = conn.transaction::<_, anyhow::Error, _>(|conn| {
+            let removed = if merge {
+                0
+            } else {
+                diesel::delete(notification_subscriptions).execute(conn)?
+            };
+            diesel::insert_into(notification_subscriptions)
+                .values(&new_subs)
+                .execute(conn)?;
+            Ok(removed)
+        })?;
+
+        let to_check: Vec<String> = new_subs
+            .iter()
+            .filter(|sub| sub.enabled)
+            .map(|sub| sub.id.clone())
+            .collect();
+        self.clone().schedule_initial_checks(to_check);
+
+        Ok(presets::SubscriptionImportResult {
+            imported: new_subs.into_iter().map(|sub| sub.id).collect(),
+            skipped,
+            removed,
+        })
+    }
+
+    fn schedule_initial_checks(self: Arc<Self>, sub_ids: Vec<String>) {
+        if sub_ids.is_empty() {
+            return;
+        }
+        let delays = presets::stagger_delays(sub_ids.len(), presets::IMPORT_CHECK_WINDOW);
+        tauri::async_runtime::spawn(async move {
+            let mut elapsed = std::time::Duration::ZERO;
+            for (sub_id, delay) in sub_ids.into_iter().zip(delays) {
+                tokio::time::sleep(delay.saturating_sub(elapsed)).await;
+                elapsed = delay;
+
+                let sub = {
+                    use crate::schema::notification_subscriptions::dsl::*;
+                    crate::utils::db::get_vesta_conn().and_then(|mut conn| {
+                        notification_subscriptions
+                            .filter(id.eq(&sub_id))
+                            .filter(enabled.eq(true))
+                            .first::<NotificationSubscription>(&mut conn)
+                            .optional()
+                            .map_err(|e| anyhow::anyhow!(e))
+                    })
+                };
+                match sub {
+                    Ok(Some(sub)) => {
+                        if let Err(e) = self.check_subscription(&sub).await {
+                            log::error!("Initial check of {} failed: {}", sub.title, e);
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(e) => log::error!("Failed to load imported subscription: {}", e),
+                }
+            }
+        });
+    }
+
     /// Initialize default subscriptions if they don't exist
     pub fn initialize_defaults(&self) -> Result<()> {
         use crate::schema::notification_subscriptions::dsl::*;
@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
 pub mod manager;
+pub mod presets;
 pub mod providers;
 
 #[async_trait]
@@ -38,6 +39,9 @@ pub struct NotificationUpdateItem {
     pub metadata: serde_json::Value,
     pub severity: Option<String>,
     pub silent: Option<bool>,
+    /// Extra actions shown after "Read More" when the item has a link.
+    #[serde(default)]
+    pub actions: Vec<crate::notifications::models::NotificationAction>,
 }
 
 pub fn clean_and_truncate(text: &str, max_len: usize) -> String {
@@ -0,0 +1,322 @@
+//! Shareable subscription preset files.
+//!
+//! A preset holds only what is needed to recreate a subscription: provider,
+//! target, title, enabled flag and filter metadata. Seen-items and
+//! `last_checked` stay local, so importing a preset behaves like subscribing
+//! fresh (the first check only marks existing items as seen).
+
+use crate::models::NotificationSubscription;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::Duration;
+
+pub const PRESET_FORMAT_VERSION: u32 = 1;
+
+/// Initial checks of imported subscriptions are spread over this window.
+pub const IMPORT_CHECK_WINDOW: Duration = Duration::from_secs(3 * 60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionPreset {
+    pub version: u32,
+    pub subscriptions: Vec<PresetSubscription>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PresetSubscription {
+    pub provider_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_id: Option<String>,
+    pub title: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedPresetEntry {
+    pub index: usize,
+    pub title: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubscriptionImportResult {
+    pub imported: Vec<String>,
+    pub skipped: Vec<SkippedPresetEntry>,
+    /// Existing subscriptions removed because the import replaced them.
+    pub removed: usize,
+}
+
+/// Identity used to dedupe subscriptions: provider plus its target.
+pub type SubscriptionKey = (String, Option<String>, Option<String>);
+
+pub fn subscription_key(
+    provider_type: &str,
+    target_url: Option<&str>,
+    target_id: Option<&str>,
+) -> SubscriptionKey {
+    (
+        provider_type.to_string(),
+        target_url.map(|url| url.trim_end_matches('/').to_string()),
+        target_id.map(str::to_string),
+    )
+}
+
+impl PresetSubscription {
+    pub fn key(&self) -> SubscriptionKey {
+        subscription_key(
+            &self.provider_type,
+            self.target_url.as_deref(),
+            self.target_id.as_deref(),
+        )
+    }
+
+    pub fn metadata_string(&self) -> Option<String> {
+        self.metadata.as_ref().map(|value| value.to_string())
+    }
+}
+
+pub fn build_preset(subscriptions: &[NotificationSubscription]) -> SubscriptionPreset {
+    SubscriptionPreset {
+        version: PRESET_FORMAT_VERSION,
+        subscriptions: subscriptions
+            .iter()
+            .map(|sub| PresetSubscription {
+                provider_type: sub.provider_type.clone(),
+                target_url: sub.target_url.clone(),
+                target_id: sub.target_id.clone(),
+                title: sub.title.clone(),
+                enabled: sub.enabled,
+                // Stored metadata that is not valid JSON is kept as a string.
+                metadata: sub.metadata.as_ref().map(|raw| {
+                    serde_json::from_str(raw)
+                        .unwrap_or_else(|_| serde_json::Value::String(raw.clone()))
+                }),
+            })
+            .collect(),
+    }
+}
+
+/// Parse a preset file. Entries are kept as raw JSON so one malformed entry
+/// is reported and skipped instead of rejecting the whole file.
+pub fn parse_preset(content: &str) -> Result<Vec<serde_json::Value>, String> {
+    #[derive(Deserialize)]
+    struct RawPreset {
+        version: u32,
+        subscriptions: Vec<serde_json::Value>,
+    }
+
+    let raw: RawPreset = serde_json::from_str(content)
+        .map_err(|e| format!("Not a subscription preset file: {}", e))?;
+    if raw.version > PRESET_FORMAT_VERSION {
+        return Err(format!(
+            "Preset format version {} is newer than supported version {}",
+            raw.version, PRESET_FORMAT_VERSION
+        ));
+    }
+    Ok(raw.subscriptions)
+}
+
+pub fn validate_entry(entry: &PresetSubscription, known_types: &[&str]) -> Result<(), String> {
+    if !known_types.contains(&entry.provider_type.as_str()) {
+        return Err(format!("Unknown provider type '{}'", entry.provider_type));
+    }
+    if entry.title.trim().is_empty() {
+        return Err("Missing title".to_string());
+    }
+    match entry.provider_type.as_str() {
+        "rss" => {
+            let url = entry.target_url.as_deref().ok_or("RSS feed has no URL")?;
+            let parsed = url::Url::parse(url).map_err(|e| format!("Invalid feed URL: {}", e))?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                return Err(format!("Unsupported feed URL scheme '{}'", parsed.scheme()));
+            }
+        }
+        "resource" if entry.target_id.as_deref().is_none_or(str::is_empty) => {
+            return Err("Resource subscription has no project id".to_string());
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Split raw preset entries into the ones to insert and the ones to skip.
+/// Entries matching `existing` or an earlier entry in the file are duplicates.
+pub fn plan_import(
+    entries: Vec<serde_json::Value>,
+    existing: &HashSet<SubscriptionKey>,
+    known_types: &[&str],
+) -> (Vec<PresetSubscription>, Vec<SkippedPresetEntry>) {
+    let mut seen = existing.clone();
+    let mut accepted = Vec::new();
+    let mut skipped = Vec::new();
+
+    for (index, raw) in entries.into_iter().enumerate() {
+        let title = raw
+            .get("title")
+            .and_then(|title| title.as_str())
+            .map(str::to_string);
+        let entry = match serde_json::from_value::<PresetSubscription>(raw) {
+            Ok(entry) => entry,
+            Err(e) => {
+                skipped.push(SkippedPresetEntry {
+                    index,
+                    title,
+                    reason: format!("Malformed entry: {}", e),
+                });
+                continue;
+            }
+        };
+        if let Err(reason) = validate_entry(&entry, known_types) {
+            skipped.push(SkippedPresetEntry {
+                index,
+                title,
+                reason,
+            });
+            continue;
+        }
+        if !seen.insert(entry.key()) {
+            skipped.push(SkippedPresetEntry {
+                index,
+                title,
+                reason: "Already subscribed".to_string(),
+            });
+            continue;
+        }
+        accepted.push(entry);
+    }
+
+    (accepted, skipped)
+}
+
+/// Delay before the initial check of each of `count` imported subscriptions,
+/// spread evenly across `window`.
+pub fn stagger_delays(count: usize, window: Duration) -> Vec<Duration> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let step = window / count as u32;
+    (0..count).map(|i| step * i as u32).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KNOWN: &[&str] = &["news", "patch_notes", "rss", "resource", "game"];
+
+    fn stored(provider: &str, url: Option<&str>, target: Option<&str>) -> NotificationSubscription {
+        NotificationSubscription {
+            id: uuid::Uuid::new_v4().to_string(),
+            provider_type: provider.to_string(),
+            target_url: url.map(str::to_string),
+            target_id: target.map(str::to_string),
+            title: format!("{} feed", provider),
+            enabled: true,
+            metadata: Some(r#"{"loaders":["neoforge"]}"#.to_string()),
+            last_checked: Some("2026-10-01T00:00:00Z".to_string()),
+            created_at: "2026-10-01T00:00:00Z".to_string(),
+            updated_at: "2026-10-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn export_import_round_trip() {
+        let subs = vec![
+            stored("rss", Some("https://neoforged.net/index.xml"), None),
+            stored("resource", None, Some("AANobbMI")),
+            stored("game", None, None),
+        ];
+        let json = serde_json::to_string_pretty(&build_preset(&subs)).unwrap();
+        assert!(!json.contains("last_checked"));
+
+        let (accepted, skipped) = plan_import(parse_preset(&json).unwrap(), &HashSet::new(), KNOWN);
+
+        assert!(skipped.is_empty(), "{:?}", skipped);
+        assert_eq!(accepted.len(), 3);
+        for (entry, sub) in accepted.iter().zip(&subs) {
+            assert_eq!(entry.provider_type, sub.provider_type);
+            assert_eq!(entry.target_url, sub.target_url);
+            assert_eq!(entry.target_id, sub.target_id);
+            assert_eq!(entry.title, sub.title);
+            assert_eq!(entry.metadata_string(), sub.metadata);
+        }
+    }
+
+    #[test]
+    fn import_dedupes_against_existing_and_within_file() {
+        let subs = vec![
+            stored("rss", Some("https://fabricmc.net/feed.xml"), None),
+            stored("rss", Some("https://quiltmc.org/feed.xml"), None),
+            stored("rss", Some("https://quiltmc.org/feed.xml/"), None),
+        ];
+        let json = serde_json::to_string(&build_preset(&subs)).unwrap();
+        let existing = HashSet::from([subscription_key(
+            "rss",
+            Some("https://fabricmc.net/feed.xml"),
+            None,
+        )]);
+
+        let (accepted, skipped) = plan_import(parse_preset(&json).unwrap(), &existing, KNOWN);
+
+        assert_eq!(accepted.len(), 1);
+        assert_eq!(
+            accepted[0].target_url.as_deref(),
+            Some("https://quiltmc.org/feed.xml")
+        );
+        let indexes: Vec<usize> = skipped.iter().map(|s| s.index).collect();
+        assert_eq!(indexes, [0, 2]);
+        assert!(skipped.iter().all(|s| s.reason == "Already subscribed"));
+    }
+
+    #[test]
+    fn malformed_file_and_entries() {
+        assert!(parse_preset("not json").is_err());
+        assert!(parse_preset(r#"{"subscriptions": []}"#).is_err());
+        assert!(parse_preset(r#"{"version": 99, "subscriptions": []}"#).is_err());
+
+        let content = r#"{
+            "version": 1,
+            "subscriptions": [
+                {"provider_type": "telegram", "title": "Unknown"},
+                {"provider_type": "rss", "title": "No URL"},
+                {"provider_type": "rss", "title": "Local", "target_url": "file:///etc/passwd"},
+                {"provider_type": "resource", "title": "No project"},
+                {"title": "No provider"},
+                "just a string",
+                {"provider_type": "news", "title": "Mojang News", "enabled": false}
+            ]
+        }"#;
+        let (accepted, skipped) =
+            plan_import(parse_preset(content).unwrap(), &HashSet::new(), KNOWN);
+
+        assert_eq!(accepted.len(), 1);
+        assert_eq!(accepted[0].title, "Mojang News");
+        assert!(!accepted[0].enabled);
+        let reasons: Vec<(usize, &str)> = skipped
+            .iter()
+            .map(|s| (s.index, s.reason.as_str()))
+            .collect();
+        assert_eq!(reasons[0], (0, "Unknown provider type 'telegram'"));
+        assert_eq!(reasons[1], (1, "RSS feed has no URL"));
+        assert_eq!(reasons[2], (2, "Unsupported feed URL scheme 'file'"));
+        assert_eq!(reasons[3], (3, "Resource subscription has no project id"));
+        assert!(reasons[4].1.starts_with("Malformed entry"));
+        assert_eq!(skipped[4].title, Some("No provider".to_string()));
+        assert!(reasons[5].1.starts_with("Malformed entry"));
+    }
+
+    #[test]
+    fn initial_checks_are_staggered_across_window() {
+        let delays = stagger_delays(4, Duration::from_secs(120));
+        assert_eq!(delays, [0, 30, 60, 90].map(Duration::from_secs).to_vec());
+        assert!(stagger_delays(0, IMPORT_CHECK_WINDOW).is_empty());
+    }
+}
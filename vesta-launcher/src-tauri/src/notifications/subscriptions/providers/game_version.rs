@@ -58,6 +58,7 @@ impl SubscriptionProvider for GameVersionProvider {
                 }),
                 severity: Some("info".to_string()),
                 silent: Some(true),
+                actions: Vec::new(),
             });
             // Mark notified so we don't spam if polling runs again before user marks seen
             let _ = VersionTrackingRepository::mark_notified("minecraft_release", latest_release);
@@ -80,6 +81,7 @@ impl SubscriptionProvider for GameVersionProvider {
                 }),
                 severity: Some("info".to_string()),
                 silent: Some(true),
+                actions: Vec::new(),
             });
             let _ = VersionTrackingRepository::mark_notified("minecraft_snapshot", latest_snapshot);
         }
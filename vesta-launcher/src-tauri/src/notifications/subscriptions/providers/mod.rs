@@ -1,4 +1,5 @@
 pub mod game_version;
+pub mod mojang_content;
 pub mod mojang_news;
 pub mod patch_notes;
 pub mod resource;
@@ -0,0 +1,307 @@
+//! Helpers shared by the Mojang launcher-content feeds (news and patch notes):
+//! picking the user's article locale and caching header images so the
+//! notification center can render them offline.
+
+use crate::notifications::subscriptions::NotificationUpdateItem;
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+pub const LAUNCHER_CONTENT_BASE_URL: &str = "https://launchercontent.mojang.com";
+
+/// Locale used when the configured language has no minecraft.net edition.
+pub const FALLBACK_LOCALE: &str = "en-us";
+
+/// Upper bound for cached feed images; least recently used files go first.
+pub const IMAGE_CACHE_MAX_BYTES: u64 = 32 * 1024 * 1024;
+
+/// minecraft.net article locales, keyed by the tags the launcher may store
+/// in `language` (lowercase, `-` separated).
+const ARTICLE_LOCALES: &[(&str, &str)] = &[
+    ("en", "en-us"),
+    ("en-us", "en-us"),
+    ("en-gb", "en-gb"),
+    ("de", "de-de"),
+    ("es", "es-es"),
+    ("es-mx", "es-mx"),
+    ("fr", "fr-fr"),
+    ("fr-ca", "fr-ca"),
+    ("it", "it-it"),
+    ("ja", "ja-jp"),
+    ("ko", "ko-kr"),
+    ("nl", "nl-nl"),
+    ("pl", "pl-pl"),
+    ("pt", "pt-br"),
+    ("pt-br", "pt-br"),
+    ("pt-pt", "pt-pt"),
+    ("ru", "ru-ru"),
+    ("sv", "sv-se"),
+    ("zh", "zh-hans"),
+    ("zh-cn", "zh-hans"),
+    ("zh-hans", "zh-hans"),
+    ("zh-tw", "zh-hant"),
+    ("zh-hant", "zh-hant"),
+];
+
+/// minecraft.net locale for a configured language, e.g. `de` -> `de-de`.
+/// Regional tags fall back to their base language, then to English.
+pub fn article_locale(language: &str) -> &'static str {
+    let tag = language.trim().to_lowercase().replace('_', "-");
+    let lookup = |key: &str| {
+        ARTICLE_LOCALES
+            .iter()
+            .find(|(candidate, _)| *candidate == key)
+            .map(|(_, locale)| *locale)
+    };
+    lookup(&tag)
+        .or_else(|| lookup(tag.split('-').next().unwrap_or_default()))
+        .unwrap_or(FALLBACK_LOCALE)
+}
+
+/// The configured UI language's article locale.
+pub fn user_article_locale() -> &'static str {
+    match crate::utils::config::get_app_config() {
+        Ok(config) => article_locale(&config.language),
+        Err(e) => {
+            log::debug!("[mojang-content] Using default locale: {}", e);
+            FALLBACK_LOCALE
+        }
+    }
+}
+
+/// `Accept-Language` value asking for `locale` with English as fallback.
+pub fn accept_language(locale: &str) -> String {
+    if locale == FALLBACK_LOCALE {
+        "en-US".to_string()
+    } else {
+        format!("{}, en-US;q=0.8", locale)
+    }
+}
+
+/// Point a minecraft.net article link at `locale`'s edition.
+pub fn localize_article_link(link: &str, locale: &str) -> String {
+    let Some(start) = link.find("minecraft.net/") else {
+        return link.to_string();
+    };
+    let path_start = start + "minecraft.net/".len();
+    let rest = &link[path_start..];
+    let segment_end = rest.find('/').unwrap_or(rest.len());
+    let segment = &rest[..segment_end];
+    let is_locale = segment.len() >= 5
+        && segment.as_bytes()[2] == b'-'
+        && segment.as_bytes()[..2].iter().all(u8::is_ascii_lowercase);
+    if !is_locale {
+        return link.to_string();
+    }
+    format!("{}{}{}", &link[..path_start], locale, &rest[segment_end..])
+}
+
+/// Absolute URL for a feed asset path such as `/images/news.jpg`.
+pub fn content_url(path: &str) -> String {
+    if path.starts_with("http://") || path.starts_with("https://") {
+        path.to_string()
+    } else {
+        format!(
+            "{}/{}",
+            LAUNCHER_CONTENT_BASE_URL,
+            path.trim_start_matches('/')
+        )
+    }
+}
+
+/// Size-bounded image cache; file modification time doubles as last use.
+pub struct FeedImageCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl FeedImageCache {
+    pub fn new(dir: PathBuf, max_bytes: u64) -> Self {
+        Self { dir, max_bytes }
+    }
+
+    /// The cache shared by the launcher, under the app cache directory.
+    pub fn for_app() -> Result<Self> {
+        let config_dir = crate::utils::db_manager::get_app_config_dir()?;
+        Ok(Self::new(
+            crate::utils::storage::artifact_cache_dir(&config_dir).join("news_images"),
+            IMAGE_CACHE_MAX_BYTES,
+        ))
+    }
+
+    fn path_for(&self, url: &str) -> PathBuf {
+        let hash = hex::encode(Sha256::digest(url.as_bytes()));
+        let extension = url
+            .split(['?', '#'])
+            .next()
+            .and_then(|path| Path::new(path).extension())
+            .and_then(|ext| ext.to_str())
+            .filter(|ext| ext.len() <= 4 && ext.chars().all(|c| c.is_ascii_alphanumeric()))
+            .unwrap_or("img");
+        self.dir.join(format!("{}.{}", &hash[..32], extension))
+    }
+
+    /// Cached copy of `url`, downloading it on a miss.
+    pub async fn get_or_fetch(&self, url: &str) -> Result<PathBuf> {
+        let path = self.path_for(url);
+        if path.is_file() {
+            touch(&path);
+            return Ok(path);
+        }
+
+        let response = piston_lib::client::shared_client().get(url).send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to fetch image {}: {}", url, response.status());
+        }
+        let bytes = response.bytes().await?;
+        self.store(&path, &bytes)?;
+        Ok(path)
+    }
+
+    fn store(&self, path: &Path, bytes: &[u8]) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let partial = path.with_extension("partial");
+        std::fs::write(&partial, bytes)?;
+        std::fs::rename(&partial, path)?;
+        self.evict();
+        Ok(())
+    }
+
+    /// Drop the least recently used images until the cache fits its budget.
+    fn evict(&self) {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        let mut files: Vec<(SystemTime, u64, PathBuf)> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                if !metadata.is_file() {
+                    return None;
+                }
+                Some((metadata.modified().ok()?, metadata.len(), entry.path()))
+            })
+            .collect();
+        let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+        files.sort();
+        for (_, size, path) in files {
+            if total <= self.max_bytes {
+                break;
+            }
+            if std::fs::remove_file(&path).is_ok() {
+                total = total.saturating_sub(size);
+            }
+        }
+    }
+}
+
+fn touch(path: &Path) {
+    if let Ok(file) = std::fs::File::options().append(true).open(path) {
+        let _ = file.set_modified(SystemTime::now());
+    }
+}
+
+/// Local path of `url` in the app's feed image cache, or `None` if it could
+/// not be cached; the notification then falls back to the remote URL.
+pub async fn cached_image_path(url: &str) -> Option<String> {
+    let cache = FeedImageCache::for_app()
+        .map_err(|e| log::debug!("[mojang-content] Image cache unavailable: {}", e))
+        .ok()?;
+    match cache.get_or_fetch(url).await {
+        Ok(path) => Some(path.to_string_lossy().to_string()),
+        Err(e) => {
+            log::debug!("[mojang-content] Failed to cache {}: {}", url, e);
+            None
+        }
+    }
+}
+
+/// Add `image_path` to an item's metadata when its `image_url` can be cached.
+pub async fn attach_cached_image(item: &mut NotificationUpdateItem) {
+    let Some(url) = item
+        .metadata
+        .get("image_url")
+        .and_then(|value| value.as_str())
+        .map(str::to_string)
+    else {
+        return;
+    };
+    if let Some(path) = cached_image_path(&url).await {
+        item.metadata["image_path"] = serde_json::Value::String(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn article_locale_falls_back_to_english() {
+        assert_eq!(article_locale("en"), "en-us");
+        assert_eq!(article_locale("de"), "de-de");
+        assert_eq!(article_locale("de_AT"), "de-de");
+        assert_eq!(article_locale("pt-PT"), "pt-pt");
+        assert_eq!(article_locale("zh-TW"), "zh-hant");
+        assert_eq!(article_locale("tlh"), "en-us");
+        assert_eq!(article_locale(""), "en-us");
+    }
+
+    #[test]
+    fn article_links_switch_locale_segment() {
+        assert_eq!(
+            localize_article_link(
+                "https://www.minecraft.net/en-us/article/minecraft-java-edition-1-21-5",
+                "de-de"
+            ),
+            "https://www.minecraft.net/de-de/article/minecraft-java-edition-1-21-5"
+        );
+        assert_eq!(
+            localize_article_link("https://www.minecraft.net/article/x", "de-de"),
+            "https://www.minecraft.net/article/x"
+        );
+        assert_eq!(
+            localize_article_link("https://example.com/en-us/article/x", "de-de"),
+            "https://example.com/en-us/article/x"
+        );
+    }
+
+    #[test]
+    fn content_urls_resolve_against_launcher_content() {
+        assert_eq!(
+            content_url("/images/news.jpg"),
+            "https://launchercontent.mojang.com/images/news.jpg"
+        );
+        assert_eq!(
+            content_url("https://cdn.example.com/a.png"),
+            "https://cdn.example.com/a.png"
+        );
+    }
+
+    #[test]
+    fn image_cache_evicts_least_recently_used() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache = FeedImageCache::new(dir.path().to_path_buf(), 10);
+        let old = cache.path_for("https://example.com/old.png");
+        let used = cache.path_for("https://example.com/used.png");
+        let new = cache.path_for("https://example.com/new.png");
+
+        cache.store(&old, b"12345")?;
+        cache.store(&used, b"12345")?;
+        let earlier = SystemTime::now() - std::time::Duration::from_secs(60);
+        for path in [&old, &used] {
+            std::fs::File::options()
+                .append(true)
+                .open(path)?
+                .set_modified(earlier)?;
+        }
+        touch(&used);
+        cache.store(&new, b"12345")?;
+
+        assert!(!old.exists());
+        assert!(used.exists());
+        assert!(new.exists());
+        assert_eq!(new.extension().and_then(|e| e.to_str()), Some("png"));
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use super::mojang_content;
 use crate::models::NotificationSubscription;
 use crate::notifications::subscriptions::{
     AvailableNotificationSource, NotificationUpdateItem, SubscriptionProvider,
@@ -23,6 +24,15 @@ struct MojangNewsEntry {
     text: String,
     #[serde(rename = "readMoreLink")]
     read_more_link: String,
+    #[serde(rename = "newsPageImage")]
+    news_page_image: Option<MojangContentImage>,
+    #[serde(rename = "playPageImage")]
+    play_page_image: Option<MojangContentImage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MojangContentImage {
+    url: String,
 }
 
 #[async_trait]
@@ -48,7 +58,15 @@ impl SubscriptionProvider for MojangNewsProvider {
         sub: &NotificationSubscription,
     ) -> Result<Vec<NotificationUpdateItem>> {
         let url = sub.target_url.as_deref().unwrap_or(MOJANG_NEWS_URL);
-        let response = piston_lib::client::shared_client().get(url).send().await?;
+        let locale = mojang_content::user_article_locale();
+        let response = piston_lib::client::shared_client()
+            .get(url)
+            .header(
+                reqwest::header::ACCEPT_LANGUAGE,
+                mojang_content::accept_language(locale),
+            )
+            .send()
+            .await?;
 
         if !response.status().is_success() {
             return Err(anyhow::anyhow!(
@@ -74,33 +92,132 @@ impl SubscriptionProvider for MojangNewsProvider {
             })
             .unwrap_or_default();
 
-        let mut items = Vec::new();
-        for entry in news.entries {
-            let entry_tag = entry.tag.as_deref().unwrap_or("News");
+        let mut items = news_items(news.entries, &allowed_tags, locale);
+        for item in &mut items {
+            mojang_content::attach_cached_image(item).await;
+        }
+
+        Ok(items)
+    }
+}
+
+fn news_items(
+    entries: Vec<MojangNewsEntry>,
+    allowed_tags: &[String],
+    locale: &str,
+) -> Vec<NotificationUpdateItem> {
+    let mut items = Vec::new();
+    for entry in entries {
+        let entry_tag = entry.tag.as_deref().unwrap_or("News");
+
+        // Keep filter logic: if tags are specified, check them. If none specified, allow all.
+        if !allowed_tags.is_empty()
+            && !allowed_tags
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(entry_tag))
+        {
+            continue;
+        }
 
-            // Keep filter logic: if tags are specified, check them. If none specified, allow all.
-            if !allowed_tags.is_empty()
-                && !allowed_tags
-                    .iter()
-                    .any(|t| t.eq_ignore_ascii_case(entry_tag))
+        let image_url = entry
+            .news_page_image
+            .or(entry.play_page_image)
+            .map(|image| mojang_content::content_url(&image.url));
+
+        items.push(NotificationUpdateItem {
+            // The English link stays the id so switching language doesn't re-notify.
+            id: entry.read_more_link.clone(),
+            title: super::decode_title(&entry.title),
+            description: Some(super::clean_and_truncate(&entry.text, 240)),
+            link: Some(mojang_content::localize_article_link(
+                &entry.read_more_link,
+                locale,
+            )),
+            metadata: serde_json::json!({
+                "tag": entry_tag,
+                "category": entry.category,
+                "locale": locale,
+                "image_url": image_url,
+            }),
+            severity: Some("info".to_string()),
+            silent: Some(true),
+            actions: Vec::new(),
+        });
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Trimmed copy of https://launchercontent.mojang.com/v2/news.json
+    const RECORDED_NEWS: &str = r#"{
+        "version": 1,
+        "entries": [
+            {
+                "title": "Minecraft Java Edition 1.21.5",
+                "tag": "News",
+                "category": "Minecraft: Java Edition",
+                "date": "2025-03-25",
+                "text": "Spring to Life drops today &amp; brings new variants.",
+                "playPageImage": {"title": "Spring", "url": "/images/spring-play.jpg"},
+                "newsPageImage": {
+                    "title": "Spring",
+                    "url": "/images/spring-news.jpg",
+                    "dimensions": {"width": 1280, "height": 720}
+                },
+                "readMoreLink": "https://www.minecraft.net/en-us/article/minecraft-java-edition-1-21-5",
+                "newsType": ["News page", "Java"],
+                "id": "3f2c"
+            },
             {
-                continue;
+                "title": "Block of the Week: Copper",
+                "tag": "Insider",
+                "category": "Minecraft",
+                "date": "2025-03-20",
+                "text": "Shiny.",
+                "readMoreLink": "https://www.minecraft.net/en-us/article/block-week-copper",
+                "newsType": ["News page"],
+                "id": "9a1b"
             }
+        ]
+    }"#;
 
-            items.push(NotificationUpdateItem {
-                id: entry.read_more_link.clone(),
-                title: super::decode_title(&entry.title),
-                description: Some(super::clean_and_truncate(&entry.text, 240)),
-                link: Some(entry.read_more_link),
-                metadata: serde_json::json!({
-                    "tag": entry_tag,
-                    "category": entry.category,
-                }),
-                severity: Some("info".to_string()),
-                silent: Some(true),
-            });
-        }
+    fn recorded_entries() -> Vec<MojangNewsEntry> {
+        serde_json::from_str::<MojangNewsResponse>(RECORDED_NEWS)
+            .unwrap()
+            .entries
+    }
 
-        Ok(items)
+    #[test]
+    fn news_links_follow_the_user_locale() {
+        let items = news_items(recorded_entries(), &[], "de-de");
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            items[0].id,
+            "https://www.minecraft.net/en-us/article/minecraft-java-edition-1-21-5"
+        );
+        assert_eq!(
+            items[0].link.as_deref(),
+            Some("https://www.minecraft.net/de-de/article/minecraft-java-edition-1-21-5")
+        );
+        assert_eq!(
+            items[0].metadata["image_url"],
+            "https://launchercontent.mojang.com/images/spring-news.jpg"
+        );
+        assert!(items[1].metadata["image_url"].is_null());
+    }
+
+    #[test]
+    fn unsupported_languages_fall_back_to_english() {
+        let locale = mojang_content::article_locale("tlh");
+        let items = news_items(recorded_entries(), &["news".to_string()], locale);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].metadata["locale"], "en-us");
+        assert_eq!(
+            items[0].link.as_deref(),
+            Some("https://www.minecraft.net/en-us/article/minecraft-java-edition-1-21-5")
+        );
     }
 }
@@ -1,4 +1,6 @@
+use super::mojang_content;
 use crate::models::NotificationSubscription;
+use crate::notifications::models::NotificationAction;
 use crate::notifications::subscriptions::{
     AvailableNotificationSource, NotificationUpdateItem, SubscriptionProvider,
 };
@@ -23,10 +25,15 @@ struct PatchNotesEntry {
     #[serde(rename = "type")]
     version_type: String, // release, snapshot
     body: String,
-    // image: Option<serde_json::Value>, // Not needed for now
+    image: Option<PatchNotesImage>,
     // contentPath: String, // Not needed
 }
 
+#[derive(Debug, Deserialize)]
+struct PatchNotesImage {
+    url: String,
+}
+
 #[async_trait]
 impl SubscriptionProvider for PatchNotesProvider {
     fn provider_type(&self) -> &str {
@@ -50,7 +57,15 @@ impl SubscriptionProvider for PatchNotesProvider {
         sub: &NotificationSubscription,
     ) -> Result<Vec<NotificationUpdateItem>> {
         let url = sub.target_url.as_deref().unwrap_or(PATCH_NOTES_URL);
-        let response = piston_lib::client::shared_client().get(url).send().await?;
+        let locale = mojang_content::user_article_locale();
+        let response = piston_lib::client::shared_client()
+            .get(url)
+            .header(
+                reqwest::header::ACCEPT_LANGUAGE,
+                mojang_content::accept_language(locale),
+            )
+            .send()
+            .await?;
 
         if !response.status().is_success() {
             return Err(anyhow::anyhow!(
@@ -77,33 +92,141 @@ impl SubscriptionProvider for PatchNotesProvider {
             })
             .unwrap_or_default();
 
-        let mut items = Vec::new();
-        for entry in entries {
-            if !allowed_types.is_empty()
-                && !allowed_types
-                    .iter()
-                    .any(|t| t.eq_ignore_ascii_case(&entry.version_type))
+        let mut items = patch_note_items(entries, &allowed_types, locale);
+        for item in &mut items {
+            mojang_content::attach_cached_image(item).await;
+        }
+
+        Ok(items)
+    }
+}
+
+fn patch_note_items(
+    entries: Vec<PatchNotesEntry>,
+    allowed_types: &[String],
+    locale: &str,
+) -> Vec<NotificationUpdateItem> {
+    let mut items = Vec::new();
+    for entry in entries {
+        if !allowed_types.is_empty()
+            && !allowed_types
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(&entry.version_type))
+        {
+            continue;
+        }
+
+        let image_url = entry
+            .image
+            .map(|image| mojang_content::content_url(&image.url));
+
+        items.push(NotificationUpdateItem {
+            id: format!("patch-{}", entry.id),
+            title: super::decode_title(&entry.title),
+            description: Some(super::clean_and_truncate(&entry.body, 180)),
+            link: Some(format!(
+                "https://www.minecraft.net/{}/article/minecraft-java-edition-{}",
+                locale,
+                entry.version.replace(".", "-")
+            )),
+            metadata: serde_json::json!({
+                "version": entry.version,
+                "version_id": entry.version,
+                "type": entry.version_type,
+                "locale": locale,
+                "image_url": image_url,
+            }),
+            severity: Some("info".to_string()),
+            silent: Some(true),
+            actions: vec![create_instance_action(&entry.version)],
+        });
+    }
+    items
+}
+
+/// Opens the install page preset to this Minecraft version.
+fn create_instance_action(version_id: &str) -> NotificationAction {
+    NotificationAction {
+        action_id: "navigate".to_string(),
+        label: format!("Create instance for {}", version_id),
+        action_type: "secondary".to_string(),
+        payload: Some(serde_json::json!({
+            "path": "/install",
+            "params": {
+                "initialMinecraftVersion": version_id,
+                "initialModloader": "vanilla",
+            },
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Trimmed copy of https://launchercontent.mojang.com/javaPatchNotes.json
+    const RECORDED_PATCH_NOTES: &str = r#"{
+        "version": 1,
+        "entries": [
             {
-                continue;
+                "title": "Minecraft Java Edition 1.21.5",
+                "type": "release",
+                "version": "1.21.5",
+                "image": {"title": "1.21.5", "url": "/images/java-1-21-5.jpg"},
+                "body": "<p>Spring to Life brings <b>new</b> mob variants.</p>",
+                "id": "1.21.5",
+                "contentPath": "1.21.5.json"
+            },
+            {
+                "title": "Minecraft Snapshot 25w14craftmine",
+                "type": "snapshot",
+                "version": "25w14craftmine",
+                "body": "<p>April fools.</p>",
+                "id": "25w14craftmine",
+                "contentPath": "25w14craftmine.json"
             }
+        ]
+    }"#;
 
-            items.push(NotificationUpdateItem {
-                id: format!("patch-{}", entry.id),
-                title: super::decode_title(&entry.title),
-                description: Some(super::clean_and_truncate(&entry.body, 180)),
-                link: Some(format!(
-                    "https://www.minecraft.net/en-us/article/minecraft-java-edition-{}",
-                    entry.version.replace(".", "-")
-                )),
-                metadata: serde_json::json!({
-                    "version": entry.version,
-                    "type": entry.version_type,
-                }),
-                severity: Some("info".to_string()),
-                silent: Some(true),
-            });
-        }
+    fn recorded_entries() -> Vec<PatchNotesEntry> {
+        serde_json::from_str::<PatchNotesResponse>(RECORDED_PATCH_NOTES)
+            .unwrap()
+            .entries
+    }
 
-        Ok(items)
+    #[test]
+    fn release_notes_carry_version_and_create_action() {
+        let items = patch_note_items(recorded_entries(), &["release".to_string()], "fr-fr");
+        assert_eq!(items.len(), 1);
+        let item = &items[0];
+        assert_eq!(item.id, "patch-1.21.5");
+        assert_eq!(
+            item.link.as_deref(),
+            Some("https://www.minecraft.net/fr-fr/article/minecraft-java-edition-1-21-5")
+        );
+        assert_eq!(item.metadata["version_id"], "1.21.5");
+        assert_eq!(
+            item.metadata["image_url"],
+            "https://launchercontent.mojang.com/images/java-1-21-5.jpg"
+        );
+        assert_eq!(item.actions.len(), 1);
+        assert_eq!(item.actions[0].label, "Create instance for 1.21.5");
+        assert_eq!(
+            item.actions[0].payload.as_ref().unwrap()["params"]["initialMinecraftVersion"],
+            "1.21.5"
+        );
+    }
+
+    #[test]
+    fn unsupported_languages_fall_back_to_english() {
+        let locale = mojang_content::article_locale("eo");
+        let items = patch_note_items(recorded_entries(), &[], locale);
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            items[0].link.as_deref(),
+            Some("https://www.minecraft.net/en-us/article/minecraft-java-edition-1-21-5")
+        );
+        assert_eq!(items[1].metadata["locale"], "en-us");
+        assert!(items[1].metadata["image_url"].is_null());
     }
 }
@@ -1,27 +1,55 @@
-use crate::models::resource::SourcePlatform;
+use crate::models::installed_resource::InstalledResource;
+use crate::models::instance::Instance;
+use crate::models::resource::{ResourceProject, ResourceVersion, SourcePlatform};
 use crate::models::NotificationSubscription;
-use crate::notifications::subscriptions::{NotificationUpdateItem, SubscriptionProvider};
+use crate::notifications::subscriptions::{
+    AvailableNotificationSource, NotificationUpdateItem, SubscriptionProvider,
+};
 use crate::resources::manager::ResourceManager;
 use anyhow::Result;
 use async_trait::async_trait;
+use diesel::prelude::*;
+use std::collections::{BTreeMap, HashMap};
 use tauri::Manager;
 
 pub struct ResourceProvider;
 
+/// An installed resource together with the instance it has to stay
+/// compatible with.
+struct Candidate {
+    resource: InstalledResource,
+    platform: SourcePlatform,
+    instance_id: i32,
+    instance_name: String,
+    game_version: String,
+    loader: String,
+}
+
+type ProjectKey = (SourcePlatform, String);
+
 #[async_trait]
 impl SubscriptionProvider for ResourceProvider {
     fn provider_type(&self) -> &str {
         "resource"
     }
 
+    fn get_available_sources(&self) -> Vec<AvailableNotificationSource> {
+        vec![AvailableNotificationSource {
+            id: "installed_resources".to_string(),
+            title: "Updates for Installed Mods".to_string(),
+            provider_type: "resource".to_string(),
+            target_url: None,
+            target_id: None,
+            metadata: None,
+        }]
+    }
+
     async fn check(
         &self,
         app_handle: &tauri::AppHandle,
         sub: &NotificationSubscription,
     ) -> Result<Vec<NotificationUpdateItem>> {
-        let project_id = sub.target_id.as_deref().ok_or_else(|| {
-            anyhow::anyhow!("Resource provider requires a target_id (project ID)")
-        })?;
+        let project_id = sub.target_id.as_deref().filter(|id| !id.is_empty());
 
         let metadata: serde_json::Value = sub
             .metadata
@@ -41,49 +69,340 @@ impl SubscriptionProvider for ResourceProvider {
 
         let rm = app_handle.state::<ResourceManager>();
 
-        // Fetch latest versions (ignoring cache to find new ones)
-        let versions = rm
-            .get_versions(platform, project_id, true, None, None)
-            .await?;
-
-        let mut items = Vec::new();
-        // Just take the latest release for now
-        if let Some(latest) = versions.first() {
-            // Generate a direct link to the version page for the given platform
-            let link = match platform {
-                SourcePlatform::Modrinth => {
-                    format!(
-                        "https://modrinth.com/project/{}/version/{}",
-                        project_id, latest.id
-                    )
-                }
-                SourcePlatform::CurseForge => {
-                    // Generic CurseForge URL pattern that works with numeric project ID and file ID
-                    format!(
-                        "https://www.curseforge.com/projects/{}/files/{}",
-                        project_id, latest.id
-                    )
+        // A subscription without a project id follows every installed resource.
+        let candidates = load_candidates(project_id.map(|id| (platform_str, id)))?;
+        if candidates.is_empty() {
+            return match project_id {
+                Some(project_id) => {
+                    latest_release_item(&rm, sub, platform, platform_str, project_id).await
                 }
+                None => Ok(Vec::new()),
             };
+        }
+
+        // One bulk request per platform: Modrinth `GET /projects?ids=[...]`,
+        // CurseForge `POST /mods`.
+        let mut projects: HashMap<ProjectKey, ResourceProject> = HashMap::new();
+        for platform in [SourcePlatform::Modrinth, SourcePlatform::CurseForge] {
+            let mut ids: Vec<String> = candidates
+                .iter()
+                .filter(|c| c.platform == platform)
+                .map(|c| c.resource.remote_id.clone())
+                .collect();
+            ids.sort();
+            ids.dedup();
+            if ids.is_empty() {
+                continue;
+            }
+            match rm.get_projects(platform, &ids).await {
+                Ok(fetched) => {
+                    for project in fetched {
+                        projects.insert((platform, project.id.clone()), project);
+                    }
+                }
+                Err(e) => log::warn!(
+                    "[ResourceProvider] Bulk project fetch failed for {:?}: {}",
+                    platform,
+                    e
+                ),
+            }
+        }
+
+        let mut versions: HashMap<ProjectKey, Vec<ResourceVersion>> = HashMap::new();
+        for candidate in &candidates {
+            let key = (candidate.platform, candidate.resource.remote_id.clone());
+            if versions.contains_key(&key) {
+                continue;
+            }
+            match rm
+                .get_versions(candidate.platform, &key.1, true, None, None)
+                .await
+            {
+                Ok(found) => {
+                    versions.insert(key, found);
+                }
+                Err(e) => log::warn!(
+                    "[ResourceProvider] Failed to fetch versions for {}: {}",
+                    key.1,
+                    e
+                ),
+            }
+        }
+
+        Ok(outdated_items(&candidates, &projects, &versions))
+    }
+}
+
+/// Tracked, enabled resources in instances that are not in the trash,
+/// optionally limited to one `(platform, project id)`.
+fn load_candidates(project: Option<(&str, &str)>) -> Result<Vec<Candidate>> {
+    use crate::schema::installed_resource::dsl as ir_dsl;
+    use crate::schema::instance::dsl as inst_dsl;
+
+    let mut conn = crate::utils::db::get_vesta_conn()?;
+    let mut query = ir_dsl::installed_resource
+        .filter(ir_dsl::is_manual.eq(false))
+        .filter(ir_dsl::is_enabled.eq(true))
+        .filter(ir_dsl::source_kind.ne("modpack"))
+        .into_boxed();
+    if let Some((platform, remote_id)) = project {
+        query = query
+            .filter(ir_dsl::platform.eq(platform.to_string()))
+            .filter(ir_dsl::remote_id.eq(remote_id.to_string()));
+    }
+    let resources = query.load::<InstalledResource>(&mut conn)?;
+
+    let instances: HashMap<i32, Instance> = inst_dsl::instance
+        .load::<Instance>(&mut conn)?
+        .into_iter()
+        .filter(|inst| !inst.is_trashed())
+        .map(|inst| (inst.id, inst))
+        .collect();
+
+    Ok(resources
+        .into_iter()
+        .filter_map(|resource| {
+            let platform =
+                crate::commands::resources::source_platform_from_str(&resource.platform)?;
+            let inst = instances.get(&resource.instance_id)?;
+            Some(Candidate {
+                platform,
+                instance_id: inst.id,
+                instance_name: inst.name.clone(),
+                game_version: inst.minecraft_version.clone(),
+                loader: inst
+                    .modloader
+                    .clone()
+                    .unwrap_or_else(|| "vanilla".to_string()),
+                resource,
+            })
+        })
+        .collect())
+}
+
+/// One item per outdated mod and target version, listing every instance
+/// that would receive it.
+fn outdated_items(
+    candidates: &[Candidate],
+    projects: &HashMap<ProjectKey, ResourceProject>,
+    versions: &HashMap<ProjectKey, Vec<ResourceVersion>>,
+) -> Vec<NotificationUpdateItem> {
+    let mut grouped: BTreeMap<(String, String), (ResourceVersion, Vec<&Candidate>)> =
+        BTreeMap::new();
+    for candidate in candidates {
+        let key = (candidate.platform, candidate.resource.remote_id.clone());
+        let Some(project_versions) = versions.get(&key) else {
+            continue;
+        };
+        let Some(best) = crate::resources::update_policy::find_best_update(
+            project_versions,
+            &candidate.resource,
+            &candidate.game_version,
+            &candidate.loader,
+        ) else {
+            continue;
+        };
+        if best.id == candidate.resource.remote_version_id {
+            continue;
+        }
+        grouped
+            .entry((candidate.resource.remote_id.clone(), best.id.clone()))
+            .or_insert_with(|| (best, Vec::new()))
+            .1
+            .push(candidate);
+    }
+
+    grouped
+        .into_iter()
+        .map(|((remote_id, version_id), (best, affected))| {
+            let first = affected[0];
+            let project = projects.get(&(first.platform, remote_id.clone()));
+            let name = project
+                .map(|p| p.name.clone())
+                .unwrap_or_else(|| first.resource.display_name.clone());
+            let instance_names: Vec<&str> =
+                affected.iter().map(|c| c.instance_name.as_str()).collect();
 
-            items.push(NotificationUpdateItem {
-                id: format!("{}-{}", project_id, latest.id),
-                title: format!("New Update: {}", latest.version_number),
+            NotificationUpdateItem {
+                id: format!("{}-{}", remote_id, version_id),
+                title: format!("Update available: {}", name),
                 description: Some(format!(
-                    "A new version for {} has been released on {}.",
-                    sub.title, platform_str
+                    "{} {} is available (installed: {}) for {}.",
+                    name,
+                    best.version_number,
+                    first.resource.current_version,
+                    instance_names.join(", ")
+                )),
+                link: Some(changelog_url(
+                    first.platform,
+                    project,
+                    &remote_id,
+                    &version_id,
                 )),
-                link: Some(link),
                 metadata: serde_json::json!({
-                    "platform": platform_str,
-                    "version_id": latest.id,
-                    "version_number": latest.version_number,
+                    "platform": first.resource.platform,
+                    "project_id": remote_id,
+                    "version_id": version_id,
+                    "version_number": best.version_number,
+                    "installed_version_id": first.resource.remote_version_id,
+                    "resource_ids": affected.iter().map(|c| c.resource.id).collect::<Vec<_>>(),
+                    "instance_ids": affected.iter().map(|c| c.instance_id).collect::<Vec<_>>(),
                 }),
                 severity: Some("info".to_string()),
                 silent: Some(true),
-            });
+                actions: Vec::new(),
+            }
+        })
+        .collect()
+}
+
+/// The version page, which carries the changelog on both platforms.
+fn changelog_url(
+    platform: SourcePlatform,
+    project: Option<&ResourceProject>,
+    project_id: &str,
+    version_id: &str,
+) -> String {
+    let web_url = project
+        .map(|p| p.web_url.trim_end_matches('/'))
+        .filter(|url| !url.is_empty());
+    match (platform, web_url) {
+        (SourcePlatform::Modrinth, Some(url)) => format!("{}/version/{}", url, version_id),
+        (SourcePlatform::Modrinth, None) => format!(
+            "https://modrinth.com/project/{}/version/{}",
+            project_id, version_id
+        ),
+        (SourcePlatform::CurseForge, Some(url)) => format!("{}/files/{}", url, version_id),
+        // Generic CurseForge URL pattern that works with numeric project ID and file ID
+        (SourcePlatform::CurseForge, None) => format!(
+            "https://www.curseforge.com/projects/{}/files/{}",
+            project_id, version_id
+        ),
+    }
+}
+
+/// Followed projects that are not installed anywhere report their newest
+/// release.
+async fn latest_release_item(
+    rm: &ResourceManager,
+    sub: &NotificationSubscription,
+    platform: SourcePlatform,
+    platform_str: &str,
+    project_id: &str,
+) -> Result<Vec<NotificationUpdateItem>> {
+    // Fetch latest versions (ignoring cache to find new ones)
+    let versions = rm
+        .get_versions(platform, project_id, true, None, None)
+        .await?;
+
+    let mut items = Vec::new();
+    if let Some(latest) = versions.first() {
+        items.push(NotificationUpdateItem {
+            id: format!("{}-{}", project_id, latest.id),
+            title: format!("New Update: {}", latest.version_number),
+            description: Some(format!(
+                "A new version for {} has been released on {}.",
+                sub.title, platform_str
+            )),
+            link: Some(changelog_url(platform, None, project_id, &latest.id)),
+            metadata: serde_json::json!({
+                "platform": platform_str,
+                "version_id": latest.id,
+                "version_number": latest.version_number,
+            }),
+            severity: Some("info".to_string()),
+            silent: Some(true),
+            actions: Vec::new(),
+        });
+    }
+
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::resource::ReleaseType;
+
+    fn candidate(id: i32, instance: &str, version_id: &str) -> Candidate {
+        Candidate {
+            resource: InstalledResource {
+                id,
+                instance_id: id,
+                platform: "modrinth".into(),
+                remote_id: "AANobbMI".into(),
+                remote_version_id: version_id.into(),
+                resource_type: "mod".into(),
+                local_path: "mods/sodium.jar".into(),
+                display_name: "Sodium".into(),
+                current_version: "0.5.8".into(),
+                is_manual: false,
+                is_enabled: true,
+                last_updated: String::new(),
+                release_type: "release".into(),
+                hash: None,
+                file_size: 0,
+                file_mtime: 0,
+                source_kind: "custom".into(),
+                source_modpack_id: None,
+                source_modpack_version_id: None,
+                source_modpack_platform: None,
+            },
+            platform: SourcePlatform::Modrinth,
+            instance_id: id,
+            instance_name: instance.into(),
+            game_version: "1.20.1".into(),
+            loader: "fabric".into(),
         }
+    }
+
+    fn versions(ids: &[&str]) -> HashMap<ProjectKey, Vec<ResourceVersion>> {
+        let list = ids
+            .iter()
+            .map(|id| ResourceVersion {
+                id: id.to_string(),
+                project_id: "AANobbMI".into(),
+                version_number: format!("v-{}", id),
+                game_versions: vec!["1.20.1".into()],
+                loaders: vec!["fabric".into()],
+                download_url: String::new(),
+                file_name: "sodium.jar".into(),
+                release_type: ReleaseType::Release,
+                hash: String::new(),
+                dependencies: vec![],
+                published_at: None,
+            })
+            .collect();
+        HashMap::from([((SourcePlatform::Modrinth, "AANobbMI".to_string()), list)])
+    }
+
+    #[test]
+    fn groups_instances_sharing_an_outdated_mod() {
+        let candidates = vec![
+            candidate(1, "Survival", "old"),
+            candidate(2, "Creative", "old"),
+        ];
+        let items = outdated_items(&candidates, &HashMap::new(), &versions(&["new", "old"]));
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].id, "AANobbMI-new");
+        assert_eq!(items[0].severity.as_deref(), Some("info"));
+        assert_eq!(
+            items[0].link.as_deref(),
+            Some("https://modrinth.com/project/AANobbMI/version/new")
+        );
+        assert!(items[0]
+            .description
+            .as_deref()
+            .unwrap()
+            .ends_with("for Survival, Creative."));
+        assert_eq!(items[0].metadata["resource_ids"], serde_json::json!([1, 2]));
+    }
 
-        Ok(items)
+    #[test]
+    fn skips_up_to_date_and_unknown_projects() {
+        let candidates = vec![candidate(1, "Survival", "new")];
+        assert!(outdated_items(&candidates, &HashMap::new(), &versions(&["new"])).is_empty());
+        assert!(outdated_items(&candidates, &HashMap::new(), &HashMap::new()).is_empty());
     }
 }
@@ -126,6 +126,7 @@ impl SubscriptionProvider for RSSProvider {
                 }),
                 severity: Some("info".to_string()),
                 silent: Some(true),
+                actions: Vec::new(),
             });
         }
 
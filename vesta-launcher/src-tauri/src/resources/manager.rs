@@ -12,8 +12,9 @@ use crate::models::resource::{
     SearchQuery, SearchResponse, SourcePlatform,
 };
 use crate::resources::sources::curseforge::CurseForgeSource;
+use crate::resources::sources::custom::{CustomResourceSource, SourceOverride};
 use crate::resources::sources::modrinth::ModrinthSource;
-use crate::resources::sources::ResourceSource;
+use crate::resources::sources::{ResourceSource, HASH_BATCH_SIZE};
 use crate::resources::update_cache::{now_datetime_str, VERSION_CACHE_TTL_MINUTES};
 use crate::schema::vesta::installed_resource::dsl as ir_dsl;
 use crate::schema::vesta::resource_metadata_cache::dsl as rmc_dsl;
@@ -57,12 +58,23 @@ async fn download_icon_with_timeout(url: &str) -> Result<Vec<u8>> {
     Ok(bytes.to_vec())
 }
 
+/// How long a "no match" answer for a file hash is trusted before asking the
+/// platform again.
+const HASH_MISS_TTL_MINUTES: i64 = 10;
+
+/// How long a project's detail page data is reused before it is fetched again.
+const PROJECT_DETAILS_TTL_MINUTES: i64 = 30;
+
 #[derive(Clone)]
 pub struct ResourceManager {
     sources: Arc<RwLock<Vec<Arc<dyn ResourceSource>>>>,
     project_cache: Arc<RwLock<HashMap<(SourcePlatform, String), ResourceProject>>>,
+    /// Full single-project fetches for detail pages, and when they expire.
+    details_cache: Arc<RwLock<HashMap<(SourcePlatform, String), (ResourceProject, NaiveDateTime)>>>,
     version_cache: Arc<RwLock<HashMap<(SourcePlatform, String), Vec<ResourceVersion>>>>,
     hash_cache: Arc<RwLock<HashMap<(SourcePlatform, String), (ResourceProject, ResourceVersion)>>>,
+    /// Hashes the platform reported as unknown, and when it did.
+    hash_miss_cache: Arc<RwLock<HashMap<(SourcePlatform, String), NaiveDateTime>>>,
     search_cache: Arc<RwLock<HashMap<String, (SearchResponse, NaiveDateTime)>>>,
     category_cache: Arc<RwLock<HashMap<SourcePlatform, (Vec<ResourceCategory>, NaiveDateTime)>>>,
     pub image_cache: Arc<RwLock<HashMap<String, String>>>,
@@ -78,8 +90,10 @@ impl ResourceManager {
         Self {
             sources: Arc::new(RwLock::new(sources)),
             project_cache: Arc::new(RwLock::new(HashMap::new())),
+            details_cache: Arc::new(RwLock::new(HashMap::new())),
             version_cache: Arc::new(RwLock::new(HashMap::new())),
             hash_cache: Arc::new(RwLock::new(HashMap::new())),
+            hash_miss_cache: Arc::new(RwLock::new(HashMap::new())),
             search_cache: Arc::new(RwLock::new(HashMap::new())),
             category_cache: Arc::new(RwLock::new(HashMap::new())),
             image_cache: Arc::new(RwLock::new(HashMap::new())),
@@ -91,8 +105,10 @@ impl ResourceManager {
 
         // 1. Clear in-memory caches
         self.project_cache.write().await.clear();
+        self.details_cache.write().await.clear();
         self.version_cache.write().await.clear();
         self.hash_cache.write().await.clear();
+        self.hash_miss_cache.write().await.clear();
         self.search_cache.write().await.clear();
         self.category_cache.write().await.clear();
         self.image_cache.write().await.clear();
@@ -553,6 +569,48 @@ impl ResourceManager {
         Ok(project)
     }
 
+    /// Fetch everything the detail page shows for a project: body, gallery,
+    /// members, license and links. Unlike [`Self::get_project`], this never
+    /// answers from search or batch results, which lack those fields.
+    pub async fn get_project_details(
+        &self,
+        platform: SourcePlatform,
+        id: &str,
+    ) -> Result<ResourceProject> {
+        let key = (platform, id.to_string());
+        {
+            let cache = self.details_cache.read().await;
+            if let Some((project, expiry)) = cache.get(&key) {
+                if expiry > &chrono::Utc::now().naive_utc() {
+                    return Ok(project.clone());
+                }
+            }
+        }
+
+        let source = self.get_source(platform).await?;
+        let project = source.get_project(id).await?;
+
+        {
+            let expiry = chrono::Utc::now().naive_utc()
+                + chrono::Duration::minutes(PROJECT_DETAILS_TTL_MINUTES);
+            let mut cache = self.details_cache.write().await;
+            cache.insert(key, (project.clone(), expiry));
+            if id != project.id {
+                cache.insert((platform, project.id.clone()), (project.clone(), expiry));
+            }
+        }
+        {
+            let mut cache = self.project_cache.write().await;
+            cache.insert((platform, id.to_string()), project.clone());
+            if id != project.id {
+                cache.insert((platform, project.id.clone()), project.clone());
+            }
+        }
+        let _ = self.cache_project_metadata(platform, &project).await;
+
+        Ok(project)
+    }
+
     pub async fn get_versions(
         &self,
         platform: SourcePlatform,
@@ -599,6 +657,30 @@ impl ResourceManager {
         Ok(versions)
     }
 
+    /// Like [`Self::get_versions`], but honours an instance's source override.
+    /// Overridden lookups skip the shared caches, which only hold data from
+    /// the official APIs.
+    pub async fn get_versions_with_override(
+        &self,
+        platform: SourcePlatform,
+        project_id: &str,
+        ignore_cache: bool,
+        source_override: Option<&SourceOverride>,
+    ) -> Result<Vec<ResourceVersion>> {
+        match source_override.filter(|o| o.applies_to(platform)) {
+            Some(source_override) => {
+                self.source_for(platform, Some(source_override))
+                    .await?
+                    .get_versions(project_id, None, None)
+                    .await
+            }
+            None => {
+                self.get_versions(platform, project_id, ignore_cache, None, None)
+                    .await
+            }
+        }
+    }
+
     pub async fn find_peer_project(
         &self,
         current: &ResourceProject,
@@ -706,6 +788,9 @@ impl ResourceManager {
                 return Ok(result.clone());
             }
         }
+        if self.is_known_hash_miss(platform, hash).await {
+            return Err(anyhow!("No {:?} file matches hash {}", platform, hash));
+        }
 
         let source = self.get_source(platform).await?;
         let (project, version) = source.get_by_hash(hash).await?;
@@ -725,6 +810,74 @@ impl ResourceManager {
         Ok((project, version))
     }
 
+    /// Bulk variant of [`Self::get_by_hash`] used by folder scans and modpack
+    /// linking. Uncached hashes are looked up in batches of
+    /// [`HASH_BATCH_SIZE`]; both hits and misses are cached so the per-file
+    /// lookups that follow don't hit the network again.
+    pub async fn get_by_hashes(
+        &self,
+        platform: SourcePlatform,
+        hashes: &[String],
+    ) -> Result<Vec<Option<(ResourceProject, ResourceVersion)>>> {
+        let mut pending = Vec::new();
+        {
+            let cache = self.hash_cache.read().await;
+            for hash in hashes {
+                if !cache.contains_key(&(platform, hash.clone()))
+                    && !pending.contains(hash)
+                    && !self.is_known_hash_miss(platform, hash).await
+                {
+                    pending.push(hash.clone());
+                }
+            }
+        }
+
+        if !pending.is_empty() {
+            let source = self.get_source(platform).await?;
+            for chunk in pending.chunks(HASH_BATCH_SIZE) {
+                let results = source.get_by_hashes(chunk).await?;
+                let now = chrono::Utc::now().naive_utc();
+                let mut projects = Vec::new();
+                {
+                    let mut h_cache = self.hash_cache.write().await;
+                    let mut misses = self.hash_miss_cache.write().await;
+                    for (hash, result) in chunk.iter().zip(results) {
+                        match result {
+                            Some((project, version)) => {
+                                misses.remove(&(platform, hash.clone()));
+                                projects.push(project.clone());
+                                h_cache.insert((platform, hash.clone()), (project, version));
+                            }
+                            None => {
+                                misses.insert((platform, hash.clone()), now);
+                            }
+                        }
+                    }
+                }
+
+                let mut seen = HashSet::new();
+                for project in projects {
+                    if seen.insert(project.id.clone()) {
+                        let _ = self.cache_project_metadata(platform, &project).await;
+                    }
+                }
+            }
+        }
+
+        let cache = self.hash_cache.read().await;
+        Ok(hashes
+            .iter()
+            .map(|hash| cache.get(&(platform, hash.clone())).cloned())
+            .collect())
+    }
+
+    async fn is_known_hash_miss(&self, platform: SourcePlatform, hash: &str) -> bool {
+        let misses = self.hash_miss_cache.read().await;
+        misses.get(&(platform, hash.to_string())).is_some_and(|at| {
+            chrono::Utc::now().naive_utc() - *at < chrono::Duration::minutes(HASH_MISS_TTL_MINUTES)
+        })
+    }
+
     pub async fn get_project_record_for_source(
         &self,
         platform: SourcePlatform,
@@ -863,6 +1016,130 @@ impl ResourceManager {
         Ok(())
     }
 
+    /// Download a specific version into the instance's matching folder and
+    /// record it in the installed-resource ledger, replacing any previously
+    /// installed version of the same project. Worlds and modpacks are rejected
+    /// because they are not installed as a single file.
+    pub async fn install_resource(
+        &self,
+        platform: SourcePlatform,
+        project_id: &str,
+        version_id: &str,
+        instance_id: i32,
+    ) -> Result<InstalledResource> {
+        use crate::schema::vesta::instance::dsl as inst_dsl;
+
+        let (game_dir, override_json) = {
+            let mut conn = get_vesta_conn()?;
+            inst_dsl::instance
+                .filter(inst_dsl::id.eq(instance_id))
+                .filter(inst_dsl::deleted_at.is_null())
+                .select((inst_dsl::game_directory, inst_dsl::instance_source_override))
+                .first::<(Option<String>, Option<String>)>(&mut conn)
+                .optional()?
+                .ok_or_else(|| anyhow!("Instance {} not found", instance_id))?
+        };
+        let game_dir = game_dir
+            .ok_or_else(|| anyhow!("Instance {} has no game directory set", instance_id))?;
+        let source_override = SourceOverride::from_column(override_json.as_deref())
+            .filter(|o| o.applies_to(platform));
+
+        let source = self.source_for(platform, source_override.as_ref()).await?;
+        let (project, version) = if source_override.is_some() {
+            (
+                source.get_project(project_id).await?,
+                source.get_version(project_id, version_id).await?,
+            )
+        } else {
+            (
+                self.get_project(platform, project_id).await?,
+                self.get_version(platform, project_id, version_id).await?,
+            )
+        };
+        let target_dir_name = single_file_install_dir(project.resource_type)?;
+
+        let target_dir = std::path::PathBuf::from(game_dir).join(target_dir_name);
+        tokio::fs::create_dir_all(&target_dir).await?;
+
+        log::info!(
+            "[ResourceManager] Installing {} {} into instance {}",
+            project.name,
+            version.version_number,
+            instance_id
+        );
+
+        let reporter: Arc<dyn piston_lib::game::installer::types::ProgressReporter> =
+            Arc::new(piston_lib::game::installer::types::SilentProgressReporter);
+        let final_path = source
+            .download_resource(&version, &target_dir, reporter)
+            .await?;
+
+        if let Some(previous) =
+            crate::resources::ledger::find_custom_remote(instance_id, project_id)?
+        {
+            let previous_path = std::path::PathBuf::from(&previous.local_path);
+            if previous_path != final_path && tokio::fs::metadata(&previous_path).await.is_ok() {
+                log::info!(
+                    "[ResourceManager] Removing previous version file: {:?}",
+                    previous_path
+                );
+                let _ = tokio::fs::remove_file(&previous_path).await;
+            }
+        }
+
+        let meta = tokio::fs::metadata(&final_path).await?;
+        let file_mtime = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+
+        crate::resources::ledger::record_download(
+            instance_id,
+            &final_path,
+            platform,
+            project_id,
+            &project.name,
+            &version,
+            project.resource_type.ledger_name(),
+            (meta.len() as i64, file_mtime),
+        )?;
+
+        if let Err(e) =
+            crate::resources::update_cache::invalidate_instance_update_snapshot(instance_id)
+        {
+            log::warn!(
+                "[update_cache] Failed to invalidate snapshot for instance {}: {}",
+                instance_id,
+                e
+            );
+        }
+
+        crate::resources::ledger::find_custom_remote(instance_id, project_id)?
+            .ok_or_else(|| anyhow!("Installed resource for {} was not recorded", project_id))
+    }
+
+    /// Source for a single request: a [`CustomResourceSource`] when the
+    /// override targets `platform`, otherwise the registered global source.
+    pub async fn source_for(
+        &self,
+        platform: SourcePlatform,
+        source_override: Option<&SourceOverride>,
+    ) -> Result<Arc<dyn ResourceSource>> {
+        match source_override.filter(|o| o.applies_to(platform)) {
+            Some(source_override) => {
+                log::debug!(
+                    "[ResourceManager] Routing {:?} request to {}",
+                    platform,
+                    source_override.base_url
+                );
+                Ok(Arc::new(CustomResourceSource::new(source_override)))
+            }
+            None => self.get_source(platform).await,
+        }
+    }
+
     async fn get_source(&self, platform: SourcePlatform) -> Result<Arc<dyn ResourceSource>> {
         let sources = self.sources.read().await;
         sources
@@ -1036,3 +1313,38 @@ pub fn is_loader_compatible(supported: &[String], target: &str) -> bool {
         supported.iter().any(|l| l.to_lowercase() == t)
     }
 }
+
+/// Folder a single downloaded file of `resource_type` is placed in.
+///
+/// Worlds ship as archives that have to be unpacked into `saves/<world>/`; dropping
+/// the zip into `saves/` would record a resource the game never lists.
+fn single_file_install_dir(resource_type: ResourceType) -> Result<&'static str> {
+    if resource_type == ResourceType::World {
+        return Err(anyhow!(
+            "Worlds are archives and cannot be installed as a single file; import the world into saves/ instead"
+        ));
+    }
+    resource_type.install_dir().ok_or_else(|| {
+        anyhow!(
+            "{:?} resources cannot be installed as a single file",
+            resource_type
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_file_install_dir_rejects_worlds_and_modpacks() {
+        assert_eq!(single_file_install_dir(ResourceType::Mod).unwrap(), "mods");
+        assert_eq!(
+            single_file_install_dir(ResourceType::DataPack).unwrap(),
+            "datapacks"
+        );
+        let err = single_file_install_dir(ResourceType::World).unwrap_err();
+        assert!(err.to_string().contains("Worlds are archives"), "{err}");
+        assert!(single_file_install_dir(ResourceType::Modpack).is_err());
+    }
+}
@@ -1,12 +1,14 @@
 use crate::models::resource::{
-    DependencyType, ReleaseType, ResourceCategory, ResourceDependency, ResourceProject,
-    ResourceType, ResourceVersion, SearchQuery, SearchResponse, SourcePlatform,
+    BodyFormat, DependencyType, GalleryImage, ReleaseType, ResourceBody, ResourceCategory,
+    ResourceDependency, ResourceLinks, ResourceMember, ResourceProject, ResourceType,
+    ResourceVersion, SearchQuery, SearchResponse, SourcePlatform,
 };
-use crate::resources::sources::ResourceSource;
+use crate::resources::sources::{read_json, ResourceSource};
 use crate::utils::url::normalize_url;
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use serde::Deserialize;
+use std::collections::HashMap;
 
 // Include generated obfuscated key
 include!(concat!(env!("OUT_DIR"), "/curseforge_key.rs"));
@@ -18,8 +20,14 @@ fn get_deobfuscated_key() -> String {
         .collect()
 }
 
+pub const CURSEFORGE_API_BASE: &str = "https://api.curseforge.com/v1";
+const CURSEFORGE_API_HOST: &str = "api.curseforge.com";
+
 pub struct CurseForgeSource {
-    api_key: String,
+    /// Only set when `base_url` points at the official API, so the launcher
+    /// key is never sent to a third-party proxy.
+    api_key: Option<String>,
+    base_url: String,
 }
 
 #[derive(Deserialize)]
@@ -54,17 +62,28 @@ struct CFMod {
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 #[allow(dead_code)]
 struct CFScreenshot {
     url: String,
     #[serde(default)]
     thumbnail_url: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
 }
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct CFLinks {
     website_url: String,
+    #[serde(default)]
+    wiki_url: Option<String>,
+    #[serde(default)]
+    issues_url: Option<String>,
+    #[serde(default)]
+    source_url: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -76,6 +95,8 @@ struct CFLogo {
 #[derive(Deserialize)]
 struct CFAuthor {
     name: String,
+    #[serde(default)]
+    url: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -133,6 +154,8 @@ struct CFFile {
     file_date: String,
     download_url: Option<String>,
     dependencies: Vec<CFDependency>,
+    #[serde(default)]
+    file_fingerprint: Option<u32>,
 }
 
 #[derive(Deserialize)]
@@ -175,29 +198,81 @@ struct CFExactMatch {
     file: CFFile,
 }
 
+fn is_official_api(base_url: &str) -> bool {
+    reqwest::Url::parse(base_url)
+        .map(|url| url.scheme() == "https" && url.host_str() == Some(CURSEFORGE_API_HOST))
+        .unwrap_or(false)
+}
+
 impl CurseForgeSource {
     pub fn new() -> Self {
-        let key = get_deobfuscated_key();
-        log::info!(
-            "CurseForgeSource initializing with key (len: {})",
-            key.len()
-        );
+        Self::with_base_url(CURSEFORGE_API_BASE)
+    }
+
+    /// Point every API call at a CurseForge-compatible proxy rooted at
+    /// `base_url` (the equivalent of `https://api.curseforge.com/v1`). The
+    /// API key is only attached when the host is the official API.
+    pub fn with_base_url(base_url: &str) -> Self {
+        let api_key = if is_official_api(base_url) {
+            let key = get_deobfuscated_key();
+            log::info!(
+                "CurseForgeSource initializing with key (len: {})",
+                key.len()
+            );
+            Some(key)
+        } else {
+            log::info!(
+                "CurseForgeSource using {} without the launcher API key",
+                base_url
+            );
+            None
+        };
+
+        Self {
+            api_key,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
 
-        Self { api_key: key }
+    fn with_headers(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        let request = request.header("Accept", "application/json");
+        match &self.api_key {
+            Some(key) => request.header("x-api-key", key),
+            None => request,
+        }
     }
 
     fn http_get(&self, url: &str) -> reqwest::RequestBuilder {
-        piston_lib::client::shared_client()
-            .get(url)
-            .header("x-api-key", &self.api_key)
-            .header("Accept", "application/json")
+        self.with_headers(piston_lib::client::shared_client().get(url))
     }
 
     fn http_post(&self, url: &str) -> reqwest::RequestBuilder {
-        piston_lib::client::shared_client()
-            .post(url)
-            .header("x-api-key", &self.api_key)
-            .header("Accept", "application/json")
+        self.with_headers(piston_lib::client::shared_client().post(url))
+    }
+
+    async fn lookup_fingerprints(&self, fingerprints: &[u32]) -> Result<CFFingerprintData> {
+        let url = format!("{}/fingerprints", self.base_url);
+        let body = serde_json::json!({ "fingerprints": fingerprints });
+        let response = self.http_post(&url).json(&body).send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!(
+                "CurseForge fingerprint lookup failed ({}): {}",
+                status,
+                body
+            ));
+        }
+
+        let result: CFFingerprintResponse = read_json(response).await.map_err(|e| {
+            anyhow!(
+                "CurseForge fingerprint JSON decode error: {}. Fingerprints: {:?}",
+                e,
+                fingerprints
+            )
+        })?;
+        Ok(result.data)
     }
 
     pub fn map_class_id_to_type(class_id: i64) -> ResourceType {
@@ -225,8 +300,8 @@ impl CurseForgeSource {
     }
 
     pub async fn fetch_categories_direct(&self) -> Result<Vec<CFCategoryFull>> {
-        let url = "https://api.curseforge.com/v1/categories?gameId=432";
-        let response = self.http_get(url).send().await?;
+        let url = format!("{}/categories?gameId=432", self.base_url);
+        let response = self.http_get(&url).send().await?;
 
         let status = response.status();
         if !status.is_success() {
@@ -266,7 +341,8 @@ impl CurseForgeSource {
 
         for class_id in types {
             let search_url = format!(
-                "https://api.curseforge.com/v1/mods/search?gameId=432&classId={}&searchFilter={}&sortField=2&sortOrder=desc",
+                "{}/mods/search?gameId=432&classId={}&searchFilter={}&sortField=2&sortOrder=desc",
+                self.base_url,
                 class_id,
                 urlencoding::encode(&slug_lower)
             );
@@ -275,7 +351,7 @@ impl CurseForgeSource {
 
             let response = self.http_get(&search_url).send().await?;
             if response.status().is_success() {
-                let search_res: CFSearchResult = response.json().await?;
+                let search_res: CFSearchResult = read_json(response).await?;
 
                 // First pass: Exact matches (highest priority)
                 for item in &search_res.data {
@@ -399,8 +475,8 @@ impl ResourceSource for CurseForgeSource {
     async fn search(&self, query: SearchQuery) -> Result<SearchResponse> {
         let class_id = Self::map_type_to_class_id(query.resource_type);
         let mut url = format!(
-            "https://api.curseforge.com/v1/mods/search?gameId=432&classId={}&index={}&pageSize={}",
-            class_id, query.offset, query.limit
+            "{}/mods/search?gameId=432&classId={}&index={}&pageSize={}",
+            self.base_url, class_id, query.offset, query.limit
         );
 
         if let Some(text) = query.text {
@@ -479,50 +555,14 @@ impl ResourceSource for CurseForgeSource {
             return Err(anyhow!("CurseForge API error ({}): {}", status, body));
         }
 
-        let response: CFSearchResult = response
-            .json()
+        let response: CFSearchResult = read_json(response)
             .await
             .map_err(|e| anyhow!("CurseForge search JSON decode error: {}. URL: {}", e, url))?;
 
         let hits = response
             .data
             .into_iter()
-            .map(|item| ResourceProject {
-                id: item.id.to_string(),
-                source: SourcePlatform::CurseForge,
-                resource_type: query.resource_type,
-                name: item.name,
-                summary: item.summary,
-                description: None,
-                icon_url: item.logo.map(|l| l.thumbnail_url),
-                author: item
-                    .authors
-                    .first()
-                    .map(|a| a.name.clone())
-                    .unwrap_or_else(|| "Unknown".to_string()),
-                authors: item.authors.iter().map(|a| a.name.clone()).collect(),
-                download_count: item.download_count as u64,
-                follower_count: 0,
-                categories: item
-                    .categories
-                    .into_iter()
-                    .map(|c| c.id.to_string())
-                    .collect(),
-                web_url: item.links.website_url,
-                external_ids: None,
-                featured_gallery: item
-                    .screenshots
-                    .as_ref()
-                    .and_then(|shots| shots.first().map(|s| s.url.clone())),
-                gallery: item
-                    .screenshots
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(|s| s.url)
-                    .collect(),
-                published_at: Some(item.date_created),
-                updated_at: Some(item.date_modified),
-            })
+            .map(|item| project_from_curseforge(item, query.resource_type, None))
             .collect();
 
         let total_hits = response.pagination.total_count as u64;
@@ -537,7 +577,7 @@ impl ResourceSource for CurseForgeSource {
             self.resolve_slug_to_id(id).await?
         };
 
-        let url = format!("https://api.curseforge.com/v1/mods/{}", numeric_id);
+        let url = format!("{}/mods/{}", self.base_url, numeric_id);
         let response = self.http_get(&url).send().await?;
         if !response.status().is_success() {
             let status = response.status();
@@ -549,7 +589,7 @@ impl ResourceSource for CurseForgeSource {
             ));
         }
 
-        let mod_response: CFModResponse = response.json().await.map_err(|e| {
+        let mod_response: CFModResponse = read_json(response).await.map_err(|e| {
             anyhow!(
                 "CurseForge project JSON decode error: {}. ID: {}",
                 e,
@@ -560,60 +600,23 @@ impl ResourceSource for CurseForgeSource {
         let item = mod_response.data;
 
         // Fetch description separately as it's not included in the main mod object
-        let desc_url = format!(
-            "https://api.curseforge.com/v1/mods/{}/description",
-            numeric_id
-        );
+        let desc_url = format!("{}/mods/{}/description", self.base_url, numeric_id);
         let desc_response = self.http_get(&desc_url).send().await?;
         let description = if desc_response.status().is_success() {
-            let desc_data: CFDescriptionResponse = desc_response.json().await.map_err(|e| {
+            let desc_data: CFDescriptionResponse = read_json(desc_response).await.map_err(|e| {
                 anyhow!(
                     "CurseForge description JSON decode error: {}. ID: {}",
                     e,
                     numeric_id
                 )
             })?;
-            Some(desc_data.data)
+            Some(sanitize_description(&desc_data.data))
         } else {
             None
         };
 
-        Ok(ResourceProject {
-            id: item.id.to_string(),
-            source: SourcePlatform::CurseForge,
-            resource_type: Self::map_class_id_to_type(item.class_id.unwrap_or(6)),
-            name: item.name,
-            summary: item.summary,
-            description,
-            icon_url: item.logo.map(|l| l.thumbnail_url),
-            author: item
-                .authors
-                .first()
-                .map(|a| a.name.clone())
-                .unwrap_or_else(|| "Unknown".to_string()),
-            authors: item.authors.iter().map(|a| a.name.clone()).collect(),
-            download_count: item.download_count as u64,
-            follower_count: 0,
-            categories: item
-                .categories
-                .into_iter()
-                .map(|c| c.id.to_string())
-                .collect(),
-            web_url: item.links.website_url,
-            external_ids: None,
-            featured_gallery: item
-                .screenshots
-                .as_ref()
-                .and_then(|shots| shots.first().map(|s| s.url.clone())),
-            gallery: item
-                .screenshots
-                .unwrap_or_default()
-                .into_iter()
-                .map(|s| s.url)
-                .collect(),
-            published_at: Some(item.date_created),
-            updated_at: Some(item.date_modified),
-        })
+        let resource_type = Self::map_class_id_to_type(item.class_id.unwrap_or(6));
+        Ok(project_from_curseforge(item, resource_type, description))
     }
 
     async fn get_projects(&self, ids: &[String]) -> Result<Vec<ResourceProject>> {
@@ -621,7 +624,7 @@ impl ResourceSource for CurseForgeSource {
             return Ok(Vec::new());
         }
 
-        let url = "https://api.curseforge.com/v1/mods";
+        let url = format!("{}/mods", self.base_url);
         let mod_ids: Vec<u32> = ids.iter().filter_map(|id| id.parse::<u32>().ok()).collect();
 
         if mod_ids.is_empty() {
@@ -632,7 +635,7 @@ impl ResourceSource for CurseForgeSource {
             "modIds": mod_ids
         });
 
-        let response = self.http_post(url).json(&body).send().await?;
+        let response = self.http_post(&url).json(&body).send().await?;
 
         if !response.status().is_success() {
             return Err(anyhow!(
@@ -646,46 +649,15 @@ impl ResourceSource for CurseForgeSource {
             data: Vec<CFMod>,
         }
 
-        let response: CFBatchResponse = response.json().await?;
+        let response: CFBatchResponse = read_json(response).await?;
 
         Ok(response
             .data
             .into_iter()
-            .map(|item| ResourceProject {
-                id: item.id.to_string(),
-                source: SourcePlatform::CurseForge,
-                resource_type: Self::map_class_id_to_type(item.class_id.unwrap_or(6)),
-                name: item.name,
-                summary: item.summary,
-                description: None, // Batch fetch doesn't include full description
-                icon_url: item.logo.map(|l| l.thumbnail_url),
-                author: item
-                    .authors
-                    .first()
-                    .map(|a| a.name.clone())
-                    .unwrap_or_else(|| "Unknown".to_string()),
-                authors: item.authors.iter().map(|a| a.name.clone()).collect(),
-                download_count: item.download_count as u64,
-                follower_count: 0,
-                categories: item
-                    .categories
-                    .into_iter()
-                    .map(|c| c.id.to_string())
-                    .collect(),
-                web_url: item.links.website_url,
-                external_ids: None,
-                featured_gallery: item
-                    .screenshots
-                    .as_ref()
-                    .and_then(|shots| shots.first().map(|s| s.url.clone())),
-                gallery: item
-                    .screenshots
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(|s| s.url)
-                    .collect(),
-                published_at: Some(item.date_created),
-                updated_at: Some(item.date_modified),
+            .map(|item| {
+                let resource_type = Self::map_class_id_to_type(item.class_id.unwrap_or(6));
+                // Batch fetch doesn't include full description
+                project_from_curseforge(item, resource_type, None)
             })
             .collect())
     }
@@ -720,8 +692,8 @@ impl ResourceSource for CurseForgeSource {
         // but this is much higher than the previous 200.
         for page_idx in 0..20 {
             let mut url = format!(
-                "https://api.curseforge.com/v1/mods/{}/files?index={}&pageSize={}",
-                numeric_id, index, page_size
+                "{}/mods/{}/files?index={}&pageSize={}",
+                self.base_url, numeric_id, index, page_size
             );
 
             if let Some(gv) = game_version {
@@ -744,7 +716,7 @@ impl ResourceSource for CurseForgeSource {
                 ));
             }
 
-            let res_data: CFFilesResponse = response.json().await.map_err(|e| {
+            let res_data: CFFilesResponse = read_json(response).await.map_err(|e| {
                 anyhow!(
                     "CurseForge versions JSON decode error: {}. Project: {}",
                     e,
@@ -852,12 +824,9 @@ impl ResourceSource for CurseForgeSource {
         };
 
         let url = if numeric_id.is_empty() {
-            format!("https://api.curseforge.com/v1/mods/files/{}", version_id)
+            format!("{}/mods/files/{}", self.base_url, version_id)
         } else {
-            format!(
-                "https://api.curseforge.com/v1/mods/{}/files/{}",
-                numeric_id, version_id
-            )
+            format!("{}/mods/{}/files/{}", self.base_url, numeric_id, version_id)
         };
         let response = self.http_get(&url).send().await?;
 
@@ -869,7 +838,7 @@ impl ResourceSource for CurseForgeSource {
             ));
         }
 
-        let res_data: CFFileResponse = response.json().await?;
+        let res_data: CFFileResponse = read_json(response).await?;
         let file = res_data.data;
         let sha1 = file
             .hashes
@@ -942,104 +911,391 @@ impl ResourceSource for CurseForgeSource {
             .parse::<u32>()
             .map_err(|_| anyhow!("Invalid fingerprint: {}", hash))?;
 
-        let url = "https://api.curseforge.com/v1/fingerprints";
-        let body = serde_json::json!({
-            "fingerprints": [fingerprint]
-        });
+        let data = self.lookup_fingerprints(&[fingerprint]).await?;
+        let match_item = data
+            .exact_matches
+            .first()
+            .ok_or_else(|| anyhow!("No match found for fingerprint"))?;
 
-        let response = self.http_post(url).json(&body).send().await?;
+        let project = self.get_project(&match_item.id.to_string()).await?;
+        let version = version_from_fingerprint_match(&match_item.file, &project.id);
+        Ok((project, version))
+    }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(anyhow!(
-                "CurseForge fingerprint lookup failed ({}): {}",
-                status,
-                body
-            ));
+    async fn get_by_hashes(
+        &self,
+        hashes: &[String],
+    ) -> Result<Vec<Option<(ResourceProject, ResourceVersion)>>> {
+        let fingerprints: Vec<Option<u32>> = hashes
+            .iter()
+            .map(|hash| hash.trim().parse::<u32>().ok())
+            .collect();
+        let mut requested: Vec<u32> = fingerprints.iter().flatten().copied().collect();
+        requested.sort_unstable();
+        requested.dedup();
+        if requested.is_empty() {
+            return Ok(vec![None; hashes.len()]);
         }
 
-        let result: CFFingerprintResponse = response.json().await.map_err(|e| {
-            anyhow!(
-                "CurseForge fingerprint JSON decode error: {}. Hash: {}",
-                e,
-                hash
-            )
-        })?;
+        let data = self.lookup_fingerprints(&requested).await?;
+        let matches = matches_by_fingerprint(&fingerprints, &data);
+        let mut project_ids: Vec<String> = matches
+            .iter()
+            .flatten()
+            .map(|item| item.id.to_string())
+            .collect();
+        project_ids.sort();
+        project_ids.dedup();
+        let projects: HashMap<String, ResourceProject> = self
+            .get_projects(&project_ids)
+            .await?
+            .into_iter()
+            .map(|project| (project.id.clone(), project))
+            .collect();
 
-        let match_item = result
-            .data
-            .exact_matches
+        Ok(matches
+            .into_iter()
+            .map(|item| {
+                let item = item?;
+                let project = projects.get(&item.id.to_string())?.clone();
+                let version = version_from_fingerprint_match(&item.file, &project.id);
+                Some((project, version))
+            })
+            .collect())
+    }
+
+    fn platform(&self) -> SourcePlatform {
+        SourcePlatform::CurseForge
+    }
+}
+
+/// Line up the exact matches of a fingerprint lookup with the requested
+/// fingerprints; unparsable and unmatched entries stay `None`.
+/// Site that relative links and images in CurseForge descriptions point into.
+const CURSEFORGE_SITE_BASE: &str = "https://www.curseforge.com/";
+
+/// Strip scripts, event handlers and other active content from a CurseForge
+/// HTML description, and make relative links and images absolute.
+fn sanitize_description(html: &str) -> String {
+    let base = url::Url::parse(CURSEFORGE_SITE_BASE).expect("valid CurseForge site URL");
+    ammonia::Builder::default()
+        .url_relative(ammonia::UrlRelative::RewriteWithBase(base))
+        .clean(html)
+        .to_string()
+}
+
+/// Build a project from its API payload. `description` is the sanitized HTML
+/// description, which only the single-project fetch retrieves.
+fn project_from_curseforge(
+    item: CFMod,
+    resource_type: ResourceType,
+    description: Option<String>,
+) -> ResourceProject {
+    let gallery: Vec<GalleryImage> = item
+        .screenshots
+        .unwrap_or_default()
+        .into_iter()
+        .map(|s| GalleryImage {
+            url: s.url,
+            title: s.title.filter(|t| !t.is_empty()),
+            description: s.description.filter(|d| !d.is_empty()),
+            featured: false,
+        })
+        .collect();
+    let non_empty = |link: Option<String>| link.filter(|l| !l.trim().is_empty());
+
+    ResourceProject {
+        id: item.id.to_string(),
+        source: SourcePlatform::CurseForge,
+        resource_type,
+        name: item.name,
+        summary: item.summary,
+        body: description.clone().map(|content| ResourceBody {
+            format: BodyFormat::Html,
+            content,
+        }),
+        description,
+        icon_url: item.logo.map(|l| l.thumbnail_url),
+        author: item
+            .authors
             .first()
-            .ok_or_else(|| anyhow!("No match found for fingerprint"))?;
+            .map(|a| a.name.clone())
+            .unwrap_or_else(|| "Unknown".to_string()),
+        authors: item.authors.iter().map(|a| a.name.clone()).collect(),
+        download_count: item.download_count as u64,
+        follower_count: 0,
+        categories: item
+            .categories
+            .into_iter()
+            .map(|c| c.id.to_string())
+            .collect(),
+        web_url: item.links.website_url.clone(),
+        external_ids: None,
+        featured_gallery: gallery.first().map(|image| image.url.clone()),
+        gallery,
+        published_at: Some(item.date_created),
+        updated_at: Some(item.date_modified),
+        members: item
+            .authors
+            .into_iter()
+            .map(|a| ResourceMember {
+                name: a.name,
+                role: None,
+                avatar_url: None,
+                url: a.url,
+            })
+            .collect(),
+        // The CurseForge API does not expose a project's license.
+        license: None,
+        links: Some(ResourceLinks {
+            website: non_empty(Some(item.links.website_url)),
+            source: non_empty(item.links.source_url),
+            issues: non_empty(item.links.issues_url),
+            wiki: non_empty(item.links.wiki_url),
+            discord: None,
+            donations: Vec::new(),
+        }),
+        client_side: None,
+        server_side: None,
+    }
+}
 
-        let project = self.get_project(&match_item.id.to_string()).await?;
+fn matches_by_fingerprint<'a>(
+    fingerprints: &[Option<u32>],
+    data: &'a CFFingerprintData,
+) -> Vec<Option<&'a CFExactMatch>> {
+    let by_fingerprint: HashMap<u32, &CFExactMatch> = data
+        .exact_matches
+        .iter()
+        .filter_map(|item| Some((item.file.file_fingerprint?, item)))
+        .collect();
+    fingerprints
+        .iter()
+        .map(|fingerprint| by_fingerprint.get(fingerprint.as_ref()?).copied())
+        .collect()
+}
 
-        // Map CFFile to ResourceVersion
-        let file = &match_item.file;
-        let sha1 = file
-            .hashes
-            .iter()
-            .find(|h| h.algo == 1)
-            .map(|h| h.value.clone())
-            .unwrap_or_default();
-        let mut loaders = Vec::new();
-        let mut game_versions = Vec::new();
-        for v in &file.game_versions {
-            match v.to_lowercase().as_str() {
-                "forge" | "fabric" | "quilt" | "neoforge" | "optifine" | "iris" => {
-                    loaders.push(v.clone())
-                }
-                _ => game_versions.push(v.clone()),
+fn version_from_fingerprint_match(file: &CFFile, project_id: &str) -> ResourceVersion {
+    let sha1 = file
+        .hashes
+        .iter()
+        .find(|h| h.algo == 1)
+        .map(|h| h.value.clone())
+        .unwrap_or_default();
+    let mut loaders = Vec::new();
+    let mut game_versions = Vec::new();
+    for v in &file.game_versions {
+        match v.to_lowercase().as_str() {
+            "forge" | "fabric" | "quilt" | "neoforge" | "optifine" | "iris" => {
+                loaders.push(v.clone())
             }
+            _ => game_versions.push(v.clone()),
         }
+    }
 
-        let version = ResourceVersion {
-            id: file.id.to_string(),
-            project_id: project.id.clone(),
-            version_number: file.display_name.clone(),
-            game_versions,
-            loaders,
-            download_url: file.download_url.clone().unwrap_or_else(|| {
-                // Fallback for hidden CF files
-                let id = file.id;
-                let major = id / 1000;
-                let minor = id % 1000;
-                format!(
-                    "https://edge.forgecdn.net/files/{}/{:03}/{}",
-                    major, minor, file.file_name
-                )
-            }),
-            file_name: file.file_name.clone(),
-            release_type: match file.release_type {
-                1 => ReleaseType::Release,
-                2 => ReleaseType::Beta,
-                3 => ReleaseType::Alpha,
-                _ => ReleaseType::Release,
+    ResourceVersion {
+        id: file.id.to_string(),
+        project_id: project_id.to_string(),
+        version_number: file.display_name.clone(),
+        game_versions,
+        loaders,
+        download_url: file.download_url.clone().unwrap_or_else(|| {
+            // Fallback for hidden CF files
+            let id = file.id;
+            let major = id / 1000;
+            let minor = id % 1000;
+            format!(
+                "https://edge.forgecdn.net/files/{}/{:03}/{}",
+                major, minor, file.file_name
+            )
+        }),
+        file_name: file.file_name.clone(),
+        release_type: match file.release_type {
+            1 => ReleaseType::Release,
+            2 => ReleaseType::Beta,
+            3 => ReleaseType::Alpha,
+            _ => ReleaseType::Release,
+        },
+        hash: sha1,
+        dependencies: file
+            .dependencies
+            .iter()
+            .map(|d| ResourceDependency {
+                project_id: d.mod_id.to_string(),
+                version_id: None,
+                file_name: None,
+                dependency_type: match d.relation_type {
+                    2 => DependencyType::Optional,
+                    3 => DependencyType::Required,
+                    5 => DependencyType::Incompatible,
+                    _ => DependencyType::Embedded,
+                },
+            })
+            .collect(),
+        published_at: Some(file.file_date.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_key_only_sent_to_official_host() {
+        assert!(CurseForgeSource::new().api_key.is_some());
+        assert!(
+            CurseForgeSource::with_base_url("https://api.curseforge.com/v1/")
+                .api_key
+                .is_some()
+        );
+        assert!(CurseForgeSource::with_base_url("https://cf.example.com/v1")
+            .api_key
+            .is_none());
+        assert!(
+            CurseForgeSource::with_base_url("https://api.curseforge.com.example.com/v1")
+                .api_key
+                .is_none()
+        );
+    }
+
+    fn exact_match(project_id: u32, file_id: i64, fingerprint: u32) -> serde_json::Value {
+        serde_json::json!({
+            "id": project_id,
+            "file": {
+                "id": file_id,
+                "modId": project_id,
+                "displayName": format!("file-{}", file_id),
+                "fileName": format!("file-{}.jar", file_id),
+                "releaseType": 1,
+                "gameVersions": ["1.20.1", "Forge"],
+                "hashes": [{ "value": "abc", "algo": 1 }],
+                "fileDate": "2024-01-01T00:00:00Z",
+                "downloadUrl": null,
+                "dependencies": [],
+                "fileFingerprint": fingerprint,
+            }
+        })
+    }
+
+    #[test]
+    fn partial_fingerprint_matches_line_up_with_inputs() {
+        // Matches come back in arbitrary order and only for known files.
+        let data: CFFingerprintData = serde_json::from_value(serde_json::json!({
+            "exactMatches": [
+                exact_match(238222, 5101366, 3000),
+                exact_match(32274, 4712345, 1000),
+            ]
+        }))
+        .unwrap();
+        let fingerprints = [Some(1000), Some(2000), None, Some(3000), Some(1000)];
+
+        let matched: Vec<Option<i64>> = matches_by_fingerprint(&fingerprints, &data)
+            .into_iter()
+            .map(|item| item.map(|item| item.file.id))
+            .collect();
+
+        assert_eq!(
+            matched,
+            vec![Some(4712345), None, None, Some(5101366), Some(4712345)]
+        );
+    }
+
+    #[test]
+    fn fingerprint_match_maps_to_version() {
+        let data: CFFingerprintData = serde_json::from_value(serde_json::json!({
+            "exactMatches": [exact_match(238222, 5101366, 3000)]
+        }))
+        .unwrap();
+
+        let version = version_from_fingerprint_match(&data.exact_matches[0].file, "238222");
+
+        assert_eq!(version.id, "5101366");
+        assert_eq!(version.project_id, "238222");
+        assert_eq!(version.loaders, vec!["Forge"]);
+        assert_eq!(version.game_versions, vec!["1.20.1"]);
+        assert_eq!(version.hash, "abc");
+        assert_eq!(
+            version.download_url,
+            "https://edge.forgecdn.net/files/5101/366/file-5101366.jar"
+        );
+    }
+
+    #[test]
+    fn project_details_come_from_mod_payload() {
+        // Trimmed from GET /v1/mods/238222.
+        let item: CFMod = serde_json::from_value(serde_json::json!({
+            "id": 238222,
+            "slug": "jei",
+            "name": "Just Enough Items (JEI)",
+            "summary": "View Items and Recipes",
+            "links": {
+                "websiteUrl": "https://www.curseforge.com/minecraft/mc-mods/jei",
+                "wikiUrl": "",
+                "issuesUrl": "https://github.com/mezz/JustEnoughItems/issues",
+                "sourceUrl": "https://github.com/mezz/JustEnoughItems"
             },
-            hash: sha1,
-            dependencies: file
-                .dependencies
-                .iter()
-                .map(|d| ResourceDependency {
-                    project_id: d.mod_id.to_string(),
-                    version_id: None,
-                    file_name: None,
-                    dependency_type: match d.relation_type {
-                        2 => DependencyType::Optional,
-                        3 => DependencyType::Required,
-                        5 => DependencyType::Incompatible,
-                        _ => DependencyType::Embedded,
-                    },
-                })
-                .collect(),
-            published_at: Some(file.file_date.clone()),
-        };
+            "logo": { "thumbnailUrl": "https://media.forgecdn.net/avatars/thumbnails/29/69/64/64/635838945588716414.jpeg" },
+            "authors": [{ "id": 17072262, "name": "mezz", "url": "https://www.curseforge.com/members/17072262-mezz?username=mezz" }],
+            "downloadCount": 380000000.0,
+            "categories": [{ "id": 423 }],
+            "classId": 6,
+            "screenshots": [
+                { "id": 31417, "title": "Recipes", "description": "", "url": "https://media.forgecdn.net/attachments/31/417/recipes.png", "thumbnailUrl": "https://media.forgecdn.net/attachments/thumbnails/31/417/310/172/recipes.png" }
+            ],
+            "dateCreated": "2015-11-23T05:04:54.53Z",
+            "dateModified": "2026-10-01T12:00:00.00Z"
+        }))
+        .unwrap();
+
+        let project = project_from_curseforge(
+            item,
+            ResourceType::Mod,
+            Some("<p>Item and recipe viewing</p>".to_string()),
+        );
 
-        Ok((project, version))
+        assert_eq!(
+            project.description.as_deref(),
+            Some("<p>Item and recipe viewing</p>")
+        );
+        assert_eq!(
+            project.body.as_ref().map(|b| b.format),
+            Some(BodyFormat::Html)
+        );
+        assert_eq!(project.gallery[0].title.as_deref(), Some("Recipes"));
+        assert_eq!(project.gallery[0].description, None);
+        assert_eq!(
+            project.featured_gallery.as_deref(),
+            Some("https://media.forgecdn.net/attachments/31/417/recipes.png")
+        );
+        assert_eq!(project.members.len(), 1);
+        assert_eq!(project.members[0].name, "mezz");
+        assert_eq!(project.license, None);
+        let links = project.links.unwrap();
+        assert_eq!(links.wiki, None);
+        assert_eq!(
+            links.issues.as_deref(),
+            Some("https://github.com/mezz/JustEnoughItems/issues")
+        );
     }
 
-    fn platform(&self) -> SourcePlatform {
-        SourcePlatform::CurseForge
+    #[test]
+    fn description_is_sanitized_and_links_made_absolute() {
+        // Shape of GET /v1/mods/{id}/description.
+        let payload: CFDescriptionResponse = serde_json::from_value(serde_json::json!({
+            "data": "<p onclick=\"steal()\">Hello<script>alert(1)</script></p>\
+                     <a href=\"/linkout?remoteUrl=https%3a%2f%2fexample.com\">site</a>\
+                     <img src=\"/images/banner.png\">\
+                     <a href=\"javascript:alert(1)\">bad</a>"
+        }))
+        .unwrap();
+
+        let html = sanitize_description(&payload.data);
+
+        assert!(!html.contains("script"));
+        assert!(!html.contains("onclick"));
+        assert!(!html.contains("javascript:"));
+        assert!(html.contains("href=\"https://www.curseforge.com/linkout?remoteUrl="));
+        assert!(html.contains("src=\"https://www.curseforge.com/images/banner.png\""));
+        assert!(html.contains("<p>Hello</p>"));
     }
 }
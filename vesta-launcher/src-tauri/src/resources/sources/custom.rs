@@ -0,0 +1,198 @@
+use crate::models::resource::{
+    ResourceCategory, ResourceProject, ResourceVersion, SearchQuery, SearchResponse, SourcePlatform,
+};
+use crate::resources::sources::curseforge::CurseForgeSource;
+use crate::resources::sources::modrinth::ModrinthSource;
+use crate::resources::sources::ResourceSource;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use piston_lib::game::installer::types::ProgressReporter;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Per-instance redirect of one platform's API to a self-hosted server,
+/// stored as JSON in `instance.instance_source_override`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceOverride {
+    pub platform: SourcePlatform,
+    pub base_url: String,
+}
+
+impl SourceOverride {
+    /// Parse and validate the stored JSON form.
+    pub fn parse(json: &str) -> Result<Self> {
+        let parsed: SourceOverride = serde_json::from_str(json)
+            .map_err(|e| anyhow!("Invalid source override JSON: {}", e))?;
+        parsed.validated()
+    }
+
+    /// Read the instance column, ignoring (and logging) unusable values so a
+    /// bad override falls back to the official API instead of breaking installs.
+    pub fn from_column(value: Option<&str>) -> Option<Self> {
+        let value = value.map(str::trim).filter(|v| !v.is_empty())?;
+        match Self::parse(value) {
+            Ok(source_override) => Some(source_override),
+            Err(e) => {
+                log::warn!("[ResourceManager] Ignoring instance source override: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Require an https URL and drop trailing slashes so paths join cleanly.
+    pub fn validated(self) -> Result<Self> {
+        let base_url = self.base_url.trim().trim_end_matches('/').to_string();
+        let parsed = reqwest::Url::parse(&base_url)
+            .map_err(|e| anyhow!("Invalid source override URL '{}': {}", base_url, e))?;
+        if parsed.scheme() != "https" || parsed.host_str().is_none() {
+            return Err(anyhow!(
+                "Source override URL must be an https address: {}",
+                base_url
+            ));
+        }
+        Ok(Self {
+            platform: self.platform,
+            base_url,
+        })
+    }
+
+    /// Whether requests for `platform` should go through this override.
+    pub fn applies_to(&self, platform: SourcePlatform) -> bool {
+        self.platform == platform
+    }
+}
+
+/// A Modrinth- or CurseForge-compatible source that talks to a custom
+/// `base_url` instead of the official endpoint.
+pub struct CustomResourceSource {
+    base_url: String,
+    inner: Box<dyn ResourceSource>,
+}
+
+impl CustomResourceSource {
+    pub fn new(source_override: &SourceOverride) -> Self {
+        let inner: Box<dyn ResourceSource> = match source_override.platform {
+            SourcePlatform::Modrinth => {
+                Box::new(ModrinthSource::with_base_url(&source_override.base_url))
+            }
+            SourcePlatform::CurseForge => {
+                Box::new(CurseForgeSource::with_base_url(&source_override.base_url))
+            }
+        };
+        Self {
+            base_url: source_override.base_url.clone(),
+            inner,
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+#[async_trait]
+impl ResourceSource for CustomResourceSource {
+    async fn search(&self, query: SearchQuery) -> Result<SearchResponse> {
+        self.inner.search(query).await
+    }
+
+    async fn get_project(&self, id: &str) -> Result<ResourceProject> {
+        self.inner.get_project(id).await
+    }
+
+    async fn get_projects(&self, ids: &[String]) -> Result<Vec<ResourceProject>> {
+        self.inner.get_projects(ids).await
+    }
+
+    async fn get_versions(
+        &self,
+        project_id: &str,
+        game_version: Option<&str>,
+        loader: Option<&str>,
+    ) -> Result<Vec<ResourceVersion>> {
+        self.inner
+            .get_versions(project_id, game_version, loader)
+            .await
+    }
+
+    async fn get_version(&self, project_id: &str, version_id: &str) -> Result<ResourceVersion> {
+        self.inner.get_version(project_id, version_id).await
+    }
+
+    async fn get_by_hash(&self, hash: &str) -> Result<(ResourceProject, ResourceVersion)> {
+        self.inner.get_by_hash(hash).await
+    }
+
+    async fn get_by_hashes(
+        &self,
+        hashes: &[String],
+    ) -> Result<Vec<Option<(ResourceProject, ResourceVersion)>>> {
+        self.inner.get_by_hashes(hashes).await
+    }
+
+    async fn get_categories(&self) -> Result<Vec<ResourceCategory>> {
+        self.inner.get_categories().await
+    }
+
+    async fn download_resource(
+        &self,
+        version: &ResourceVersion,
+        dest_dir: &Path,
+        reporter: Arc<dyn ProgressReporter>,
+    ) -> Result<PathBuf> {
+        self.inner
+            .download_resource(version, dest_dir, reporter)
+            .await
+    }
+
+    fn platform(&self) -> SourcePlatform {
+        self.inner.platform()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_normalizes_override() {
+        let parsed = SourceOverride::parse(
+            r#"{"platform":"modrinth","base_url":" https://mr.example.com/v2/ "}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.platform, SourcePlatform::Modrinth);
+        assert_eq!(parsed.base_url, "https://mr.example.com/v2");
+        assert!(parsed.applies_to(SourcePlatform::Modrinth));
+        assert!(!parsed.applies_to(SourcePlatform::CurseForge));
+
+        let cf = SourceOverride::parse(
+            r#"{"platform":"curseforge","base_url":"https://10.0.0.2:8443"}"#,
+        )
+        .unwrap();
+        assert_eq!(cf.platform, SourcePlatform::CurseForge);
+    }
+
+    #[test]
+    fn rejects_unusable_overrides() {
+        assert!(SourceOverride::parse(r#"{"platform":"modrinth","base_url":"ftp://x"}"#).is_err());
+        assert!(SourceOverride::parse(r#"{"platform":"modrinth","base_url":"http://x"}"#).is_err());
+        assert!(
+            SourceOverride::parse(r#"{"platform":"modrinth","base_url":"not a url"}"#).is_err()
+        );
+        assert!(SourceOverride::parse(r#"{"platform":"hangar","base_url":"https://x"}"#).is_err());
+        assert_eq!(SourceOverride::from_column(Some("{broken")), None);
+        assert_eq!(SourceOverride::from_column(Some("  ")), None);
+        assert_eq!(SourceOverride::from_column(None), None);
+    }
+
+    #[test]
+    fn custom_source_keeps_platform_and_base_url() {
+        let source = CustomResourceSource::new(&SourceOverride {
+            platform: SourcePlatform::CurseForge,
+            base_url: "https://cf.example.com/v1".to_string(),
+        });
+        assert_eq!(source.platform(), SourcePlatform::CurseForge);
+        assert_eq!(source.base_url(), "https://cf.example.com/v1");
+    }
+}
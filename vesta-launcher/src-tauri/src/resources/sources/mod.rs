@@ -1,15 +1,33 @@
 use crate::models::resource::{
     ResourceCategory, ResourceProject, ResourceVersion, SearchQuery, SearchResponse, SourcePlatform,
 };
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use async_trait::async_trait;
+use piston_lib::game::installer::types::ProgressReporter;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 pub mod curseforge;
+pub mod custom;
 pub mod modrinth;
 
 #[cfg(test)]
 mod tests;
 
+/// Maximum number of hashes sent in one bulk lookup request.
+pub const HASH_BATCH_SIZE: usize = 100;
+
+/// Decode a JSON API response, counting its body as metadata traffic.
+pub(crate) async fn read_json<T: serde::de::DeserializeOwned>(
+    response: reqwest::Response,
+) -> Result<T> {
+    use piston_lib::game::installer::core::traffic::{record_traffic, TrafficCategory};
+
+    let body = response.bytes().await?;
+    record_traffic(TrafficCategory::Metadata, body.len() as u64);
+    Ok(serde_json::from_slice(&body)?)
+}
+
 #[async_trait]
 pub trait ResourceSource: Send + Sync {
     async fn search(&self, query: SearchQuery) -> Result<SearchResponse>;
@@ -23,7 +41,65 @@ pub trait ResourceSource: Send + Sync {
     ) -> Result<Vec<ResourceVersion>>;
     async fn get_version(&self, project_id: &str, version_id: &str) -> Result<ResourceVersion>;
     async fn get_by_hash(&self, hash: &str) -> Result<(ResourceProject, ResourceVersion)>;
+
+    /// Identify several files at once. The result lines up with `hashes`;
+    /// unknown files are `None`. Platforms with a bulk endpoint override this.
+    async fn get_by_hashes(
+        &self,
+        hashes: &[String],
+    ) -> Result<Vec<Option<(ResourceProject, ResourceVersion)>>> {
+        let mut results = Vec::with_capacity(hashes.len());
+        for hash in hashes {
+            results.push(self.get_by_hash(hash).await.ok());
+        }
+        Ok(results)
+    }
+
     async fn get_categories(&self) -> Result<Vec<ResourceCategory>>;
 
+    /// Download the primary file of `version` into `dest_dir`, verifying its
+    /// SHA-1 when the platform provides one, and return the written path.
+    async fn download_resource(
+        &self,
+        version: &ResourceVersion,
+        dest_dir: &Path,
+        reporter: Arc<dyn ProgressReporter>,
+    ) -> Result<PathBuf> {
+        let file_name = primary_file_name(version)?;
+        if version.download_url.is_empty() {
+            return Err(anyhow!(
+                "Download URL is empty. This resource may not be available for direct download."
+            ));
+        }
+
+        let dest = dest_dir.join(file_name);
+        let expected_sha1 = Some(version.hash.as_str()).filter(|hash| !hash.is_empty());
+        reporter.set_message(&format!("Downloading {}", file_name));
+        piston_lib::game::installer::core::downloader::download_to_path(
+            piston_lib::client::shared_client(),
+            &version.download_url,
+            &dest,
+            expected_sha1,
+            reporter.as_ref(),
+        )
+        .await?;
+
+        Ok(dest)
+    }
+
     fn platform(&self) -> SourcePlatform;
 }
+
+/// File name of the version's primary file, rejecting names that would escape
+/// the destination directory.
+pub(crate) fn primary_file_name(version: &ResourceVersion) -> Result<&str> {
+    let name = version.file_name.as_str();
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+        return Err(anyhow!(
+            "Invalid file name '{}' for version {}",
+            name,
+            version.id
+        ));
+    }
+    Ok(name)
+}
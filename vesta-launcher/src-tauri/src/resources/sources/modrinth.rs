@@ -1,13 +1,15 @@
 use crate::models::resource::{
-    DependencyType, ReleaseType, ResourceCategory, ResourceDependency, ResourceProject,
+    BodyFormat, DependencyType, GalleryImage, ReleaseType, ResourceBody, ResourceCategory,
+    ResourceDependency, ResourceLicense, ResourceLinks, ResourceMember, ResourceProject,
     ResourceType, ResourceVersion, SearchQuery, SearchResponse, SourcePlatform,
 };
-use crate::resources::sources::ResourceSource;
+use crate::resources::sources::{read_json, ResourceSource};
 use anyhow::anyhow;
 use anyhow::Result;
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Deserialize)]
 struct ModrinthCategory {
@@ -45,6 +47,8 @@ struct ModrinthProjectHit {
     follows: u64,
     gallery: Option<Vec<String>>,
     featured_gallery: Option<String>,
+    client_side: Option<String>,
+    server_side: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -64,6 +68,27 @@ struct ModrinthProject {
     followers: u64,
     team: String,
     curseforge_id: Option<String>,
+    license: Option<ModrinthLicense>,
+    issues_url: Option<String>,
+    source_url: Option<String>,
+    wiki_url: Option<String>,
+    discord_url: Option<String>,
+    #[serde(default)]
+    donation_urls: Vec<ModrinthDonationUrl>,
+    client_side: Option<String>,
+    server_side: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ModrinthLicense {
+    id: String,
+    name: String,
+    url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ModrinthDonationUrl {
+    url: String,
 }
 
 #[derive(Deserialize)]
@@ -75,10 +100,10 @@ struct ModrinthTeamMember {
 #[derive(Deserialize)]
 struct ModrinthUser {
     username: String,
+    avatar_url: Option<String>,
 }
 
 #[derive(Deserialize)]
-#[allow(dead_code)]
 struct ModrinthGalleryItem {
     url: String,
     featured: Option<bool>,
@@ -121,14 +146,24 @@ struct ModrinthHashes {
     sha1: String,
 }
 
+pub const MODRINTH_API_BASE: &str = "https://api.modrinth.com/v2";
+
 pub struct ModrinthSource {
     client: Client,
+    base_url: String,
 }
 
 impl ModrinthSource {
     pub fn new() -> Self {
+        Self::with_base_url(MODRINTH_API_BASE)
+    }
+
+    /// Point every API call at a Modrinth-compatible server rooted at
+    /// `base_url` (the equivalent of `https://api.modrinth.com/v2`).
+    pub fn with_base_url(base_url: &str) -> Self {
         Self {
             client: piston_lib::client::shared_client().clone(),
+            base_url: base_url.trim_end_matches('/').to_string(),
         }
     }
 
@@ -145,8 +180,7 @@ impl ModrinthSource {
             ));
         }
 
-        response
-            .json()
+        read_json(response)
             .await
             .map_err(|e| anyhow!("Modrinth search JSON decode error: {}. URL: {}", e, url))
     }
@@ -156,7 +190,8 @@ impl ModrinthSource {
 impl ResourceSource for ModrinthSource {
     async fn search(&self, query: SearchQuery) -> Result<SearchResponse> {
         let mut url = format!(
-            "https://api.modrinth.com/v2/search?query={}&limit={}&offset={}",
+            "{}/search?query={}&limit={}&offset={}",
+            self.base_url,
             urlencoding::encode(query.text.as_deref().unwrap_or("")),
             query.limit,
             query.offset
@@ -235,8 +270,8 @@ impl ResourceSource for ModrinthSource {
         if result.hits.is_empty() && is_blank_query && has_optional_filters && query.offset == 0 {
             let fallback_facets = format!("[[\"project_type:{}\"]]", mr_type);
             let mut fallback_url = format!(
-                "https://api.modrinth.com/v2/search?query=&limit={}&offset=0",
-                query.limit
+                "{}/search?query=&limit={}&offset=0",
+                self.base_url, query.limit
             );
             if let Some(sort) = &query.sort_by {
                 fallback_url.push_str(&format!("&index={}", sort));
@@ -287,10 +322,21 @@ impl ResourceSource for ModrinthSource {
                     categories: hit.categories.unwrap_or_default(),
                     web_url: format!("https://modrinth.com/{}/{}", hit.project_type, hit.slug),
                     external_ids: None,
-                    gallery: hit.gallery.unwrap_or_default(),
+                    gallery: hit
+                        .gallery
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(GalleryImage::from_url)
+                        .collect(),
                     featured_gallery: hit.featured_gallery,
                     published_at: hit.published,
                     updated_at: hit.updated,
+                    body: None,
+                    members: Vec::new(),
+                    license: None,
+                    links: None,
+                    client_side: hit.client_side,
+                    server_side: hit.server_side,
                 }
             })
             .collect();
@@ -302,7 +348,7 @@ impl ResourceSource for ModrinthSource {
     }
 
     async fn get_project(&self, id: &str) -> Result<ResourceProject> {
-        let url = format!("https://api.modrinth.com/v2/project/{}", id);
+        let url = format!("{}/project/{}", self.base_url, id);
         let response = self.client.get(&url).send().await?;
 
         if !response.status().is_success() {
@@ -315,95 +361,23 @@ impl ResourceSource for ModrinthSource {
             ));
         }
 
-        let project: ModrinthProject = response
-            .json()
+        let project: ModrinthProject = read_json(response)
             .await
             .map_err(|e| anyhow!("Modrinth project JSON decode error: {}. ID: {}", e, id))?;
 
         // Fetch team members to find author
-        let team_url = format!("https://api.modrinth.com/v2/team/{}/members", project.team);
+        let team_url = format!("{}/team/{}/members", self.base_url, project.team);
         let team_response = self.client.get(&team_url).send().await?;
 
         let members: Vec<ModrinthTeamMember> = if team_response.status().is_success() {
-            team_response.json().await.unwrap_or_else(|_| Vec::new())
+            read_json(team_response)
+                .await
+                .unwrap_or_else(|_| Vec::new())
         } else {
             Vec::new()
         };
 
-        // Use the first "Owner" or just first member
-        let author_name = if members.is_empty() {
-            "Unknown".to_string()
-        } else {
-            members
-                .iter()
-                .find(|m| m.role.to_lowercase() == "owner")
-                .map(|m| m.user.username.clone())
-                .unwrap_or_else(|| {
-                    members
-                        .first()
-                        .map(|m| m.user.username.clone())
-                        .unwrap_or_else(|| "Unknown".to_string())
-                })
-        };
-
-        let authors_list = if members.is_empty() {
-            vec!["Unknown".to_string()]
-        } else {
-            members.iter().map(|m| m.user.username.clone()).collect()
-        };
-
-        let res_type = match project.project_type.as_str() {
-            "mod" => ResourceType::Mod,
-            "resourcepack" => ResourceType::ResourcePack,
-            "shader" => ResourceType::Shader,
-            "datapack" => ResourceType::DataPack,
-            "modpack" => ResourceType::Modpack,
-            _ => ResourceType::Mod,
-        };
-
-        let mut external_ids = std::collections::HashMap::new();
-        if let Some(cf_id) = project.curseforge_id {
-            external_ids.insert("curseforge".to_string(), cf_id);
-        }
-
-        let featured_gallery = project
-            .gallery
-            .as_ref()
-            .and_then(|items| items.iter().find(|i| i.featured == Some(true)))
-            .and_then(|item| item.raw_url.clone().or_else(|| Some(item.url.clone())));
-
-        Ok(ResourceProject {
-            id: project.id,
-            source: SourcePlatform::Modrinth,
-            resource_type: res_type,
-            name: project.title,
-            summary: project.description,
-            description: Some(project.body),
-            icon_url: project.icon_url,
-            author: author_name,
-            authors: authors_list,
-            download_count: project.downloads,
-            follower_count: project.followers,
-            categories: project.categories,
-            web_url: format!(
-                "https://modrinth.com/{}/{}",
-                project.project_type, project.slug
-            ),
-            external_ids: if external_ids.is_empty() {
-                None
-            } else {
-                Some(external_ids)
-            },
-            gallery: project
-                .gallery
-                .unwrap_or_default()
-                .into_iter()
-                .map(|i| i.raw_url.unwrap_or(i.url))
-                .collect(),
-            featured_gallery,
-            published_at: Some(project.published),
-            updated_at: Some(project.updated),
-        })
+        Ok(project_from_modrinth(project, &members))
     }
 
     async fn get_projects(&self, ids: &[String]) -> Result<Vec<ResourceProject>> {
@@ -414,7 +388,7 @@ impl ResourceSource for ModrinthSource {
         let ids_json = serde_json::to_string(ids)?;
         let response = self
             .client
-            .get("https://api.modrinth.com/v2/projects")
+            .get(format!("{}/projects", self.base_url))
             .query(&[("ids", &ids_json)])
             .send()
             .await?;
@@ -426,7 +400,7 @@ impl ResourceSource for ModrinthSource {
             ));
         }
 
-        let projects: Vec<ModrinthProject> = response.json().await.map_err(|e| {
+        let projects: Vec<ModrinthProject> = read_json(response).await.map_err(|e| {
             anyhow!(
                 "Modrinth batch projects JSON decode error: {}. IDs: {}",
                 e,
@@ -434,59 +408,10 @@ impl ResourceSource for ModrinthSource {
             )
         })?;
 
+        // The batch endpoint has no team data, so authors stay unknown.
         Ok(projects
             .into_iter()
-            .map(|p| {
-                let res_type = match p.project_type.as_str() {
-                    "mod" => ResourceType::Mod,
-                    "resourcepack" => ResourceType::ResourcePack,
-                    "shader" => ResourceType::Shader,
-                    "datapack" => ResourceType::DataPack,
-                    "modpack" => ResourceType::Modpack,
-                    _ => ResourceType::Mod,
-                };
-
-                let mut external_ids = std::collections::HashMap::new();
-                if let Some(cf_id) = p.curseforge_id {
-                    external_ids.insert("curseforge".to_string(), cf_id);
-                }
-
-                let featured_gallery = p
-                    .gallery
-                    .as_ref()
-                    .and_then(|items| items.iter().find(|i| i.featured == Some(true)))
-                    .and_then(|item| item.raw_url.clone().or_else(|| Some(item.url.clone())));
-
-                ResourceProject {
-                    id: p.id,
-                    source: SourcePlatform::Modrinth,
-                    resource_type: res_type,
-                    name: p.title,
-                    summary: p.description,
-                    description: Some(p.body),
-                    icon_url: p.icon_url,
-                    author: "Unknown".to_string(),
-                    authors: vec!["Unknown".to_string()],
-                    download_count: p.downloads,
-                    follower_count: p.followers,
-                    categories: p.categories,
-                    web_url: format!("https://modrinth.com/{}/{}", p.project_type, p.slug),
-                    external_ids: if external_ids.is_empty() {
-                        None
-                    } else {
-                        Some(external_ids)
-                    },
-                    gallery: p
-                        .gallery
-                        .unwrap_or_default()
-                        .into_iter()
-                        .map(|i| i.raw_url.unwrap_or(i.url))
-                        .collect(),
-                    featured_gallery,
-                    published_at: Some(p.published),
-                    updated_at: Some(p.updated),
-                }
-            })
+            .map(|p| project_from_modrinth(p, &[]))
             .collect())
     }
 
@@ -496,7 +421,7 @@ impl ResourceSource for ModrinthSource {
         game_version: Option<&str>,
         loader: Option<&str>,
     ) -> Result<Vec<ResourceVersion>> {
-        let url = format!("https://api.modrinth.com/v2/project/{}/version", project_id);
+        let url = format!("{}/project/{}/version", self.base_url, project_id);
 
         let mut params = Vec::new();
         if let Some(gv) = game_version {
@@ -518,7 +443,7 @@ impl ResourceSource for ModrinthSource {
             ));
         }
 
-        let versions: Vec<ModrinthVersion> = response.json().await.map_err(|e| {
+        let versions: Vec<ModrinthVersion> = read_json(response).await.map_err(|e| {
             anyhow!(
                 "Modrinth versions JSON decode error: {}. Project: {}",
                 e,
@@ -592,7 +517,7 @@ impl ResourceSource for ModrinthSource {
     }
 
     async fn get_version(&self, _project_id: &str, version_id: &str) -> Result<ResourceVersion> {
-        let url = format!("https://api.modrinth.com/v2/version/{}", version_id);
+        let url = format!("{}/version/{}", self.base_url, version_id);
         let response = self.client.get(&url).send().await?;
 
         if !response.status().is_success() {
@@ -602,7 +527,7 @@ impl ResourceSource for ModrinthSource {
             ));
         }
 
-        let v: ModrinthVersion = response.json().await?;
+        let v: ModrinthVersion = read_json(response).await?;
         let primary_file = v
             .files
             .iter()
@@ -657,10 +582,7 @@ impl ResourceSource for ModrinthSource {
     }
 
     async fn get_by_hash(&self, hash: &str) -> Result<(ResourceProject, ResourceVersion)> {
-        let url = format!(
-            "https://api.modrinth.com/v2/version_file/{}?algorithm=sha1",
-            hash
-        );
+        let url = format!("{}/version_file/{}?algorithm=sha1", self.base_url, hash);
         let response = self.client.get(&url).send().await?;
 
         if !response.status().is_success() {
@@ -673,7 +595,7 @@ impl ResourceSource for ModrinthSource {
             ));
         }
 
-        let v: ModrinthVersion = response.json().await.map_err(|e| {
+        let v: ModrinthVersion = read_json(response).await.map_err(|e| {
             anyhow!(
                 "Modrinth hash lookup JSON decode error: {}. Hash: {}",
                 e,
@@ -683,68 +605,77 @@ impl ResourceSource for ModrinthSource {
 
         let project = self.get_project(&v.project_id).await?;
 
-        let primary_file = v
-            .files
-            .iter()
-            .find(|f| f.primary)
-            .or_else(|| {
-                v.files.iter().find(|f| {
-                    let url = f.url.to_lowercase();
-                    (url.ends_with(".mrpack") || url.ends_with(".jar") || url.ends_with(".zip"))
-                        && !url.ends_with(".cosign-bundle.json")
-                })
-            })
-            .unwrap_or(&v.files[0]);
-
+        let version = version_from_hash_match(v);
         log::info!(
-            "[Modrinth] get_by_hash: Selected project {} version {}, file: {} (primary: {})",
+            "[Modrinth] get_by_hash: Selected project {} version {}, file: {}",
             project.name,
-            v.version_number,
-            primary_file.filename,
-            primary_file.primary
+            version.version_number,
+            version.file_name
         );
 
-        let version = ResourceVersion {
-            id: v.id,
-            project_id: v.project_id,
-            version_number: v.version_number,
-            game_versions: v.game_versions,
-            loaders: v.loaders,
-            download_url: primary_file.url.clone(),
-            file_name: primary_file.filename.clone(),
-            release_type: match v.version_type.as_str() {
-                "release" => ReleaseType::Release,
-                "beta" => ReleaseType::Beta,
-                "alpha" => ReleaseType::Alpha,
-                _ => ReleaseType::Release,
-            },
-            hash: primary_file.hashes.sha1.clone(),
-            dependencies: v
-                .dependencies
-                .into_iter()
-                .filter(|d| d.project_id.is_some())
-                .map(|d| ResourceDependency {
-                    project_id: d.project_id.unwrap(),
-                    version_id: d.version_id,
-                    file_name: d.file_name,
-                    dependency_type: match d.dependency_type.as_str() {
-                        "required" => DependencyType::Required,
-                        "optional" => DependencyType::Optional,
-                        "incompatible" => DependencyType::Incompatible,
-                        "embedded" => DependencyType::Embedded,
-                        _ => DependencyType::Optional,
-                    },
-                })
-                .collect(),
-            published_at: Some(v.date_published),
-        };
-
         Ok((project, version))
     }
 
+    async fn get_by_hashes(
+        &self,
+        hashes: &[String],
+    ) -> Result<Vec<Option<(ResourceProject, ResourceVersion)>>> {
+        let mut requested: Vec<String> = hashes
+            .iter()
+            .map(|hash| hash.trim().to_ascii_lowercase())
+            .filter(|hash| !hash.is_empty())
+            .collect();
+        requested.sort();
+        requested.dedup();
+        if requested.is_empty() {
+            return Ok(vec![None; hashes.len()]);
+        }
+
+        let url = format!("{}/version_files", self.base_url);
+        let body = serde_json::json!({ "hashes": requested, "algorithm": "sha1" });
+        let response = self.client.post(&url).json(&body).send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!(
+                "Modrinth bulk hash lookup failed ({}): {}",
+                status,
+                body
+            ));
+        }
+
+        let by_hash: HashMap<String, ModrinthVersion> = read_json(response).await.map_err(|e| {
+            anyhow!(
+                "Modrinth bulk hash lookup JSON decode error: {}. Hashes: {}",
+                e,
+                requested.len()
+            )
+        })?;
+
+        let mut project_ids: Vec<String> = by_hash.values().map(|v| v.project_id.clone()).collect();
+        project_ids.sort();
+        project_ids.dedup();
+        let projects: HashMap<String, ResourceProject> = self
+            .get_projects(&project_ids)
+            .await?
+            .into_iter()
+            .map(|project| (project.id.clone(), project))
+            .collect();
+
+        Ok(versions_by_input(hashes, by_hash)
+            .into_iter()
+            .map(|version| {
+                let version = version?;
+                let project = projects.get(&version.project_id)?.clone();
+                Some((project, version))
+            })
+            .collect())
+    }
+
     async fn get_categories(&self) -> Result<Vec<ResourceCategory>> {
-        let url = "https://api.modrinth.com/v2/tag/category";
-        let response = self.client.get(url).send().await?;
+        let url = format!("{}/tag/category", self.base_url);
+        let response = self.client.get(&url).send().await?;
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
@@ -755,7 +686,7 @@ impl ResourceSource for ModrinthSource {
             ));
         }
 
-        let cats: Vec<ModrinthCategory> = response.json().await?;
+        let cats: Vec<ModrinthCategory> = read_json(response).await?;
         Ok(cats
             .into_iter()
             .filter(|c| {
@@ -802,3 +733,301 @@ impl ResourceSource for ModrinthSource {
         SourcePlatform::Modrinth
     }
 }
+
+/// Map a `version_files` response (keyed by hash) back onto the requested
+/// hashes; unmatched entries stay `None`.
+fn versions_by_input(
+    hashes: &[String],
+    mut by_hash: HashMap<String, ModrinthVersion>,
+) -> Vec<Option<ResourceVersion>> {
+    let mut converted: HashMap<String, ResourceVersion> = HashMap::new();
+    hashes
+        .iter()
+        .map(|hash| {
+            let key = hash.trim().to_ascii_lowercase();
+            if let Some(version) = by_hash.remove(&key) {
+                converted.insert(key.clone(), version_from_hash_match(version));
+            }
+            converted.get(&key).cloned()
+        })
+        .collect()
+}
+
+/// Build a project from its API payload. `members` is the project's team and
+/// may be empty, in which case the author is unknown.
+fn project_from_modrinth(
+    project: ModrinthProject,
+    members: &[ModrinthTeamMember],
+) -> ResourceProject {
+    // Use the first "Owner" or just first member
+    let author_name = members
+        .iter()
+        .find(|m| m.role.to_lowercase() == "owner")
+        .or_else(|| members.first())
+        .map(|m| m.user.username.clone())
+        .unwrap_or_else(|| "Unknown".to_string());
+
+    let authors_list = if members.is_empty() {
+        vec!["Unknown".to_string()]
+    } else {
+        members.iter().map(|m| m.user.username.clone()).collect()
+    };
+
+    let res_type = match project.project_type.as_str() {
+        "mod" => ResourceType::Mod,
+        "resourcepack" => ResourceType::ResourcePack,
+        "shader" => ResourceType::Shader,
+        "datapack" => ResourceType::DataPack,
+        "modpack" => ResourceType::Modpack,
+        _ => ResourceType::Mod,
+    };
+
+    let mut external_ids = std::collections::HashMap::new();
+    if let Some(cf_id) = project.curseforge_id {
+        external_ids.insert("curseforge".to_string(), cf_id);
+    }
+
+    let gallery: Vec<GalleryImage> = project
+        .gallery
+        .unwrap_or_default()
+        .into_iter()
+        .map(|i| GalleryImage {
+            url: i.raw_url.unwrap_or(i.url),
+            title: i.title,
+            description: i.description,
+            featured: i.featured == Some(true),
+        })
+        .collect();
+    let featured_gallery = gallery
+        .iter()
+        .find(|image| image.featured)
+        .map(|image| image.url.clone());
+
+    let web_url = format!(
+        "https://modrinth.com/{}/{}",
+        project.project_type, project.slug
+    );
+
+    ResourceProject {
+        id: project.id,
+        source: SourcePlatform::Modrinth,
+        resource_type: res_type,
+        name: project.title,
+        summary: project.description,
+        description: Some(project.body.clone()),
+        icon_url: project.icon_url,
+        author: author_name,
+        authors: authors_list,
+        download_count: project.downloads,
+        follower_count: project.followers,
+        categories: project.categories,
+        web_url: web_url.clone(),
+        external_ids: if external_ids.is_empty() {
+            None
+        } else {
+            Some(external_ids)
+        },
+        gallery,
+        featured_gallery,
+        published_at: Some(project.published),
+        updated_at: Some(project.updated),
+        body: Some(ResourceBody {
+            format: BodyFormat::Markdown,
+            content: project.body,
+        }),
+        members: members
+            .iter()
+            .map(|m| ResourceMember {
+                name: m.user.username.clone(),
+                role: Some(m.role.clone()),
+                avatar_url: m.user.avatar_url.clone(),
+                url: Some(format!("https://modrinth.com/user/{}", m.user.username)),
+            })
+            .collect(),
+        license: project.license.map(|l| ResourceLicense {
+            id: l.id,
+            name: l.name,
+            url: l.url,
+        }),
+        links: Some(ResourceLinks {
+            website: Some(web_url),
+            source: project.source_url,
+            issues: project.issues_url,
+            wiki: project.wiki_url,
+            discord: project.discord_url,
+            donations: project.donation_urls.into_iter().map(|d| d.url).collect(),
+        }),
+        client_side: project.client_side,
+        server_side: project.server_side,
+    }
+}
+
+/// The version a hash lookup resolved to, described by its primary file.
+fn version_from_hash_match(v: ModrinthVersion) -> ResourceVersion {
+    let primary_file = v
+        .files
+        .iter()
+        .find(|f| f.primary)
+        .or_else(|| {
+            v.files.iter().find(|f| {
+                let url = f.url.to_lowercase();
+                (url.ends_with(".mrpack") || url.ends_with(".jar") || url.ends_with(".zip"))
+                    && !url.ends_with(".cosign-bundle.json")
+            })
+        })
+        .unwrap_or(&v.files[0]);
+
+    ResourceVersion {
+        id: v.id,
+        project_id: v.project_id,
+        version_number: v.version_number,
+        game_versions: v.game_versions,
+        loaders: v.loaders,
+        download_url: primary_file.url.clone(),
+        file_name: primary_file.filename.clone(),
+        release_type: match v.version_type.as_str() {
+            "release" => ReleaseType::Release,
+            "beta" => ReleaseType::Beta,
+            "alpha" => ReleaseType::Alpha,
+            _ => ReleaseType::Release,
+        },
+        hash: primary_file.hashes.sha1.clone(),
+        dependencies: v
+            .dependencies
+            .into_iter()
+            .filter(|d| d.project_id.is_some())
+            .map(|d| ResourceDependency {
+                project_id: d.project_id.unwrap(),
+                version_id: d.version_id,
+                file_name: d.file_name,
+                dependency_type: match d.dependency_type.as_str() {
+                    "required" => DependencyType::Required,
+                    "optional" => DependencyType::Optional,
+                    "incompatible" => DependencyType::Incompatible,
+                    "embedded" => DependencyType::Embedded,
+                    _ => DependencyType::Optional,
+                },
+            })
+            .collect(),
+        published_at: Some(v.date_published),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(id: &str, sha1: &str) -> ModrinthVersion {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "project_id": "proj",
+            "version_number": "1.0.0",
+            "game_versions": ["1.21.1"],
+            "loaders": ["fabric"],
+            "files": [
+                { "url": "https://cdn.example/a.jar.cosign-bundle.json", "filename": "a.jar.cosign-bundle.json", "hashes": { "sha1": "ffff" }, "primary": false },
+                { "url": "https://cdn.example/a.jar", "filename": "a.jar", "hashes": { "sha1": sha1 }, "primary": false }
+            ],
+            "version_type": "beta",
+            "dependencies": [],
+            "date_published": "2026-01-01T00:00:00Z"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn bulk_hash_results_line_up_with_inputs() {
+        let by_hash = HashMap::from([("abc".to_string(), version("v1", "abc"))]);
+        let hashes = vec!["ABC".to_string(), "missing".to_string(), "abc".to_string()];
+
+        let versions = versions_by_input(&hashes, by_hash);
+
+        assert_eq!(versions.len(), 3);
+        assert!(versions[1].is_none());
+        for matched in [&versions[0], &versions[2]] {
+            let matched = matched.as_ref().unwrap();
+            assert_eq!(matched.id, "v1");
+            assert_eq!(matched.file_name, "a.jar");
+            assert_eq!(matched.release_type, ReleaseType::Beta);
+        }
+    }
+
+    #[test]
+    fn project_details_come_from_project_and_team_payloads() {
+        // Trimmed from GET /v2/project/sodium and its team members.
+        let project: ModrinthProject = serde_json::from_value(serde_json::json!({
+            "id": "AANobbMI",
+            "slug": "sodium",
+            "title": "Sodium",
+            "description": "The fastest rendering optimization mod for Minecraft.",
+            "body": "## Features\n\nSodium is a powerful rendering engine.",
+            "icon_url": "https://cdn.modrinth.com/data/AANobbMI/icon.png",
+            "downloads": 60000000,
+            "followers": 30000,
+            "categories": ["optimization"],
+            "project_type": "mod",
+            "team": "4reLOAKe",
+            "published": "2021-01-03T00:53:34.185936Z",
+            "updated": "2026-09-30T18:21:05.154339Z",
+            "license": { "id": "LicenseRef-Polyform-Shield-License-1.0.0", "name": "", "url": "https://polyformproject.org/licenses/shield/1.0.0/" },
+            "issues_url": "https://github.com/CaffeineMC/sodium/issues",
+            "source_url": "https://github.com/CaffeineMC/sodium",
+            "wiki_url": null,
+            "discord_url": "https://caffeinemc.net/discord",
+            "donation_urls": [{ "id": "patreon", "platform": "Patreon", "url": "https://www.patreon.com/jellysquid" }],
+            "gallery": [
+                { "url": "https://cdn.modrinth.com/data/AANobbMI/images/a.webp", "raw_url": "https://cdn.modrinth.com/data/AANobbMI/images/a.png", "featured": false, "title": "Comparison", "description": null },
+                { "url": "https://cdn.modrinth.com/data/AANobbMI/images/b.webp", "featured": true, "title": null, "description": "Shaders off" }
+            ],
+            "curseforge_id": null
+        }))
+        .unwrap();
+        let members: Vec<ModrinthTeamMember> = serde_json::from_value(serde_json::json!([
+            { "role": "Developer", "user": { "username": "IMS", "avatar_url": null } },
+            { "role": "Owner", "user": { "username": "jellysquid3", "avatar_url": "https://cdn.modrinth.com/user/avatar.png" } }
+        ]))
+        .unwrap();
+
+        let project = project_from_modrinth(project, &members);
+
+        assert_eq!(project.author, "jellysquid3");
+        assert_eq!(
+            project.body,
+            Some(ResourceBody {
+                format: BodyFormat::Markdown,
+                content: "## Features\n\nSodium is a powerful rendering engine.".to_string(),
+            })
+        );
+        assert_eq!(project.gallery.len(), 2);
+        assert_eq!(
+            project.gallery[0].url,
+            "https://cdn.modrinth.com/data/AANobbMI/images/a.png"
+        );
+        assert_eq!(project.gallery[0].title.as_deref(), Some("Comparison"));
+        assert_eq!(
+            project.featured_gallery.as_deref(),
+            Some("https://cdn.modrinth.com/data/AANobbMI/images/b.webp")
+        );
+        assert_eq!(project.members.len(), 2);
+        assert_eq!(project.members[1].role.as_deref(), Some("Owner"));
+        assert_eq!(
+            project.members[1].url.as_deref(),
+            Some("https://modrinth.com/user/jellysquid3")
+        );
+        assert_eq!(
+            project.license.as_ref().map(|l| l.id.as_str()),
+            Some("LicenseRef-Polyform-Shield-License-1.0.0")
+        );
+        let links = project.links.unwrap();
+        assert_eq!(
+            links.website.as_deref(),
+            Some("https://modrinth.com/mod/sodium")
+        );
+        assert_eq!(
+            links.source.as_deref(),
+            Some("https://github.com/CaffeineMC/sodium")
+        );
+        assert_eq!(links.wiki, None);
+        assert_eq!(links.donations, vec!["https://www.patreon.com/jellysquid"]);
+    }
+}
@@ -141,4 +141,34 @@ mod tests {
             }
         }
     }
+
+    fn version_with_file(file_name: &str) -> crate::models::resource::ResourceVersion {
+        crate::models::resource::ResourceVersion {
+            id: "v1".to_string(),
+            project_id: "p1".to_string(),
+            version_number: "1.0.0".to_string(),
+            game_versions: vec![],
+            loaders: vec![],
+            download_url: "https://example.com/file.jar".to_string(),
+            file_name: file_name.to_string(),
+            release_type: crate::models::resource::ReleaseType::Release,
+            hash: String::new(),
+            dependencies: vec![],
+            published_at: None,
+        }
+    }
+
+    #[test]
+    fn primary_file_name_rejects_paths() {
+        use crate::resources::sources::primary_file_name;
+
+        assert_eq!(
+            primary_file_name(&version_with_file("sodium-0.5.jar")).unwrap(),
+            "sodium-0.5.jar"
+        );
+        assert!(primary_file_name(&version_with_file("")).is_err());
+        assert!(primary_file_name(&version_with_file("..")).is_err());
+        assert!(primary_file_name(&version_with_file("../evil.jar")).is_err());
+        assert!(primary_file_name(&version_with_file("mods\\evil.jar")).is_err());
+    }
 }
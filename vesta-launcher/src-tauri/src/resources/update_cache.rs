@@ -14,12 +14,23 @@ pub const VERSION_CACHE_TTL_MINUTES: i64 = 30;
 pub const INSTANCE_UPDATE_CHECK_TTL_MINUTES: i64 = 5;
 
 pub fn instance_update_fingerprint(inst: &Instance) -> String {
-    format!(
+    let mut fingerprint = format!(
         "{}|{}|{}",
         inst.minecraft_version,
         inst.modloader.as_deref().unwrap_or("vanilla"),
         inst.modpack_version_id.as_deref().unwrap_or("")
-    )
+    );
+    // Only instances with a source override get the extra segment, so
+    // snapshots for everyone else stay valid.
+    if let Some(source_override) = inst
+        .instance_source_override
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())
+    {
+        fingerprint.push('|');
+        fingerprint.push_str(source_override);
+    }
+    fingerprint
 }
 
 fn parse_naive_datetime(value: &str) -> Option<NaiveDateTime> {
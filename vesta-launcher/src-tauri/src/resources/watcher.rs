@@ -3,7 +3,8 @@ use crate::models::resource::SourcePlatform;
 pub use crate::resources::ledger::ResourceProvenance;
 use crate::resources::ResourceManager;
 use crate::schema::installed_resource::dsl as ir_dsl;
-use crate::utils::hash::{calculate_curseforge_fingerprint, calculate_sha1};
+use crate::utils::fingerprint_cache::cached_file_hashes;
+use crate::utils::hash::HashAlgorithm;
 use crate::utils::instance_helpers::normalize_path;
 use anyhow::Context;
 use anyhow::Result;
@@ -219,11 +220,8 @@ impl ResourceWatcher {
                 failed: 0,
             });
         }
+        let mut files = Vec::with_capacity(candidates.len());
         for path in candidates {
-            let app = self.app_handle.clone();
-            let limiter_spawn = scan_limiter.clone();
-            let in_flight_spawn = in_flight_scans.clone();
-
             // Get metadata for quick check
             let path_meta = path.clone();
             let (file_size, file_mtime) =
@@ -240,6 +238,15 @@ impl ResourceWatcher {
                                 .unwrap_or(0),
                         )
                     });
+            files.push((path, file_size, file_mtime));
+        }
+
+        prefetch_hash_matches(&self.app_handle, &files).await;
+
+        for (path, file_size, file_mtime) in files {
+            let app = self.app_handle.clone();
+            let limiter_spawn = scan_limiter.clone();
+            let in_flight_spawn = in_flight_scans.clone();
 
             if !mark_in_flight(&in_flight_scans, db_id, &path).await {
                 summary.skipped += 1;
@@ -471,6 +478,97 @@ fn is_enabled_path(path: &Path) -> bool {
     !path.to_string_lossy().to_lowercase().ends_with(".disabled")
 }
 
+/// Whether the ledger already describes this file with the same size, mtime
+/// and enabled state, so hashing and identification can be skipped.
+fn metadata_unchanged(
+    path_str: &str,
+    file_size: i64,
+    file_mtime: i64,
+    is_enabled: bool,
+) -> Result<bool> {
+    use crate::utils::db::get_vesta_conn;
+    use diesel::prelude::*;
+    let Ok(mut conn) = get_vesta_conn() else {
+        return Ok(false);
+    };
+    let Some(res) = ir_dsl::installed_resource
+        .filter(ir_dsl::local_path.eq(path_str))
+        .first::<InstalledResource>(&mut conn)
+        .optional()?
+    else {
+        return Ok(false);
+    };
+
+    // Resources marked as unresolved "manual" files or "modpack" overrides with no
+    // remote ID are identified again, to see if they can be linked now.
+    let unresolved_manual = res.platform == "manual" && res.remote_id.is_empty();
+    let unresolved_modpack = res.platform == "modpack" && res.remote_id.is_empty();
+    Ok(res.file_size == file_size
+        && res.file_mtime == file_mtime
+        && res.is_enabled == is_enabled
+        && !unresolved_manual
+        && !unresolved_modpack)
+}
+
+/// Look up every file of a scan that needs identification with the platforms'
+/// bulk hash endpoints, so the per-file workers are served from the
+/// [`ResourceManager`] hash cache instead of one request per file.
+async fn prefetch_hash_matches(app: &AppHandle, files: &[(PathBuf, i64, i64)]) {
+    let network_manager = app.state::<crate::utils::network::NetworkManager>();
+    if network_manager.get_status() == crate::utils::network::NetworkStatus::Offline {
+        return;
+    }
+
+    let mut sha1s = Vec::new();
+    let mut fingerprints = Vec::new();
+    for (path, file_size, file_mtime) in files {
+        let path_str = normalize_path(path);
+        if metadata_unchanged(&path_str, *file_size, *file_mtime, is_enabled_path(path))
+            .unwrap_or(false)
+        {
+            continue;
+        }
+        let Ok(hashes) = cached_file_hashes(
+            path,
+            &[HashAlgorithm::Sha1, HashAlgorithm::CurseForgeFingerprint],
+        )
+        .await
+        else {
+            continue;
+        };
+        sha1s.extend(hashes.sha1);
+        fingerprints.extend(hashes.curseforge_fingerprint.map(|fp| fp.to_string()));
+    }
+    if sha1s.is_empty() {
+        return;
+    }
+
+    let resource_manager = app.state::<ResourceManager>();
+    for (platform, hashes) in [
+        (SourcePlatform::Modrinth, sha1s),
+        (SourcePlatform::CurseForge, fingerprints),
+    ] {
+        if hashes.is_empty() {
+            continue;
+        }
+        match tokio::time::timeout(
+            Duration::from_secs(30),
+            resource_manager.get_by_hashes(platform, &hashes),
+        )
+        .await
+        {
+            Ok(Ok(results)) => log::info!(
+                "[ResourceWatcher] Bulk {:?} lookup matched {}/{} files",
+                platform,
+                results.iter().filter(|r| r.is_some()).count(),
+                hashes.len()
+            ),
+            Ok(Err(e)) => log::warn!("[ResourceWatcher] Bulk {:?} lookup failed: {}", platform, e),
+            Err(_) => log::warn!("[ResourceWatcher] Bulk {:?} lookup timed out", platform),
+        }
+    }
+}
+
 async fn identify_and_link_resource(
     app: &AppHandle,
     instance_db_id: i32,
@@ -505,42 +603,22 @@ async fn identify_and_link_resource(
     let is_enabled = is_enabled_path(path);
 
     // 1. FAST CHECK: Metadata Skip
-    {
-        use crate::models::installed_resource::InstalledResource;
-        use crate::schema::installed_resource::dsl as ir_dsl;
-        use crate::utils::db::get_vesta_conn;
-        use diesel::prelude::*;
-        if let Ok(mut conn) = get_vesta_conn() {
-            let existing = ir_dsl::installed_resource
-                .filter(ir_dsl::local_path.eq(&path_str))
-                .first::<InstalledResource>(&mut conn)
-                .optional()?;
-
-            if let Some(res) = existing {
-                // If metadata matches AND enabled status matches, we can skip everything
-                // EXCEPT if the resource is currently marked as a "modpack" override with no remote ID.
-                // In that case, we want to try identifying it at least once to see if it can be linked.
-                if res.file_size == file_size
-                    && res.file_mtime == file_mtime
-                    && res.is_enabled == is_enabled
-                {
-                    let unresolved_manual = res.platform == "manual" && res.remote_id.is_empty();
-                    if (!unresolved_manual)
-                        && (res.platform != "modpack" || !res.remote_id.is_empty())
-                    {
-                        log::debug!(
-                            "[ResourceWatcher] Metadata match for {}, skipping scan",
-                            path_str
-                        );
-                        return Ok(());
-                    }
-                }
-            }
-        }
+    if metadata_unchanged(&path_str, file_size, file_mtime, is_enabled)? {
+        log::debug!(
+            "[ResourceWatcher] Metadata match for {}, skipping scan",
+            path_str
+        );
+        return Ok(());
     }
 
     // 2. IDENTIFY: If metadata changed or is new, we must hash
-    let hash = calculate_sha1(path)?;
+    // Both platforms' digests come from one read, and are reused on rescans.
+    let hashes = cached_file_hashes(
+        path,
+        &[HashAlgorithm::Sha1, HashAlgorithm::CurseForgeFingerprint],
+    )
+    .await?;
+    let hash = hashes.sha1.clone().context("SHA1 was not computed")?;
     log::debug!("[ResourceWatcher] Identified hash for {:?}: {}", path, hash);
 
     let network_manager = app.state::<crate::utils::network::NetworkManager>();
@@ -649,7 +727,7 @@ async fn identify_and_link_resource(
                 }
             }
             SourcePlatform::CurseForge => {
-                if let Ok(fp) = calculate_curseforge_fingerprint(path) {
+                if let Some(fp) = hashes.curseforge_fingerprint {
                     if let Ok(Ok((project, version))) = tokio::time::timeout(
                         Duration::from_secs(12),
                         resource_manager.get_by_hash(SourcePlatform::CurseForge, &fp.to_string()),
@@ -60,6 +60,13 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    app_setting (key) {
+        key -> Text,
+        value -> Text,
+    }
+}
+
 diesel::table! {
     global_java_paths (id) {
         id -> Integer,
@@ -70,4 +77,33 @@ diesel::table! {
     }
 }
 
-diesel::allow_tables_to_appear_in_same_query!(app_config, global_java_paths,);
+diesel::table! {
+    theme_preset (id) {
+        id -> Text,
+        name -> Text,
+        builtin -> Bool,
+        payload -> Text,
+        created_at -> Text,
+    }
+}
+
+diesel::table! {
+    window_state (label) {
+        label -> Text,
+        x -> Integer,
+        y -> Integer,
+        width -> Integer,
+        height -> Integer,
+        maximized -> Bool,
+        monitor_name -> Nullable<Text>,
+        updated_at -> Text,
+    }
+}
+
+diesel::allow_tables_to_appear_in_same_query!(
+    app_config,
+    app_setting,
+    global_java_paths,
+    theme_preset,
+    window_state,
+);
@@ -22,6 +22,8 @@ diesel::table! {
         theme_data -> Nullable<Text>,
         theme_window_effect -> Nullable<Text>,
         theme_background_opacity -> Nullable<Integer>,
+        xuid -> Nullable<Text>,
+        xbox_avatar_url -> Nullable<Text>,
     }
 }
 
@@ -106,6 +108,13 @@ diesel::table! {
         pre_launch_hook -> Nullable<Text>,
         wrapper_command -> Nullable<Text>,
         post_exit_hook -> Nullable<Text>,
+        deleted_at -> Nullable<Text>,
+        instance_source_override -> Nullable<Text>,
+        verify_on_launch -> Nullable<Bool>,
+        notes -> Nullable<Text>,
+        config_override_json -> Nullable<Text>,
+        trashed_from_directory -> Nullable<Text>,
+        slug -> Nullable<Text>,
     }
 }
 
@@ -168,6 +177,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    instance_operation (id) {
+        id -> Integer,
+        instance_id -> Integer,
+        kind -> Text,
+        started_at -> Text,
+        finished_at -> Nullable<Text>,
+        success -> Nullable<Bool>,
+        error_code -> Nullable<Text>,
+        details -> Text,
+    }
+}
+
 diesel::table! {
     instance_resource_update_check (instance_id) {
         instance_id -> Integer,
@@ -177,6 +199,20 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    play_session (id) {
+        id -> Integer,
+        instance_id -> Integer,
+        started_at -> Text,
+        ended_at -> Text,
+        exit_code -> Nullable<Integer>,
+        crashed -> Bool,
+        safe_mode -> Bool,
+        partial_minutes -> Nullable<Integer>,
+        active_seconds -> Nullable<Integer>,
+    }
+}
+
 diesel::table! {
     resource_metadata_cache (id) {
         id -> Nullable<Integer>,
@@ -228,6 +264,40 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    task_history (id) {
+        id -> Integer,
+        client_key -> Text,
+        name -> Text,
+        status -> Text,
+        priority -> Text,
+        queued_at -> Text,
+        started_at -> Nullable<Text>,
+        finished_at -> Text,
+        duration_ms -> Nullable<BigInt>,
+        error -> Nullable<Text>,
+        bytes_downloaded -> BigInt,
+    }
+}
+
+diesel::table! {
+    download_stats (month, category) {
+        month -> Text,
+        category -> Text,
+        bytes -> BigInt,
+    }
+}
+
+diesel::table! {
+    event_log (id) {
+        id -> Integer,
+        kind -> Text,
+        instance_id -> Nullable<Integer>,
+        error_code -> Nullable<Text>,
+        created_at -> Text,
+    }
+}
+
 diesel::table! {
     user_version_tracking (id) {
         id -> Integer,
@@ -239,7 +309,9 @@ diesel::table! {
 }
 
 diesel::joinable!(installed_resource -> instance (instance_id));
+diesel::joinable!(instance_operation -> instance (instance_id));
 diesel::joinable!(instance_resource_update_check -> instance (instance_id));
+diesel::joinable!(play_session -> instance (instance_id));
 diesel::joinable!(notification_seen_items -> notification_subscriptions (subscription_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
@@ -247,14 +319,19 @@ diesel::allow_tables_to_appear_in_same_query!(
     account_skin_history,
     installed_resource,
     instance,
+    instance_operation,
     notification,
     notification_seen_items,
     notification_subscriptions,
     pinned_page,
+    play_session,
     instance_resource_update_check,
     resource_metadata_cache,
     resource_project,
     saved_themes,
+    task_history,
     task_state,
     user_version_tracking,
+    download_stats,
+    event_log,
 );
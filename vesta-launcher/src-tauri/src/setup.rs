@@ -1,4 +1,4 @@
-use crate::discord::DiscordManager;
+use crate::integrations::DiscordManager;
 use crate::notifications::manager::NotificationManager;
 use crate::notifications::subscriptions::manager::SubscriptionManager;
 use crate::tasks::manager::TaskManager;
@@ -19,6 +19,24 @@ pub fn init(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     app.asset_protocol_scope()
         .allow_directory(&app_data_dir, true)?;
 
+    // Keeps the headless CLI from writing while the GUI runs.
+    match crate::utils::launcher_lock::acquire(&app_data_dir) {
+        Ok(lock) => {
+            app.manage(lock);
+        }
+        Err(e) => log::warn!("Failed to take the launcher lock: {}", e),
+    }
+
+    let sync_provider = crate::startup::database_health::sync_folder_provider(&app_data_dir);
+    if let Some(provider) = sync_provider {
+        log::warn!(
+            "App data directory {} is inside a {} folder; sync clients can corrupt its databases",
+            app_data_dir.display(),
+            provider
+        );
+    }
+    let database_repairs = crate::startup::database_health::check_databases(&app_data_dir);
+
     // CRITICAL: Initialize Diesel connection pools FIRST before any other code runs
     // This ensures migrations are applied before any queries are executed
     log::info!("Initializing databases with Diesel and running migrations...");
@@ -62,11 +80,23 @@ pub fn init(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
 
     // Clean up old log files (>30 days)
     crate::logging::cleanup_old_logs();
+    crate::startup::maintenance::schedule_trash_purge();
+    crate::startup::maintenance::schedule_history_prune();
+    crate::startup::maintenance::schedule_natives_prune();
+    crate::startup::maintenance::schedule_event_log_trim();
+    crate::startup::maintenance::schedule_artifact_cache_gc(app.handle().clone());
+    crate::tasks::download_stats::start();
 
     // Initialize NotificationManager
     let notification_manager = NotificationManager::new(app.handle().clone());
     let _ = notification_manager.clear_task_notifications();
 
+    crate::startup::database_health::publish_notifications(
+        &notification_manager,
+        &database_repairs,
+        sync_provider,
+    );
+
     crate::startup::recovery::publish_interrupted_notifications(
         notification_manager.clone(),
         interrupted_instances,
@@ -74,10 +104,7 @@ pub fn init(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
 
     // Initialize DiscordManager
     let discord_manager = DiscordManager::new(app.handle().clone());
-    let dm = discord_manager.clone();
-    tauri::async_runtime::spawn(async move {
-        dm.init().await;
-    });
+    discord_manager.init();
     app.manage(discord_manager);
 
     // Initialize TaskManager
@@ -85,8 +112,11 @@ pub fn init(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     app.manage(task_manager);
 
     crate::tasks::notification_actions::register(&notification_manager);
+    crate::tasks::network_gate::register(&notification_manager);
     crate::instance::notification_actions::register(&notification_manager);
     crate::startup::update_actions::register(&notification_manager);
+    crate::startup::tray::register(&notification_manager);
+    crate::startup::self_update::register(&notification_manager);
     crate::auth::notification_actions::register(&notification_manager);
     app.manage(notification_manager);
 
@@ -121,6 +151,7 @@ pub fn init(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     crate::startup::shell::initialize(app)?;
 
     crate::startup::metadata::submit_manifest_generation(app.handle().clone());
+    crate::startup::metadata::preload_recent_version_manifests();
     crate::startup::accounts::validate_active_session(app.handle().clone());
 
     crate::startup::updates::schedule_update_check(app.handle().clone());
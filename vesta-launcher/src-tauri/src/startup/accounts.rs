@@ -2,7 +2,8 @@ use crate::utils::config::{get_app_config, update_app_config};
 use crate::utils::db::get_vesta_conn;
 use crate::utils::db_manager::get_app_config_dir;
 use diesel::prelude::*;
-use tauri::Manager;
+use futures::stream::{self, StreamExt};
+use tauri::{Emitter, Manager};
 
 pub fn cleanup_temporary_accounts() {
     cleanup_guest_session();
@@ -33,31 +34,91 @@ pub fn submit_profile_sync(app: &tauri::App) {
     });
 }
 
+/// Accounts refreshed at the same time during the startup pass.
+const STARTUP_REFRESH_CONCURRENCY: usize = 3;
+
+/// Payload of `vesta://accounts-refreshed`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AccountsRefreshed {
+    pub refreshed: Vec<String>,
+    pub failed: Vec<(String, String)>,
+}
+
 pub fn validate_active_session(app_handle: tauri::AppHandle) {
     tauri::async_runtime::spawn(async move {
         tokio::time::sleep(std::time::Duration::from_millis(600)).await;
         log::info!("[startup] Performing proactive session validation...");
 
-        let active_account = match crate::auth::get_active_account() {
-            Ok(Some(account)) => Some(account),
-            Ok(None) => repair_missing_active_account(app_handle.clone()),
-            Err(error) => {
-                log::error!("Failed to get active account: {}", error);
-                None
+        if let Ok(None) = crate::auth::get_active_account() {
+            repair_missing_active_account(app_handle.clone());
+        }
+
+        let result = refresh_all_account_tokens(&app_handle).await;
+        log::info!(
+            "[startup] Token refresh pass: {} ok, {} failed",
+            result.refreshed.len(),
+            result.failed.len()
+        );
+        let _ = app_handle.emit("vesta://accounts-refreshed", result);
+    });
+}
+
+/// Validate every signed-in account's tokens, a few at a time. Failures are
+/// collected (and flagged on the account for the UI) rather than aborting.
+async fn refresh_all_account_tokens(app_handle: &tauri::AppHandle) -> AccountsRefreshed {
+    let accounts = match load_refreshable_accounts() {
+        Ok(accounts) => accounts,
+        Err(error) => {
+            log::error!("Failed to load accounts for token refresh: {}", error);
+            Vec::new()
+        }
+    };
+
+    let outcomes: Vec<(String, Result<(), String>)> = stream::iter(accounts)
+        .map(|account_uuid| {
+            let app_handle = app_handle.clone();
+            async move {
+                let outcome =
+                    crate::auth::ensure_account_tokens_valid(app_handle, account_uuid.clone())
+                        .await;
+                (account_uuid, outcome)
             }
-        };
-
-        if let Some(account) = active_account {
-            if account.uuid != crate::auth::GUEST_UUID {
-                match crate::auth::ensure_account_tokens_valid(app_handle, account.uuid).await {
-                    Ok(()) => log::info!("[startup] Proactive session validation succeeded"),
-                    Err(error) => {
-                        log::warn!("[startup] Proactive session validation failed: {}", error)
-                    }
-                }
+        })
+        .buffer_unordered(STARTUP_REFRESH_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut result = AccountsRefreshed {
+        refreshed: Vec::new(),
+        failed: Vec::new(),
+    };
+    for (account_uuid, outcome) in outcomes {
+        match outcome {
+            Ok(()) => result.refreshed.push(account_uuid),
+            Err(error) => {
+                log::warn!(
+                    "[startup] Token refresh failed for {}: {}",
+                    account_uuid,
+                    error
+                );
+                result.failed.push((account_uuid, error));
             }
         }
-    });
+    }
+    result
+}
+
+/// Microsoft accounts that can refresh; Guest and Demo sessions have no tokens.
+fn load_refreshable_accounts() -> anyhow::Result<Vec<String>> {
+    use crate::schema::account::dsl::*;
+
+    let mut conn = get_vesta_conn()?;
+    Ok(account
+        .filter(account_type.ne(crate::auth::ACCOUNT_TYPE_GUEST))
+        .filter(account_type.ne(crate::auth::ACCOUNT_TYPE_DEMO))
+        .filter(refresh_token.is_not_null())
+        .select(uuid)
+        .load::<String>(&mut conn)?)
 }
 
 fn repair_missing_active_account(
@@ -0,0 +1,400 @@
+//! Integrity check and self-repair for the SQLite databases, run before the
+//! connection pools open them.
+//!
+//! A database that fails `PRAGMA integrity_check` (or cannot be read at all)
+//! is moved aside and replaced by a freshly migrated one. Rows are then copied
+//! back table by table from the damaged copy; tables that cannot be read are
+//! reported as lost. The damaged file is kept next to the new one.
+
+use crate::notifications::manager::NotificationManager;
+use crate::notifications::models::{CreateNotificationInput, NotificationType};
+use crate::utils::db::{CONFIG_DB_FILE, CONFIG_MIGRATIONS, VESTA_DB_FILE, VESTA_MIGRATIONS};
+use diesel::prelude::*;
+use diesel::sql_query;
+use diesel::sql_types::Text;
+use diesel_migrations::{EmbeddedMigrations, MigrationHarness};
+use std::path::{Path, PathBuf};
+
+/// Folders of sync clients known to lock or roll back SQLite files.
+const SYNC_FOLDER_MARKERS: &[(&str, &str)] = &[
+    ("onedrive", "OneDrive"),
+    ("dropbox", "Dropbox"),
+    ("google drive", "Google Drive"),
+    ("googledrive", "Google Drive"),
+    ("icloud drive", "iCloud Drive"),
+    ("mobile documents", "iCloud Drive"),
+];
+
+/// Outcome of rebuilding one damaged database.
+#[derive(Debug)]
+pub struct DatabaseRepair {
+    pub file_name: &'static str,
+    pub backup_path: PathBuf,
+    pub salvaged_tables: Vec<String>,
+    pub lost_tables: Vec<String>,
+}
+
+#[derive(QueryableByName)]
+struct IntegrityRow {
+    #[diesel(sql_type = Text)]
+    integrity_check: String,
+}
+
+#[derive(QueryableByName)]
+struct NameRow {
+    #[diesel(sql_type = Text)]
+    name: String,
+}
+
+/// Whether the database at `db_path` passes `PRAGMA integrity_check`. A
+/// missing file counts as healthy; it is created by the migrations.
+pub fn is_healthy(db_path: &Path) -> bool {
+    if !db_path.exists() {
+        return true;
+    }
+    let url = db_path.to_string_lossy().to_string();
+    let mut conn = match SqliteConnection::establish(&url) {
+        Ok(conn) => conn,
+        Err(e) => {
+            log::error!("Failed to open {}: {}", db_path.display(), e);
+            return false;
+        }
+    };
+    match sql_query("PRAGMA integrity_check").load::<IntegrityRow>(&mut conn) {
+        Ok(rows) => {
+            let problems: Vec<_> = rows
+                .into_iter()
+                .map(|row| row.integrity_check)
+                .filter(|line| line != "ok")
+                .collect();
+            if !problems.is_empty() {
+                log::error!(
+                    "Integrity check failed for {}: {}",
+                    db_path.display(),
+                    problems.join("; ")
+                );
+            }
+            problems.is_empty()
+        }
+        Err(e) => {
+            log::error!("Integrity check failed for {}: {}", db_path.display(), e);
+            false
+        }
+    }
+}
+
+/// Move the database and its WAL/SHM files aside, keeping them together so
+/// the backup stays readable.
+fn move_aside(db_path: &Path) -> std::io::Result<PathBuf> {
+    let stamp = chrono::Utc::now().format("%Y%m%d-%H%M%S");
+    let backup = db_path.with_file_name(format!(
+        "{}.corrupt-{}",
+        db_path.file_name().unwrap_or_default().to_string_lossy(),
+        stamp
+    ));
+    std::fs::rename(db_path, &backup)?;
+    for suffix in ["-wal", "-shm"] {
+        let sidecar = PathBuf::from(format!("{}{}", db_path.display(), suffix));
+        if sidecar.exists() {
+            let target = PathBuf::from(format!("{}{}", backup.display(), suffix));
+            if let Err(e) = std::fs::rename(&sidecar, &target) {
+                log::warn!("Failed to move {}: {}", sidecar.display(), e);
+            }
+        }
+    }
+    Ok(backup)
+}
+
+fn column_names(conn: &mut SqliteConnection, schema: &str, table: &str) -> Vec<String> {
+    sql_query(format!(
+        "SELECT name FROM pragma_table_info('{}', '{}')",
+        table.replace('\'', "''"),
+        schema
+    ))
+    .load::<NameRow>(conn)
+    .map(|rows| rows.into_iter().map(|row| row.name).collect())
+    .unwrap_or_default()
+}
+
+/// Tables of the fresh schema that hold rows of their own: virtual tables and
+/// their shadow tables are filled by triggers instead.
+fn copyable_tables(conn: &mut SqliteConnection) -> QueryResult<Vec<String>> {
+    let virtual_tables: Vec<String> = sql_query(
+        "SELECT name FROM main.sqlite_master WHERE type = 'table' AND sql LIKE 'CREATE VIRTUAL%'",
+    )
+    .load::<NameRow>(conn)?
+    .into_iter()
+    .map(|row| row.name)
+    .collect();
+    Ok(sql_query(
+        "SELECT name FROM main.sqlite_master WHERE type = 'table' \
+         AND name NOT LIKE 'sqlite_%' AND name != '__diesel_schema_migrations'",
+    )
+    .load::<NameRow>(conn)?
+    .into_iter()
+    .map(|row| row.name)
+    .filter(|name| {
+        !virtual_tables
+            .iter()
+            .any(|vt| name == vt || name.starts_with(&format!("{}_", vt)))
+    })
+    .collect())
+}
+
+/// Create a migrated database at `db_path` and copy every readable table over
+/// from `backup`.
+fn rebuild(
+    db_path: &Path,
+    backup: &Path,
+    file_name: &'static str,
+    migrations: EmbeddedMigrations,
+) -> anyhow::Result<DatabaseRepair> {
+    let mut conn = SqliteConnection::establish(&db_path.to_string_lossy())?;
+    conn.run_pending_migrations(migrations)
+        .map_err(|e| anyhow::anyhow!("Migration failed: {}", e))?;
+
+    let tables = copyable_tables(&mut conn)?;
+    let mut repair = DatabaseRepair {
+        file_name,
+        backup_path: backup.to_path_buf(),
+        salvaged_tables: Vec::new(),
+        lost_tables: Vec::new(),
+    };
+
+    let attached = sql_query("ATTACH DATABASE ? AS damaged")
+        .bind::<Text, _>(backup.to_string_lossy().to_string())
+        .execute(&mut conn);
+    if let Err(e) = attached {
+        log::error!("Failed to open damaged {}: {}", file_name, e);
+        repair.lost_tables = tables;
+        return Ok(repair);
+    }
+
+    for table in tables {
+        let fresh_columns = column_names(&mut conn, "main", &table);
+        let old_columns = column_names(&mut conn, "damaged", &table);
+        let shared: Vec<String> = fresh_columns
+            .into_iter()
+            .filter(|column| old_columns.contains(column))
+            .map(|column| format!("\"{}\"", column.replace('"', "\"\"")))
+            .collect();
+        if shared.is_empty() {
+            repair.lost_tables.push(table);
+            continue;
+        }
+        let columns = shared.join(", ");
+        let copy = format!(
+            "INSERT OR IGNORE INTO main.\"{table}\" ({columns}) SELECT {columns} FROM damaged.\"{table}\"",
+            table = table.replace('"', "\"\""),
+            columns = columns
+        );
+        match sql_query(copy).execute(&mut conn) {
+            Ok(rows) => {
+                log::info!("Recovered {} rows of {}.{}", rows, file_name, table);
+                repair.salvaged_tables.push(table);
+            }
+            Err(e) => {
+                log::error!("Could not recover {}.{}: {}", file_name, table, e);
+                repair.lost_tables.push(table);
+            }
+        }
+    }
+
+    let _ = sql_query("DETACH DATABASE damaged").execute(&mut conn);
+    Ok(repair)
+}
+
+/// Check one database and rebuild it if it is damaged.
+pub fn check_and_repair(
+    db_path: &Path,
+    file_name: &'static str,
+    migrations: EmbeddedMigrations,
+) -> anyhow::Result<Option<DatabaseRepair>> {
+    if is_healthy(db_path) {
+        return Ok(None);
+    }
+    let backup = move_aside(db_path)?;
+    log::warn!(
+        "Moved damaged {} to {}, rebuilding",
+        file_name,
+        backup.display()
+    );
+    rebuild(db_path, &backup, file_name, migrations).map(Some)
+}
+
+/// Check both databases in `app_data_dir`. Errors are logged; the pools
+/// report them again if the files are still unusable.
+pub fn check_databases(app_data_dir: &Path) -> Vec<DatabaseRepair> {
+    [
+        (CONFIG_DB_FILE, CONFIG_MIGRATIONS),
+        (VESTA_DB_FILE, VESTA_MIGRATIONS),
+    ]
+    .into_iter()
+    .filter_map(|(file_name, migrations)| {
+        match check_and_repair(&app_data_dir.join(file_name), file_name, migrations) {
+            Ok(repair) => repair,
+            Err(e) => {
+                log::error!("Failed to repair {}: {}", file_name, e);
+                None
+            }
+        }
+    })
+    .collect()
+}
+
+/// Sync client whose folder contains `path`, if any.
+pub fn sync_folder_provider(path: &Path) -> Option<&'static str> {
+    path.components().find_map(|component| {
+        let part = component.as_os_str().to_string_lossy().to_lowercase();
+        SYNC_FOLDER_MARKERS
+            .iter()
+            .find(|(marker, _)| part.starts_with(marker))
+            .map(|(_, provider)| *provider)
+    })
+}
+
+/// Tell the user about rebuilt databases and a synced data directory.
+pub fn publish_notifications(
+    manager: &NotificationManager,
+    repairs: &[DatabaseRepair],
+    sync_provider: Option<&str>,
+) {
+    for repair in repairs {
+        let lost = if repair.lost_tables.is_empty() {
+            "All readable data was recovered.".to_string()
+        } else {
+            format!(
+                "Data in {} could not be recovered.",
+                repair.lost_tables.join(", ")
+            )
+        };
+        if let Err(e) = manager.create(CreateNotificationInput {
+            client_key: Some(format!("database_repaired_{}", repair.file_name)),
+            title: Some("Database Repaired".to_string()),
+            description: Some(format!(
+                "{} was damaged and has been rebuilt. {} The damaged file was kept at {}.",
+                repair.file_name,
+                lost,
+                repair.backup_path.display()
+            )),
+            severity: Some("error".to_string()),
+            notification_type: Some(NotificationType::Patient),
+            dismissible: Some(true),
+            persist: Some(true),
+            ..Default::default()
+        }) {
+            log::error!("Failed to create database repair notification: {}", e);
+        }
+    }
+
+    if let Some(provider) = sync_provider {
+        if let Err(e) = manager.create(CreateNotificationInput {
+            client_key: Some("data_dir_in_sync_folder".to_string()),
+            title: Some("Launcher Data Is Being Synced".to_string()),
+            description: Some(format!(
+                "The launcher's data folder is inside {}. Sync clients can corrupt its databases; consider moving it out of the synced folder.",
+                provider
+            )),
+            severity: Some("warning".to_string()),
+            notification_type: Some(NotificationType::Patient),
+            dismissible: Some(true),
+            persist: Some(true),
+            ..Default::default()
+        }) {
+            log::error!("Failed to create sync folder notification: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::account::NewAccount;
+    use crate::schema::account::dsl as acct_dsl;
+
+    fn migrated_db(path: &Path) -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(&path.to_string_lossy()).unwrap();
+        conn.run_pending_migrations(VESTA_MIGRATIONS).unwrap();
+        conn
+    }
+
+    fn insert_account(conn: &mut SqliteConnection, account_uuid: &str) {
+        diesel::insert_into(acct_dsl::account)
+            .values(NewAccount {
+                uuid: account_uuid.to_string(),
+                username: "Player".to_string(),
+                ..Default::default()
+            })
+            .execute(conn)
+            .unwrap();
+    }
+
+    #[test]
+    fn corrupted_header_is_replaced_by_a_working_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join(VESTA_DB_FILE);
+        drop(migrated_db(&db_path));
+        let mut bytes = std::fs::read(&db_path).unwrap();
+        bytes[..16].copy_from_slice(b"not a database!!");
+        std::fs::write(&db_path, bytes).unwrap();
+        assert!(!is_healthy(&db_path));
+
+        let repair = check_and_repair(&db_path, VESTA_DB_FILE, VESTA_MIGRATIONS)
+            .unwrap()
+            .expect("damaged database should be rebuilt");
+
+        assert!(repair.backup_path.exists());
+        assert!(repair.salvaged_tables.is_empty());
+        assert!(repair.lost_tables.contains(&"account".to_string()));
+        let mut conn = SqliteConnection::establish(&db_path.to_string_lossy()).unwrap();
+        assert!(conn
+            .pending_migrations(VESTA_MIGRATIONS)
+            .unwrap()
+            .is_empty());
+        insert_account(&mut conn, "aaaa");
+        let count: i64 = acct_dsl::account.count().get_result(&mut conn).unwrap();
+        assert_eq!(count, 1);
+        assert!(is_healthy(&db_path));
+    }
+
+    #[test]
+    fn readable_tables_are_copied_into_the_rebuilt_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let backup = dir.path().join("vesta.db.corrupt-test");
+        insert_account(&mut migrated_db(&backup), "aaaa");
+
+        let db_path = dir.path().join(VESTA_DB_FILE);
+        let repair = rebuild(&db_path, &backup, VESTA_DB_FILE, VESTA_MIGRATIONS).unwrap();
+
+        assert!(repair.salvaged_tables.contains(&"account".to_string()));
+        let mut conn = SqliteConnection::establish(&db_path.to_string_lossy()).unwrap();
+        let uuids: Vec<String> = acct_dsl::account
+            .select(acct_dsl::uuid)
+            .load(&mut conn)
+            .unwrap();
+        assert_eq!(uuids, vec!["aaaa".to_string()]);
+    }
+
+    #[test]
+    fn missing_database_counts_as_healthy() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(is_healthy(&dir.path().join(VESTA_DB_FILE)));
+        assert!(check_databases(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn detects_sync_client_folders() {
+        assert_eq!(
+            sync_folder_provider(Path::new("C:/Users/me/OneDrive - Contoso/VestaLauncher")),
+            Some("OneDrive")
+        );
+        assert_eq!(
+            sync_folder_provider(Path::new("/home/me/Dropbox/.VestaLauncher")),
+            Some("Dropbox")
+        );
+        assert_eq!(
+            sync_folder_provider(Path::new("/home/me/.local/share/VestaLauncher")),
+            None
+        );
+    }
+}
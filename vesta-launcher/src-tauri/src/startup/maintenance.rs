@@ -0,0 +1,183 @@
+use crate::utils::config::get_app_config;
+use crate::utils::db::get_vesta_conn;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+const TRASH_PURGE_INITIAL_DELAY: Duration = Duration::from_secs(60);
+const TRASH_PURGE_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const HISTORY_PRUNE_INITIAL_DELAY: Duration = Duration::from_secs(90);
+const HISTORY_PRUNE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const NATIVES_PRUNE_INITIAL_DELAY: Duration = Duration::from_secs(120);
+const NATIVES_PRUNE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const EVENT_LOG_TRIM_INITIAL_DELAY: Duration = Duration::from_secs(150);
+const EVENT_LOG_TRIM_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const ARTIFACT_GC_INITIAL_DELAY: Duration = Duration::from_secs(180);
+const ARTIFACT_GC_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Periodically purge trashed instances older than `instance_trash_retention_days`.
+/// A retention of zero or less keeps trashed instances until they are purged manually.
+pub fn schedule_trash_purge() {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(TRASH_PURGE_INITIAL_DELAY).await;
+
+        loop {
+            match get_app_config() {
+                Ok(config) if config.instance_trash_retention_days > 0 => {
+                    let retention_days = config.instance_trash_retention_days as i64;
+                    let result = tokio::task::spawn_blocking(move || {
+                        let mut conn = get_vesta_conn().map_err(|error| error.to_string())?;
+                        crate::instance::trash::purge_expired(&mut conn, retention_days)
+                    })
+                    .await;
+
+                    match result {
+                        Ok(Ok(purged)) if !purged.is_empty() => log::info!(
+                            "Purged {} trashed instance(s) older than {} days",
+                            purged.len(),
+                            retention_days
+                        ),
+                        Ok(Ok(_)) => log::debug!("No trashed instances past retention"),
+                        Ok(Err(error)) => log::warn!("Trash purge failed: {}", error),
+                        Err(error) => log::warn!("Trash purge task panicked: {}", error),
+                    }
+                }
+                Ok(_) => log::debug!("Automatic trash purge disabled by config"),
+                Err(error) => log::error!("Failed to get app config for trash purge: {}", error),
+            }
+
+            tokio::time::sleep(TRASH_PURGE_INTERVAL).await;
+        }
+    });
+}
+
+/// Periodically cap each instance's operation history at
+/// `MAX_OPERATIONS_PER_INSTANCE` rows.
+pub fn schedule_history_prune() {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(HISTORY_PRUNE_INITIAL_DELAY).await;
+
+        loop {
+            let result = tokio::task::spawn_blocking(|| {
+                let mut conn = get_vesta_conn().map_err(|error| error.to_string())?;
+                crate::instance::history::prune_history(
+                    &mut conn,
+                    crate::instance::history::MAX_OPERATIONS_PER_INSTANCE,
+                )
+            })
+            .await;
+
+            match result {
+                Ok(Ok(removed)) if removed > 0 => {
+                    log::info!("Pruned {} old instance history entries", removed)
+                }
+                Ok(Ok(_)) => log::debug!("No instance history past retention"),
+                Ok(Err(error)) => log::warn!("Instance history prune failed: {}", error),
+                Err(error) => log::warn!("Instance history prune task panicked: {}", error),
+            }
+
+            tokio::time::sleep(HISTORY_PRUNE_INTERVAL).await;
+        }
+    });
+}
+
+/// Periodically remove extracted natives for versions that are no longer
+/// installed, so stale LWJGL binaries don't pile up under `data/natives`.
+pub fn schedule_natives_prune() {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(NATIVES_PRUNE_INITIAL_DELAY).await;
+
+        loop {
+            let result = tokio::task::spawn_blocking(|| {
+                let data_dir = crate::utils::db_manager::get_app_config_dir()
+                    .map_err(|error| error.to_string())?
+                    .join("data");
+                piston_lib::game::launcher::prune_stale_natives(&data_dir)
+                    .map_err(|error| error.to_string())
+            })
+            .await;
+
+            match result {
+                Ok(Ok(removed)) if !removed.is_empty() => {
+                    log::info!("Removed {} stale natives directories", removed.len())
+                }
+                Ok(Ok(_)) => log::debug!("No stale natives directories"),
+                Ok(Err(error)) => log::warn!("Natives prune failed: {}", error),
+                Err(error) => log::warn!("Natives prune task panicked: {}", error),
+            }
+
+            tokio::time::sleep(NATIVES_PRUNE_INTERVAL).await;
+        }
+    });
+}
+
+/// Periodically drop local event log entries past `EVENT_LOG_RETENTION_DAYS`
+/// or beyond `MAX_EVENT_LOG_ROWS`.
+pub fn schedule_event_log_trim() {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(EVENT_LOG_TRIM_INITIAL_DELAY).await;
+
+        loop {
+            let result = tokio::task::spawn_blocking(|| {
+                let mut conn = get_vesta_conn().map_err(|error| error.to_string())?;
+                crate::utils::event_log::trim_events(
+                    &mut conn,
+                    crate::utils::event_log::EVENT_LOG_RETENTION_DAYS,
+                    crate::utils::event_log::MAX_EVENT_LOG_ROWS,
+                )
+            })
+            .await;
+
+            match result {
+                Ok(Ok(removed)) if removed > 0 => {
+                    log::info!("Trimmed {} old event log entries", removed)
+                }
+                Ok(Ok(_)) => log::debug!("No event log entries past retention"),
+                Ok(Err(error)) => log::warn!("Event log trim failed: {}", error),
+                Err(error) => log::warn!("Event log trim task panicked: {}", error),
+            }
+
+            tokio::time::sleep(EVENT_LOG_TRIM_INTERVAL).await;
+        }
+    });
+}
+
+/// Periodically delete artifact blobs no install references anymore.
+/// Skipped while tasks are running, since installs ingest blobs before
+/// recording them.
+pub fn schedule_artifact_cache_gc(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(ARTIFACT_GC_INITIAL_DELAY).await;
+
+        loop {
+            let busy = app_handle
+                .try_state::<crate::tasks::manager::TaskManager>()
+                .is_some_and(|task_manager| !task_manager.get_active_tasks().is_empty());
+            if busy {
+                log::debug!("Skipping artifact cache GC while tasks are running");
+            } else {
+                let result = tokio::task::spawn_blocking(|| {
+                    let config_dir = crate::utils::db_manager::get_app_config_dir()
+                        .map_err(|error| error.to_string())?;
+                    crate::utils::storage::collect_artifact_cache_garbage(&config_dir)
+                })
+                .await;
+
+                match result {
+                    Ok(Ok(stats)) if stats.files_deleted > 0 => {
+                        log::info!(
+                            "Artifact cache GC removed {} file(s), {} bytes",
+                            stats.files_deleted,
+                            stats.bytes_reclaimed
+                        );
+                        let _ = app_handle.emit("storage-snapshot-invalidated", ());
+                    }
+                    Ok(Ok(_)) => log::debug!("No unreferenced artifact blobs"),
+                    Ok(Err(error)) => log::warn!("{}", error),
+                    Err(error) => log::warn!("Artifact cache GC task panicked: {}", error),
+                }
+            }
+
+            tokio::time::sleep(ARTIFACT_GC_INTERVAL).await;
+        }
+    });
+}
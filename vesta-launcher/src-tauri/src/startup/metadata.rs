@@ -1,9 +1,14 @@
 use crate::metadata_cache::MetadataCache;
 use crate::tasks::manager::TaskManager;
 use crate::tasks::manifest::GenerateManifestTask;
+use crate::utils::db::get_vesta_conn;
 use crate::utils::db_manager::get_app_config_dir;
+use diesel::prelude::*;
 use tauri::Manager;
 
+/// Versions whose JSON is fetched at startup so reinstalling them is instant.
+const PRELOADED_VERSION_COUNT: usize = 5;
+
 pub fn register_and_warm(app: &mut tauri::App) {
     app.manage(MetadataCache::new());
 
@@ -53,3 +58,92 @@ pub fn submit_manifest_generation(app_handle: tauri::AppHandle) {
         }
     });
 }
+
+/// Download the version JSONs of the most recently played Minecraft versions
+/// in the background.
+pub fn preload_recent_version_manifests() {
+    tauri::async_runtime::spawn(async move {
+        let Ok(config_dir) = get_app_config_dir() else {
+            return;
+        };
+        let versions = match get_vesta_conn()
+            .map_err(|e| e.to_string())
+            .and_then(|mut conn| {
+                recent_minecraft_versions(&mut conn, PRELOADED_VERSION_COUNT)
+                    .map_err(|e| e.to_string())
+            }) {
+            Ok(versions) => versions,
+            Err(error) => {
+                log::warn!("Failed to read recently played versions: {}", error);
+                return;
+            }
+        };
+
+        if let Err(error) =
+            piston_lib::game::installer::preload::PreloadManager::preload_version_manifests(
+                versions,
+                &config_dir.join("data"),
+            )
+            .await
+        {
+            log::warn!("[startup] {}", error);
+        }
+    });
+}
+
+/// Distinct Minecraft versions of played instances, most recent first.
+fn recent_minecraft_versions(
+    conn: &mut SqliteConnection,
+    limit: usize,
+) -> QueryResult<Vec<String>> {
+    use crate::schema::instance::dsl::*;
+
+    let played: Vec<String> = instance
+        .filter(last_played.is_not_null())
+        .order(last_played.desc())
+        .select(minecraft_version)
+        .load(conn)?;
+
+    let mut versions: Vec<String> = Vec::with_capacity(limit);
+    for version in played {
+        if versions.len() == limit {
+            break;
+        }
+        if !versions.contains(&version) {
+            versions.push(version);
+        }
+    }
+    Ok(versions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::connection::SimpleConnection;
+    use diesel_migrations::MigrationHarness;
+
+    #[test]
+    fn recent_versions_are_distinct_and_ordered_by_last_played() {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.run_pending_migrations(crate::utils::db::VESTA_MIGRATIONS)
+            .unwrap();
+        conn.batch_execute(
+            "INSERT INTO instance (id, name, minecraft_version, last_played) VALUES
+                (1, 'A', '1.20.1', '2026-10-01T10:00:00Z'),
+                (2, 'B', '1.21.1', '2026-10-03T10:00:00Z'),
+                (3, 'C', '1.20.1', '2026-10-02T10:00:00Z'),
+                (4, 'D', '1.8.9', NULL),
+                (5, 'E', '1.19.2', '2026-09-01T10:00:00Z');",
+        )
+        .unwrap();
+
+        assert_eq!(
+            recent_minecraft_versions(&mut conn, 5).unwrap(),
+            vec!["1.21.1", "1.20.1", "1.19.2"]
+        );
+        assert_eq!(
+            recent_minecraft_versions(&mut conn, 1).unwrap(),
+            vec!["1.21.1"]
+        );
+    }
+}
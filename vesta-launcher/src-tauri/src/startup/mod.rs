@@ -1,8 +1,12 @@
 pub mod accounts;
+pub mod database_health;
+pub mod maintenance;
 pub mod metadata;
 pub mod processes;
 pub mod recovery;
 pub mod resources;
+pub mod self_update;
 pub mod shell;
+pub mod tray;
 pub mod update_actions;
 pub mod updates;
@@ -88,6 +88,7 @@ pub fn start_resource_watchers(app: &mut tauri::App) {
 fn load_instances() -> Result<Vec<Instance>, String> {
     let mut conn = get_vesta_conn().map_err(|error| error.to_string())?;
     instance
+        .filter(deleted_at.is_null())
         .load::<Instance>(&mut conn)
         .map_err(|error| error.to_string())
 }
@@ -0,0 +1,1045 @@
+//! Launcher self-update on top of `tauri-plugin-updater`.
+//!
+//! The plugin finds releases: the endpoint follows the configured
+//! `update_channel`. The bundle is downloaded here rather than through
+//! `Update::download` so an interrupted download resumes from
+//! `<cache>/launcher-updates/<version>/`. Before it is staged, the bundle is
+//! checked against the feed's optional `sha256` and against the release's
+//! minisign signature with the `plugins.updater.pubkey` from
+//! `tauri.conf.json`, the same check the plugin performs. It is installed
+//! when the launcher exits. Nothing is applied while tasks are running or an
+//! instance is launched.
+
+use crate::notifications::manager::{ActionHandler, NotificationManager};
+use crate::notifications::models::{CreateNotificationInput, NotificationAction, NotificationType};
+use crate::tasks::manager::TaskManager;
+use anyhow::{Context, Result};
+use base64::Engine;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_updater::{Update, UpdaterExt};
+use tokio::io::AsyncWriteExt;
+
+const STABLE_ENDPOINT: &str = "https://r2.vestalauncher.com/launcher/releases/latest.json";
+const BETA_ENDPOINT: &str = "https://r2.vestalauncher.com/launcher/releases/beta/latest.json";
+const NOTIFICATION_CLIENT_KEY: &str = "app_update";
+const UPDATES_DIR_NAME: &str = "launcher-updates";
+const PARTIAL_SUFFIX: &str = ".part";
+/// Minimum bytes between `core://update-status` emissions while downloading.
+const PROGRESS_EMIT_INTERVAL: u64 = 512 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateChannel {
+    Stable,
+    Beta,
+}
+
+impl UpdateChannel {
+    /// Unknown values fall back to the stable channel.
+    pub fn from_config(value: &str) -> Self {
+        if value.trim().eq_ignore_ascii_case("beta") {
+            UpdateChannel::Beta
+        } else {
+            UpdateChannel::Stable
+        }
+    }
+
+    fn endpoint(self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => STABLE_ENDPOINT,
+            UpdateChannel::Beta => BETA_ENDPOINT,
+        }
+    }
+
+    /// Whether `remote` should replace `current`. The stable channel never
+    /// offers prereleases, even if the beta feed is misconfigured into it.
+    fn accepts(self, current: &semver::Version, remote: &semver::Version) -> bool {
+        remote > current && (self == UpdateChannel::Beta || remote.pre.is_empty())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum UpdateStatus {
+    Idle,
+    Checking,
+    UpToDate {
+        checked_at: String,
+    },
+    Available {
+        version: String,
+        notes: Option<String>,
+    },
+    Downloading {
+        version: String,
+        downloaded: u64,
+        total: Option<u64>,
+    },
+    Ready {
+        version: String,
+        notes: Option<String>,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+/// A platform entry of the release feed (`latest.json`). `sha256` is our
+/// addition to the updater's format and optional.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct FeedAsset {
+    pub url: String,
+    pub signature: String,
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReleaseFeed {
+    pub version: semver::Version,
+    pub notes: Option<String>,
+    pub asset: FeedAsset,
+}
+
+#[derive(Deserialize)]
+struct RawFeed {
+    version: String,
+    #[serde(default)]
+    notes: Option<String>,
+    #[serde(default)]
+    platforms: HashMap<String, FeedAsset>,
+}
+
+/// Parse a release feed and pick the asset the plugin chose, identified by
+/// its download URL. Accepts the static format (`platforms.<target>`) and
+/// the dynamic one (`url`/`signature` at the top level). Versions may carry
+/// a `v` prefix and prerelease tags.
+pub fn parse_feed(raw: &serde_json::Value, download_url: &str) -> Result<ReleaseFeed> {
+    let feed: RawFeed = serde_json::from_value(raw.clone()).context("Malformed release feed")?;
+    let version = semver::Version::parse(feed.version.trim().trim_start_matches('v'))
+        .with_context(|| format!("Invalid release version {:?}", feed.version))?;
+
+    let asset = if feed.platforms.is_empty() {
+        serde_json::from_value::<FeedAsset>(raw.clone())
+            .context("Release feed has no platforms and no top-level url")?
+    } else {
+        feed.platforms
+            .into_values()
+            .find(|asset| asset.url == download_url)
+            .with_context(|| format!("Release feed has no entry for {}", download_url))?
+    };
+    if asset.url != download_url {
+        anyhow::bail!("Release feed has no entry for {}", download_url);
+    }
+    if let Some(sha256) = &asset.sha256 {
+        if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+            anyhow::bail!("Malformed sha256 in release feed: {:?}", sha256);
+        }
+    }
+
+    Ok(ReleaseFeed {
+        version,
+        notes: feed.notes,
+        asset,
+    })
+}
+
+/// Check `bytes` against the feed's sha256, when published, and the
+/// release signature. `signature` and `pubkey` are base64-encoded minisign
+/// texts, as in the feed and `tauri.conf.json`.
+fn verify_bundle(bytes: &[u8], asset: &FeedAsset, pubkey: &str) -> Result<()> {
+    if let Some(expected) = &asset.sha256 {
+        let actual = crate::utils::hash::calculate_sha256_from_bytes(bytes);
+        if !actual.eq_ignore_ascii_case(expected) {
+            anyhow::bail!(
+                "Update checksum mismatch: expected {}, got {}",
+                expected,
+                actual
+            );
+        }
+    }
+
+    let decode = |value: &str| -> Result<String> {
+        let raw = base64::engine::general_purpose::STANDARD.decode(value.trim())?;
+        Ok(String::from_utf8(raw)?)
+    };
+    let public_key = minisign_verify::PublicKey::decode(
+        &decode(pubkey).context("Updater public key is not base64")?,
+    )
+    .map_err(|e| anyhow::anyhow!("Invalid updater public key: {}", e))?;
+    let signature = minisign_verify::Signature::decode(
+        &decode(&asset.signature).context("Update signature is not base64")?,
+    )
+    .map_err(|e| anyhow::anyhow!("Invalid update signature: {}", e))?;
+    public_key
+        .verify(bytes, &signature, true)
+        .map_err(|e| anyhow::anyhow!("Update signature verification failed: {}", e))
+}
+
+/// File name of the bundle in the staging directory.
+fn bundle_file_name(url: &str) -> String {
+    url.split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .filter(|name| !name.is_empty() && *name != "." && *name != ".." && !name.contains('\\'))
+        .unwrap_or("update.bundle")
+        .to_string()
+}
+
+/// Download `asset` into `dir` and verify it. A previous partial download is
+/// resumed when the server supports ranges; one that fails verification is
+/// deleted so the next attempt starts over. An already verified bundle is
+/// returned without a request.
+async fn download_verified(
+    client: &reqwest::Client,
+    asset: &FeedAsset,
+    dir: &Path,
+    pubkey: &str,
+    mut on_progress: impl FnMut(u64, Option<u64>),
+) -> Result<Vec<u8>> {
+    tokio::fs::create_dir_all(dir)
+        .await
+        .with_context(|| format!("Failed to create {:?}", dir))?;
+    let file_name = bundle_file_name(&asset.url);
+    let final_path = dir.join(&file_name);
+    if let Ok(bytes) = tokio::fs::read(&final_path).await {
+        if verify_bundle(&bytes, asset, pubkey).is_ok() {
+            return Ok(bytes);
+        }
+        let _ = tokio::fs::remove_file(&final_path).await;
+    }
+
+    let partial_path = dir.join(format!("{}{}", file_name, PARTIAL_SUFFIX));
+    let resume_from = tokio::fs::metadata(&partial_path)
+        .await
+        .map(|meta| meta.len())
+        .unwrap_or(0);
+    let mut request = client
+        .get(&asset.url)
+        .header("Accept", "application/octet-stream");
+    if resume_from > 0 {
+        request = request.header("Range", format!("bytes={}-", resume_from));
+    }
+    let response = request.send().await.context("Update request failed")?;
+
+    let status = response.status();
+    if status != reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        // Otherwise the partial file already holds every byte.
+        let response = response
+            .error_for_status()
+            .context("Update download returned an error")?;
+        let resuming = resume_from > 0 && status == reqwest::StatusCode::PARTIAL_CONTENT;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resuming)
+            .truncate(!resuming)
+            .open(&partial_path)
+            .await
+            .with_context(|| format!("Failed to open {:?}", partial_path))?;
+
+        let mut downloaded = if resuming { resume_from } else { 0 };
+        let total = response.content_length().map(|len| downloaded + len);
+        let mut stream = response.bytes_stream();
+        let streamed: Result<()> = async {
+            while let Some(chunk) = stream.next().await {
+                let chunk = chunk.context("Update download interrupted")?;
+                file.write_all(&chunk)
+                    .await
+                    .context("Failed to write update")?;
+                downloaded += chunk.len() as u64;
+                on_progress(downloaded, total);
+            }
+            Ok(())
+        }
+        .await;
+        // Flush even when interrupted so the next attempt resumes from here.
+        file.flush().await.context("Failed to flush update")?;
+        streamed?;
+    }
+
+    let bytes = tokio::fs::read(&partial_path)
+        .await
+        .with_context(|| format!("Failed to read {:?}", partial_path))?;
+    if let Err(e) = verify_bundle(&bytes, asset, pubkey) {
+        let _ = tokio::fs::remove_file(&partial_path).await;
+        return Err(e);
+    }
+    tokio::fs::rename(&partial_path, &final_path)
+        .await
+        .with_context(|| format!("Failed to move update into {:?}", final_path))?;
+    Ok(bytes)
+}
+
+/// Only the version being staged is kept; older downloads are dead weight.
+fn prune_other_versions(updates_dir: &Path, keep: &str) {
+    let Ok(entries) = std::fs::read_dir(updates_dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if entry.file_name().to_string_lossy() != keep {
+            let _ = std::fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+fn updates_dir(app: &AppHandle) -> Result<std::path::PathBuf> {
+    Ok(app
+        .path()
+        .app_cache_dir()
+        .context("App cache directory is unavailable")?
+        .join(UPDATES_DIR_NAME))
+}
+
+/// `plugins.updater.pubkey` from `tauri.conf.json`.
+fn updater_pubkey(app: &AppHandle) -> Option<String> {
+    app.config()
+        .plugins
+        .0
+        .get("updater")?
+        .get("pubkey")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Download and verify the bundle of `update`, reporting progress.
+async fn stage_update(app: &AppHandle, update: &Update) -> Result<Vec<u8>> {
+    let feed = parse_feed(&update.raw_json, update.download_url.as_str())?;
+    let pubkey = updater_pubkey(app).context("No updater public key is configured")?;
+    let version = feed.version.to_string();
+    let updates_dir = updates_dir(app)?;
+    prune_other_versions(&updates_dir, &version);
+
+    let mut last_emitted = 0u64;
+    download_verified(
+        piston_lib::client::shared_client(),
+        &feed.asset,
+        &updates_dir.join(&version),
+        &pubkey,
+        |downloaded, total| {
+            if downloaded.saturating_sub(last_emitted) >= PROGRESS_EMIT_INTERVAL {
+                last_emitted = downloaded;
+                set_status(
+                    app,
+                    UpdateStatus::Downloading {
+                        version: update.version.clone(),
+                        downloaded,
+                        total,
+                    },
+                );
+            }
+        },
+    )
+    .await
+}
+
+struct StagedUpdate {
+    update: Update,
+    bytes: Vec<u8>,
+}
+
+pub struct UpdaterState {
+    status: Mutex<UpdateStatus>,
+    available: Mutex<Option<Update>>,
+    staged: Mutex<Option<StagedUpdate>>,
+    in_flight: AtomicBool,
+    apply_on_exit: AtomicBool,
+}
+
+impl Default for UpdaterState {
+    fn default() -> Self {
+        Self {
+            status: Mutex::new(UpdateStatus::Idle),
+            available: Mutex::new(None),
+            staged: Mutex::new(None),
+            in_flight: AtomicBool::new(false),
+            apply_on_exit: AtomicBool::new(false),
+        }
+    }
+}
+
+impl UpdaterState {
+    pub fn status(&self) -> UpdateStatus {
+        self.status.lock().unwrap().clone()
+    }
+}
+
+fn set_status(app: &AppHandle, status: UpdateStatus) {
+    if let Some(state) = app.try_state::<UpdaterState>() {
+        *state.status.lock().unwrap() = status.clone();
+    }
+    let _ = app.emit("core://update-status", status);
+}
+
+fn fail(app: &AppHandle, error: String) -> String {
+    set_status(
+        app,
+        UpdateStatus::Failed {
+            error: error.clone(),
+        },
+    );
+    error
+}
+
+/// True while work is in progress that an installer must not interrupt.
+fn is_busy(app: &AppHandle) -> bool {
+    let tasks_running = app
+        .try_state::<TaskManager>()
+        .map(|tm| !tm.get_active_tasks().is_empty())
+        .unwrap_or(false);
+    let instances_running = crate::utils::process_state::load_running_processes()
+        .map(|processes| !processes.is_empty())
+        .unwrap_or(false);
+    tasks_running || instances_running
+}
+
+async fn find_update(app: &AppHandle) -> tauri_plugin_updater::Result<Option<Update>> {
+    let config = crate::utils::config::get_app_config().ok();
+    let channel = config
+        .as_ref()
+        .map(|config| UpdateChannel::from_config(&config.update_channel))
+        .unwrap_or(UpdateChannel::Stable);
+    let endpoint = channel
+        .endpoint()
+        .parse()
+        .expect("update endpoint is a valid URL");
+
+    let mut builder = app
+        .updater_builder()
+        .endpoints(vec![endpoint])?
+        .version_comparator(move |current, remote| channel.accepts(&current, &remote.version));
+    let proxy = config
+        .filter(|config| config.proxy_enabled)
+        .and_then(|config| config.proxy_url)
+        .and_then(|url| url.trim().parse().ok());
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(proxy);
+    }
+    builder.build()?.check().await
+}
+
+/// Check the configured channel. With auto-update on, a newer release is
+/// downloaded in the background; otherwise it is reported as available.
+pub async fn check_for_updates(app: &AppHandle) -> Result<UpdateStatus, String> {
+    let state = app.state::<UpdaterState>();
+    if state.in_flight.load(Ordering::SeqCst) {
+        return Ok(state.status());
+    }
+
+    set_status(app, UpdateStatus::Checking);
+    let update = match find_update(app).await {
+        Ok(update) => update,
+        Err(e) => return Err(fail(app, format!("Failed to check for updates: {}", e))),
+    };
+
+    let Some(update) = update else {
+        set_status(
+            app,
+            UpdateStatus::UpToDate {
+                checked_at: chrono::Utc::now().to_rfc3339(),
+            },
+        );
+        return Ok(state.status());
+    };
+
+    let already_staged = state
+        .staged
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|staged| staged.update.version == update.version);
+    if already_staged {
+        set_status(
+            app,
+            UpdateStatus::Ready {
+                version: update.version.clone(),
+                notes: update.body.clone(),
+            },
+        );
+        return Ok(state.status());
+    }
+
+    set_status(
+        app,
+        UpdateStatus::Available {
+            version: update.version.clone(),
+            notes: update.body.clone(),
+        },
+    );
+    *state.available.lock().unwrap() = Some(update);
+
+    let auto = crate::utils::config::get_app_config()
+        .map(|config| config.auto_update_enabled)
+        .unwrap_or(false);
+    if auto {
+        return download_update(app);
+    }
+    Ok(state.status())
+}
+
+/// Download the release found by the last check in the background.
+pub fn download_update(app: &AppHandle) -> Result<UpdateStatus, String> {
+    let state = app.state::<UpdaterState>();
+    let Some(update) = state.available.lock().unwrap().clone() else {
+        return Err("No launcher update is available to download".to_string());
+    };
+    if state.in_flight.swap(true, Ordering::SeqCst) {
+        return Ok(state.status());
+    }
+
+    set_status(
+        app,
+        UpdateStatus::Downloading {
+            version: update.version.clone(),
+            downloaded: 0,
+            total: None,
+        },
+    );
+    let status = state.status();
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = stage_update(&app, &update).await;
+
+        match result {
+            Ok(bytes) => {
+                log::info!("Downloaded launcher update {}", update.version);
+                set_status(
+                    &app,
+                    UpdateStatus::Ready {
+                        version: update.version.clone(),
+                        notes: update.body.clone(),
+                    },
+                );
+                let version = update.version.clone();
+                *app.state::<UpdaterState>().staged.lock().unwrap() =
+                    Some(StagedUpdate { update, bytes });
+                notify_update_ready(&app, &version);
+            }
+            Err(e) => {
+                log::error!(
+                    "Failed to download launcher update {}: {:#}",
+                    update.version,
+                    e
+                );
+                fail(&app, format!("Failed to download update: {:#}", e));
+            }
+        }
+        app.state::<UpdaterState>()
+            .in_flight
+            .store(false, Ordering::SeqCst);
+    });
+
+    Ok(status)
+}
+
+fn notify_update_ready(app: &AppHandle, version: &str) {
+    let actions = vec![NotificationAction {
+        action_id: "apply_launcher_update".to_string(),
+        label: "Restart to update".to_string(),
+        action_type: "primary".to_string(),
+        payload: None,
+    }];
+    let manager = app.state::<NotificationManager>();
+    if let Err(e) = manager.create(CreateNotificationInput {
+        client_key: Some(NOTIFICATION_CLIENT_KEY.to_string()),
+        title: Some("Update ready".to_string()),
+        description: Some(format!(
+            "Vesta {} has been downloaded and will be installed when you restart.",
+            version
+        )),
+        severity: Some("info".to_string()),
+        notification_type: Some(NotificationType::Patient),
+        dismissible: Some(true),
+        persist: Some(true),
+        silent: Some(false),
+        actions: Some(serde_json::to_string(&actions).unwrap_or_default()),
+        progress: None,
+        current_step: None,
+        total_steps: None,
+        metadata: None,
+        show_on_completion: None,
+    }) {
+        log::error!("Failed to create update-ready notification: {}", e);
+    }
+}
+
+/// Called from `RunEvent::Exit`. Installs a downloaded update when the user
+/// asked for it or auto-update is on, unless work is still in progress.
+pub fn on_exit(app: &AppHandle) {
+    let Some(state) = app.try_state::<UpdaterState>() else {
+        return;
+    };
+    let Some(staged) = state.staged.lock().unwrap().take() else {
+        return;
+    };
+    let requested = state.apply_on_exit.load(Ordering::SeqCst);
+    let auto = crate::utils::config::get_app_config()
+        .map(|config| config.auto_update_enabled)
+        .unwrap_or(false);
+    if !requested && !auto {
+        return;
+    }
+    if is_busy(app) {
+        log::info!(
+            "Not installing launcher update {} on exit: tasks or instances are still running",
+            staged.update.version
+        );
+        return;
+    }
+
+    log::info!("Installing launcher update {}", staged.update.version);
+    match staged.update.install(&staged.bytes) {
+        Ok(()) => {
+            if let Ok(dir) = updates_dir(app) {
+                let _ = std::fs::remove_dir_all(dir);
+            }
+        }
+        Err(e) => log::error!(
+            "Failed to install launcher update {}: {}",
+            staged.update.version,
+            e
+        ),
+    }
+}
+
+struct DownloadLauncherUpdate;
+
+impl ActionHandler for DownloadLauncherUpdate {
+    fn handle(
+        &self,
+        app_handle: &AppHandle,
+        _client_key: Option<String>,
+        _payload: Option<serde_json::Value>,
+    ) -> Result<()> {
+        download_update(app_handle).map_err(|e| anyhow::anyhow!(e))?;
+        Ok(())
+    }
+}
+
+struct ApplyLauncherUpdate;
+
+impl ActionHandler for ApplyLauncherUpdate {
+    fn handle(
+        &self,
+        app_handle: &AppHandle,
+        _client_key: Option<String>,
+        _payload: Option<serde_json::Value>,
+    ) -> Result<()> {
+        if is_busy(app_handle) {
+            anyhow::bail!("Finish running tasks and close running instances before updating");
+        }
+        app_handle
+            .state::<UpdaterState>()
+            .apply_on_exit
+            .store(true, Ordering::SeqCst);
+        app_handle.exit(0);
+        Ok(())
+    }
+}
+
+pub fn register(manager: &NotificationManager) {
+    manager.register_action("download_launcher_update", Arc::new(DownloadLauncherUpdate));
+    manager.register_action("apply_launcher_update", Arc::new(ApplyLauncherUpdate));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(version: &str) -> semver::Version {
+        semver::Version::parse(version).unwrap()
+    }
+
+    #[test]
+    fn channel_from_config_defaults_to_stable() {
+        assert_eq!(UpdateChannel::from_config("beta"), UpdateChannel::Beta);
+        assert_eq!(UpdateChannel::from_config(" BETA "), UpdateChannel::Beta);
+        assert_eq!(UpdateChannel::from_config("stable"), UpdateChannel::Stable);
+        assert_eq!(UpdateChannel::from_config("nightly"), UpdateChannel::Stable);
+    }
+
+    #[test]
+    fn stable_channel_skips_prereleases() {
+        let stable = UpdateChannel::Stable;
+        assert!(stable.accepts(&v("0.2.0"), &v("0.3.0")));
+        assert!(!stable.accepts(&v("0.2.0"), &v("0.3.0-beta.1")));
+        assert!(!stable.accepts(&v("0.3.0"), &v("0.3.0")));
+        assert!(!stable.accepts(&v("0.3.0"), &v("0.2.9")));
+        // Leaving the beta channel still picks up the final release.
+        assert!(stable.accepts(&v("0.3.0-beta.2"), &v("0.3.0")));
+    }
+
+    #[test]
+    fn beta_channel_orders_prereleases() {
+        let beta = UpdateChannel::Beta;
+        assert!(beta.accepts(&v("0.2.0"), &v("0.3.0-beta.1")));
+        assert!(beta.accepts(&v("0.3.0-beta.1"), &v("0.3.0-beta.2")));
+        assert!(beta.accepts(&v("0.3.0-beta.10"), &v("0.3.0")));
+        assert!(!beta.accepts(&v("0.3.0-beta.2"), &v("0.3.0-beta.1")));
+        assert!(!beta.accepts(&v("0.3.0"), &v("0.3.0-beta.9")));
+    }
+
+    fn b64(bytes: &[u8]) -> String {
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    }
+
+    /// Minisign key as the updater config stores it, with a fixed key id.
+    struct TestKey(ring::signature::Ed25519KeyPair);
+
+    impl TestKey {
+        const KEY_ID: [u8; 8] = *b"vestakey";
+
+        fn new(seed: u8) -> Self {
+            Self(ring::signature::Ed25519KeyPair::from_seed_unchecked(&[seed; 32]).unwrap())
+        }
+
+        fn pubkey(&self) -> String {
+            use ring::signature::KeyPair;
+            let bin = [
+                b"Ed".as_slice(),
+                &Self::KEY_ID,
+                self.0.public_key().as_ref(),
+            ]
+            .concat();
+            b64(format!("untrusted comment: minisign public key\n{}\n", b64(&bin)).as_bytes())
+        }
+
+        fn sign(&self, data: &[u8]) -> String {
+            let trusted = "timestamp:0\tfile:bundle";
+            let signature = self.0.sign(data);
+            let bin = [b"Ed".as_slice(), &Self::KEY_ID, signature.as_ref()].concat();
+            let global = self
+                .0
+                .sign(&[signature.as_ref(), trusted.as_bytes()].concat());
+            b64(format!(
+                "untrusted comment: signature\n{}\ntrusted comment: {}\n{}\n",
+                b64(&bin),
+                trusted,
+                b64(global.as_ref())
+            )
+            .as_bytes())
+        }
+    }
+
+    struct Served {
+        url: String,
+        ranges: Arc<Mutex<Vec<Option<String>>>>,
+    }
+
+    /// Serve `body` once per connection. With `honor_range` a `Range` request
+    /// gets a 206 for the rest of the body; `cut_after` closes the connection
+    /// after that many body bytes.
+    async fn serve(body: Vec<u8>, honor_range: bool, cut_after: Option<usize>) -> Served {
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!(
+            "http://{}/Vesta_0.4.0_amd64.AppImage",
+            listener.local_addr().unwrap()
+        );
+        let ranges = Arc::new(Mutex::new(Vec::new()));
+        let seen = ranges.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                }
+                let range = String::from_utf8_lossy(&request).lines().find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("range")
+                        .then(|| value.trim().to_string())
+                });
+                seen.lock().unwrap().push(range.clone());
+
+                let start = range
+                    .filter(|_| honor_range)
+                    .and_then(|value| {
+                        value
+                            .strip_prefix("bytes=")?
+                            .trim_end_matches('-')
+                            .parse()
+                            .ok()
+                    })
+                    .unwrap_or(0usize);
+                let rest = &body[start.min(body.len())..];
+                let head = if start > 0 {
+                    format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {}-{}/{}\r\nConnection: close\r\n\r\n",
+                        rest.len(),
+                        start,
+                        body.len() - 1,
+                        body.len()
+                    )
+                } else {
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        rest.len()
+                    )
+                };
+                let sent = cut_after.map_or(rest.len(), |cut| cut.min(rest.len()));
+                socket.write_all(head.as_bytes()).await.unwrap();
+                socket.write_all(&rest[..sent]).await.unwrap();
+                let _ = socket.shutdown().await;
+            }
+        });
+        Served { url, ranges }
+    }
+
+    fn bundle() -> Vec<u8> {
+        (0..4096u32).map(|i| (i % 251) as u8).collect()
+    }
+
+    fn asset(url: &str, key: &TestKey, body: &[u8]) -> FeedAsset {
+        FeedAsset {
+            url: url.to_string(),
+            signature: key.sign(body),
+            sha256: Some(crate::utils::hash::calculate_sha256_from_bytes(body)),
+        }
+    }
+
+    #[test]
+    fn parse_feed_picks_the_chosen_platform_and_keeps_prerelease_tags() {
+        let raw = serde_json::json!({
+            "version": "v0.4.0-beta.2",
+            "notes": "Beta notes",
+            "pub_date": "2026-10-01T00:00:00Z",
+            "platforms": {
+                "linux-x86_64": {
+                    "url": "https://example.com/Vesta_0.4.0-beta.2_amd64.AppImage",
+                    "signature": "c2ln",
+                    "sha256": "AB".repeat(32)
+                },
+                "windows-x86_64": {
+                    "url": "https://example.com/Vesta_0.4.0-beta.2_x64-setup.exe",
+                    "signature": "c2ln"
+                }
+            }
+        });
+
+        let feed =
+            parse_feed(&raw, "https://example.com/Vesta_0.4.0-beta.2_x64-setup.exe").unwrap();
+        assert_eq!(feed.version, v("0.4.0-beta.2"));
+        assert_eq!(feed.notes.as_deref(), Some("Beta notes"));
+        assert_eq!(feed.asset.sha256, None);
+        assert!(UpdateChannel::Beta.accepts(&v("0.4.0-beta.1"), &feed.version));
+        assert!(!UpdateChannel::Stable.accepts(&v("0.3.0"), &feed.version));
+
+        let linux = parse_feed(
+            &raw,
+            "https://example.com/Vesta_0.4.0-beta.2_amd64.AppImage",
+        )
+        .unwrap();
+        assert_eq!(linux.asset.sha256, Some("AB".repeat(32)));
+    }
+
+    #[test]
+    fn parse_feed_accepts_the_dynamic_format() {
+        let raw = serde_json::json!({
+            "version": "0.4.0",
+            "url": "https://example.com/Vesta.AppImage",
+            "signature": "c2ln"
+        });
+        let feed = parse_feed(&raw, "https://example.com/Vesta.AppImage").unwrap();
+        assert_eq!(feed.version, v("0.4.0"));
+        assert_eq!(feed.asset.signature, "c2ln");
+    }
+
+    #[test]
+    fn parse_feed_rejects_malformed_feeds() {
+        let url = "https://example.com/Vesta.AppImage";
+        let with = |version: &str, sha256: &str| {
+            serde_json::json!({
+                "version": version,
+                "platforms": {
+                    "linux-x86_64": { "url": url, "signature": "c2ln", "sha256": sha256 }
+                }
+            })
+        };
+
+        assert!(parse_feed(&with("0.4", &"a".repeat(64)), url).is_err());
+        assert!(parse_feed(&with("0.4.0", "not-a-hash"), url).is_err());
+        assert!(parse_feed(&with("0.4.0", &"a".repeat(64)), "https://example.com/other").is_err());
+        assert!(parse_feed(&serde_json::json!({ "version": "0.4.0" }), url).is_err());
+        assert!(parse_feed(&with("0.4.0", &"a".repeat(64)), url).is_ok());
+    }
+
+    #[test]
+    fn bundle_file_name_stays_inside_the_staging_directory() {
+        assert_eq!(
+            bundle_file_name("https://example.com/a/Vesta_0.4.0_x64-setup.exe?sig=1"),
+            "Vesta_0.4.0_x64-setup.exe"
+        );
+        assert_eq!(bundle_file_name("https://example.com/a/"), "update.bundle");
+        assert_eq!(bundle_file_name("https://example.com/.."), "update.bundle");
+    }
+
+    #[tokio::test]
+    async fn interrupted_download_resumes_from_the_partial_file() {
+        let body = bundle();
+        let key = TestKey::new(7);
+        let dir = tempfile::tempdir().unwrap();
+        let client = reqwest::Client::new();
+
+        let cut = serve(body.clone(), true, Some(1000)).await;
+        let asset = asset(&cut.url, &key, &body);
+        let err = download_verified(&client, &asset, dir.path(), &key.pubkey(), |_, _| {})
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", err).contains("interrupted"), "{:#}", err);
+        let partial = dir.path().join("Vesta_0.4.0_amd64.AppImage.part");
+        assert_eq!(std::fs::metadata(&partial).unwrap().len(), 1000);
+
+        let resumed = serve(body.clone(), true, None).await;
+        let asset = FeedAsset {
+            url: resumed.url.clone(),
+            ..asset
+        };
+        let mut progress = Vec::new();
+        let bytes = download_verified(&client, &asset, dir.path(), &key.pubkey(), |done, total| {
+            progress.push((done, total))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(bytes, body);
+        assert_eq!(
+            resumed.ranges.lock().unwrap().as_slice(),
+            [Some("bytes=1000-".to_string())]
+        );
+        assert_eq!(progress.last(), Some(&(4096, Some(4096))));
+        assert!(!partial.exists());
+        assert!(dir.path().join("Vesta_0.4.0_amd64.AppImage").exists());
+    }
+
+    #[tokio::test]
+    async fn server_without_range_support_restarts_the_download() {
+        let body = bundle();
+        let key = TestKey::new(7);
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Vesta_0.4.0_amd64.AppImage.part"), b"stale").unwrap();
+
+        let served = serve(body.clone(), false, None).await;
+        let asset = asset(&served.url, &key, &body);
+        let bytes = download_verified(
+            &reqwest::Client::new(),
+            &asset,
+            dir.path(),
+            &key.pubkey(),
+            |_, _| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(bytes, body);
+    }
+
+    #[tokio::test]
+    async fn checksum_mismatch_discards_the_download() {
+        let body = bundle();
+        let key = TestKey::new(7);
+        let dir = tempfile::tempdir().unwrap();
+        let served = serve(body.clone(), true, None).await;
+        let asset = FeedAsset {
+            sha256: Some("0".repeat(64)),
+            ..asset(&served.url, &key, &body)
+        };
+
+        let err = download_verified(
+            &reqwest::Client::new(),
+            &asset,
+            dir.path(),
+            &key.pubkey(),
+            |_, _| {},
+        )
+        .await
+        .unwrap_err();
+
+        assert!(err.to_string().contains("checksum mismatch"), "{:#}", err);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn bad_signature_discards_the_download() {
+        let body = bundle();
+        let trusted = TestKey::new(7);
+        let forger = TestKey::new(8);
+        let dir = tempfile::tempdir().unwrap();
+        let served = serve(body.clone(), true, None).await;
+        // Right checksum, wrong signer.
+        let asset = asset(&served.url, &forger, &body);
+
+        let err = download_verified(
+            &reqwest::Client::new(),
+            &asset,
+            dir.path(),
+            &trusted.pubkey(),
+            |_, _| {},
+        )
+        .await
+        .unwrap_err();
+
+        assert!(
+            err.to_string().contains("signature verification failed"),
+            "{:#}",
+            err
+        );
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn verified_bundle_is_reused_without_a_request() {
+        let body = bundle();
+        let key = TestKey::new(7);
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Vesta_0.4.0_amd64.AppImage"), &body).unwrap();
+        // Nothing listens here; a request would fail.
+        let asset = asset("http://127.0.0.1:9/Vesta_0.4.0_amd64.AppImage", &key, &body);
+
+        let bytes = download_verified(
+            &reqwest::Client::new(),
+            &asset,
+            dir.path(),
+            &key.pubkey(),
+            |_, _| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(bytes, body);
+    }
+
+    #[test]
+    fn prune_keeps_only_the_staged_version() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("0.3.0")).unwrap();
+        std::fs::create_dir_all(dir.path().join("0.4.0")).unwrap();
+
+        prune_other_versions(dir.path(), "0.4.0");
+
+        assert!(!dir.path().join("0.3.0").exists());
+        assert!(dir.path().join("0.4.0").exists());
+    }
+
+    #[test]
+    fn status_serializes_with_state_tag() {
+        let status = UpdateStatus::Downloading {
+            version: "0.3.0".to_string(),
+            downloaded: 5,
+            total: Some(10),
+        };
+        assert_eq!(
+            serde_json::to_value(&status).unwrap(),
+            serde_json::json!({ "state": "downloading", "version": "0.3.0", "downloaded": 5, "total": 10 })
+        );
+    }
+}
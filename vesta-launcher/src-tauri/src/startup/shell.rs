@@ -1,8 +1,5 @@
 use crate::utils::config::get_app_config;
-use tauri::menu::{Menu, MenuItem};
-use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder};
 use tauri::webview::Color;
-use tauri::Manager;
 
 pub fn initialize(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     let os = if cfg!(target_os = "macos") {
@@ -18,7 +15,7 @@ pub fn initialize(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>
     if let Err(error) = sync_autostart(app.handle(), config.autostart_enabled) {
         log::warn!("Failed to sync autostart state with config: {}", error);
     }
-    if let Err(error) = create_tray(app.handle(), config.show_tray_icon) {
+    if let Err(error) = crate::startup::tray::create_tray(app.handle(), config.show_tray_icon) {
         log::warn!("Failed to initialize tray: {}", error);
     }
     if let Err(error) = crate::commands::app::sync_tray_visibility_with_config(app.handle()) {
@@ -28,7 +25,8 @@ pub fn initialize(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>
         );
     }
 
-    let window = build_main_window(app, os, &config)?;
+    let window = build_launcher_window(app.handle(), "main", os, &config)?;
+    crate::utils::window_state::restore_window_state(&window);
     crate::commands::app::set_window_effect(window, "none".to_string()).unwrap_or(());
 
     let args: Vec<String> = std::env::args().collect();
@@ -39,8 +37,10 @@ pub fn initialize(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>
     Ok(())
 }
 
-fn build_main_window(
-    app: &mut tauri::App,
+/// Build a hidden full launcher window; the frontend presents it once painted.
+pub(crate) fn build_launcher_window(
+    app: &tauri::AppHandle,
+    label: &str,
     os: &str,
     config: &crate::utils::config::AppConfig,
 ) -> Result<tauri::WebviewWindow, tauri::Error> {
@@ -54,7 +54,7 @@ fn build_main_window(
         bootstrap = serde_json::to_string(&bootstrap).expect("serialize startup snapshot"),
     );
     let builder =
-        tauri::WebviewWindowBuilder::new(app, "main", tauri::WebviewUrl::App("index.html".into()))
+        tauri::WebviewWindowBuilder::new(app, label, tauri::WebviewUrl::App("index.html".into()))
             .initialization_script(&initialization_script)
             .title("Vesta Launcher")
             .inner_size(
@@ -77,58 +77,6 @@ fn build_main_window(
     builder.build()
 }
 
-fn create_tray(
-    app: &tauri::AppHandle,
-    show_tray_icon: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let show = MenuItem::with_id(app, "tray_show", "Show", true, None::<&str>)?;
-    let hide = MenuItem::with_id(app, "tray_hide", "Hide", true, None::<&str>)?;
-    let quit = MenuItem::with_id(app, "tray_quit", "Quit", true, None::<&str>)?;
-    let menu = Menu::with_items(app, &[&show, &hide, &quit])?;
-    let icon = app.default_window_icon().ok_or_else(|| {
-        std::io::Error::new(
-            std::io::ErrorKind::NotFound,
-            "default window icon is missing",
-        )
-    })?;
-
-    TrayIconBuilder::with_id("main-tray")
-        .icon(icon.clone())
-        .menu(&menu)
-        .show_menu_on_left_click(cfg!(target_os = "linux"))
-        .on_menu_event(|app, event| match event.id.as_ref() {
-            "tray_show" => {
-                let _ = crate::utils::windows::ensure_main_window_visible(app);
-            }
-            "tray_hide" => {
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.hide();
-                }
-            }
-            "tray_quit" => {
-                let _ = crate::commands::app::request_guarded_exit(app, "tray-menu");
-            }
-            _ => {}
-        })
-        .on_tray_icon_event(|tray, event| {
-            #[cfg(any(target_os = "windows", target_os = "macos"))]
-            if let tauri::tray::TrayIconEvent::Click {
-                button: MouseButton::Left,
-                button_state: MouseButtonState::Up,
-                ..
-            } = event
-            {
-                let _ = crate::utils::windows::ensure_main_window_visible(tray.app_handle());
-            }
-        })
-        .build(app)?;
-
-    if let Some(tray) = app.tray_by_id("main-tray") {
-        let _ = tray.set_visible(show_tray_icon);
-    }
-    Ok(())
-}
-
 #[cfg(desktop)]
 fn sync_autostart(app: &tauri::AppHandle, should_enable: bool) -> Result<(), String> {
     use tauri_plugin_autostart::ManagerExt;
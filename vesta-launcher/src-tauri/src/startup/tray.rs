@@ -0,0 +1,399 @@
+//! System tray: running-instance controls, task tooltip and guarded quit.
+//!
+//! The menu is rebuilt whenever an Instance launches or exits so each running
+//! game gets its own Kill entry. The tooltip is refreshed on a short interval
+//! from the TaskManager's active tasks and their notification progress.
+
+use crate::notifications::manager::{ActionHandler, NotificationManager};
+use crate::notifications::models::{CreateNotificationInput, NotificationAction, NotificationType};
+use crate::tasks::manager::TaskManager;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder};
+use tauri::{AppHandle, Listener, Manager};
+
+pub const TRAY_ID: &str = "main-tray";
+
+const KILL_ITEM_PREFIX: &str = "tray_kill:";
+const TOOLTIP_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+const BASE_TOOLTIP: &str = "Vesta Launcher";
+const QUIT_CONFIRM_CLIENT_KEY: &str = "tray_quit_confirm";
+
+/// Instances currently shown in the tray menu, keyed by slug.
+#[derive(Default)]
+struct TrayInstances(Mutex<BTreeMap<String, String>>);
+
+pub fn create_tray(
+    app: &AppHandle,
+    show_tray_icon: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    app.manage(TrayInstances::default());
+
+    let menu = build_menu(app, &BTreeMap::new())?;
+    let icon = app.default_window_icon().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "default window icon is missing",
+        )
+    })?;
+
+    TrayIconBuilder::with_id(TRAY_ID)
+        .icon(icon.clone())
+        .menu(&menu)
+        .tooltip(BASE_TOOLTIP)
+        .show_menu_on_left_click(cfg!(target_os = "linux"))
+        .on_menu_event(|app, event| handle_menu_event(app, event.id.as_ref()))
+        .on_tray_icon_event(|tray, event| {
+            #[cfg(any(target_os = "windows", target_os = "macos"))]
+            if let tauri::tray::TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                let _ = crate::utils::windows::ensure_main_window_visible(tray.app_handle());
+            }
+        })
+        .build(app)?;
+
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let _ = tray.set_visible(show_tray_icon);
+    }
+
+    listen_for_instance_changes(app);
+    listen_for_config_changes(app);
+    spawn_tooltip_refresh(app.clone());
+    Ok(())
+}
+
+fn build_menu(
+    app: &AppHandle,
+    running: &BTreeMap<String, String>,
+) -> tauri::Result<Menu<tauri::Wry>> {
+    let menu = Menu::new(app)?;
+    menu.append(&MenuItem::with_id(
+        app,
+        "tray_show",
+        "Show launcher",
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&MenuItem::with_id(
+        app,
+        "tray_hide",
+        "Hide",
+        true,
+        None::<&str>,
+    )?)?;
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+
+    if running.is_empty() {
+        menu.append(&MenuItem::with_id(
+            app,
+            "tray_no_instances",
+            "No running instances",
+            false,
+            None::<&str>,
+        )?)?;
+    } else {
+        for (slug, name) in running {
+            let kill = MenuItem::with_id(
+                app,
+                format!("{}{}", KILL_ITEM_PREFIX, slug),
+                "Kill",
+                true,
+                None::<&str>,
+            )?;
+            menu.append(&Submenu::with_items(app, name, true, &[&kill])?)?;
+        }
+    }
+
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&MenuItem::with_id(
+        app,
+        "tray_quit",
+        "Quit",
+        true,
+        None::<&str>,
+    )?)?;
+    Ok(menu)
+}
+
+fn handle_menu_event(app: &AppHandle, id: &str) {
+    match id {
+        "tray_show" => {
+            let _ = crate::utils::windows::ensure_main_window_visible(app);
+        }
+        "tray_hide" => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.hide();
+            }
+        }
+        "tray_quit" => quit_from_tray(app),
+        _ => {
+            if let Some(slug) = id.strip_prefix(KILL_ITEM_PREFIX) {
+                kill_from_tray(app.clone(), slug.to_string());
+            }
+        }
+    }
+}
+
+fn kill_from_tray(app: AppHandle, slug: String) {
+    tauri::async_runtime::spawn(async move {
+        let Some(inst) = crate::instance::lifecycle::find_instance_by_slug(&slug) else {
+            log::warn!("[tray] Kill requested for unknown instance {}", slug);
+            return;
+        };
+        if let Err(e) = crate::instance::lifecycle::kill_instance(app, inst).await {
+            log::error!("[tray] Failed to kill instance {}: {}", slug, e);
+        }
+    });
+}
+
+/// Quit straight through the guarded-exit flow when idle; while tasks are
+/// running, ask for confirmation through a notification first.
+fn quit_from_tray(app: &AppHandle) {
+    let active = app
+        .try_state::<TaskManager>()
+        .map(|tm| tm.get_active_tasks())
+        .unwrap_or_default();
+    if active.is_empty() {
+        let _ = crate::commands::app::request_guarded_exit(app, "tray-menu");
+        return;
+    }
+
+    let _ = crate::utils::windows::ensure_main_window_visible(app);
+    let actions = vec![NotificationAction {
+        action_id: "confirm_quit".to_string(),
+        label: "Quit anyway".to_string(),
+        action_type: "destructive".to_string(),
+        payload: None,
+    }];
+    let manager = app.state::<NotificationManager>();
+    if let Err(e) = manager.create(CreateNotificationInput {
+        client_key: Some(QUIT_CONFIRM_CLIENT_KEY.to_string()),
+        title: Some("Tasks are still running".to_string()),
+        description: Some(format!(
+            "{} task(s) in progress will be interrupted if you quit now.",
+            active.len()
+        )),
+        severity: Some("warning".to_string()),
+        notification_type: Some(NotificationType::Patient),
+        dismissible: Some(true),
+        persist: Some(false),
+        silent: Some(false),
+        actions: Some(serde_json::to_string(&actions).unwrap_or_default()),
+        progress: None,
+        current_step: None,
+        total_steps: None,
+        metadata: None,
+        show_on_completion: None,
+    }) {
+        log::error!("[tray] Failed to create quit confirmation: {}", e);
+        let _ = crate::commands::app::request_guarded_exit(app, "tray-menu");
+    }
+}
+
+fn refresh_menu(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let running = app.state::<TrayInstances>().0.lock().unwrap().clone();
+    match build_menu(app, &running) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => log::warn!("[tray] Failed to rebuild tray menu: {}", e),
+    }
+}
+
+fn instance_id_from_payload(payload: &str) -> Option<(String, Option<String>)> {
+    let value = serde_json::from_str::<serde_json::Value>(payload).ok()?;
+    let slug = value["instance_id"].as_str()?.to_string();
+    let name = value["name"].as_str().map(str::to_string);
+    Some((slug, name))
+}
+
+fn listen_for_instance_changes(app: &AppHandle) {
+    let launched_app = app.clone();
+    app.listen("core://instance-launched", move |event| {
+        let Some((slug, name)) = instance_id_from_payload(event.payload()) else {
+            return;
+        };
+        // Reattached processes are announced without a name.
+        let name = name
+            .or_else(|| {
+                crate::instance::lifecycle::find_instance_by_slug(&slug).map(|inst| inst.name)
+            })
+            .unwrap_or_else(|| slug.clone());
+        launched_app
+            .state::<TrayInstances>()
+            .0
+            .lock()
+            .unwrap()
+            .insert(slug, name);
+        refresh_menu(&launched_app);
+    });
+
+    for event_name in ["core://instance-exited", "core://instance-killed"] {
+        let stopped_app = app.clone();
+        app.listen(event_name, move |event| {
+            let Some((slug, _)) = instance_id_from_payload(event.payload()) else {
+                return;
+            };
+            let removed = stopped_app
+                .state::<TrayInstances>()
+                .0
+                .lock()
+                .unwrap()
+                .remove(&slug)
+                .is_some();
+            if removed {
+                refresh_menu(&stopped_app);
+            }
+        });
+    }
+}
+
+fn listen_for_config_changes(app: &AppHandle) {
+    let config_app = app.clone();
+    app.listen("config-updated", move |event| {
+        let Ok(payload) = serde_json::from_str::<serde_json::Value>(event.payload()) else {
+            return;
+        };
+        if payload["field"] != "show_tray_icon" {
+            return;
+        }
+        // Never hide the only way back to a launcher that was closed to tray.
+        let main_hidden = config_app
+            .get_webview_window("main")
+            .map(|window| !window.is_visible().unwrap_or(true))
+            .unwrap_or(false);
+        if main_hidden {
+            return;
+        }
+        if let Err(e) = crate::commands::app::sync_tray_visibility_with_config(&config_app) {
+            log::warn!("[tray] Failed to apply tray visibility change: {}", e);
+        }
+    });
+}
+
+fn spawn_tooltip_refresh(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last = String::new();
+        let mut interval = tokio::time::interval(TOOLTIP_REFRESH_INTERVAL);
+        loop {
+            interval.tick().await;
+            let Some(tray) = app.tray_by_id(TRAY_ID) else {
+                continue;
+            };
+            let tooltip = current_tooltip(&app);
+            if tooltip != last {
+                let _ = tray.set_tooltip(Some(&tooltip));
+                last = tooltip;
+            }
+        }
+    });
+}
+
+fn current_tooltip(app: &AppHandle) -> String {
+    let Some(task_manager) = app.try_state::<TaskManager>() else {
+        return BASE_TOOLTIP.to_string();
+    };
+    let keys = task_manager.get_active_task_keys();
+    if keys.is_empty() {
+        return tooltip_text(0, None);
+    }
+
+    let progresses: Vec<i32> = app
+        .try_state::<NotificationManager>()
+        .and_then(|manager| manager.list(false, false).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|n| n.client_key.as_ref().is_some_and(|key| keys.contains(key)))
+        .filter_map(|n| n.progress)
+        .collect();
+    tooltip_text(keys.len(), overall_progress(&progresses))
+}
+
+/// Average of the determinate progress values, or `None` if every task is
+/// indeterminate.
+fn overall_progress(progresses: &[i32]) -> Option<i32> {
+    let determinate: Vec<i32> = progresses
+        .iter()
+        .copied()
+        .filter(|p| *p >= 0)
+        .map(|p| p.min(100))
+        .collect();
+    if determinate.is_empty() {
+        return None;
+    }
+    Some(determinate.iter().sum::<i32>() / determinate.len() as i32)
+}
+
+fn tooltip_text(active_tasks: usize, progress: Option<i32>) -> String {
+    match (active_tasks, progress) {
+        (0, _) => BASE_TOOLTIP.to_string(),
+        (1, Some(p)) => format!("{} - 1 task running ({}%)", BASE_TOOLTIP, p),
+        (1, None) => format!("{} - 1 task running", BASE_TOOLTIP),
+        (n, Some(p)) => format!("{} - {} tasks running ({}%)", BASE_TOOLTIP, n, p),
+        (n, None) => format!("{} - {} tasks running", BASE_TOOLTIP, n),
+    }
+}
+
+struct ConfirmQuit;
+
+impl ActionHandler for ConfirmQuit {
+    fn handle(
+        &self,
+        app_handle: &AppHandle,
+        _client_key: Option<String>,
+        _payload: Option<serde_json::Value>,
+    ) -> anyhow::Result<()> {
+        log::info!("[tray] Quit confirmed while tasks were running");
+        app_handle.exit(0);
+        Ok(())
+    }
+}
+
+pub fn register(manager: &NotificationManager) {
+    manager.register_action("confirm_quit", Arc::new(ConfirmQuit));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overall_progress_ignores_indeterminate_tasks() {
+        assert_eq!(overall_progress(&[]), None);
+        assert_eq!(overall_progress(&[-1, -1]), None);
+        assert_eq!(overall_progress(&[40, -1, 60]), Some(50));
+        assert_eq!(overall_progress(&[150]), Some(100));
+    }
+
+    #[test]
+    fn tooltip_reflects_task_count_and_progress() {
+        assert_eq!(tooltip_text(0, Some(20)), "Vesta Launcher");
+        assert_eq!(
+            tooltip_text(1, Some(20)),
+            "Vesta Launcher - 1 task running (20%)"
+        );
+        assert_eq!(tooltip_text(3, None), "Vesta Launcher - 3 tasks running");
+    }
+
+    #[test]
+    fn parses_instance_events() {
+        assert_eq!(
+            instance_id_from_payload(r#"{"instance_id":"my-pack","name":"My Pack"}"#),
+            Some(("my-pack".to_string(), Some("My Pack".to_string())))
+        );
+        assert_eq!(
+            instance_id_from_payload(r#"{"instance_id":"my-pack","reattached":true}"#),
+            Some(("my-pack".to_string(), None))
+        );
+        assert_eq!(instance_id_from_payload(r#"{"pid":1}"#), None);
+    }
+}
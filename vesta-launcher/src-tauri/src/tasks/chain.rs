@@ -0,0 +1,293 @@
+//! Chains of tasks that must run one after another.
+//!
+//! Only the running task of a chain is known to the
+//! [`TaskManager`](super::manager::TaskManager); the rest wait here and are
+//! submitted when their predecessor succeeds. A failed or cancelled task
+//! drops the rest of its chain. Like the registry, this never talks to Tauri
+//! itself: [`TaskChains::finish`] says what the manager should do next.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// Finished chains kept for `get_chain_status`; older ones are forgotten.
+const MAX_FINISHED_CHAINS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainState {
+    Running,
+    Completed,
+    Failed,
+}
+
+/// Returned by `get_chain_status`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainStatus {
+    pub chain_id: String,
+    pub state: ChainState,
+    /// Names of every task in the chain, in order.
+    pub tasks: Vec<String>,
+    /// Index of the running task, or of the one that failed.
+    pub current_index: usize,
+    /// Client key of the running task, once it has been queued.
+    pub current_task_id: Option<String>,
+    pub completed: usize,
+    pub error: Option<String>,
+}
+
+/// Payload of `core://task-chain-failed`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskChainFailed {
+    pub chain_id: String,
+    pub failed_at_index: usize,
+    pub error: String,
+}
+
+/// What the manager does after a chained task finished.
+pub enum ChainStep<J> {
+    /// Submit the next task of the chain.
+    Next {
+        chain_id: String,
+        job: J,
+    },
+    Completed(String),
+    /// Emit `core://task-chain-failed`; the remaining tasks were dropped.
+    Failed(TaskChainFailed),
+}
+
+struct Chain<J> {
+    names: Vec<String>,
+    pending: VecDeque<J>,
+    current: usize,
+    current_task_id: Option<String>,
+    state: ChainState,
+    error: Option<String>,
+}
+
+/// Chains by id plus the client key of each chain's running task.
+pub struct TaskChains<J> {
+    chains: HashMap<String, Chain<J>>,
+    by_task: HashMap<String, String>,
+    finished: VecDeque<String>,
+    next_seq: u64,
+}
+
+impl<J> Default for TaskChains<J> {
+    fn default() -> Self {
+        Self {
+            chains: HashMap::new(),
+            by_task: HashMap::new(),
+            finished: VecDeque::new(),
+            next_seq: 0,
+        }
+    }
+}
+
+impl<J> TaskChains<J> {
+    /// Register a chain of `jobs` named `names` and return its id with the
+    /// first job, which the caller submits right away.
+    pub fn start(&mut self, names: Vec<String>, jobs: Vec<J>) -> Option<(String, J)> {
+        let mut pending: VecDeque<J> = jobs.into();
+        let first = pending.pop_front()?;
+        let chain_id = format!(
+            "chain_{}_{}",
+            chrono::Utc::now().timestamp_millis(),
+            self.next_seq
+        );
+        self.next_seq += 1;
+        self.chains.insert(
+            chain_id.clone(),
+            Chain {
+                names,
+                pending,
+                current: 0,
+                current_task_id: None,
+                state: ChainState::Running,
+                error: None,
+            },
+        );
+        Some((chain_id, first))
+    }
+
+    /// Record the client key the current task of `chain_id` was queued as.
+    pub fn bind(&mut self, chain_id: &str, task_id: &str) {
+        if let Some(chain) = self.chains.get_mut(chain_id) {
+            chain.current_task_id = Some(task_id.to_string());
+            self.by_task
+                .insert(task_id.to_string(), chain_id.to_string());
+        }
+    }
+
+    /// A chained task left the manager. `None` when `task_id` isn't part of
+    /// a running chain.
+    pub fn finish(&mut self, task_id: &str, result: &Result<(), String>) -> Option<ChainStep<J>> {
+        let chain_id = self.by_task.remove(task_id)?;
+        match result {
+            Ok(()) => self.advance(&chain_id),
+            Err(error) => self.fail(&chain_id, error.clone()),
+        }
+    }
+
+    /// The current task of `chain_id` was never queued, e.g. because a task
+    /// with the same key was already active.
+    pub fn fail(&mut self, chain_id: &str, error: String) -> Option<ChainStep<J>> {
+        let chain = self.chains.get_mut(chain_id)?;
+        if chain.state != ChainState::Running {
+            return None;
+        }
+        chain.pending.clear();
+        chain.state = ChainState::Failed;
+        chain.error = Some(error.clone());
+        let failed_at_index = chain.current;
+        self.mark_finished(chain_id);
+        Some(ChainStep::Failed(TaskChainFailed {
+            chain_id: chain_id.to_string(),
+            failed_at_index,
+            error,
+        }))
+    }
+
+    pub fn status(&self, chain_id: &str) -> Option<ChainStatus> {
+        let chain = self.chains.get(chain_id)?;
+        let completed = match chain.state {
+            ChainState::Completed => chain.names.len(),
+            _ => chain.current,
+        };
+        Some(ChainStatus {
+            chain_id: chain_id.to_string(),
+            state: chain.state,
+            tasks: chain.names.clone(),
+            current_index: chain.current,
+            current_task_id: chain.current_task_id.clone(),
+            completed,
+            error: chain.error.clone(),
+        })
+    }
+
+    fn advance(&mut self, chain_id: &str) -> Option<ChainStep<J>> {
+        let chain = self.chains.get_mut(chain_id)?;
+        chain.current_task_id = None;
+        match chain.pending.pop_front() {
+            Some(job) => {
+                chain.current += 1;
+                Some(ChainStep::Next {
+                    chain_id: chain_id.to_string(),
+                    job,
+                })
+            }
+            None => {
+                chain.state = ChainState::Completed;
+                self.mark_finished(chain_id);
+                Some(ChainStep::Completed(chain_id.to_string()))
+            }
+        }
+    }
+
+    fn mark_finished(&mut self, chain_id: &str) {
+        self.finished.push_back(chain_id.to_string());
+        while self.finished.len() > MAX_FINISHED_CHAINS {
+            if let Some(oldest) = self.finished.pop_front() {
+                self.chains.remove(&oldest);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain_of(chains: &mut TaskChains<&'static str>, jobs: &[&'static str]) -> String {
+        let names = jobs.iter().map(|job| job.to_string()).collect();
+        let (chain_id, first) = chains.start(names, jobs.to_vec()).unwrap();
+        assert_eq!(first, jobs[0]);
+        chain_id
+    }
+
+    #[test]
+    fn tasks_run_in_order_after_each_success() {
+        let mut chains = TaskChains::default();
+        let id = chain_of(&mut chains, &["fabric", "fabric-api", "mods"]);
+
+        chains.bind(&id, "task-1");
+        let Some(ChainStep::Next { job, .. }) = chains.finish("task-1", &Ok(())) else {
+            panic!("second task should be submitted");
+        };
+        assert_eq!(job, "fabric-api");
+        let status = chains.status(&id).unwrap();
+        assert_eq!((status.current_index, status.completed), (1, 1));
+        assert_eq!(status.current_task_id, None);
+
+        chains.bind(&id, "task-2");
+        assert_eq!(
+            chains.status(&id).unwrap().current_task_id.as_deref(),
+            Some("task-2")
+        );
+        assert!(matches!(
+            chains.finish("task-2", &Ok(())),
+            Some(ChainStep::Next { job: "mods", .. })
+        ));
+        chains.bind(&id, "task-3");
+        assert!(matches!(
+            chains.finish("task-3", &Ok(())),
+            Some(ChainStep::Completed(_))
+        ));
+
+        let status = chains.status(&id).unwrap();
+        assert_eq!(status.state, ChainState::Completed);
+        assert_eq!(status.completed, 3);
+    }
+
+    #[test]
+    fn failure_drops_the_rest_of_the_chain() {
+        let mut chains = TaskChains::default();
+        let id = chain_of(&mut chains, &["fabric", "fabric-api", "mods"]);
+        chains.bind(&id, "task-1");
+        chains.finish("task-1", &Ok(()));
+        chains.bind(&id, "task-2");
+
+        let Some(ChainStep::Failed(failed)) =
+            chains.finish("task-2", &Err("Download failed".to_string()))
+        else {
+            panic!("chain should fail");
+        };
+        assert_eq!(
+            failed,
+            TaskChainFailed {
+                chain_id: id.clone(),
+                failed_at_index: 1,
+                error: "Download failed".to_string(),
+            }
+        );
+        let status = chains.status(&id).unwrap();
+        assert_eq!(status.state, ChainState::Failed);
+        assert_eq!(status.completed, 1);
+
+        // Late reports for the failed chain change nothing.
+        assert!(chains.finish("task-2", &Ok(())).is_none());
+        assert!(chains.fail(&id, "again".to_string()).is_none());
+    }
+
+    #[test]
+    fn unrelated_tasks_and_empty_chains_are_ignored() {
+        let mut chains: TaskChains<&str> = TaskChains::default();
+        assert!(chains.start(Vec::new(), Vec::new()).is_none());
+        assert!(chains.finish("task-1", &Ok(())).is_none());
+        assert!(chains.status("chain_missing").is_none());
+    }
+
+    #[test]
+    fn only_recent_finished_chains_are_kept() {
+        let mut chains = TaskChains::default();
+        let first = chain_of(&mut chains, &["a"]);
+        chains.bind(&first, "task-0");
+        chains.finish("task-0", &Ok(()));
+        for i in 0..MAX_FINISHED_CHAINS {
+            let id = chain_of(&mut chains, &["a"]);
+            chains.bind(&id, &format!("task-{}", i + 1));
+            chains.finish(&format!("task-{}", i + 1), &Ok(()));
+        }
+        assert!(chains.status(&first).is_none());
+        assert_eq!(chains.chains.len(), MAX_FINISHED_CHAINS);
+    }
+}
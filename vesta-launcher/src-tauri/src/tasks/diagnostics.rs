@@ -0,0 +1,465 @@
+use crate::models::instance::Instance;
+use crate::notifications::manager::NotificationManager;
+use crate::notifications::models::{
+    CreateNotificationInput, NotificationAction, NotificationSeverity, NotificationType,
+};
+use crate::notifications::store::NotificationStore;
+use crate::tasks::manager::{BoxFuture, Task, TaskContext};
+use crate::utils::db::get_vesta_conn;
+use crate::utils::redact::Scrubber;
+use diesel::prelude::*;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+use zip::{write::FileOptions, ZipWriter};
+
+/// Lines kept from the end of each instance log.
+pub const INSTANCE_LOG_TAIL_LINES: usize = 2000;
+const RECENT_FAILURE_LIMIT: usize = 25;
+
+pub struct DiagnosticsBundleTask {
+    pub dest_path: PathBuf,
+    pub include_instance_logs: bool,
+    /// Add the local event log; the user opts in per export.
+    pub include_event_log: bool,
+}
+
+impl Task for DiagnosticsBundleTask {
+    fn name(&self) -> String {
+        "Generating diagnostics bundle".to_string()
+    }
+
+    fn id(&self) -> Option<String> {
+        Some("diagnostics_bundle".to_string())
+    }
+
+    fn cancellable(&self) -> bool {
+        true
+    }
+
+    fn starting_description(&self) -> String {
+        "Collecting launcher information...".to_string()
+    }
+
+    fn run(&self, ctx: TaskContext) -> BoxFuture<'static, Result<(), String>> {
+        let dest_path = self.dest_path.clone();
+        let include_instance_logs = self.include_instance_logs;
+        let include_event_log = self.include_event_log;
+
+        Box::pin(async move {
+            let build_ctx = ctx.clone();
+            let app_version = ctx.app_handle.package_info().version.to_string();
+            let bundle_path = tauri::async_runtime::spawn_blocking(move || {
+                export_bundle(
+                    &dest_path,
+                    include_instance_logs,
+                    include_event_log,
+                    &app_version,
+                    |step, total_steps, description| {
+                        if *build_ctx.cancel_rx.borrow() {
+                            return Err("Diagnostics bundle cancelled".to_string());
+                        }
+                        build_ctx.update_full(
+                            (step * 100 / total_steps.max(1)).min(99),
+                            description.to_string(),
+                            Some(step),
+                            Some(total_steps),
+                        );
+                        Ok(())
+                    },
+                )
+            })
+            .await
+            .map_err(|e| format!("Diagnostics bundle worker failed: {}", e))??;
+
+            log::info!("[diagnostics] Wrote bundle to {:?}", bundle_path);
+            notify_bundle_ready(&ctx.app_handle, &bundle_path);
+            Ok(())
+        })
+    }
+}
+
+/// A destination ending in `.zip` is used as-is; anything else is treated as
+/// the folder to place a timestamped bundle in.
+pub fn resolve_bundle_path(dest_path: &Path) -> PathBuf {
+    let is_zip = dest_path
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("zip"))
+        .unwrap_or(false);
+    if is_zip && !dest_path.is_dir() {
+        return dest_path.to_path_buf();
+    }
+    dest_path.join(format!(
+        "vesta-diagnostics-{}.zip",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ))
+}
+
+/// Write a bundle for `dest_path` (see [`resolve_bundle_path`]) and return
+/// where it was written. `progress` gets `(step, total_steps, description)`
+/// before each step; an error from it aborts the bundle. A partial bundle is
+/// removed on failure.
+pub fn export_bundle(
+    dest_path: &Path,
+    include_instance_logs: bool,
+    include_event_log: bool,
+    app_version: &str,
+    progress: impl FnMut(i32, i32, &str) -> Result<(), String>,
+) -> Result<PathBuf, String> {
+    let bundle_path = resolve_bundle_path(dest_path);
+    let result = write_bundle(
+        &bundle_path,
+        include_instance_logs,
+        include_event_log,
+        app_version,
+        progress,
+    );
+    if result.is_err() {
+        let _ = std::fs::remove_file(&bundle_path);
+    }
+    result.map(|_| bundle_path)
+}
+
+/// The last `max_lines` lines of `content`.
+pub fn tail_lines(content: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let start = lines.len().saturating_sub(max_lines);
+    lines[start..].join("\n")
+}
+
+struct BundleWriter {
+    zip: ZipWriter<std::fs::File>,
+    scrubber: Scrubber,
+}
+
+impl BundleWriter {
+    fn add_text(&mut self, name: &str, content: &str) -> Result<(), String> {
+        let options =
+            FileOptions::<()>::default().compression_method(zip::CompressionMethod::Deflated);
+        self.zip
+            .start_file(name, options)
+            .map_err(|e| format!("Failed to add {} to bundle: {}", name, e))?;
+        self.zip
+            .write_all(content.as_bytes())
+            .map_err(|e| format!("Failed to write {} to bundle: {}", name, e))
+    }
+
+    fn add_json(&mut self, name: &str, mut value: serde_json::Value) -> Result<(), String> {
+        self.scrubber.scrub_json(&mut value);
+        let rendered = serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?;
+        self.add_text(name, &rendered)
+    }
+
+    fn add_log(&mut self, name: &str, path: &Path, tail: Option<usize>) -> Result<(), String> {
+        let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        let content = String::from_utf8_lossy(&bytes);
+        let content = match tail {
+            Some(max_lines) => tail_lines(&content, max_lines),
+            None => content.into_owned(),
+        };
+        let scrubbed = self.scrubber.scrub(&content);
+        self.add_text(name, &scrubbed)
+    }
+}
+
+fn write_bundle(
+    bundle_path: &Path,
+    include_instance_logs: bool,
+    include_event_log: bool,
+    app_version: &str,
+    mut progress: impl FnMut(i32, i32, &str) -> Result<(), String>,
+) -> Result<(), String> {
+    if let Some(parent) = bundle_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+    }
+    let file = std::fs::File::create(bundle_path)
+        .map_err(|e| format!("Failed to create {:?}: {}", bundle_path, e))?;
+    let mut writer = BundleWriter {
+        zip: ZipWriter::new(file),
+        scrubber: Scrubber::new(account_identities(), true),
+    };
+
+    let launcher_logs = list_log_files(crate::utils::db_manager::get_launcher_log_dir().ok());
+    let instances = load_instances();
+    let instance_logs = if include_instance_logs {
+        instance_log_files(&instances)
+    } else {
+        Vec::new()
+    };
+    let total_steps =
+        (5 + include_event_log as usize + launcher_logs.len() + instance_logs.len()) as i32;
+    let mut step = 0;
+    let mut advance = |description: &str| -> Result<(), String> {
+        step += 1;
+        progress(step, total_steps, description)
+    };
+
+    advance("Collecting system information...")?;
+    writer.add_json("system.json", system_info(app_version))?;
+
+    advance("Scanning Java installations...")?;
+    writer.add_json("java.json", java_installations())?;
+
+    advance("Collecting configuration...")?;
+    writer.add_json("config.json", redacted_config())?;
+    writer.add_json("database.json", crate::utils::db::get_schema_versions())?;
+
+    advance("Listing instances...")?;
+    writer.add_json("instances.json", instance_summaries(&instances))?;
+
+    advance("Collecting recent task failures...")?;
+    writer.add_json("task_failures.json", recent_task_failures())?;
+
+    if include_event_log {
+        advance("Collecting event log...")?;
+        writer.add_json("event_log.json", event_log())?;
+    }
+
+    for (name, path) in &launcher_logs {
+        advance(&format!("Adding launcher log {}...", name))?;
+        if let Err(e) = writer.add_log(&format!("logs/launcher/{}", name), path, None) {
+            log::warn!("[diagnostics] Skipping launcher log {}: {}", name, e);
+        }
+    }
+
+    for (name, path) in &instance_logs {
+        advance(&format!("Adding instance log {}...", name))?;
+        if let Err(e) = writer.add_log(
+            &format!("logs/instances/{}", name),
+            path,
+            Some(INSTANCE_LOG_TAIL_LINES),
+        ) {
+            log::warn!("[diagnostics] Skipping instance log {}: {}", name, e);
+        }
+    }
+
+    writer
+        .zip
+        .finish()
+        .map_err(|e| format!("Failed to finalize bundle: {}", e))?;
+    Ok(())
+}
+
+/// Usernames and UUIDs of every signed-in account, hashed out of the bundle.
+fn account_identities() -> Vec<String> {
+    use crate::schema::vesta::account::dsl as acc_dsl;
+
+    let Ok(mut conn) = get_vesta_conn() else {
+        return Vec::new();
+    };
+    acc_dsl::account
+        .select((acc_dsl::username, acc_dsl::uuid))
+        .load::<(String, String)>(&mut conn)
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(|(username, uuid)| [username, uuid])
+        .collect()
+}
+
+fn system_info(app_version: &str) -> serde_json::Value {
+    serde_json::json!({
+        "app_version": app_version,
+        "os": std::env::consts::OS,
+        "os_version": sysinfo::System::long_os_version(),
+        "arch": std::env::consts::ARCH,
+        "kernel_version": sysinfo::System::kernel_version(),
+        "total_memory_mb": piston_lib::utils::hardware::get_total_memory_mb(),
+        "generated_at": chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+fn java_installations() -> serde_json::Value {
+    let describe = |java: piston_lib::game::installer::core::jre_manager::DetectedJava| {
+        serde_json::json!({
+            "path": java.path.to_string_lossy(),
+            "major_version": java.major_version,
+            "is_64bit": java.is_64bit,
+        })
+    };
+    serde_json::json!({
+        "managed": crate::utils::java::get_managed_javas()
+            .into_iter()
+            .map(describe)
+            .collect::<Vec<_>>(),
+        "system": crate::utils::java::scan_system_javas_filtered()
+            .into_iter()
+            .map(describe)
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn redacted_config() -> serde_json::Value {
+    match crate::utils::config::get_app_config() {
+        Ok(config) => {
+            let mut value = serde_json::to_value(&config).unwrap_or_default();
+            if let Some(proxy_url) = config.proxy_url.as_deref() {
+                value["proxy_url"] =
+                    serde_json::Value::String(piston_lib::client::redact_proxy_url(proxy_url));
+            }
+            value
+        }
+        Err(e) => serde_json::json!({ "error": e.to_string() }),
+    }
+}
+
+fn load_instances() -> Vec<Instance> {
+    use crate::schema::vesta::instance::dsl as inst_dsl;
+
+    let Ok(mut conn) = get_vesta_conn() else {
+        return Vec::new();
+    };
+    inst_dsl::instance
+        .load::<Instance>(&mut conn)
+        .unwrap_or_default()
+}
+
+fn instance_summaries(instances: &[Instance]) -> serde_json::Value {
+    serde_json::Value::Array(
+        instances
+            .iter()
+            .map(|inst| {
+                serde_json::json!({
+                    "id": inst.id,
+                    "slug": inst.slug(),
+                    "minecraft_version": inst.minecraft_version,
+                    "modloader": inst.modloader,
+                    "modloader_version": inst.modloader_version,
+                    "installation_status": inst.installation_status,
+                    "last_operation": inst.last_operation,
+                    "crashed": inst.crashed,
+                    "modpack_platform": inst.modpack_platform,
+                    "min_memory": inst.min_memory,
+                    "max_memory": inst.max_memory,
+                    "trashed": inst.is_trashed(),
+                })
+            })
+            .collect(),
+    )
+}
+
+fn recent_task_failures() -> serde_json::Value {
+    let mut failures: Vec<_> = NotificationStore::list(false, false)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|n| n.severity == NotificationSeverity::Error)
+        .collect();
+    failures.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    serde_json::Value::Array(
+        failures
+            .into_iter()
+            .take(RECENT_FAILURE_LIMIT)
+            .map(|n| {
+                serde_json::json!({
+                    "title": n.title,
+                    "description": n.description,
+                    "created_at": n.created_at,
+                })
+            })
+            .collect(),
+    )
+}
+
+fn event_log() -> serde_json::Value {
+    get_vesta_conn()
+        .map_err(|e| e.to_string())
+        .and_then(|mut conn| {
+            crate::utils::event_log::recent_events(
+                &mut conn,
+                crate::utils::event_log::MAX_EVENT_LOG_ROWS,
+            )
+        })
+        .map(|events| serde_json::json!(events))
+        .unwrap_or_else(|e| serde_json::json!({ "error": e }))
+}
+
+fn list_log_files(dir: Option<PathBuf>) -> Vec<(String, PathBuf)> {
+    let Some(entries) = dir.and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(String, PathBuf)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let name = path.file_name()?.to_string_lossy().to_string();
+            Some((name, path))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn instance_log_files(instances: &[Instance]) -> Vec<(String, PathBuf)> {
+    let Ok(logs_dir) =
+        crate::utils::db_manager::get_app_config_dir().map(|dir| dir.join("data").join("logs"))
+    else {
+        return Vec::new();
+    };
+    instances
+        .iter()
+        .map(|inst| format!("{}.log", inst.slug()))
+        .map(|name| {
+            let path = logs_dir.join(&name);
+            (name, path)
+        })
+        .filter(|(_, path)| path.is_file())
+        .collect()
+}
+
+fn notify_bundle_ready(app: &tauri::AppHandle, bundle_path: &Path) {
+    let folder = bundle_path.parent().unwrap_or(bundle_path);
+    let actions = vec![NotificationAction {
+        action_id: "open_folder".to_string(),
+        label: "Open folder".to_string(),
+        action_type: "primary".to_string(),
+        payload: Some(serde_json::json!({ "path": folder.to_string_lossy() })),
+    }];
+    let manager = app.state::<NotificationManager>();
+    if let Err(e) = manager.create(CreateNotificationInput {
+        client_key: Some("diagnostics_bundle_ready".to_string()),
+        title: Some("Diagnostics bundle ready".to_string()),
+        description: Some(format!("Saved to {}", bundle_path.display())),
+        severity: Some("success".to_string()),
+        notification_type: Some(NotificationType::Patient),
+        dismissible: Some(true),
+        persist: Some(true),
+        silent: Some(false),
+        actions: Some(serde_json::to_string(&actions).unwrap_or_default()),
+        progress: None,
+        current_step: None,
+        total_steps: None,
+        metadata: None,
+        show_on_completion: None,
+    }) {
+        log::error!("Failed to create diagnostics bundle notification: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_only_the_log_tail() {
+        let content = (1..=10)
+            .map(|n| format!("line {}", n))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(tail_lines(&content, 3), "line 8\nline 9\nline 10");
+        assert_eq!(tail_lines(&content, 50), content);
+        assert_eq!(tail_lines("", 5), "");
+    }
+
+    #[test]
+    fn resolves_bundle_path_for_files_and_folders() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let explicit = dir.path().join("support.zip");
+        assert_eq!(resolve_bundle_path(&explicit), explicit);
+
+        let generated = resolve_bundle_path(dir.path());
+        assert_eq!(generated.parent(), Some(dir.path()));
+        let name = generated.file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.starts_with("vesta-diagnostics-") && name.ends_with(".zip"));
+    }
+}
@@ -0,0 +1,179 @@
+//! Downloaded bytes per category, kept per UTC month in `download_stats`.
+//!
+//! piston-lib reports every counted transfer (installs, resource and modpack
+//! downloads, resource API responses) to the sink installed by [`start`].
+//! Totals collect in memory and are written out when a task finishes, when
+//! stats are read, and every [`FLUSH_INTERVAL`].
+
+use crate::utils::db::get_vesta_conn;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Text};
+use diesel::SqliteConnection;
+use lazy_static::lazy_static;
+use piston_lib::game::installer::core::traffic::{set_traffic_sink, TrafficCategory};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::Duration;
+
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+lazy_static! {
+    static ref PENDING: Mutex<HashMap<TrafficCategory, u64>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsRange {
+    /// The current calendar month (UTC).
+    Month,
+    Lifetime,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadStats {
+    pub range: StatsRange,
+    /// `YYYY-MM` for [`StatsRange::Month`].
+    pub month: Option<String>,
+    pub total_bytes: u64,
+    /// Bytes per category; every category is listed, including empty ones.
+    pub categories: BTreeMap<String, u64>,
+}
+
+/// Install the traffic sink and the periodic flush.
+pub fn start() {
+    set_traffic_sink(|category, bytes| {
+        let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+        *pending.entry(category).or_default() += bytes;
+    });
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(FLUSH_INTERVAL).await;
+            match tokio::task::spawn_blocking(flush_pending).await {
+                Ok(Err(error)) => log::warn!("Download stats flush failed: {}", error),
+                Err(error) => log::warn!("Download stats flush task panicked: {}", error),
+                Ok(Ok(())) => {}
+            }
+        }
+    });
+}
+
+pub(crate) fn current_month() -> String {
+    chrono::Utc::now().format("%Y-%m").to_string()
+}
+
+/// Write the pending totals to the current month. On failure they are kept
+/// for the next flush.
+pub(crate) fn flush_pending() -> Result<(), String> {
+    let drained: Vec<(TrafficCategory, u64)> = {
+        let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+        std::mem::take(&mut *pending).into_iter().collect()
+    };
+    if drained.is_empty() {
+        return Ok(());
+    }
+
+    let result = get_vesta_conn()
+        .map_err(|e| e.to_string())
+        .and_then(|mut conn| add_bytes(&mut conn, &current_month(), &drained));
+    if result.is_err() {
+        let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+        for (category, bytes) in drained {
+            *pending.entry(category).or_default() += bytes;
+        }
+    }
+    result
+}
+
+pub(crate) fn add_bytes(
+    conn: &mut SqliteConnection,
+    month: &str,
+    totals: &[(TrafficCategory, u64)],
+) -> Result<(), String> {
+    conn.transaction(|conn| {
+        for (category, bytes) in totals {
+            diesel::sql_query(
+                "INSERT INTO download_stats (month, category, bytes) VALUES (?, ?, ?)
+                 ON CONFLICT(month, category) DO UPDATE SET bytes = bytes + excluded.bytes",
+            )
+            .bind::<Text, _>(month)
+            .bind::<Text, _>(category.as_str())
+            .bind::<BigInt, _>(i64::try_from(*bytes).unwrap_or(i64::MAX))
+            .execute(conn)?;
+        }
+        Ok(())
+    })
+    .map_err(|e: diesel::result::Error| format!("Failed to record download stats: {}", e))
+}
+
+pub(crate) fn load_stats(
+    conn: &mut SqliteConnection,
+    range: StatsRange,
+    month: &str,
+) -> Result<DownloadStats, String> {
+    use crate::schema::download_stats::dsl;
+
+    let mut query = dsl::download_stats
+        .select((dsl::category, dsl::bytes))
+        .into_boxed();
+    if range == StatsRange::Month {
+        query = query.filter(dsl::month.eq(month.to_string()));
+    }
+    let rows: Vec<(String, i64)> = query
+        .load(conn)
+        .map_err(|e| format!("Failed to load download stats: {}", e))?;
+
+    let mut categories: BTreeMap<String, u64> = TrafficCategory::ALL
+        .into_iter()
+        .map(|category| (category.as_str().to_string(), 0))
+        .collect();
+    for (category, bytes) in rows {
+        *categories.entry(category).or_default() += bytes.max(0) as u64;
+    }
+
+    Ok(DownloadStats {
+        range,
+        month: (range == StatsRange::Month).then(|| month.to_string()),
+        total_bytes: categories.values().sum(),
+        categories,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel_migrations::MigrationHarness;
+
+    fn conn() -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.run_pending_migrations(crate::utils::db::VESTA_MIGRATIONS)
+            .unwrap();
+        conn
+    }
+
+    #[test]
+    fn accumulates_per_month_and_over_the_lifetime() {
+        let mut conn = conn();
+        add_bytes(
+            &mut conn,
+            "2026-09",
+            &[(TrafficCategory::Assets, 500), (TrafficCategory::Jre, 200)],
+        )
+        .unwrap();
+        add_bytes(&mut conn, "2026-10", &[(TrafficCategory::Assets, 100)]).unwrap();
+        add_bytes(&mut conn, "2026-10", &[(TrafficCategory::Assets, 50)]).unwrap();
+
+        let month = load_stats(&mut conn, StatsRange::Month, "2026-10").unwrap();
+        assert_eq!(month.month.as_deref(), Some("2026-10"));
+        assert_eq!(month.total_bytes, 150);
+        assert_eq!(month.categories["assets"], 150);
+        assert_eq!(month.categories["jre"], 0);
+
+        let lifetime = load_stats(&mut conn, StatsRange::Lifetime, "2026-10").unwrap();
+        assert_eq!(lifetime.month, None);
+        assert_eq!(lifetime.total_bytes, 850);
+        assert_eq!(lifetime.categories["assets"], 650);
+        assert_eq!(lifetime.categories.len(), TrafficCategory::ALL.len());
+    }
+}
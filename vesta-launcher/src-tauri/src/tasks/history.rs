@@ -0,0 +1,124 @@
+//! Persisted record of finished tasks, shown by `get_task_history`.
+
+use crate::models::task_history::{NewTaskHistoryEntry, TaskHistoryEntry};
+use crate::schema::task_history::dsl as th_dsl;
+use crate::tasks::registry::FinishedTask;
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+
+/// Rows kept in `task_history`; older rows are dropped on insert.
+pub(crate) const MAX_TASK_HISTORY: i64 = 500;
+
+/// Longest error summary stored per task.
+const MAX_ERROR_LEN: usize = 500;
+
+fn error_summary(error: &str) -> String {
+    let first_line = error.lines().next().unwrap_or_default().trim();
+    match first_line.char_indices().nth(MAX_ERROR_LEN) {
+        Some((end, _)) => format!("{}…", &first_line[..end]),
+        None => first_line.to_string(),
+    }
+}
+
+pub(crate) fn record_task(
+    conn: &mut SqliteConnection,
+    task: &FinishedTask,
+    bytes_downloaded: u64,
+    keep: i64,
+) -> Result<(), String> {
+    let row = NewTaskHistoryEntry {
+        client_key: task.id.clone(),
+        name: task.name.clone(),
+        status: task.state.as_str().to_string(),
+        priority: task.priority.as_str().to_string(),
+        queued_at: task.queued_at.clone(),
+        started_at: task.started_at.clone(),
+        finished_at: task.finished_at.clone(),
+        duration_ms: task.duration_ms,
+        error: task.error.as_deref().map(error_summary),
+        bytes_downloaded: bytes_downloaded as i64,
+    };
+    conn.transaction(|conn| {
+        diesel::insert_into(th_dsl::task_history)
+            .values(&row)
+            .execute(conn)?;
+        diesel::sql_query(
+            "DELETE FROM task_history WHERE id NOT IN (
+                SELECT id FROM task_history ORDER BY finished_at DESC, id DESC LIMIT ?
+            )",
+        )
+        .bind::<diesel::sql_types::BigInt, _>(keep)
+        .execute(conn)
+    })
+    .map(|_| ())
+    .map_err(|e: diesel::result::Error| format!("Failed to record task history: {}", e))
+}
+
+/// Most recently finished tasks, newest first.
+pub(crate) fn recent_tasks(
+    conn: &mut SqliteConnection,
+    limit: i64,
+) -> Result<Vec<TaskHistoryEntry>, String> {
+    th_dsl::task_history
+        .order((th_dsl::finished_at.desc(), th_dsl::id.desc()))
+        .limit(limit)
+        .load::<TaskHistoryEntry>(conn)
+        .map_err(|e| format!("Failed to load task history: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tasks::registry::{TaskPriority, TaskStatus};
+    use diesel_migrations::MigrationHarness;
+
+    fn conn() -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.run_pending_migrations(crate::utils::db::VESTA_MIGRATIONS)
+            .unwrap();
+        conn
+    }
+
+    fn finished(id: &str, state: TaskStatus, error: Option<&str>) -> FinishedTask {
+        FinishedTask {
+            id: id.to_string(),
+            name: format!("Task {}", id),
+            state,
+            priority: TaskPriority::Normal,
+            queued_at: "2026-10-17T10:00:00+00:00".to_string(),
+            started_at: Some("2026-10-17T10:00:01+00:00".to_string()),
+            finished_at: format!("2026-10-17T10:00:{:02}+00:00", id.len() + 2),
+            duration_ms: Some(1500),
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn records_failure_summary_and_trims_old_rows() {
+        let mut conn = conn();
+        record_task(&mut conn, &finished("a", TaskStatus::Completed, None), 0, 2).unwrap();
+        record_task(
+            &mut conn,
+            &finished("bb", TaskStatus::Failed, Some("HTTP 503\nstack trace")),
+            4096,
+            2,
+        )
+        .unwrap();
+        record_task(
+            &mut conn,
+            &finished("ccc", TaskStatus::Cancelled, None),
+            0,
+            2,
+        )
+        .unwrap();
+
+        let history = recent_tasks(&mut conn, 10).unwrap();
+        let keys: Vec<&str> = history.iter().map(|t| t.client_key.as_str()).collect();
+        assert_eq!(keys, ["ccc", "bb"]);
+        assert_eq!(history[1].status, "failed");
+        assert_eq!(history[1].error.as_deref(), Some("HTTP 503"));
+        assert_eq!(history[1].duration_ms, Some(1500));
+        assert_eq!(history[1].bytes_downloaded, 4096);
+        assert_eq!(history[0].status, "cancelled");
+    }
+}
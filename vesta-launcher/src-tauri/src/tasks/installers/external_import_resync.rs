@@ -174,7 +174,7 @@ impl Task for ImportResourceResyncTask {
                 game_dir: PathBuf::from(&target_dir),
                 java_path: target_instance.java_path.as_ref().map(PathBuf::from),
                 dry_run: false,
-                concurrency: 8,
+                concurrency: crate::utils::network::instance_download_concurrency(&target_instance),
                 artifact_cache_max_bytes: crate::utils::config::get_app_config()
                     .map(|config| {
                         crate::utils::storage::normalize_artifact_cache_limit_bytes(
@@ -184,6 +184,7 @@ impl Task for ImportResourceResyncTask {
                     .unwrap_or(
                         piston_lib::game::installer::types::DEFAULT_ARTIFACT_CACHE_MAX_BYTES,
                     ),
+                artifact_cache_compression: false,
                 force_overwrite_configs: false,
                 repair_scope: piston_lib::game::installer::types::RepairScope::Full,
                 remediation_policy:
@@ -5,6 +5,7 @@ pub mod modpack;
 
 use anyhow::Result;
 use piston_lib::game::installer::install_instance;
+use piston_lib::game::installer::metrics::InstallMetrics;
 use piston_lib::game::installer::types::{
     InstallSpec, ModloaderType, NotificationActionSpec, ProgressReporter,
 };
@@ -17,12 +18,15 @@ use crate::models::instance::Instance;
 use crate::notifications::manager::NotificationManager;
 use crate::notifications::models::ProgressUpdate;
 use crate::tasks::manager::{Task, TaskContext};
+use crate::utils::instance_runtime::InstanceRuntimeFields;
 
 /// Task adapter for game installation
 pub struct InstallInstanceTask {
     instance: Instance,
     dry_run: bool,
     update_notification_title: bool,
+    /// Runtime fields the instance row keeps until this install succeeds.
+    version_change: Option<InstanceRuntimeFields>,
 }
 
 impl InstallInstanceTask {
@@ -31,6 +35,7 @@ impl InstallInstanceTask {
             instance,
             dry_run: false,
             update_notification_title: true,
+            version_change: None,
         }
     }
 
@@ -41,6 +46,12 @@ impl InstallInstanceTask {
     pub fn set_update_notification_title(&mut self, update_notification_title: bool) {
         self.update_notification_title = update_notification_title;
     }
+
+    /// Install `instance`'s version as a change from `previous`: the row is
+    /// switched to the new version on success and left untouched on failure.
+    pub fn set_version_change(&mut self, previous: InstanceRuntimeFields) {
+        self.version_change = Some(previous);
+    }
 }
 
 impl Task for InstallInstanceTask {
@@ -65,6 +76,10 @@ impl Task for InstallInstanceTask {
         0
     }
 
+    fn max_retries(&self) -> u32 {
+        2
+    }
+
     fn starting_description(&self) -> String {
         // Build friendly version string for notification
         let modloader = self.instance.modloader.as_deref().unwrap_or("vanilla");
@@ -97,6 +112,7 @@ impl Task for InstallInstanceTask {
         let instance = self.instance.clone();
         let dry_run = self.dry_run;
         let update_notification_title = self.update_notification_title;
+        let version_change = self.version_change.clone();
         let app_handle = ctx.app_handle.clone();
         let notification_id = ctx.notification_id.clone();
         let pause_rx = ctx.pause_rx.clone();
@@ -105,7 +121,40 @@ impl Task for InstallInstanceTask {
             ctx.set_title(format!("Installing {}", instance.name));
         }
 
-        Box::pin(async move {
+        // An install over an already-installed instance is a version change.
+        let history_kind = if version_change.is_some()
+            || instance.installation_status.as_deref() == Some("installed")
+        {
+            crate::instance::history::OperationKind::Update
+        } else {
+            crate::instance::history::OperationKind::Install
+        };
+        let mut history_details = serde_json::json!({
+            "minecraft_version": instance.minecraft_version.clone(),
+            "modloader": instance.modloader.clone(),
+            "modloader_version": instance.modloader_version.clone(),
+        });
+        if let (Some(previous), Some(details)) =
+            (version_change.as_ref(), history_details.as_object_mut())
+        {
+            details.insert(
+                "previous_minecraft_version".to_string(),
+                previous.minecraft_version.clone().into(),
+            );
+            details.insert(
+                "previous_modloader".to_string(),
+                previous.modloader.clone().into(),
+            );
+            details.insert(
+                "previous_modloader_version".to_string(),
+                previous.modloader_version.clone().into(),
+            );
+        }
+        let history_instance_id = instance.id;
+        let history_notes = crate::instance::history::OperationNotes::default();
+        let operation_notes = history_notes.clone();
+
+        let operation = async move {
             log::info!(
                 "[InstallTask] Starting installation for instance '{}' (dry_run={})",
                 instance.name,
@@ -142,20 +191,45 @@ impl Task for InstallInstanceTask {
             let mut pause_rx_watcher = pause_rx.clone();
             let current_step_for_pause = Arc::new(RwLock::new(String::new()));
             let reporter_current_step = current_step_for_pause.clone();
+            let install_metrics = Arc::new(std::sync::Mutex::new(InstallMetrics::default()));
 
             let reporter: std::sync::Arc<dyn ProgressReporter> =
                 std::sync::Arc::new(TauriProgressReporter {
                     ctx: ctx.clone(),
                     current_step: reporter_current_step,
                     dry_run,
+                    metrics: install_metrics.clone(),
                     last_emit: Arc::new(std::sync::Mutex::new(
                         std::time::Instant::now() - std::time::Duration::from_secs(1),
                     )),
                     last_percent: std::sync::atomic::AtomicI32::new(-1),
                     last_step_current: std::sync::atomic::AtomicI32::new(-1),
                     last_step_total: std::sync::atomic::AtomicI32::new(-1),
+                    last_download_emit: Arc::new(std::sync::Mutex::new(
+                        std::time::Instant::now() - std::time::Duration::from_secs(1),
+                    )),
+                    bytes_drive_percent: std::sync::atomic::AtomicBool::new(true),
                 });
 
+            if !dry_run {
+                if let Err(e) = network_preflight(&ctx, &instance, &data_dir).await {
+                    // Same status handling as a failed install below.
+                    if instance.id > 0 {
+                        let status_val = if version_change.is_some() {
+                            "installed".to_string()
+                        } else {
+                            format!("failed:{}", e)
+                        };
+                        let _ = crate::commands::instances::update_installation_status(
+                            &app_handle,
+                            instance.id,
+                            &status_val,
+                        );
+                    }
+                    return Err(e);
+                }
+            }
+
             let java_path = if dry_run {
                 instance.java_path.as_ref().map(PathBuf::from)
             } else {
@@ -168,13 +242,27 @@ impl Task for InstallInstanceTask {
                 .await?;
                 Some(PathBuf::from(verified))
             };
-            let artifact_cache_max_bytes = crate::utils::config::get_app_config()
+            let app_config = crate::utils::config::get_app_config().ok();
+            let artifact_cache_max_bytes = app_config
+                .as_ref()
                 .map(|config| {
                     crate::utils::storage::normalize_artifact_cache_limit_bytes(
                         config.artifact_cache_max_bytes,
                     ) as u64
                 })
                 .unwrap_or(piston_lib::game::installer::types::DEFAULT_ARTIFACT_CACHE_MAX_BYTES);
+            let artifact_cache_compression = app_config
+                .as_ref()
+                .is_some_and(|config| config.cache_compression_enabled);
+            let concurrency = crate::utils::network::instance_download_concurrency(&instance);
+
+            if !dry_run {
+                // Asset downloads are the bulk of a fresh install; send them
+                // to the fastest mirror. Cached after the first install.
+                if let Err(e) = crate::utils::network::MirrorSelector::select_asset_mirror().await {
+                    log::warn!("[InstallTask] Asset mirror benchmark failed: {}", e);
+                }
+            }
 
             let spec = InstallSpec {
                 version_id: instance.minecraft_version.clone(),
@@ -184,8 +272,9 @@ impl Task for InstallInstanceTask {
                 game_dir: game_dir.clone(),
                 java_path,
                 dry_run,
-                concurrency: 8,
+                concurrency,
                 artifact_cache_max_bytes,
+                artifact_cache_compression,
                 force_overwrite_configs: false,
                 repair_scope: piston_lib::game::installer::types::RepairScope::Full,
                 remediation_policy:
@@ -260,6 +349,19 @@ impl Task for InstallInstanceTask {
                 }
             };
 
+            if let Ok(metrics) = install_metrics.lock() {
+                if !metrics.is_empty() {
+                    if let (Ok(value), Ok(mut notes)) =
+                        (serde_json::to_value(&*metrics), operation_notes.lock())
+                    {
+                        notes.insert(
+                            crate::instance::history::INSTALL_METRICS_KEY.to_string(),
+                            value,
+                        );
+                    }
+                }
+            }
+
             match result {
                 Ok(_) => {
                     log::info!(
@@ -267,6 +369,15 @@ impl Task for InstallInstanceTask {
                         instance.name
                     );
 
+                    // A version change only lands on the row once the new
+                    // version is fully installed.
+                    if version_change.is_some() && instance.id > 0 && !dry_run {
+                        crate::utils::instance_runtime::sync_fields(
+                            instance.id,
+                            &InstanceRuntimeFields::from_instance(&instance),
+                        )?;
+                    }
+
                     // Update database status to 'installed'
                     if instance.id > 0 {
                         if let Err(e) = crate::commands::instances::update_installation_status(
@@ -305,9 +416,14 @@ impl Task for InstallInstanceTask {
                 Err(e) => {
                     log::error!("[InstallTask] Installation failed: {}", e);
 
-                    // Update database status to 'failed' with reason
+                    // Update database status to 'failed' with reason. A failed
+                    // version change leaves the previous install usable.
                     if instance.id > 0 {
-                        let status_val = format!("failed:{}", e);
+                        let status_val = if version_change.is_some() {
+                            "installed".to_string()
+                        } else {
+                            format!("failed:{}", e)
+                        };
                         if let Err(status_err) =
                             crate::commands::instances::update_installation_status(
                                 &app_handle,
@@ -325,7 +441,44 @@ impl Task for InstallInstanceTask {
                     Err(e.to_string())
                 }
             }
-        })
+        };
+
+        Box::pin(crate::instance::history::track_with_notes(
+            history_instance_id,
+            history_kind,
+            history_details,
+            history_notes,
+            operation,
+        ))
+    }
+}
+
+/// Wait for the network, then hold the install for the metered-download
+/// policy. Missing assets stand in for the download size.
+async fn network_preflight(
+    ctx: &TaskContext,
+    instance: &Instance,
+    data_dir: &std::path::Path,
+) -> Result<(), String> {
+    crate::tasks::network_gate::wait_for_network(ctx).await?;
+    match piston_lib::game::installer::asset_delta::missing_asset_bytes(
+        data_dir,
+        &instance.minecraft_version,
+    )
+    .await
+    {
+        Ok(estimated_bytes) => {
+            crate::tasks::network_gate::confirm_metered_download(
+                ctx,
+                &instance.name,
+                estimated_bytes,
+            )
+            .await
+        }
+        Err(e) => {
+            log::warn!("[InstallTask] Could not estimate download size: {}", e);
+            Ok(())
+        }
     }
 }
 
@@ -334,11 +487,67 @@ pub struct TauriProgressReporter {
     pub ctx: TaskContext,
     pub current_step: Arc<RwLock<String>>,
     pub dry_run: bool,
+    /// Metrics the installer reports through `record_metric`.
+    pub metrics: Arc<std::sync::Mutex<InstallMetrics>>,
     // Throttling state for progress events
     pub last_emit: Arc<std::sync::Mutex<std::time::Instant>>,
     pub last_percent: std::sync::atomic::AtomicI32,
     pub last_step_current: std::sync::atomic::AtomicI32,
     pub last_step_total: std::sync::atomic::AtomicI32,
+    /// Throttling state for `core://download-eta` events
+    pub last_download_emit: Arc<std::sync::Mutex<std::time::Instant>>,
+    /// Whether `update_bytes` sets the percent. Single-file downloads rely on
+    /// it; once a step sets its own percent (e.g. a batch mapping its bytes
+    /// onto a span) byte totals no longer override it until the next step.
+    pub bytes_drive_percent: std::sync::atomic::AtomicBool,
+}
+
+impl TauriProgressReporter {
+    /// Emit a progress update, throttled by time and percent delta.
+    fn emit_percent(&self, percent: i32) {
+        // Throttling constants (placeholder for future config integration)
+        const MIN_INTERVAL_MS: u64 = 150;
+        const MIN_PERCENT_DELTA: i32 = 1;
+
+        // Always emit 0 and 100 for clarity
+        let prev = self.last_percent.load(std::sync::atomic::Ordering::Relaxed);
+        let mut allow = percent == 0 || percent == 100;
+        if !allow {
+            let delta = percent - prev;
+            if delta.abs() >= MIN_PERCENT_DELTA {
+                let mut guard = self.last_emit.lock().unwrap();
+                if guard.elapsed() >= std::time::Duration::from_millis(MIN_INTERVAL_MS) {
+                    *guard = std::time::Instant::now();
+                    allow = true;
+                }
+            }
+        }
+        if allow {
+            self.last_percent
+                .store(percent, std::sync::atomic::Ordering::Relaxed);
+
+            let current_step = self
+                .last_step_current
+                .load(std::sync::atomic::Ordering::Relaxed);
+            let total_steps = self
+                .last_step_total
+                .load(std::sync::atomic::Ordering::Relaxed);
+
+            self.ctx.update_progress(
+                percent,
+                if current_step >= 0 {
+                    Some(current_step)
+                } else {
+                    None
+                },
+                if total_steps >= 0 {
+                    Some(total_steps)
+                } else {
+                    None
+                },
+            );
+        }
+    }
 }
 
 impl ProgressReporter for TauriProgressReporter {
@@ -353,6 +562,8 @@ impl ProgressReporter for TauriProgressReporter {
             .store(-1, std::sync::atomic::Ordering::Relaxed);
         self.last_step_total
             .store(-1, std::sync::atomic::Ordering::Relaxed);
+        self.bytes_drive_percent
+            .store(true, std::sync::atomic::Ordering::Relaxed);
 
         // 1. Update overall status via unified context
         ctx.update_description(name_str.clone());
@@ -374,57 +585,58 @@ impl ProgressReporter for TauriProgressReporter {
     }
 
     fn update_bytes(&self, transferred: u64, total: Option<u64>) {
+        if !self
+            .bytes_drive_percent
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            return;
+        }
         if let Some(total) = total {
             if total > 0 {
                 let percent = ((transferred as f64 / total as f64) * 100.0) as i32;
-                self.set_percent(percent);
+                self.emit_percent(percent);
             }
         }
     }
 
-    fn set_percent(&self, percent: i32) {
-        // Throttling constants (placeholder for future config integration)
-        const MIN_INTERVAL_MS: u64 = 150;
-        const MIN_PERCENT_DELTA: i32 = 1;
-
-        // Always emit 0 and 100 for clarity
-        let prev = self.last_percent.load(std::sync::atomic::Ordering::Relaxed);
-        let mut allow = percent == 0 || percent == 100;
-        if !allow {
-            let delta = percent - prev;
-            if delta.abs() >= MIN_PERCENT_DELTA {
-                let mut guard = self.last_emit.lock().unwrap();
-                if guard.elapsed() >= std::time::Duration::from_millis(MIN_INTERVAL_MS) {
-                    *guard = std::time::Instant::now();
-                    allow = true;
-                }
+    fn update_download_progress(
+        &self,
+        file_name: &str,
+        bytes_done: u64,
+        bytes_total: Option<u64>,
+        speed_bps: u64,
+        eta_secs: Option<u64>,
+    ) {
+        use tauri::Emitter;
+        const MIN_INTERVAL_MS: u64 = 250;
+
+        self.update_bytes(bytes_done, bytes_total);
+
+        let finished = bytes_total.is_some_and(|total| bytes_done >= total);
+        {
+            let mut guard = self.last_download_emit.lock().unwrap();
+            if !finished && guard.elapsed() < std::time::Duration::from_millis(MIN_INTERVAL_MS) {
+                return;
             }
+            *guard = std::time::Instant::now();
         }
-        if allow {
-            self.last_percent
-                .store(percent, std::sync::atomic::Ordering::Relaxed);
-
-            let current_step = self
-                .last_step_current
-                .load(std::sync::atomic::Ordering::Relaxed);
-            let total_steps = self
-                .last_step_total
-                .load(std::sync::atomic::Ordering::Relaxed);
+        let _ = self.ctx.app_handle.emit(
+            "core://download-eta",
+            serde_json::json!({
+                "task": self.ctx.notification_id,
+                "file_name": file_name,
+                "bytes_done": bytes_done,
+                "bytes_total": bytes_total,
+                "speed_bps": speed_bps,
+                "eta_secs": eta_secs,
+            }),
+        );
+    }
 
-            self.ctx.update_progress(
-                percent,
-                if current_step >= 0 {
-                    Some(current_step)
-                } else {
-                    None
-                },
-                if total_steps >= 0 {
-                    Some(total_steps)
-                } else {
-                    None
-                },
-            );
-        }
+    fn set_percent(&self, percent: i32) {
+        self.bytes_drive_percent
+            .store(false, std::sync::atomic::Ordering::Relaxed);
+        self.emit_percent(percent);
     }
 
     fn set_message(&self, message: &str) {
@@ -538,9 +750,15 @@ impl ProgressReporter for TauriProgressReporter {
     fn is_dry_run(&self) -> bool {
         self.dry_run
     }
+
+    fn record_metric(&self, key: &str, value: u64) {
+        if let Ok(mut metrics) = self.metrics.lock() {
+            metrics.record(key, value);
+        }
+    }
 }
 
-fn parse_modloader(modloader: &str) -> Option<ModloaderType> {
+pub(crate) fn parse_modloader(modloader: &str) -> Option<ModloaderType> {
     match modloader.to_lowercase().as_str() {
         "vanilla" => None,
         "fabric" => Some(ModloaderType::Fabric),
@@ -1,8 +1,9 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
-use tauri::Manager;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tauri::{Emitter, Listener, Manager};
+use tokio::sync::{oneshot, RwLock};
 
 use crate::models::instance::Instance;
 use crate::models::SourcePlatform;
@@ -12,10 +13,41 @@ use crate::tasks::manager::{Task, TaskContext};
 
 use anyhow::Result;
 use piston_lib::game::installer::core::modpack_installer::{
-    ModpackInstaller, ModpackResolvedCF, ModpackResolvedModrinth, ModpackResolver,
+    ModpackInstallOptions, ModpackInstaller, ModpackResolvedCF, ModpackResolvedModrinth,
+    ModpackResolver, OptionalModpackMod,
 };
+use serde::{Deserialize, Serialize};
 use tokio::fs;
 
+/// Sent with the optional mods of a pack before its mods are downloaded.
+const OPTIONAL_MODS_EVENT: &str = "vesta://modpack-optional-mods";
+/// Sent back by the UI with the file ids the user kept.
+const OPTIONAL_MODS_SELECTION_EVENT: &str = "vesta://optional-mods-selection";
+/// After this long without an answer every optional mod is installed.
+const OPTIONAL_MODS_SELECTION_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Serialize, Clone)]
+struct OptionalModsPrompt {
+    request_id: String,
+    pack_name: String,
+    mods: Vec<OptionalModEntry>,
+}
+
+#[derive(Serialize, Clone)]
+struct OptionalModEntry {
+    project_id: Option<u32>,
+    file_id: u32,
+    name: Option<String>,
+    summary: Option<String>,
+    icon_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OptionalModsSelection {
+    request_id: String,
+    selected_file_ids: Vec<u32>,
+}
+
 #[derive(Clone)]
 pub enum ModpackSource {
     Path(PathBuf),
@@ -204,6 +236,84 @@ impl ModpackResolver for PistonModpackResolver {
             })
         })
     }
+
+    fn select_optional_mods(
+        &self,
+        pack_name: &str,
+        optional: Vec<OptionalModpackMod>,
+    ) -> futures::future::BoxFuture<'static, HashSet<u32>> {
+        let handle = self.app_handle.clone();
+        let pack_name = pack_name.to_string();
+        Box::pin(async move {
+            let all: HashSet<u32> = optional.iter().map(|m| m.file_id).collect();
+            let rm = handle.state::<ResourceManager>().inner().clone();
+
+            // Names are best effort; the UI falls back to the file id.
+            let mods = futures::future::join_all(optional.into_iter().map(|m| {
+                let rm = rm.clone();
+                async move {
+                    let project = match m.project_id {
+                        Some(pid) => rm
+                            .get_project(SourcePlatform::CurseForge, &pid.to_string())
+                            .await
+                            .ok(),
+                        None => None,
+                    };
+                    OptionalModEntry {
+                        project_id: m.project_id,
+                        file_id: m.file_id,
+                        name: project.as_ref().map(|p| p.name.clone()),
+                        summary: project.as_ref().map(|p| p.summary.clone()),
+                        icon_url: project.and_then(|p| p.icon_url),
+                    }
+                }
+            }))
+            .await;
+
+            let request_id = uuid::Uuid::new_v4().to_string();
+            let (tx, rx) = oneshot::channel::<HashSet<u32>>();
+            let tx = std::sync::Mutex::new(Some(tx));
+            let expected_id = request_id.clone();
+            let listener = handle.listen(OPTIONAL_MODS_SELECTION_EVENT, move |event| {
+                let Ok(selection) = serde_json::from_str::<OptionalModsSelection>(event.payload())
+                else {
+                    return;
+                };
+                if selection.request_id != expected_id {
+                    return;
+                }
+                if let Some(tx) = tx.lock().unwrap().take() {
+                    let _ = tx.send(selection.selected_file_ids.into_iter().collect());
+                }
+            });
+
+            let prompt = OptionalModsPrompt {
+                request_id,
+                pack_name,
+                mods,
+            };
+            if let Err(e) = handle.emit(OPTIONAL_MODS_EVENT, prompt) {
+                log::warn!(
+                    "[PistonModpackResolver] Failed to ask for optional mods: {}",
+                    e
+                );
+                handle.unlisten(listener);
+                return all;
+            }
+
+            let selected = match tokio::time::timeout(OPTIONAL_MODS_SELECTION_TIMEOUT, rx).await {
+                Ok(Ok(selected)) => selected,
+                _ => {
+                    log::info!(
+                        "[PistonModpackResolver] No optional mod selection received, installing all"
+                    );
+                    all
+                }
+            };
+            handle.unlisten(listener);
+            selected
+        })
+    }
 }
 
 impl InstallModpackTask {
@@ -250,14 +360,33 @@ impl Task for InstallModpackTask {
                     ctx: ctx.clone(),
                     current_step: Arc::new(RwLock::new(String::new())),
                     dry_run: false,
+                    metrics: Default::default(),
                     last_emit: Arc::new(std::sync::Mutex::new(
                         std::time::Instant::now() - std::time::Duration::from_secs(1),
                     )),
                     last_percent: std::sync::atomic::AtomicI32::new(-1),
                     last_step_current: std::sync::atomic::AtomicI32::new(-1),
                     last_step_total: std::sync::atomic::AtomicI32::new(-1),
+                    last_download_emit: Arc::new(std::sync::Mutex::new(
+                        std::time::Instant::now() - std::time::Duration::from_secs(1),
+                    )),
+                    bytes_drive_percent: std::sync::atomic::AtomicBool::new(true),
                 });
 
+            let data_dir = crate::utils::db_manager::get_app_config_dir()
+                .map_err(|e| e.to_string())?
+                .join("data");
+
+            crate::tasks::network_gate::wait_for_network(&ctx).await?;
+            let estimated_bytes =
+                modpack_download_estimate(&source, metadata.as_ref(), &data_dir).await;
+            crate::tasks::network_gate::confirm_metered_download(
+                &ctx,
+                &instance.name,
+                estimated_bytes,
+            )
+            .await?;
+
             let modpack_path = match source {
                 ModpackSource::Path(p) => p,
                 ModpackSource::Url(u) => {
@@ -288,6 +417,10 @@ impl Task for InstallModpackTask {
                         let chunk = item.map_err(|e| e.to_string())?;
                         file.write_all(&chunk).await.map_err(|e| e.to_string())?;
                         downloaded += chunk.len() as u64;
+                        piston_lib::game::installer::core::traffic::record_traffic(
+                            piston_lib::game::installer::core::traffic::TrafficCategory::Modpacks,
+                            chunk.len() as u64,
+                        );
 
                         if let Some(total) = total_size {
                             let percent = (downloaded as f32 / total as f32) * 100.0;
@@ -303,9 +436,6 @@ impl Task for InstallModpackTask {
                 modpack_path
             );
 
-            let data_dir = crate::utils::db_manager::get_app_config_dir()
-                .map_err(|e| e.to_string())?
-                .join("data");
             let game_dir = PathBuf::from(
                 instance
                     .game_directory
@@ -321,7 +451,11 @@ impl Task for InstallModpackTask {
 
             let resolver = Arc::new(PistonModpackResolver::new(app_handle.clone()));
 
-            let java_path = instance.java_path.as_ref().map(PathBuf::from);
+            let options = ModpackInstallOptions {
+                java_path: instance.java_path.as_ref().map(PathBuf::from),
+                concurrency: crate::utils::network::instance_download_concurrency(&instance),
+                ..ModpackInstallOptions::default()
+            };
 
             let (metadata, override_mods) = match ModpackInstaller::install_from_zip_with_metadata(
                 &modpack_path,
@@ -330,7 +464,7 @@ impl Task for InstallModpackTask {
                 &data_dir,
                 reporter.clone(),
                 Some(resolver.clone()),
-                java_path,
+                options,
             )
             .await
             {
@@ -473,6 +607,26 @@ impl Task for InstallModpackTask {
                     }
                 }
 
+                // Identify all Modrinth files in bulk; the per-mod lookups below
+                // are then answered from the hash cache.
+                let sha1s: Vec<String> = mods
+                    .iter()
+                    .filter_map(|m| match m {
+                        piston_lib::game::modpack::types::ModpackMod::Modrinth {
+                            hashes, ..
+                        } => hashes.get("sha1").cloned(),
+                        _ => None,
+                    })
+                    .collect();
+                if !sha1s.is_empty() {
+                    if let Err(e) = rm
+                        .get_by_hashes(crate::models::SourcePlatform::Modrinth, &sha1s)
+                        .await
+                    {
+                        log::warn!("[ModpackTask] Bulk Modrinth hash lookup failed: {}", e);
+                    }
+                }
+
                 for res_entry in mods {
                     match res_entry {
                         piston_lib::game::modpack::types::ModpackMod::Modrinth {
@@ -658,6 +812,36 @@ impl Task for InstallModpackTask {
     }
 }
 
+/// Rough download size of a modpack install: the pack archive when it comes
+/// from a URL, plus the game assets still missing for its Minecraft version.
+/// Mod files are not counted; their sizes are only known once resolved.
+async fn modpack_download_estimate(
+    source: &ModpackSource,
+    metadata: Option<&piston_lib::game::modpack::types::ModpackMetadata>,
+    data_dir: &std::path::Path,
+) -> u64 {
+    let archive_bytes = match source {
+        ModpackSource::Url(url) => piston_lib::client::shared_client()
+            .head(url)
+            .send()
+            .await
+            .ok()
+            .and_then(|response| response.content_length())
+            .unwrap_or(0),
+        ModpackSource::Path(_) => 0,
+    };
+    let asset_bytes = match metadata {
+        Some(metadata) => piston_lib::game::installer::asset_delta::missing_asset_bytes(
+            data_dir,
+            &metadata.minecraft_version,
+        )
+        .await
+        .unwrap_or(0),
+        None => 0,
+    };
+    archive_bytes + asset_bytes
+}
+
 /// Enrich missing platform mod hashes/urls via [`ModpackResolver`] (Modrinth or CurseForge per entry).
 pub async fn enrich_manifest_platform_hashes(
     app_handle: &tauri::AppHandle,
@@ -689,6 +873,17 @@ pub fn spawn_manifest_resource_linking(
         let pack_provenance =
             crate::resources::watcher::modpack_provenance_for_instance(instance_id).ok();
 
+        let sha1s: Vec<String> = mods
+            .iter()
+            .filter(|m| matches!(m.source, ModSource::Modrinth { .. }))
+            .filter_map(|m| m.sha1.clone())
+            .collect();
+        if !sha1s.is_empty() {
+            if let Err(e) = rm.get_by_hashes(SourcePlatform::Modrinth, &sha1s).await {
+                log::warn!("[ModpackTask] Bulk Modrinth hash lookup failed: {}", e);
+            }
+        }
+
         for m in mods {
             let Some(local_path) = resolve_mod_path_on_disk(&game_dir, &m.path) else {
                 continue;
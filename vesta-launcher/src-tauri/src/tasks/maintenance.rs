@@ -182,6 +182,7 @@ impl Task for CloneInstanceTask {
                     pre_launch_hook: source.pre_launch_hook.clone(),
                     wrapper_command: source.wrapper_command.clone(),
                     post_exit_hook: source.post_exit_hook.clone(),
+                    slug: Some(final_slug.clone()),
                 };
 
                 diesel::insert_into(instance)
@@ -507,7 +508,9 @@ impl Task for RepairInstanceTask {
         let inst_id = self.instance_id;
         let scope = self.scope.clone();
 
-        Box::pin(async move {
+        let history_details = serde_json::json!({ "scope": scope });
+
+        let operation = async move {
             let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
             use crate::schema::instance::dsl::*;
 
@@ -581,6 +584,7 @@ impl Task for RepairInstanceTask {
                 })
                 .unwrap_or(piston_lib::game::installer::types::DEFAULT_ARTIFACT_CACHE_MAX_BYTES);
             spec.java_path = inst.java_path.as_ref().map(std::path::PathBuf::from);
+            spec.concurrency = crate::utils::network::instance_download_concurrency(&inst);
             // Pass modloader info so the verifier uses the correct manifest
             // (e.g. fabric-loader-X-1.20.1 instead of vanilla 1.20.1)
             spec.modloader = inst.modloader.as_deref().and_then(|m| match m {
@@ -701,6 +705,7 @@ impl Task for RepairInstanceTask {
                         false,
                         repair_reporter,
                         Some(resolver),
+                        crate::utils::network::instance_download_concurrency(&inst),
                     ).await {
                         Ok(repaired) => {
                             log::info!("[RepairInstanceTask] Modpack repair complete");
@@ -759,6 +764,7 @@ impl Task for RepairInstanceTask {
                                 false,
                                 repair_reporter,
                                 Some(resolver),
+                                crate::utils::network::instance_download_concurrency(&inst),
                             ).await {
                                 Ok(repaired) => {
                                     log::info!("[RepairInstanceTask] Modpack repair complete");
@@ -839,7 +845,14 @@ impl Task for RepairInstanceTask {
 
             ctx.update_progress(100, Some(3), Some(3));
             Ok(())
-        })
+        };
+
+        Box::pin(crate::instance::history::track(
+            inst_id,
+            crate::instance::history::OperationKind::Repair,
+            history_details,
+            operation,
+        ))
     }
 }
 
@@ -875,7 +888,7 @@ impl Task for DeleteInstanceTask {
     }
 
     fn completion_description(&self) -> String {
-        "Instance deleted".to_string()
+        "Instance moved to trash".to_string()
     }
 
     fn run(&self, ctx: TaskContext) -> BoxFuture<'static, Result<(), String>> {
@@ -883,7 +896,7 @@ impl Task for DeleteInstanceTask {
 
         Box::pin(async move {
             log::info!("[delete_instance_task] start instance_id={}", instance_id);
-            ctx.update_full(5, "Stopping watcher...".to_string(), Some(1), Some(5));
+            ctx.update_full(5, "Stopping watcher...".to_string(), Some(1), Some(4));
             let watcher = ctx
                 .app_handle
                 .state::<crate::resources::watcher::ResourceWatcher>();
@@ -904,7 +917,7 @@ impl Task for DeleteInstanceTask {
                 25,
                 "Resolving instance details...".to_string(),
                 Some(2),
-                Some(5),
+                Some(4),
             );
             let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
             use crate::schema::instance::dsl::*;
@@ -914,62 +927,44 @@ impl Task for DeleteInstanceTask {
                 .first::<Instance>(&mut conn)
                 .map_err(|e| format!("Instance not found: {}", e))?;
             let slug_val = inst.slug();
-            let game_dir = inst.game_directory.clone();
 
-            if let Some(gd) = game_dir {
-                ctx.update_full(
-                    55,
-                    "Removing instance files...".to_string(),
-                    Some(3),
-                    Some(5),
+            if inst.is_trashed() {
+                log::info!(
+                    "[delete_instance_task] instance_id={} already in trash",
+                    instance_id
                 );
-                let gd_path = std::path::PathBuf::from(gd);
-                if gd_path.exists() {
-                    tokio::task::spawn_blocking({
-                        let path = gd_path.clone();
-                        move || std::fs::remove_dir_all(&path)
-                    })
-                    .await
-                    .map_err(|e| format!("Failed to await instance removal task: {}", e))?
-                    .map_err(|e| {
-                        format!(
-                            "Failed to remove instance files at '{}': {}",
-                            gd_path.display(),
-                            e
-                        )
-                    })?;
-                }
+                return Ok(());
             }
 
             ctx.update_full(
-                80,
-                "Removing database references...".to_string(),
+                55,
+                "Moving instance to trash...".to_string(),
+                Some(3),
                 Some(4),
-                Some(5),
             );
-            conn.transaction::<_, diesel::result::Error, _>(|conn| {
-                diesel::delete(
-                    crate::schema::installed_resource::dsl::installed_resource.filter(
-                        crate::schema::installed_resource::dsl::instance_id.eq(instance_id),
-                    ),
-                )
-                .execute(conn)?;
+            let config = crate::utils::config::get_app_config().map_err(|e| e.to_string())?;
+            let app_config_dir =
+                crate::utils::db_manager::get_app_config_dir().map_err(|e| e.to_string())?;
+            let instances_root =
+                resolve_instances_root(&app_config_dir, config.default_game_dir.as_deref());
 
-                diesel::delete(instance.find(instance_id)).execute(conn)?;
-                Ok(())
+            let trash_dir = tokio::task::spawn_blocking(move || {
+                crate::instance::trash::move_to_trash(&mut conn, &inst, &instances_root)
             })
-            .map_err(|e| format!("Failed to delete instance from database: {}", e))?;
+            .await
+            .map_err(|e| format!("Failed to await instance trash task: {}", e))??;
 
-            ctx.update_full(95, "Finalizing...".to_string(), Some(5), Some(5));
+            ctx.update_full(95, "Finalizing...".to_string(), Some(4), Some(4));
             use tauri::Emitter;
             let _ = ctx.app_handle.emit(
                 "core://instance-deleted",
                 serde_json::json!({ "id": instance_id }),
             );
             log::info!(
-                "[delete_instance_task] completed instance_id={} slug={}",
+                "[delete_instance_task] completed instance_id={} slug={} trash_dir={:?}",
                 instance_id,
-                slug_val
+                slug_val,
+                trash_dir
             );
             Ok(())
         })
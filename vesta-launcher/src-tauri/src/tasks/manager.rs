@@ -3,15 +3,23 @@ use crate::notifications::models::{
     CreateNotificationInput, NotificationAction, NotificationSeverity, NotificationType,
     ProgressUpdate, PROGRESS_INDETERMINATE,
 };
+use crate::tasks::chain::{ChainStatus, ChainStep, TaskChains};
+use crate::tasks::registry::{
+    CancelOutcome, FinishedTask, TaskInfo, TaskPriority, TaskRegistry, TaskStateChange, TaskSummary,
+};
+use crate::tasks::{download_stats, history};
+use crate::utils::db::get_vesta_conn;
+use piston_lib::game::installer::core::traffic::{with_task_traffic, TrafficCounter};
 use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicI32, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
 use tauri::ipc::Channel;
 use tauri::{AppHandle, Emitter, Manager};
-use tokio::sync::{mpsc, watch, Semaphore};
+use tokio::sync::{mpsc, watch, Notify, Semaphore};
 
 #[derive(Clone)]
 pub struct TaskContext {
@@ -20,6 +28,8 @@ pub struct TaskContext {
     pub cancel_rx: watch::Receiver<bool>,
     pub pause_rx: watch::Receiver<bool>,
     pub progress_channel: Option<Channel<ProgressUpdate>>,
+    /// Last reported percent, read by `list_active_tasks`.
+    pub percent: Arc<AtomicI32>,
 }
 
 impl TaskContext {
@@ -45,6 +55,8 @@ impl TaskContext {
         current_step: Option<i32>,
         total_steps: Option<i32>,
     ) {
+        self.percent.store(progress, Ordering::Relaxed);
+
         // 1. Update the channel if available
         if let Some(ref channel) = self.progress_channel {
             let _ = channel.send(ProgressUpdate::Progress {
@@ -93,6 +105,8 @@ impl TaskContext {
         current_step: Option<i32>,
         total_steps: Option<i32>,
     ) {
+        self.percent.store(progress, Ordering::Relaxed);
+
         // 1. Update the channel if available
         if let Some(ref channel) = self.progress_channel {
             let _ = channel.send(ProgressUpdate::Progress {
@@ -135,6 +149,10 @@ pub trait Task: Send + Sync {
     fn pausable(&self) -> bool {
         false
     }
+    /// Queued tasks start in priority order, oldest first within a priority.
+    fn priority(&self) -> TaskPriority {
+        TaskPriority::Normal
+    }
     #[allow(dead_code)]
     fn serialize(&self) -> Option<String> {
         None
@@ -156,13 +174,156 @@ pub trait Task: Send + Sync {
     fn completion_description(&self) -> String {
         "Completed successfully".to_string()
     }
+    /// How many times a transient failure is retried before the task fails.
+    fn max_retries(&self) -> u32 {
+        0
+    }
+    /// Whether `error` is worth retrying, e.g. a dropped connection.
+    fn is_transient_error(&self, error: &str) -> bool {
+        let error = error.to_lowercase();
+        ["connection", "timeout", "503"]
+            .iter()
+            .any(|marker| error.contains(marker))
+    }
     /// Execute task work.
     fn run(&self, ctx: TaskContext) -> BoxFuture<'static, Result<(), String>>;
 }
 
+/// Longest wait between two attempts of a failing task.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Wait before retry number `attempt` (1-based): 1s, 2s, 4s... up to a minute.
+fn retry_delay(attempt: u32) -> Duration {
+    let secs = 1u64
+        .checked_shl(attempt.saturating_sub(1))
+        .unwrap_or(u64::MAX);
+    Duration::from_secs(secs).min(MAX_RETRY_DELAY)
+}
+
+/// Payload of `core://task-retrying`.
+#[derive(Debug, Clone, serde::Serialize)]
+struct TaskRetrying {
+    task_id: String,
+    attempt: u32,
+    delay_secs: u64,
+}
+
 pub struct QueuedTask {
     pub task: Box<dyn Task>,
     pub progress_channel: Option<Channel<ProgressUpdate>>,
+    /// Chain the task belongs to, see [`TaskManager::submit_chain`].
+    pub chain_id: Option<String>,
+}
+
+/// A task waiting in the registry queue for a free worker.
+struct PendingTask {
+    task: Box<dyn Task>,
+    progress_channel: Option<Channel<ProgressUpdate>>,
+    cancel_rx: watch::Receiver<bool>,
+    pause_rx: watch::Receiver<bool>,
+    percent: Arc<AtomicI32>,
+}
+
+type SharedRegistry = Arc<Mutex<TaskRegistry<PendingTask>>>;
+type SharedChains = Arc<Mutex<TaskChains<Box<dyn Task>>>>;
+
+fn emit_state_change(app: &AppHandle, change: &TaskStateChange) {
+    let _ = app.emit("core://task-state-changed", change);
+}
+
+/// Persist a finished task. History is best-effort and never fails the task.
+fn record_finished(task: &FinishedTask, bytes_downloaded: u64) {
+    if let Err(e) = get_vesta_conn()
+        .map_err(|e| e.to_string())
+        .and_then(|mut conn| {
+            history::record_task(&mut conn, task, bytes_downloaded, history::MAX_TASK_HISTORY)
+        })
+    {
+        log::warn!("{} for task {}", e, task.id);
+    }
+    if let Err(e) = download_stats::flush_pending() {
+        log::warn!("{} after task {}", e, task.id);
+    }
+}
+
+/// Submit the next task of a chain, or report that the chain failed.
+fn apply_chain_step(
+    app: &AppHandle,
+    sender: &mpsc::Sender<QueuedTask>,
+    chains: &SharedChains,
+    step: Option<ChainStep<Box<dyn Task>>>,
+) {
+    match step {
+        Some(ChainStep::Next { chain_id, job }) => {
+            let sender = sender.clone();
+            let app = app.clone();
+            let chains = chains.clone();
+            tauri::async_runtime::spawn(async move {
+                let queued = QueuedTask {
+                    task: job,
+                    progress_channel: None,
+                    chain_id: Some(chain_id.clone()),
+                };
+                if let Err(e) = sender.send(queued).await {
+                    let step = chains.lock().unwrap().fail(&chain_id, e.to_string());
+                    apply_chain_step(&app, &sender, &chains, step);
+                }
+            });
+        }
+        Some(ChainStep::Completed(chain_id)) => {
+            log::info!("TaskManager: Task chain {} completed", chain_id);
+        }
+        Some(ChainStep::Failed(failed)) => {
+            log::warn!(
+                "TaskManager: Task chain {} failed at task {}: {}",
+                failed.chain_id,
+                failed.failed_at_index,
+                failed.error
+            );
+            let _ = app.emit("core://task-chain-failed", &failed);
+        }
+        None => {}
+    }
+}
+
+/// Turn the "Waiting for worker..." notification of a task that never
+/// started into a cancellation notice.
+fn notify_cancelled_before_start(
+    app: &AppHandle,
+    client_key: &str,
+    task_name: String,
+    task_id: Option<String>,
+) {
+    let manager = app.state::<NotificationManager>();
+    if let Err(e) = manager.create(CreateNotificationInput {
+        client_key: Some(client_key.to_string()),
+        title: Some(task_name),
+        description: Some("Task cancelled.".to_string()),
+        severity: Some("warning".to_string()),
+        notification_type: Some(NotificationType::Patient),
+        dismissible: Some(true),
+        persist: Some(true),
+        silent: Some(false),
+        actions: None,
+        progress: None,
+        current_step: None,
+        total_steps: None,
+        metadata: None,
+        show_on_completion: None,
+    }) {
+        log::error!(
+            "Failed to create task-cancel notification for {}: {}",
+            client_key,
+            e
+        );
+    }
+
+    // Notify frontend about failure if it's a resource download
+    if let Some(task_id) = task_id {
+        if task_id.starts_with("download_") {
+            let _ = app.emit("resource-install-error", task_id);
+        }
+    }
 }
 
 pub struct TaskManager {
@@ -172,25 +333,31 @@ pub struct TaskManager {
     current_limit: Mutex<usize>,
     cancellation_tokens: Arc<Mutex<HashMap<String, watch::Sender<bool>>>>,
     pause_tokens: Arc<Mutex<HashMap<String, watch::Sender<bool>>>>,
-    active_tasks: Arc<Mutex<HashMap<String, String>>>,
+    registry: SharedRegistry,
+    chains: SharedChains,
 }
 
 impl TaskManager {
     pub fn new(app_handle: AppHandle) -> Self {
         let (sender, mut receiver) = mpsc::channel::<QueuedTask>(100);
-        let initial_limit = 2;
+        let initial_limit = 3;
         let semaphore = Arc::new(Semaphore::new(initial_limit));
         let current_limit = Mutex::new(initial_limit);
         let cancellation_tokens = Arc::new(Mutex::new(HashMap::new()));
         let pause_tokens = Arc::new(Mutex::new(HashMap::new()));
-        let active_tasks = Arc::new(Mutex::new(HashMap::new()));
+        let registry: SharedRegistry = Arc::new(Mutex::new(TaskRegistry::default()));
+        let chains: SharedChains = Arc::new(Mutex::new(TaskChains::default()));
+        let queue_notify = Arc::new(Notify::new());
 
-        let manager_semaphore = semaphore.clone();
         let manager_app = app_handle.clone();
         let manager_tokens = cancellation_tokens.clone();
         let manager_pause_tokens = pause_tokens.clone();
-        let manager_active_tasks = active_tasks.clone();
+        let manager_registry = registry.clone();
+        let manager_notify = queue_notify.clone();
+        let manager_chains = chains.clone();
+        let manager_sender = sender.clone();
 
+        // Intake: register submitted tasks and queue them without waiting for a worker.
         tauri::async_runtime::spawn(async move {
             static TASK_COUNTER: AtomicU64 = AtomicU64::new(0);
             log::info!("TaskManager: Worker loop started, ready to receive tasks");
@@ -198,6 +365,7 @@ impl TaskManager {
             while let Some(queued_task) = receiver.recv().await {
                 let task = queued_task.task;
                 let progress_channel = queued_task.progress_channel;
+                let chain_id = queued_task.chain_id;
                 log::info!("TaskManager: Received task: {}", task.name());
 
                 let task_name = task.name();
@@ -210,23 +378,24 @@ impl TaskManager {
                     .id()
                     .unwrap_or_else(|| format!("task_{}_{}", chrono::Utc::now().timestamp(), id));
 
-                // Check if task is already running (deduplication)
-                {
-                    let active = manager_active_tasks.lock().unwrap();
-                    if active.contains_key(&client_key) {
-                        log::info!(
-                            "TaskManager: Task with ID {} already active, ignoring submission",
-                            client_key
-                        );
-                        continue;
+                // Check if task is already queued or running (deduplication)
+                if manager_registry.lock().unwrap().contains(&client_key) {
+                    log::info!(
+                        "TaskManager: Task with ID {} already active, ignoring submission",
+                        client_key
+                    );
+                    if let Some(chain_id) = chain_id {
+                        let step = manager_chains
+                            .lock()
+                            .unwrap()
+                            .fail(&chain_id, format!("Task {} is already running", client_key));
+                        apply_chain_step(&manager_app, &manager_sender, &manager_chains, step);
                     }
+                    continue;
+                }
+                if let Some(chain_id) = &chain_id {
+                    manager_chains.lock().unwrap().bind(chain_id, &client_key);
                 }
-
-                // Track active task
-                manager_active_tasks
-                    .lock()
-                    .unwrap()
-                    .insert(client_key.clone(), task_name.clone());
 
                 let manager = manager_app.state::<NotificationManager>();
 
@@ -307,84 +476,97 @@ impl TaskManager {
                         .insert(client_key.clone(), pause_tx);
                 }
 
-                // Acquire permit to respect concurrency limit
-                log::info!(
-                    "TaskManager: Waiting for worker permit for task: {}",
-                    task_name
+                let percent = Arc::new(AtomicI32::new(PROGRESS_INDETERMINATE));
+                let info = TaskInfo {
+                    id: client_key.clone(),
+                    name: task_name,
+                    priority: task.priority(),
+                    cancellable: is_cancellable,
+                    percent: percent.clone(),
+                };
+                let change = manager_registry.lock().unwrap().enqueue(
+                    info,
+                    PendingTask {
+                        task,
+                        progress_channel,
+                        cancel_rx: rx,
+                        pause_rx,
+                        percent,
+                    },
                 );
-                let permit = match manager_semaphore.clone().acquire_owned().await {
+                emit_state_change(&manager_app, &change);
+                manager_notify.notify_one();
+            }
+        });
+
+        let dispatch_semaphore = semaphore.clone();
+        let retry_semaphore = semaphore.clone();
+        let dispatch_app = app_handle.clone();
+        let dispatch_tokens = cancellation_tokens.clone();
+        let dispatch_pause_tokens = pause_tokens.clone();
+        let dispatch_registry = registry.clone();
+        let dispatch_chains = chains.clone();
+        let dispatch_sender = sender.clone();
+
+        // Dispatcher: start the highest-priority queued task whenever a worker is free.
+        tauri::async_runtime::spawn(async move {
+            loop {
+                while !dispatch_registry.lock().unwrap().has_queued() {
+                    queue_notify.notified().await;
+                }
+
+                let permit = match dispatch_semaphore.clone().acquire_owned().await {
                     Ok(p) => p,
                     Err(_) => break, // Semaphore closed
                 };
+
+                // The queue may have been emptied by a cancel while we waited.
+                let Some((client_key, pending, change)) =
+                    dispatch_registry.lock().unwrap().start_next()
+                else {
+                    continue;
+                };
+                emit_state_change(&dispatch_app, &change);
+
+                let task = pending.task;
+                let task_name = task.name();
+                let is_cancellable = task.cancellable();
+                let is_pausable = task.pausable();
+                let task_total_steps = task.total_steps();
                 log::info!(
                     "TaskManager: Acquired worker permit for task: {}",
                     task_name
                 );
 
-                let app = manager_app.clone();
-                let tokens = manager_tokens.clone();
-                let p_tokens = manager_pause_tokens.clone();
-                let active_tasks = manager_active_tasks.clone();
-                let key_clone = client_key.clone();
+                let app = dispatch_app.clone();
+                let tokens = dispatch_tokens.clone();
+                let p_tokens = dispatch_pause_tokens.clone();
+                let registry = dispatch_registry.clone();
+                let chains = dispatch_chains.clone();
+                let chain_sender = dispatch_sender.clone();
+                let worker_semaphore = retry_semaphore.clone();
+                let key_clone = client_key;
 
                 tokio::spawn(async move {
-                    // Check if cancelled while waiting
-                    if *rx.borrow() {
-                        let manager = app.state::<NotificationManager>();
-                        if let Err(e) = manager.create(CreateNotificationInput {
-                            client_key: Some(key_clone.clone()),
-                            title: Some(task_name),
-                            description: Some("Task cancelled.".to_string()),
-                            severity: Some("warning".to_string()),
-                            notification_type: Some(NotificationType::Patient),
-                            dismissible: Some(true),
-                            persist: Some(true),
-                            silent: Some(false),
-                            actions: None,
-                            progress: None,
-                            current_step: None,
-                            total_steps: None,
-                            metadata: None,
-                            show_on_completion: None,
-                        }) {
-                            log::error!(
-                                "Failed to create task-cancel notification for {}: {}",
-                                key_clone,
-                                e
-                            );
-                        }
-
-                        // Notify frontend about failure if it's a resource download
-                        if let Some(task_id) = task.id() {
-                            if task_id.starts_with("download_") {
-                                let _ = app.emit("resource-install-error", task_id);
-                            }
-                        }
-
-                        // Cleanup tokens
-                        if is_cancellable {
-                            tokens.lock().unwrap().remove(&key_clone);
-                        }
-                        if is_pausable {
-                            p_tokens.lock().unwrap().remove(&key_clone);
-                        }
-                        active_tasks.lock().unwrap().remove(&key_clone);
-                        drop(permit);
-                        return;
-                    }
-
+                    let mut permit = Some(permit);
                     let ctx = TaskContext {
                         app_handle: app.clone(),
                         notification_id: key_clone.clone(),
-                        cancel_rx: rx,
-                        pause_rx,
-                        progress_channel,
+                        cancel_rx: pending.cancel_rx,
+                        pause_rx: pending.pause_rx,
+                        progress_channel: pending.progress_channel,
+                        percent: pending.percent,
                     };
 
                     log::info!("TaskManager: Executing task: {}", task_name);
                     // Update initial progress to 0 and starting description.
                     {
                         let manager = app.state::<NotificationManager>();
+                        let (initial_current_step, initial_total_steps) = if task_total_steps > 0 {
+                            (Some(0), Some(task_total_steps))
+                        } else {
+                            (None, None)
+                        };
                         let _ = manager.update_progress_with_description(
                             key_clone.clone(),
                             0,
@@ -394,7 +576,62 @@ impl TaskManager {
                         );
                     }
 
-                    let run_result = task.run(ctx.clone()).await;
+                    let traffic = Arc::new(TrafficCounter::default());
+                    let max_retries = task.max_retries();
+                    let mut attempt = 0;
+                    let run_result = loop {
+                        let result =
+                            with_task_traffic(traffic.clone(), task.run(ctx.clone())).await;
+                        let Err(error) = &result else {
+                            break result;
+                        };
+                        if attempt >= max_retries
+                            || *ctx.cancel_rx.borrow()
+                            || !task.is_transient_error(error)
+                        {
+                            break result;
+                        }
+
+                        attempt += 1;
+                        let delay = retry_delay(attempt);
+                        log::warn!(
+                            "TaskManager: Task {} failed ({}), retry {}/{} in {}s",
+                            task_name,
+                            error,
+                            attempt,
+                            max_retries,
+                            delay.as_secs()
+                        );
+                        let _ = app.emit(
+                            "core://task-retrying",
+                            TaskRetrying {
+                                task_id: key_clone.clone(),
+                                attempt,
+                                delay_secs: delay.as_secs(),
+                            },
+                        );
+                        ctx.update_description(format!(
+                            "Failed: {}. Retrying in {}s...",
+                            error,
+                            delay.as_secs()
+                        ));
+
+                        // Free the worker while waiting, then queue for one again.
+                        permit.take();
+                        let mut cancel_rx = ctx.cancel_rx.clone();
+                        tokio::select! {
+                            _ = tokio::time::sleep(delay) => {}
+                            _ = cancel_rx.wait_for(|cancelled| *cancelled) => {}
+                        }
+                        if *ctx.cancel_rx.borrow() {
+                            break result;
+                        }
+                        permit = worker_semaphore.clone().acquire_owned().await.ok();
+                        if permit.is_none() {
+                            break result;
+                        }
+                    };
+                    let cancel_requested = *ctx.cancel_rx.borrow();
 
                     // Cleanup tokens after run
                     if is_cancellable {
@@ -403,7 +640,17 @@ impl TaskManager {
                     if is_pausable {
                         p_tokens.lock().unwrap().remove(&key_clone);
                     }
-                    active_tasks.lock().unwrap().remove(&key_clone);
+                    let finished =
+                        registry
+                            .lock()
+                            .unwrap()
+                            .finish(&key_clone, &run_result, cancel_requested);
+                    if let Some((finished, change)) = finished {
+                        record_finished(&finished, traffic.total());
+                        emit_state_change(&app, &change);
+                    }
+                    let step = chains.lock().unwrap().finish(&key_clone, &run_result);
+                    apply_chain_step(&app, &chain_sender, &chains, step);
 
                     let manager = app.state::<NotificationManager>();
                     match run_result {
@@ -476,15 +723,6 @@ impl TaskManager {
                         }
                     }
 
-                    // Cleanup tokens
-                    if is_cancellable {
-                        tokens.lock().unwrap().remove(&key_clone);
-                    }
-                    if is_pausable {
-                        p_tokens.lock().unwrap().remove(&key_clone);
-                    }
-
-                    // TODO: The type cast 'as unknown as number' suggests a TypeScript/JavaScript pattern in Rust code. This appears to be in a setTimeout context, but the cast is unnecessary and potentially indicates confusion between JavaScript and Rust. The result of setTimeout in a browser context would be a timeout ID, but this is Rust backend code.
                     // Permit is dropped here, allowing next task to run
                     drop(permit);
                 });
@@ -498,19 +736,31 @@ impl TaskManager {
             current_limit,
             cancellation_tokens,
             pause_tokens,
-            active_tasks,
+            registry,
+            chains,
         }
     }
 
     pub fn get_active_tasks(&self) -> Vec<String> {
-        self.active_tasks
-            .lock()
-            .unwrap()
-            .values()
-            .cloned()
+        self.list_active_tasks()
+            .into_iter()
+            .map(|task| task.name)
             .collect()
     }
 
+    /// Client keys of the tasks currently queued or running.
+    pub fn get_active_task_keys(&self) -> Vec<String> {
+        self.list_active_tasks()
+            .into_iter()
+            .map(|task| task.id)
+            .collect()
+    }
+
+    /// Running tasks followed by the queue in the order it will be dispatched.
+    pub fn list_active_tasks(&self) -> Vec<TaskSummary> {
+        self.registry.lock().unwrap().snapshot()
+    }
+
     pub async fn submit(&self, task: Box<dyn Task>) -> Result<(), String> {
         self.submit_with_channel(task, None).await
     }
@@ -530,6 +780,7 @@ impl TaskManager {
             .send(QueuedTask {
                 task,
                 progress_channel,
+                chain_id: None,
             })
             .await
         {
@@ -551,25 +802,86 @@ impl TaskManager {
         }
     }
 
+    /// Run `tasks` one after another: each is queued only once its
+    /// predecessor succeeded, and a failure drops the rest of the chain and
+    /// emits `core://task-chain-failed`. Returns the chain id for
+    /// [`get_chain_status`](Self::get_chain_status).
+    #[allow(dead_code)]
+    pub async fn submit_chain(&self, tasks: Vec<Box<dyn Task>>) -> Result<String, String> {
+        let names = tasks.iter().map(|task| task.name()).collect();
+        let (chain_id, first) = self
+            .chains
+            .lock()
+            .unwrap()
+            .start(names, tasks)
+            .ok_or_else(|| "A task chain needs at least one task".to_string())?;
+        log::info!("[TaskManager::submit_chain] Submitting chain {}", chain_id);
+
+        let queued = QueuedTask {
+            task: first,
+            progress_channel: None,
+            chain_id: Some(chain_id.clone()),
+        };
+        if let Err(e) = self.sender.send(queued).await {
+            self.chains.lock().unwrap().fail(&chain_id, e.to_string());
+            return Err(e.to_string());
+        }
+        Ok(chain_id)
+    }
+
+    pub fn get_chain_status(&self, chain_id: &str) -> Option<ChainStatus> {
+        self.chains.lock().unwrap().status(chain_id)
+    }
+
+    /// Cancel a task. A queued task is removed from the queue without ever
+    /// starting; a running task is signalled and stops at its next check.
     pub fn cancel_task(&self, client_key: &str) -> Result<(), String> {
-        let tokens = self.cancellation_tokens.lock().unwrap();
-        if let Some(tx) = tokens.get(client_key) {
-            let _ = tx.send(true);
-            Ok(())
-        } else {
-            Err("Task not found or not cancellable".to_string())
+        let outcome = self.registry.lock().unwrap().request_cancel(client_key);
+        match outcome {
+            CancelOutcome::Dequeued {
+                job,
+                finished,
+                change,
+            } => {
+                self.cancellation_tokens.lock().unwrap().remove(client_key);
+                self.pause_tokens.lock().unwrap().remove(client_key);
+                notify_cancelled_before_start(
+                    &self.app_handle,
+                    client_key,
+                    finished.name.clone(),
+                    job.task.id(),
+                );
+                record_finished(&finished, 0);
+                emit_state_change(&self.app_handle, &change);
+                let step = self
+                    .chains
+                    .lock()
+                    .unwrap()
+                    .finish(client_key, &Err("Task cancelled".to_string()));
+                apply_chain_step(&self.app_handle, &self.sender, &self.chains, step);
+                Ok(())
+            }
+            CancelOutcome::Signal(change) => {
+                if let Some(tx) = self.cancellation_tokens.lock().unwrap().get(client_key) {
+                    let _ = tx.send(true);
+                }
+                emit_state_change(&self.app_handle, &change);
+                Ok(())
+            }
+            CancelOutcome::NotFound => Err("Task not found or not cancellable".to_string()),
         }
     }
 
     /// Cancel all active tasks associated with a specific instance (e.g. before deletion)
     pub fn cancel_instance_tasks(&self, instance_id: i32) {
-        let tokens = self.cancellation_tokens.lock().unwrap();
         let install_prefix = format!("install_instance_{}", instance_id);
         let download_prefix = format!("download_{}_", instance_id);
 
-        for (key, tx) in tokens.iter() {
+        for key in self.get_active_task_keys() {
             if key.starts_with(&install_prefix) || key.starts_with(&download_prefix) {
-                let _ = tx.send(true);
+                if self.cancel_task(&key).is_err() {
+                    continue;
+                }
                 log::info!(
                     "TaskManager: Sent automatic cancel signal to associated task: {}",
                     key
@@ -609,6 +921,11 @@ impl TaskManager {
             let _ = manager.update_notification_actions(client_key.to_string(), actions);
             let _ = manager.upsert_description(client_key, "Paused");
 
+            let change = self.registry.lock().unwrap().set_paused(client_key, true);
+            if let Some(change) = change {
+                emit_state_change(&self.app_handle, &change);
+            }
+
             Ok(())
         } else {
             Err("Task not found or not pausable".to_string())
@@ -646,6 +963,11 @@ impl TaskManager {
             let _ = manager.update_notification_actions(client_key.to_string(), actions);
             let _ = manager.upsert_description(client_key, "Resuming...");
 
+            let change = self.registry.lock().unwrap().set_paused(client_key, false);
+            if let Some(change) = change {
+                emit_state_change(&self.app_handle, &change);
+            }
+
             Ok(())
         } else {
             Err("Task not found or not pausable".to_string())
@@ -872,3 +1194,28 @@ impl Task for TestTask {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delay_doubles_up_to_a_minute() {
+        let delays: Vec<u64> = (1..=8).map(|n| retry_delay(n).as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 32, 60, 60]);
+        assert_eq!(retry_delay(u32::MAX), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn transient_errors_are_recognised() {
+        let task = TestTask {
+            title: "test".to_string(),
+            duration_secs: 0,
+        };
+        assert_eq!(task.max_retries(), 0);
+        assert!(task.is_transient_error("Connection reset by peer"));
+        assert!(task.is_transient_error("operation timeout"));
+        assert!(task.is_transient_error("HTTP 503 Service Unavailable"));
+        assert!(!task.is_transient_error("Invalid modpack manifest"));
+    }
+}
@@ -1,9 +1,10 @@
 use anyhow::Result;
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 use tokio::fs;
 
 use crate::metadata_cache::MetadataCache;
 use crate::tasks::manager::{BoxFuture, Task, TaskContext};
+use crate::tasks::registry::TaskPriority;
 
 pub struct GenerateManifestTask {
     force_refresh: bool,
@@ -40,6 +41,10 @@ impl Task for GenerateManifestTask {
         }
     }
 
+    fn priority(&self) -> TaskPriority {
+        TaskPriority::Low
+    }
+
     fn cancellable(&self) -> bool {
         false
     }
@@ -98,6 +103,22 @@ impl Task for GenerateManifestTask {
                 .state::<crate::utils::network::NetworkManager>()
                 .get_status();
 
+            // Snapshot the metadata we had before so the UI can be told what changed.
+            let previous = match app
+                .try_state::<MetadataCache>()
+                .and_then(|cache| cache.get())
+            {
+                Some(meta) => Some(meta),
+                None => {
+                    piston_lib::game::metadata::cache::load_cached_metadata_if_present(&data_dir)
+                        .await
+                        .unwrap_or_else(|e| {
+                            log::warn!("Failed to load cached metadata for diffing: {}", e);
+                            None
+                        })
+                }
+            };
+
             // Load or fetch metadata (ManifestCache handles freshness via ETag)
             let metadata_res = if force_refresh {
                 log::info!("Force refreshing PistonMetadata (bypassing cache)...");
@@ -137,6 +158,19 @@ impl Task for GenerateManifestTask {
                 log::warn!("MetadataCache state not found; fast-path cache disabled");
             }
 
+            if let Some(previous) = previous {
+                let changes = piston_lib::game::metadata::diff(&previous, &metadata);
+                if !changes.is_empty() {
+                    log::info!(
+                        "Metadata changed: {} new, {} removed game versions, loader updates for {}",
+                        changes.new_versions.len(),
+                        changes.removed_versions.len(),
+                        changes.loader_updates.len()
+                    );
+                    let _ = app.emit("vesta://metadata-updated", &changes);
+                }
+            }
+
             log::info!("PistonManifest generation completed successfully");
             Ok(())
         })
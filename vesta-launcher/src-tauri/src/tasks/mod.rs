@@ -1,9 +1,15 @@
+pub mod chain;
+pub mod diagnostics;
+pub(crate) mod download_stats;
+pub(crate) mod history;
 pub mod installers;
 pub mod maintenance;
 pub mod manager;
 pub mod manifest;
 pub mod modpack_export;
+pub mod network_gate;
 pub mod notification_actions;
+pub mod registry;
 pub mod resource_download;
 pub mod store;
 pub mod sync_profiles;
@@ -10,6 +10,7 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 
 pub struct ModpackExportTask {
+    pub instance_id: i32,
     pub instance_name: String,
     pub game_dir: String,
     pub output_path: String,
@@ -61,13 +62,23 @@ impl Task for ModpackExportTask {
             ctx: ctx.clone(),
             current_step: Arc::new(RwLock::new(String::new())),
             dry_run: false,
+            metrics: Default::default(),
             last_emit: Arc::new(std::sync::Mutex::new(std::time::Instant::now())),
             last_percent: std::sync::atomic::AtomicI32::new(0),
             last_step_current: std::sync::atomic::AtomicI32::new(-1),
             last_step_total: std::sync::atomic::AtomicI32::new(-1),
+            last_download_emit: Arc::new(std::sync::Mutex::new(
+                std::time::Instant::now() - std::time::Duration::from_secs(1),
+            )),
+            bytes_drive_percent: std::sync::atomic::AtomicBool::new(true),
         });
 
-        Box::pin(async move {
+        let history_details = serde_json::json!({
+            "format": format,
+            "output_path": output_path.clone(),
+        });
+
+        let operation = async move {
             // If exporting to CurseForge, we need to ensure we have numeric IDs for linking.
             // If IDs are non-numeric (e.g. from Modrinth), we try to resolve them via hash.
             if format == ModpackFormat::CurseForge {
@@ -80,31 +91,29 @@ impl Task for ModpackExportTask {
                         return Err("Export cancelled".to_string());
                     }
 
-                    if let ExportEntry::Mod {
-                        path,
-                        source_id,
-                        version_id,
-                        external_ids,
-                        ..
-                    } = entry
-                    {
-                        let is_numeric = !version_id.is_empty()
-                            && version_id.chars().all(|c| c.is_ascii_digit());
+                    if let ExportEntry::Mod(m) = entry {
+                        let is_numeric = !m.version_id.is_empty()
+                            && m.version_id.chars().all(|c| c.is_ascii_digit());
 
                         // If not numeric, try to resolve via CurseForge fingerprint
                         if !is_numeric {
-                            let full_path = PathBuf::from(&game_dir).join(&path);
+                            let full_path = PathBuf::from(&game_dir).join(&m.path);
                             if full_path.exists() {
-                                if let Ok(fp) =
-                                    crate::utils::hash::calculate_curseforge_fingerprint(&full_path)
+                                if let Ok(Some(fp)) =
+                                    crate::utils::fingerprint_cache::cached_file_hashes(
+                                        &full_path,
+                                        &[crate::utils::hash::HashAlgorithm::CurseForgeFingerprint],
+                                    )
+                                    .await
+                                    .map(|hashes| hashes.curseforge_fingerprint)
                                 {
                                     if let Ok((p, v)) = rm
                                         .get_by_hash(SourcePlatform::CurseForge, &fp.to_string())
                                         .await
                                     {
-                                        *source_id = p.id;
-                                        *version_id = v.id;
-                                        *external_ids = p.external_ids;
+                                        m.source_id = p.id;
+                                        m.version_id = v.id;
+                                        m.external_ids = p.external_ids;
                                         resolved_count += 1;
                                     }
                                 }
@@ -118,6 +127,49 @@ impl Task for ModpackExportTask {
                 );
             }
 
+            // Platform metadata only records SHA-1, so take SHA-512 from the
+            // fingerprint cache rather than hashing every mod during the export.
+            if format == ModpackFormat::Modrinth {
+                reporter.set_message("Collecting file hashes...");
+                for entry in spec.entries.iter_mut() {
+                    if reporter.is_cancelled() {
+                        return Err("Export cancelled".to_string());
+                    }
+
+                    let ExportEntry::Mod(m) = entry else {
+                        continue;
+                    };
+                    if m.platform.is_none() || m.sha512.is_some() {
+                        continue;
+                    }
+
+                    let full_path = PathBuf::from(&game_dir).join(&m.path);
+                    let Ok(hashes) = crate::utils::fingerprint_cache::cached_file_hashes(
+                        &full_path,
+                        &[
+                            crate::utils::hash::HashAlgorithm::Sha1,
+                            crate::utils::hash::HashAlgorithm::Sha512,
+                        ],
+                    )
+                    .await
+                    else {
+                        continue;
+                    };
+                    // The file changed since it was installed; describe
+                    // what is actually on disk.
+                    if !m.sha1.as_deref().is_some_and(|recorded| {
+                        hashes
+                            .sha1
+                            .as_deref()
+                            .is_some_and(|local| local.eq_ignore_ascii_case(recorded))
+                    }) {
+                        m.sha1 = hashes.sha1;
+                        m.file_size = full_path.metadata().ok().map(|meta| meta.len());
+                    }
+                    m.sha512 = hashes.sha512;
+                }
+            }
+
             // Run the export in a blocking thread since it's a CPU/IO intensive sync operation
             tokio::task::spawn_blocking(move || {
                 export_modpack(game_dir, spec, output_path, format, reporter.as_ref())
@@ -125,6 +177,13 @@ impl Task for ModpackExportTask {
             })
             .await
             .map_err(|e| e.to_string())?
-        })
+        };
+
+        Box::pin(crate::instance::history::track(
+            self.instance_id,
+            crate::instance::history::OperationKind::Export,
+            history_details,
+            operation,
+        ))
     }
 }
@@ -0,0 +1,301 @@
+//! Network checks install tasks run before they start downloading.
+//!
+//! A task picked up while the launcher is offline waits for the network
+//! instead of failing. A large download on a metered connection follows
+//! `allow_large_downloads_on_metered`: it goes ahead, is refused, or is held
+//! paused behind a confirm notification until the user decides.
+
+use crate::notifications::manager::{ActionHandler, NotificationManager};
+use crate::notifications::models::{CreateNotificationInput, NotificationAction, NotificationType};
+use crate::tasks::manager::{TaskContext, TaskManager};
+use crate::utils::network::{MeteredDownloadPolicy, NetworkManager, NetworkStatus};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::{AppHandle, Manager};
+use tokio::sync::oneshot;
+
+/// Downloads at least this large count as large on a metered connection.
+pub const LARGE_DOWNLOAD_THRESHOLD_BYTES: u64 = 200 * 1024 * 1024;
+
+/// Tasks waiting for a metered-download decision, by task client key.
+static PENDING_DECISIONS: OnceLock<Mutex<HashMap<String, oneshot::Sender<bool>>>> = OnceLock::new();
+
+fn pending_decisions() -> std::sync::MutexGuard<'static, HashMap<String, oneshot::Sender<bool>>> {
+    PENDING_DECISIONS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeteredDecision {
+    Proceed,
+    Refuse,
+    Ask,
+}
+
+/// Only a known-metered connection and a download over the threshold
+/// involve the policy.
+pub fn metered_decision(
+    policy: MeteredDownloadPolicy,
+    metered: Option<bool>,
+    estimated_bytes: u64,
+) -> MeteredDecision {
+    if metered != Some(true) || estimated_bytes < LARGE_DOWNLOAD_THRESHOLD_BYTES {
+        return MeteredDecision::Proceed;
+    }
+    match policy {
+        MeteredDownloadPolicy::Always => MeteredDecision::Proceed,
+        MeteredDownloadPolicy::Never => MeteredDecision::Refuse,
+        MeteredDownloadPolicy::Ask => MeteredDecision::Ask,
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    const MIB: f64 = 1024.0 * 1024.0;
+    let mib = bytes as f64 / MIB;
+    if mib >= 1024.0 {
+        format!("{:.1} GB", mib / 1024.0)
+    } else {
+        format!("{:.0} MB", mib)
+    }
+}
+
+fn confirm_client_key(task_key: &str) -> String {
+    format!("metered_confirm_{}", task_key)
+}
+
+/// Wait until the network monitor reports the launcher online. The task's
+/// notification reads "Waiting for network..." meanwhile.
+pub async fn wait_for_network(ctx: &TaskContext) -> Result<(), String> {
+    let mut status_rx = ctx.app_handle.state::<NetworkManager>().subscribe();
+    if *status_rx.borrow_and_update() == NetworkStatus::Online {
+        return Ok(());
+    }
+
+    log::info!(
+        "[NetworkGate] {} is waiting for the network",
+        ctx.notification_id
+    );
+    ctx.update_description("Waiting for network...".to_string());
+    let mut cancel_rx = ctx.cancel_rx.clone();
+    loop {
+        tokio::select! {
+            changed = status_rx.changed() => {
+                changed.map_err(|_| "Network monitor stopped".to_string())?;
+                if *status_rx.borrow_and_update() == NetworkStatus::Online {
+                    ctx.update_description("Network available, continuing...".to_string());
+                    return Ok(());
+                }
+            }
+            _ = cancel_rx.changed() => {
+                if *cancel_rx.borrow() {
+                    return Err("Installation cancelled".to_string());
+                }
+            }
+        }
+    }
+}
+
+/// Apply the metered-download policy to a task about to download
+/// `estimated_bytes`. Under "ask" the task is paused and a confirm
+/// notification is shown; resuming the task also counts as confirming.
+pub async fn confirm_metered_download(
+    ctx: &TaskContext,
+    task_label: &str,
+    estimated_bytes: u64,
+) -> Result<(), String> {
+    let policy = crate::utils::config::get_app_config()
+        .map(|config| MeteredDownloadPolicy::from_config(&config.allow_large_downloads_on_metered))
+        .unwrap_or(MeteredDownloadPolicy::Ask);
+    let metered = ctx.app_handle.state::<NetworkManager>().is_metered();
+
+    match metered_decision(policy, metered, estimated_bytes) {
+        MeteredDecision::Proceed => return Ok(()),
+        MeteredDecision::Refuse => {
+            return Err(format!(
+                "{} needs about {} and large downloads are disabled on metered connections",
+                task_label,
+                format_size(estimated_bytes)
+            ))
+        }
+        MeteredDecision::Ask => {}
+    }
+
+    let task_key = ctx.notification_id.clone();
+    let (decision_tx, decision_rx) = oneshot::channel();
+    pending_decisions().insert(task_key.clone(), decision_tx);
+
+    let task_manager = ctx.app_handle.state::<TaskManager>();
+    let paused = task_manager.pause_task(&task_key).is_ok();
+    if !paused {
+        ctx.update_description("Waiting for confirmation...".to_string());
+    }
+
+    let notifications = ctx.app_handle.state::<NotificationManager>();
+    let payload = serde_json::json!({ "task": task_key });
+    let actions = vec![
+        NotificationAction {
+            action_id: "allow_metered_download".to_string(),
+            label: "Download".to_string(),
+            action_type: "primary".to_string(),
+            payload: Some(payload.clone()),
+        },
+        NotificationAction {
+            action_id: "deny_metered_download".to_string(),
+            label: "Cancel".to_string(),
+            action_type: "secondary".to_string(),
+            payload: Some(payload),
+        },
+    ];
+    if let Err(e) = notifications.create(CreateNotificationInput {
+        client_key: Some(confirm_client_key(&task_key)),
+        title: Some("Large download on a metered connection".to_string()),
+        description: Some(format!(
+            "{} needs to download about {}. Download now?",
+            task_label,
+            format_size(estimated_bytes)
+        )),
+        severity: Some("warning".to_string()),
+        notification_type: Some(NotificationType::Patient),
+        dismissible: Some(false),
+        persist: Some(true),
+        silent: Some(false),
+        actions: Some(serde_json::to_string(&actions).unwrap_or_default()),
+        progress: None,
+        current_step: None,
+        total_steps: None,
+        metadata: None,
+        show_on_completion: None,
+    }) {
+        log::error!(
+            "Failed to create metered-download notification for {}: {}",
+            task_key,
+            e
+        );
+    }
+
+    let mut cancel_rx = ctx.cancel_rx.clone();
+    let mut pause_rx = ctx.pause_rx.clone();
+    let mut decision_rx = decision_rx;
+    let allowed = loop {
+        tokio::select! {
+            decision = &mut decision_rx => break decision.unwrap_or(false),
+            _ = cancel_rx.changed() => {
+                if *cancel_rx.borrow() {
+                    break false;
+                }
+            }
+            changed = pause_rx.changed(), if paused => {
+                if changed.is_err() || !*pause_rx.borrow() {
+                    break true;
+                }
+            }
+        }
+    };
+
+    pending_decisions().remove(&task_key);
+    let _ = notifications.delete(confirm_client_key(&task_key));
+    if !allowed {
+        return Err("Installation cancelled".to_string());
+    }
+    if paused && *ctx.pause_rx.borrow() {
+        let _ = task_manager.resume_task(&task_key);
+    }
+    log::info!(
+        "[NetworkGate] Metered download of {} confirmed for {}",
+        format_size(estimated_bytes),
+        task_key
+    );
+    Ok(())
+}
+
+fn task_key_from_payload(payload: Option<serde_json::Value>) -> Result<String> {
+    payload
+        .as_ref()
+        .and_then(|p| p.get("task"))
+        .and_then(|task| task.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| anyhow::anyhow!("Missing task in metered download payload"))
+}
+
+struct MeteredDownloadDecision {
+    allow: bool,
+}
+
+impl ActionHandler for MeteredDownloadDecision {
+    fn handle(
+        &self,
+        _app_handle: &AppHandle,
+        _client_key: Option<String>,
+        payload: Option<serde_json::Value>,
+    ) -> Result<()> {
+        let task_key = task_key_from_payload(payload)?;
+        let sender = pending_decisions()
+            .remove(&task_key)
+            .ok_or_else(|| anyhow::anyhow!("No pending download decision for {}", task_key))?;
+        let _ = sender.send(self.allow);
+        Ok(())
+    }
+}
+
+pub fn register(manager: &NotificationManager) {
+    manager.register_action(
+        "allow_metered_download",
+        Arc::new(MeteredDownloadDecision { allow: true }),
+    );
+    manager.register_action(
+        "deny_metered_download",
+        Arc::new(MeteredDownloadDecision { allow: false }),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LARGE: u64 = LARGE_DOWNLOAD_THRESHOLD_BYTES;
+
+    #[test]
+    fn policy_only_applies_to_large_metered_downloads() {
+        use MeteredDownloadPolicy::*;
+
+        assert_eq!(
+            metered_decision(Never, Some(false), LARGE),
+            MeteredDecision::Proceed
+        );
+        assert_eq!(
+            metered_decision(Never, None, LARGE),
+            MeteredDecision::Proceed
+        );
+        assert_eq!(
+            metered_decision(Never, Some(true), LARGE - 1),
+            MeteredDecision::Proceed
+        );
+        assert_eq!(
+            metered_decision(Never, Some(true), LARGE),
+            MeteredDecision::Refuse
+        );
+        assert_eq!(
+            metered_decision(Ask, Some(true), LARGE),
+            MeteredDecision::Ask
+        );
+        assert_eq!(
+            metered_decision(Always, Some(true), LARGE),
+            MeteredDecision::Proceed
+        );
+    }
+
+    #[test]
+    fn decisions_name_their_task() {
+        assert_eq!(
+            task_key_from_payload(Some(serde_json::json!({ "task": "install_instance_4" })))
+                .unwrap(),
+            "install_instance_4"
+        );
+        assert!(task_key_from_payload(None).is_err());
+        assert_eq!(format_size(300 * 1024 * 1024), "300 MB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+}
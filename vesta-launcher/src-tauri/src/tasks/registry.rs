@@ -0,0 +1,458 @@
+//! Bookkeeping for tasks known to the [`TaskManager`](super::manager::TaskManager).
+//!
+//! The registry owns the pending queue and the state of every queued or
+//! running task. Every method that changes a task's state returns the
+//! [`TaskStateChange`] to emit, so the manager stays the only place that
+//! talks to Tauri and the state machine can be tested on its own.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl TaskPriority {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TaskPriority::Low => "low",
+            TaskPriority::Normal => "normal",
+            TaskPriority::High => "high",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    /// Waiting for a free worker.
+    Queued,
+    Running,
+    Paused,
+    /// Cancel was requested; waiting for the running task to stop.
+    Waiting,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl TaskStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TaskStatus::Queued => "queued",
+            TaskStatus::Running => "running",
+            TaskStatus::Paused => "paused",
+            TaskStatus::Waiting => "waiting",
+            TaskStatus::Completed => "completed",
+            TaskStatus::Failed => "failed",
+            TaskStatus::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// Payload of `core://task-state-changed`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskStateChange {
+    pub id: String,
+    pub name: String,
+    pub state: TaskStatus,
+    pub previous: Option<TaskStatus>,
+    pub error: Option<String>,
+}
+
+/// A queued or running task as returned by `list_active_tasks`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSummary {
+    pub id: String,
+    pub name: String,
+    pub state: TaskStatus,
+    pub priority: TaskPriority,
+    /// Last reported progress, `None` while indeterminate.
+    pub percent: Option<i32>,
+    pub queued_at: String,
+    pub started_at: Option<String>,
+    pub cancellable: bool,
+}
+
+/// A task that left the registry, ready to be written to the history table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FinishedTask {
+    pub id: String,
+    pub name: String,
+    pub state: TaskStatus,
+    pub priority: TaskPriority,
+    pub queued_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: String,
+    pub duration_ms: Option<i64>,
+    pub error: Option<String>,
+}
+
+pub struct TaskInfo {
+    pub id: String,
+    pub name: String,
+    pub priority: TaskPriority,
+    pub cancellable: bool,
+    /// Shared with the task's `TaskContext`, which stores reported progress.
+    pub percent: Arc<AtomicI32>,
+}
+
+struct Entry {
+    info: TaskInfo,
+    state: TaskStatus,
+    queued_at: String,
+    started_at: Option<String>,
+}
+
+impl Entry {
+    fn summary(&self) -> TaskSummary {
+        let percent = self.info.percent.load(Ordering::Relaxed);
+        TaskSummary {
+            id: self.info.id.clone(),
+            name: self.info.name.clone(),
+            state: self.state,
+            priority: self.info.priority,
+            percent: (percent >= 0).then_some(percent),
+            queued_at: self.queued_at.clone(),
+            started_at: self.started_at.clone(),
+            cancellable: self.info.cancellable,
+        }
+    }
+
+    fn change(&mut self, state: TaskStatus, error: Option<String>) -> TaskStateChange {
+        let previous = std::mem::replace(&mut self.state, state);
+        TaskStateChange {
+            id: self.info.id.clone(),
+            name: self.info.name.clone(),
+            state,
+            previous: Some(previous),
+            error,
+        }
+    }
+
+    fn into_finished(self, error: Option<String>) -> FinishedTask {
+        let finished_at = chrono::Utc::now();
+        let duration_ms = self
+            .started_at
+            .as_deref()
+            .and_then(|started| chrono::DateTime::parse_from_rfc3339(started).ok())
+            .map(|started| {
+                finished_at
+                    .signed_duration_since(started)
+                    .num_milliseconds()
+                    .max(0)
+            });
+        FinishedTask {
+            id: self.info.id,
+            name: self.info.name,
+            state: self.state,
+            priority: self.info.priority,
+            queued_at: self.queued_at,
+            started_at: self.started_at,
+            finished_at: finished_at.to_rfc3339(),
+            duration_ms,
+            error,
+        }
+    }
+}
+
+pub enum CancelOutcome<J> {
+    /// The task had not started; it was removed from the queue.
+    Dequeued {
+        job: J,
+        finished: FinishedTask,
+        change: TaskStateChange,
+    },
+    /// The task is running; the caller must signal its cancel token.
+    Signal(TaskStateChange),
+    NotFound,
+}
+
+/// Queued and running tasks plus the pending queue. `J` is whatever the
+/// manager needs to start a task later.
+pub struct TaskRegistry<J> {
+    entries: HashMap<String, Entry>,
+    queue: Vec<(u64, String, J)>,
+    next_seq: u64,
+}
+
+impl<J> Default for TaskRegistry<J> {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::new(),
+            queue: Vec::new(),
+            next_seq: 0,
+        }
+    }
+}
+
+impl<J> TaskRegistry<J> {
+    pub fn contains(&self, id: &str) -> bool {
+        self.entries.contains_key(id)
+    }
+
+    pub fn has_queued(&self) -> bool {
+        !self.queue.is_empty()
+    }
+
+    pub fn enqueue(&mut self, info: TaskInfo, job: J) -> TaskStateChange {
+        let change = TaskStateChange {
+            id: info.id.clone(),
+            name: info.name.clone(),
+            state: TaskStatus::Queued,
+            previous: None,
+            error: None,
+        };
+        self.queue.push((self.next_seq, info.id.clone(), job));
+        self.next_seq += 1;
+        self.entries.insert(
+            info.id.clone(),
+            Entry {
+                info,
+                state: TaskStatus::Queued,
+                queued_at: chrono::Utc::now().to_rfc3339(),
+                started_at: None,
+            },
+        );
+        change
+    }
+
+    /// Take the highest-priority queued task (oldest first within a
+    /// priority) and mark it running.
+    pub fn start_next(&mut self) -> Option<(String, J, TaskStateChange)> {
+        let position = self
+            .queue
+            .iter()
+            .enumerate()
+            .max_by(|(_, (seq_a, key_a, _)), (_, (seq_b, key_b, _))| {
+                let priority = |key: &String| self.entries.get(key).map(|e| e.info.priority);
+                priority(key_a).cmp(&priority(key_b)).then(seq_b.cmp(seq_a))
+            })
+            .map(|(position, _)| position)?;
+        let (_, id, job) = self.queue.remove(position);
+        let entry = self.entries.get_mut(&id)?;
+        entry.started_at = Some(chrono::Utc::now().to_rfc3339());
+        let change = entry.change(TaskStatus::Running, None);
+        Some((id, job, change))
+    }
+
+    pub fn set_paused(&mut self, id: &str, paused: bool) -> Option<TaskStateChange> {
+        let entry = self.entries.get_mut(id)?;
+        let next = match (entry.state, paused) {
+            (TaskStatus::Running, true) => TaskStatus::Paused,
+            (TaskStatus::Paused, false) => TaskStatus::Running,
+            _ => return None,
+        };
+        Some(entry.change(next, None))
+    }
+
+    pub fn request_cancel(&mut self, id: &str) -> CancelOutcome<J> {
+        if !self
+            .entries
+            .get(id)
+            .is_some_and(|entry| entry.info.cancellable)
+        {
+            return CancelOutcome::NotFound;
+        }
+        if let Some(position) = self.queue.iter().position(|(_, key, _)| key == id) {
+            let (_, _, job) = self.queue.remove(position);
+            let Some(mut entry) = self.entries.remove(id) else {
+                return CancelOutcome::NotFound;
+            };
+            let change = entry.change(TaskStatus::Cancelled, None);
+            return CancelOutcome::Dequeued {
+                job,
+                finished: entry.into_finished(None),
+                change,
+            };
+        }
+        match self.entries.get_mut(id) {
+            Some(entry) => CancelOutcome::Signal(entry.change(TaskStatus::Waiting, None)),
+            None => CancelOutcome::NotFound,
+        }
+    }
+
+    /// Remove a started task once `run` returned. A task that was asked to
+    /// cancel counts as cancelled whatever it returned.
+    pub fn finish(
+        &mut self,
+        id: &str,
+        result: &Result<(), String>,
+        cancel_requested: bool,
+    ) -> Option<(FinishedTask, TaskStateChange)> {
+        let mut entry = self.entries.remove(id)?;
+        let (state, error) = match result {
+            _ if cancel_requested => (TaskStatus::Cancelled, result.clone().err()),
+            Ok(()) => (TaskStatus::Completed, None),
+            Err(e) => (TaskStatus::Failed, Some(e.clone())),
+        };
+        let change = entry.change(state, error.clone());
+        Some((entry.into_finished(error), change))
+    }
+
+    /// Running tasks first, then the queue in dispatch order.
+    pub fn snapshot(&self) -> Vec<TaskSummary> {
+        let mut running: Vec<TaskSummary> = self
+            .entries
+            .values()
+            .filter(|entry| entry.state != TaskStatus::Queued)
+            .map(Entry::summary)
+            .collect();
+        running.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+
+        let mut queued: Vec<(TaskPriority, u64, TaskSummary)> = self
+            .queue
+            .iter()
+            .filter_map(|(seq, key, _)| {
+                let entry = self.entries.get(key)?;
+                Some((entry.info.priority, *seq, entry.summary()))
+            })
+            .collect();
+        queued.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        running
+            .into_iter()
+            .chain(queued.into_iter().map(|(_, _, summary)| summary))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(id: &str, priority: TaskPriority) -> TaskInfo {
+        TaskInfo {
+            id: id.to_string(),
+            name: format!("Task {}", id),
+            priority,
+            cancellable: true,
+            percent: Arc::new(AtomicI32::new(-1)),
+        }
+    }
+
+    #[test]
+    fn cancelled_queued_task_leaves_queue_without_starting() {
+        let mut registry = TaskRegistry::default();
+        registry.enqueue(info("a", TaskPriority::Normal), "job-a");
+        let queued = registry.enqueue(info("b", TaskPriority::Normal), "job-b");
+        assert_eq!(queued.state, TaskStatus::Queued);
+
+        let CancelOutcome::Dequeued {
+            job,
+            finished,
+            change,
+        } = registry.request_cancel("b")
+        else {
+            panic!("queued task should be dequeued");
+        };
+        assert_eq!(job, "job-b");
+        assert_eq!(change.previous, Some(TaskStatus::Queued));
+        assert_eq!(change.state, TaskStatus::Cancelled);
+        assert_eq!(finished.state, TaskStatus::Cancelled);
+        assert_eq!(finished.started_at, None);
+        assert_eq!(finished.duration_ms, None);
+
+        assert!(!registry.contains("b"));
+        let (id, _, _) = registry.start_next().unwrap();
+        assert_eq!(id, "a");
+        assert!(registry.start_next().is_none());
+    }
+
+    #[test]
+    fn non_cancellable_task_stays_queued() {
+        let mut registry = TaskRegistry::default();
+        let mut task = info("a", TaskPriority::Normal);
+        task.cancellable = false;
+        registry.enqueue(task, ());
+
+        assert!(matches!(
+            registry.request_cancel("a"),
+            CancelOutcome::NotFound
+        ));
+        assert!(registry.has_queued());
+    }
+
+    #[test]
+    fn failed_running_task_records_error_and_duration() {
+        let mut registry = TaskRegistry::default();
+        registry.enqueue(info("install", TaskPriority::Normal), ());
+        let (_, _, started) = registry.start_next().unwrap();
+        assert_eq!(started.previous, Some(TaskStatus::Queued));
+        assert_eq!(started.state, TaskStatus::Running);
+
+        let paused = registry.set_paused("install", true).unwrap();
+        assert_eq!(paused.state, TaskStatus::Paused);
+        let resumed = registry.set_paused("install", false).unwrap();
+        assert_eq!(resumed.state, TaskStatus::Running);
+
+        let (finished, change) = registry
+            .finish("install", &Err("Connection reset".to_string()), false)
+            .unwrap();
+        assert_eq!(change.previous, Some(TaskStatus::Running));
+        assert_eq!(change.state, TaskStatus::Failed);
+        assert_eq!(change.error.as_deref(), Some("Connection reset"));
+        assert_eq!(finished.state, TaskStatus::Failed);
+        assert!(finished.started_at.is_some());
+        assert!(finished.duration_ms.is_some());
+        assert!(registry.snapshot().is_empty());
+    }
+
+    #[test]
+    fn cancelling_running_task_waits_for_it_to_stop() {
+        let mut registry = TaskRegistry::default();
+        registry.enqueue(info("a", TaskPriority::Normal), ());
+        registry.start_next().unwrap();
+
+        let CancelOutcome::Signal(change) = registry.request_cancel("a") else {
+            panic!("running task should be signalled");
+        };
+        assert_eq!(change.state, TaskStatus::Waiting);
+        assert_eq!(registry.snapshot()[0].state, TaskStatus::Waiting);
+
+        let (finished, _) = registry.finish("a", &Ok(()), true).unwrap();
+        assert_eq!(finished.state, TaskStatus::Cancelled);
+        assert!(matches!(
+            registry.request_cancel("a"),
+            CancelOutcome::NotFound
+        ));
+    }
+
+    #[test]
+    fn higher_priority_starts_first_then_fifo() {
+        let mut registry = TaskRegistry::default();
+        registry.enqueue(info("low", TaskPriority::Low), ());
+        registry.enqueue(info("first", TaskPriority::Normal), ());
+        registry.enqueue(info("urgent", TaskPriority::High), ());
+        registry.enqueue(info("second", TaskPriority::Normal), ());
+
+        let listed: Vec<String> = registry.snapshot().into_iter().map(|t| t.id).collect();
+        assert_eq!(listed, ["urgent", "first", "second", "low"]);
+
+        let order: Vec<String> =
+            std::iter::from_fn(|| registry.start_next().map(|(id, _, _)| id)).collect();
+        assert_eq!(order, ["urgent", "first", "second", "low"]);
+    }
+
+    #[test]
+    fn snapshot_reports_progress_from_context() {
+        let mut registry = TaskRegistry::default();
+        let task = info("a", TaskPriority::Normal);
+        let percent = task.percent.clone();
+        registry.enqueue(task, ());
+        assert_eq!(registry.snapshot()[0].percent, None);
+
+        registry.start_next().unwrap();
+        percent.store(42, Ordering::Relaxed);
+        let summary = &registry.snapshot()[0];
+        assert_eq!(summary.state, TaskStatus::Running);
+        assert_eq!(summary.percent, Some(42));
+    }
+}
@@ -2,6 +2,7 @@ use crate::models::resource::{ResourceType, ResourceVersion, SourcePlatform};
 use crate::notifications::models::PROGRESS_INDETERMINATE;
 use crate::schema::instance::dsl as instances_dsl;
 use crate::tasks::manager::{Task, TaskContext};
+use crate::tasks::registry::TaskPriority;
 use crate::utils::db::get_vesta_conn;
 use crate::utils::instance_helpers::normalize_path;
 use diesel::prelude::*;
@@ -74,6 +75,11 @@ impl Task for ResourceDownloadTask {
         true
     }
 
+    fn priority(&self) -> TaskPriority {
+        // Single-file installs are quick; don't park them behind a full instance install.
+        TaskPriority::High
+    }
+
     fn show_completion_notification(&self) -> bool {
         true
     }
@@ -127,16 +133,9 @@ impl Task for ResourceDownloadTask {
             let instance_path = PathBuf::from(instance_path_str);
 
             // 2. Determine target directory
-            let target_dir_name = match resource_type {
-                ResourceType::Mod => "mods",
-                ResourceType::ResourcePack => "resourcepacks",
-                ResourceType::Shader => "shaderpacks",
-                ResourceType::DataPack => "datapacks",
-                ResourceType::World => "saves",
-                ResourceType::Modpack => {
-                    return Err("Modpack installation not supported yet".to_string())
-                }
-            };
+            let target_dir_name = resource_type
+                .install_dir()
+                .ok_or_else(|| "Modpack installation not supported yet".to_string())?;
 
             let target_dir = instance_path.join(target_dir_name);
             if !target_dir.exists() {
@@ -208,6 +207,10 @@ impl Task for ResourceDownloadTask {
                 file.write_all(&chunk).await.map_err(|e| e.to_string())?;
                 hasher.update(&chunk);
                 downloaded += chunk.len() as u64;
+                piston_lib::game::installer::core::traffic::record_traffic(
+                    piston_lib::game::installer::core::traffic::TrafficCategory::Mods,
+                    chunk.len() as u64,
+                );
 
                 let now = std::time::Instant::now();
                 if now.duration_since(last_update).as_millis() > 250 {
@@ -324,14 +327,7 @@ impl Task for ResourceDownloadTask {
             let project_name = project_name.clone();
             let version = version.clone();
             let final_path = final_path.clone();
-            let resource_type_name = match resource_type {
-                ResourceType::Mod => "mod",
-                ResourceType::ResourcePack => "resourcepack",
-                ResourceType::Shader => "shader",
-                ResourceType::DataPack => "datapack",
-                ResourceType::Modpack => "modpack",
-                ResourceType::World => "world",
-            };
+            let resource_type_name = resource_type.ledger_name();
             tauri::async_runtime::spawn_blocking(move || {
                 crate::resources::ledger::record_download(
                     instance_id,
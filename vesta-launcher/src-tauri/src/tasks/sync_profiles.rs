@@ -4,6 +4,7 @@ use crate::models::skin_history::NewAccountSkinHistory;
 use crate::schema::vesta::account;
 use crate::schema::vesta::account_skin_history;
 use crate::tasks::manager::{BoxFuture, Task, TaskContext};
+use crate::tasks::registry::TaskPriority;
 use crate::utils::cape_cache::get_or_cache_cape_bytes;
 use crate::utils::db::get_vesta_conn;
 use crate::utils::texture::compute_texture_key;
@@ -273,6 +274,10 @@ pub async fn sync_account_profile_data(
 }
 
 impl Task for SyncAccountProfilesTask {
+    fn priority(&self) -> TaskPriority {
+        TaskPriority::Low
+    }
+
     fn run(&self, ctx: TaskContext) -> BoxFuture<'static, Result<(), String>> {
         Box::pin(async move {
             let mut conn = get_vesta_conn().map_err(|e| e.to_string())?;
@@ -48,7 +48,9 @@ impl Task for UpdateModpackTask {
         let new_version_id = self.new_version_id.clone();
         let app_handle = ctx.app_handle.clone();
 
-        Box::pin(async move {
+        let history_details = serde_json::json!({ "version_id": new_version_id.clone() });
+
+        let operation = async move {
             // ─── Load instance ───────────────────────────────────────────
             let mut conn =
                 crate::utils::db::get_vesta_conn().map_err(|e| format!("DB error: {}", e))?;
@@ -168,6 +170,13 @@ impl Task for UpdateModpackTask {
             );
 
             Ok(())
-        })
+        };
+
+        Box::pin(crate::instance::history::track(
+            instance_id,
+            crate::instance::history::OperationKind::ModpackUpdate,
+            history_details,
+            operation,
+        ))
     }
 }
@@ -13,7 +13,12 @@
 //!
 //! ## Adding a New Config Field
 //!
-//! 3 steps needed:
+//! `app_config` is kept within diesel's 64-column table limit and is full. New
+//! settings are stored as JSON values in the `app_setting` key/value table: add the
+//! field to `AppConfig` with `#[diesel(skip_insertion, skip_update)]`, list it in
+//! `APP_SETTING_FIELDS` and give it a default. No migration is needed.
+//!
+//! The steps below describe the original column-backed fields:
 //!
 //! 1. **Add field to `AppConfig` struct**:
 //!    ```rust
@@ -86,6 +91,8 @@
 //! - **Zero Boilerplate**: No manual SQL for CRUD operations
 //! - **Frontend Ready**: Automatic JSON serialization via serde
 
+pub mod theme_presets;
+
 use crate::schema::config::app_config;
 use crate::utils::db::{get_config_conn, get_vesta_conn};
 use diesel::prelude::*;
@@ -96,7 +103,7 @@ use tauri::Emitter;
 /// Main application configuration struct
 ///
 /// This struct is the single source of truth for the app_config table schema.
-#[derive(Selectable, Insertable, AsChangeset, Serialize, Deserialize, Clone, Debug)]
+#[derive(Insertable, AsChangeset, Serialize, Deserialize, Clone, Debug)]
 #[diesel(table_name = app_config)]
 #[diesel(check_for_backend(diesel::sqlite::Sqlite))]
 pub struct AppConfig {
@@ -123,7 +130,7 @@ pub struct AppConfig {
     pub active_account_uuid: Option<String>,
 
     // Theme system fields
-    pub theme_id: String,   // Current theme preset ID (e.g., "vesta", "solar")
+    pub theme_id: String, // theme_preset row ID (e.g., "vesta"), or an imported saved_themes ID
     pub theme_mode: String, // "template" or "advanced"
     pub theme_primary_hue: i32, // User-customized primary hue
     pub theme_primary_sat: Option<i32>, // Advanced mode: primary saturation
@@ -167,6 +174,44 @@ pub struct AppConfig {
     pub proxy_url: Option<String>,
     pub proxy_apply_to_games: bool,
     pub artifact_cache_max_bytes: i64,
+
+    // Stored in app_setting; see `APP_SETTING_FIELDS`.
+    #[diesel(skip_insertion, skip_update)]
+    pub instance_trash_retention_days: i32,
+    #[diesel(skip_insertion, skip_update)]
+    pub game_launch_pre_command: Option<String>,
+    #[diesel(skip_insertion, skip_update)]
+    pub game_launch_post_command: Option<String>,
+    #[diesel(skip_insertion, skip_update)]
+    pub update_channel: String,
+    #[diesel(skip_insertion, skip_update)]
+    pub allow_config_edits_while_running: bool,
+    #[diesel(skip_insertion, skip_update)]
+    pub cache_compression_enabled: bool,
+    /// Spot-check client jar and library hashes before each launch.
+    #[diesel(skip_insertion, skip_update)]
+    pub verify_on_launch: bool,
+    /// List snapshots alongside releases in version pickers.
+    #[diesel(skip_insertion, skip_update)]
+    pub show_snapshots: bool,
+    /// "ask", "never" or "always"; see `MeteredDownloadPolicy`.
+    #[diesel(skip_insertion, skip_update)]
+    pub allow_large_downloads_on_metered: String,
+    /// Refuse to launch when duplicate or incompatible mods are detected.
+    #[diesel(skip_insertion, skip_update)]
+    pub strict_mod_conflicts: bool,
+    /// Parallel asset object downloads during installs.
+    #[diesel(skip_insertion, skip_update)]
+    pub asset_concurrency: i32,
+    /// Parallel library and native downloads during installs.
+    #[diesel(skip_insertion, skip_update)]
+    pub library_concurrency: i32,
+    /// Parallel mod downloads from Modrinth/CurseForge, e.g. for modpacks.
+    #[diesel(skip_insertion, skip_update)]
+    pub mod_download_concurrency: i32,
+    /// "unset", "accepted" or "declined"; see `UsageStatsConsent`.
+    #[diesel(skip_insertion, skip_update)]
+    pub usage_stats_consent: String,
 }
 
 impl diesel::Queryable<crate::schema::config::app_config::SqlType, diesel::sqlite::Sqlite>
@@ -300,6 +345,9 @@ impl diesel::Queryable<crate::schema::config::app_config::SqlType, diesel::sqlit
             proxy_url: row.52,
             proxy_apply_to_games: row.53,
             artifact_cache_max_bytes: row.54,
+
+            // app_setting fields are filled in by `load_app_settings`.
+            ..AppConfig::default()
         })
     }
 }
@@ -348,6 +396,24 @@ impl Default for AppConfig {
             proxy_url: None,
             proxy_apply_to_games: false,
             artifact_cache_max_bytes: crate::utils::storage::DEFAULT_ARTIFACT_CACHE_MAX_BYTES,
+            instance_trash_retention_days: crate::instance::trash::DEFAULT_TRASH_RETENTION_DAYS,
+            game_launch_pre_command: None,
+            game_launch_post_command: None,
+            update_channel: "stable".to_string(),
+            allow_config_edits_while_running: false,
+            cache_compression_enabled: false,
+            verify_on_launch: false,
+            show_snapshots: false,
+            allow_large_downloads_on_metered: crate::utils::network::MeteredDownloadPolicy::Ask
+                .as_str()
+                .to_string(),
+            strict_mod_conflicts: false,
+            asset_concurrency: 16,
+            library_concurrency: 8,
+            mod_download_concurrency: 4,
+            usage_stats_consent: crate::utils::privacy::UsageStatsConsent::Unset
+                .as_str()
+                .to_string(),
 
             setup_completed: false,
             setup_step: 0,
@@ -355,7 +421,7 @@ impl Default for AppConfig {
 
             use_dedicated_gpu: true,
             telemetry_enabled: true,
-            discord_presence_enabled: true,
+            discord_presence_enabled: false,
             auto_install_dependencies: true,
             default_width: 854,
             default_height: 480,
@@ -935,9 +1001,12 @@ pub fn init_config_db() -> Result<(), anyhow::Error> {
             crate::utils::memory_policy::dynamic_preferred_max_memory_mb(system_ram_mb);
         normalize_memory_config(&mut default_config);
         normalize_artifact_cache_config(&mut default_config);
-        diesel::insert_into(app_config)
-            .values(&default_config)
-            .execute(&mut conn)?;
+        conn.transaction(|conn| {
+            diesel::insert_into(app_config)
+                .values(&default_config)
+                .execute(conn)?;
+            store_app_settings(conn, &default_config)
+        })?;
         log::info!("Created default configuration");
     }
 
@@ -954,10 +1023,11 @@ pub fn get_app_config() -> Result<AppConfig, anyhow::Error> {
 
     let mut conn = get_config_conn()?;
 
-    app_config
+    let config = app_config
         .filter(id.eq(1))
         .first::<AppConfig>(&mut conn)
-        .map_err(|e| anyhow::anyhow!("Config row not found: {}", e))
+        .map_err(|e| anyhow::anyhow!("Config row not found: {}", e))?;
+    load_app_settings(&mut conn, config)
 }
 
 /// Update application configuration
@@ -970,9 +1040,84 @@ pub fn update_app_config(config: &AppConfig) -> Result<(), anyhow::Error> {
 
     let mut conn = get_config_conn()?;
 
-    diesel::update(app_config.filter(id.eq(1)))
-        .set(config)
-        .execute(&mut conn)?;
+    conn.transaction(|conn| {
+        diesel::update(app_config.filter(id.eq(1)))
+            .set(config)
+            .execute(conn)?;
+        store_app_settings(conn, config)
+    })
+}
+
+/// `AppConfig` fields persisted as JSON values in the `app_setting` key/value table.
+///
+/// `app_config` has to stay within diesel's 64-column table limit, so new settings
+/// are added here instead of as columns. A missing key falls back to `AppConfig::default()`.
+const APP_SETTING_FIELDS: &[&str] = &[
+    "instance_trash_retention_days",
+    "game_launch_pre_command",
+    "game_launch_post_command",
+    "update_channel",
+    "allow_config_edits_while_running",
+    "cache_compression_enabled",
+    "verify_on_launch",
+    "show_snapshots",
+    "allow_large_downloads_on_metered",
+    "strict_mod_conflicts",
+    "asset_concurrency",
+    "library_concurrency",
+    "mod_download_concurrency",
+    "usage_stats_consent",
+];
+
+/// Overlay the `app_setting` rows onto a config loaded from `app_config`.
+///
+/// Values that no longer deserialize into their field are ignored and keep the default.
+fn load_app_settings(
+    conn: &mut SqliteConnection,
+    config: AppConfig,
+) -> Result<AppConfig, anyhow::Error> {
+    use crate::schema::config::app_setting::dsl::*;
+
+    let rows: Vec<(String, String)> = app_setting
+        .select((key, value))
+        .filter(key.eq_any(APP_SETTING_FIELDS))
+        .load(conn)?;
+
+    let mut merged = serde_json::to_value(&config)?;
+    for (field, raw) in rows {
+        let Ok(parsed) = serde_json::from_str::<Value>(&raw) else {
+            log::warn!("Ignoring unparsable app setting '{}'", field);
+            continue;
+        };
+        let mut candidate = merged.clone();
+        candidate[field.as_str()] = parsed;
+        if serde_json::from_value::<AppConfig>(candidate.clone()).is_ok() {
+            merged = candidate;
+        } else {
+            log::warn!("Ignoring app setting '{}' with an invalid value", field);
+        }
+    }
+
+    Ok(serde_json::from_value(merged)?)
+}
+
+/// Upsert every `APP_SETTING_FIELDS` value of `config` into `app_setting`.
+fn store_app_settings(
+    conn: &mut SqliteConnection,
+    config: &AppConfig,
+) -> Result<(), anyhow::Error> {
+    use crate::schema::config::app_setting::dsl::*;
+
+    let config_value = serde_json::to_value(config)?;
+    for field in APP_SETTING_FIELDS {
+        let raw = config_value.get(*field).unwrap_or(&Value::Null).to_string();
+        diesel::insert_into(app_setting)
+            .values((key.eq(*field), value.eq(&raw)))
+            .on_conflict(key)
+            .do_update()
+            .set(value.eq(&raw))
+            .execute(conn)?;
+    }
 
     Ok(())
 }
@@ -1204,4 +1349,57 @@ mod tests {
         // TODO: Rewrite test for Diesel
         assert!(true);
     }
+
+    fn settings_conn() -> diesel::SqliteConnection {
+        use diesel::Connection;
+        use diesel_migrations::MigrationHarness;
+
+        let mut conn = diesel::SqliteConnection::establish(":memory:").unwrap();
+        conn.run_pending_migrations(crate::utils::db::CONFIG_MIGRATIONS)
+            .unwrap();
+        conn
+    }
+
+    #[test]
+    fn app_settings_round_trip_through_the_key_value_table() {
+        let mut conn = settings_conn();
+        let mut config = super::AppConfig::default();
+        config.verify_on_launch = true;
+        config.update_channel = "beta".to_string();
+        config.game_launch_pre_command = Some("echo {instance}".to_string());
+        config.asset_concurrency = 32;
+
+        super::store_app_settings(&mut conn, &config).unwrap();
+        // Storing twice updates the existing rows instead of failing on the key.
+        super::store_app_settings(&mut conn, &config).unwrap();
+
+        let loaded = super::load_app_settings(&mut conn, super::AppConfig::default()).unwrap();
+        assert!(loaded.verify_on_launch);
+        assert_eq!(loaded.update_channel, "beta");
+        assert_eq!(
+            loaded.game_launch_pre_command.as_deref(),
+            Some("echo {instance}")
+        );
+        assert_eq!(loaded.asset_concurrency, 32);
+    }
+
+    #[test]
+    fn invalid_app_setting_values_fall_back_to_defaults() {
+        use diesel::connection::SimpleConnection;
+
+        let mut conn = settings_conn();
+        conn.batch_execute(
+            "INSERT OR REPLACE INTO app_setting (key, value) VALUES
+                ('asset_concurrency', '\"lots\"'),
+                ('show_snapshots', 'not json'),
+                ('library_concurrency', '3');",
+        )
+        .unwrap();
+
+        let loaded = super::load_app_settings(&mut conn, super::AppConfig::default()).unwrap();
+        let defaults = super::AppConfig::default();
+        assert_eq!(loaded.asset_concurrency, defaults.asset_concurrency);
+        assert_eq!(loaded.show_snapshots, defaults.show_snapshots);
+        assert_eq!(loaded.library_concurrency, 3);
+    }
 }
@@ -0,0 +1,271 @@
+//! Saved theme presets.
+//!
+//! Each row of the config database's `theme_preset` table holds a full
+//! canonical theme payload (the JSON stored in `app_config.theme_data`).
+//! Builtin rows are seeded by migration and cannot be deleted; user presets
+//! are snapshots saved from the settings page. `app_config.theme_id` names the
+//! applied row, so switching presets never overwrites a saved one. Imported
+//! themes stay in the vesta database's `saved_themes` library.
+
+use super::{
+    apply_payload_to_scalar_fields, build_canonical_theme_payload, config_field_value,
+    get_app_config, merge_theme_payload, normalize_payload_style, preset_theme_payload,
+    sync_theme_to_account, update_app_config, AppConfig,
+};
+use crate::schema::config::theme_preset;
+use crate::utils::db::get_config_conn;
+use anyhow::{anyhow, bail};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::Emitter;
+
+/// Preset used as the starting point for a new preset that is not saved
+/// from the current theme.
+const BLANK_PRESET_ID: &str = "custom";
+
+#[derive(Debug, Clone, Queryable, Selectable, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = theme_preset)]
+#[diesel(check_for_backend(diesel::sqlite::Sqlite))]
+pub struct ThemePreset {
+    pub id: String,
+    pub name: String,
+    pub builtin: bool,
+    /// Canonical theme JSON, same shape as `AppConfig::theme_data`.
+    pub payload: String,
+    pub created_at: String,
+}
+
+/// Builtin presets first, then user presets oldest first.
+pub fn list_presets(conn: &mut SqliteConnection) -> QueryResult<Vec<ThemePreset>> {
+    theme_preset::table
+        .order((
+            theme_preset::builtin.desc(),
+            theme_preset::created_at.asc(),
+            theme_preset::name.asc(),
+        ))
+        .select(ThemePreset::as_select())
+        .load(conn)
+}
+
+pub fn find_preset(
+    conn: &mut SqliteConnection,
+    preset_id: &str,
+) -> QueryResult<Option<ThemePreset>> {
+    theme_preset::table
+        .find(preset_id)
+        .select(ThemePreset::as_select())
+        .first(conn)
+        .optional()
+}
+
+/// Store `payload` as a new user preset called `name`.
+pub fn insert_preset(
+    conn: &mut SqliteConnection,
+    name: &str,
+    mut payload: Value,
+) -> Result<ThemePreset, anyhow::Error> {
+    let name = name.trim();
+    if name.is_empty() {
+        bail!("Theme name cannot be empty");
+    }
+    let Some(obj) = payload.as_object_mut() else {
+        bail!("Theme payload is not an object");
+    };
+    let preset_id = format!("user-{}", uuid::Uuid::new_v4().simple());
+    obj.insert("id".to_string(), Value::String(preset_id.clone()));
+    obj.insert("name".to_string(), Value::String(name.to_string()));
+
+    let preset = ThemePreset {
+        id: preset_id,
+        name: name.to_string(),
+        builtin: false,
+        payload: serde_json::to_string(&payload)?,
+        created_at: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+    };
+    diesel::insert_into(theme_preset::table)
+        .values(&preset)
+        .execute(conn)?;
+    Ok(preset)
+}
+
+/// Delete a user preset. Builtins and the applied preset are kept.
+pub fn delete_preset(
+    conn: &mut SqliteConnection,
+    preset_id: &str,
+    active_theme_id: &str,
+) -> Result<(), anyhow::Error> {
+    let preset =
+        find_preset(conn, preset_id)?.ok_or_else(|| anyhow!("Theme not found: {}", preset_id))?;
+    if preset.builtin {
+        bail!("Built-in themes cannot be deleted");
+    }
+    if preset.id == active_theme_id {
+        bail!("Switch to another theme before deleting the current one");
+    }
+    diesel::delete(theme_preset::table.find(preset_id)).execute(conn)?;
+    Ok(())
+}
+
+/// `config` with every theme field taken from `preset`.
+fn config_with_preset(
+    config: &AppConfig,
+    preset: &ThemePreset,
+) -> Result<AppConfig, anyhow::Error> {
+    let stored: Value = serde_json::from_str(&preset.payload)
+        .map_err(|e| anyhow!("Theme '{}' has an invalid payload: {}", preset.name, e))?;
+    let mut payload = preset_theme_payload(&preset.id);
+    merge_theme_payload(&mut payload, stored);
+    normalize_payload_style(&mut payload);
+    if let Some(obj) = payload.as_object_mut() {
+        obj.insert("id".to_string(), Value::String(preset.id.clone()));
+    }
+
+    let mut next = config.clone();
+    apply_payload_to_scalar_fields(&mut next, &payload);
+    next.theme_id = preset.id.clone();
+    next.theme_data = Some(serde_json::to_string(&payload)?);
+    Ok(next)
+}
+
+/// Config fields whose serialized value differs between `before` and `after`.
+fn changed_fields(before: &AppConfig, after: &AppConfig) -> Vec<String> {
+    let (Ok(Value::Object(before)), Ok(Value::Object(after))) =
+        (serde_json::to_value(before), serde_json::to_value(after))
+    else {
+        return Vec::new();
+    };
+    after
+        .iter()
+        .filter(|(field, value)| before.get(*field) != Some(*value))
+        .map(|(field, _)| field.clone())
+        .collect()
+}
+
+// ==================== Tauri Commands ====================
+
+#[tauri::command]
+pub fn list_theme_presets() -> Result<Vec<ThemePreset>, String> {
+    let mut conn = get_config_conn().map_err(|e| e.to_string())?;
+    list_presets(&mut conn).map_err(|e| e.to_string())
+}
+
+/// Save a new user preset, either a snapshot of the current theme or a copy
+/// of the blank custom theme.
+#[tauri::command]
+pub fn save_theme_preset(name: String, from_current: bool) -> Result<ThemePreset, String> {
+    let payload = if from_current {
+        let config = get_app_config().map_err(|e| e.to_string())?;
+        build_canonical_theme_payload(&config)
+    } else {
+        preset_theme_payload(BLANK_PRESET_ID)
+    };
+    let mut conn = get_config_conn().map_err(|e| e.to_string())?;
+    let preset = insert_preset(&mut conn, &name, payload).map_err(|e| e.to_string())?;
+    log::info!("Saved theme preset '{}' ({})", preset.name, preset.id);
+    Ok(preset)
+}
+
+/// Apply a preset and emit `config-updated` for every field it changed.
+#[tauri::command]
+pub fn apply_theme_preset(app_handle: tauri::AppHandle, id: String) -> Result<(), String> {
+    let preset = {
+        let mut conn = get_config_conn().map_err(|e| e.to_string())?;
+        find_preset(&mut conn, &id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Theme not found: {}", id))?
+    };
+    let config = get_app_config().map_err(|e| e.to_string())?;
+    let updated_config = config_with_preset(&config, &preset).map_err(|e| e.to_string())?;
+    let changed = changed_fields(&config, &updated_config);
+    if changed.is_empty() {
+        return Ok(());
+    }
+
+    log::info!("Applying theme preset '{}' ({})", preset.name, preset.id);
+    update_app_config(&updated_config).map_err(|e| e.to_string())?;
+
+    for field in changed {
+        let emitted_value = config_field_value(&updated_config, &field);
+        if let Some(ref account_uuid) = updated_config.active_account_uuid {
+            let _ = sync_theme_to_account(&field, &emitted_value, account_uuid);
+        }
+        let event_payload = serde_json::json!({
+            "field": field,
+            "value": emitted_value,
+        });
+        let _ = app_handle.emit("config-updated", event_payload);
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_theme_preset(id: String) -> Result<(), String> {
+    let config = get_app_config().map_err(|e| e.to_string())?;
+    let mut conn = get_config_conn().map_err(|e| e.to_string())?;
+    delete_preset(&mut conn, &id, &config.theme_id).map_err(|e| e.to_string())?;
+    log::info!("Deleted theme preset {}", id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel_migrations::MigrationHarness;
+
+    fn conn() -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.run_pending_migrations(crate::utils::db::CONFIG_MIGRATIONS)
+            .unwrap();
+        conn
+    }
+
+    #[test]
+    fn builtin_presets_are_seeded_and_protected() {
+        let mut conn = conn();
+        let presets = list_presets(&mut conn).unwrap();
+        assert!(presets.iter().all(|preset| preset.builtin));
+        let vesta = presets.iter().find(|preset| preset.id == "vesta").unwrap();
+        let payload: Value = serde_json::from_str(&vesta.payload).unwrap();
+        assert_eq!(payload, preset_theme_payload("vesta"));
+
+        let err = delete_preset(&mut conn, "solar", "vesta").unwrap_err();
+        assert!(err.to_string().contains("Built-in"));
+        assert!(find_preset(&mut conn, "solar").unwrap().is_some());
+    }
+
+    #[test]
+    fn saved_presets_round_trip_through_the_config() {
+        let mut conn = conn();
+        let mut config = AppConfig::default();
+        config.theme_id = "custom".to_string();
+        config.theme_primary_hue = 12;
+        config.theme_border_width = Some(3);
+        config.theme_data = None;
+
+        let saved =
+            insert_preset(&mut conn, " Ember ", build_canonical_theme_payload(&config)).unwrap();
+        assert_eq!(saved.name, "Ember");
+        assert!(!saved.builtin);
+
+        let solar = find_preset(&mut conn, "solar").unwrap().unwrap();
+        let on_solar = config_with_preset(&config, &solar).unwrap();
+        assert_eq!(on_solar.theme_id, "solar");
+        assert_eq!(on_solar.theme_primary_hue, 40);
+        assert!(changed_fields(&config, &on_solar).contains(&"theme_primary_hue".to_string()));
+
+        let back = config_with_preset(&on_solar, &saved).unwrap();
+        assert_eq!(back.theme_id, saved.id);
+        assert_eq!(back.theme_primary_hue, 12);
+        assert_eq!(back.theme_border_width, Some(3));
+        let data: Value = serde_json::from_str(back.theme_data.as_deref().unwrap()).unwrap();
+        assert_eq!(data["name"], "Ember");
+
+        let err = delete_preset(&mut conn, &saved.id, &saved.id).unwrap_err();
+        assert!(err.to_string().contains("Switch"));
+        delete_preset(&mut conn, &saved.id, "solar").unwrap();
+        assert!(find_preset(&mut conn, &saved.id).unwrap().is_none());
+        assert!(insert_preset(&mut conn, "  ", serde_json::json!({})).is_err());
+    }
+}
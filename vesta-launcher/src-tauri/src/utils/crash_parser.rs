@@ -1,3 +1,4 @@
+use super::hs_err;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -9,7 +10,7 @@ pub struct CrashSuspect {
     pub display_name: String,
     pub mod_id: Option<String>,
     pub reason: Option<String>,
-    pub suspect_kind: String, // "affected_mod" | "missing_dependency"
+    pub suspect_kind: String, // "affected_mod" | "missing_dependency" | "native_library"
 }
 
 /// Crash details extracted from logs and files
@@ -584,52 +585,100 @@ fn strip_log_prefix(line: &str) -> &str {
     line.trim_start()
 }
 
-/// Check for JVM crash files (hs_err_pidXXXXX.log) created after launch start
+/// Check for JVM crash files (hs_err_pidXXXXX.log) created after launch start.
+/// A Minecraft crash report written during the same run is merged in, since
+/// the game sometimes gets one out before the native crash takes the JVM down.
 fn check_jvm_crash(game_dir: &Path, launch_start_time: SystemTime) -> Option<CrashDetails> {
-    // Look for hs_err files in game directory
-    if let Ok(entries) = fs::read_dir(game_dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if let Some(filename) = path.file_name() {
-                if let Some(name_str) = filename.to_str() {
-                    if name_str.starts_with("hs_err_pid") && name_str.ends_with(".log") {
-                        // Check if this file was modified after launch
-                        if let Ok(metadata) = fs::metadata(&path) {
-                            if let Ok(modified_time) = metadata.modified() {
-                                if modified_time > launch_start_time {
-                                    log::error!("JVM crash detected: {:?}", path);
-
-                                    return Some(CrashDetails {
-                                        crash_id: new_crash_id(),
-                                        crash_type: "jvm".to_string(),
-                                        category: "jvm".to_string(),
-                                        title: "Java virtual machine crash".to_string(),
-                                        message: "Java Virtual Machine crashed".to_string(),
-                                        evidence: None,
-                                        suspected_resources: Vec::new(),
-                                        suspects: Vec::new(),
-                                        suggested_fixes: vec![
-                                            "Try another Java runtime for this instance.".to_string(),
-                                            "Lower memory settings if the crash repeats immediately.".to_string(),
-                                        ],
-                                        affected_mod_count: None,
-                                        report_path: path.to_string_lossy().to_string().into(),
-                                        log_path: None,
-                                        timestamp: chrono::Utc::now().to_rfc3339(),
-                                        confidence: 0.98,
-                                        mclogs_url: None,
-                                        analysis: None,
-                                    });
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+    let path = hs_err::find_new_log(game_dir, launch_start_time)?;
+    log::error!("JVM crash detected: {:?}", path);
+    let content = fs::read_to_string(&path).unwrap_or_default();
+    let minecraft_report =
+        find_latest_crash_report(&game_dir.join("crash-reports"), Some(launch_start_time));
+    let minecraft_content = minecraft_report
+        .as_deref()
+        .and_then(|report| fs::read_to_string(report).ok());
+    Some(build_jvm_crash(
+        &content,
+        Some(path.to_string_lossy().to_string()),
+        minecraft_report,
+        minecraft_content.as_deref(),
+    ))
+}
+
+/// Crash details for an hs_err log, optionally merged with the Minecraft
+/// crash report of the same run.
+fn build_jvm_crash(
+    hs_err_content: &str,
+    hs_err_path: Option<String>,
+    minecraft_report: Option<String>,
+    minecraft_content: Option<&str>,
+) -> CrashDetails {
+    let report = hs_err::parse(hs_err_content);
+    let culprit = report
+        .frame_module
+        .as_deref()
+        .and_then(hs_err::native_culprit);
+
+    let mut message = match (&report.error, &report.frame_module) {
+        (Some(error), Some(module)) => format!("{} in {}", error, module),
+        (Some(error), None) => error.clone(),
+        _ => "Java Virtual Machine crashed".to_string(),
+    };
+    if let Some(culprit) = &culprit {
+        message.push_str(&format!(" ({})", culprit.name));
+    }
+
+    let mut suspects = Vec::new();
+    if let (Some(culprit), Some(module)) = (&culprit, &report.frame_module) {
+        suspects.push(CrashSuspect {
+            display_name: culprit.name.clone(),
+            mod_id: None,
+            reason: Some(format!("Crashed in {}", module)),
+            suspect_kind: "native_library".to_string(),
+        });
+    }
+    if let Some(content) = minecraft_content {
+        for suspect in generic_suspects(content) {
+            push_unique_suspect(&mut suspects, suspect);
         }
     }
 
-    None
+    let mut suggested_fixes = Vec::new();
+    if let Some(culprit) = &culprit {
+        suggested_fixes.push(culprit.suggestion.clone());
+    }
+    for fix in [
+        "Try another Java runtime for this instance.",
+        "Lower memory settings if the crash repeats immediately.",
+    ] {
+        if !suggested_fixes.iter().any(|existing| existing == fix) {
+            suggested_fixes.push(fix.to_string());
+        }
+    }
+
+    let evidence = report
+        .problematic_frame
+        .as_ref()
+        .map(|frame| format!("Problematic frame: {}", frame));
+    let mut crash = build_crash(
+        "jvm",
+        "jvm",
+        "Java virtual machine crash",
+        message,
+        evidence,
+        suspects,
+        suggested_fixes,
+        None,
+        hs_err_path,
+        None,
+        if culprit.is_some() { 0.98 } else { 0.9 },
+    );
+    crash.analysis = Some(serde_json::json!({
+        "jvm": report,
+        "native_culprit": culprit,
+        "minecraft_crash_report": minecraft_report,
+    }));
+    crash
 }
 
 /// Extract a meaningful error message from mod error logs
@@ -1156,4 +1205,39 @@ More details:
         assert_eq!(crash.title, "Native library failure");
         assert!(crash.report_path.is_some());
     }
+
+    #[test]
+    fn jvm_crash_merges_minecraft_report_and_names_driver() {
+        let hs_err = "#  EXCEPTION_ACCESS_VIOLATION (0xc0000005) at pc=0x00007ffd1a4f6c3e, pid=18244, tid=15512\n\
+# JRE version: OpenJDK Runtime Environment (17.0.8+7) (build 17.0.8+7-LTS)\n\
+# Problematic frame:\n\
+# C  [nvoglv64.dll+0x10d2a1]\n";
+        let report = "Description: Rendering overlay\n\
+java.lang.IllegalStateException: GLFW error\n\
+\tat sodium-fabric-0.5.8.jar//me.jellysquid.mods.sodium.Render.draw\n";
+
+        let crash = build_jvm_crash(
+            hs_err,
+            Some("hs_err_pid18244.log".to_string()),
+            Some("crash-reports/crash-2026-10-17_14.02.11-client.txt".to_string()),
+            Some(report),
+        );
+
+        assert_eq!(crash.crash_type, "jvm");
+        assert_eq!(
+            crash.message,
+            "EXCEPTION_ACCESS_VIOLATION (0xc0000005) in nvoglv64.dll (NVIDIA graphics driver)"
+        );
+        assert_eq!(crash.suspects[0].suspect_kind, "native_library");
+        assert_eq!(
+            crash.suggested_fixes[0],
+            "Update the NVIDIA graphics driver."
+        );
+        let analysis = crash.analysis.unwrap();
+        assert_eq!(
+            analysis["minecraft_crash_report"],
+            "crash-reports/crash-2026-10-17_14.02.11-client.txt"
+        );
+        assert_eq!(analysis["jvm"]["frame_module"], "nvoglv64.dll");
+    }
 }
@@ -11,6 +11,9 @@ use std::time::Duration;
 
 pub type DbPool = Pool<ConnectionManager<SqliteConnection>>;
 
+/// How long a caller waits for a free pooled connection before giving up.
+const POOL_CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
 /// Helper to retry database operations when locked.
 /// For use in tasks, use `with_retry_cancellable`.
 pub fn with_retry<T, F>(op: F) -> Result<T, anyhow::Error>
@@ -90,11 +93,48 @@ impl r2d2::CustomizeConnection<SqliteConnection, r2d2::Error> for SqliteCustomiz
     }
 }
 
+fn build_pool(url: String, max_size: u32, timeout: Duration) -> Result<DbPool, anyhow::Error> {
+    let manager = ConnectionManager::<SqliteConnection>::new(url);
+    Ok(Pool::builder()
+        .max_size(max_size)
+        .connection_timeout(timeout)
+        .connection_customizer(Box::new(SqliteCustomizer))
+        .build(manager)?)
+}
+
+/// Check out a connection without holding the global pool lock while waiting,
+/// so one slow checkout does not block every other caller.
+fn checkout(
+    pool: &Mutex<Option<DbPool>>,
+    name: &str,
+) -> Result<r2d2::PooledConnection<ConnectionManager<SqliteConnection>>, anyhow::Error> {
+    let pool = pool
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("{} database pool not initialized", name))?;
+    pool.get().map_err(|e| {
+        let state = pool.state();
+        anyhow::anyhow!(
+            "Timed out after {}s waiting for a {} database connection ({}/{} in use): {}",
+            pool.connection_timeout().as_secs_f32(),
+            name,
+            state.connections - state.idle_connections,
+            pool.max_size(),
+            e
+        )
+    })
+}
+
 // Embed migrations at compile time
 // Separate migration sets for different databases
 pub const VESTA_MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations/vesta");
 pub const CONFIG_MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations/config");
 
+/// File names of the two databases inside the app data directory.
+pub const VESTA_DB_FILE: &str = "vesta.db";
+pub const CONFIG_DB_FILE: &str = "app_config.db";
+
 #[tauri::command]
 pub fn get_db_status() -> Result<serde_json::Value, String> {
     let mut status = serde_json::json!({});
@@ -118,6 +158,38 @@ pub fn get_db_status() -> Result<serde_json::Value, String> {
     Ok(status)
 }
 
+/// Applied and pending migration counts plus the newest applied version for
+/// both databases, as reported in diagnostics bundles.
+pub fn get_schema_versions() -> serde_json::Value {
+    let mut versions = serde_json::json!({});
+    if let Ok(mut conn) = get_vesta_conn() {
+        versions["vesta"] = migration_summary(&mut conn, VESTA_MIGRATIONS);
+    }
+    if let Ok(mut conn) = get_config_conn() {
+        versions["config"] = migration_summary(&mut conn, CONFIG_MIGRATIONS);
+    }
+    versions
+}
+
+fn migration_summary(
+    conn: &mut SqliteConnection,
+    migrations: EmbeddedMigrations,
+) -> serde_json::Value {
+    let applied: Vec<String> = conn
+        .applied_migrations()
+        .map(|versions| versions.iter().map(|v| v.to_string()).collect())
+        .unwrap_or_default();
+    let pending = conn
+        .pending_migrations(migrations)
+        .map(|pending| pending.len())
+        .ok();
+    serde_json::json!({
+        "latest": applied.iter().max(),
+        "applied": applied.len(),
+        "pending": pending,
+    })
+}
+
 fn get_tables(conn: &mut SqliteConnection) -> Vec<String> {
     use diesel::sql_query;
     use diesel::RunQueryDsl;
@@ -142,16 +214,12 @@ lazy_static::lazy_static! {
 
 /// Initialize the vesta.db connection pool
 pub fn init_vesta_pool(path: PathBuf) -> Result<(), anyhow::Error> {
-    let db_path = path.join("vesta.db");
+    let db_path = path.join(VESTA_DB_FILE);
     let url = db_path.to_string_lossy().to_string();
 
     log::info!("Connecting to vesta database at {}", url);
 
-    let manager = ConnectionManager::<SqliteConnection>::new(url);
-    let pool = Pool::builder()
-        .max_size(16)
-        .connection_customizer(Box::new(SqliteCustomizer))
-        .build(manager)?;
+    let pool = build_pool(url, 16, POOL_CONNECTION_TIMEOUT)?;
 
     // Run migrations
     let mut conn = pool.get()?;
@@ -185,16 +253,12 @@ pub fn init_vesta_pool(path: PathBuf) -> Result<(), anyhow::Error> {
 
 /// Initialize the app_config.db connection pool
 pub fn init_config_pool(path: PathBuf) -> Result<(), anyhow::Error> {
-    let db_path = path.join("app_config.db");
+    let db_path = path.join(CONFIG_DB_FILE);
     let url = db_path.to_string_lossy().to_string();
 
     log::info!("Connecting to config database at {}", url);
 
-    let manager = ConnectionManager::<SqliteConnection>::new(url);
-    let pool = Pool::builder()
-        .max_size(4)
-        .connection_customizer(Box::new(SqliteCustomizer))
-        .build(manager)?;
+    let pool = build_pool(url, 4, POOL_CONNECTION_TIMEOUT)?;
 
     // Run migrations (same migrations, they'll check if tables exist)
     let mut conn = pool.get()?;
@@ -223,25 +287,13 @@ pub fn init_config_pool(path: PathBuf) -> Result<(), anyhow::Error> {
 /// Get a connection from the vesta.db pool
 pub fn get_vesta_conn(
 ) -> Result<r2d2::PooledConnection<ConnectionManager<SqliteConnection>>, anyhow::Error> {
-    VESTA_POOL
-        .lock()
-        .unwrap()
-        .as_ref()
-        .ok_or_else(|| anyhow::anyhow!("Vesta database pool not initialized"))?
-        .get()
-        .map_err(|e| anyhow::anyhow!("Failed to get vesta connection: {}", e))
+    checkout(&VESTA_POOL, "Vesta")
 }
 
 /// Get a connection from the app_config.db pool
 pub fn get_config_conn(
 ) -> Result<r2d2::PooledConnection<ConnectionManager<SqliteConnection>>, anyhow::Error> {
-    CONFIG_POOL
-        .lock()
-        .unwrap()
-        .as_ref()
-        .ok_or_else(|| anyhow::anyhow!("Config database pool not initialized"))?
-        .get()
-        .map_err(|e| anyhow::anyhow!("Failed to get config connection: {}", e))
+    checkout(&CONFIG_POOL, "Config")
 }
 
 /// Get the vesta database pool (for advanced usage)
@@ -261,3 +313,79 @@ pub fn get_config_pool() -> Result<DbPool, anyhow::Error> {
         .clone()
         .ok_or_else(|| anyhow::anyhow!("Config database pool not initialized"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::connection::SimpleConnection;
+
+    fn temp_pool(dir: &tempfile::TempDir, max_size: u32, timeout: Duration) -> DbPool {
+        let url = dir.path().join("test.db").to_string_lossy().to_string();
+        let pool = build_pool(url, max_size, timeout).unwrap();
+        pool.get()
+            .unwrap()
+            .batch_execute("CREATE TABLE t (v INTEGER); INSERT INTO t VALUES (1), (2), (3);")
+            .unwrap();
+        pool
+    }
+
+    #[test]
+    fn pooled_connections_serve_concurrent_reads() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = temp_pool(&dir, 4, POOL_CONNECTION_TIMEOUT);
+
+        let handles: Vec<_> = (0..16)
+            .map(|_| {
+                let pool = pool.clone();
+                thread::spawn(move || {
+                    let mut conn = pool.get().unwrap();
+                    diesel::sql_query("SELECT v FROM t")
+                        .execute(&mut conn)
+                        .unwrap();
+                    thread::sleep(Duration::from_millis(20));
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let state = pool.state();
+        assert!(state.connections <= 4);
+        assert_eq!(state.idle_connections, state.connections);
+    }
+
+    #[test]
+    fn exhausted_pool_times_out_with_descriptive_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = temp_pool(&dir, 1, Duration::from_millis(100));
+        let slot = Mutex::new(Some(pool.clone()));
+
+        let _held = pool.get().unwrap();
+        let Err(err) = checkout(&slot, "Test") else {
+            panic!("checkout should time out while the only connection is held");
+        };
+        let err = err.to_string();
+        assert!(err.contains("Timed out"), "{err}");
+        assert!(
+            err.contains("Test database connection (1/1 in use)"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn connections_use_wal_journal() {
+        #[derive(QueryableByName)]
+        struct JournalMode {
+            #[diesel(sql_type = diesel::sql_types::Text)]
+            journal_mode: String,
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let pool = temp_pool(&dir, 2, POOL_CONNECTION_TIMEOUT);
+        let mode: JournalMode = diesel::sql_query("PRAGMA journal_mode")
+            .get_result(&mut pool.get().unwrap())
+            .unwrap();
+        assert_eq!(mode.journal_mode.to_lowercase(), "wal");
+    }
+}
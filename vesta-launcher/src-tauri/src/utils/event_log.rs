@@ -0,0 +1,169 @@
+//! Local log of coarse usage events for the user's own diagnostics.
+//!
+//! Installs and launches are recorded whatever the usage statistics consent
+//! says, because the log never leaves the machine on its own. It is trimmed
+//! by the maintenance job and can be cleared with `purge_event_log`.
+
+use crate::models::event_log::{EventLogEntry, NewEventLogEntry};
+use crate::schema::event_log::dsl;
+use crate::utils::db::get_vesta_conn;
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+
+/// Days of events kept by the maintenance job.
+pub(crate) const EVENT_LOG_RETENTION_DAYS: i64 = 90;
+
+/// Rows kept by the maintenance job, whatever their age.
+pub(crate) const MAX_EVENT_LOG_ROWS: i64 = 5_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EventKind {
+    InstallStarted,
+    InstallSucceeded,
+    InstallFailed,
+    Launch,
+}
+
+impl EventKind {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            EventKind::InstallStarted => "install_started",
+            EventKind::InstallSucceeded => "install_succeeded",
+            EventKind::InstallFailed => "install_failed",
+            EventKind::Launch => "launch",
+        }
+    }
+}
+
+pub(crate) fn record_event(
+    conn: &mut SqliteConnection,
+    kind: EventKind,
+    instance_id: Option<i32>,
+    error_code: Option<&str>,
+) -> Result<(), String> {
+    diesel::insert_into(dsl::event_log)
+        .values(NewEventLogEntry {
+            kind: kind.as_str().to_string(),
+            instance_id,
+            error_code: error_code.map(str::to_string),
+            created_at: chrono::Utc::now().to_rfc3339(),
+        })
+        .execute(conn)
+        .map(|_| ())
+        .map_err(|e| format!("Failed to record {} event: {}", kind.as_str(), e))
+}
+
+/// [`record_event`] on a fresh connection. Failures are logged, never
+/// returned, so a broken log can't fail an install or launch.
+pub(crate) fn record(kind: EventKind, instance_id: Option<i32>, error_code: Option<&str>) {
+    if let Err(e) = get_vesta_conn()
+        .map_err(|e| e.to_string())
+        .and_then(|mut conn| record_event(&mut conn, kind, instance_id, error_code))
+    {
+        log::warn!("{}", e);
+    }
+}
+
+/// Newest events first.
+pub(crate) fn recent_events(
+    conn: &mut SqliteConnection,
+    limit: i64,
+) -> Result<Vec<EventLogEntry>, String> {
+    dsl::event_log
+        .order((dsl::created_at.desc(), dsl::id.desc()))
+        .limit(limit)
+        .select(EventLogEntry::as_select())
+        .load(conn)
+        .map_err(|e| format!("Failed to load event log: {}", e))
+}
+
+pub(crate) fn count_events(conn: &mut SqliteConnection) -> Result<i64, String> {
+    dsl::event_log
+        .count()
+        .get_result(conn)
+        .map_err(|e| format!("Failed to count event log: {}", e))
+}
+
+/// Delete every event. Returns the number of rows removed.
+pub(crate) fn purge_events(conn: &mut SqliteConnection) -> Result<usize, String> {
+    diesel::delete(dsl::event_log)
+        .execute(conn)
+        .map_err(|e| format!("Failed to purge event log: {}", e))
+}
+
+/// Drop events older than `retention_days`, then all but the newest
+/// `max_rows`. Returns the number of rows removed.
+pub(crate) fn trim_events(
+    conn: &mut SqliteConnection,
+    retention_days: i64,
+    max_rows: i64,
+) -> Result<usize, String> {
+    let cutoff = (chrono::Utc::now() - chrono::Duration::days(retention_days)).to_rfc3339();
+    let expired = diesel::delete(dsl::event_log.filter(dsl::created_at.lt(cutoff)))
+        .execute(conn)
+        .map_err(|e| format!("Failed to trim event log: {}", e))?;
+    let overflow = diesel::sql_query(
+        "DELETE FROM event_log WHERE id NOT IN (
+            SELECT id FROM event_log ORDER BY created_at DESC, id DESC LIMIT ?
+        )",
+    )
+    .bind::<diesel::sql_types::BigInt, _>(max_rows)
+    .execute(conn)
+    .map_err(|e| format!("Failed to trim event log: {}", e))?;
+    Ok(expired + overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::connection::SimpleConnection;
+    use diesel_migrations::MigrationHarness;
+
+    fn conn() -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        conn.run_pending_migrations(crate::utils::db::VESTA_MIGRATIONS)
+            .unwrap();
+        conn
+    }
+
+    #[test]
+    fn events_are_recorded_newest_first_and_purged() {
+        let mut conn = conn();
+        record_event(&mut conn, EventKind::InstallStarted, Some(1), None).unwrap();
+        record_event(
+            &mut conn,
+            EventKind::InstallFailed,
+            Some(1),
+            Some("cancelled"),
+        )
+        .unwrap();
+        record_event(&mut conn, EventKind::Launch, Some(2), None).unwrap();
+
+        let events = recent_events(&mut conn, 10).unwrap();
+        let kinds: Vec<&str> = events.iter().map(|e| e.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["launch", "install_failed", "install_started"]);
+        assert_eq!(events[1].error_code.as_deref(), Some("cancelled"));
+        assert_eq!(count_events(&mut conn).unwrap(), 3);
+
+        assert_eq!(purge_events(&mut conn).unwrap(), 3);
+        assert_eq!(count_events(&mut conn).unwrap(), 0);
+    }
+
+    #[test]
+    fn trim_drops_expired_and_overflowing_rows() {
+        let mut conn = conn();
+        conn.batch_execute(
+            "INSERT INTO event_log (kind, created_at)
+             VALUES ('launch', '2000-01-01T00:00:00+00:00');",
+        )
+        .unwrap();
+        for _ in 0..4 {
+            record_event(&mut conn, EventKind::Launch, None, None).unwrap();
+        }
+
+        assert_eq!(trim_events(&mut conn, 90, 3).unwrap(), 2);
+        let remaining = recent_events(&mut conn, 10).unwrap();
+        assert_eq!(remaining.len(), 3);
+        assert!(remaining.iter().all(|e| !e.created_at.starts_with("2000")));
+    }
+}
@@ -0,0 +1,214 @@
+//! On-disk cache of file digests, so rescans and repeated modpack lookups
+//! do not re-read unchanged files.
+//!
+//! Each entry is a small JSON file under `cache/fingerprints`, named after
+//! the canonical path. An entry is only used while the file's size and
+//! modification time still match; the oldest entries are pruned once the
+//! cache holds more than [`MAX_ENTRIES`].
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::utils::hash::{hash_file_multi, hash_file_multi_blocking, FileHashes, HashAlgorithm};
+
+pub const MAX_ENTRIES: usize = 4096;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry {
+    path: String,
+    size: u64,
+    modified_ms: u128,
+    hashes: FileHashes,
+}
+
+pub struct FingerprintCache {
+    dir: PathBuf,
+    max_entries: usize,
+}
+
+impl FingerprintCache {
+    pub fn new(dir: PathBuf, max_entries: usize) -> Self {
+        Self { dir, max_entries }
+    }
+
+    /// The cache shared by the launcher, under the app cache directory.
+    pub fn for_app() -> Result<Self> {
+        let config_dir = crate::utils::db_manager::get_app_config_dir()?;
+        Ok(Self::new(
+            crate::utils::storage::artifact_cache_dir(&config_dir).join("fingerprints"),
+            MAX_ENTRIES,
+        ))
+    }
+
+    fn entry_path(&self, canonical: &str) -> PathBuf {
+        let key = crate::utils::hash::calculate_sha1_from_bytes(canonical.as_bytes());
+        self.dir.join(format!("{}.json", key))
+    }
+
+    fn read_entry(&self, canonical: &str, size: u64, modified_ms: u128) -> Option<FileHashes> {
+        let raw = std::fs::read_to_string(self.entry_path(canonical)).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&raw).ok()?;
+        (entry.path == canonical && entry.size == size && entry.modified_ms == modified_ms)
+            .then_some(entry.hashes)
+    }
+
+    /// Digests of `path` for `algorithms`, reading the file only if the cache
+    /// is missing some of them. Blocking.
+    pub fn get_or_hash(&self, path: &Path, algorithms: &[HashAlgorithm]) -> Result<FileHashes> {
+        let (canonical, size, modified_ms) = file_identity(path)?;
+        let mut hashes = self
+            .read_entry(&canonical, size, modified_ms)
+            .unwrap_or_default();
+        let missing = hashes.missing(algorithms);
+        if missing.is_empty() {
+            return Ok(hashes);
+        }
+
+        hashes.merge(hash_file_multi_blocking(path, &missing)?);
+        let entry = CacheEntry {
+            path: canonical.clone(),
+            size,
+            modified_ms,
+            hashes: hashes.clone(),
+        };
+        if let Err(e) = self.write_entry(&canonical, &entry) {
+            log::debug!("[fingerprint-cache] Failed to store {:?}: {}", path, e);
+        }
+        Ok(hashes)
+    }
+
+    fn write_entry(&self, canonical: &str, entry: &CacheEntry) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let target = self.entry_path(canonical);
+        let partial = target.with_extension("json.partial");
+        std::fs::write(&partial, serde_json::to_vec(entry)?)?;
+        std::fs::rename(&partial, &target)?;
+        self.prune();
+        Ok(())
+    }
+
+    fn prune(&self) {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        let mut files: Vec<(std::time::SystemTime, PathBuf)> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let modified = entry.metadata().ok()?.modified().ok()?;
+                Some((modified, entry.path()))
+            })
+            .collect();
+        if files.len() <= self.max_entries {
+            return;
+        }
+        files.sort();
+        let excess = files.len() - self.max_entries;
+        for (_, path) in files.into_iter().take(excess) {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+fn file_identity(path: &Path) -> Result<(String, u64, u128)> {
+    let canonical = std::fs::canonicalize(path)?;
+    let metadata = std::fs::metadata(&canonical)?;
+    let modified_ms = metadata
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    Ok((
+        canonical.to_string_lossy().to_string(),
+        metadata.len(),
+        modified_ms,
+    ))
+}
+
+/// Digests of `path` through the app's fingerprint cache, on a blocking
+/// thread. Falls back to hashing directly if the cache is unavailable.
+pub async fn cached_file_hashes(path: &Path, algorithms: &[HashAlgorithm]) -> Result<FileHashes> {
+    let cache = match FingerprintCache::for_app() {
+        Ok(cache) => cache,
+        Err(e) => {
+            log::debug!("[fingerprint-cache] Cache unavailable: {}", e);
+            return hash_file_multi(path, algorithms).await;
+        }
+    };
+    let path = path.to_path_buf();
+    let algorithms = algorithms.to_vec();
+    tokio::task::spawn_blocking(move || cache.get_or_hash(&path, &algorithms)).await?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    impl FingerprintCache {
+        fn lookup(&self, path: &Path) -> Option<FileHashes> {
+            let (canonical, size, modified_ms) = file_identity(path).ok()?;
+            self.read_entry(&canonical, size, modified_ms)
+        }
+    }
+
+    #[test]
+    fn repeat_calls_hit_the_cache() -> Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let cache = FingerprintCache::new(cache_dir.path().to_path_buf(), MAX_ENTRIES);
+        let mut file = tempfile::NamedTempFile::new()?;
+        file.write_all(b"hello world")?;
+
+        assert!(cache.lookup(file.path()).is_none());
+        let first = cache.get_or_hash(file.path(), &[HashAlgorithm::Sha1])?;
+        assert_eq!(cache.lookup(file.path()), Some(first.clone()));
+        assert_eq!(
+            cache.get_or_hash(file.path(), &[HashAlgorithm::Sha1])?,
+            first
+        );
+
+        // Asking for more digests extends the same entry.
+        let both = cache.get_or_hash(
+            file.path(),
+            &[HashAlgorithm::Sha1, HashAlgorithm::CurseForgeFingerprint],
+        )?;
+        assert_eq!(both.sha1, first.sha1);
+        assert_eq!(cache.lookup(file.path()), Some(both));
+        Ok(())
+    }
+
+    #[test]
+    fn changed_files_are_rehashed() -> Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let cache = FingerprintCache::new(cache_dir.path().to_path_buf(), MAX_ENTRIES);
+        let mut file = tempfile::NamedTempFile::new()?;
+        file.write_all(b"hello world")?;
+        let before = cache.get_or_hash(file.path(), &[HashAlgorithm::Sha1])?;
+
+        file.write_all(b", again")?;
+        assert!(cache.lookup(file.path()).is_none());
+        let after = cache.get_or_hash(file.path(), &[HashAlgorithm::Sha1])?;
+        assert_ne!(before.sha1, after.sha1);
+        Ok(())
+    }
+
+    #[test]
+    fn cache_size_is_capped() -> Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let cache = FingerprintCache::new(cache_dir.path().to_path_buf(), 2);
+        let files: Vec<_> = (0..4)
+            .map(|i| {
+                let mut file = tempfile::NamedTempFile::new().unwrap();
+                write!(file, "file {}", i).unwrap();
+                file
+            })
+            .collect();
+        for file in &files {
+            cache.get_or_hash(file.path(), &[HashAlgorithm::Murmur2])?;
+        }
+        assert_eq!(std::fs::read_dir(cache_dir.path())?.count(), 2);
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::{BufReader, Read};
 use std::path::Path;
@@ -73,6 +74,202 @@ pub fn calculate_sha256(path: &Path) -> Result<String> {
     Ok(hex::encode(hasher.finalize()))
 }
 
+/// A digest [`hash_file_multi`] can compute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HashAlgorithm {
+    Sha1,
+    Sha512,
+    /// Raw MurmurHash2, see [`calculate_murmur2_raw`].
+    Murmur2,
+    /// See [`calculate_curseforge_fingerprint`].
+    CurseForgeFingerprint,
+}
+
+/// Digests of one file; only the requested ones are set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileHashes {
+    pub sha1: Option<String>,
+    pub sha512: Option<String>,
+    pub murmur2: Option<u32>,
+    pub curseforge_fingerprint: Option<u32>,
+}
+
+impl FileHashes {
+    pub fn has(&self, algorithm: HashAlgorithm) -> bool {
+        match algorithm {
+            HashAlgorithm::Sha1 => self.sha1.is_some(),
+            HashAlgorithm::Sha512 => self.sha512.is_some(),
+            HashAlgorithm::Murmur2 => self.murmur2.is_some(),
+            HashAlgorithm::CurseForgeFingerprint => self.curseforge_fingerprint.is_some(),
+        }
+    }
+
+    /// Algorithms of `algorithms` this value does not have yet.
+    pub fn missing(&self, algorithms: &[HashAlgorithm]) -> Vec<HashAlgorithm> {
+        let mut missing: Vec<HashAlgorithm> = Vec::new();
+        for &algorithm in algorithms {
+            if !self.has(algorithm) && !missing.contains(&algorithm) {
+                missing.push(algorithm);
+            }
+        }
+        missing
+    }
+
+    /// Fill unset digests from `other`.
+    pub fn merge(&mut self, other: FileHashes) {
+        self.sha1 = self.sha1.take().or(other.sha1);
+        self.sha512 = self.sha512.take().or(other.sha512);
+        self.murmur2 = self.murmur2.or(other.murmur2);
+        self.curseforge_fingerprint = self.curseforge_fingerprint.or(other.curseforge_fingerprint);
+    }
+}
+
+const MURMUR2_M: u32 = 0x5bd1_e995;
+
+/// Incremental MurmurHash2 (32-bit), equal to `murmur2::murmur2` over the
+/// same bytes. The total length must be known up front.
+struct Murmur2 {
+    h: u32,
+    tail: [u8; 4],
+    tail_len: usize,
+}
+
+impl Murmur2 {
+    fn new(seed: u32, len: u32) -> Self {
+        Self {
+            h: seed ^ len,
+            tail: [0; 4],
+            tail_len: 0,
+        }
+    }
+
+    fn mix(&mut self, block: [u8; 4]) {
+        let mut k = u32::from_le_bytes(block);
+        k = k.wrapping_mul(MURMUR2_M);
+        k ^= k >> 24;
+        k = k.wrapping_mul(MURMUR2_M);
+        self.h = self.h.wrapping_mul(MURMUR2_M) ^ k;
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        if self.tail_len > 0 {
+            let take = (4 - self.tail_len).min(data.len());
+            self.tail[self.tail_len..self.tail_len + take].copy_from_slice(&data[..take]);
+            self.tail_len += take;
+            data = &data[take..];
+            if self.tail_len < 4 {
+                return;
+            }
+            self.mix(self.tail);
+            self.tail_len = 0;
+        }
+        let mut blocks = data.chunks_exact(4);
+        for block in &mut blocks {
+            self.mix([block[0], block[1], block[2], block[3]]);
+        }
+        let rest = blocks.remainder();
+        self.tail[..rest.len()].copy_from_slice(rest);
+        self.tail_len = rest.len();
+    }
+
+    fn finish(mut self) -> u32 {
+        if self.tail_len > 0 {
+            for i in (0..self.tail_len).rev() {
+                self.h ^= (self.tail[i] as u32) << (8 * i);
+            }
+            self.h = self.h.wrapping_mul(MURMUR2_M);
+        }
+        let mut h = self.h;
+        h ^= h >> 13;
+        h = h.wrapping_mul(MURMUR2_M);
+        h ^= h >> 15;
+        h
+    }
+}
+
+fn is_fingerprint_whitespace(b: u8) -> bool {
+    b == 9 || b == 10 || b == 13 || b == 32
+}
+
+/// Compute every requested digest of `reader` in one pass. `len` must be
+/// the exact number of bytes the reader yields.
+pub fn hash_reader_multi<R: Read>(
+    mut reader: R,
+    len: u64,
+    algorithms: &[HashAlgorithm],
+) -> Result<FileHashes> {
+    use sha1::Digest as _;
+
+    let mut sha1 = algorithms
+        .contains(&HashAlgorithm::Sha1)
+        .then(sha1::Sha1::new);
+    let mut sha512 = algorithms
+        .contains(&HashAlgorithm::Sha512)
+        .then(<sha2::Sha512 as sha2::Digest>::new);
+    let mut raw_murmur2 = algorithms
+        .contains(&HashAlgorithm::Murmur2)
+        .then(|| Murmur2::new(1, len as u32));
+    // The fingerprint hashes the length after filtering, so the filtered
+    // bytes have to be kept until the end.
+    let mut fingerprint_data = algorithms
+        .contains(&HashAlgorithm::CurseForgeFingerprint)
+        .then(|| Vec::with_capacity(len as usize));
+
+    let mut buffer = [0u8; READ_CHUNK_SIZE];
+    let mut total = 0u64;
+    loop {
+        let n = reader.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        let chunk = &buffer[..n];
+        total += n as u64;
+        if let Some(hasher) = sha1.as_mut() {
+            hasher.update(chunk);
+        }
+        if let Some(hasher) = sha512.as_mut() {
+            sha2::Digest::update(hasher, chunk);
+        }
+        if let Some(hasher) = raw_murmur2.as_mut() {
+            hasher.update(chunk);
+        }
+        if let Some(data) = fingerprint_data.as_mut() {
+            data.extend(
+                chunk
+                    .iter()
+                    .copied()
+                    .filter(|&b| !is_fingerprint_whitespace(b)),
+            );
+        }
+    }
+    if total != len {
+        anyhow::bail!("File changed while hashing ({} of {} bytes)", total, len);
+    }
+
+    Ok(FileHashes {
+        sha1: sha1.map(|hasher| hex::encode(hasher.finalize())),
+        sha512: sha512.map(|hasher| hex::encode(sha2::Digest::finalize(hasher))),
+        murmur2: raw_murmur2.map(Murmur2::finish),
+        curseforge_fingerprint: fingerprint_data.map(|data| murmur2::murmur2(&data, 1)),
+    })
+}
+
+/// Blocking form of [`hash_file_multi`].
+pub fn hash_file_multi_blocking(path: &Path, algorithms: &[HashAlgorithm]) -> Result<FileHashes> {
+    let file = File::open(path)?;
+    let len = file.metadata()?.len();
+    hash_reader_multi(BufReader::new(file), len, algorithms)
+}
+
+/// Compute several digests of a file while reading it only once, on a
+/// blocking thread.
+pub async fn hash_file_multi(path: &Path, algorithms: &[HashAlgorithm]) -> Result<FileHashes> {
+    let path = path.to_path_buf();
+    let algorithms = algorithms.to_vec();
+    tokio::task::spawn_blocking(move || hash_file_multi_blocking(&path, &algorithms)).await?
+}
+
 pub fn calculate_sha1_from_bytes(data: &[u8]) -> String {
     use sha1::{Digest, Sha1};
     let mut hasher = Sha1::new();
@@ -80,6 +277,13 @@ pub fn calculate_sha1_from_bytes(data: &[u8]) -> String {
     hex::encode(hasher.finalize())
 }
 
+pub fn calculate_sha256_from_bytes(data: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hex::encode(hasher.finalize())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,4 +325,75 @@ mod tests {
         assert_eq!(fp1, fp2);
         Ok(())
     }
+
+    /// Counts bytes handed out, to check a file is read once.
+    struct CountingReader<R> {
+        inner: R,
+        read: u64,
+    }
+
+    impl<R: Read> Read for CountingReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            self.read += n as u64;
+            Ok(n)
+        }
+    }
+
+    const ALL: [HashAlgorithm; 4] = [
+        HashAlgorithm::Sha1,
+        HashAlgorithm::Sha512,
+        HashAlgorithm::Murmur2,
+        HashAlgorithm::CurseForgeFingerprint,
+    ];
+
+    #[test]
+    fn streaming_murmur2_matches_one_shot() {
+        let data: Vec<u8> = (0..40_003u32).map(|i| (i * 31 % 251) as u8).collect();
+        for len in [0, 1, 2, 3, 4, 5, 7, 8, 4097, data.len()] {
+            let slice = &data[..len];
+            for split in [1, 3, 4, 1000] {
+                let mut hasher = Murmur2::new(1, len as u32);
+                for chunk in slice.chunks(split) {
+                    hasher.update(chunk);
+                }
+                assert_eq!(hasher.finish(), murmur2::murmur2(slice, 1), "len {}", len);
+            }
+        }
+    }
+
+    #[test]
+    fn hash_file_multi_matches_single_hashes() -> Result<()> {
+        let mut file = NamedTempFile::new()?;
+        let content: Vec<u8> = (0..50_000u32)
+            .map(|i| if i % 17 == 0 { b' ' } else { (i % 251) as u8 })
+            .collect();
+        file.write_all(&content)?;
+
+        let hashes = hash_file_multi_blocking(file.path(), &ALL)?;
+        assert_eq!(hashes.sha1, Some(calculate_sha1(file.path())?));
+        assert_eq!(hashes.murmur2, Some(calculate_murmur2_raw(file.path())?));
+        assert_eq!(
+            hashes.curseforge_fingerprint,
+            Some(calculate_curseforge_fingerprint(file.path())?)
+        );
+        assert_eq!(hashes.sha512.as_ref().map(String::len), Some(128));
+
+        let only_sha1 = hash_file_multi_blocking(file.path(), &[HashAlgorithm::Sha1])?;
+        assert_eq!(only_sha1.sha1, hashes.sha1);
+        assert_eq!(only_sha1.missing(&ALL).len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn hash_reader_multi_reads_input_once() -> Result<()> {
+        let content = vec![7u8; 100_000];
+        let mut reader = CountingReader {
+            inner: content.as_slice(),
+            read: 0,
+        };
+        hash_reader_multi(&mut reader, content.len() as u64, &ALL)?;
+        assert_eq!(reader.read, content.len() as u64);
+        Ok(())
+    }
 }
@@ -1,5 +1,6 @@
 use crate::models::instance::Instance;
 use crate::utils::config::AppConfig;
+use piston_lib::utils::process::PistonCommandExt;
 use std::collections::HashMap;
 
 /// Parses Environment Variables from a string (one per line, format KEY=VALUE)
@@ -47,3 +48,224 @@ pub fn resolve_env_vars(config: &AppConfig, instance: &Instance) -> HashMap<Stri
 
     vars
 }
+
+/// Maximum time a launcher-side pre/post launch command may run before it is killed.
+pub const LAUNCH_COMMAND_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Values available to `game_launch_pre_command` / `game_launch_post_command` templates.
+#[derive(Debug, Clone)]
+pub struct LaunchCommandVars {
+    pub instance_id: String,
+    pub game_dir: std::path::PathBuf,
+    pub minecraft_version: String,
+}
+
+impl LaunchCommandVars {
+    fn interpolate(&self, arg: &str) -> String {
+        arg.replace("{instance_id}", &self.instance_id)
+            .replace("{game_dir}", &self.game_dir.to_string_lossy())
+            .replace("{minecraft_version}", &self.minecraft_version)
+    }
+}
+
+/// Split a command line the way `CommandLineToArgvW` does: only double quotes group, and
+/// backslashes are literal unless they precede a quote, so `C:\Tools\pre.bat` survives.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn split_windows_args(line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let mut backslashes = 1;
+                while chars.peek() == Some(&'\\') {
+                    chars.next();
+                    backslashes += 1;
+                }
+                in_arg = true;
+                if chars.peek() == Some(&'"') {
+                    current.push_str(&"\\".repeat(backslashes / 2));
+                    if backslashes % 2 == 1 {
+                        chars.next();
+                        current.push('"');
+                    }
+                } else {
+                    current.push_str(&"\\".repeat(backslashes));
+                }
+            }
+            '"' => {
+                in_arg = true;
+                if in_quotes && chars.peek() == Some(&'"') {
+                    chars.next();
+                    current.push('"');
+                } else {
+                    in_quotes = !in_quotes;
+                }
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            c => {
+                in_arg = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+    args
+}
+
+/// Split a command template into program + args using the platform's shell rules: Windows
+/// command-line rules on Windows (unquoted `C:\...` paths work), POSIX shell words elsewhere.
+#[cfg(windows)]
+fn split_launch_command(template: &str) -> Vec<String> {
+    split_windows_args(template)
+}
+
+#[cfg(not(windows))]
+fn split_launch_command(template: &str) -> Vec<String> {
+    shlex::split(template)
+        .unwrap_or_else(|| template.split_whitespace().map(|s| s.to_string()).collect())
+}
+
+/// Split a command template into program + args and substitute template variables.
+///
+/// Splitting happens before substitution so paths containing spaces stay a single argument.
+pub fn build_launch_command_argv(template: &str, vars: &LaunchCommandVars) -> Vec<String> {
+    split_launch_command(template)
+        .iter()
+        .map(|arg| vars.interpolate(arg))
+        .collect()
+}
+
+/// Run a launcher-side launch command in the instance game directory and wait for it,
+/// killing it after `timeout`. Non-zero exit codes are reported as errors.
+pub async fn run_launch_command(
+    label: &str,
+    template: &str,
+    vars: &LaunchCommandVars,
+    timeout: std::time::Duration,
+) -> Result<(), String> {
+    let argv = build_launch_command_argv(template, vars);
+    let Some((program, args)) = argv.split_first() else {
+        return Ok(());
+    };
+
+    log::info!("[hooks] Running {} command: {:?}", label, argv);
+    let mut command = std::process::Command::new(program);
+    command
+        .args(args)
+        .current_dir(&vars.game_dir)
+        .suppress_console();
+
+    let label = label.to_string();
+    tokio::task::spawn_blocking(move || {
+        let mut child = command
+            .spawn()
+            .map_err(|e| format!("Failed to start {} command: {}", label, e))?;
+        let deadline = std::time::Instant::now() + timeout;
+
+        loop {
+            match child.try_wait() {
+                Ok(Some(status)) if status.success() => return Ok(()),
+                Ok(Some(status)) => {
+                    return Err(format!("{} command exited with {}", label, status));
+                }
+                Ok(None) if std::time::Instant::now() >= deadline => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(format!(
+                        "{} command timed out after {}s",
+                        label,
+                        timeout.as_secs()
+                    ));
+                }
+                Ok(None) => std::thread::sleep(std::time::Duration::from_millis(100)),
+                Err(e) => return Err(format!("Failed to wait for {} command: {}", label, e)),
+            }
+        }
+    })
+    .await
+    .map_err(|e| format!("Launch command task panicked: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn launch_command_substitutes_variables_per_argument() {
+        let vars = LaunchCommandVars {
+            instance_id: "my-pack".to_string(),
+            game_dir: std::path::PathBuf::from("/games/My Pack"),
+            minecraft_version: "1.20.1".to_string(),
+        };
+
+        let argv = build_launch_command_argv(
+            "backup.sh --dir {game_dir} --name {instance_id}-{minecraft_version}",
+            &vars,
+        );
+
+        assert_eq!(
+            argv,
+            vec![
+                "backup.sh",
+                "--dir",
+                "/games/My Pack",
+                "--name",
+                "my-pack-1.20.1"
+            ]
+        );
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn launch_command_respects_quoting() {
+        let vars = LaunchCommandVars {
+            instance_id: "a".to_string(),
+            game_dir: std::path::PathBuf::from("/tmp"),
+            minecraft_version: "1.21".to_string(),
+        };
+
+        let argv = build_launch_command_argv("notify-send 'Started {instance_id}'", &vars);
+        assert_eq!(argv, vec!["notify-send", "Started a"]);
+    }
+
+    #[test]
+    fn windows_split_keeps_backslashes_in_paths() {
+        assert_eq!(
+            split_windows_args(r"C:\Tools\pre.bat --dir {game_dir}"),
+            vec![r"C:\Tools\pre.bat", "--dir", "{game_dir}"]
+        );
+        assert_eq!(
+            split_windows_args(r#""C:\Program Files\Backup\run.exe" "Started {instance_id}""#),
+            vec![r"C:\Program Files\Backup\run.exe", "Started {instance_id}"]
+        );
+        assert_eq!(
+            split_windows_args(r#"echo "ends with\\" \"quoted\" \\server\share """#),
+            vec!["echo", r"ends with\", r#""quoted""#, r"\\server\share", ""]
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn launch_command_keeps_unquoted_windows_paths() {
+        let vars = LaunchCommandVars {
+            instance_id: "a".to_string(),
+            game_dir: std::path::PathBuf::from(r"C:\Games\My Pack"),
+            minecraft_version: "1.21".to_string(),
+        };
+
+        let argv = build_launch_command_argv(r"C:\Tools\pre.bat {game_dir}", &vars);
+        assert_eq!(argv, vec![r"C:\Tools\pre.bat", r"C:\Games\My Pack"]);
+    }
+}
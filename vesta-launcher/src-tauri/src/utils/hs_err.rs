@@ -0,0 +1,320 @@
+//! HotSpot fatal error logs (`hs_err_pid*.log`).
+//!
+//! When the JVM itself dies (an access violation in native code, usually a
+//! graphics driver) Minecraft never gets to write a crash report; HotSpot
+//! drops an `hs_err_pid<pid>.log` into the working directory instead. Only
+//! the header and the memory line are parsed, which is enough to name the
+//! native module that crashed.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Where collected hs_err logs are moved, relative to the game directory.
+pub const JVM_CRASH_DIR: &str = "crash-reports/jvm";
+
+/// Summary of an hs_err log.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct HsErrReport {
+    /// Signal or exception, e.g. `EXCEPTION_ACCESS_VIOLATION (0xc0000005)`.
+    pub error: Option<String>,
+    pub pid: Option<u32>,
+    pub jre_version: Option<String>,
+    pub java_vm: Option<String>,
+    /// The frame line as printed, e.g. `C  [atio6axx.dll+0x1a2b3c]`.
+    pub problematic_frame: Option<String>,
+    /// Native library of the problematic frame, e.g. `atio6axx.dll`.
+    pub frame_module: Option<String>,
+    pub memory_summary: Option<String>,
+}
+
+/// A native library known to crash Minecraft and what to do about it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NativeCulprit {
+    pub name: String,
+    pub suggestion: String,
+}
+
+/// Module name prefixes (lowercase) and who ships them.
+const KNOWN_MODULES: &[(&[&str], &str, &str)] = &[
+    (
+        &[
+            "nvoglv",
+            "libnvidia-glcore",
+            "libnvidia-eglcore",
+            "libglx_nvidia",
+        ],
+        "NVIDIA graphics driver",
+        "Update the NVIDIA graphics driver.",
+    ),
+    (
+        &["atio6axx", "atioglxx", "atig6txx", "amdxc", "amdvlk"],
+        "AMD graphics driver",
+        "Update the AMD graphics driver.",
+    ),
+    (
+        &[
+            "ig9icd",
+            "ig8icd",
+            "ig7icd",
+            "igxelpicd",
+            "igdumd",
+            "iris_dri",
+        ],
+        "Intel graphics driver",
+        "Update the Intel graphics driver.",
+    ),
+    (
+        &["radeonsi_dri", "libgallium", "libglx_mesa", "swrast_dri"],
+        "Mesa graphics driver",
+        "Update Mesa from your distribution's packages.",
+    ),
+    (
+        &["lwjgl", "liblwjgl", "glfw", "libglfw"],
+        "LWJGL native library",
+        "Repair the instance so its native libraries are extracted again.",
+    ),
+    (
+        &["openal", "libopenal"],
+        "OpenAL audio library",
+        "Check the audio device, or repair the instance to restore OpenAL.",
+    ),
+    (
+        &["jvm.dll", "libjvm"],
+        "Java virtual machine",
+        "Try another Java runtime for this instance.",
+    ),
+];
+
+/// Parse the header and memory line of an hs_err log.
+pub fn parse(content: &str) -> HsErrReport {
+    let mut report = HsErrReport::default();
+    let mut lines = content.lines().map(str::trim_end).peekable();
+
+    while let Some(line) = lines.next() {
+        let text = line.trim_start_matches('#').trim();
+        if report.error.is_none() && text.contains(" at pc=") {
+            if let Some((error, rest)) = text.split_once(" at pc=") {
+                report.error = Some(error.trim().to_string());
+                report.pid = rest
+                    .split(',')
+                    .filter_map(|part| part.trim().strip_prefix("pid="))
+                    .find_map(|pid| pid.parse().ok());
+            }
+        } else if let Some(version) = text.strip_prefix("JRE version:") {
+            report.jre_version = Some(version.trim().to_string());
+        } else if let Some(vm) = text.strip_prefix("Java VM:") {
+            report.java_vm = Some(vm.trim().to_string());
+        } else if text == "Problematic frame:" {
+            if let Some(frame) = lines.peek() {
+                let frame = frame.trim_start_matches('#').trim();
+                if !frame.is_empty() {
+                    report.frame_module = frame_module(frame);
+                    report.problematic_frame = Some(frame.to_string());
+                }
+            }
+        } else if report.memory_summary.is_none() && line.starts_with("Memory:") {
+            report.memory_summary = Some(line.trim().to_string());
+        }
+    }
+
+    report
+}
+
+/// Library name between `[` and `+`/`]` of a native (`C`) or VM (`V`) frame,
+/// e.g. `C  [lib+0x..]`. Compiled Java frames (`J`, `j`) have none.
+fn frame_module(frame: &str) -> Option<String> {
+    if !(frame.starts_with("C ") || frame.starts_with("V ")) {
+        return None;
+    }
+    let start = frame.find('[')? + 1;
+    let rest = &frame[start..];
+    let end = rest.find(['+', ']']).unwrap_or(rest.len());
+    let module = rest[..end].trim();
+    (!module.is_empty()).then(|| module.to_string())
+}
+
+/// Known culprit for the module a crash happened in.
+pub fn native_culprit(module: &str) -> Option<NativeCulprit> {
+    let lower = module.to_ascii_lowercase();
+    KNOWN_MODULES
+        .iter()
+        .find(|(prefixes, _, _)| prefixes.iter().any(|prefix| lower.starts_with(prefix)))
+        .map(|(_, name, suggestion)| NativeCulprit {
+            name: name.to_string(),
+            suggestion: suggestion.to_string(),
+        })
+}
+
+/// Newest hs_err log in `game_dir` written after `since`.
+pub fn find_new_log(game_dir: &Path, since: SystemTime) -> Option<PathBuf> {
+    fs::read_dir(game_dir)
+        .ok()?
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("hs_err_pid") && name.ends_with(".log")
+        })
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            (modified > since).then(|| (entry.path(), modified))
+        })
+        .max_by_key(|(_, modified)| *modified)
+        .map(|(path, _)| path)
+}
+
+/// Move an hs_err log into [`JVM_CRASH_DIR`] and return its new path.
+pub fn archive_log(game_dir: &Path, log: &Path) -> Result<PathBuf, String> {
+    let dir = game_dir.join(JVM_CRASH_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let file_name = log
+        .file_name()
+        .ok_or_else(|| format!("{} is not a file", log.display()))?;
+    let target = dir.join(file_name);
+    if fs::rename(log, &target).is_err() {
+        fs::copy(log, &target)
+            .and_then(|_| fs::remove_file(log))
+            .map_err(|e| format!("Failed to move {}: {}", log.display(), e))?;
+    }
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOWS_AMD_SAMPLE: &str = r#"#
+# A fatal error has been detected by the Java Runtime Environment:
+#
+#  EXCEPTION_ACCESS_VIOLATION (0xc0000005) at pc=0x00007ffd1a4f6c3e, pid=18244, tid=15512
+#
+# JRE version: OpenJDK Runtime Environment Microsoft-8035246 (17.0.8+7) (build 17.0.8+7-LTS)
+# Java VM: OpenJDK 64-Bit Server VM Microsoft-8035246 (17.0.8+7-LTS, mixed mode, tiered, compressed oops, compressed class ptrs, g1 gc, windows-amd64)
+# Problematic frame:
+# C  [atio6axx.dll+0x196c3e]
+#
+# No core dump will be written. Minidumps are not enabled by default on client versions of Windows
+#
+# If you would like to submit a bug report, please visit:
+#   https://github.com/microsoft/openjdk/issues
+# The crash happened outside the Java Virtual Machine in native code.
+# See problematic frame for where to report the bug.
+#
+
+---------------  S U M M A R Y ------------
+
+Command Line: -Xmx4096M -Xms4096M net.minecraft.client.main.Main --username Steve
+
+Host: AMD Ryzen 5 3600 6-Core Processor              , 12 cores, 15G,  Windows 10 , 64 bit Build 19041 (10.0.19041.3636)
+Time: Sat Oct 17 14:02:11 2026 W. Europe Summer Time elapsed time: 38.211424 seconds (0d 0h 0m 38s)
+
+---------------  S Y S T E M  ---------------
+
+OS:
+ Windows 10 , 64 bit Build 19041 (10.0.19041.3636)
+
+Memory: 4k page, system-wide physical 16310M (5123M free)
+TotalPageFile size 18742M (AvailPageFile size 4711M)
+current process WorkingSet (physical memory assigned to process): 1893M, peak: 1893M
+"#;
+
+    const LINUX_JVM_SAMPLE: &str = r#"#
+# A fatal error has been detected by the Java Runtime Environment:
+#
+#  SIGSEGV (0xb) at pc=0x00007f3c8e2d1a50, pid=40213, tid=40260
+#
+# JRE version: OpenJDK Runtime Environment Temurin-21.0.4+7 (21.0.4+7) (build 21.0.4+7-LTS)
+# Java VM: OpenJDK 64-Bit Server VM Temurin-21.0.4+7 (21.0.4+7-LTS, mixed mode, sharing, tiered, compressed oops, compressed class ptrs, g1 gc, linux-amd64)
+# Problematic frame:
+# C  [libnvidia-glcore.so.550.107.02+0xe51a50]
+#
+# Core dump will be written. Default location: Core dumps may be processed with "/usr/lib/systemd/systemd-coredump %P %u %g %s %t %c %h" (or dumping to /home/steve/.minecraft/core.40213)
+#
+
+Memory: 4k page, physical 32766448k(9122140k free), swap 8388604k(8388604k free)
+"#;
+
+    const JIT_FRAME_SAMPLE: &str = r#"#  SIGSEGV (0xb) at pc=0x00007f1e9d0c5ab4, pid=777, tid=790
+#
+# JRE version: OpenJDK Runtime Environment (17.0.12+7) (build 17.0.12+7)
+# Problematic frame:
+# J 10452 c2 net.minecraft.world.level.chunk.PalettedContainer.get(I)Ljava/lang/Object; (18 bytes) @ 0x00007f1e9d0c5ab4 [0x00007f1e9d0c5a60+0x0000000000000054]
+#
+"#;
+
+    #[test]
+    fn parses_windows_driver_crash_header() {
+        let report = parse(WINDOWS_AMD_SAMPLE);
+        assert_eq!(
+            report.error.as_deref(),
+            Some("EXCEPTION_ACCESS_VIOLATION (0xc0000005)")
+        );
+        assert_eq!(report.pid, Some(18244));
+        assert_eq!(
+            report.jre_version.as_deref(),
+            Some("OpenJDK Runtime Environment Microsoft-8035246 (17.0.8+7) (build 17.0.8+7-LTS)")
+        );
+        assert_eq!(
+            report.problematic_frame.as_deref(),
+            Some("C  [atio6axx.dll+0x196c3e]")
+        );
+        assert_eq!(report.frame_module.as_deref(), Some("atio6axx.dll"));
+        assert_eq!(
+            report.memory_summary.as_deref(),
+            Some("Memory: 4k page, system-wide physical 16310M (5123M free)")
+        );
+        assert_eq!(
+            native_culprit("atio6axx.dll").map(|culprit| culprit.name),
+            Some("AMD graphics driver".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_linux_driver_crash_header() {
+        let report = parse(LINUX_JVM_SAMPLE);
+        assert_eq!(report.error.as_deref(), Some("SIGSEGV (0xb)"));
+        assert_eq!(report.pid, Some(40213));
+        assert_eq!(
+            report.frame_module.as_deref(),
+            Some("libnvidia-glcore.so.550.107.02")
+        );
+        assert!(report.java_vm.unwrap().contains("linux-amd64"));
+        assert_eq!(
+            native_culprit(&report.frame_module.unwrap()).map(|culprit| culprit.name),
+            Some("NVIDIA graphics driver".to_string())
+        );
+    }
+
+    #[test]
+    fn jit_frames_have_no_native_module() {
+        let report = parse(JIT_FRAME_SAMPLE);
+        assert_eq!(report.pid, Some(777));
+        assert!(report
+            .problematic_frame
+            .unwrap()
+            .starts_with("J 10452 c2 net.minecraft"));
+        assert_eq!(report.frame_module, None);
+    }
+
+    #[test]
+    fn new_logs_are_found_and_archived() {
+        let dir = tempfile::tempdir().unwrap();
+        let before = SystemTime::now() - std::time::Duration::from_secs(60);
+        let log = dir.path().join("hs_err_pid18244.log");
+        fs::write(&log, WINDOWS_AMD_SAMPLE).unwrap();
+        fs::write(dir.path().join("latest.log"), "not a crash").unwrap();
+
+        assert_eq!(find_new_log(dir.path(), before), Some(log.clone()));
+        assert_eq!(find_new_log(dir.path(), SystemTime::now()), None);
+
+        let archived = archive_log(dir.path(), &log).unwrap();
+        assert_eq!(
+            archived,
+            dir.path().join(JVM_CRASH_DIR).join("hs_err_pid18244.log")
+        );
+        assert!(!log.exists());
+        assert_eq!(find_new_log(dir.path(), before), None);
+    }
+}
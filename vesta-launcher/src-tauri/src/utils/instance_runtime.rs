@@ -6,7 +6,7 @@ use crate::models::instance::Instance;
 use crate::utils::db::get_vesta_conn;
 
 /// Minecraft version and modloader columns on an instance row.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub struct InstanceRuntimeFields {
     pub minecraft_version: String,
     pub modloader: Option<String>,
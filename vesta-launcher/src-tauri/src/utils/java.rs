@@ -99,7 +99,7 @@ pub fn resolve_required_java_from_manifest(
     }
 
     if let Some(version_meta) = metadata.game_versions.iter().find(|v| v.id == mc_version) {
-        if is_legacy_version_type(&version_meta.version_type) {
+        if is_legacy_version_type(version_meta.version_type.as_str()) {
             return Ok(LEGACY_JAVA_MAJOR);
         }
 
@@ -132,15 +132,7 @@ pub async fn resolve_required_java_major(
         }
     }
 
-    let data_dir = get_app_config_dir()
-        .map_err(|e| e.to_string())?
-        .join("data");
-    let manifest_cache = ManifestCache::new(data_dir.join("manifests"));
-    let preferred = manifest_cache
-        .resolve_java_major_for_version(mc_version)
-        .await
-        .map(preferred_java_major)
-        .map_err(|e| e.to_string())?;
+    let preferred = resolve_java_major_from_version_json(mc_version).await?;
 
     metadata
         .java_major_version_by_game_version
@@ -161,6 +153,20 @@ pub async fn resolve_required_java_major(
     Ok(preferred)
 }
 
+/// Required Java major for `mc_version` from its version JSON, downloading
+/// the JSON when it isn't cached yet.
+pub async fn resolve_java_major_from_version_json(mc_version: &str) -> Result<u32, String> {
+    let data_dir = get_app_config_dir()
+        .map_err(|e| e.to_string())?
+        .join("data");
+    let manifest_cache = ManifestCache::new(data_dir.join("manifests"));
+    manifest_cache
+        .resolve_java_major_for_version(mc_version)
+        .await
+        .map(preferred_java_major)
+        .map_err(|e| e.to_string())
+}
+
 fn is_path_only_java_command(java_path: &str) -> bool {
     matches!(java_path, "java" | "java.exe")
 }
@@ -230,23 +236,35 @@ pub async fn resolve_java_path_for_version(
     ensure_managed_java_preference(required_major)
 }
 
-pub async fn resolve_instance_java_path(
-    app_handle: &AppHandle,
-    instance: &Instance,
-) -> Result<String, String> {
+/// Java explicitly configured for `instance`: its config override, its own
+/// path, then the global one. `None` means the version's managed/active Java
+/// is used.
+pub fn configured_instance_java_path(instance: &Instance) -> Result<Option<String>, String> {
+    if let Some(path) =
+        crate::instance::config_override::InstanceConfigOverride::for_instance(instance)
+            .and_then(|config_override| config_override.java_path)
+    {
+        return Ok(Some(path));
+    }
+
     if !instance.use_global_java_path {
         if let Some(ref path) = instance.java_path {
             if !path.is_empty() {
-                return Ok(path.clone());
+                return Ok(Some(path.clone()));
             }
         }
     }
 
     let app_config = crate::utils::config::get_app_config().map_err(|e| e.to_string())?;
-    if let Some(path) = app_config.java_path.clone() {
-        if !path.is_empty() {
-            return Ok(path);
-        }
+    Ok(app_config.java_path.filter(|path| !path.is_empty()))
+}
+
+pub async fn resolve_instance_java_path(
+    app_handle: &AppHandle,
+    instance: &Instance,
+) -> Result<String, String> {
+    if let Some(path) = configured_instance_java_path(instance)? {
+        return Ok(path);
     }
 
     resolve_java_path_for_version(app_handle, &instance.minecraft_version).await
@@ -654,6 +672,94 @@ pub async fn ensure_java_for_launch(
     .await
 }
 
+/// A Java install offered for an instance by `suggest_java_for_instance`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct JavaSuggestion {
+    pub path: PathBuf,
+    pub major_version: u32,
+    pub is_64bit: bool,
+    pub is_managed: bool,
+    pub matches_required: bool,
+}
+
+/// Order `managed` and `system` installs best first for `required_major`:
+/// exact major, 64-bit, launcher-managed, then the closest major.
+pub fn rank_java_candidates(
+    managed: Vec<jre_manager::DetectedJava>,
+    system: Vec<jre_manager::DetectedJava>,
+    required_major: Option<u32>,
+) -> Vec<JavaSuggestion> {
+    let mut seen = std::collections::HashSet::new();
+    let mut suggestions: Vec<JavaSuggestion> = managed
+        .into_iter()
+        .map(|java| (java, true))
+        .chain(system.into_iter().map(|java| (java, false)))
+        .filter(|(java, _)| seen.insert(java.path.clone()))
+        .map(|(java, is_managed)| JavaSuggestion {
+            matches_required: required_major.is_none_or(|major| java.major_version == major),
+            path: java.path,
+            major_version: java.major_version,
+            is_64bit: java.is_64bit,
+            is_managed,
+        })
+        .collect();
+
+    suggestions.sort_by_key(|java| {
+        let distance = required_major.map_or(0, |major| java.major_version.abs_diff(major));
+        (
+            !java.matches_required,
+            !java.is_64bit,
+            !java.is_managed,
+            distance,
+            std::cmp::Reverse(java.major_version),
+        )
+    });
+    suggestions
+}
+
+/// Outcome of checking an instance's Java override on save. The path is
+/// saved either way; the UI shows `error` or `warnings` next to it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct JavaPathReport {
+    pub path: String,
+    /// Set when the path couldn't be run as Java at all.
+    pub error: Option<String>,
+    pub warnings: Vec<jre_manager::JavaWarning>,
+}
+
+/// Validate a Java override being saved. `Ok(None)` means nothing to report;
+/// `Err` is a 32-bit Java on a 64-bit system, which is never saved.
+pub async fn check_java_override(
+    java_path: &str,
+    required_major: Option<u32>,
+) -> Result<Option<JavaPathReport>, String> {
+    // Bare `java` resolves through PATH at launch time.
+    if is_path_only_java_command(java_path) {
+        return Ok(None);
+    }
+
+    let path = PathBuf::from(java_path);
+    let validation =
+        tokio::task::spawn_blocking(move || jre_manager::validate_java_path(&path, required_major))
+            .await
+            .map_err(|e| e.to_string())?;
+
+    match validation {
+        Ok(validation) if validation.warnings.is_empty() => Ok(None),
+        Ok(validation) => Ok(Some(JavaPathReport {
+            path: java_path.to_string(),
+            error: None,
+            warnings: validation.warnings,
+        })),
+        Err(e) if e.downcast_ref::<jre_manager::JavaArchMismatch>().is_some() => Err(e.to_string()),
+        Err(e) => Ok(Some(JavaPathReport {
+            path: java_path.to_string(),
+            error: Some(e.to_string()),
+            warnings: Vec::new(),
+        })),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -663,4 +769,88 @@ mod tests {
         assert!(!is_configured_java_managed("java", 21));
         assert!(!is_configured_java_managed("java.exe", 21));
     }
+
+    fn detected(path: &str, major_version: u32, is_64bit: bool) -> jre_manager::DetectedJava {
+        jre_manager::DetectedJava {
+            path: PathBuf::from(path),
+            major_version,
+            is_64bit,
+        }
+    }
+
+    #[test]
+    fn java_candidates_rank_required_major_first() {
+        let managed = vec![detected("/jre/zulu-17/bin/java", 17, true)];
+        let system = vec![
+            detected("/usr/lib/jvm/java-8/bin/java", 8, true),
+            detected("/usr/lib/jvm/java-21-x86/bin/java", 21, false),
+            detected("/usr/lib/jvm/java-21/bin/java", 21, true),
+            detected("/jre/zulu-17/bin/java", 17, true),
+        ];
+
+        let ranked = rank_java_candidates(managed, system, Some(21));
+        let paths: Vec<_> = ranked
+            .iter()
+            .map(|java| java.path.to_str().unwrap())
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "/usr/lib/jvm/java-21/bin/java",
+                "/usr/lib/jvm/java-21-x86/bin/java",
+                "/jre/zulu-17/bin/java",
+                "/usr/lib/jvm/java-8/bin/java",
+            ]
+        );
+        assert!(ranked[0].matches_required && !ranked[0].is_managed);
+        assert!(ranked[2].is_managed && !ranked[2].matches_required);
+    }
+
+    #[test]
+    fn java_candidates_prefer_managed_without_requirement() {
+        let ranked = rank_java_candidates(
+            vec![detected("/jre/zulu-17/bin/java", 17, true)],
+            vec![detected("/usr/lib/jvm/java-21/bin/java", 21, true)],
+            None,
+        );
+        assert!(ranked.iter().all(|java| java.matches_required));
+        assert_eq!(ranked[0].major_version, 17);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn java_override_reports_problems_without_rejecting_them() {
+        use std::os::unix::fs::PermissionsExt;
+
+        assert_eq!(check_java_override("java", Some(21)).await, Ok(None));
+
+        let tmp = tempfile::tempdir().unwrap();
+        let missing = tmp.path().join("missing").to_string_lossy().to_string();
+        let report = check_java_override(&missing, Some(21))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(report.error.is_some());
+
+        let java = tmp.path().join("java");
+        std::fs::write(
+            &java,
+            "#!/bin/sh\necho 'openjdk version \"17.0.8\" 2023-07-18' >&2\n\
+             echo 'OpenJDK 64-Bit Server VM (build 17.0.8+7, mixed mode)' >&2\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&java, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let java = java.to_string_lossy().to_string();
+
+        assert_eq!(check_java_override(&java, Some(17)).await, Ok(None));
+        let report = check_java_override(&java, Some(21)).await.unwrap().unwrap();
+        assert_eq!(report.error, None);
+        assert_eq!(
+            report.warnings,
+            vec![jre_manager::JavaWarning::MajorMismatch {
+                found: 17,
+                required: 21
+            }]
+        );
+    }
 }
@@ -112,12 +112,27 @@ pub fn normalize_opened_path(arg: &str) -> Option<String> {
     None
 }
 
+/// Escape hatch that opens an extra launcher window in the running process
+/// instead of focusing the existing one.
+pub const NEW_WINDOW_FLAG: &str = "--new-window";
+
+pub fn wants_new_window(args: &[String]) -> bool {
+    args.iter().skip(1).any(|arg| arg == NEW_WINDOW_FLAG)
+}
+
 pub fn ingest_launch_args(args: &[String]) {
     if args.len() <= 1 {
         return;
     }
 
-    let tail = args[1..].to_vec();
+    let tail: Vec<String> = args[1..]
+        .iter()
+        .filter(|arg| arg.as_str() != NEW_WINDOW_FLAG)
+        .cloned()
+        .collect();
+    if tail.is_empty() {
+        return;
+    }
     let has_cli_or_deeplink = tail
         .iter()
         .any(|arg| arg.starts_with('-') || arg.starts_with("vesta://"));
@@ -231,6 +246,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ingest_launch_args_strips_new_window_flag() {
+        let _guard = TEST_LOCK.lock().expect("test lock");
+        reset_queue();
+        let args = vec!["vesta".to_string(), NEW_WINDOW_FLAG.to_string()];
+        assert!(wants_new_window(&args));
+        ingest_launch_args(&args);
+        assert!(drain_queue().is_empty());
+
+        let args = vec![
+            "vesta".to_string(),
+            NEW_WINDOW_FLAG.to_string(),
+            "vesta://open-instance?slug=my-pack".to_string(),
+        ];
+        ingest_launch_args(&args);
+        assert_eq!(
+            drain_queue(),
+            vec![QueuedIntent::Argv {
+                args: vec!["vesta://open-instance?slug=my-pack".to_string()],
+            }]
+        );
+        assert!(!wants_new_window(&["vesta".to_string()]));
+    }
+
     #[test]
     fn normalize_opened_path_rejects_bare_tokens() {
         assert_eq!(normalize_opened_path("modrinth"), None);
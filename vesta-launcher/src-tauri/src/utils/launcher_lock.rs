@@ -0,0 +1,60 @@
+//! Exclusive lock on the launcher data directory.
+//!
+//! The GUI holds it for its whole lifetime and the headless CLI holds it
+//! while a command writes. The single-instance plugin only stops a second
+//! GUI from starting; this lock also keeps the CLI from writing the data
+//! directory or databases underneath a running launcher.
+
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+
+const LOCK_FILE_NAME: &str = "launcher.lock";
+
+/// Held lock. The GUI keeps it in Tauri state so it lives as long as the app.
+pub struct LauncherLock {
+    _file: File,
+}
+
+fn lock_path(app_config_dir: &Path) -> PathBuf {
+    app_config_dir.join(LOCK_FILE_NAME)
+}
+
+fn open_lock_file(app_config_dir: &Path) -> std::io::Result<File> {
+    std::fs::create_dir_all(app_config_dir)?;
+    OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path(app_config_dir))
+}
+
+/// Take the lock for this process. The lock is released when the returned
+/// guard is dropped or the process exits.
+pub fn acquire(app_config_dir: &Path) -> std::io::Result<LauncherLock> {
+    let file = open_lock_file(app_config_dir)?;
+    match file.try_lock() {
+        Ok(()) => Ok(LauncherLock { _file: file }),
+        Err(TryLockError::WouldBlock) => Err(std::io::Error::new(
+            std::io::ErrorKind::WouldBlock,
+            "another Vesta process holds the launcher lock",
+        )),
+        Err(TryLockError::Error(e)) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lock_is_exclusive_until_dropped() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        let guard = acquire(tmp.path()).unwrap();
+        let err = acquire(tmp.path()).err().unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::WouldBlock);
+
+        drop(guard);
+        assert!(acquire(tmp.path()).is_ok());
+    }
+}
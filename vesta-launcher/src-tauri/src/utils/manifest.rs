@@ -32,9 +32,11 @@ pub async fn queue_manifest_generation(
 pub async fn load_manifest(app_handle: &tauri::AppHandle) -> Result<PistonMetadata, String> {
     // 1. In-memory cache hit — instant
     if let Some(cache) = app_handle.try_state::<MetadataCache>() {
-        if let Some(mut meta) = cache.get() {
+        if let Some(snapshot) = cache.snapshot() {
+            let mut meta = (*snapshot).clone();
+            // Don't overwrite metadata a manifest refresh stored meanwhile.
             if super::java::normalize_metadata_java_requirements(&mut meta) {
-                cache.set(&meta);
+                cache.replace_if_current(&snapshot, meta.clone());
             }
             return Ok(meta);
         }
@@ -3,8 +3,11 @@ pub mod config;
 pub mod crash_fixtures;
 pub mod crash_parser;
 pub mod dialog_manager;
+pub mod event_log;
+pub mod fingerprint_cache;
 pub mod hash;
 pub mod hooks;
+pub mod hs_err;
 pub mod image;
 pub mod java;
 pub mod manifest;
@@ -18,11 +21,15 @@ pub mod file_drop;
 pub mod instance_helpers;
 pub mod instance_runtime;
 pub mod launch_intents;
+pub mod launcher_lock;
+pub mod privacy;
 pub mod process_state;
+pub mod redact;
 pub mod sanitize;
 pub mod storage;
 pub mod texture;
 pub mod url;
 pub mod version_tracking;
 pub mod window_effects;
+pub mod window_state;
 pub mod windows;
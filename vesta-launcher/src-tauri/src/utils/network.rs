@@ -1,6 +1,11 @@
+use anyhow::Result;
+use piston_lib::game::installer::types::DownloadConcurrency;
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
 use tauri::{AppHandle, Emitter};
+use tokio::sync::watch;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -9,31 +14,70 @@ pub enum NetworkStatus {
     Offline,
 }
 
+/// Time between connectivity probes while online.
+const ONLINE_PROBE_INTERVAL: Duration = Duration::from_secs(60);
+/// First retry after a failed probe; doubles per failure up to the online interval.
+const OFFLINE_PROBE_BASE: Duration = Duration::from_secs(5);
+
+/// Connectivity as seen by the background probe, for `get_network_health`.
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkHealth {
+    pub status: NetworkStatus,
+    /// `None` when the platform does not report connection cost.
+    pub metered: Option<bool>,
+    pub consecutive_failures: u32,
+    pub last_checked: Option<String>,
+    pub next_check_secs: u64,
+}
+
+#[derive(Default)]
+struct ProbeState {
+    metered: Option<bool>,
+    consecutive_failures: u32,
+    last_checked: Option<chrono::DateTime<chrono::Utc>>,
+    next_check: Duration,
+}
+
 pub struct NetworkManager {
-    status: Arc<Mutex<NetworkStatus>>,
+    status: Arc<watch::Sender<NetworkStatus>>,
+    probe: Arc<Mutex<ProbeState>>,
     app_handle: AppHandle,
 }
 
 impl NetworkManager {
     pub fn new(app_handle: AppHandle) -> Self {
-        let status = Arc::new(Mutex::new(NetworkStatus::Online));
-        let status_clone = status.clone();
-        let app_handle_clone = app_handle.clone();
+        let (status, _) = watch::channel(NetworkStatus::Online);
+        let manager = Self {
+            status: Arc::new(status),
+            probe: Arc::new(Mutex::new(ProbeState::default())),
+            app_handle,
+        };
 
+        let status = manager.status.clone();
+        let probe = manager.probe.clone();
+        let app_handle = manager.app_handle.clone();
         tauri::async_runtime::spawn(async move {
-            let actual = Self::verify_online_static().await;
-            let mut s = match status_clone.lock() {
-                Ok(guard) => guard,
-                Err(_) => return,
-            };
-            if *s != actual {
-                *s = actual;
-                drop(s);
-                let _ = app_handle_clone.emit("core://network-status-changed", actual);
+            loop {
+                let actual = Self::verify_online_static().await;
+                let metered = detect_metered().await;
+                let delay = {
+                    let mut state = probe.lock().unwrap_or_else(|e| e.into_inner());
+                    state.metered = metered;
+                    state.last_checked = Some(chrono::Utc::now());
+                    if actual == NetworkStatus::Online {
+                        state.consecutive_failures = 0;
+                    } else {
+                        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+                    }
+                    state.next_check = next_probe_delay(state.consecutive_failures);
+                    state.next_check
+                };
+                Self::apply_status(&status, &app_handle, actual);
+                tokio::time::sleep(delay).await;
             }
         });
 
-        Self { status, app_handle }
+        manager
     }
 
     async fn verify_online_static() -> NetworkStatus {
@@ -55,24 +99,390 @@ impl NetworkManager {
     }
 
     pub fn get_status(&self) -> NetworkStatus {
-        self.status
-            .lock()
-            .map(|s| *s)
-            .unwrap_or(NetworkStatus::Offline)
+        *self.status.borrow()
     }
 
     pub fn set_status(&self, new_status: NetworkStatus) {
-        let mut status = match self.status.lock() {
-            Ok(guard) => guard,
-            Err(_) => return,
-        };
-        if *status != new_status {
-            *status = new_status;
+        Self::apply_status(&self.status, &self.app_handle, new_status);
+    }
+
+    fn apply_status(
+        status: &watch::Sender<NetworkStatus>,
+        app_handle: &AppHandle,
+        new_status: NetworkStatus,
+    ) {
+        let changed = status.send_if_modified(|current| {
+            let changed = *current != new_status;
+            *current = new_status;
+            changed
+        });
+        if changed {
             log::info!("[NetworkManager] Status changed to: {:?}", new_status);
-            drop(status);
-            let _ = self
-                .app_handle
-                .emit("core://network-status-changed", new_status);
+            let _ = app_handle.emit("core://network-status-changed", new_status);
+        }
+    }
+
+    /// Receiver that sees every status change, for tasks waiting on the network.
+    pub fn subscribe(&self) -> watch::Receiver<NetworkStatus> {
+        self.status.subscribe()
+    }
+
+    /// Last metered state reported by the platform, if any.
+    pub fn is_metered(&self) -> Option<bool> {
+        self.probe.lock().unwrap_or_else(|e| e.into_inner()).metered
+    }
+
+    pub fn health(&self) -> NetworkHealth {
+        let state = self.probe.lock().unwrap_or_else(|e| e.into_inner());
+        NetworkHealth {
+            status: self.get_status(),
+            metered: state.metered,
+            consecutive_failures: state.consecutive_failures,
+            last_checked: state.last_checked.map(|time| time.to_rfc3339()),
+            next_check_secs: state.next_check.as_secs(),
+        }
+    }
+}
+
+/// Delay before the next probe: the regular interval while online, and an
+/// exponential backoff from [`OFFLINE_PROBE_BASE`] while probes keep failing.
+fn next_probe_delay(consecutive_failures: u32) -> Duration {
+    if consecutive_failures == 0 {
+        return ONLINE_PROBE_INTERVAL;
+    }
+    let factor = 1u32 << (consecutive_failures - 1).min(8);
+    (OFFLINE_PROBE_BASE * factor).min(ONLINE_PROBE_INTERVAL)
+}
+
+/// Whether the active connection is metered, as reported by the platform:
+/// the connection cost API on Windows and NetworkManager on Linux. `None`
+/// when it cannot be determined.
+pub async fn detect_metered() -> Option<bool> {
+    tokio::task::spawn_blocking(detect_metered_blocking)
+        .await
+        .ok()
+        .flatten()
+}
+
+#[cfg(target_os = "windows")]
+fn detect_metered_blocking() -> Option<bool> {
+    use piston_lib::utils::process::PistonCommandExt;
+
+    let script = "$p = [Windows.Networking.Connectivity.NetworkInformation,Windows.Networking.Connectivity,ContentType=WindowsRuntime]::GetInternetConnectionProfile(); \
+                  if ($p) { $p.GetConnectionCost().NetworkCostType }";
+    let output = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-Command", script])
+        .suppress_console()
+        .output()
+        .ok()?;
+    parse_windows_cost_type(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(target_os = "linux")]
+fn detect_metered_blocking() -> Option<bool> {
+    let output = std::process::Command::new("busctl")
+        .args([
+            "--system",
+            "get-property",
+            "org.freedesktop.NetworkManager",
+            "/org/freedesktop/NetworkManager",
+            "org.freedesktop.NetworkManager",
+            "Metered",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_nm_metered(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn detect_metered_blocking() -> Option<bool> {
+    None
+}
+
+/// `NetworkCostType` from the Windows connection profile: `Fixed` and
+/// `Variable` plans are metered.
+#[cfg_attr(not(any(target_os = "windows", test)), allow(dead_code))]
+fn parse_windows_cost_type(output: &str) -> Option<bool> {
+    match output.trim() {
+        "Unrestricted" => Some(false),
+        "Fixed" | "Variable" => Some(true),
+        _ => None,
+    }
+}
+
+/// NetworkManager's `NMMetered` as printed by busctl (`u 1`): 1 and 3 are
+/// metered (set or guessed), 2 and 4 are not, 0 is unknown.
+#[cfg_attr(not(any(target_os = "linux", test)), allow(dead_code))]
+fn parse_nm_metered(output: &str) -> Option<bool> {
+    let value: u32 = output.trim().strip_prefix("u ")?.trim().parse().ok()?;
+    match value {
+        1 | 3 => Some(true),
+        2 | 4 => Some(false),
+        _ => None,
+    }
+}
+
+/// What to do about large downloads on a metered connection
+/// (`AppConfig::allow_large_downloads_on_metered`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MeteredDownloadPolicy {
+    Ask,
+    Never,
+    Always,
+}
+
+impl MeteredDownloadPolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Ask => "ask",
+            Self::Never => "never",
+            Self::Always => "always",
+        }
+    }
+
+    /// Unknown values fall back to asking.
+    pub fn from_config(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "never" => Self::Never,
+            "always" => Self::Always,
+            _ => Self::Ask,
         }
     }
 }
+
+/// Largest per-phase install download concurrency; more only adds contention.
+const MAX_DOWNLOAD_CONCURRENCY: i32 = 64;
+
+/// Per-phase install download limits from `AppConfig`.
+pub fn download_concurrency(config: &crate::utils::config::AppConfig) -> DownloadConcurrency {
+    let limit = |value: i32| value.clamp(1, MAX_DOWNLOAD_CONCURRENCY) as usize;
+    DownloadConcurrency {
+        assets: limit(config.asset_concurrency),
+        libraries: limit(config.library_concurrency),
+        mods: limit(config.mod_download_concurrency),
+    }
+}
+
+/// Install download limits for `instance`: the app settings, unless the
+/// instance overrides its download threads.
+pub fn instance_download_concurrency(
+    instance: &crate::models::instance::Instance,
+) -> DownloadConcurrency {
+    let defaults = crate::utils::config::get_app_config()
+        .map(|config| download_concurrency(&config))
+        .unwrap_or_default();
+    crate::instance::config_override::InstanceConfigOverride::for_instance(instance)
+        .unwrap_or_default()
+        .download_concurrency(defaults)
+}
+
+/// Longest a mirror may take to serve the benchmark file.
+const MIRROR_BENCHMARK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+type BenchmarkCache = HashMap<(Vec<String>, String), Vec<(String, u64)>>;
+
+static MIRROR_BENCHMARKS: OnceLock<Mutex<BenchmarkCache>> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MirrorBenchmarkResult {
+    pub url: String,
+    pub latency_ms: Option<u64>,
+    pub reachable: bool,
+}
+
+/// Picks the fastest download mirror before a large batch download.
+pub struct MirrorSelector;
+
+impl MirrorSelector {
+    /// Download `test_path` from every candidate concurrently and return the
+    /// mirrors that served it, fastest first, with their latency in
+    /// milliseconds. Results are cached for the session; a run where nothing
+    /// answered is not, so a later call can retry once the network is back.
+    pub async fn benchmark(candidates: Vec<String>, test_path: &str) -> Result<Vec<(String, u64)>> {
+        if candidates.is_empty() {
+            anyhow::bail!("No mirrors to benchmark");
+        }
+        let key = (candidates.clone(), test_path.to_string());
+        if let Some(cached) = Self::cache().get(&key) {
+            return Ok(cached.clone());
+        }
+
+        let client = piston_lib::client::shared_client();
+        let path = test_path.trim_start_matches('/');
+        let timings = futures::future::join_all(candidates.iter().map(|mirror| {
+            let url = format!("{}/{}", mirror.trim_end_matches('/'), path);
+            async move {
+                let latency = Self::download_latency(client, &url).await;
+                match latency {
+                    Some(ms) => log::info!("[MirrorSelector] {} served in {}ms", mirror, ms),
+                    None => log::info!("[MirrorSelector] {} unreachable", mirror),
+                }
+                latency.map(|ms| (mirror.clone(), ms))
+            }
+        }))
+        .await;
+
+        let mut ranked: Vec<(String, u64)> = timings.into_iter().flatten().collect();
+        ranked.sort_by_key(|(_, ms)| *ms);
+        if !ranked.is_empty() {
+            Self::cache().insert(key, ranked.clone());
+        }
+        Ok(ranked)
+    }
+
+    /// Benchmark the bundled asset mirrors and make the fastest the preferred
+    /// host for asset downloads this session.
+    pub async fn select_asset_mirror() -> Result<Vec<(String, u64)>> {
+        use piston_lib::game::installer::asset_mirrors;
+
+        let ranked = Self::benchmark(
+            asset_mirrors::BUNDLED_ASSET_MIRRORS
+                .iter()
+                .map(|mirror| mirror.to_string())
+                .collect(),
+            asset_mirrors::BENCHMARK_ASSET_PATH,
+        )
+        .await?;
+        asset_mirrors::set_preferred_mirror(ranked.first().map(|(url, _)| url.as_str()))?;
+        Ok(ranked)
+    }
+
+    async fn download_latency(client: &reqwest::Client, url: &str) -> Option<u64> {
+        let started = std::time::Instant::now();
+        let download = async {
+            let response = client.get(url).send().await.ok()?.error_for_status().ok()?;
+            response.bytes().await.ok()
+        };
+        tokio::time::timeout(MIRROR_BENCHMARK_TIMEOUT, download)
+            .await
+            .ok()??;
+        Some(started.elapsed().as_millis() as u64)
+    }
+
+    fn cache() -> std::sync::MutexGuard<'static, BenchmarkCache> {
+        MIRROR_BENCHMARKS
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Every candidate with its benchmark latency, reachable mirrors first.
+pub fn benchmark_results(
+    candidates: &[String],
+    ranked: &[(String, u64)],
+) -> Vec<MirrorBenchmarkResult> {
+    let mut results: Vec<MirrorBenchmarkResult> = ranked
+        .iter()
+        .map(|(url, ms)| MirrorBenchmarkResult {
+            url: url.clone(),
+            latency_ms: Some(*ms),
+            reachable: true,
+        })
+        .collect();
+    results.extend(
+        candidates
+            .iter()
+            .filter(|url| !ranked.iter().any(|(ranked_url, _)| ranked_url == *url))
+            .map(|url| MirrorBenchmarkResult {
+                url: url.clone(),
+                latency_ms: None,
+                reachable: false,
+            }),
+    );
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn download_concurrency_is_clamped_per_phase() {
+        let config = crate::utils::config::AppConfig {
+            asset_concurrency: 500,
+            library_concurrency: 0,
+            mod_download_concurrency: 6,
+            ..Default::default()
+        };
+        assert_eq!(
+            download_concurrency(&config),
+            DownloadConcurrency {
+                assets: 64,
+                libraries: 1,
+                mods: 6,
+            }
+        );
+    }
+
+    #[test]
+    fn offline_probes_back_off_to_the_online_interval() {
+        assert_eq!(next_probe_delay(0), ONLINE_PROBE_INTERVAL);
+        assert_eq!(next_probe_delay(1), Duration::from_secs(5));
+        assert_eq!(next_probe_delay(2), Duration::from_secs(10));
+        assert_eq!(next_probe_delay(3), Duration::from_secs(20));
+        assert_eq!(next_probe_delay(5), ONLINE_PROBE_INTERVAL);
+        assert_eq!(next_probe_delay(u32::MAX), ONLINE_PROBE_INTERVAL);
+    }
+
+    #[test]
+    fn platform_metered_reports_are_parsed() {
+        assert_eq!(parse_nm_metered("u 1\n"), Some(true));
+        assert_eq!(parse_nm_metered("u 3"), Some(true));
+        assert_eq!(parse_nm_metered("u 4"), Some(false));
+        assert_eq!(parse_nm_metered("u 0"), None);
+        assert_eq!(parse_nm_metered("garbage"), None);
+
+        assert_eq!(parse_windows_cost_type("Variable\r\n"), Some(true));
+        assert_eq!(parse_windows_cost_type("Unrestricted"), Some(false));
+        assert_eq!(parse_windows_cost_type("Unknown"), None);
+        assert_eq!(parse_windows_cost_type(""), None);
+    }
+
+    #[test]
+    fn metered_policy_defaults_to_asking() {
+        assert_eq!(
+            MeteredDownloadPolicy::from_config("Never"),
+            MeteredDownloadPolicy::Never
+        );
+        assert_eq!(
+            MeteredDownloadPolicy::from_config("always"),
+            MeteredDownloadPolicy::Always
+        );
+        assert_eq!(
+            MeteredDownloadPolicy::from_config("bogus"),
+            MeteredDownloadPolicy::Ask
+        );
+        assert_eq!(MeteredDownloadPolicy::Ask.as_str(), "ask");
+    }
+
+    #[test]
+    fn unreachable_mirrors_are_listed_after_the_ranked_ones() {
+        let candidates = vec![
+            "https://a.example/".to_string(),
+            "https://b.example/".to_string(),
+            "https://c.example/".to_string(),
+        ];
+        let ranked = vec![
+            ("https://c.example/".to_string(), 40),
+            ("https://a.example/".to_string(), 120),
+        ];
+        let results = benchmark_results(&candidates, &ranked);
+        let urls: Vec<&str> = results.iter().map(|r| r.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://c.example/",
+                "https://a.example/",
+                "https://b.example/"
+            ]
+        );
+        assert_eq!(results[0].latency_ms, Some(40));
+        assert!(!results[2].reachable);
+        assert_eq!(results[2].latency_ms, None);
+    }
+}
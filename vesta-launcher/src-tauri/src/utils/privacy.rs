@@ -0,0 +1,107 @@
+//! Consent for anonymized usage statistics.
+//!
+//! Nothing is transmitted yet; this only records the user's answer so future
+//! analytics can check it. The local event log (`utils::event_log`) is kept
+//! regardless of consent and never leaves the machine unless the user adds it
+//! to a diagnostics bundle.
+
+use serde::{Deserialize, Serialize};
+
+/// The user's answer to the usage statistics prompt
+/// (`AppConfig::usage_stats_consent`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageStatsConsent {
+    Unset,
+    Accepted,
+    Declined,
+}
+
+impl UsageStatsConsent {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Unset => "unset",
+            Self::Accepted => "accepted",
+            Self::Declined => "declined",
+        }
+    }
+
+    /// Unknown values count as unanswered, so the user is asked again.
+    pub fn from_config(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "accepted" => Self::Accepted,
+            "declined" => Self::Declined,
+            _ => Self::Unset,
+        }
+    }
+
+    /// Only an explicit accept allows collection.
+    pub fn allows_collection(self) -> bool {
+        self == Self::Accepted
+    }
+
+    /// Apply the user's answer. Once answered, consent can be changed either
+    /// way but never reset to unanswered.
+    pub fn transition(self, next: Self) -> Result<Self, String> {
+        match next {
+            Self::Unset if self != Self::Unset => {
+                Err("Usage statistics consent cannot be reset to unset".to_string())
+            }
+            _ => Ok(next),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PrivacySettings {
+    pub usage_stats_consent: UsageStatsConsent,
+    /// Rows currently in the local event log.
+    pub event_log_entries: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consent_defaults_to_unset_for_unknown_values() {
+        assert_eq!(
+            UsageStatsConsent::from_config("accepted"),
+            UsageStatsConsent::Accepted
+        );
+        assert_eq!(
+            UsageStatsConsent::from_config(" Declined "),
+            UsageStatsConsent::Declined
+        );
+        assert_eq!(UsageStatsConsent::from_config(""), UsageStatsConsent::Unset);
+        assert_eq!(
+            UsageStatsConsent::from_config("yes"),
+            UsageStatsConsent::Unset
+        );
+        assert_eq!(
+            crate::utils::config::AppConfig::default().usage_stats_consent,
+            "unset"
+        );
+    }
+
+    #[test]
+    fn answered_consent_can_change_but_not_reset() {
+        use UsageStatsConsent::*;
+
+        assert_eq!(Unset.transition(Unset), Ok(Unset));
+        assert_eq!(Unset.transition(Accepted), Ok(Accepted));
+        assert_eq!(Unset.transition(Declined), Ok(Declined));
+        assert_eq!(Accepted.transition(Declined), Ok(Declined));
+        assert_eq!(Declined.transition(Accepted), Ok(Accepted));
+        assert_eq!(Accepted.transition(Accepted), Ok(Accepted));
+        assert!(Accepted.transition(Unset).is_err());
+        assert!(Declined.transition(Unset).is_err());
+    }
+
+    #[test]
+    fn only_accepted_consent_allows_collection() {
+        assert!(UsageStatsConsent::Accepted.allows_collection());
+        assert!(!UsageStatsConsent::Declined.allows_collection());
+        assert!(!UsageStatsConsent::Unset.allows_collection());
+    }
+}
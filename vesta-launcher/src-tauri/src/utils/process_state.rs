@@ -31,12 +31,16 @@ pub struct InstanceRunState {
 
     /// Timestamp when the process was started
     pub started_at: String,
+
+    /// Whether this session was launched in safe mode
+    #[serde(default)]
+    pub safe_mode: bool,
 }
 
 /// Get the path to the process state file in app data
 fn get_process_state_file() -> Result<PathBuf> {
     let app_dir = crate::utils::db_manager::get_app_config_dir()?;
-    Ok(app_dir.join("running_processes.json"))
+    Ok(app_dir.join(piston_lib::game::launcher::RUN_STATE_FILE_NAME))
 }
 
 /// Load all persisted running process states
@@ -104,6 +108,7 @@ mod tests {
             version_id: "1.20.1".to_string(),
             modloader: None,
             started_at: "2025-12-17T00:00:00Z".to_string(),
+            safe_mode: false,
         };
 
         let json = serde_json::to_string(&state).unwrap();
@@ -1,59 +1,90 @@
-use std::borrow::Cow;
+use unicode_normalization::UnicodeNormalization;
+
+/// Maximum slug length in bytes (slugs are always ASCII).
+const MAX_SLUG_LEN: usize = 64;
 
 /// Convert a user-provided instance name into a filesystem-safe slug suitable
 /// for using as an instance ID and folder name.
+///
+/// The name is NFC-normalized so precomposed and combining accents match, then
+/// transliterated to ASCII with `deunicode` (a port of Unidecode's tables):
+/// accents are dropped and other scripts, CJK included, are romanized. Anything
+/// left that isn't `[a-z0-9_]` (punctuation, whitespace, characters invalid in
+/// Windows or Unix file names) becomes a single hyphen.
 pub fn sanitize_instance_name(name: &str) -> String {
-    // Quick normalization: trim and lower-case
+    let normalized: String = name.trim().nfc().collect();
+    let ascii = deunicode::deunicode_with_tofu(&normalized, "-");
+
+    let mut out = String::with_capacity(ascii.len());
+    for c in ascii.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            out.push(c.to_ascii_lowercase());
+        } else if !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+
+    let slug = out.trim_matches('-');
+    let slug = slug[..slug.len().min(MAX_SLUG_LEN)].trim_end_matches('-');
+    if slug.is_empty() {
+        "instance".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// The slug mapping used before names were transliterated: ASCII letters,
+/// digits, `-` and `_` are kept and every other character becomes a hyphen.
+///
+/// Instances created before the switch have no stored slug and keep this
+/// mapping, so their ids, shortcuts and deep links stay valid. The frontend's
+/// `sanitizeInstanceName` mirrors it.
+pub fn legacy_instance_slug(name: &str) -> String {
     let n = name.trim().to_lowercase();
 
-    // Build an ASCII-friendly slug: allow a-z, 0-9, hyphen and underscore
-    // convert whitespace and other punctuation into single hyphens
     let mut out = String::with_capacity(n.len());
     let mut last_was_dash = false;
-
     for ch in n.chars() {
-        if ch.is_ascii_alphanumeric() {
+        if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
             out.push(ch);
             last_was_dash = false;
-        } else if ch == '-' || ch == '_' {
-            out.push(ch);
-            last_was_dash = false;
-        } else if ch.is_whitespace() || ch.is_ascii_punctuation() {
-            if !last_was_dash {
-                out.push('-');
-                last_was_dash = true;
-            }
-        } else {
-            // skip other characters (non-ascii, combining marks, etc.)
-            // This deliberately removes characters which might be unsafe on
-            // some platforms or lead to confusing filesystem names.
-            if !last_was_dash {
-                out.push('-');
-                last_was_dash = true;
-            }
+        } else if !last_was_dash {
+            out.push('-');
+            last_was_dash = true;
         }
     }
 
-    // Trim leading/trailing dashes
     let slug = out.trim_matches('-');
-
-    // Fallback to "instance" if empty
     let slug = if slug.is_empty() { "instance" } else { slug };
+    slug[..slug.len().min(MAX_SLUG_LEN)].to_string()
+}
 
-    // Limit length to a reasonable maximum (64 characters)
-    let s: Cow<'_, str> = if slug.len() > 64 {
-        Cow::Owned(slug[..64].to_string())
-    } else {
-        Cow::Borrowed(slug)
-    };
-
-    s.into_owned()
+/// Slug of an instance row: the stored `slug` column, or the legacy mapping of
+/// its name for rows created before slugs were stored.
+pub fn resolve_instance_slug(name: &str, stored_slug: Option<&str>) -> String {
+    match stored_slug.filter(|slug| !slug.is_empty()) {
+        Some(slug) => slug.to_string(),
+        None => legacy_instance_slug(name),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn assert_slug_shape(slug: &str) {
+        assert!(!slug.is_empty());
+        assert!(slug.len() <= MAX_SLUG_LEN, "{} is too long", slug);
+        assert!(
+            slug.chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_'),
+            "{} has invalid characters",
+            slug
+        );
+        assert!(!slug.starts_with('-') && !slug.ends_with('-'), "{}", slug);
+        assert!(!slug.contains("--"), "{}", slug);
+    }
+
     #[test]
     fn basic_slug() {
         assert_eq!(sanitize_instance_name("My Instance"), "my-instance");
@@ -64,4 +95,196 @@ mod tests {
         assert!(s.len() <= 64, "slug should be at most 64 characters");
         assert!(s.starts_with("a-very-long-name-that-should-be-truncated-because-it-exceeds"));
     }
+
+    #[test]
+    fn windows_reserved_characters_become_single_hyphens() {
+        assert_eq!(
+            sanitize_instance_name("a<b>c:d\"e|f?g*h"),
+            "a-b-c-d-e-f-g-h"
+        );
+        assert_eq!(
+            sanitize_instance_name("mods / 1.20 \\ fabric"),
+            "mods-1-20-fabric"
+        );
+        assert_eq!(sanitize_instance_name("<<>>::??"), "instance");
+    }
+
+    #[test]
+    fn collapses_and_trims_hyphens() {
+        assert_eq!(
+            sanitize_instance_name("--Survival---World--"),
+            "survival-world"
+        );
+        assert_eq!(sanitize_instance_name("a - - b"), "a-b");
+        assert_eq!(
+            sanitize_instance_name("tab\tand\nnewline"),
+            "tab-and-newline"
+        );
+    }
+
+    #[test]
+    fn strips_latin_accents() {
+        assert_eq!(sanitize_instance_name("Café Crème"), "cafe-creme");
+        assert_eq!(sanitize_instance_name("Ñandú"), "nandu");
+        assert_eq!(sanitize_instance_name("Žluťoučký kůň"), "zlutoucky-kun");
+        assert_eq!(
+            sanitize_instance_name("Zażółć gęślą jaźń"),
+            "zazolc-gesla-jazn"
+        );
+    }
+
+    #[test]
+    fn precomposed_and_combining_forms_match() {
+        let composed = "R\u{00E9}sum\u{00E9}";
+        let decomposed = "Re\u{0301}sume\u{0301}";
+        assert_eq!(sanitize_instance_name(composed), "resume");
+        assert_eq!(sanitize_instance_name(decomposed), "resume");
+    }
+
+    #[test]
+    fn latin_letters_without_decomposition() {
+        assert_eq!(sanitize_instance_name("Straße"), "strasse");
+        assert_eq!(sanitize_instance_name("Ærø Øl"), "aero-ol");
+        assert_eq!(sanitize_instance_name("Łódź"), "lodz");
+        assert_eq!(sanitize_instance_name("Þórr"), "thorr");
+        assert_eq!(sanitize_instance_name("cœur"), "coeur");
+    }
+
+    #[test]
+    fn compatibility_forms_are_folded() {
+        assert_eq!(sanitize_instance_name("ＦＵＬＬ　ＷＩＤＴＨ"), "full-width");
+        assert_eq!(sanitize_instance_name("ﬁnal ﬂight"), "final-flight");
+        assert_eq!(sanitize_instance_name("Season ²"), "season-2");
+    }
+
+    #[test]
+    fn transliterates_russian() {
+        assert_eq!(sanitize_instance_name("Привет мир"), "privet-mir");
+        assert_eq!(sanitize_instance_name("Ёжик в тумане"), "iozhik-v-tumane");
+        assert_eq!(sanitize_instance_name("Щукин Объект"), "shchukin-ob-ekt");
+    }
+
+    #[test]
+    fn transliterates_ukrainian_and_serbian() {
+        assert_eq!(sanitize_instance_name("Київ"), "kiyiv");
+        assert_eq!(sanitize_instance_name("Љубљана"), "ljubljana");
+    }
+
+    #[test]
+    fn transliterates_greek() {
+        assert_eq!(sanitize_instance_name("Ελλάδα"), "ellada");
+        assert_eq!(sanitize_instance_name("Ψυχή"), "psukhe");
+        assert_eq!(sanitize_instance_name("θάλασσα"), "thalassa");
+    }
+
+    #[test]
+    fn transliterates_arabic_and_persian() {
+        assert_eq!(sanitize_instance_name("مرحبا"), "mrhb");
+        assert_eq!(sanitize_instance_name("سيرفر ١٢"), "syrfr-12");
+        assert_eq!(sanitize_instance_name("پیچ"), "pych");
+    }
+
+    #[test]
+    fn transliterates_hebrew_and_thai() {
+        assert_eq!(sanitize_instance_name("גשר"), "gshr");
+        assert_eq!(sanitize_instance_name("ภาษาไทย"), "phaasaaaithy");
+    }
+
+    #[test]
+    fn transliterates_chinese() {
+        assert_eq!(sanitize_instance_name("我的世界"), "wo-de-shi-jie");
+        assert_eq!(sanitize_instance_name("  我的世界 "), "wo-de-shi-jie");
+        assert_eq!(sanitize_instance_name("世界"), "shi-jie");
+    }
+
+    #[test]
+    fn transliterates_japanese_and_korean() {
+        assert_eq!(sanitize_instance_name("マインクラフト"), "mainkurahuto");
+        assert_eq!(sanitize_instance_name("마인크래프트"), "mainkeuraepeuteu");
+    }
+
+    #[test]
+    fn mixed_cjk_keeps_the_ascii_part() {
+        assert_eq!(
+            sanitize_instance_name("Create 机械动力 1.20"),
+            "create-ji-jie-dong-li-1-20"
+        );
+    }
+
+    #[test]
+    fn emoji_are_spelled_out() {
+        assert_eq!(
+            sanitize_instance_name("🔥 Fire Pack 🔥"),
+            "fire-fire-pack-fire"
+        );
+        assert_eq!(sanitize_instance_name("❤️"), "heart");
+    }
+
+    #[test]
+    fn mixed_scripts() {
+        assert_eq!(
+            sanitize_instance_name("Café Москва Αθήνα"),
+            "cafe-moskva-athena"
+        );
+        assert_eq!(
+            sanitize_instance_name("SkyBlock — Остров #2"),
+            "skyblock-ostrov-2"
+        );
+    }
+
+    #[test]
+    fn long_transliterations_are_cut_to_64() {
+        let slug = sanitize_instance_name(&"Щ".repeat(40));
+        assert_eq!(slug.len(), MAX_SLUG_LEN);
+        assert_slug_shape(&slug);
+    }
+
+    #[test]
+    fn truncation_never_leaves_a_trailing_hyphen() {
+        let name = format!("{} tail", "a".repeat(63));
+        assert_eq!(sanitize_instance_name(&name), "a".repeat(63));
+    }
+
+    #[test]
+    fn control_characters_are_separators() {
+        assert_eq!(
+            sanitize_instance_name("bad\u{0001}name\u{001F}x"),
+            "bad-name-x"
+        );
+    }
+
+    #[test]
+    fn output_is_always_a_valid_slug() {
+        for name in [
+            "Привет, 世界! 🌍",
+            "ελληνικά/русский\\العربية",
+            "   ",
+            "...",
+            "CON",
+            "a_b-c",
+            "Team👨‍👩‍👧Family",
+        ] {
+            assert_slug_shape(&sanitize_instance_name(name));
+        }
+    }
+
+    #[test]
+    fn legacy_mapping_is_unchanged() {
+        assert_eq!(legacy_instance_slug("My Instance"), "my-instance");
+        assert_eq!(legacy_instance_slug("Mod--Pack"), "mod--pack");
+        assert_eq!(legacy_instance_slug("Café Crème"), "caf-cr-me");
+        assert_eq!(legacy_instance_slug("Create 机械动力 1.20"), "create-1-20");
+        assert_eq!(legacy_instance_slug("Привет мир"), "instance");
+        assert_eq!(legacy_instance_slug(&"a".repeat(70)), "a".repeat(64));
+    }
+
+    #[test]
+    fn stored_slug_wins_over_legacy_mapping() {
+        assert_eq!(
+            resolve_instance_slug("Привет мир", Some("privet-mir")),
+            "privet-mir"
+        );
+        assert_eq!(resolve_instance_slug("Café Crème", None), "caf-cr-me");
+        assert_eq!(resolve_instance_slug("Café Crème", Some("")), "caf-cr-me");
+    }
 }
@@ -498,6 +498,24 @@ pub fn enforce_governed_cache_limit(
     Ok(())
 }
 
+/// Delete artifact blobs no install references anymore. Must not run while
+/// an install is ingesting artifacts.
+pub fn collect_artifact_cache_garbage(
+    config_dir: &Path,
+) -> Result<piston_lib::game::installer::cache::GCStats, String> {
+    let mut artifact_cache =
+        piston_lib::game::installer::cache::ArtifactCache::load_with_labels(config_dir)
+            .map_err(|e| format!("Failed to load artifact cache: {}", e))?;
+    let stats = artifact_cache
+        .collect_garbage()
+        .map_err(|e| format!("Artifact cache GC failed: {}", e))?;
+    artifact_cache
+        .save()
+        .map_err(|e| format!("Failed to save artifact cache after GC: {}", e))?;
+    invalidate_storage_snapshot_cache();
+    Ok(stats)
+}
+
 pub fn clear_storage_path(path: &Path) -> Result<(), String> {
     if !path.exists() {
         return Ok(());
@@ -562,4 +580,34 @@ mod tests {
     fn cache_clear_policy_includes_runtime_modpack_cache() {
         assert!(cache_clear_targets().contains(&CacheClearTarget::RuntimeModpackCache));
     }
+
+    #[test]
+    fn cache_clear_policy_includes_artifact_cache() {
+        assert!(cache_clear_targets().contains(&CacheClearTarget::ArtifactCache));
+    }
+
+    #[test]
+    fn artifact_cache_gc_reclaims_unreferenced_blobs_and_saves_the_index() {
+        use piston_lib::game::installer::cache::{ArtifactCache, InstallArtifactRef};
+
+        let config_dir = tempfile::tempdir().unwrap();
+        let mut cache = ArtifactCache::load_with_labels(config_dir.path()).unwrap();
+        let kept_file = config_dir.path().join("kept.jar");
+        let dropped_file = config_dir.path().join("dropped.jar");
+        fs::write(&kept_file, b"kept").unwrap();
+        fs::write(&dropped_file, b"dropped").unwrap();
+        let kept = cache.ingest_file(&kept_file, None, None).unwrap();
+        let dropped = cache.ingest_file(&dropped_file, None, None).unwrap();
+        cache.record_install("1.20.1", None, &[InstallArtifactRef::new("kept", &kept)]);
+        cache.save().unwrap();
+
+        let stats = collect_artifact_cache_garbage(config_dir.path()).unwrap();
+
+        assert_eq!(stats.files_deleted, 1);
+        assert_eq!(stats.bytes_reclaimed, b"dropped".len() as u64);
+        let reloaded = ArtifactCache::load_with_labels(config_dir.path()).unwrap();
+        assert!(reloaded.has_artifact(&kept));
+        assert!(!reloaded.has_artifact(&dropped));
+        assert!(reloaded.get_artifact_path(&kept).exists());
+    }
 }
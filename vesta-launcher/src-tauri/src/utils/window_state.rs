@@ -0,0 +1,417 @@
+//! Per-window geometry persistence.
+//!
+//! Position, size, maximized state and monitor are stored in the config
+//! database's `window_state` table, keyed by window label. Saves are debounced
+//! while the user drags or resizes; restores clamp onto a monitor that is still
+//! connected so a window never comes back off-screen.
+
+use crate::schema::config::window_state::dsl as ws_dsl;
+use crate::utils::db::get_config_conn;
+use diesel::prelude::*;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{Manager, PhysicalPosition, PhysicalSize};
+
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Fraction of the work area used when a saved size no longer fits, or when a
+/// maximized window needs an un-maximize size.
+const FALLBACK_AREA_FRACTION: f64 = 0.8;
+
+lazy_static! {
+    static ref PENDING_SAVES: Mutex<HashMap<String, u64>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, Clone, Queryable, Insertable, AsChangeset)]
+#[diesel(table_name = crate::schema::config::window_state)]
+pub struct WindowState {
+    pub label: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub maximized: bool,
+    pub monitor_name: Option<String>,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Rect {
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
+impl Rect {
+    fn intersects(&self, other: &Rect) -> bool {
+        self.x < other.x + other.width
+            && other.x < self.x + self.width
+            && self.y < other.y + other.height
+            && other.y < self.y + self.height
+    }
+
+    fn center(&self) -> (i64, i64) {
+        (
+            self.x as i64 + self.width as i64 / 2,
+            self.y as i64 + self.height as i64 / 2,
+        )
+    }
+
+    /// Squared distance from `point` to the nearest point of this rect.
+    fn distance_sq(&self, (px, py): (i64, i64)) -> i64 {
+        let dx = (self.x as i64 - px)
+            .max(0)
+            .max(px - (self.x as i64 + self.width as i64));
+        let dy = (self.y as i64 - py)
+            .max(0)
+            .max(py - (self.y as i64 + self.height as i64));
+        dx * dx + dy * dy
+    }
+}
+
+struct MonitorArea {
+    name: Option<String>,
+    work_area: Rect,
+}
+
+/// Windows that share a persisted geometry. Reusable page viewers and extra
+/// launcher windows get a new numbered label per session, so each kind maps to
+/// one key.
+pub fn state_key(label: &str) -> &str {
+    if label.starts_with("page-viewer-") {
+        "page-viewer"
+    } else if label.starts_with(crate::utils::windows::EXTRA_LAUNCHER_WINDOW_PREFIX) {
+        "launcher"
+    } else {
+        label
+    }
+}
+
+/// Pick the monitor to restore onto: the saved one by name if it is still
+/// connected and the window overlaps it, otherwise the monitor nearest to the
+/// saved window's center.
+fn pick_monitor<'a>(
+    saved: &Rect,
+    monitor_name: Option<&str>,
+    monitors: &'a [MonitorArea],
+) -> Option<&'a MonitorArea> {
+    if let Some(name) = monitor_name {
+        if let Some(monitor) = monitors
+            .iter()
+            .find(|m| m.name.as_deref() == Some(name) && m.work_area.intersects(saved))
+        {
+            return Some(monitor);
+        }
+    }
+    let center = saved.center();
+    monitors
+        .iter()
+        .min_by_key(|m| m.work_area.distance_sq(center))
+}
+
+/// Shrink `saved` to fit `area` and move it fully inside. Sizes that no longer
+/// fit fall back to a centered fraction of the work area.
+fn fit_to_work_area(saved: Rect, area: Rect) -> Rect {
+    let fits = saved.width <= area.width && saved.height <= area.height;
+    let (width, height) = if fits {
+        (saved.width, saved.height)
+    } else {
+        (
+            (area.width as f64 * FALLBACK_AREA_FRACTION) as i32,
+            (area.height as f64 * FALLBACK_AREA_FRACTION) as i32,
+        )
+    };
+
+    let (x, y) = if fits && saved.intersects(&area) {
+        (
+            saved.x.clamp(area.x, area.x + area.width - width),
+            saved.y.clamp(area.y, area.y + area.height - height),
+        )
+    } else {
+        (
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+        )
+    };
+
+    Rect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+/// Un-maximize geometry for a window saved while maximized. A saved size that
+/// covers (nearly) the whole work area would make un-maximizing a no-op.
+fn unmaximize_rect(saved: Rect, area: Rect) -> Rect {
+    let covers_area = saved.width as f64 >= area.width as f64 * 0.95
+        && saved.height as f64 >= area.height as f64 * 0.95;
+    if covers_area {
+        fit_to_work_area(
+            Rect {
+                width: area.width + 1,
+                height: area.height + 1,
+                ..saved
+            },
+            area,
+        )
+    } else {
+        fit_to_work_area(saved, area)
+    }
+}
+
+fn load_state(key: &str) -> Option<WindowState> {
+    let mut conn = get_config_conn().ok()?;
+    ws_dsl::window_state
+        .filter(ws_dsl::label.eq(key))
+        .first::<WindowState>(&mut conn)
+        .optional()
+        .ok()
+        .flatten()
+}
+
+fn save_state(state: &WindowState) -> Result<(), String> {
+    let mut conn = get_config_conn().map_err(|e| e.to_string())?;
+    diesel::insert_into(ws_dsl::window_state)
+        .values(state)
+        .on_conflict(ws_dsl::label)
+        .do_update()
+        .set(state)
+        .execute(&mut conn)
+        .map_err(|e| format!("Failed to save window state: {}", e))?;
+    Ok(())
+}
+
+/// Apply the persisted geometry for `window`, if any. Call right after the
+/// window is built and before it is shown.
+pub fn restore_window_state(window: &tauri::WebviewWindow) {
+    let Some(state) = load_state(state_key(window.label())) else {
+        return;
+    };
+
+    let monitors: Vec<MonitorArea> = match window.available_monitors() {
+        Ok(monitors) => monitors
+            .iter()
+            .map(|m| {
+                let area = m.work_area();
+                MonitorArea {
+                    name: m.name().cloned(),
+                    work_area: Rect {
+                        x: area.position.x,
+                        y: area.position.y,
+                        width: area.size.width as i32,
+                        height: area.size.height as i32,
+                    },
+                }
+            })
+            .collect(),
+        Err(e) => {
+            log::warn!("Failed to list monitors for window restore: {}", e);
+            return;
+        }
+    };
+
+    let saved = Rect {
+        x: state.x,
+        y: state.y,
+        width: state.width.max(1),
+        height: state.height.max(1),
+    };
+    let Some(monitor) = pick_monitor(&saved, state.monitor_name.as_deref(), &monitors) else {
+        return;
+    };
+    if state.monitor_name.is_some() && monitor.name != state.monitor_name {
+        log::info!(
+            "Saved monitor {:?} for window '{}' is unavailable; restoring onto {:?}",
+            state.monitor_name,
+            window.label(),
+            monitor.name
+        );
+    }
+
+    let rect = if state.maximized {
+        unmaximize_rect(saved, monitor.work_area)
+    } else {
+        fit_to_work_area(saved, monitor.work_area)
+    };
+
+    let _ = window.set_size(PhysicalSize::new(rect.width as u32, rect.height as u32));
+    let _ = window.set_position(PhysicalPosition::new(rect.x, rect.y));
+    if state.maximized {
+        let _ = window.maximize();
+    }
+}
+
+/// Record the window's geometry once it has stopped moving/resizing.
+pub fn schedule_save(window: &tauri::Window) {
+    let key = state_key(window.label()).to_string();
+    let generation = {
+        let mut pending = PENDING_SAVES.lock().unwrap();
+        let generation = pending.entry(key.clone()).or_insert(0);
+        *generation += 1;
+        *generation
+    };
+
+    let window = window.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SAVE_DEBOUNCE).await;
+        if PENDING_SAVES.lock().unwrap().get(&key) != Some(&generation) {
+            return;
+        }
+        if let Err(e) = capture_and_save(&window, &key) {
+            log::debug!("Skipped saving window state for '{}': {}", key, e);
+        }
+    });
+}
+
+fn capture_and_save(window: &tauri::Window, key: &str) -> Result<(), String> {
+    if !window.is_visible().unwrap_or(false) || window.is_minimized().unwrap_or(false) {
+        return Err("window is hidden or minimized".to_string());
+    }
+    let maximized = window.is_maximized().unwrap_or(false);
+    let monitor_name = window
+        .current_monitor()
+        .ok()
+        .flatten()
+        .and_then(|m| m.name().cloned());
+
+    // While maximized, keep the last normal geometry so un-maximizing after a
+    // restore returns to a sensible size.
+    let previous = load_state(key);
+    let (x, y, width, height) = match (&previous, maximized) {
+        (Some(prev), true) => (prev.x, prev.y, prev.width, prev.height),
+        _ => {
+            let position = window.outer_position().map_err(|e| e.to_string())?;
+            let size = window.inner_size().map_err(|e| e.to_string())?;
+            if size.width == 0 || size.height == 0 {
+                return Err("window has no size".to_string());
+            }
+            (
+                position.x,
+                position.y,
+                size.width as i32,
+                size.height as i32,
+            )
+        }
+    };
+
+    save_state(&WindowState {
+        label: key.to_string(),
+        x,
+        y,
+        width,
+        height,
+        maximized,
+        monitor_name,
+        updated_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// Forget the saved geometry for a window (support escape hatch for a window
+/// stuck off-screen). An open window is re-centered on its current monitor.
+#[tauri::command]
+pub fn reset_window_state(app_handle: tauri::AppHandle, label: String) -> Result<(), String> {
+    let key = state_key(&label).to_string();
+    let mut conn = get_config_conn().map_err(|e| e.to_string())?;
+    diesel::delete(ws_dsl::window_state.filter(ws_dsl::label.eq(&key)))
+        .execute(&mut conn)
+        .map_err(|e| format!("Failed to reset window state: {}", e))?;
+    PENDING_SAVES.lock().unwrap().remove(&key);
+
+    if let Some(window) = app_handle.get_webview_window(&label) {
+        let _ = window.unmaximize();
+        window
+            .center()
+            .map_err(|e| format!("Failed to center window: {}", e))?;
+    }
+    log::info!("Reset saved window state for '{}'", key);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: i32, y: i32, width: i32, height: i32) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    fn monitor(name: &str, area: Rect) -> MonitorArea {
+        MonitorArea {
+            name: Some(name.to_string()),
+            work_area: area,
+        }
+    }
+
+    #[test]
+    fn page_viewers_share_one_key() {
+        assert_eq!(state_key("page-viewer-3"), "page-viewer");
+        assert_eq!(state_key("main"), "main");
+        assert_eq!(state_key("launcher-2"), "launcher");
+    }
+
+    #[test]
+    fn keeps_geometry_that_still_fits() {
+        let area = rect(0, 0, 1920, 1040);
+        let saved = rect(100, 80, 1200, 800);
+        assert_eq!(fit_to_work_area(saved, area), saved);
+    }
+
+    #[test]
+    fn pulls_partially_offscreen_windows_back_inside() {
+        let area = rect(0, 0, 1920, 1040);
+        assert_eq!(
+            fit_to_work_area(rect(1500, 900, 800, 600), area),
+            rect(1120, 440, 800, 600)
+        );
+    }
+
+    #[test]
+    fn falls_back_to_nearest_monitor_when_saved_one_is_gone() {
+        let monitors = vec![
+            monitor("primary", rect(0, 0, 1920, 1040)),
+            monitor("right", rect(1920, 0, 2560, 1400)),
+        ];
+        // Saved on a monitor far to the left that is no longer connected.
+        let saved = rect(-2400, 100, 1200, 800);
+        let picked = pick_monitor(&saved, Some("left"), &monitors).unwrap();
+        assert_eq!(picked.name.as_deref(), Some("primary"));
+
+        let fitted = fit_to_work_area(saved, picked.work_area);
+        assert!(fitted.intersects(&picked.work_area));
+        assert!(fitted.x >= 0 && fitted.x + fitted.width <= 1920);
+    }
+
+    #[test]
+    fn prefers_the_named_monitor_when_connected() {
+        let monitors = vec![
+            monitor("primary", rect(0, 0, 1920, 1040)),
+            monitor("right", rect(1920, 0, 2560, 1400)),
+        ];
+        let saved = rect(2000, 100, 1200, 800);
+        let picked = pick_monitor(&saved, Some("right"), &monitors).unwrap();
+        assert_eq!(picked.name.as_deref(), Some("right"));
+    }
+
+    #[test]
+    fn maximized_windows_get_a_smaller_unmaximize_size() {
+        let area = rect(0, 0, 1920, 1040);
+        let restored = unmaximize_rect(rect(0, 0, 1920, 1040), area);
+        assert_eq!(restored.width, 1536);
+        assert_eq!(restored.height, 832);
+        assert_eq!(restored.x, 192);
+        assert_eq!(restored.y, 104);
+
+        let normal = rect(200, 100, 1000, 700);
+        assert_eq!(unmaximize_rect(normal, area), normal);
+    }
+}
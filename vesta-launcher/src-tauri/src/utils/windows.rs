@@ -1,11 +1,17 @@
 use serde_json::Value;
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Mutex;
 use tauri::webview::Color;
 use tauri::{Emitter, Manager};
 
 const IDLE_MINI_WINDOW_CAPACITY: usize = 2;
 
+/// Label prefix for additional launcher windows opened via `--new-window`.
+pub const EXTRA_LAUNCHER_WINDOW_PREFIX: &str = "launcher-";
+
+static NEXT_LAUNCHER_WINDOW_ID: AtomicU64 = AtomicU64::new(1);
+
 #[derive(Default)]
 struct MiniWindowRegistryInner {
     next_window_id: u64,
@@ -125,6 +131,7 @@ fn build_mini_window(
         .title_bar_style(tauri::TitleBarStyle::Overlay);
 
     let window = win_builder.build()?;
+    crate::utils::window_state::restore_window_state(&window);
     crate::commands::app::set_window_effect(window.clone(), "none".to_string()).unwrap_or(());
     Ok(window)
 }
@@ -168,6 +175,23 @@ pub async fn launch_window(
     Ok(label)
 }
 
+/// Open an additional full launcher window in this process. Used when a second
+/// launch passes `--new-window` instead of focusing the existing main window.
+pub fn launch_new_window(app_handle: &tauri::AppHandle) -> Result<String, String> {
+    let label = format!(
+        "{}{}",
+        EXTRA_LAUNCHER_WINDOW_PREFIX,
+        NEXT_LAUNCHER_WINDOW_ID.fetch_add(1, Ordering::Relaxed)
+    );
+    let config = crate::utils::config::get_app_config().unwrap_or_default();
+    let window =
+        crate::startup::shell::build_launcher_window(app_handle, &label, os_name(), &config)
+            .map_err(|error| format!("Failed to create launcher window: {error}"))?;
+    crate::utils::window_state::restore_window_state(&window);
+    crate::commands::app::set_window_effect(window, "none".to_string()).unwrap_or(());
+    Ok(label)
+}
+
 #[tauri::command]
 pub fn preload_mini_window_route(app_handle: tauri::AppHandle, path: String) -> Result<(), String> {
     for label in app_handle
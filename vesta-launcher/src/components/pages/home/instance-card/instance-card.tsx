@@ -348,7 +348,10 @@ export default function InstanceCard(props: InstanceCardProps) {
 
 	const openInstanceFolder = async () => {
 		try {
-			await invoke("open_instance_folder", { instanceIdSlug: instanceSlug() });
+			await invoke("open_instance_folder", {
+				instanceId: props.instance.id,
+				sub: null,
+			});
 		} catch (e) {
 			console.error("Failed to open instance folder:", e);
 			showToast({
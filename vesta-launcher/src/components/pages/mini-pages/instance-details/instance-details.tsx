@@ -69,6 +69,7 @@ import {
 } from "@utils/instance-draft";
 import type { Instance } from "@utils/instances";
 import {
+	changeInstanceVersion,
 	DEFAULT_ICONS,
 	duplicateInstance,
 	getInstance,
@@ -1722,32 +1723,49 @@ export default function InstanceDetails(
 			}
 		}
 
-		if (
-			nextMcVersion !== inst.minecraftVersion &&
-			!(await confirmMinecraftVersionChange({
-				instanceName: inst.name,
-				currentVersion: inst.minecraftVersion,
-				nextVersion: nextMcVersion,
-				context: "manual",
-			}))
-		) {
-			return;
-		}
+		const targetLoaderVersion =
+			nextLoader === "vanilla" ? null : nextLoaderVersion || null;
 
 		setBusy(true);
 		try {
-			// updateInstance expects full Instance object
-			await updateInstance({
-				...inst,
-				minecraftVersion: nextMcVersion,
-				modloader: nextLoader === "vanilla" ? null : nextLoader,
-				modloaderVersion:
-					nextLoader === "vanilla" ? null : nextLoaderVersion || null,
-			});
-			await repairInstance(inst.id);
+			const plan = await changeInstanceVersion(
+				inst.id,
+				nextMcVersion,
+				nextLoader,
+				targetLoaderVersion,
+				true,
+			);
+			if (nextMcVersion !== inst.minecraftVersion) {
+				const confirmed = await confirmMinecraftVersionChange({
+					instanceName: inst.name,
+					currentVersion: inst.minecraftVersion,
+					nextVersion: nextMcVersion,
+					context: "manual",
+					warnings: plan.warnings,
+				});
+				if (!confirmed) return;
+			} else if (plan.warnings.length > 0) {
+				showToast({
+					title: "Compatibility Warning",
+					description: plan.warnings.join("\n"),
+					severity: "warning",
+				});
+			}
+
+			await changeInstanceVersion(
+				inst.id,
+				nextMcVersion,
+				nextLoader,
+				targetLoaderVersion,
+			);
 			await refetch();
 		} catch (e) {
 			console.error("Failed to update instance version:", e);
+			showToast({
+				title: "Version Change Failed",
+				description: String(e),
+				severity: "error",
+			});
 		} finally {
 			setBusy(false);
 		}
@@ -2412,8 +2430,10 @@ export default function InstanceDetails(
 	};
 
 	const openInstanceFolder = async () => {
+		const instanceId = instance()?.id;
+		if (instanceId === undefined) return;
 		try {
-			await invoke("open_instance_folder", { instanceIdSlug: slug() });
+			await invoke("open_instance_folder", { instanceId, sub: null });
 		} catch (e) {
 			console.error("Failed to open instance folder:", e);
 		}
@@ -146,7 +146,7 @@ const ResourceCard: Component<{
 	const bgImage = createMemo(() => {
 		const p = props.project;
 		if (p.featured_gallery) return p.featured_gallery;
-		if (p.gallery.length > 0) return p.gallery[0];
+		if (p.gallery.length > 0) return p.gallery[0].url;
 		return null;
 	});
 
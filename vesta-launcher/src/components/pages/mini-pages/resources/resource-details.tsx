@@ -1285,7 +1285,7 @@ const ResourceDetailsPage: Component<{
 		}
 
 		try {
-			const p = await resources.getProject(platform, id);
+			const p = await resources.getProjectDetails(platform, id);
 			if (p) setProjectCache(platform, p);
 
 			setProject(p);
@@ -2510,9 +2510,12 @@ const ResourceDetailsPage: Component<{
 													{(item) => (
 														<div
 															class={styles["gallery-item"]}
-															onClick={() => setSelectedGalleryItem(item)}
+															onClick={() => setSelectedGalleryItem(item.url)}
 														>
-															<img src={item} alt="Gallery Item" />
+															<img
+																src={item.url}
+																alt={item.title || "Gallery Item"}
+															/>
 														</div>
 													)}
 												</For>
@@ -3052,8 +3055,8 @@ const ResourceDetailsPage: Component<{
 					<ImageViewer
 						src={selectedGalleryItem()}
 						images={project()?.gallery?.map((item) => ({
-							src: item,
-							title: project()?.name || "Resource Gallery",
+							src: item.url,
+							title: item.title || project()?.name || "Resource Gallery",
 						}))}
 						title={project()?.name || "Resource Gallery"}
 						showDelete={false}
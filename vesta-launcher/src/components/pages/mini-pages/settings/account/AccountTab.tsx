@@ -54,6 +54,7 @@ interface Account {
 	skin_url?: string;
 	skin_variant?: string;
 	cape_url?: string;
+	xbox_avatar_url?: string | null;
 	skin_data?: string;
 	cape_data?: string;
 }
@@ -1,6 +1,7 @@
 import { openMiniPage } from "@components/page-viewer/page-viewer";
 import { SettingsField } from "@components/settings";
 import {
+	handleCleanUpArtifacts,
 	handleClearCache,
 	handleOpenAppData,
 	handleOpenLauncherLogs,
@@ -688,6 +689,12 @@ export function StorageUsageViewer() {
 						actionLabel="Open"
 						onAction={handleOpenLauncherLogs}
 					/>
+					<SettingsField
+						label="Clean Up Artifacts"
+						description="Delete cached game files that no installed version uses anymore."
+						actionLabel="Clean Up"
+						onAction={handleCleanUpArtifacts}
+					/>
 					<SettingsField
 						label="Clear Cache"
 						description="Remove cached metadata and temporary files. Installed instances are not affected."
@@ -29,10 +29,50 @@ export type ResourceProject = {
 	categories: string[];
 	web_url: string;
 	external_ids?: Record<string, string>;
-	gallery: string[];
+	gallery: GalleryImage[];
 	featured_gallery?: string | null;
 	published_at: string | null;
 	updated_at: string | null;
+	body?: ResourceBody | null;
+	members?: ResourceMember[];
+	license?: ResourceLicense | null;
+	links?: ResourceLinks | null;
+	client_side?: string | null;
+	server_side?: string | null;
+};
+
+export type GalleryImage = {
+	url: string;
+	title: string | null;
+	description: string | null;
+	featured: boolean;
+};
+
+export type ResourceBody = {
+	format: "markdown" | "html";
+	content: string;
+};
+
+export type ResourceMember = {
+	name: string;
+	role: string | null;
+	avatar_url: string | null;
+	url: string | null;
+};
+
+export type ResourceLicense = {
+	id: string;
+	name: string;
+	url: string | null;
+};
+
+export type ResourceLinks = {
+	website: string | null;
+	source: string | null;
+	issues: string | null;
+	wiki: string | null;
+	discord: string | null;
+	donations: string[];
 };
 
 export type SearchResponse = {
@@ -446,6 +486,13 @@ export const resources = {
 		});
 	},
 
+	getProjectDetails: async (platform: SourcePlatform, id: string) => {
+		return await invoke<ResourceProject>("get_resource_project_details", {
+			platform,
+			id,
+		});
+	},
+
 	getProjects: async (platform: SourcePlatform, ids: string[]) => {
 		if (ids.length === 0) return [];
 		return await invoke<ResourceProject[]>("get_resource_projects", {
@@ -30,6 +30,7 @@ import {
 	setUiChromeModeEnabled,
 	uiChromeModeEnabled,
 } from "@utils/config-sync";
+import { formatBytes } from "@utils/format-bytes";
 import { hasTauriRuntime } from "@utils/tauri-runtime";
 import { getStartupConfig } from "@utils/startup-state";
 import {
@@ -169,7 +170,7 @@ export const [startupCheckUpdates, setStartupCheckUpdates] = createSignal(true);
 export const [useDedicatedGpu, setUseDedicatedGpu] = createSignal(false);
 export const [telemetryEnabled, setTelemetryEnabled] = createSignal(true);
 export const [discordPresenceEnabled, setDiscordPresenceEnabled] =
-	createSignal(true);
+	createSignal(false);
 export const [autoInstallDependencies, setAutoInstallDependencies] =
 	createSignal(true);
 export const [proxyEnabled, setProxyEnabled] = createSignal(false);
@@ -1272,10 +1273,7 @@ export async function handleDiscordToggle(checked: boolean) {
 	setDiscordPresenceEnabled(checked);
 	if (hasTauriRuntime()) {
 		try {
-			await invoke("update_config_field", {
-				field: "discord_presence_enabled",
-				value: checked,
-			});
+			await invoke("set_discord_presence_enabled", { enabled: checked });
 		} catch (e) {
 			console.error("Failed to persist discord_presence_enabled:", e);
 			setDiscordPresenceEnabled(prev);
@@ -1523,6 +1521,35 @@ export async function handleClearCache() {
 	}
 }
 
+export async function handleCleanUpArtifacts() {
+	if (hasTauriRuntime()) {
+		try {
+			const stats = await invoke<{
+				files_deleted: number;
+				bytes_reclaimed: number;
+			}>("run_cache_gc");
+			refetchSize();
+			const snapshot = await fetchStorageSnapshot(true);
+			mutateStorageSnapshot(snapshot);
+			showToast({
+				title: "Artifacts Cleaned Up",
+				description:
+					stats.files_deleted > 0
+						? `Removed ${stats.files_deleted} unused artifact${stats.files_deleted === 1 ? "" : "s"} (${formatBytes(stats.bytes_reclaimed)}).`
+						: "No unused artifacts were found.",
+				severity: "success",
+			});
+		} catch (e) {
+			console.error("Failed to clean up artifacts:", e);
+			showToast({
+				title: "Clean Up Failed",
+				description: String(e),
+				severity: "error",
+			});
+		}
+	}
+}
+
 export async function handleArtifactCacheLimitChange(nextBytes: number) {
 	const previous = artifactCacheLimitBytes();
 	const normalized =
@@ -1595,7 +1622,7 @@ async function initializeSettings() {
 				setStartupCheckUpdates(config.startup_check_updates ?? true);
 				setUseDedicatedGpu(config.use_dedicated_gpu ?? true);
 				setTelemetryEnabled(config.telemetry_enabled ?? true);
-				setDiscordPresenceEnabled(config.discord_presence_enabled ?? true);
+				setDiscordPresenceEnabled(config.discord_presence_enabled ?? false);
 				setAutoInstallDependencies(config.auto_install_dependencies ?? true);
 				setProxyEnabled(config.proxy_enabled ?? false);
 				setProxyUrl(config.proxy_url ?? "");
@@ -1744,7 +1771,7 @@ async function initializeSettings() {
 		if (field === "use_dedicated_gpu") setUseDedicatedGpu(value ?? true);
 		if (field === "telemetry_enabled") setTelemetryEnabled(value ?? true);
 		if (field === "discord_presence_enabled")
-			setDiscordPresenceEnabled(value ?? true);
+			setDiscordPresenceEnabled(value ?? false);
 		if (field === "reduced_motion") setReducedMotion(value ?? false);
 		if (field === "autostart_enabled") setAutostartEnabled(value ?? false);
 		if (field === "show_tray_icon") setShowTrayIcon(value ?? true);
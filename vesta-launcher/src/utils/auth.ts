@@ -41,15 +41,21 @@ export type AuthStage =
 	| { stage: "Start" }
 	| { stage: "AuthCode"; code: string; url: string; expires_in: number }
 	| { stage: "Polling" }
+	| { stage: "BrowserOpened"; url: string }
 	| { stage: "Complete"; user_uuid: string; user_username: string }
 	| { stage: "Cancelled" }
 	| { stage: "Error"; message: string };
 
+export type LoginMethod = "device_code" | "browser";
+
 /**
- * Start Microsoft OAuth device-code login flow
+ * Start Microsoft OAuth login. "browser" is the fallback for networks that
+ * block the device-code endpoint.
  */
-export async function startLogin(): Promise<void> {
-	await invoke("start_login");
+export async function startLogin(
+	loginMethod: LoginMethod = "device_code",
+): Promise<void> {
+	await invoke("start_login", { loginMethod });
 }
 
 /**
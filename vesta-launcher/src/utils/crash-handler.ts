@@ -10,7 +10,11 @@ export interface CrashSuspect {
 	display_name: string;
 	mod_id?: string | null;
 	reason?: string | null;
-	suspect_kind: "affected_mod" | "missing_dependency" | string;
+	suspect_kind:
+		| "affected_mod"
+		| "missing_dependency"
+		| "native_library"
+		| string;
 }
 
 export interface CrashEvent {
@@ -165,6 +165,9 @@ export interface Instance {
 	 * or if the backend does not report an operation for this instance.
 	 */
 	lastOperation?: string | null;
+	notes?: string | null;
+	// Stored instance id; null for instances created before slugs were stored
+	slug?: string | null;
 }
 
 // Simplified version for creating new instances
@@ -183,6 +186,9 @@ export interface CreateInstanceData {
 	modpackPlatform?: string;
 	modpackIconUrl?: string;
 	iconData?: Uint8Array;
+	// Copy options, servers and keybind configs from this instance
+	copySettingsFromInstance?: number;
+	copyResourcePacks?: boolean;
 }
 
 // Metadata types from piston-lib
@@ -260,6 +266,11 @@ export async function listInstances(): Promise<Instance[]> {
 	return await invoke<Instance[]>("list_instances");
 }
 
+// Full-text search over instance names and notes, best match first
+export async function searchInstances(query: string): Promise<Instance[]> {
+	return await invoke<Instance[]>("search_instances", { query });
+}
+
 // Create a new instance (returns the new ID)
 export async function createInstance(
 	data: CreateInstanceData,
@@ -310,6 +321,8 @@ export async function createInstance(
 	try {
 		const result = await invoke<number>("create_instance", {
 			instanceData: instance,
+			copySettingsFromInstance: data.copySettingsFromInstance ?? null,
+			copyResourcePacks: data.copyResourcePacks ?? null,
 		});
 		console.log("[createInstance] DB insert successful, new ID:", result);
 		return result;
@@ -319,9 +332,63 @@ export async function createInstance(
 	}
 }
 
+export type JavaWarning =
+	| { kind: "major_mismatch"; found: number; required: number }
+	| { kind: "bit32" };
+
+/** Problems with a Java override that was saved anyway. */
+export interface JavaPathReport {
+	path: string;
+	error: string | null;
+	warnings: JavaWarning[];
+}
+
 // Update an existing instance
-export async function updateInstance(instance: Instance): Promise<void> {
-	await invoke("update_instance", { instanceData: instance });
+export async function updateInstance(
+	instance: Instance,
+): Promise<JavaPathReport | null> {
+	return await invoke<JavaPathReport | null>("update_instance", {
+		instanceData: instance,
+	});
+}
+
+export interface InstanceRuntime {
+	minecraft_version: string;
+	modloader: string | null;
+	modloader_version: string | null;
+}
+
+export interface VersionChangePlan {
+	instance_id: number;
+	from: InstanceRuntime;
+	to: InstanceRuntime;
+	downgrade: boolean;
+	incompatible_mods: {
+		resource_id: number;
+		display_name: string;
+		current_version: string;
+	}[];
+	unchecked_mods: number;
+	warnings: string[];
+	queued: boolean;
+}
+
+// Change an instance's Minecraft/loader version. The instance only switches
+// once the new version is installed; with dryRun nothing is queued.
+export async function changeInstanceVersion(
+	id: number,
+	minecraftVersion: string,
+	modloader: string | null,
+	modloaderVersion: string | null,
+	dryRun = false,
+): Promise<VersionChangePlan> {
+	return await invoke<VersionChangePlan>("change_instance_version", {
+		instanceId: id,
+		newMcVersion: minecraftVersion,
+		newLoader: modloader,
+		newLoaderVersion: modloaderVersion,
+		dryRun,
+	});
 }
 
 // Unlink instance from modpack
@@ -406,6 +473,19 @@ export async function repairInstance(
 	});
 }
 
+export interface ModpackRepairReport {
+	checked: number;
+	repaired: number;
+	irreparable: string[];
+}
+
+// Re-download missing or corrupt mods of a modpack instance
+export async function repairModpack(id: number): Promise<ModpackRepairReport> {
+	return await invoke<ModpackRepairReport>("repair_modpack", {
+		instanceId: id,
+	});
+}
+
 // Reset an instance (Hard Reset)
 export async function resetInstance(id: number): Promise<void> {
 	await invoke("reset_instance", { instanceId: id });
@@ -505,7 +585,10 @@ export async function installInstance(instance: Instance): Promise<void> {
 }
 
 // Launch an instance (placeholder implementation - backend may actually run the game)
-export async function launchInstance(instance: Instance): Promise<void> {
+export async function launchInstance(
+	instance: Instance,
+	server?: { host: string; port: number },
+): Promise<void> {
 	const slug = getInstanceSlug(instance);
 
 	if (instancesState.runningIds[slug]) {
@@ -530,7 +613,15 @@ export async function launchInstance(instance: Instance): Promise<void> {
 		instance,
 	);
 	try {
-		await invoke("launch_instance", { instanceData: instance });
+		if (server) {
+			await invoke("launch_instance_and_connect", {
+				instanceData: instance,
+				host: server.host,
+				port: server.port,
+			});
+		} else {
+			await invoke("launch_instance", { instanceData: instance });
+		}
 		console.log("[launchInstance] Launch command completed");
 		// Belt-and-suspenders: backend emits core://instance-launched, but if that
 		// event was missed we must not leave the UI stuck in "warming".
@@ -588,6 +679,29 @@ export async function isInstanceRunning(instance: Instance): Promise<boolean> {
 	}
 }
 
+// Memory and CPU usage of a running game, sampled every 10 seconds
+export interface ProcessMetrics {
+	pid: number;
+	rss_mb: number;
+	virt_mb: number;
+	cpu_pct: number;
+	priority?:
+		| "idle"
+		| "below_normal"
+		| "normal"
+		| "above_normal"
+		| "high"
+		| null;
+	sampled_at: string;
+}
+
+// Recent usage samples of a running instance, oldest first
+export async function getProcessMetrics(
+	instanceId: string,
+): Promise<ProcessMetrics[]> {
+	return await invoke<ProcessMetrics[]>("get_process_metrics", { instanceId });
+}
+
 // Get Minecraft versions metadata
 export async function getMinecraftVersions(): Promise<PistonMetadata> {
 	return await invoke<PistonMetadata>("get_minecraft_versions");
@@ -650,7 +764,8 @@ export function getInstanceId(instance: Instance): number | null {
 	return instance.id;
 }
 
-// Create a filesystem-safe slug from an instance name — mirrors the backend sanitizer
+// Create a filesystem-safe slug from an instance name — mirrors the backend's legacy slug
+// mapping, which still applies to instances without a stored slug
 export function sanitizeInstanceName(name: string): string {
 	const n = (name || "").trim().toLowerCase();
 	let out = "";
@@ -686,5 +801,5 @@ export function sanitizeInstanceName(name: string): string {
 }
 
 export function getInstanceSlug(instance: Instance): string {
-	return sanitizeInstanceName(instance.name);
+	return instance.slug || sanitizeInstanceName(instance.name);
 }
@@ -7,6 +7,8 @@ export interface MinecraftVersionChangeParams {
 	currentVersion: string;
 	nextVersion: string;
 	context: MinecraftVersionChangeContext;
+	/** Extra warnings from a dry run, e.g. mods without a matching version. */
+	warnings?: string[];
 }
 
 function buildDescription(params: MinecraftVersionChangeParams): string {
@@ -20,6 +22,7 @@ function buildDescription(params: MinecraftVersionChangeParams): string {
 		"",
 		"Existing worlds may become incompatible or unusable after this change.",
 		"",
+		...(params.warnings ?? []).flatMap((warning) => [warning, ""]),
 		"Are you sure you want to continue?",
 	].join("\n");
 }
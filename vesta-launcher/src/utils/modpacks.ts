@@ -87,6 +87,30 @@ export interface ExportCandidate {
 	versionId?: string;
 	hash?: string;
 	downloadUrl?: string;
+	suggested?: boolean;
+	sizeWarning?: boolean;
+	/** Per-file override of the project's client/server support. */
+	env?: ExportEnv | null;
+	optional?: boolean;
+}
+
+export type ExportSide = "required" | "optional" | "unsupported";
+
+export interface ExportEnv {
+	client: ExportSide;
+	server: ExportSide;
+}
+
+export interface ExportDirectorySize {
+	path: string;
+	size: number;
+	fileCount: number;
+	sizeWarning: boolean;
+}
+
+export interface ExportCandidateList {
+	candidates: ExportCandidate[];
+	directories: ExportDirectorySize[];
 }
 
 export async function getModpackInfo(
@@ -253,8 +277,12 @@ export async function installModpackFromUrl(
 
 export async function listExportCandidates(
 	instanceId: number,
-): Promise<ExportCandidate[]> {
-	return await invoke("list_export_candidates", { instanceId });
+	sizeWarningBytes?: number,
+): Promise<ExportCandidateList> {
+	return await invoke("list_export_candidates", {
+		instanceId,
+		sizeWarningBytes,
+	});
 }
 
 export async function exportInstanceToModpack(
@@ -333,16 +333,32 @@ async function updateNotificationProgress(params: {
 	await invoke("update_notification_progress", { payload: params });
 }
 
-async function listNotifications(filters?: {
+type NotificationFilters = {
 	severity?: NotificationSeverity;
 	read?: boolean;
 	notification_type?: NotificationType;
-}): Promise<BackendNotification[]> {
+	search?: string;
+	oldest_first?: boolean;
+	limit?: number;
+	offset?: number;
+};
+
+async function listNotifications(
+	filters?: NotificationFilters,
+): Promise<BackendNotification[]> {
 	return await invoke<BackendNotification[]>("list_notifications", {
 		filters: filters || null,
 	});
 }
 
+async function countNotifications(
+	filters?: NotificationFilters,
+): Promise<number> {
+	return await invoke<number>("count_notifications", {
+		filters: filters || null,
+	});
+}
+
 async function markNotificationRead(id: number): Promise<void> {
 	await invoke("mark_notification_read", { id });
 }
@@ -614,6 +630,7 @@ export {
 	handleProgressUpdate,
 	invokeNotificationAction,
 	listNotifications,
+	countNotifications,
 	markNotificationRead,
 	type Notification,
 	type NotificationAction,
@@ -1,6 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { check, type Update } from "@tauri-apps/plugin-updater";
 import {
 	createNotification,
 	type NotificationAction,
@@ -9,102 +8,98 @@ import {
 	updateNotificationProgress,
 } from "./notifications";
 
-let pendingUpdate: Update | null = null;
+// Mirrors the backend UpdateStatus enum (startup/self_update.rs)
+type LauncherUpdateStatus =
+	| { state: "idle" }
+	| { state: "checking" }
+	| { state: "up_to_date"; checked_at: string }
+	| { state: "available"; version: string; notes: string | null }
+	| {
+			state: "downloading";
+			version: string;
+			downloaded: number;
+			total: number | null;
+	  }
+	| { state: "ready"; version: string; notes: string | null }
+	| { state: "failed"; error: string };
+
 let isListenerInitialized = false;
-let isDownloading = false;
 let isChecking = false;
-let isDownloaded = false;
-
-async function getUpdaterCheckOptions() {
-	const config = await invoke<any>("get_config");
-	const proxyUrl =
-		config.proxy_enabled &&
-		typeof config.proxy_url === "string" &&
-		config.proxy_url.trim()
-			? config.proxy_url.trim()
-			: undefined;
-
-	return {
-		config,
-		options: proxyUrl ? { proxy: proxyUrl } : undefined,
-	};
-}
+let downloadingVersion: string | null = null;
 
 export function initUpdateListener() {
 	if (isListenerInitialized) return;
 
+	// The backend downloads and verifies the update, then installs it on exit.
 	listen("core://install-app-update", async () => {
-		console.log("[Updater] Received core://install-app-update event");
-		if (pendingUpdate) {
-			console.log(
-				`[Updater] Found pending update: ${pendingUpdate.version} (isDownloaded: ${isDownloaded}, isDownloading: ${isDownloading})`,
+		const status = await invoke<LauncherUpdateStatus>("get_update_status");
+		if (status.state !== "ready") {
+			console.warn(
+				`[Updater] Install requested while update is ${status.state}; checking again`,
 			);
+			checkForAppUpdates(false);
+			return;
+		}
+		try {
+			await invoke("invoke_notification_action", {
+				actionId: "apply_launcher_update",
+			});
+		} catch (error) {
+			console.error("[Updater] Failed to apply update:", error);
+			await showAlert(
+				"error",
+				"Update Error",
+				`Failed to install the update: ${error}. Please try again manually.`,
+				null,
+				null,
+				null,
+				null,
+				"immediate",
+			);
+		}
+	});
 
-			// Check if already downloaded - Tauri plugin might lose state on hot-reload,
-			// but if the UI is showing the "Install" button, we likely have the 'Finished' event cached.
-			if (!isDownloaded) {
-				console.warn(
-					"[Updater] Update.install called but isDownloaded is false. Starting download...",
-				);
-				if (!isDownloading) {
-					await downloadUpdate();
-				}
-				return;
-			}
-
-			try {
-				console.log("[Updater] Calling pendingUpdate.install()...");
+	listen("core://download-app-update", async () => {
+		await downloadUpdate();
+	});
 
-				// Re-use the existing notification to show installation progress
+	listen<LauncherUpdateStatus>("core://update-status", async (event) => {
+		const status = event.payload;
+		if (status.state === "downloading") {
+			const progress =
+				status.total && status.total > 0
+					? Math.round((status.downloaded / status.total) * 100)
+					: PROGRESS_INDETERMINATE;
+			if (downloadingVersion !== status.version) {
+				downloadingVersion = status.version;
 				await createNotification({
-					title: "Installing Update",
-					description: "Applying changes and restarting...",
+					title: "Updating Vesta",
+					description: `Downloading version ${status.version}...`,
 					notification_type: "progress",
 					severity: "info",
-					progress: PROGRESS_INDETERMINATE,
+					progress,
 					client_key: "app_update",
 					dismissible: false,
 				});
-
-				// We call it without parameters as the artifacts are already downloaded.
-				await pendingUpdate.install();
-				console.log(
-					"[Updater] pendingUpdate.install() call returned. Triggering backend restart...",
-				);
-
-				// TRIGGER BACKEND RESTART explicitly as fallback for some platforms/dev modes
-				await invoke("invoke_notification_action", {
-					actionId: "restart_app",
+			} else {
+				await updateNotificationProgress({
+					client_key: "app_update",
+					progress,
 				});
-
-				console.log("[Updater] Restart command invoked.");
-			} catch (error) {
-				console.error("[Updater] Failed to install update:", error);
-				await showAlert(
-					"error",
-					"Update Error",
-					`Failed to install the update: ${error}. Please try again manually.`,
-					null,
-					null,
-					null,
-					null,
-					"immediate",
-				);
 			}
-		} else {
-			console.error(
-				"[Updater] Received install event but pendingUpdate is null",
-			);
-			// If we don't have a pending update, check again
-			checkForAppUpdates(false);
-		}
-	});
-
-	listen("core://download-app-update", async () => {
-		if (pendingUpdate) {
-			await downloadUpdate();
-		} else {
-			checkForAppUpdates(false);
+		} else if (status.state === "failed" && downloadingVersion) {
+			// The "Update ready" notification for success comes from the backend.
+			downloadingVersion = null;
+			await createNotification({
+				title: "Download Error",
+				description: status.error,
+				notification_type: "patient",
+				severity: "error",
+				dismissible: true,
+				client_key: "app_update",
+			});
+		} else if (status.state === "ready") {
+			downloadingVersion = null;
 		}
 	});
 
@@ -112,120 +107,12 @@ export function initUpdateListener() {
 }
 
 export async function downloadUpdate() {
-	if (isDownloaded || isDownloading || !pendingUpdate) return;
-	isDownloading = true;
-
-	const update = pendingUpdate;
-
 	try {
-		// Create a progress notification
-		await createNotification({
-			title: "Updating Vesta",
-			description: `Downloading version ${update.version}...`,
-			notification_type: "progress",
-			severity: "info",
-			progress: 0,
-			client_key: "app_update",
-			dismissible: false,
-		});
-
-		let downloaded = 0;
-		let contentLength = 0;
-		let lastReportedProgress = -5; // Start low to ensure first update
-
-		await update.download((event) => {
-			switch (event.event) {
-				case "Started": {
-					contentLength = event.data.contentLength || 0;
-					break;
-				}
-				case "Progress": {
-					downloaded += event.data.chunkLength;
-					const progress =
-						contentLength > 0
-							? Math.round((downloaded / contentLength) * 100)
-							: PROGRESS_INDETERMINATE;
-
-					// Only update if progress has changed significantly to avoid IPC spam
-					if (
-						progress === PROGRESS_INDETERMINATE ||
-						progress >= lastReportedProgress + 1 ||
-						progress === 100
-					) {
-						lastReportedProgress = progress;
-						updateNotificationProgress({
-							client_key: "app_update",
-							progress: progress,
-						});
-					}
-					break;
-				}
-				case "Finished": {
-					// The download is complete, but we wait for the outer promise to resolve
-					// to ensure that post-download checks (e.g. signature verification)
-					// have also completed successfully.
-					console.log("[Updater] Download callback: Finished event received.");
-					break;
-				}
-			}
-		});
-
-		isDownloading = false;
-		isDownloaded = true;
-
-		// Convert to Patient with Install action
-		const actions: NotificationAction[] = [
-			{
-				id: "install_app_update",
-				label: "Install & Restart",
-				type: "primary",
-			},
-		];
-
-		await createNotification({
-			title: "Update Downloaded",
-			description: `Vesta v${update.version} is ready to install. Restart to apply changes.`,
-			notification_type: "patient",
-			severity: "success",
-			dismissible: false,
-			actions: actions,
-			client_key: "app_update",
-		});
+		await invoke<LauncherUpdateStatus>("download_launcher_update");
 	} catch (error) {
-		isDownloading = false;
-		isDownloaded = false;
 		console.error("Failed to download update:", error);
-
-		let errorMessage = "Failed to download the update. Please try again.";
-		let errorTitle = "Download Error";
-
-		if (
-			error &&
-			typeof error === "string" &&
-			(error.includes("Invalid encoding in minisign data") ||
-				error.includes("signature"))
-		) {
-			errorTitle = "Update Verification Failed";
-			errorMessage =
-				"The update signature is missing or malformed for this platform. This is likely an issue with the release build.";
-		} else if (
-			error instanceof Error &&
-			(error.message.includes("Invalid encoding in minisign data") ||
-				error.message.includes("signature"))
-		) {
-			errorTitle = "Update Verification Failed";
-			errorMessage =
-				"The update signature is missing or malformed for this platform. This is likely an issue with the release build.";
-		}
-
-		await createNotification({
-			title: errorTitle,
-			description: errorMessage,
-			notification_type: "patient",
-			severity: "error",
-			dismissible: true,
-			client_key: "app_update",
-		});
+		// The previous check is gone (e.g. after a restart), so look again.
+		checkForAppUpdates(false);
 	}
 }
 
@@ -234,62 +121,64 @@ export async function checkForAppUpdates(silent = false) {
 	isChecking = true;
 	initUpdateListener();
 	try {
-		const { config, options } = await getUpdaterCheckOptions();
-		const update = await check(options);
-
-		if (update) {
-			isDownloaded = false;
-			pendingUpdate = update;
-
-			const autoUpdate = config.auto_update_enabled ?? true;
-
-			if (autoUpdate) {
-				if (!silent) {
-					await showAlert(
-						"info",
-						"Update Available",
-						`Version ${update.version} is available. Downloading now...`,
-						null,
-						null,
-						null,
-						null,
-						"immediate",
-					);
-				}
-				downloadUpdate();
-			} else {
-				const actions: NotificationAction[] = [
-					{
-						id: "download_app_update",
-						label: "Download",
-						type: "primary",
-					},
-				];
+		// The backend picks the endpoint for the configured update channel and
+		// starts the download itself when auto-update is on.
+		const status = await invoke<LauncherUpdateStatus>("check_for_updates");
+
+		if (status.state === "available") {
+			const actions: NotificationAction[] = [
+				{
+					id: "download_launcher_update",
+					label: "Download",
+					type: "primary",
+				},
+			];
 
-				await createNotification({
-					title: "Update Available",
-					description: `Vesta Launcher v${update.version} is now available!`,
-					notification_type: "patient",
-					severity: "info",
-					dismissible: false,
-					actions: actions,
-					client_key: "app_update",
-				});
+			await createNotification({
+				title: "Update Available",
+				description: `Vesta Launcher v${status.version} is now available!`,
+				notification_type: "patient",
+				severity: "info",
+				dismissible: false,
+				actions: actions,
+				client_key: "app_update",
+			});
 
-				if (!silent) {
-					await showAlert(
-						"info",
-						"Update Available",
-						`Version ${update.version} is available.`,
-						null,
-						null,
-						null,
-						null,
-						"immediate",
-					);
-				}
+			if (!silent) {
+				await showAlert(
+					"info",
+					"Update Available",
+					`Version ${status.version} is available.`,
+					null,
+					null,
+					null,
+					null,
+					"immediate",
+				);
 			}
-		} else if (!silent) {
+		} else if (status.state === "downloading" && !silent) {
+			await showAlert(
+				"info",
+				"Update Available",
+				`Version ${status.version} is available. Downloading now...`,
+				null,
+				null,
+				null,
+				null,
+				"immediate",
+			);
+		} else if (status.state === "ready" && !silent) {
+			await showAlert(
+				"info",
+				"Update Ready",
+				`Version ${status.version} will be installed when you restart Vesta.`,
+				null,
+				null,
+				null,
+				null,
+				"immediate",
+			);
+		} else if (status.state === "up_to_date" && !silent) {
 			await showAlert(
 				"success",
 				"No Updates",